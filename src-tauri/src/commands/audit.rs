@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditQuery};
+use crate::utils::errors::AppError;
+
+/// Query a connection's audit log, newest first.
+///
+/// Covers editor writes, key deletes and renames, mutating CLI commands,
+/// publishes and client kills made through the app.
+#[tauri::command]
+pub async fn audit_query(
+    connection_id: String,
+    query: Option<AuditQuery>,
+    audit: State<'_, AuditLog>,
+) -> Result<Vec<AuditEntry>, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    audit
+        .query(&connection_id, &query.unwrap_or_default())
+        .await
+}
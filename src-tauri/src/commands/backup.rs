@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::config::{backup_store, profile_store};
+use crate::redis::backup::model::{BackupRecord, BackupSchedule, BackupVerifyReport};
+use crate::redis::backup::{scheduler, verify};
+use crate::redis::connection::manager::ConnectionManager;
+use crate::utils::errors::AppError;
+use crate::utils::tasks::TaskRegistry;
+
+/// Take a backup of a connected server now.
+///
+/// Uses the profile's backup pattern and retention if configured; otherwise
+/// backs up every key and keeps all archives.
+///
+/// With a `task_id`, the backup runs as a task that `task_cancel` can abort.
+#[tauri::command]
+pub async fn backup_run_now(
+    connection_id: String,
+    task_id: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+    app_handle: tauri::AppHandle,
+) -> Result<BackupRecord, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let redis_version = manager.get_capabilities(&uuid).await?.redis_version;
+
+    let schedule = profile_store::load_profile(&app_handle, &uuid)
+        .await?
+        .and_then(|p| p.backup)
+        .unwrap_or(BackupSchedule {
+            enabled: false,
+            interval_minutes: 0,
+            keep_last: u32::MAX,
+            pattern: "*".into(),
+        });
+
+    let app = app_handle.clone();
+    tasks
+        .run(
+            &app_handle,
+            task_id,
+            "backup",
+            Some(&connection_id),
+            |_| async move {
+                scheduler::run_backup(&app, &pool, &uuid.to_string(), &redis_version, &schedule)
+                    .await
+            },
+        )
+        .await
+}
+
+/// List backup archives for a connection profile, newest first.
+#[tauri::command]
+pub async fn backup_list(
+    connection_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<BackupRecord>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    backup_store::list_archives(&app_handle, &uuid.to_string()).await
+}
+
+/// Verify a backup archive against a target server before restoring it.
+///
+/// Checks per-key checksums and dry-runs RESTORE of every payload into a
+/// scratch key on the target.
+#[tauri::command]
+pub async fn backup_verify(
+    connection_id: String,
+    backup_id: String,
+    target_connection_id: String,
+    manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<BackupVerifyReport, AppError> {
+    let source = Uuid::parse_str(&connection_id)?;
+    let target = Uuid::parse_str(&target_connection_id)?;
+
+    let archive = backup_store::read_archive(&app_handle, &source.to_string(), &backup_id).await?;
+    // RESTORE into a scratch key is still a write on the target
+    let pool = manager.get_write_pool(&target).await?;
+    let target_version = manager.get_capabilities(&target).await?.redis_version;
+
+    let report = verify::verify_archive(&pool, &backup_id, &archive, &target_version).await?;
+
+    tracing::info!(
+        connection_id = %connection_id,
+        backup = %backup_id,
+        target = %target_connection_id,
+        verified = report.verified_keys,
+        failed = report.checksum_failures.len() + report.restore_failures.len(),
+        "Backup verified"
+    );
+
+    Ok(report)
+}
@@ -1,33 +1,60 @@
 // SPDX-License-Identifier: MIT
 
-use tauri::State;
+use tauri::ipc::Channel;
+use tauri::{Emitter, State};
 use uuid::Uuid;
 
-use crate::redis::browser::model::{KeyInfo, KeyNode, ScanResult};
-use crate::redis::browser::{scanner, tree};
+use crate::config::{keyspace_store, settings_store};
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::browser::filter::{KeyFilter, KeyFilterResult, KeyMatcher};
+use crate::redis::browser::introspect;
+use crate::redis::browser::invalidation::{self, KeyspaceWatcher, WatchOptions};
+use crate::redis::browser::key_index::KeyIndex;
+use crate::redis::browser::keyspace::{self, KeyspaceSnapshotter};
+use crate::redis::browser::lint;
+use crate::redis::browser::model::{
+    KeyInfo, KeyIntrospection, KeyListPage, KeyListQuery, KeyNode, KeyspaceComparison,
+    KeyspaceSample, KeyspaceSnapshot, LintReport, NamespaceStats, NamingRules, ScanResult,
+    SchemaReport, TenantReport, Ttl, ValueSearchHits, ValueSearchSpec,
+};
+use crate::redis::browser::rename::{self, KeyRewrite, RenameReport};
+use crate::redis::browser::tenants::{self, TenantTemplate};
+use crate::redis::browser::value_search::{self, ValueSearch};
+use crate::redis::browser::{sampling, scanner, schema, stats, tree};
+use crate::redis::confirm::model::{Confirmable, DestructiveOp};
+use crate::redis::confirm::registry::ConfirmationRegistry;
 use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::{command_policy, durability};
+use crate::utils::chunked::{self, ChunkSender, StreamChunk};
 use crate::utils::errors::AppError;
+use crate::utils::progress::{ProgressEvent, ProgressPhase};
+use crate::utils::tasks::TaskRegistry;
 
 /// Scan keys matching a pattern on the connected Redis server.
 ///
 /// Uses the cursor-based SCAN command. Call repeatedly with the returned
-/// cursor until `finished` is true.
+/// cursor until `finished` is true. `db` scans another database than the
+/// profile's without reconnecting.
 #[tauri::command]
 pub async fn browser_scan_keys(
     connection_id: String,
     cursor: u64,
     pattern: String,
     count: u32,
+    db: Option<u8>,
     manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
 ) -> Result<ScanResult, AppError> {
     if pattern.is_empty() {
         return Err(AppError::InvalidInput("Pattern must not be empty".into()));
     }
 
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
 
     let result = scanner::scan_keys(&pool, cursor, &pattern, count).await?;
+    key_index.record(uuid, db, &result.keys).await;
 
     tracing::debug!(
         connection_id = %connection_id,
@@ -40,6 +67,33 @@ pub async fn browser_scan_keys(
     Ok(result)
 }
 
+/// Scan every key matching a pattern, streaming them through `on_chunk`.
+///
+/// The channel receives `data` chunks of up to `chunk_size` keys, then a
+/// `done` marker with the total (or `failed`). Returns the total as well.
+#[tauri::command]
+pub async fn browser_scan_stream(
+    connection_id: String,
+    pattern: String,
+    chunk_size: Option<u32>,
+    db: Option<u8>,
+    on_chunk: Channel<StreamChunk<String>>,
+    manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+) -> Result<u64, AppError> {
+    if pattern.is_empty() {
+        return Err(AppError::InvalidInput("Pattern must not be empty".into()));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+
+    let mut sender = ChunkSender::new(on_chunk, chunked::chunk_size(chunk_size)?);
+    let recorder = key_index.recorder(uuid, db);
+    let outcome = scanner::stream_keys(&pool, &pattern, &mut sender, &recorder).await;
+    sender.finish(outcome)
+}
+
 /// Build a key tree from a flat list of keys.
 ///
 /// Splits keys by the delimiter and returns root-level `KeyNode` items.
@@ -77,19 +131,236 @@ pub async fn browser_get_children(
     Ok(children)
 }
 
+/// MEMORY USAGE SAMPLES used for keys info when the caller doesn't choose.
+const DEFAULT_MEMORY_SAMPLES: u32 = 5;
+/// Sub-pipelines run at once for keys info when the caller doesn't choose.
+const DEFAULT_KEYS_INFO_CONCURRENCY: u32 = 4;
+/// Largest page `browser_list_keys` returns.
+const MAX_LIST_PAGE: u32 = 1000;
+/// Keys `browser_filter_keys` returns when the caller doesn't choose.
+const DEFAULT_FILTER_LIMIT: u32 = 1000;
+/// Most keys `browser_filter_keys` returns.
+const MAX_FILTER_LIMIT: u32 = 100_000;
+/// Keys sampled by `browser_namespace_stats` when the caller doesn't choose.
+const DEFAULT_STATS_SAMPLE: u32 = 500;
+/// Most keys `browser_namespace_stats` samples.
+const MAX_STATS_SAMPLE: u32 = 10_000;
+
 /// Get metadata (type + TTL) for a batch of keys using pipeline.
 ///
 /// Called by the frontend to load metadata for keys visible in the viewport.
+/// With `with_memory`, each key's MEMORY USAGE is included as `sizeBytes`,
+/// estimated from `memory_samples` elements of aggregate types (default 5,
+/// 0 for an exact count).
+///
+/// Large batches run as several smaller pipelines, `concurrency` at a time
+/// (default 4). Keys whose pipeline failed are left out of the result.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn browser_get_keys_info(
     connection_id: String,
     keys: Vec<String>,
+    db: Option<u8>,
+    with_memory: Option<bool>,
+    memory_samples: Option<u32>,
+    concurrency: Option<u32>,
     manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
 ) -> Result<Vec<KeyInfo>, AppError> {
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+
+    let samples = with_memory
+        .unwrap_or(false)
+        .then(|| memory_samples.unwrap_or(DEFAULT_MEMORY_SAMPLES));
+    let concurrency = concurrency.unwrap_or(DEFAULT_KEYS_INFO_CONCURRENCY) as usize;
+    let infos = scanner::get_keys_info(&pool, &keys, samples, concurrency).await?;
+    key_index.record_info(uuid, db, &infos).await;
+    Ok(infos)
+}
+
+/// List known keys as a flat, paginated table.
+///
+/// Works on the key index filled by earlier scans rather than the server, so
+/// filtering and sorting millions of keys costs no round trips and only one
+/// page crosses IPC. Metadata for the page is loaded fresh (and cached, so
+/// type/TTL/size sorting improves as the user pages); keys found to be gone
+/// are dropped from the page and the index.
+#[tauri::command]
+pub async fn browser_list_keys(
+    connection_id: String,
+    db: Option<u8>,
+    query: KeyListQuery,
+    manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+) -> Result<KeyListPage, AppError> {
+    if !(1..=MAX_LIST_PAGE).contains(&query.limit) {
+        return Err(AppError::InvalidInput(format!(
+            "Page size must be between 1 and {MAX_LIST_PAGE}"
+        )));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+
+    let listing = key_index.list(uuid, db, &query).await;
+    let samples = query.with_memory.then_some(DEFAULT_MEMORY_SAMPLES);
+    let infos = scanner::get_keys_info(
+        &pool,
+        &listing.keys,
+        samples,
+        DEFAULT_KEYS_INFO_CONCURRENCY as usize,
+    )
+    .await?;
+    key_index.record_info(uuid, db, &infos).await;
+
+    Ok(KeyListPage {
+        items: infos
+            .into_iter()
+            .filter(|info| info.ttl != Ttl::Missing)
+            .collect(),
+        total_keys: listing.total_keys,
+        matched: listing.matched,
+        offset: query.offset,
+        unsorted: listing.unsorted,
+    })
+}
+
+/// Filter known keys by glob, regex, exclusion globs and type.
+///
+/// Runs over the key index filled by earlier scans, for audits that SCAN
+/// MATCH can't express (e.g. everything except `cache:*`). With a type
+/// filter, types not cached yet are loaded from the server in pipelines and
+/// cached. Returns at most `limit` keys (default 1000) plus the total count.
+#[tauri::command]
+pub async fn browser_filter_keys(
+    connection_id: String,
+    db: Option<u8>,
+    filter: KeyFilter,
+    limit: Option<u32>,
+    manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+) -> Result<KeyFilterResult, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_FILTER_LIMIT);
+    if !(1..=MAX_FILTER_LIMIT).contains(&limit) {
+        return Err(AppError::InvalidInput(format!(
+            "Limit must be between 1 and {MAX_FILTER_LIMIT}"
+        )));
+    }
 
-    scanner::get_keys_info(&pool, &keys).await
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let matcher = KeyMatcher::compile(filter)?;
+    let (candidates, scanned) = key_index.filter(uuid, db, &matcher).await;
+
+    let mut types_loaded = 0;
+    let keys: Vec<String> = if matcher.filters_types() {
+        let unknown: Vec<String> = candidates
+            .iter()
+            .filter(|(_, key_type)| key_type.is_none())
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut loaded = std::collections::HashMap::new();
+        if !unknown.is_empty() {
+            let pool = manager.get_pool_for_db(&uuid, db).await?;
+            let infos = scanner::get_keys_info(
+                &pool,
+                &unknown,
+                None,
+                DEFAULT_KEYS_INFO_CONCURRENCY as usize,
+            )
+            .await?;
+            key_index.record_info(uuid, db, &infos).await;
+            types_loaded = infos.len() as u64;
+            loaded.extend(
+                infos
+                    .into_iter()
+                    .filter(|info| info.ttl != Ttl::Missing)
+                    .map(|info| (info.key, info.key_type)),
+            );
+        }
+        candidates
+            .into_iter()
+            .filter_map(|(key, key_type)| {
+                let key_type = key_type.or_else(|| loaded.remove(&key))?;
+                matcher.matches_type(&key_type).then_some(key)
+            })
+            .collect()
+    } else {
+        candidates.into_iter().map(|(key, _)| key).collect()
+    };
+
+    Ok(KeyFilterResult {
+        matched: keys.len() as u64,
+        keys: keys.into_iter().take(limit as usize).collect(),
+        scanned,
+        types_loaded,
+    })
+}
+
+/// Summarize the keys under a tree folder: count, types, TTLs and memory.
+///
+/// The count comes from the key index; types, TTLs and MEMORY USAGE are
+/// fetched for up to `sample_size` keys (default 500) spread across the
+/// folder, and memory is extrapolated from them. An empty `prefix` covers
+/// every known key.
+///
+/// With a `task_id`, the sampling runs as a task that `task_cancel` can abort.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_namespace_stats(
+    connection_id: String,
+    prefix: String,
+    delimiter: String,
+    db: Option<u8>,
+    sample_size: Option<u32>,
+    task_id: Option<String>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<NamespaceStats, AppError> {
+    let sample_size = sample_size.unwrap_or(DEFAULT_STATS_SAMPLE);
+    if !(1..=MAX_STATS_SAMPLE).contains(&sample_size) {
+        return Err(AppError::InvalidInput(format!(
+            "Sample size must be between 1 and {MAX_STATS_SAMPLE}"
+        )));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+
+    let key_prefix = if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{prefix}{delimiter}")
+    };
+    let (key_count, sample) = key_index
+        .sample_prefix(uuid, db, &key_prefix, sample_size as usize)
+        .await;
+    let infos = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "namespaceStats",
+            Some(&connection_id),
+            |reporter| async move {
+                let total = sample.len() as u64;
+                reporter.progress(ProgressPhase::Processing, 0, Some(total), None);
+                let infos = scanner::get_keys_info(
+                    &pool,
+                    &sample,
+                    Some(DEFAULT_MEMORY_SAMPLES),
+                    DEFAULT_KEYS_INFO_CONCURRENCY as usize,
+                )
+                .await?;
+                reporter.progress(ProgressPhase::Done, total, Some(total), None);
+                Ok(infos)
+            },
+        )
+        .await?;
+    key_index.record_info(uuid, db, &infos).await;
+
+    Ok(stats::summarize(&prefix, key_count, &infos))
 }
 
 /// Get detailed info for a single key (type, TTL, encoding, element count).
@@ -97,6 +368,7 @@ pub async fn browser_get_keys_info(
 pub async fn browser_get_key_info(
     connection_id: String,
     key: String,
+    db: Option<u8>,
     manager: State<'_, ConnectionManager>,
 ) -> Result<KeyInfo, AppError> {
     if key.is_empty() {
@@ -104,17 +376,45 @@ pub async fn browser_get_key_info(
     }
 
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
 
     scanner::get_key_detail(&pool, &key).await
 }
 
+/// Low-level storage details of a key: OBJECT ENCODING, REFCOUNT,
+/// IDLETIME and FREQ, and DEBUG OBJECT's serialized length where the server
+/// allows it. Figures the server refuses are left empty with the reason.
+#[tauri::command]
+pub async fn browser_introspect_key(
+    connection_id: String,
+    key: String,
+    db: Option<u8>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<KeyIntrospection, AppError> {
+    if key.is_empty() {
+        return Err(AppError::InvalidInput("Key must not be empty".into()));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let debug_args = ["DEBUG".to_string(), "OBJECT".to_string()];
+    let debug =
+        command_policy::check_blocked(&manager.get_command_policy(&uuid).await?, &debug_args)
+            .is_ok();
+
+    introspect::introspect_key(&pool, &key, debug).await
+}
+
 /// Delete one or more keys using UNLINK (non-blocking).
 #[tauri::command]
 pub async fn browser_delete_keys(
     connection_id: String,
     keys: Vec<String>,
+    db: Option<u8>,
     manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
 ) -> Result<u64, AppError> {
     if keys.is_empty() {
         return Err(AppError::InvalidInput(
@@ -123,9 +423,30 @@ pub async fn browser_delete_keys(
     }
 
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_write_pool_for_db(&uuid, db).await?;
 
     let count = scanner::delete_keys(&pool, &keys).await?;
+    key_index.remove(uuid, db, &keys).await;
+    for key in &keys {
+        audit
+            .record(AuditEntry::new(
+                &connection_id,
+                AuditSource::Browser,
+                "deleteKeys",
+                Some(key),
+                db.map(|db| vec![format!("db={db}")]).unwrap_or_default(),
+            ))
+            .await;
+    }
+    durability::acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        db,
+        "deleteKeys",
+        None,
+    )
+    .await;
 
     tracing::info!(
         connection_id = %connection_id,
@@ -137,22 +458,174 @@ pub async fn browser_delete_keys(
     Ok(count)
 }
 
+/// Delete every key matching a glob pattern.
+///
+/// Without `confirmation_token` nothing is deleted: the returned pending
+/// confirmation carries the matching key count and its token.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_delete_by_pattern(
+    connection_id: String,
+    pattern: String,
+    confirmation_token: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    key_index: State<'_, KeyIndex>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
+) -> Result<Confirmable<u64>, AppError> {
+    if pattern.is_empty() {
+        return Err(AppError::InvalidInput("Pattern must not be empty".into()));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+
+    let op = DestructiveOp::DeleteByPattern {
+        pattern: pattern.clone(),
+    };
+    if let Some(confirmation) = confirmations
+        .gate(&pool, &connection_id, op, confirmation_token.as_deref())
+        .await?
+    {
+        return Ok(Confirmable::Pending {
+            confirmation: Box::new(confirmation),
+        });
+    }
+
+    let deleted = scanner::delete_by_pattern(&pool, &pattern).await?;
+    key_index.remove_matching(uuid, None, &pattern).await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Browser,
+            "deleteByPattern",
+            None,
+            vec![pattern.clone(), format!("deleted={deleted}")],
+        ))
+        .await;
+    durability::acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        None,
+        "deleteByPattern",
+        None,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, pattern = %pattern, deleted = deleted, "Keys deleted by pattern");
+    Ok(Confirmable::Done { result: deleted })
+}
+
+/// Set the TTL of every key matching a pattern, or persist them when
+/// `ttl_seconds` is null. Requires a confirmation token like
+/// `browser_delete_by_pattern`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_set_ttl_by_pattern(
+    connection_id: String,
+    pattern: String,
+    ttl_seconds: Option<i64>,
+    confirmation_token: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
+) -> Result<Confirmable<u64>, AppError> {
+    if pattern.is_empty() {
+        return Err(AppError::InvalidInput("Pattern must not be empty".into()));
+    }
+    if ttl_seconds.is_some_and(|secs| secs <= 0) {
+        return Err(AppError::InvalidInput(
+            "TTL must be positive; pass null to remove expiry".into(),
+        ));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+
+    let op = DestructiveOp::BulkTtl {
+        pattern: pattern.clone(),
+        ttl_seconds,
+    };
+    if let Some(confirmation) = confirmations
+        .gate(&pool, &connection_id, op, confirmation_token.as_deref())
+        .await?
+    {
+        return Ok(Confirmable::Pending {
+            confirmation: Box::new(confirmation),
+        });
+    }
+
+    let updated = scanner::set_ttl_by_pattern(&pool, &pattern, ttl_seconds).await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Browser,
+            "setTtlByPattern",
+            None,
+            vec![
+                pattern.clone(),
+                ttl_seconds.map_or_else(|| "persist".into(), |t| t.to_string()),
+                format!("updated={updated}"),
+            ],
+        ))
+        .await;
+    durability::acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        None,
+        "setTtlByPattern",
+        None,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, pattern = %pattern, updated = updated, "TTL updated by pattern");
+    Ok(Confirmable::Done { result: updated })
+}
+
 /// Rename a key. Fails if the new name already exists.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn browser_rename_key(
     connection_id: String,
     old_name: String,
     new_name: String,
+    db: Option<u8>,
     manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
 ) -> Result<(), AppError> {
     if old_name.is_empty() || new_name.is_empty() {
         return Err(AppError::InvalidInput("Key names must not be empty".into()));
     }
 
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_write_pool_for_db(&uuid, db).await?;
 
     scanner::rename_key(&pool, &old_name, &new_name).await?;
+    key_index.rename(uuid, db, &old_name, &new_name).await;
+    let mut args = vec![new_name.clone()];
+    args.extend(db.map(|db| format!("db={db}")));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Browser,
+            "renameKey",
+            Some(&old_name),
+            args,
+        ))
+        .await;
+    durability::acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        db,
+        "renameKey",
+        Some(&new_name),
+    )
+    .await;
 
     tracing::info!(
         connection_id = %connection_id,
@@ -164,6 +637,667 @@ pub async fn browser_rename_key(
     Ok(())
 }
 
+/// Rename every key matching `pattern` to `replacement`, where each holds
+/// a single `*` standing for the part of the name that is kept (e.g.
+/// `cache:v1:*` → `cache:v2:*`).
+///
+/// Keys are renamed with RENAMENX in atomic batches, so an existing key is
+/// never overwritten; such collisions are counted and sampled in the report.
+/// With `dry_run` nothing is renamed and the report shows what would happen.
+///
+/// With a `task_id`, the rename runs as a task that `task_cancel` can abort
+/// between batches, and keys matched so far are reported as `task:progress`
+/// after each batch.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_rename_by_pattern(
+    connection_id: String,
+    pattern: String,
+    replacement: String,
+    dry_run: bool,
+    db: Option<u8>,
+    task_id: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+    tasks: State<'_, TaskRegistry>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
+) -> Result<RenameReport, AppError> {
+    let rewrite = KeyRewrite::parse(&pattern, &replacement)?;
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = if dry_run {
+        manager.get_pool_for_db(&uuid, db).await?
+    } else {
+        manager.get_write_pool_for_db(&uuid, db).await?
+    };
+
+    let recorder = key_index.recorder(uuid, db);
+    let phase = if dry_run {
+        ProgressPhase::Scanning
+    } else {
+        ProgressPhase::Writing
+    };
+    let report = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "renameByPattern",
+            Some(&connection_id),
+            |reporter| async move {
+                rename::rename_by_pattern(&pool, &rewrite, dry_run, &recorder, |report| {
+                    let message = format!(
+                        "{} renamed, {} collisions",
+                        report.renamed, report.collisions
+                    );
+                    reporter.progress(phase, report.matched, None, Some(message));
+                })
+                .await
+            },
+        )
+        .await?;
+
+    if !dry_run {
+        let mut args = vec![
+            pattern.clone(),
+            replacement.clone(),
+            format!("renamed={}", report.renamed),
+            format!("collisions={}", report.collisions),
+        ];
+        args.extend(db.map(|db| format!("db={db}")));
+        audit
+            .record(AuditEntry::new(
+                &connection_id,
+                AuditSource::Browser,
+                "renameByPattern",
+                None,
+                args,
+            ))
+            .await;
+        durability::acknowledge(
+            &app_handle,
+            &manager,
+            &connection_id,
+            db,
+            "renameByPattern",
+            None,
+        )
+        .await;
+        tracing::info!(
+            connection_id = %connection_id,
+            pattern = %pattern,
+            replacement = %replacement,
+            renamed = report.renamed,
+            collisions = report.collisions,
+            "Keys renamed by pattern"
+        );
+    }
+
+    Ok(report)
+}
+
+/// Minimum interval between scheduled keyspace snapshots.
+const MIN_SNAPSHOT_INTERVAL_SECS: u64 = 60;
+
+/// Longest accepted snapshot name.
+const MAX_SNAPSHOT_NAME_CHARS: usize = 100;
+
+/// Scan the whole keyspace now and persist a per-prefix summary, plus a key
+/// index (type and memory per key) for key-level comparisons.
+///
+/// With a `task_id`, the scan runs as a task that `task_cancel` can abort,
+/// reporting keys scanned so far as `task:progress`.
+#[tauri::command]
+pub async fn browser_capture_keyspace_snapshot(
+    connection_id: String,
+    delimiter: String,
+    name: Option<String>,
+    task_id: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+    app_handle: tauri::AppHandle,
+) -> Result<KeyspaceSnapshot, AppError> {
+    let delimiter = if delimiter.is_empty() {
+        ":".to_string()
+    } else {
+        delimiter
+    };
+
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if name
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_SNAPSHOT_NAME_CHARS)
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Snapshot name must be at most {MAX_SNAPSHOT_NAME_CHARS} characters"
+        )));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let throttle = manager.get_throttle(&uuid).await?;
+
+    let id = connection_id.clone();
+    let (snapshot, keys) = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "keyspaceSnapshot",
+            Some(&connection_id),
+            |reporter| async move {
+                keyspace::capture_snapshot_with_progress(
+                    &pool,
+                    &throttle,
+                    &id,
+                    &delimiter,
+                    name,
+                    |scanned| {
+                        reporter.progress(ProgressPhase::Scanning, scanned, None, None);
+                    },
+                )
+                .await
+            },
+        )
+        .await?;
+    keyspace_store::save_snapshot(&app_handle, &snapshot, &keys).await?;
+
+    tracing::info!(
+        connection_id = %connection_id,
+        total_keys = snapshot.total_keys,
+        namespaces = snapshot.namespaces.len(),
+        "Keyspace snapshot captured"
+    );
+
+    Ok(snapshot)
+}
+
+/// Estimate the key count per prefix from `samples` RANDOMKEY draws, for
+/// servers where a full snapshot SCAN is too expensive. Each estimate comes
+/// with a 95% confidence interval.
+///
+/// With a `task_id`, the sampling runs as a task that `task_cancel` can abort.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_sample_keyspace(
+    connection_id: String,
+    delimiter: String,
+    samples: Option<u32>,
+    db: Option<u8>,
+    task_id: Option<String>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<KeyspaceSample, AppError> {
+    let delimiter = if delimiter.is_empty() {
+        ":".to_string()
+    } else {
+        delimiter
+    };
+    let samples = samples.unwrap_or(sampling::DEFAULT_SAMPLES);
+    if samples == 0 || samples > sampling::MAX_SAMPLES {
+        return Err(AppError::InvalidInput(format!(
+            "Samples must be between 1 and {}",
+            sampling::MAX_SAMPLES
+        )));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let sample = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "keyspaceSample",
+            Some(&connection_id),
+            |_| async move { sampling::sample_namespaces(&pool, &delimiter, samples).await },
+        )
+        .await?;
+    tracing::info!(
+        connection_id = %connection_id,
+        samples = sample.samples,
+        namespaces = sample.namespaces.len(),
+        "Keyspace sampled"
+    );
+    Ok(sample)
+}
+
+/// Rank tenants of a shared keyspace by estimated memory, given a namespace
+/// template with an `{id}` placeholder such as `tenant:{id}:*`.
+///
+/// Every matching key is counted per tenant ID; MEMORY USAGE is read for up
+/// to `samples_per_tenant` keys per tenant (default 20) and scaled up to
+/// the tenant's key count. Returns the `limit` largest tenants (default 100).
+///
+/// With a `task_id`, the scan runs as a task that `task_cancel` can abort,
+/// reporting matching keys seen so far as `task:progress`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_tenant_report(
+    connection_id: String,
+    template: String,
+    samples_per_tenant: Option<u32>,
+    limit: Option<u32>,
+    db: Option<u8>,
+    task_id: Option<String>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<TenantReport, AppError> {
+    let samples = samples_per_tenant.unwrap_or(tenants::DEFAULT_SAMPLES_PER_TENANT);
+    if samples > tenants::MAX_SAMPLES_PER_TENANT {
+        return Err(AppError::InvalidInput(format!(
+            "Samples per tenant must be at most {}",
+            tenants::MAX_SAMPLES_PER_TENANT
+        )));
+    }
+    let limit = limit.unwrap_or(tenants::DEFAULT_TENANT_LIMIT);
+    if limit == 0 || limit > tenants::MAX_TENANT_LIMIT {
+        return Err(AppError::InvalidInput(format!(
+            "Limit must be between 1 and {}",
+            tenants::MAX_TENANT_LIMIT
+        )));
+    }
+    // Fail on a bad template before starting a task.
+    TenantTemplate::parse(&template)?;
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let throttle = manager.get_throttle(&uuid).await?;
+    let glob = template.clone();
+    let report = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "tenantReport",
+            Some(&connection_id),
+            |reporter| async move {
+                tenants::tenant_report(&pool, &throttle, &glob, samples, limit, |matched| {
+                    reporter.progress(ProgressPhase::Scanning, matched, None, None);
+                })
+                .await
+            },
+        )
+        .await?;
+    tracing::info!(
+        connection_id = %connection_id,
+        template = %template,
+        tenants = report.tenant_count,
+        keys = report.matched_keys,
+        "Tenant report built"
+    );
+    Ok(report)
+}
+
+/// Check the keys matching `pattern` (default all) against naming rules
+/// (default: the `keyNamingRules` setting): delimiter consistency, depth,
+/// forbidden characters, segment case and missing TTLs. Violations come
+/// back grouped by rule.
+///
+/// With a `task_id`, the scan runs as a task that `task_cancel` can abort,
+/// reporting keys scanned so far as `task:progress`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_lint_keys(
+    connection_id: String,
+    rules: Option<NamingRules>,
+    pattern: Option<String>,
+    db: Option<u8>,
+    task_id: Option<String>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<LintReport, AppError> {
+    let rules = match rules {
+        Some(rules) => rules,
+        None => {
+            settings_store::load_settings(&app_handle)
+                .await?
+                .key_naming_rules
+        }
+    };
+    lint::validate(&rules)?;
+    let pattern = pattern
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| "*".to_string());
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let throttle = manager.get_throttle(&uuid).await?;
+    let report = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "keyLint",
+            Some(&connection_id),
+            |reporter| async move {
+                lint::lint_keys(&pool, &throttle, &rules, &pattern, |scanned| {
+                    reporter.progress(ProgressPhase::Scanning, scanned, None, None);
+                })
+                .await
+            },
+        )
+        .await?;
+    tracing::info!(
+        connection_id = %connection_id,
+        scanned = report.scanned,
+        violating = report.violating_keys,
+        "Keys linted"
+    );
+    Ok(report)
+}
+
+/// Infer the common field schema of the hashes and JSON documents matching
+/// `pattern` (e.g. `user:*`) from a sample of them: field names, value
+/// types, fill rate and example values.
+///
+/// With a `task_id`, the sampling runs as a task that `task_cancel` can abort.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_infer_schema(
+    connection_id: String,
+    pattern: String,
+    samples: Option<u32>,
+    db: Option<u8>,
+    task_id: Option<String>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<SchemaReport, AppError> {
+    if pattern.is_empty() {
+        return Err(AppError::InvalidInput("Pattern must not be empty".into()));
+    }
+    let samples = samples.unwrap_or(schema::DEFAULT_SAMPLES);
+    if samples == 0 || samples > schema::MAX_SAMPLES {
+        return Err(AppError::InvalidInput(format!(
+            "Samples must be between 1 and {}",
+            schema::MAX_SAMPLES
+        )));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let glob = pattern.clone();
+    let report = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "schemaInference",
+            Some(&connection_id),
+            |_| async move { schema::infer_schema(&pool, &glob, samples).await },
+        )
+        .await?;
+    tracing::info!(
+        connection_id = %connection_id,
+        pattern = %pattern,
+        keys = report.hash_keys + report.json_keys,
+        fields = report.fields.len(),
+        "Schema inferred"
+    );
+    Ok(report)
+}
+
+/// Start searching values across the keyspace for a substring or regex.
+/// Returns the search ID.
+///
+/// Keys come from SCAN, optionally narrowed by a key pattern and types, and
+/// each value is read only up to the size caps in `spec`. Hits arrive per
+/// SCAN batch as `browser:value-search-hits` events, and progress as
+/// `task:progress` with the search ID as task ID (the last one with phase
+/// `done` or `failed`). `browser_cancel_value_search` (or `task_cancel`)
+/// stops the search early.
+#[tauri::command]
+pub async fn browser_search_values(
+    connection_id: String,
+    spec: ValueSearchSpec,
+    db: Option<u8>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<String, AppError> {
+    let search = ValueSearch::new(spec)?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let throttle = manager.get_throttle(&uuid).await?;
+
+    let search_id = Uuid::new_v4().to_string();
+    let id = search_id.clone();
+    let task_handle = app_handle.clone();
+    let task_connection = connection_id.clone();
+    tasks
+        .spawn(
+            &task_handle,
+            search_id.clone(),
+            "valueSearch",
+            Some(&task_connection),
+            move |_| async move {
+                let progress = |tally: &value_search::SearchTally| {
+                    ProgressEvent::new(
+                        &id,
+                        "valueSearch",
+                        Some(&connection_id),
+                        ProgressPhase::Scanning,
+                    )
+                    .processed(tally.scanned_keys, None)
+                };
+                let mut reached = value_search::SearchTally::default();
+                let outcome = value_search::search(&pool, &throttle, &search, |tally, hits| {
+                    reached = tally.clone();
+                    if !hits.is_empty() {
+                        let batch = ValueSearchHits {
+                            search_id: id.clone(),
+                            connection_id: connection_id.clone(),
+                            hits,
+                            total_hits: tally.total_hits,
+                            truncated: tally.truncated,
+                        };
+                        let _ = app_handle.emit("browser:value-search-hits", &batch);
+                    }
+                    progress(tally)
+                        .message(Some(tally.summary()))
+                        .emit(&app_handle);
+                })
+                .await;
+                match &outcome {
+                    Ok(_) => progress(&reached)
+                        .finished(None)
+                        .message(Some(reached.summary())),
+                    Err(e) => {
+                        tracing::warn!(search_id = %id, "Value search failed: {e}");
+                        progress(&reached).finished(Some(e.to_string()))
+                    }
+                }
+                .emit(&app_handle);
+                tracing::info!(
+                    search_id = %id,
+                    scanned = reached.scanned_keys,
+                    hits = reached.total_hits,
+                    "Value search finished"
+                );
+                outcome.map(|_| ())
+            },
+        )
+        .await?;
+    Ok(search_id)
+}
+
+/// Stop a running value search. Returns whether it was still running.
+#[tauri::command]
+pub async fn browser_cancel_value_search(
+    search_id: String,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<bool, AppError> {
+    Ok(tasks.cancel(&search_id).await)
+}
+
+/// Capture keyspace snapshots periodically, emitting `browser:keyspace-snapshot`.
+#[tauri::command]
+pub async fn browser_start_keyspace_snapshots(
+    connection_id: String,
+    delimiter: String,
+    interval_secs: u64,
+    manager: State<'_, ConnectionManager>,
+    snapshotter: State<'_, KeyspaceSnapshotter>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let delimiter = if delimiter.is_empty() {
+        ":".to_string()
+    } else {
+        delimiter
+    };
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let throttle = manager.get_throttle(&uuid).await?;
+
+    let interval = interval_secs.max(MIN_SNAPSHOT_INTERVAL_SECS);
+    snapshotter
+        .start(uuid, throttle, delimiter, interval, app_handle)
+        .await;
+    Ok(())
+}
+
+/// Stop periodic keyspace snapshots for a connection.
+#[tauri::command]
+pub async fn browser_stop_keyspace_snapshots(
+    connection_id: String,
+    snapshotter: State<'_, KeyspaceSnapshotter>,
+) -> Result<(), AppError> {
+    snapshotter.stop(&connection_id).await;
+    Ok(())
+}
+
+/// List persisted keyspace snapshots for a connection, oldest first.
+#[tauri::command]
+pub async fn browser_list_keyspace_snapshots(
+    connection_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<KeyspaceSnapshot>, AppError> {
+    keyspace_store::list_snapshots(&app_handle, &connection_id).await
+}
+
+/// Compare two keyspace snapshots, showing which namespaces grew or shrank.
+///
+/// When both snapshots have key indexes, each namespace also lists the keys
+/// added, removed and grown in between.
+#[tauri::command]
+pub async fn browser_compare_keyspace_snapshots(
+    before_id: String,
+    after_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<KeyspaceComparison, AppError> {
+    let before_id = Uuid::parse_str(&before_id)?;
+    let after_id = Uuid::parse_str(&after_id)?;
+    let before = keyspace_store::load_snapshot(&app_handle, &before_id).await?;
+    let after = keyspace_store::load_snapshot(&app_handle, &after_id).await?;
+    let before_keys = keyspace_store::load_key_index(&app_handle, &before_id).await?;
+    let after_keys = keyspace_store::load_key_index(&app_handle, &after_id).await?;
+    Ok(match (before_keys, after_keys) {
+        (Some(before_keys), Some(after_keys)) => {
+            keyspace::compare_with_keys(&before, &before_keys, &after, &after_keys)
+        }
+        _ => keyspace::compare(&before, &after),
+    })
+}
+
+/// Delete a persisted keyspace snapshot.
+#[tauri::command]
+pub async fn browser_delete_keyspace_snapshot(
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    keyspace_store::delete_snapshot(&app_handle, &uuid).await
+}
+
+/// Default window keyspace events are collected over before a hint.
+const DEFAULT_INVALIDATE_DEBOUNCE_MS: u64 = 2000;
+
+/// Watch keyspace notifications and emit `browser:invalidate` hints naming
+/// the namespace prefixes whose keys changed, at most once per `debounce_ms`.
+///
+/// Prefixes are the first `depth` segments of changed keys (default 1).
+/// The server must publish keyspace events; with `enable_notifications`
+/// they are switched on if needed (a config change, so not on read-only
+/// connections or where the command policy blocks CONFIG SET). Returns the server's effective `notify-keyspace-events`
+/// flags, if it allows reading them. Replaces any watch already running for
+/// the connection.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn browser_watch_keyspace(
+    connection_id: String,
+    delimiter: String,
+    db: Option<u8>,
+    depth: Option<u32>,
+    debounce_ms: Option<u64>,
+    enable_notifications: Option<bool>,
+    manager: State<'_, ConnectionManager>,
+    watcher: State<'_, KeyspaceWatcher>,
+    key_index: State<'_, KeyIndex>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<String>, AppError> {
+    let delimiter = if delimiter.is_empty() {
+        ":".to_string()
+    } else {
+        delimiter
+    };
+    let debounce_ms = debounce_ms.unwrap_or(DEFAULT_INVALIDATE_DEBOUNCE_MS);
+    if !(100..=60_000).contains(&debounce_ms) {
+        return Err(AppError::InvalidInput(
+            "Debounce must be between 100 and 60000 ms".into(),
+        ));
+    }
+
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let enable = enable_notifications.unwrap_or(false);
+    let pool = if enable {
+        let config_set = ["CONFIG".to_string(), "SET".to_string()];
+        command_policy::check_blocked(&manager.get_command_policy(&uuid).await?, &config_set)?;
+        manager.get_write_pool(&uuid).await?
+    } else {
+        manager.get_pool(&uuid).await?
+    };
+    let mut conn = pool.get().await?;
+    let flags = invalidation::ensure_notifications(&mut conn, enable).await?;
+    drop(conn);
+    if let Some((before, after)) = &flags {
+        if before != after {
+            audit
+                .record(AuditEntry::new(
+                    &connection_id,
+                    AuditSource::Browser,
+                    "enableKeyspaceNotifications",
+                    None,
+                    vec![before.clone(), after.clone()],
+                ))
+                .await;
+        }
+    }
+
+    let options = WatchOptions {
+        db: match db {
+            Some(db) => db,
+            None => manager.get_profile(&uuid).await?.database,
+        },
+        delimiter,
+        depth: depth.map_or(1, |d| d.max(1) as usize),
+        debounce: std::time::Duration::from_millis(debounce_ms),
+    };
+    let url = manager.get_connection_url(&uuid).await?;
+    watcher
+        .start(uuid, url, options, key_index.recorder(uuid, db), app_handle)
+        .await?;
+    Ok(flags.map(|(_, after)| after))
+}
+
+/// Stop watching keyspace notifications for a connection.
+#[tauri::command]
+pub async fn browser_unwatch_keyspace(
+    connection_id: String,
+    watcher: State<'_, KeyspaceWatcher>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    watcher.stop(&uuid).await;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,72 +1,142 @@
 // SPDX-License-Identifier: MIT
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::time::Duration;
 
+use deadpool_redis::Pool;
 use tauri::State;
-use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::browser::key_index::KeyIndex;
 use crate::redis::cli::{
-    executor,
-    model::{CommandSuggestion, ExecuteResponse, HistoryEntry},
+    broadcast,
+    completion::{self, Completion, CompletionKind, CompletionResult, Target},
+    executor::{self, ResultStream},
+    history::{self, CliHistory},
+    model::{
+        BroadcastResult, CommandResult, CommandSuggestion, ExecuteResponse, HistoryEntry,
+        PermissionCheck, StreamOptions,
+    },
+    parser, permissions,
+    requests::CliRequests,
     suggestions,
 };
+use crate::redis::confirm::model::DestructiveOp;
+use crate::redis::confirm::registry::ConfirmationRegistry;
 use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::{command_policy, timeout, write_guard};
+use crate::utils::chunked;
 use crate::utils::errors::AppError;
 
-/// Per-connection command history, stored in memory (frontend also persists).
-pub struct CliHistory {
-    histories: Arc<RwLock<HashMap<Uuid, Vec<HistoryEntry>>>>,
-}
-
-impl Default for CliHistory {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl CliHistory {
-    pub fn new() -> Self {
-        Self {
-            histories: Arc::new(RwLock::new(HashMap::new())),
-        }
-    }
-
-    async fn push(&self, id: &Uuid, entry: HistoryEntry) {
-        let mut map = self.histories.write().await;
-        let history = map.entry(*id).or_default();
-        history.push(entry);
-        // Keep last 500 entries per connection
-        if history.len() > 500 {
-            history.drain(..history.len() - 500);
-        }
-    }
-
-    async fn get(&self, id: &Uuid, limit: usize) -> Vec<HistoryEntry> {
-        let map = self.histories.read().await;
-        map.get(id)
-            .map(|h| {
-                let start = h.len().saturating_sub(limit);
-                h[start..].to_vec()
-            })
-            .unwrap_or_default()
-    }
-}
-
 /// Execute a Redis command string.
+///
+/// Unknown commands and wrong argument counts are rejected locally when the
+/// server reported its command table at connect time. Mutating commands are
+/// rejected on read-only connections, and commands the profile's command
+/// policy blocks are refused. Dangerous commands (the built-in table with
+/// the policy's allow and confirm lists applied) are held back and answered
+/// with a pending confirmation; they run only when re-sent with its
+/// `confirmation_token`. `db` runs the command
+/// against another database than the profile's. `raw` adds the reply's RESP
+/// wire form and type tree to the response.
+///
+/// With `stream`, the command can be aborted with `cli_cancel` and a large
+/// array reply is sent as `cli:result-chunk` events rather than in the
+/// response.
+///
+/// With `preflight`, the server is first asked (ACL DRYRUN) whether the
+/// connection's user may run the command, and a denial is returned as a
+/// permission error before anything runs or a confirmation is issued.
+///
+/// A MOVED or ASK reply from a cluster node comes back annotated with the
+/// redirect; with `follow_redirects`, the command is re-sent once to that
+/// node over a one-off connection and the response names it.
+///
+/// The command fails with a timeout error once it has waited longer than
+/// the profile's write timeout (for writes) or read timeout (otherwise).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn cli_execute(
     connection_id: String,
     command: String,
-    force: bool,
+    confirmation_token: Option<String>,
+    db: Option<u8>,
+    raw: Option<bool>,
+    stream: Option<StreamOptions>,
+    preflight: Option<bool>,
+    follow_redirects: Option<bool>,
+    app_handle: tauri::AppHandle,
     manager: State<'_, ConnectionManager>,
     history: State<'_, CliHistory>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    requests: State<'_, CliRequests>,
+    audit: State<'_, AuditLog>,
 ) -> Result<ExecuteResponse, AppError> {
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_pool_for_db(&uuid, db).await?;
+    let args = parser::parse_command(&command);
+    write_guard::check_command(manager.is_readonly(&uuid).await?, &args)?;
+    let warning = command_policy::check(&manager.get_command_policy(&uuid).await?, &args)?;
+    manager.get_command_catalog(&uuid).await?.validate(&args)?;
+    if preflight.unwrap_or(false) {
+        let check = permissions::check_permission(&pool, &args).await?;
+        if check.allowed == Some(false) {
+            return Err(AppError::PermissionDenied(
+                check.reason.unwrap_or_else(|| "Rejected by ACL".into()),
+            ));
+        }
+    }
+
+    let limit = timeout::for_command(&manager.get_profile(&uuid).await?.timeout, &args);
+    let operation = args.first().map(|a| a.to_uppercase()).unwrap_or_default();
 
-    let response = executor::execute(&pool, &command, force).await;
+    let follow = match follow_redirects {
+        Some(true) => Some(manager.get_connection_url(&uuid).await?),
+        _ => None,
+    };
+
+    let pending = match warning {
+        Some(_) => {
+            let op = DestructiveOp::CliCommand {
+                command: command.trim().to_string(),
+            };
+            confirmations
+                .gate(&pool, &connection_id, op, confirmation_token.as_deref())
+                .await?
+        }
+        None => None,
+    };
+
+    let raw = raw.unwrap_or(false);
+    let response = match (pending, stream) {
+        (Some(confirmation), _) => Ok(ExecuteResponse {
+            result: CommandResult::Error(format!(
+                "DANGEROUS: {} — Re-send with the confirmation token to proceed.",
+                confirmation.impact.summary
+            )),
+            duration_ms: 0.0,
+            command: command.clone(),
+            confirmation: Some(confirmation),
+            raw: None,
+            streamed: None,
+            redirect: None,
+        }),
+        // Dangerous commands reaching this point carried a valid token.
+        (None, Some(options)) => {
+            let sink = ResultStream {
+                app_handle,
+                request_id: options.request_id.clone(),
+                chunk_size: chunked::chunk_size(options.chunk_size)?,
+            };
+            let input = command.clone();
+            let work = run_command(pool, input, raw, follow, limit, operation, Some(sink));
+            requests.run(options.request_id, work).await
+        }
+        (None, None) => {
+            run_command(pool, command.clone(), raw, follow, limit, operation, None).await
+        }
+    };
 
     // Record in history
     let entry = HistoryEntry {
@@ -77,15 +147,192 @@ pub async fn cli_execute(
     };
     history.push(&uuid, entry).await;
 
+    let executed = response.as_ref().is_ok_and(|r| r.confirmation.is_none());
+    if executed && write_guard::is_write_command(&args) {
+        let mut args = history::redact_credentials(&args).into_iter();
+        let name = args.next().unwrap_or_default().to_uppercase();
+        let mut args: Vec<String> = args.collect();
+        args.extend(db.map(|db| format!("db={db}")));
+        audit
+            .record(AuditEntry::new(
+                &connection_id,
+                AuditSource::Cli,
+                &name,
+                None,
+                args,
+            ))
+            .await;
+    }
+
     response
 }
 
+/// Run `command` within the profile's timeout, sending a large array reply
+/// to `sink` as chunk events when one is given.
+async fn run_command(
+    pool: Pool,
+    command: String,
+    raw: bool,
+    follow: Option<String>,
+    limit: Option<Duration>,
+    operation: String,
+    sink: Option<ResultStream>,
+) -> Result<ExecuteResponse, AppError> {
+    let work = executor::execute(&pool, &command, true, raw, sink.as_ref(), follow.as_deref());
+    timeout::with_timeout(limit, &operation, work).await
+}
+
+/// Ask the server whether the connection's ACL user may run `command`,
+/// without running it (ACL DRYRUN, Redis 7).
+///
+/// `allowed` is `None` when the server can't tell; `reason` then says why.
+#[tauri::command]
+pub async fn cli_check_permission(
+    connection_id: String,
+    command: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<PermissionCheck, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let args = parser::parse_command(&command);
+    permissions::check_permission(&pool, &args).await
+}
+
+/// Run the same command on several connections at once and return each
+/// connection's reply and duration, in the order the IDs were given.
+///
+/// Only read commands are allowed unless `allow_writes` is set, and even
+/// then read-only profiles refuse writes. Dangerous and blocking commands
+/// are rejected outright. A target that is not connected, or whose server
+/// rejects the command, gets an error entry; the others still run.
+#[tauri::command]
+pub async fn cli_execute_broadcast(
+    connection_ids: Vec<String>,
+    command: String,
+    allow_writes: Option<bool>,
+    db: Option<u8>,
+    manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
+) -> Result<Vec<BroadcastResult>, AppError> {
+    let targets = broadcast::parse_targets(&connection_ids)?;
+    let args = parser::parse_command(&command);
+    broadcast::check_command(&args, allow_writes.unwrap_or(false))?;
+
+    let manager = manager.inner();
+    let results = futures::future::join_all(
+        targets
+            .iter()
+            .map(|id| broadcast::run_on(manager, *id, &command, &args, db)),
+    )
+    .await;
+
+    if write_guard::is_write_command(&args) {
+        let name = args[0].to_uppercase();
+        let redacted = history::redact_credentials(&args);
+        for result in results.iter().filter(|r| r.error.is_none()) {
+            let mut entry_args = redacted[1..].to_vec();
+            entry_args.extend(db.map(|db| format!("db={db}")));
+            audit
+                .record(AuditEntry::new(
+                    &result.connection_id,
+                    AuditSource::Cli,
+                    &name,
+                    None,
+                    entry_args,
+                ))
+                .await;
+        }
+    }
+    tracing::info!(targets = targets.len(), "Command broadcast");
+    Ok(results)
+}
+
+/// Abort a command started with `stream` options. Returns whether it was
+/// still running.
+#[tauri::command]
+pub async fn cli_cancel(
+    request_id: String,
+    requests: State<'_, CliRequests>,
+) -> Result<bool, AppError> {
+    Ok(requests.cancel(&request_id).await)
+}
+
 /// Get autocomplete suggestions for a command prefix.
+///
+/// With a connected `connection_id`, commands the server documents but the
+/// built-in table lacks (e.g. module commands) are included.
 #[tauri::command]
 pub async fn cli_get_command_suggestions(
     prefix: String,
+    connection_id: Option<String>,
+    manager: State<'_, ConnectionManager>,
 ) -> Result<Vec<CommandSuggestion>, AppError> {
-    Ok(suggestions::get_suggestions(&prefix))
+    match connection_id {
+        Some(id) => {
+            let uuid = Uuid::parse_str(&id)?;
+            Ok(manager
+                .get_command_catalog(&uuid)
+                .await?
+                .suggestions(&prefix))
+        }
+        None => Ok(suggestions::get_suggestions(&prefix)),
+    }
+}
+
+/// Syntax and summary for the command being typed (subcommands included,
+/// e.g. `CONFIG GET`), or `None` if it is unknown.
+#[tauri::command]
+pub async fn cli_get_command_help(
+    connection_id: String,
+    command: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Option<CommandSuggestion>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(manager.get_command_catalog(&uuid).await?.help(&command))
+}
+
+/// Completions for the last token of a partly typed command line.
+///
+/// The first token completes to command names and the second to
+/// subcommands of container commands such as `CONFIG`, both fuzzily
+/// matched. In key positions, keys already seen by the key browser for this
+/// connection and `db` are offered by prefix.
+#[tauri::command]
+pub async fn cli_complete(
+    connection_id: String,
+    line: String,
+    db: Option<u8>,
+    limit: Option<u32>,
+    manager: State<'_, ConnectionManager>,
+    key_index: State<'_, KeyIndex>,
+) -> Result<CompletionResult, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let limit = limit.map_or(20, |l| l as usize);
+    let catalog = manager.get_command_catalog(&uuid).await?;
+    let context = completion::analyze(&line, &catalog);
+
+    let items = match &context.target {
+        Target::Command => completion::complete_command(&catalog, &context.partial, limit),
+        Target::Subcommand(command) => {
+            completion::complete_subcommand(&catalog, command, &context.partial, limit)
+        }
+        Target::Key => key_index
+            .complete(uuid, db, &context.partial, limit)
+            .await
+            .into_iter()
+            .map(|key| Completion {
+                value: parser::quote_arg(&key),
+                kind: CompletionKind::Key,
+                detail: None,
+            })
+            .collect(),
+        Target::Other => Vec::new(),
+    };
+
+    Ok(CompletionResult {
+        replace_from: context.replace_from,
+        items,
+    })
 }
 
 /// Get command history for a connection.
@@ -100,3 +347,42 @@ pub async fn cli_get_command_history(
     let limit = limit.unwrap_or(100) as usize;
     Ok(history.get(&uuid, limit).await)
 }
+
+/// Search a connection's history (including earlier sessions), newest first.
+///
+/// Every whitespace-separated term in `query` must appear in the command,
+/// ignoring case.
+#[tauri::command]
+pub async fn cli_search_history(
+    connection_id: String,
+    query: String,
+    limit: Option<u64>,
+    history: State<'_, CliHistory>,
+) -> Result<Vec<HistoryEntry>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    #[allow(clippy::cast_possible_truncation)]
+    let limit = limit.unwrap_or(100) as usize;
+    Ok(history.search(&uuid, &query, limit).await)
+}
+
+/// Get the pinned commands for a connection.
+#[tauri::command]
+pub async fn cli_get_pinned_commands(
+    connection_id: String,
+    history: State<'_, CliHistory>,
+) -> Result<Vec<String>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(history.pinned(&uuid).await)
+}
+
+/// Pin or unpin a command. Returns the updated pinned list.
+#[tauri::command]
+pub async fn cli_set_command_pinned(
+    connection_id: String,
+    command: String,
+    pinned: bool,
+    history: State<'_, CliHistory>,
+) -> Result<Vec<String>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    history.set_pinned(&uuid, &command, pinned).await
+}
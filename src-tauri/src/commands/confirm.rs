@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+
+use crate::redis::confirm::registry::ConfirmationRegistry;
+use crate::utils::errors::AppError;
+
+/// Discard a pending confirmation token without running its operation.
+#[tauri::command]
+pub async fn confirm_cancel(
+    token: String,
+    confirmations: State<'_, ConfirmationRegistry>,
+) -> Result<bool, AppError> {
+    Ok(confirmations.cancel(&token).await)
+}
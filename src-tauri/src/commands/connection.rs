@@ -1,12 +1,35 @@
 // SPDX-License-Identifier: MIT
 
-use tauri::State;
+use tauri::{Emitter, State};
 use uuid::Uuid;
 
-use crate::config::profile_store;
+use crate::config::profile_vault::ProfileStoreStatus;
+use crate::config::{profile_store, workspace_store};
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::backup::scheduler::BackupScheduler;
+use crate::redis::browser::invalidation::KeyspaceWatcher;
+use crate::redis::browser::key_index::KeyIndex;
+use crate::redis::cli::model::CommandResult;
+use crate::redis::cli::parser;
+use crate::redis::confirm::model::{Confirmable, DestructiveOp};
+use crate::redis::confirm::registry::ConfirmationRegistry;
+use crate::redis::connection::latency_probe::{self, LatencyProber, LatencyStats};
 use crate::redis::connection::manager::{self, ConnectionManager};
-use crate::redis::connection::model::{ConnectionProfile, ConnectionState, ServerInfoSummary};
+use crate::redis::connection::model::{
+    ConfigDrift, ConfigDriftEvent, ConnectionGroup, ConnectionProfile, ConnectionState,
+    DatabaseSummary, ProfileImportResult, RespProtocol, ScanPreset, ServerCapabilities,
+    ServerInfoSummary, WriteConcern,
+};
+use crate::redis::connection::portable;
+use crate::redis::connection::push::PushChannels;
+use crate::redis::connection::supervisor::ConnectionSupervisor;
 use crate::redis::connection::uri::parse_redis_uri;
+use crate::redis::connection::write_guard;
+use crate::redis::connection::{command_policy, databases};
+use crate::redis::devtools::tracking::TrackingSessions;
+use crate::redis::editor::undo::UndoJournal;
+use crate::redis::monitor::slow_log::SlowLogTracker;
 use crate::utils::errors::AppError;
 
 /// Test a Redis connection without persisting it.
@@ -57,16 +80,18 @@ pub async fn connection_save(
     profile: ConnectionProfile,
     app_handle: tauri::AppHandle,
 ) -> Result<ConnectionProfile, AppError> {
-    if profile.name.is_empty() {
-        return Err(AppError::InvalidInput(
-            "Connection name must not be empty".into(),
-        ));
-    }
-    if profile.host.is_empty() {
-        return Err(AppError::InvalidInput("Host must not be empty".into()));
+    profile_store::validate_profile(&profile)?;
+    if let Some(group_id) = profile.group_id {
+        let groups = profile_store::load_groups(&app_handle).await?;
+        if !groups.iter().any(|g| g.id == group_id) {
+            return Err(AppError::NotFound(format!(
+                "Connection group {group_id} not found"
+            )));
+        }
     }
 
     let mut profile = profile;
+    profile.tags = profile_store::normalize_tags(profile.tags);
     profile.updated_at = chrono::Utc::now();
 
     profile_store::save_profile(&app_handle, &profile).await?;
@@ -75,38 +100,288 @@ pub async fn connection_save(
     Ok(profile)
 }
 
-/// List all saved connection profiles.
+/// List all saved connection profiles, ordered by position then name.
 #[tauri::command]
 pub async fn connection_list(
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<ConnectionProfile>, AppError> {
-    profile_store::load_all_profiles(&app_handle).await
+    let mut profiles = profile_store::load_all_profiles(&app_handle).await?;
+    profile_store::sort_profiles(&mut profiles);
+    Ok(profiles)
+}
+
+/// Move profiles into a group (or the top level with no `group_id`), in the given order.
+#[tauri::command]
+pub async fn connection_reorder(
+    group_id: Option<String>,
+    ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let group_id = group_id.as_deref().map(Uuid::parse_str).transpose()?;
+    let ids = parse_ids(&ids)?;
+    profile_store::reorder_profiles(&app_handle, group_id, &ids).await
+}
+
+/// Save a scan preset (pattern, COUNT, delimiter, type filter) on a
+/// profile, replacing the one with the same ID. Returns the updated profile.
+#[tauri::command]
+pub async fn connection_save_scan_preset(
+    id: String,
+    preset: ScanPreset,
+    make_default: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<ConnectionProfile, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let profile =
+        profile_store::save_scan_preset(&app_handle, &uuid, preset, make_default.unwrap_or(false))
+            .await?;
+    tracing::info!(id = %uuid, presets = profile.scan_presets.len(), "Scan preset saved");
+    Ok(profile)
+}
+
+/// Delete a scan preset from a profile. Returns the updated profile.
+#[tauri::command]
+pub async fn connection_delete_scan_preset(
+    id: String,
+    preset_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ConnectionProfile, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let preset_id = Uuid::parse_str(&preset_id)?;
+    profile_store::delete_scan_preset(&app_handle, &uuid, &preset_id).await
+}
+
+/// Choose the scan preset the key browser opens with for a profile, or
+/// none for a plain `*` scan. Returns the updated profile.
+#[tauri::command]
+pub async fn connection_set_default_scan_preset(
+    id: String,
+    preset_id: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<ConnectionProfile, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let preset_id = preset_id.as_deref().map(Uuid::parse_str).transpose()?;
+    profile_store::set_default_scan_preset(&app_handle, &uuid, preset_id).await
+}
+
+// ---------------------------------------------------------------------------
+// Groups
+// ---------------------------------------------------------------------------
+
+/// List connection groups in display order.
+#[tauri::command]
+pub async fn connection_group_list(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<ConnectionGroup>, AppError> {
+    profile_store::load_groups(&app_handle).await
+}
+
+/// Create a connection group at the end of the list.
+#[tauri::command]
+pub async fn connection_group_create(
+    name: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ConnectionGroup, AppError> {
+    let group = profile_store::create_group(&app_handle, &name).await?;
+    tracing::info!(id = %group.id, name = %group.name, "Connection group created");
+    Ok(group)
+}
+
+/// Rename a connection group.
+#[tauri::command]
+pub async fn connection_group_rename(
+    id: String,
+    name: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ConnectionGroup, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    profile_store::rename_group(&app_handle, &uuid, &name).await
+}
+
+/// Delete a connection group; its profiles move to the top level.
+#[tauri::command]
+pub async fn connection_group_delete(
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    profile_store::delete_group(&app_handle, &uuid).await?;
+    tracing::info!(id = %uuid, "Connection group deleted");
+    Ok(())
+}
+
+/// Set the display order of connection groups.
+#[tauri::command]
+pub async fn connection_group_reorder(
+    ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let ids = parse_ids(&ids)?;
+    profile_store::reorder_groups(&app_handle, &ids).await
+}
+
+// ---------------------------------------------------------------------------
+// Profile encryption
+// ---------------------------------------------------------------------------
+
+/// Whether `connections.json` is encrypted and, if so, locked.
+#[tauri::command]
+pub async fn connection_store_status(
+    app_handle: tauri::AppHandle,
+) -> Result<ProfileStoreStatus, AppError> {
+    profile_store::store_status(&app_handle).await
+}
+
+/// Encrypt the saved profiles with a master passphrase (Argon2id + AES-256-GCM).
+///
+/// The store stays unlocked for this session.
+#[tauri::command]
+pub async fn connection_store_enable_encryption(
+    passphrase: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    profile_store::enable_encryption(&app_handle, &passphrase).await?;
+    tracing::info!("Connection profile encryption enabled");
+    Ok(())
+}
+
+/// Unlock the encrypted profiles; fails with `Decryption` on a wrong passphrase.
+#[tauri::command]
+pub async fn connection_store_unlock(
+    passphrase: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    profile_store::unlock(&app_handle, &passphrase).await?;
+    tracing::info!("Connection profiles unlocked");
+    Ok(())
+}
+
+/// Lock the encrypted profiles. Open connections are left running.
+#[tauri::command]
+pub async fn connection_store_lock(app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    profile_store::lock(&app_handle).await?;
+    tracing::info!("Connection profiles locked");
+    Ok(())
+}
+
+/// Decrypt the saved profiles back to plaintext.
+#[tauri::command]
+pub async fn connection_store_disable_encryption(
+    passphrase: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    profile_store::disable_encryption(&app_handle, &passphrase).await?;
+    tracing::info!("Connection profile encryption disabled");
+    Ok(())
+}
+
+fn parse_ids(ids: &[String]) -> Result<Vec<Uuid>, AppError> {
+    ids.iter()
+        .map(|id| Uuid::parse_str(id).map_err(AppError::from))
+        .collect()
+}
+
+/// Export profiles as a portable JSON document.
+///
+/// Exports every profile unless `ids` is given. Secrets are encrypted with
+/// `passphrase` when one is supplied and left out otherwise.
+#[tauri::command]
+pub async fn connection_export(
+    ids: Option<Vec<String>>,
+    passphrase: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, AppError> {
+    let mut profiles = profile_store::load_all_profiles(&app_handle).await?;
+    if let Some(ids) = ids {
+        let ids = parse_ids(&ids)?;
+        profiles.retain(|p| ids.contains(&p.id));
+    }
+
+    let count = profiles.len();
+    let bundle = portable::export_profiles(profiles, passphrase.as_deref())?;
+    let data = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize export: {e}")))?;
+
+    tracing::info!(
+        count = count,
+        encrypted = bundle.secrets.is_some(),
+        "Connection profiles exported"
+    );
+    Ok(data)
+}
+
+/// Import profiles from a `RedisLens` export, a `RedisInsight` database export
+/// or a list of Redis URIs.
+///
+/// Entries that fail the checks `connection_save` makes, or match an
+/// existing profile's host, port, database and SSH host, are skipped.
+#[tauri::command]
+pub async fn connection_import(
+    content: String,
+    passphrase: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<ProfileImportResult, AppError> {
+    let parsed = portable::parse_import(&content, passphrase.as_deref()).await?;
+    let existing = profile_store::load_all_profiles(&app_handle).await?;
+    let result = portable::dedupe(&existing, parsed);
+
+    if !result.imported.is_empty() {
+        profile_store::insert_profiles(&app_handle, &result.imported).await?;
+    }
+
+    tracing::info!(
+        imported = result.imported.len(),
+        skipped = result.skipped.len(),
+        "Connection profiles imported"
+    );
+    Ok(result)
 }
 
 /// Delete a connection profile.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn connection_delete(
     id: String,
     manager: State<'_, ConnectionManager>,
+    backups: State<'_, BackupScheduler>,
+    journal: State<'_, UndoJournal>,
+    supervisor: State<'_, ConnectionSupervisor>,
+    prober: State<'_, LatencyProber>,
+    push: State<'_, PushChannels>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), AppError> {
     let uuid = Uuid::parse_str(&id)?;
 
     // Disconnect if active
+    supervisor.stop(&uuid.to_string()).await;
+    prober.stop(&uuid).await;
+    push.close(&uuid).await;
+    backups.stop(&uuid.to_string()).await;
+    journal.clear(&uuid.to_string()).await;
     manager.disconnect(&uuid).await;
 
     // Delete from disk
     profile_store::delete_profile(&app_handle, &uuid).await?;
+    if let Err(e) = workspace_store::remove_profile(&app_handle, &uuid.to_string()).await {
+        tracing::warn!(id = %uuid, "Failed to clear workspace state: {e}");
+    }
 
     tracing::info!(id = %uuid, "Connection profile deleted");
     Ok(())
 }
 
 /// Connect to a Redis server using a saved profile.
+///
+/// Emits `connection:drift` if the server deviates from the profile's baseline,
+/// and starts the profile's backup schedule if one is enabled. The connection
+/// is then health-checked and reconnected automatically, with state changes
+/// emitted as `connection:state-changed`.
 #[tauri::command]
 pub async fn connection_connect(
     id: String,
     manager: State<'_, ConnectionManager>,
+    backups: State<'_, BackupScheduler>,
+    supervisor: State<'_, ConnectionSupervisor>,
     app_handle: tauri::AppHandle,
 ) -> Result<ServerInfoSummary, AppError> {
     let uuid = Uuid::parse_str(&id)?;
@@ -117,16 +392,64 @@ pub async fn connection_connect(
 
     tracing::info!(id = %uuid, name = %profile.name, "Connecting");
 
-    manager.connect(profile).await
+    let schedule = profile.backup.clone().filter(|b| b.enabled);
+    let summary = manager.connect(profile).await?;
+    supervisor.start(uuid, app_handle.clone()).await;
+
+    if let Some(schedule) = schedule {
+        let throttle = manager.get_throttle(&uuid).await?;
+        backups
+            .start(
+                uuid,
+                throttle,
+                summary.redis_version.clone(),
+                schedule,
+                app_handle.clone(),
+            )
+            .await;
+    }
+
+    let drift = manager.get_config_drift(&uuid).await?;
+    if !drift.is_empty() {
+        tracing::warn!(id = %uuid, count = drift.len(), "Server deviates from profile baseline");
+        let event = ConfigDriftEvent {
+            connection_id: id,
+            drift,
+        };
+        if let Err(e) = app_handle.emit("connection:drift", &event) {
+            tracing::warn!(error = %e, "Failed to emit drift event");
+        }
+    }
+
+    Ok(summary)
 }
 
 /// Disconnect from a Redis server.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn connection_disconnect(
     id: String,
     manager: State<'_, ConnectionManager>,
+    backups: State<'_, BackupScheduler>,
+    journal: State<'_, UndoJournal>,
+    supervisor: State<'_, ConnectionSupervisor>,
+    key_index: State<'_, KeyIndex>,
+    keyspace_watcher: State<'_, KeyspaceWatcher>,
+    slow_log_tracker: State<'_, SlowLogTracker>,
+    prober: State<'_, LatencyProber>,
+    push: State<'_, PushChannels>,
+    tracking: State<'_, TrackingSessions>,
 ) -> Result<(), AppError> {
     let uuid = Uuid::parse_str(&id)?;
+    supervisor.stop(&uuid.to_string()).await;
+    prober.stop(&uuid).await;
+    push.close(&uuid).await;
+    tracking.stop(&uuid).await;
+    keyspace_watcher.stop(&uuid).await;
+    slow_log_tracker.forget(&uuid).await;
+    backups.stop(&uuid.to_string()).await;
+    journal.clear(&uuid.to_string()).await;
+    key_index.clear(&uuid).await;
     manager.disconnect(&uuid).await;
     Ok(())
 }
@@ -141,6 +464,291 @@ pub async fn connection_state(
     Ok(manager.get_state(&uuid).await)
 }
 
+/// PING a connection every `interval_ms` (default 1s) to track rolling
+/// min/avg/p99 round-trip times and lost probes. Changes in the derived
+/// quality are emitted as `connection:quality`. Replaces a running probe.
+#[tauri::command]
+pub async fn connection_start_latency_probe(
+    id: String,
+    interval_ms: Option<u64>,
+    manager: State<'_, ConnectionManager>,
+    prober: State<'_, LatencyProber>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    prober
+        .start(
+            uuid,
+            pool,
+            latency_probe::probe_interval(interval_ms),
+            app_handle,
+        )
+        .await;
+    Ok(())
+}
+
+/// Stop the latency probe. Returns whether one was running.
+#[tauri::command]
+pub async fn connection_stop_latency_probe(
+    id: String,
+    prober: State<'_, LatencyProber>,
+) -> Result<bool, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    Ok(prober.stop(&uuid).await)
+}
+
+/// Rolling latency figures, or null if no probe is running.
+#[tauri::command]
+pub async fn connection_latency_stats(
+    id: String,
+    prober: State<'_, LatencyProber>,
+) -> Result<Option<LatencyStats>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    Ok(prober.stats(&uuid).await)
+}
+
+/// Open a dedicated RESP3 connection whose server push messages
+/// (invalidations, Pub/Sub messages) are emitted as `connection:push`.
+/// Replaces a channel already open. The profile must use RESP3.
+#[tauri::command]
+pub async fn connection_open_push_channel(
+    id: String,
+    manager: State<'_, ConnectionManager>,
+    push: State<'_, PushChannels>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    if manager.get_profile(&uuid).await?.protocol != RespProtocol::Resp3 {
+        return Err(AppError::InvalidInput(
+            "Push messages need RESP3; enable it in the connection profile".into(),
+        ));
+    }
+    let url = manager.get_connection_url(&uuid).await?;
+    push.open(uuid, &url, app_handle).await
+}
+
+/// Run a command on the push channel, e.g. `CLIENT TRACKING ON` or
+/// `SUBSCRIBE orders`, so the pushes it causes arrive as events.
+///
+/// Mutating commands are rejected on read-only connections, commands the
+/// profile's policy blocks are refused, and dangerous commands are refused
+/// here; run those from the CLI.
+#[tauri::command]
+pub async fn connection_push_execute(
+    id: String,
+    command: String,
+    manager: State<'_, ConnectionManager>,
+    push: State<'_, PushChannels>,
+    audit: State<'_, AuditLog>,
+) -> Result<CommandResult, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let args = parser::parse_command(&command);
+    write_guard::check_command(manager.is_readonly(&uuid).await?, &args)?;
+    manager.get_command_catalog(&uuid).await?.validate(&args)?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    if let Some(warning) = command_policy::check(&policy, &args)? {
+        return Err(AppError::InvalidInput(format!(
+            "{} Run it from the CLI instead.",
+            warning.message
+        )));
+    }
+    let result = push.execute(&uuid, &args).await?;
+
+    if write_guard::is_write_command(&args) {
+        let name = args[0].to_uppercase();
+        audit
+            .record(AuditEntry::new(
+                &id,
+                AuditSource::Cli,
+                &name,
+                None,
+                args[1..].to_vec(),
+            ))
+            .await;
+    }
+    Ok(result)
+}
+
+/// Close the push channel. Returns whether one was open.
+#[tauri::command]
+pub async fn connection_close_push_channel(
+    id: String,
+    push: State<'_, PushChannels>,
+) -> Result<bool, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    Ok(push.close(&uuid).await)
+}
+
+/// Make editor changes and bulk key operations on this connection wait
+/// until `concern.replicas` replicas acknowledged them (WAIT), or stop
+/// waiting with null. Each such write then emits `connection:write-ack`
+/// with the number of replicas that confirmed it. Kept until disconnect.
+#[tauri::command]
+pub async fn connection_set_write_concern(
+    id: String,
+    concern: Option<WriteConcern>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    if let Some(concern) = &concern {
+        concern.validate()?;
+    }
+    manager.set_write_concern(&uuid, concern).await?;
+    tracing::info!(id = %uuid, concern = ?concern, "Write concern updated");
+    Ok(())
+}
+
+/// The write concern set on a connection, or null.
+#[tauri::command]
+pub async fn connection_write_concern(
+    id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Option<WriteConcern>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    Ok(manager.write_concern(&uuid).await)
+}
+
+/// Get the capabilities (modules, version-gated features) of a connected server.
+#[tauri::command]
+pub async fn connection_capabilities(
+    id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<ServerCapabilities, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    manager.get_capabilities(&uuid).await
+}
+
+/// Get deviations from the profile's baseline found when connecting.
+#[tauri::command]
+pub async fn connection_config_drift(
+    id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Vec<ConfigDrift>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    manager.get_config_drift(&uuid).await
+}
+
+/// List every logical database with its key count, marking the profile's own.
+///
+/// Any of them can then be targeted through the `db` argument of browser and
+/// CLI commands without editing the profile.
+#[tauri::command]
+pub async fn connection_list_databases(
+    id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Vec<DatabaseSummary>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let active = manager.get_profile(&uuid).await?.database;
+    databases::list_databases(&pool, active).await
+}
+
+/// Delete every key in a database (the profile's own when `db` is null).
+///
+/// Without `confirmation_token` nothing is deleted: the returned pending
+/// confirmation carries the current DBSIZE and its token. Returns the number
+/// of keys the database held. Refused on read-only profiles and when the
+/// profile's command policy blocks FLUSHDB; no confirmation is asked when
+/// it allows it.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn connection_flush_db(
+    id: String,
+    db: Option<u8>,
+    async_flush: Option<bool>,
+    confirmation_token: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    key_index: State<'_, KeyIndex>,
+    audit: State<'_, AuditLog>,
+) -> Result<Confirmable<u64>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let pool = manager.get_write_pool_for_db(&uuid, db).await?;
+    let target = match db {
+        Some(db) => db,
+        None => manager.get_profile(&uuid).await?.database,
+    };
+    let async_flush = async_flush.unwrap_or(false);
+
+    let policy = manager.get_command_policy(&uuid).await?;
+    if command_policy::check(&policy, &["FLUSHDB".to_string()])?.is_some() {
+        let op = DestructiveOp::FlushDb {
+            db: target,
+            async_flush,
+        };
+        if let Some(confirmation) = confirmations
+            .gate(&pool, &id, op, confirmation_token.as_deref())
+            .await?
+        {
+            return Ok(Confirmable::Pending {
+                confirmation: Box::new(confirmation),
+            });
+        }
+    }
+
+    let keys = databases::db_size(&pool).await?;
+    databases::flush_db(&pool, async_flush).await?;
+    key_index.remove_matching(uuid, db, "*").await;
+    audit
+        .record(AuditEntry::new(
+            &id,
+            AuditSource::Server,
+            "flushDb",
+            None,
+            vec![target.to_string(), format!("keys={keys}")],
+        ))
+        .await;
+    tracing::warn!(connection_id = %id, db = target, keys = keys, async_flush = async_flush, "Database flushed");
+    Ok(Confirmable::Done { result: keys })
+}
+
+/// Delete every key in every database. Confirmation-gated and subject to
+/// the command policy like `connection_flush_db`; returns the number of
+/// keys the server held.
+#[tauri::command]
+pub async fn connection_flush_all(
+    id: String,
+    async_flush: Option<bool>,
+    confirmation_token: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    key_index: State<'_, KeyIndex>,
+    audit: State<'_, AuditLog>,
+) -> Result<Confirmable<u64>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    let async_flush = async_flush.unwrap_or(false);
+
+    let policy = manager.get_command_policy(&uuid).await?;
+    if command_policy::check(&policy, &["FLUSHALL".to_string()])?.is_some() {
+        let op = DestructiveOp::FlushAll { async_flush };
+        if let Some(confirmation) = confirmations
+            .gate(&pool, &id, op, confirmation_token.as_deref())
+            .await?
+        {
+            return Ok(Confirmable::Pending {
+                confirmation: Box::new(confirmation),
+            });
+        }
+    }
+
+    let keys = databases::total_keys(&pool).await?;
+    databases::flush_all(&pool, async_flush).await?;
+    key_index.clear(&uuid).await;
+    audit
+        .record(AuditEntry::new(
+            &id,
+            AuditSource::Server,
+            "flushAll",
+            None,
+            vec![format!("keys={keys}")],
+        ))
+        .await;
+    tracing::warn!(connection_id = %id, keys = keys, async_flush = async_flush, "All databases flushed");
+    Ok(Confirmable::Done { result: keys })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,483 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::{Emitter, Manager, State};
+use uuid::Uuid;
+
+use crate::config::settings_store;
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::confirm::model::{Confirmable, DestructiveOp};
+use crate::redis::confirm::registry::ConfirmationRegistry;
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::{durability, write_guard};
+use crate::redis::devtools::benchmark::{self, Benchmarks};
+use crate::redis::devtools::generator::{self, KeyTemplate};
+use crate::redis::devtools::locks;
+use crate::redis::devtools::model::{
+    BenchFigures, BenchSpec, GeneratorSpec, LockScan, ReplayFinished, ReplayReport, ReplaySpec,
+    TrackingStats, MAX_LOCK_RESULTS,
+};
+use crate::redis::devtools::replay;
+use crate::redis::devtools::tracking::TrackingSessions;
+use crate::utils::errors::AppError;
+use crate::utils::progress::{ProgressEvent, ProgressPhase};
+use crate::utils::tasks::TaskRegistry;
+
+/// Start writing synthetic keys, e.g. to demo the app or load-test the key
+/// browser against a realistic keyspace. Returns the generation ID.
+///
+/// Keys are written in pipelines in the background; progress arrives as
+/// `task:progress` events with the generation ID as task ID (the last one
+/// `done` or `failed`), and `devtools_cancel_generate` (or `task_cancel`)
+/// stops it. Existing
+/// keys with generated names are overwritten.
+#[tauri::command]
+pub async fn devtools_generate(
+    connection_id: String,
+    spec: GeneratorSpec,
+    db: Option<u8>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<String, AppError> {
+    spec.validate()?;
+    KeyTemplate::parse(&spec.key_template)?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool_for_db(&uuid, db).await?;
+
+    let generation_id = Uuid::new_v4().to_string();
+    let mut args = vec![
+        spec.key_type.as_str().to_string(),
+        spec.count.to_string(),
+        spec.key_template.clone(),
+    ];
+    args.extend(db.map(|db| format!("db={db}")));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::DevTools,
+            "generateData",
+            None,
+            args,
+        ))
+        .await;
+
+    let id = generation_id.clone();
+    let total = spec.count;
+    let task_handle = app_handle.clone();
+    let task_connection = connection_id.clone();
+    tasks
+        .spawn(
+            &task_handle,
+            generation_id.clone(),
+            "generate",
+            Some(&task_connection),
+            move |_| async move {
+                let progress = |written: u64| {
+                    ProgressEvent::new(
+                        &id,
+                        "generate",
+                        Some(&connection_id),
+                        ProgressPhase::Writing,
+                    )
+                    .processed(written, Some(total))
+                };
+                let mut reached = 0;
+                let outcome = generator::generate(&pool, &spec, |written| {
+                    reached = written;
+                    progress(written).emit(&app_handle);
+                })
+                .await;
+                let error = outcome.as_ref().err().map(|e| {
+                    tracing::warn!(generation_id = %id, "Data generation failed: {e}");
+                    e.to_string()
+                });
+                progress(reached).finished(error).emit(&app_handle);
+                tracing::info!(generation_id = %id, written = reached, "Data generation finished");
+                outcome.map(|_| ())
+            },
+        )
+        .await?;
+    Ok(generation_id)
+}
+
+/// Stop a running generation; keys already written stay. Returns whether
+/// it was still running.
+#[tauri::command]
+pub async fn devtools_cancel_generate(
+    generation_id: String,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<bool, AppError> {
+    Ok(tasks.cancel(&generation_id).await)
+}
+
+/// Start a benchmark: `clients` connections send the weighted command mix
+/// in pipelines for `duration_secs`. Returns the benchmark ID.
+///
+/// Ops/sec and latency percentiles arrive about once a second as
+/// `devtools:bench-figures`, and progress as `task:progress` with the
+/// benchmark ID as task ID (the last one with phase `done` or `failed`).
+/// Commands only touch keys under `redislens:bench:`, which are deleted
+/// when the run ends. Workloads with writes are refused on read-only
+/// connections.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn devtools_benchmark(
+    connection_id: String,
+    spec: BenchSpec,
+    db: Option<u8>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    benchmarks: State<'_, Benchmarks>,
+    audit: State<'_, AuditLog>,
+) -> Result<String, AppError> {
+    spec.validate()?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    if spec.writes() && manager.is_readonly(&uuid).await? {
+        return Err(write_guard::readonly_error());
+    }
+    let url = manager.get_connection_url(&uuid).await?;
+    let conns = benchmark::connect(&url, spec.clients, db).await?;
+
+    let benchmark_id = Uuid::new_v4().to_string();
+    if spec.writes() {
+        let mut args = vec![
+            format!("clients={}", spec.clients),
+            format!("pipeline={}", spec.pipeline),
+            format!("duration={}s", spec.duration_secs),
+        ];
+        args.extend(db.map(|db| format!("db={db}")));
+        audit
+            .record(AuditEntry::new(
+                &connection_id,
+                AuditSource::DevTools,
+                "benchmark",
+                None,
+                args,
+            ))
+            .await;
+    }
+
+    let stop = benchmarks.register(&benchmark_id).await;
+    let ids = (benchmark_id.clone(), connection_id);
+    let duration_ms = u64::from(spec.duration_secs) * 1_000;
+    tauri::async_runtime::spawn(async move {
+        let id = ids.0.clone();
+        let progress = |figures: &BenchFigures| {
+            ProgressEvent::new(
+                &figures.benchmark_id,
+                "benchmark",
+                Some(&figures.connection_id),
+                ProgressPhase::Processing,
+            )
+            .processed(figures.elapsed_ms, Some(duration_ms))
+        };
+        let ops_per_sec =
+            |figures: &BenchFigures| Some(format!("{:.0} ops/sec", figures.ops_per_sec));
+        let emit = |figures: &BenchFigures| {
+            let _ = app_handle.emit("devtools:bench-figures", figures);
+            progress(figures)
+                .message(ops_per_sec(figures))
+                .emit(&app_handle);
+        };
+        let (report, error) = benchmark::run(conns, spec, stop, ids, emit).await;
+        let _ = app_handle.emit("devtools:bench-figures", &report);
+        match error {
+            Some(error) => progress(&report).finished(Some(error)),
+            None => progress(&report)
+                .finished(None)
+                .message(ops_per_sec(&report)),
+        }
+        .emit(&app_handle);
+        app_handle.state::<Benchmarks>().finish(&id).await;
+        tracing::info!(
+            benchmark_id = %id,
+            ops = report.total_ops,
+            ops_per_sec = report.ops_per_sec,
+            p99_us = report.latency.p99_us,
+            "Benchmark finished"
+        );
+    });
+    Ok(benchmark_id)
+}
+
+/// Stop a running benchmark early. It still reports its figures and
+/// deletes its keys. Returns whether it was running.
+#[tauri::command]
+pub async fn devtools_stop_benchmark(
+    benchmark_id: String,
+    benchmarks: State<'_, Benchmarks>,
+) -> Result<bool, AppError> {
+    Ok(benchmarks.stop(&benchmark_id).await)
+}
+
+/// Check a command file against a connection without sending anything.
+///
+/// The report lists the commands a replay would refuse: dangerous and
+/// blocking commands, those the profile's command policy blocks or wants
+/// confirmed, writes on a read-only connection, and unknown
+/// commands or wrong argument counts.
+#[tauri::command]
+pub async fn devtools_validate_replay(
+    connection_id: String,
+    spec: ReplaySpec,
+    manager: State<'_, ConnectionManager>,
+) -> Result<ReplayReport, AppError> {
+    spec.validate()?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let file = replay::read_file(&spec.path, spec.format).await?;
+    let catalog = manager.get_command_catalog(&uuid).await?;
+    let readonly = manager.is_readonly(&uuid).await?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    let (_, report) = replay::check(&file.commands, readonly, &policy, &catalog, file.truncated);
+    Ok(report)
+}
+
+/// Replay a command file (an AOF tail or a command log) against a
+/// connection, e.g. to reproduce production traffic on a staging server.
+/// Returns the replay ID.
+///
+/// Commands are checked as by `devtools_validate_replay` and refused ones
+/// skipped. The rest are sent in order on a dedicated connection, so
+/// SELECTs in the file apply, at most `rate_per_sec` a second. Progress
+/// arrives as `task:progress` with the replay ID as task ID, and the report
+/// with per-command errors as `devtools:replay-report` when it ends.
+/// `devtools_cancel_replay` (or `task_cancel`) stops it.
+#[tauri::command]
+pub async fn devtools_replay(
+    connection_id: String,
+    spec: ReplaySpec,
+    db: Option<u8>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<String, AppError> {
+    spec.validate()?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let file = replay::read_file(&spec.path, spec.format).await?;
+    let catalog = manager.get_command_catalog(&uuid).await?;
+    let readonly = manager.is_readonly(&uuid).await?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    let url = manager.get_connection_url(&uuid).await?;
+    let mut conn = benchmark::connect(&url, 1, db)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Connection("Replay connection failed".into()))?;
+
+    let replay_id = Uuid::new_v4().to_string();
+    let mut args = vec![
+        spec.path.clone(),
+        format!("commands={}", file.commands.len()),
+    ];
+    args.extend(spec.rate_per_sec.map(|rate| format!("rate={rate}/s")));
+    args.extend(db.map(|db| format!("db={db}")));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::DevTools,
+            "replay",
+            None,
+            args,
+        ))
+        .await;
+
+    let id = replay_id.clone();
+    let task_handle = app_handle.clone();
+    let task_connection = connection_id.clone();
+    tasks
+        .spawn(
+            &task_handle,
+            replay_id.clone(),
+            "replay",
+            Some(&task_connection),
+            move |_| async move {
+                let (runnable, mut report) =
+                    replay::check(&file.commands, readonly, &policy, &catalog, file.truncated);
+                let total = runnable.len() as u64;
+                let progress = |report: &ReplayReport| {
+                    ProgressEvent::new(&id, "replay", Some(&connection_id), ProgressPhase::Writing)
+                        .processed(report.sent + report.failed, Some(total))
+                        .message((report.failed > 0).then(|| format!("{} failed", report.failed)))
+                };
+                let outcome = replay::run(&mut conn, &runnable, &spec, &mut report, |report| {
+                    progress(report).emit(&app_handle);
+                })
+                .await;
+                let error = outcome.as_ref().err().map(|e| {
+                    tracing::warn!(replay_id = %id, "Replay stopped: {e}");
+                    e.to_string()
+                });
+                let finished = ReplayFinished {
+                    replay_id: id.clone(),
+                    connection_id: connection_id.clone(),
+                    report: report.clone(),
+                };
+                let _ = app_handle.emit("devtools:replay-report", &finished);
+                progress(&report).finished(error).emit(&app_handle);
+                tracing::info!(
+                    replay_id = %id,
+                    sent = report.sent,
+                    failed = report.failed,
+                    rejected = report.rejected,
+                    "Replay finished"
+                );
+                outcome
+            },
+        )
+        .await?;
+    Ok(replay_id)
+}
+
+/// Stop a running replay; commands already sent stay applied. Returns
+/// whether it was still running.
+#[tauri::command]
+pub async fn devtools_cancel_replay(
+    replay_id: String,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<bool, AppError> {
+    Ok(tasks.cancel(&replay_id).await)
+}
+
+/// Start an experimental client-side caching session: a dedicated RESP3
+/// connection enables CLIENT TRACKING in broadcast mode for `prefixes`
+/// (every key when empty) and counts the invalidations it receives per key.
+/// Replaces a session already running. Needs Redis 6+.
+#[tauri::command]
+pub async fn devtools_start_tracking(
+    connection_id: String,
+    prefixes: Option<Vec<String>>,
+    manager: State<'_, ConnectionManager>,
+    sessions: State<'_, TrackingSessions>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let url = manager.get_connection_url(&uuid).await?;
+    sessions
+        .start(uuid, &url, prefixes.unwrap_or_default())
+        .await
+}
+
+/// Invalidation figures of the running session with its `limit` (default
+/// 20) most-invalidated keys, or null if none is running.
+#[tauri::command]
+pub async fn devtools_tracking_stats(
+    connection_id: String,
+    limit: Option<usize>,
+    sessions: State<'_, TrackingSessions>,
+) -> Result<Option<TrackingStats>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(sessions.stats(&uuid, limit).await)
+}
+
+/// End the tracking session and return its final figures, or null if none
+/// was running.
+#[tauri::command]
+pub async fn devtools_stop_tracking(
+    connection_id: String,
+    sessions: State<'_, TrackingSessions>,
+) -> Result<Option<TrackingStats>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(sessions.stop(&uuid).await)
+}
+
+/// Find lock keys matching `patterns` (default: the `lockPatterns` setting)
+/// and show each one's owner token, TTL and idle time. With `lease_ms`, the
+/// TTL locks are taken with, each lock's age is shown too. Stops after
+/// `limit` keys (default 1000).
+#[tauri::command]
+pub async fn devtools_scan_locks(
+    connection_id: String,
+    patterns: Option<Vec<String>>,
+    lease_ms: Option<u64>,
+    limit: Option<u32>,
+    manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<LockScan, AppError> {
+    let patterns = match patterns {
+        Some(patterns) => patterns,
+        None => {
+            settings_store::load_settings(&app_handle)
+                .await?
+                .lock_patterns
+        }
+    };
+    if patterns.is_empty() || patterns.iter().any(String::is_empty) {
+        return Err(AppError::InvalidInput(
+            "Lock patterns must not be empty".into(),
+        ));
+    }
+    let limit = limit.unwrap_or(1000);
+    if !(1..=MAX_LOCK_RESULTS).contains(&limit) {
+        return Err(AppError::InvalidInput(format!(
+            "Limit must be between 1 and {MAX_LOCK_RESULTS}"
+        )));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    locks::scan_locks(&pool, &patterns, lease_ms, limit).await
+}
+
+/// Force-release a lock by deleting its key. With `owner`, the lock is only
+/// deleted while that token still holds it, so a lock taken over in the
+/// meantime survives. Returns whether it was deleted.
+///
+/// Without `confirmation_token` nothing is deleted: the returned pending
+/// confirmation says who holds the lock.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn devtools_release_lock(
+    connection_id: String,
+    key: String,
+    owner: Option<String>,
+    confirmation_token: Option<String>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<Confirmable<bool>, AppError> {
+    if key.is_empty() {
+        return Err(AppError::InvalidInput("Key must not be empty".into()));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+
+    let op = DestructiveOp::ReleaseLock {
+        key: key.clone(),
+        owner: owner.clone(),
+    };
+    if let Some(confirmation) = confirmations
+        .gate(&pool, &connection_id, op, confirmation_token.as_deref())
+        .await?
+    {
+        return Ok(Confirmable::Pending {
+            confirmation: Box::new(confirmation),
+        });
+    }
+
+    let released = locks::release_lock(&pool, &key, owner.as_deref()).await?;
+    let mut args = vec![format!("released={released}")];
+    args.extend(owner.map(|owner| format!("owner={owner}")));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::DevTools,
+            "releaseLock",
+            Some(&key),
+            args,
+        ))
+        .await;
+    if released {
+        durability::acknowledge(
+            &app_handle,
+            &manager,
+            &connection_id,
+            None,
+            "releaseLock",
+            Some(&key),
+        )
+        .await;
+    }
+    tracing::info!(connection_id = %connection_id, key = %key, released, "Lock force-released");
+    Ok(Confirmable::Done { result: released })
+}
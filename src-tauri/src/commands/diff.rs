@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::diff::config;
+use crate::redis::diff::model::{
+    ConfigDiffReport, ConfigSnapshot, ConfigSource, DiffOptions, DiffReport,
+};
+use crate::redis::diff::reader::{self, DiffTarget};
+use crate::utils::errors::AppError;
+
+/// Compare a key, or every key matching a pattern, between two
+/// connections (or two databases of one connection).
+///
+/// Reports keys missing on either side, type mismatches, differing hash
+/// fields, set/zset members, scores, list elements and stream entries, and
+/// TTLs further apart than the tolerance. Large values are compared by size
+/// only.
+#[tauri::command]
+pub async fn diff_keys(
+    left_connection_id: String,
+    right_connection_id: String,
+    options: DiffOptions,
+    manager: State<'_, ConnectionManager>,
+) -> Result<DiffReport, AppError> {
+    let left_id = Uuid::parse_str(&left_connection_id)?;
+    let right_id = Uuid::parse_str(&right_connection_id)?;
+    if left_id == right_id && options.left_db == options.right_db {
+        return Err(AppError::InvalidInput(
+            "Pick two different connections or databases to compare".into(),
+        ));
+    }
+    let target = DiffTarget::from_options(&options)?;
+    let left = manager.get_pool_for_db(&left_id, options.left_db).await?;
+    let right = manager.get_pool_for_db(&right_id, options.right_db).await?;
+
+    let mut report = reader::diff(&left, &right, &target, options.ttl_tolerance_ms).await?;
+    report.left_connection_id = left_connection_id;
+    report.right_connection_id = right_connection_id;
+    tracing::info!(
+        left = %left_id,
+        right = %right_id,
+        compared = report.keys_compared,
+        differing = report.diffs.len(),
+        "Keys compared"
+    );
+    Ok(report)
+}
+
+/// Capture a connection's `CONFIG GET *`, with its version and replication
+/// role, as a snapshot that can be saved as a baseline file. Password
+/// parameters are redacted.
+#[tauri::command]
+pub async fn diff_capture_config(
+    connection_id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<ConfigSnapshot, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    config::capture(&pool, &connection_id).await
+}
+
+/// Compare the configuration of two servers, or of a server and a saved
+/// snapshot. Differing parameters are ranked by severity, with divergences
+/// that change eviction or durability (such as `maxmemory-policy`) first.
+#[tauri::command]
+pub async fn diff_config(
+    left: ConfigSource,
+    right: ConfigSource,
+    manager: State<'_, ConnectionManager>,
+) -> Result<ConfigDiffReport, AppError> {
+    let left = resolve_config(left, &manager).await?;
+    let right = resolve_config(right, &manager).await?;
+    let report = config::compare(left, right);
+    tracing::info!(
+        differing = report.differences.len(),
+        dangerous = report.dangerous,
+        "Configs compared"
+    );
+    Ok(report)
+}
+
+async fn resolve_config(
+    source: ConfigSource,
+    manager: &ConnectionManager,
+) -> Result<ConfigSnapshot, AppError> {
+    match source {
+        ConfigSource::Connection { connection_id } => {
+            let uuid = Uuid::parse_str(&connection_id)?;
+            let pool = manager.get_pool(&uuid).await?;
+            config::capture(&pool, &connection_id).await
+        }
+        ConfigSource::Snapshot { snapshot } => Ok(*snapshot),
+    }
+}
@@ -1,17 +1,39 @@
 // SPDX-License-Identifier: MIT
 
-use tauri::State;
+use base64::Engine;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Emitter, State};
 use uuid::Uuid;
 
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::browser::invalidation;
+use crate::redis::confirm::model::{Confirmable, DestructiveOp};
+use crate::redis::confirm::registry::ConfirmationRegistry;
+use crate::redis::connection::capabilities::parse_version;
+use crate::redis::connection::durability;
 use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::editor::expiry_watch::{ExpiryWatch, ExpiryWatcher};
+use crate::redis::editor::key_watch::{KeyFingerprint, KeyWatchOptions, KeyWatcher};
 use crate::redis::editor::model::{
-    BitmapInfo, GeoMember, HashField, HashScanResult, HllInfo, JsonValue, ListElement,
-    SetScanResult, StreamInfo, StreamRangeResult, StringValue, TtlInfo, ZSetMember, ZSetScanResult,
+    BinaryEncoding, BitfieldOp, BitfieldOverflow, BitmapInfo, CollectionItem, ExpectedValue,
+    ExpireCondition, Expiry, FullValue, GeoMember, GeoSearchQuery, GeoSearchResult, HashField,
+    HashFieldValue, HashScanResult, HllInfo, JsonPatch, JsonValue, KeySearchResult, ListElement,
+    SetScanResult, StreamInfo, StreamRangeResult, StreamTrimStrategy, StringValue, TtlInfo,
+    UndoEntry, ValueExportFormat, ValueExportReport, ValueSearchMode, ZRangeBy, ZRangeQuery,
+    ZSetBulkAddReport, ZSetMember, ZSetRank, ZSetScanResult,
 };
+use crate::redis::editor::string_stream::{StringChunk, StringStreamInfo};
+use crate::redis::editor::undo::{self, UndoJournal};
+use crate::redis::editor::write_queue::{KeyQueueDepth, KeyWriteQueue};
 use crate::redis::editor::{
-    hash_ops, list_ops, set_ops, special_ops, stream_ops, string_ops, ttl_ops, zset_ops,
+    cas, hash_ops, json_patch, list_ops, search_ops, set_ops, special_ops, stream_ops, string_ops,
+    string_stream, ttl_ops, value_export, value_stream, zset_ops,
 };
+use crate::utils::chunked::{self, ChunkSender, StreamChunk};
 use crate::utils::errors::AppError;
+use crate::utils::progress::ProgressPhase;
+use crate::utils::tasks::TaskRegistry;
 
 // ---------------------------------------------------------------------------
 // String commands
@@ -32,21 +54,105 @@ pub async fn editor_get_string_value(
 }
 
 /// Set a string value, optionally with a TTL.
+///
+/// With `expected`, the write only happens if the value still matches what
+/// the editor read, and fails with `Conflict` otherwise.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_set_string_value(
     connection_id: String,
     key: String,
     value: String,
     ttl: Option<i64>,
+    expected: Option<ExpectedValue>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
-    string_ops::set_string_value(&pool, &key, &value, ttl).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    match &expected {
+        Some(expected) => cas::set_string_value(&pool, &key, &value, ttl, expected).await?,
+        None => string_ops::set_string_value(&pool, &key, &value, ttl).await?,
+    }
+    journal
+        .record(&connection_id, &key, "setStringValue", prior)
+        .await;
+    let mut args = vec![format!("bytes={}", value.len())];
+    args.extend(ttl.map(|t| t.to_string()));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setStringValue",
+            Some(&key),
+            args,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "setStringValue",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, "String value saved");
     Ok(())
 }
 
+/// Set a string to raw bytes given as base64 or hex, optionally with a TTL,
+/// so binary values round-trip without UTF-8 conversion.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_set_string_binary(
+    connection_id: String,
+    key: String,
+    payload: String,
+    encoding: BinaryEncoding,
+    ttl: Option<i64>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<(), AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let bytes = encoding.decode(&payload)?;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    string_ops::set_string_bytes(&pool, &key, &bytes, ttl).await?;
+    journal
+        .record(&connection_id, &key, "setStringBinary", prior)
+        .await;
+    let mut args = vec![format!("bytes={}", bytes.len())];
+    args.extend(ttl.map(|t| t.to_string()));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setStringBinary",
+            Some(&key),
+            args,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "setStringBinary",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, bytes = bytes.len(), "Binary string value saved");
+    Ok(())
+}
+
 /// Get a substring of a string value (for large strings).
 #[tauri::command]
 pub async fn editor_get_string_range(
@@ -61,10 +167,221 @@ pub async fn editor_get_string_range(
     string_ops::get_string_range(&pool, &key, start, end).await
 }
 
+/// Add `delta` to an integer string (negative to decrement) and return the
+/// new value.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_incr_string(
+    connection_id: String,
+    key: String,
+    delta: i64,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<i64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let value = string_ops::incr_string(&pool, &key, delta).await?;
+    journal
+        .record(&connection_id, &key, "incrString", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "incrString",
+            Some(&key),
+            vec![delta.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "incrString", &key).await;
+    tracing::info!(connection_id = %connection_id, key = %key, delta = delta, "String incremented");
+    Ok(value)
+}
+
+/// Add `delta` to a floating-point string and return the new value.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_incr_string_float(
+    connection_id: String,
+    key: String,
+    delta: f64,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<f64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    if !delta.is_finite() {
+        return Err(AppError::InvalidInput(
+            "Increment must be a finite number".into(),
+        ));
+    }
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let value = string_ops::incr_string_float(&pool, &key, delta).await?;
+    journal
+        .record(&connection_id, &key, "incrStringFloat", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "incrStringFloat",
+            Some(&key),
+            vec![delta.to_string()],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "incrStringFloat",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, delta = delta, "String incremented");
+    Ok(value)
+}
+
+/// Append to a string value and return the new length.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_append_string(
+    connection_id: String,
+    key: String,
+    value: String,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let length = string_ops::append_string(&pool, &key, &value).await?;
+    journal
+        .record(&connection_id, &key, "appendString", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "appendString",
+            Some(&key),
+            vec![format!("bytes={}", value.len())],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "appendString", &key).await;
+    tracing::info!(connection_id = %connection_id, key = %key, length = length, "String appended");
+    Ok(length)
+}
+
+/// Overwrite part of a string at `offset` (SETRANGE) and return the new
+/// length, for patching large strings without rewriting them.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_set_string_range(
+    connection_id: String,
+    key: String,
+    offset: u64,
+    value: String,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let length = string_ops::set_string_range(&pool, &key, offset, value.as_bytes()).await?;
+    journal
+        .record(&connection_id, &key, "setStringRange", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setStringRange",
+            Some(&key),
+            vec![offset.to_string(), format!("bytes={}", value.len())],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "setStringRange",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, offset = offset, "String range set");
+    Ok(length)
+}
+
+/// Overwrite part of a string at `offset` with raw bytes given as base64 or
+/// hex, and return the new length.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_set_string_range_binary(
+    connection_id: String,
+    key: String,
+    offset: u64,
+    payload: String,
+    encoding: BinaryEncoding,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let bytes = encoding.decode(&payload)?;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let length = string_ops::set_string_range(&pool, &key, offset, &bytes).await?;
+    journal
+        .record(&connection_id, &key, "setStringRangeBinary", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setStringRangeBinary",
+            Some(&key),
+            vec![offset.to_string(), format!("bytes={}", bytes.len())],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "setStringRangeBinary",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, offset = offset, "Binary string range set");
+    Ok(length)
+}
+
 // ---------------------------------------------------------------------------
 // Hash commands
 // ---------------------------------------------------------------------------
 
+/// Most fields `editor_sample_hash_fields` returns.
+const MAX_HASH_SAMPLE: u32 = 1000;
+
 /// Get all fields of a hash (suitable for small hashes).
 #[tauri::command]
 pub async fn editor_get_hash_all(
@@ -92,37 +409,248 @@ pub async fn editor_scan_hash_fields(
     hash_ops::scan_hash_fields(&pool, &key, cursor, &pattern, count).await
 }
 
-/// Set a single hash field.
+/// Set a single hash field; with `expected`, only if it still holds that
+/// value (`Conflict` otherwise).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_set_hash_field(
     connection_id: String,
     key: String,
     field: String,
     value: String,
+    expected: Option<ExpectedValue>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<bool, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
-    let created = hash_ops::set_hash_field(&pool, &key, &field, &value).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let created = match &expected {
+        Some(expected) => {
+            cas::set_hash_field(&pool, &key, &field, &value, expected).await?;
+            false
+        }
+        None => hash_ops::set_hash_field(&pool, &key, &field, &value).await?,
+    };
+    journal
+        .record(&connection_id, &key, "setHashField", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setHashField",
+            Some(&key),
+            vec![field.clone(), value],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "setHashField", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, field = %field, "Hash field set");
     Ok(created)
 }
 
 /// Delete one or more hash fields.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_delete_hash_fields(
     connection_id: String,
     key: String,
     fields: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let count = hash_ops::delete_hash_fields(&pool, &key, &fields).await?;
+    journal
+        .record(&connection_id, &key, "deleteHashFields", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "deleteHashFields",
+            Some(&key),
+            fields,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "deleteHashFields",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, deleted = count, "Hash fields deleted");
     Ok(count)
 }
 
+/// Set many hash fields in one call. Returns the number of fields created.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_set_hash_fields(
+    connection_id: String,
+    key: String,
+    fields: Vec<HashField>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    if fields.is_empty() {
+        return Err(AppError::InvalidInput(
+            "At least one field must be provided".into(),
+        ));
+    }
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let created = hash_ops::set_hash_fields(&pool, &key, &fields).await?;
+    journal
+        .record(&connection_id, &key, "setHashFields", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setHashFields",
+            Some(&key),
+            fields.into_iter().map(|f| f.field).collect(),
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "setHashFields", &key).await;
+    tracing::info!(connection_id = %connection_id, key = %key, created = created, "Hash fields set");
+    Ok(created)
+}
+
+/// Get the values of selected hash fields; missing fields have a null value.
+#[tauri::command]
+pub async fn editor_get_hash_fields(
+    connection_id: String,
+    key: String,
+    fields: Vec<String>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Vec<HashFieldValue>, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    hash_ops::get_hash_fields(&pool, &key, &fields).await
+}
+
+/// Increment an integer hash field by `delta` and return the new value.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_incr_hash_field(
+    connection_id: String,
+    key: String,
+    field: String,
+    delta: i64,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<i64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let value = hash_ops::incr_hash_field(&pool, &key, &field, delta).await?;
+    journal
+        .record(&connection_id, &key, "incrHashField", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "incrHashField",
+            Some(&key),
+            vec![field.clone(), delta.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "incrHashField", &key).await;
+    tracing::info!(connection_id = %connection_id, key = %key, field = %field, "Hash field incremented");
+    Ok(value)
+}
+
+/// Increment a floating-point hash field by `delta` and return the new value.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_incr_hash_field_float(
+    connection_id: String,
+    key: String,
+    field: String,
+    delta: f64,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<f64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    if !delta.is_finite() {
+        return Err(AppError::InvalidInput(
+            "Increment must be a finite number".into(),
+        ));
+    }
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let value = hash_ops::incr_hash_field_float(&pool, &key, &field, delta).await?;
+    journal
+        .record(&connection_id, &key, "incrHashFieldFloat", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "incrHashFieldFloat",
+            Some(&key),
+            vec![field.clone(), delta.to_string()],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "incrHashFieldFloat",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, field = %field, "Hash field incremented");
+    Ok(value)
+}
+
+/// Sample up to `count` random fields with values (HRANDFIELD), to preview
+/// hashes too large to load in full.
+#[tauri::command]
+pub async fn editor_sample_hash_fields(
+    connection_id: String,
+    key: String,
+    count: u32,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Vec<HashField>, AppError> {
+    validate_key(&key)?;
+    if !(1..=MAX_HASH_SAMPLE).contains(&count) {
+        return Err(AppError::InvalidInput(format!(
+            "Sample size must be between 1 and {MAX_HASH_SAMPLE}"
+        )));
+    }
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    hash_ops::random_hash_fields(&pool, &key, count).await
+}
+
 // ---------------------------------------------------------------------------
 // List commands
 // ---------------------------------------------------------------------------
@@ -143,52 +671,313 @@ pub async fn editor_get_list_range(
 
 /// Push an element to the head or tail of a list.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_push_list_element(
     connection_id: String,
     key: String,
     value: String,
     head: bool,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let new_len = list_ops::push_list_element(&pool, &key, &value, head).await?;
+    journal
+        .record(&connection_id, &key, "pushListElement", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "pushListElement",
+            Some(&key),
+            vec![value, if head { "head" } else { "tail" }.into()],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "pushListElement",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, head = head, "List element pushed");
     Ok(new_len)
 }
 
-/// Set the value of a list element at a specific index.
+/// Set the value of a list element at a specific index; with `expected`,
+/// only if it still holds that value (`Conflict` otherwise).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_set_list_element(
     connection_id: String,
     key: String,
     index: i64,
     value: String,
+    expected: Option<ExpectedValue>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
-    list_ops::set_list_element(&pool, &key, index, &value).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    match &expected {
+        Some(expected) => cas::set_list_element(&pool, &key, index, &value, expected).await?,
+        None => list_ops::set_list_element(&pool, &key, index, &value).await?,
+    }
+    journal
+        .record(&connection_id, &key, "setListElement", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setListElement",
+            Some(&key),
+            vec![index.to_string(), value],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "setListElement",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, index = index, "List element set");
     Ok(())
 }
 
 /// Remove elements from a list by value.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_remove_list_element(
     connection_id: String,
     key: String,
     count: i64,
     value: String,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let removed = list_ops::remove_list_element(&pool, &key, count, &value).await?;
+    journal
+        .record(&connection_id, &key, "removeListElement", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "removeListElement",
+            Some(&key),
+            vec![count.to_string(), value],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "removeListElement",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, removed = removed, "List elements removed");
     Ok(removed)
 }
 
+/// Insert an element before or after the first occurrence of `pivot`.
+/// Returns the new list length.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_insert_list_element(
+    connection_id: String,
+    key: String,
+    pivot: String,
+    value: String,
+    before: bool,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let new_len = list_ops::insert_list_element(&pool, &key, &pivot, &value, before).await?;
+    journal
+        .record(&connection_id, &key, "insertListElement", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "insertListElement",
+            Some(&key),
+            vec![if before { "before" } else { "after" }.into(), pivot, value],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "insertListElement",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, before = before, "List element inserted");
+    Ok(new_len)
+}
+
+/// Delete the list element at `index` atomically and return its value.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_delete_list_index(
+    connection_id: String,
+    key: String,
+    index: i64,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<String, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let removed = list_ops::delete_list_index(&pool, &key, index).await?;
+    journal
+        .record(&connection_id, &key, "deleteListIndex", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "deleteListIndex",
+            Some(&key),
+            vec![index.to_string()],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "deleteListIndex",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, index = index, "List element deleted at index");
+    Ok(removed)
+}
+
+/// Move the list element at `from` to index `to` atomically (drag to
+/// reorder) and return its value. With `expected`, only if the element at
+/// `from` still holds that value (`Conflict` otherwise).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_move_list_element(
+    connection_id: String,
+    key: String,
+    from: i64,
+    to: i64,
+    expected: Option<ExpectedValue>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<String, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let moved = list_ops::move_list_element(&pool, &key, from, to, expected.as_ref()).await?;
+    journal
+        .record(&connection_id, &key, "moveListElement", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "moveListElement",
+            Some(&key),
+            vec![from.to_string(), to.to_string()],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "moveListElement",
+        &key,
+    )
+    .await;
+    tracing::info!(connection_id = %connection_id, key = %key, from = from, to = to, "List element moved");
+    Ok(moved)
+}
+
+/// Search inside one key's value: hash fields, set and sorted set members,
+/// list elements, or stream field names and values, by `mode` (substring
+/// by default). Matches come with their position (list index, sorted set
+/// rank, stream ID) so the editor can jump to them.
+#[tauri::command]
+pub async fn editor_search_in_key(
+    connection_id: String,
+    key: String,
+    query: String,
+    mode: Option<ValueSearchMode>,
+    limit: Option<u32>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<KeySearchResult, AppError> {
+    validate_key(&key)?;
+    let limit = limit.unwrap_or(search_ops::DEFAULT_MATCH_LIMIT);
+    if limit == 0 || limit > search_ops::MAX_MATCH_LIMIT {
+        return Err(AppError::InvalidInput(format!(
+            "Match limit must be between 1 and {}",
+            search_ops::MAX_MATCH_LIMIT
+        )));
+    }
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    search_ops::search_in_key(&pool, &key, &query, mode.unwrap_or_default(), limit).await
+}
+
+/// Find the indexes of elements equal to `element` (LPOS). `count` defaults
+/// to 1; pass 0 for every match.
+#[tauri::command]
+pub async fn editor_list_positions(
+    connection_id: String,
+    key: String,
+    element: String,
+    rank: Option<i64>,
+    count: Option<u64>,
+    max_len: Option<u64>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Vec<i64>, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    list_ops::list_positions(&pool, &key, &element, rank, count.unwrap_or(1), max_len).await
+}
+
 // ---------------------------------------------------------------------------
 // Set commands
 // ---------------------------------------------------------------------------
@@ -222,30 +1011,77 @@ pub async fn editor_scan_set_members(
 
 /// Add one or more members to a set.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_add_set_members(
     connection_id: String,
     key: String,
     members: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let added = set_ops::add_set_members(&pool, &key, &members).await?;
+    journal
+        .record(&connection_id, &key, "addSetMembers", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "addSetMembers",
+            Some(&key),
+            members,
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "addSetMembers", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, added = added, "Set members added");
     Ok(added)
 }
 
 /// Remove one or more members from a set.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_remove_set_members(
     connection_id: String,
     key: String,
     members: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let removed = set_ops::remove_set_members(&pool, &key, &members).await?;
+    journal
+        .record(&connection_id, &key, "removeSetMembers", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "removeSetMembers",
+            Some(&key),
+            members,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "removeSetMembers",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, removed = removed, "Set members removed");
     Ok(removed)
 }
@@ -268,34 +1104,122 @@ pub async fn editor_get_ttl(
 
 /// Set TTL on a key (in seconds).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_set_ttl(
     connection_id: String,
     key: String,
     seconds: i64,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<bool, AppError> {
     validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
     if seconds <= 0 {
         return Err(AppError::InvalidInput(
             "TTL must be a positive number of seconds".into(),
         ));
     }
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let result = ttl_ops::set_key_ttl(&pool, &key, seconds).await?;
+    journal.record(&connection_id, &key, "setTtl", prior).await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setTtl",
+            Some(&key),
+            vec![seconds.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "setTtl", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, seconds = seconds, "TTL set");
     Ok(result)
 }
 
+/// Set a key's expiry in seconds or milliseconds from now, or at a Unix
+/// timestamp (EXPIRE, PEXPIRE, EXPIREAT, PEXPIREAT). `condition` (NX, XX,
+/// GT, LT) needs Redis 7. Returns false if the condition kept the expiry.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_set_expiry(
+    connection_id: String,
+    key: String,
+    expiry: Expiry,
+    condition: Option<ExpireCondition>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<bool, AppError> {
+    validate_key(&key)?;
+    ttl_ops::validate_expiry(expiry, chrono::Utc::now())?;
+    if condition.is_some() {
+        let uuid = Uuid::parse_str(&connection_id)?;
+        let version = manager.get_capabilities(&uuid).await?.redis_version;
+        if parse_version(&version) < (7, 0, 0) {
+            return Err(AppError::InvalidInput(format!(
+                "NX/XX/GT/LT expiry conditions need Redis 7.0 or later (server is {version})"
+            )));
+        }
+    }
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let result = ttl_ops::set_key_expiry(&pool, &key, expiry, condition).await?;
+    journal
+        .record(&connection_id, &key, "setExpiry", prior)
+        .await;
+    let (command, value) = ttl_ops::expiry_command(expiry);
+    let mut args = vec![command.to_string(), value.to_string()];
+    args.extend(condition.map(|c| ttl_ops::condition_arg(c).to_string()));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setExpiry",
+            Some(&key),
+            args,
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "setExpiry", &key).await;
+    tracing::info!(connection_id = %connection_id, key = %key, ?expiry, ?condition, "Expiry set");
+    Ok(result)
+}
+
 /// Remove TTL from a key, making it persistent.
 #[tauri::command]
 pub async fn editor_persist_key(
     connection_id: String,
     key: String,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<bool, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let result = ttl_ops::persist_key(&pool, &key).await?;
+    journal
+        .record(&connection_id, &key, "persistKey", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "persistKey",
+            Some(&key),
+            vec![],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "persistKey", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, "Key persisted (TTL removed)");
     Ok(result)
 }
@@ -318,6 +1242,49 @@ pub async fn editor_get_zset_range(
     zset_ops::get_zset_range(&pool, &key, start, stop).await
 }
 
+/// Get sorted set members by score or lex range, optionally reversed and
+/// paged with `offset`/`count`.
+#[tauri::command]
+pub async fn editor_get_zset_range_by(
+    connection_id: String,
+    key: String,
+    query: ZRangeQuery,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Vec<ZSetMember>, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    zset_ops::get_zset_range_by(&pool, &key, &query).await
+}
+
+/// Look up a member's rank (ZRANK/ZREVRANK) and score. Returns null if the
+/// member isn't in the set.
+#[tauri::command]
+pub async fn editor_get_zset_member_rank(
+    connection_id: String,
+    key: String,
+    member: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<Option<ZSetRank>, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    zset_ops::get_zset_member_rank(&pool, &key, &member).await
+}
+
+/// Count members within a score or lex range.
+#[tauri::command]
+pub async fn editor_zset_count(
+    connection_id: String,
+    key: String,
+    by: ZRangeBy,
+    min: String,
+    max: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    zset_ops::zset_count(&pool, &key, by, &min, &max).await
+}
+
 /// Scan sorted set members with ZSCAN (for large sorted sets).
 #[tauri::command]
 pub async fn editor_scan_zset_members(
@@ -335,47 +1302,178 @@ pub async fn editor_scan_zset_members(
 
 /// Add or update a member in a sorted set.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_add_zset_member(
     connection_id: String,
     key: String,
     member: String,
     score: f64,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let added = zset_ops::add_zset_member(&pool, &key, &member, score).await?;
+    journal
+        .record(&connection_id, &key, "addZsetMember", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "addZsetMember",
+            Some(&key),
+            vec![member.clone(), score.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "addZsetMember", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, member = %member, "ZSet member added");
     Ok(added)
 }
 
 /// Remove one or more members from a sorted set.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_remove_zset_members(
     connection_id: String,
     key: String,
     members: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let removed = zset_ops::remove_zset_members(&pool, &key, &members).await?;
+    journal
+        .record(&connection_id, &key, "removeZsetMembers", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "removeZsetMembers",
+            Some(&key),
+            members,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "removeZsetMembers",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, removed = removed, "ZSet members removed");
     Ok(removed)
 }
 
+/// Add or update many sorted set members at once, from `members` or from
+/// pasted `score member` lines in `text` (exactly one of the two).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_add_zset_members_bulk(
+    connection_id: String,
+    key: String,
+    members: Option<Vec<ZSetMember>>,
+    text: Option<String>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<ZSetBulkAddReport, AppError> {
+    validate_key(&key)?;
+    let members = match (members, text) {
+        (Some(members), None) => members,
+        (None, Some(text)) => zset_ops::parse_score_lines(&text)?,
+        _ => {
+            return Err(AppError::InvalidInput(
+                "Provide either members or pasted text".into(),
+            ))
+        }
+    };
+    if members.is_empty() {
+        return Err(AppError::InvalidInput("No members to add".into()));
+    }
+
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let report = zset_ops::add_zset_members_bulk(&pool, &key, &members).await?;
+    journal
+        .record(&connection_id, &key, "addZsetMembersBulk", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "addZsetMembersBulk",
+            Some(&key),
+            vec![report.total.to_string()],
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "addZsetMembersBulk",
+        &key,
+    )
+    .await;
+    tracing::info!(
+        connection_id = %connection_id,
+        key = %key,
+        added = report.added,
+        updated = report.updated,
+        "ZSet members bulk added"
+    );
+    Ok(report)
+}
+
 /// Increment a member's score by a delta.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_incr_zset_score(
     connection_id: String,
     key: String,
     member: String,
     delta: f64,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<f64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
-    zset_ops::incr_zset_score(&pool, &key, &member, delta).await
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let score = zset_ops::incr_zset_score(&pool, &key, &member, delta).await?;
+    journal
+        .record(&connection_id, &key, "incrZsetScore", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "incrZsetScore",
+            Some(&key),
+            vec![member, delta.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "incrZsetScore", &key).await;
+    Ok(score)
 }
 
 /// Get the cardinality of a sorted set.
@@ -426,35 +1524,152 @@ pub async fn editor_get_stream_range_rev(
 
 /// Add an entry to a stream.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_add_stream_entry(
     connection_id: String,
     key: String,
     id: String,
     fields: Vec<(String, String)>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<String, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let entry_id = stream_ops::add_stream_entry(&pool, &key, &id, &fields).await?;
+    journal
+        .record(&connection_id, &key, "addStreamEntry", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "addStreamEntry",
+            Some(&key),
+            fields.into_iter().flat_map(|(f, v)| [f, v]).collect(),
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "addStreamEntry",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, entry_id = %entry_id, "Stream entry added");
     Ok(entry_id)
 }
 
 /// Delete entries from a stream.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_delete_stream_entries(
     connection_id: String,
     key: String,
     ids: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let deleted = stream_ops::delete_stream_entries(&pool, &key, &ids).await?;
+    journal
+        .record(&connection_id, &key, "deleteStreamEntries", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "deleteStreamEntries",
+            Some(&key),
+            ids,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "deleteStreamEntries",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, deleted = deleted, "Stream entries deleted");
     Ok(deleted)
 }
 
+/// Trim a stream by MAXLEN or MINID.
+///
+/// Without `confirmation_token` nothing is trimmed: the returned pending
+/// confirmation carries the number of entries that would be removed.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_trim_stream(
+    connection_id: String,
+    key: String,
+    strategy: StreamTrimStrategy,
+    threshold: String,
+    approximate: bool,
+    confirmation_token: Option<String>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<Confirmable<u64>, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    if threshold.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Trim threshold must not be empty".into(),
+        ));
+    }
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+
+    let op = DestructiveOp::StreamTrim {
+        key: key.clone(),
+        strategy,
+        threshold: threshold.clone(),
+        approximate,
+    };
+    if let Some(confirmation) = confirmations
+        .gate(&pool, &connection_id, op, confirmation_token.as_deref())
+        .await?
+    {
+        return Ok(Confirmable::Pending {
+            confirmation: Box::new(confirmation),
+        });
+    }
+
+    let prior = undo::capture(&pool, &key).await;
+    let removed = stream_ops::trim_stream(&pool, &key, strategy, &threshold, approximate).await?;
+    journal
+        .record(&connection_id, &key, "trimStream", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "trimStream",
+            Some(&key),
+            vec![format!("{strategy:?}"), threshold],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "trimStream", &key).await;
+    tracing::info!(connection_id = %connection_id, key = %key, removed = removed, "Stream trimmed");
+    Ok(Confirmable::Done { result: removed })
+}
+
 /// Get stream info including consumer groups.
 #[tauri::command]
 pub async fn editor_get_stream_info(
@@ -486,21 +1701,111 @@ pub async fn editor_get_json_value(
 
 /// Set a JSON value (uses `RedisJSON` module or plain SET).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_set_json_value(
     connection_id: String,
     key: String,
     path: String,
     value: String,
     use_module: bool,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     special_ops::set_json_value(&pool, &key, &path, &value, use_module).await?;
+    journal
+        .record(&connection_id, &key, "setJsonValue", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setJsonValue",
+            Some(&key),
+            vec![path, value],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "setJsonValue", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, "JSON value set");
     Ok(())
 }
 
+/// Diff the stored `RedisJSON` document against an edited version, as the
+/// path operations `editor_patch_json_value` would send. Writes nothing.
+#[tauri::command]
+pub async fn editor_diff_json_value(
+    connection_id: String,
+    key: String,
+    value: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<JsonPatch, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    json_patch::diff_json(&pool, &key, &value).await
+}
+
+/// Save an edited `RedisJSON` document as minimal JSON.SET/JSON.DEL path
+/// operations instead of rewriting it whole, and return what was sent.
+///
+/// With `base`, the document the edit started from, a document changed on
+/// the server meanwhile is a `Conflict` and nothing is written.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_patch_json_value(
+    connection_id: String,
+    key: String,
+    value: String,
+    base: Option<String>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<JsonPatch, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
+    let patch = json_patch::patch_json(&pool, &key, &value, base.as_deref()).await?;
+    if patch.ops.is_empty() {
+        return Ok(patch);
+    }
+    journal
+        .record(&connection_id, &key, "patchJsonValue", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "patchJsonValue",
+            Some(&key),
+            patch.ops.iter().map(|op| op.path.clone()).collect(),
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "patchJsonValue",
+        &key,
+    )
+    .await;
+    tracing::info!(
+        connection_id = %connection_id,
+        key = %key,
+        ops = patch.ops.len(),
+        full_rewrite = patch.full_rewrite,
+        "JSON value patched"
+    );
+    Ok(patch)
+}
+
 // ---------------------------------------------------------------------------
 // HyperLogLog commands
 // ---------------------------------------------------------------------------
@@ -519,15 +1824,42 @@ pub async fn editor_get_hll_info(
 
 /// Add elements to a `HyperLogLog`.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_add_hll_elements(
     connection_id: String,
     key: String,
     elements: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<bool, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let changed = special_ops::add_hll_elements(&pool, &key, &elements).await?;
+    journal
+        .record(&connection_id, &key, "addHllElements", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "addHllElements",
+            Some(&key),
+            elements,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "addHllElements",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, "HLL elements added");
     Ok(changed)
 }
@@ -552,20 +1884,87 @@ pub async fn editor_get_bitmap_info(
 
 /// Set a single bit in a bitmap.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_set_bitmap_bit(
     connection_id: String,
     key: String,
     offset: u64,
     value: u8,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u8, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let old = special_ops::set_bitmap_bit(&pool, &key, offset, value).await?;
+    journal
+        .record(&connection_id, &key, "setBitmapBit", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "setBitmapBit",
+            Some(&key),
+            vec![offset.to_string(), value.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "setBitmapBit", &key).await;
     tracing::debug!(connection_id = %connection_id, key = %key, offset = offset, "Bit set");
     Ok(old)
 }
 
+/// Run BITFIELD GET/SET/INCRBY operations on packed integer fields.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_bitfield_ops(
+    connection_id: String,
+    key: String,
+    ops: Vec<BitfieldOp>,
+    overflow: Option<BitfieldOverflow>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<Vec<Option<i64>>, AppError> {
+    validate_key(&key)?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let writes = ops.iter().any(|op| !matches!(op, BitfieldOp::Get { .. }));
+    let pool = if writes {
+        resolve_write_pool(&connection_id, &manager).await?
+    } else {
+        resolve_pool(&connection_id, &manager).await?
+    };
+    let prior = if writes {
+        Some(undo::capture(&pool, &key).await)
+    } else {
+        None
+    };
+    let results = special_ops::bitfield_ops(&pool, &key, &ops, overflow).await?;
+    if let Some(prior) = prior {
+        journal
+            .record(&connection_id, &key, "bitfieldOps", prior)
+            .await;
+        audit
+            .record(AuditEntry::new(
+                &connection_id,
+                AuditSource::Editor,
+                "bitfieldOps",
+                Some(&key),
+                ops.iter().map(|op| format!("{op:?}")).collect(),
+            ))
+            .await;
+        acknowledge(&app_handle, &manager, &connection_id, "bitfieldOps", &key).await;
+        tracing::info!(connection_id = %connection_id, key = %key, ops = ops.len(), "Bitfield modified");
+    }
+    Ok(results)
+}
+
 // ---------------------------------------------------------------------------
 // Geospatial commands
 // ---------------------------------------------------------------------------
@@ -584,17 +1983,37 @@ pub async fn editor_get_geo_members(
 
 /// Add a geospatial member.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_add_geo_member(
     connection_id: String,
     key: String,
     longitude: f64,
     latitude: f64,
     member: String,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let added = special_ops::add_geo_member(&pool, &key, longitude, latitude, &member).await?;
+    journal
+        .record(&connection_id, &key, "addGeoMember", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "addGeoMember",
+            Some(&key),
+            vec![member.clone(), longitude.to_string(), latitude.to_string()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "addGeoMember", &key).await;
     tracing::info!(connection_id = %connection_id, key = %key, member = %member, "Geo member added");
     Ok(added)
 }
@@ -616,19 +2035,461 @@ pub async fn editor_geo_distance(
 
 /// Remove geospatial members.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn editor_remove_geo_members(
     connection_id: String,
     key: String,
     members: Vec<String>,
+    app_handle: AppHandle,
     manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     validate_key(&key)?;
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let _queued = write_queue.acquire(&connection_id, &key).await;
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    let prior = undo::capture(&pool, &key).await;
     let removed = special_ops::remove_geo_members(&pool, &key, &members).await?;
+    journal
+        .record(&connection_id, &key, "removeGeoMembers", prior)
+        .await;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "removeGeoMembers",
+            Some(&key),
+            members,
+        ))
+        .await;
+    acknowledge(
+        &app_handle,
+        &manager,
+        &connection_id,
+        "removeGeoMembers",
+        &key,
+    )
+    .await;
     tracing::info!(connection_id = %connection_id, key = %key, removed = removed, "Geo members removed");
     Ok(removed)
 }
 
+/// Search geospatial members by radius or box around a member or a point.
+#[tauri::command]
+pub async fn editor_geo_search(
+    connection_id: String,
+    key: String,
+    query: GeoSearchQuery,
+    manager: State<'_, ConnectionManager>,
+) -> Result<GeoSearchResult, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    special_ops::geo_search(&pool, &key, &query).await
+}
+
+// ---------------------------------------------------------------------------
+// Streamed reads
+// ---------------------------------------------------------------------------
+
+/// Read every element of a list, hash, set, sorted set or stream key through
+/// `on_chunk`, for values too large to return in one response.
+///
+/// The channel receives `data` chunks, then a `done` marker with the element
+/// count (or `failed`). Returns the count as well.
+#[tauri::command]
+pub async fn editor_stream_value(
+    connection_id: String,
+    key: String,
+    chunk_size: Option<u32>,
+    on_chunk: Channel<StreamChunk<CollectionItem>>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<u64, AppError> {
+    validate_key(&key)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+
+    let mut sender = ChunkSender::new(on_chunk, chunked::chunk_size(chunk_size)?);
+    let outcome = value_stream::stream_value(&pool, &key, &mut sender).await;
+    sender.finish(outcome)
+}
+
+/// Read a whole collection value in one reply (e.g. to copy it), up to
+/// `max_items` elements and `max_bytes` of element text. `truncated` says
+/// whether a limit was hit; `total` is the value's full element count.
+#[tauri::command]
+pub async fn editor_read_full_value(
+    connection_id: String,
+    key: String,
+    max_items: Option<u64>,
+    max_bytes: Option<u64>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<FullValue, AppError> {
+    validate_key(&key)?;
+    let max_items = max_items.unwrap_or(value_stream::DEFAULT_FULL_READ_ITEMS);
+    if !(1..=value_stream::MAX_FULL_READ_ITEMS).contains(&max_items) {
+        return Err(AppError::InvalidInput(format!(
+            "maxItems must be between 1 and {}",
+            value_stream::MAX_FULL_READ_ITEMS
+        )));
+    }
+    let max_bytes = max_bytes.unwrap_or(value_stream::DEFAULT_FULL_READ_BYTES);
+    if !(1..=value_stream::MAX_FULL_READ_BYTES).contains(&max_bytes) {
+        return Err(AppError::InvalidInput(format!(
+            "maxBytes must be between 1 and {}",
+            value_stream::MAX_FULL_READ_BYTES
+        )));
+    }
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    value_stream::read_full_value(&pool, &key, max_items, max_bytes).await
+}
+
+/// Export a list, hash, set or sorted set value to a CSV or JSON file at
+/// `path`, reading it page by page so large collections stay out of memory.
+///
+/// With a `task_id`, the export runs as a task that reports progress and
+/// that `task_cancel` can abort.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_export_key_value(
+    connection_id: String,
+    key: String,
+    path: String,
+    format: ValueExportFormat,
+    task_id: Option<String>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<ValueExportReport, AppError> {
+    validate_key(&key)?;
+    if path.trim().is_empty() {
+        return Err(AppError::InvalidInput("Export path is required".into()));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let throttle = manager.get_throttle(&uuid).await?;
+
+    let target = key.clone();
+    let report = tasks
+        .run(
+            &app_handle,
+            task_id,
+            "valueExport",
+            Some(&connection_id),
+            |reporter| async move {
+                value_export::export_value(
+                    &pool,
+                    &throttle,
+                    &target,
+                    std::path::Path::new(&path),
+                    format,
+                    |rows, total| {
+                        reporter.progress(ProgressPhase::Writing, rows, Some(total), None);
+                    },
+                )
+                .await
+            },
+        )
+        .await?;
+
+    tracing::info!(
+        connection_id = %connection_id,
+        key = %key,
+        rows = report.rows,
+        path = %report.path,
+        "Key value exported"
+    );
+    Ok(report)
+}
+
+/// Stream a string value (or part of it) in GETRANGE chunks, for values too
+/// large to load at once.
+///
+/// Returns right away with the stream ID and STRLEN; chunks then arrive as
+/// `editor:string-chunk` events, ending with one that has `done` set.
+/// `editor_cancel_string_stream` (or `task_cancel` with the stream ID) stops
+/// the stream early.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn editor_stream_string(
+    connection_id: String,
+    key: String,
+    offset: Option<u64>,
+    length: Option<u64>,
+    chunk_bytes: Option<u32>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<StringStreamInfo, AppError> {
+    validate_key(&key)?;
+    let chunk = string_stream::chunk_bytes(chunk_bytes)?;
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    let total_bytes = string_stream::string_length(&pool, &key).await?;
+    let (start, end) = string_stream::byte_range(total_bytes, offset.unwrap_or(0), length);
+    let info = StringStreamInfo {
+        stream_id: Uuid::new_v4().to_string(),
+        total_bytes,
+        start,
+        end,
+    };
+
+    let stream_id = info.stream_id.clone();
+    let task_handle = app_handle.clone();
+    let task_connection = connection_id.clone();
+    tasks
+        .spawn(
+            &task_handle,
+            info.stream_id.clone(),
+            "stringStream",
+            Some(&task_connection),
+            move |_| async move {
+                let emit = |offset: u64, data: &[u8], done: bool, error: Option<String>| {
+                    let chunk = StringChunk {
+                        stream_id: stream_id.clone(),
+                        offset,
+                        data: base64::engine::general_purpose::STANDARD.encode(data),
+                        total_bytes,
+                        done,
+                        error,
+                    };
+                    let _ = app_handle.emit("editor:string-chunk", &chunk);
+                };
+                let mut reached = start;
+                let outcome =
+                    string_stream::stream_string(&pool, &key, (start, end), chunk, |at, bytes| {
+                        reached = at + bytes.len() as u64;
+                        emit(at, &bytes, false, None);
+                    })
+                    .await;
+                let error = outcome.as_ref().err().map(|e| {
+                    tracing::warn!(stream_id = %stream_id, key = %key, "String stream failed: {e}");
+                    e.to_string()
+                });
+                emit(reached, &[], true, error);
+                outcome.map(|_| ())
+            },
+        )
+        .await?;
+    tracing::debug!(connection_id = %connection_id, stream_id = %info.stream_id, bytes = end - start, "String stream started");
+    Ok(info)
+}
+
+/// Stop a running string stream. Returns whether it was still running.
+#[tauri::command]
+pub async fn editor_cancel_string_stream(
+    stream_id: String,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<bool, AppError> {
+    Ok(tasks.cancel(&stream_id).await)
+}
+
+// ---------------------------------------------------------------------------
+// Key watch commands
+// ---------------------------------------------------------------------------
+
+/// Default poll interval for watched keys.
+const DEFAULT_KEY_WATCH_INTERVAL_MS: u64 = 2000;
+
+/// Largest threshold of an expiry watch: a week.
+const MAX_EXPIRY_THRESHOLD_SECS: u64 = 7 * 24 * 3600;
+
+/// Watch a key open in an editor for changes made by other clients, and
+/// return its current fingerprint (`None` if it doesn't exist).
+///
+/// Changes arrive as `editor:key-changed` events. Keys are polled every
+/// `interval_ms`; with `notifications`, keyspace notifications (which must be
+/// enabled on the server) also report changes as they happen. Both options
+/// are taken from the first key watched on a connection.
+#[tauri::command]
+pub async fn editor_watch_key(
+    connection_id: String,
+    key: String,
+    interval_ms: Option<u64>,
+    notifications: Option<bool>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    watcher: State<'_, KeyWatcher>,
+) -> Result<Option<KeyFingerprint>, AppError> {
+    validate_key(&key)?;
+    let interval_ms = interval_ms.unwrap_or(DEFAULT_KEY_WATCH_INTERVAL_MS);
+    if !(250..=60_000).contains(&interval_ms) {
+        return Err(AppError::InvalidInput(
+            "Watch interval must be between 250 and 60000 ms".into(),
+        ));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let notifications = if notifications.unwrap_or(false) {
+        let mut conn = pool.get().await?;
+        invalidation::ensure_notifications(&mut conn, false).await?;
+        drop(conn);
+        Some((
+            manager.get_connection_url(&uuid).await?,
+            manager.get_profile(&uuid).await?.database,
+        ))
+    } else {
+        None
+    };
+    let options = KeyWatchOptions {
+        interval: std::time::Duration::from_millis(interval_ms),
+        notifications,
+    };
+    let fingerprint = watcher
+        .watch(uuid, key.clone(), pool, options, app_handle)
+        .await?;
+    tracing::debug!(connection_id = %connection_id, key = %key, "Key watch added");
+    Ok(fingerprint)
+}
+
+/// Stop watching a key.
+#[tauri::command]
+pub async fn editor_unwatch_key(
+    connection_id: String,
+    key: String,
+    watcher: State<'_, KeyWatcher>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    watcher.unwatch(&uuid, &key).await;
+    Ok(())
+}
+
+/// Keys currently watched on a connection.
+#[tauri::command]
+pub async fn editor_watched_keys(
+    connection_id: String,
+    watcher: State<'_, KeyWatcher>,
+) -> Result<Vec<String>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(watcher.watched(&uuid).await)
+}
+
+/// Add a key to the connection's expiry watch list, or update its entry.
+///
+/// A `key:expiring` event fires when the key's TTL drops below
+/// `threshold_secs` and again when it expires; with `notify`, an OS
+/// notification is shown too.
+#[tauri::command]
+pub async fn editor_watch_expiry(
+    connection_id: String,
+    key: String,
+    threshold_secs: u64,
+    notify: Option<bool>,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    watcher: State<'_, ExpiryWatcher>,
+) -> Result<ExpiryWatch, AppError> {
+    validate_key(&key)?;
+    if !(1..=MAX_EXPIRY_THRESHOLD_SECS).contains(&threshold_secs) {
+        return Err(AppError::InvalidInput(format!(
+            "Expiry threshold must be between 1 and {MAX_EXPIRY_THRESHOLD_SECS} seconds"
+        )));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let watch = watcher
+        .watch(
+            uuid,
+            key.clone(),
+            threshold_secs,
+            notify.unwrap_or(false),
+            pool,
+            app_handle,
+        )
+        .await?;
+    tracing::debug!(connection_id = %connection_id, key = %key, threshold_secs, "Expiry watch added");
+    Ok(watch)
+}
+
+/// Remove a key from the expiry watch list.
+#[tauri::command]
+pub async fn editor_unwatch_expiry(
+    connection_id: String,
+    key: String,
+    watcher: State<'_, ExpiryWatcher>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    watcher.unwatch(&uuid, &key).await;
+    Ok(())
+}
+
+/// The connection's expiry watch list with each key's last-read TTL.
+#[tauri::command]
+pub async fn editor_expiry_watches(
+    connection_id: String,
+    watcher: State<'_, ExpiryWatcher>,
+) -> Result<Vec<ExpiryWatch>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(watcher.watched(&uuid).await)
+}
+
+// ---------------------------------------------------------------------------
+// Undo commands
+// ---------------------------------------------------------------------------
+
+/// Revert the most recent editor change on a connection.
+///
+/// Returns the reverted entry, or `None` if the journal is empty.
+#[tauri::command]
+pub async fn editor_undo_last(
+    connection_id: String,
+    app_handle: AppHandle,
+    manager: State<'_, ConnectionManager>,
+    journal: State<'_, UndoJournal>,
+    write_queue: State<'_, KeyWriteQueue>,
+    audit: State<'_, AuditLog>,
+) -> Result<Option<UndoEntry>, AppError> {
+    let pool = resolve_write_pool(&connection_id, &manager).await?;
+    // Queue behind writes to the key before taking its entry, so none can
+    // land between the two. A write recorded while waiting becomes the
+    // newest entry; start over with it.
+    let (entry, _queued) = loop {
+        let Some(key) = journal.last_key(&connection_id).await else {
+            return Ok(None);
+        };
+        let queued = write_queue.acquire(&connection_id, &key).await;
+        if let Some(entry) = journal.pop_last_if(&connection_id, &key).await {
+            break (entry, queued);
+        }
+    };
+
+    if let Err(e) = undo::restore(&pool, &entry).await {
+        journal.push_back(&connection_id, entry).await;
+        return Err(e);
+    }
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Editor,
+            "undo",
+            Some(&entry.key),
+            vec![entry.operation.clone()],
+        ))
+        .await;
+    acknowledge(&app_handle, &manager, &connection_id, "undo", &entry.key).await;
+    tracing::info!(connection_id = %connection_id, key = %entry.key, operation = %entry.operation, "Editor change undone");
+    Ok(Some(entry))
+}
+
+/// List journaled editor changes for a connection, newest first.
+#[tauri::command]
+pub async fn editor_undo_history(
+    connection_id: String,
+    journal: State<'_, UndoJournal>,
+) -> Result<Vec<UndoEntry>, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    Ok(journal.history(&connection_id).await)
+}
+
+/// Keys of a connection with editor writes running or queued, and how many,
+/// deepest queue first.
+#[tauri::command]
+pub async fn editor_write_queue_depths(
+    connection_id: String,
+    write_queue: State<'_, KeyWriteQueue>,
+) -> Result<Vec<KeyQueueDepth>, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    Ok(write_queue.depths(&connection_id))
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -648,6 +2509,35 @@ async fn resolve_pool(
     manager.get_pool(&uuid).await
 }
 
+/// Like `resolve_pool`, but rejects read-only connections.
+async fn resolve_write_pool(
+    connection_id: &str,
+    manager: &State<'_, ConnectionManager>,
+) -> Result<deadpool_redis::Pool, AppError> {
+    let uuid = Uuid::parse_str(connection_id)?;
+    manager.get_write_pool(&uuid).await
+}
+
+/// After a change to `key`, wait for the connection's write concern, if
+/// one is set, and report it as `connection:write-ack`.
+async fn acknowledge(
+    app_handle: &AppHandle,
+    manager: &State<'_, ConnectionManager>,
+    connection_id: &str,
+    operation: &str,
+    key: &str,
+) {
+    durability::acknowledge(
+        app_handle,
+        manager,
+        connection_id,
+        None,
+        operation,
+        Some(key),
+    )
+    .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
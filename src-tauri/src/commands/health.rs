@@ -1,6 +1,19 @@
 // SPDX-License-Identifier: MIT
 
+use std::time::{Duration, Instant};
+
 use serde::Serialize;
+use uuid::Uuid;
+
+use crate::config::profile_store;
+use crate::redis::connection::model::ConnectionProfile;
+use crate::utils::errors::AppError;
+
+/// Default time allowed for each profile's TCP probe.
+const DEFAULT_PROBE_TIMEOUT_MS: u64 = 2_000;
+
+/// Upper bound on the probe timeout a caller may request.
+const MAX_PROBE_TIMEOUT_MS: u64 = 30_000;
 
 /// Health check response returned to the frontend.
 #[derive(Debug, Serialize)]
@@ -18,3 +31,296 @@ pub fn health_check() -> HealthResponse {
         version: env!("CARGO_PKG_VERSION").to_string(),
     }
 }
+
+/// Outcome of one diagnostic check, ordered from best to worst.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+    Ok,
+    Skipped,
+    Warning,
+    Error,
+}
+
+/// One app-level check on the troubleshooting screen.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Reachability of one saved profile.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileProbe {
+    pub id: Uuid,
+    pub name: String,
+    /// `host:port` that was probed: the SSH host for tunneled profiles.
+    pub target: String,
+    pub status: CheckStatus,
+    pub latency_ms: Option<u64>,
+    pub detail: Option<String>,
+}
+
+/// Structured result of `health_diagnostics`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    pub version: String,
+    /// Worst status across the checks; unreachable profiles count as warnings.
+    pub status: CheckStatus,
+    pub checks: Vec<DiagnosticCheck>,
+    pub profiles: Vec<ProfileProbe>,
+}
+
+/// Run the troubleshooting checks: app data dir writability, the profile
+/// store, how saved secrets are stored, and optionally a TCP probe of every
+/// saved profile.
+#[tauri::command]
+pub async fn health_diagnostics(
+    probe: Option<bool>,
+    timeout_ms: Option<u64>,
+    app_handle: tauri::AppHandle,
+) -> Result<DiagnosticsReport, AppError> {
+    let mut checks = vec![check_data_dir(&app_handle).await];
+
+    let profiles = match profile_store::load_all_profiles(&app_handle).await {
+        Ok(profiles) => {
+            checks.push(DiagnosticCheck::new(
+                "profileStore",
+                CheckStatus::Ok,
+                format!("{} saved profile(s)", profiles.len()),
+            ));
+            profiles
+        }
+        Err(AppError::Locked(_)) => {
+            checks.push(DiagnosticCheck::new(
+                "profileStore",
+                CheckStatus::Warning,
+                "Profile store is encrypted and locked; unlock it to list profiles",
+            ));
+            Vec::new()
+        }
+        Err(e) => {
+            checks.push(DiagnosticCheck::new(
+                "profileStore",
+                CheckStatus::Error,
+                e.to_string(),
+            ));
+            Vec::new()
+        }
+    };
+    checks.push(check_secret_storage(&app_handle).await);
+
+    let timeout = Duration::from_millis(
+        timeout_ms
+            .unwrap_or(DEFAULT_PROBE_TIMEOUT_MS)
+            .clamp(1, MAX_PROBE_TIMEOUT_MS),
+    );
+    let profiles = if probe.unwrap_or(false) {
+        futures::future::join_all(profiles.iter().map(|p| probe_profile(p, timeout))).await
+    } else {
+        profiles.iter().map(skipped_probe).collect()
+    };
+
+    let status = overall_status(&checks, &profiles);
+    tracing::info!(?status, profiles = profiles.len(), "Diagnostics run");
+    Ok(DiagnosticsReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        status,
+        checks,
+        profiles,
+    })
+}
+
+/// Create the app data dir if needed and write and remove a scratch file in it.
+async fn check_data_dir(app_handle: &tauri::AppHandle) -> DiagnosticCheck {
+    use tauri::Manager;
+    const NAME: &str = "dataDir";
+    let dir = match app_handle.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            return DiagnosticCheck::new(
+                NAME,
+                CheckStatus::Error,
+                format!("Failed to resolve app data dir: {e}"),
+            )
+        }
+    };
+    if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+        return DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Error,
+            format!("Failed to create {}: {e}", dir.display()),
+        );
+    }
+    let scratch = dir.join(format!(".write-test-{}", Uuid::new_v4()));
+    if let Err(e) = tokio::fs::write(&scratch, b"ok").await {
+        return DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Error,
+            format!("{} is not writable: {e}", dir.display()),
+        );
+    }
+    let _ = tokio::fs::remove_file(&scratch).await;
+    DiagnosticCheck::new(
+        NAME,
+        CheckStatus::Ok,
+        format!("{} is writable", dir.display()),
+    )
+}
+
+/// Report where saved passwords live. There is no OS keychain integration:
+/// secrets sit in `connections.json`, protected only by encryption at rest.
+async fn check_secret_storage(app_handle: &tauri::AppHandle) -> DiagnosticCheck {
+    const NAME: &str = "secretStorage";
+    match profile_store::store_status(app_handle).await {
+        Ok(status) if status.locked => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            "Saved passwords are encrypted and locked",
+        ),
+        Ok(status) if status.encrypted => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            "Saved passwords are encrypted at rest",
+        ),
+        Ok(_) => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            "Saved passwords are stored in plaintext; enable profile encryption",
+        ),
+        Err(e) => DiagnosticCheck::new(NAME, CheckStatus::Error, e.to_string()),
+    }
+}
+
+/// Host and port a profile's first hop connects to.
+fn probe_target(profile: &ConnectionProfile) -> (String, u16) {
+    match &profile.ssh {
+        Some(ssh) if ssh.enabled => (ssh.host.clone(), ssh.port),
+        _ => (profile.host.clone(), profile.port),
+    }
+}
+
+fn skipped_probe(profile: &ConnectionProfile) -> ProfileProbe {
+    let (host, port) = probe_target(profile);
+    ProfileProbe {
+        id: profile.id,
+        name: profile.name.clone(),
+        target: format!("{host}:{port}"),
+        status: CheckStatus::Skipped,
+        latency_ms: None,
+        detail: None,
+    }
+}
+
+/// Open (and drop) a TCP connection to the profile's first hop.
+async fn probe_profile(profile: &ConnectionProfile, timeout: Duration) -> ProfileProbe {
+    let mut result = skipped_probe(profile);
+    let (host, port) = probe_target(profile);
+    let started = Instant::now();
+    match tokio::time::timeout(
+        timeout,
+        tokio::net::TcpStream::connect((host.as_str(), port)),
+    )
+    .await
+    {
+        Ok(Ok(_)) => {
+            result.status = CheckStatus::Ok;
+            result.latency_ms =
+                Some(u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX));
+        }
+        Ok(Err(e)) => {
+            result.status = CheckStatus::Error;
+            result.detail = Some(e.to_string());
+        }
+        Err(_) => {
+            result.status = CheckStatus::Error;
+            result.detail = Some(format!("No response within {}ms", timeout.as_millis()));
+        }
+    }
+    result
+}
+
+/// Worst status across the checks, with unreachable profiles capped at a warning.
+fn overall_status(checks: &[DiagnosticCheck], profiles: &[ProfileProbe]) -> CheckStatus {
+    let profiles = profiles.iter().map(|p| p.status.min(CheckStatus::Warning));
+    checks
+        .iter()
+        .map(|c| c.status)
+        .chain(profiles)
+        .filter(|s| *s != CheckStatus::Skipped)
+        .max()
+        .unwrap_or(CheckStatus::Ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::connection::model::{SshAuth, SshConfig};
+
+    fn probe(status: CheckStatus) -> ProfileProbe {
+        ProfileProbe {
+            id: Uuid::nil(),
+            name: "p".into(),
+            target: "localhost:6379".into(),
+            status,
+            latency_ms: None,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_overall_status() {
+        let ok = DiagnosticCheck::new("dataDir", CheckStatus::Ok, "");
+        assert_eq!(
+            overall_status(std::slice::from_ref(&ok), &[]),
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            overall_status(std::slice::from_ref(&ok), &[probe(CheckStatus::Skipped)]),
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            overall_status(std::slice::from_ref(&ok), &[probe(CheckStatus::Error)]),
+            CheckStatus::Warning
+        );
+        let failed = DiagnosticCheck::new("profileStore", CheckStatus::Error, "");
+        assert_eq!(
+            overall_status(&[ok, failed], &[probe(CheckStatus::Ok)]),
+            CheckStatus::Error
+        );
+    }
+
+    #[test]
+    fn test_probe_target_uses_ssh_host() {
+        let mut profile = ConnectionProfile::new_standalone("prod".into(), "10.0.0.5".into(), 6380);
+        assert_eq!(probe_target(&profile), ("10.0.0.5".into(), 6380));
+
+        profile.ssh = Some(SshConfig {
+            enabled: true,
+            host: "bastion".into(),
+            port: 2222,
+            username: "ops".into(),
+            auth: SshAuth::Agent,
+            local_port: None,
+            log_path: None,
+        });
+        assert_eq!(probe_target(&profile), ("bastion".into(), 2222));
+
+        profile.ssh.as_mut().unwrap().enabled = false;
+        assert_eq!(probe_target(&profile), ("10.0.0.5".into(), 6380));
+    }
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::config::job_store;
+use crate::redis::jobs::model::{JobRun, ScheduledJob};
+use crate::redis::jobs::scheduler::{self, JobScheduler};
+use crate::utils::errors::AppError;
+
+/// Run history entries returned when the caller does not choose.
+const DEFAULT_RUN_LIMIT: usize = 50;
+
+/// List scheduled jobs, optionally only those of one connection.
+#[tauri::command]
+pub async fn job_list(
+    connection_id: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<ScheduledJob>, AppError> {
+    let mut jobs = job_store::load_jobs(&app_handle).await?;
+    if let Some(connection_id) = connection_id {
+        jobs.retain(|j| j.connection_id == connection_id);
+    }
+    Ok(jobs)
+}
+
+/// Create or update a job and (re)schedule it.
+#[tauri::command]
+pub async fn job_save(
+    job: ScheduledJob,
+    app_handle: tauri::AppHandle,
+    scheduler: State<'_, JobScheduler>,
+) -> Result<ScheduledJob, AppError> {
+    Uuid::parse_str(&job.connection_id)?;
+    job.validate()?;
+    let mut job = job;
+    job.name = job.name.trim().to_string();
+    job.updated_at = chrono::Utc::now();
+    job_store::save_job(&app_handle, &job).await?;
+    scheduler.start(job.clone(), app_handle).await;
+    Ok(job)
+}
+
+/// Delete a job and its run history.
+#[tauri::command]
+pub async fn job_delete(
+    id: String,
+    app_handle: tauri::AppHandle,
+    scheduler: State<'_, JobScheduler>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    scheduler.stop(&uuid).await;
+    job_store::delete_job(&app_handle, &uuid).await
+}
+
+/// Enable or disable a job without changing anything else.
+#[tauri::command]
+pub async fn job_set_enabled(
+    id: String,
+    enabled: bool,
+    app_handle: tauri::AppHandle,
+    scheduler: State<'_, JobScheduler>,
+) -> Result<ScheduledJob, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let mut job = job_store::get_job(&app_handle, &uuid).await?;
+    job.enabled = enabled;
+    job.updated_at = chrono::Utc::now();
+    job_store::save_job(&app_handle, &job).await?;
+    scheduler.start(job.clone(), app_handle).await;
+    Ok(job)
+}
+
+/// Run a job now, outside its schedule. The run is recorded like a
+/// scheduled one; disabled jobs can be run this way too.
+#[tauri::command]
+pub async fn job_run_now(id: String, app_handle: tauri::AppHandle) -> Result<JobRun, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let job = job_store::get_job(&app_handle, &uuid).await?;
+    Ok(scheduler::record_run(&app_handle, &job).await)
+}
+
+/// A job's run history, newest first.
+#[tauri::command]
+pub async fn job_runs(
+    id: String,
+    limit: Option<usize>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<JobRun>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    job_store::list_runs(&app_handle, &uuid, limit.unwrap_or(DEFAULT_RUN_LIMIT)).await
+}
@@ -1,9 +1,21 @@
 // SPDX-License-Identifier: MIT
 
+pub mod audit;
+pub mod backup;
 pub mod browser;
 pub mod cli;
+pub mod confirm;
 pub mod connection;
+pub mod devtools;
+pub mod diff;
 pub mod editor;
 pub mod health;
+pub mod job;
 pub mod monitor;
 pub mod pubsub;
+pub mod queues;
+pub mod settings;
+pub mod snippet;
+pub mod task;
+pub mod template;
+pub mod workspace;
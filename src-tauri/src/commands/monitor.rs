@@ -1,49 +1,266 @@
 // SPDX-License-Identifier: MIT
 
+use std::time::Duration;
+
 use tauri::State;
 use uuid::Uuid;
 
+use crate::config::{alert_store, info_pin_store, settings_store};
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::confirm::model::{Confirmable, DestructiveOp};
+use crate::redis::confirm::registry::ConfirmationRegistry;
+use crate::redis::connection::command_policy;
 use crate::redis::connection::manager::ConnectionManager;
-use crate::redis::monitor::model::{ClientInfo, MemoryStats, SlowLogEntry, StatsSnapshot};
-use crate::redis::monitor::{client_list, info_parser, poller, slow_log};
+use crate::redis::monitor::log_tail::{self, LogTailer};
+use crate::redis::monitor::metrics::{self, MetricsHistory};
+use crate::redis::monitor::model::{
+    AlertRecord, ClientFilter, ClientKillFilter, ClientPage, InfoDiff, InfoSection, MemoryStats,
+    MetricPoint, MetricThresholds, MetricsExportFormat, MonitorSubscription, PersistenceStatus,
+    PinnedInfo, SlowLogDigest, SlowLogEntry, StatsSnapshot, UpgradeReport,
+};
+use crate::redis::monitor::slow_log::SlowLogTracker;
+use crate::redis::monitor::{
+    client_list, info_diff, info_parser, persistence, poller, slow_log, upgrade_advisor,
+};
 use crate::utils::errors::AppError;
+use crate::utils::event_throttle::EventThrottler;
 
 /// Fetch a one-shot server info snapshot (no polling).
 #[tauri::command]
 pub async fn monitor_server_info(
     connection_id: String,
     manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
 ) -> Result<StatsSnapshot, AppError> {
     let pool = resolve_pool(&connection_id, &manager).await?;
+    let thresholds = resolve_thresholds(&connection_id, &manager, &app_handle).await?;
     let mut conn = pool.get().await?;
     let raw: String = redis::cmd("INFO").arg("ALL").query_async(&mut conn).await?;
-    Ok(info_parser::build_snapshot(&raw))
+    Ok(info_parser::build_snapshot(&raw, &thresholds))
 }
 
 /// Start background polling that emits `monitor:stats` events.
+///
+/// Subscribes the default view; see `monitor_subscribe`.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn monitor_start_polling(
     connection_id: String,
     interval_ms: u64,
+    sections: Option<Vec<InfoSection>>,
     manager: State<'_, ConnectionManager>,
     monitor_poller: State<'_, poller::MonitorPoller>,
+    throttler: State<'_, EventThrottler>,
+    history: State<'_, MetricsHistory>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), AppError> {
-    let pool = resolve_pool(&connection_id, &manager).await?;
-    let interval = if interval_ms < 500 { 2000 } else { interval_ms };
-    monitor_poller
-        .start(connection_id, pool, interval, app_handle)
-        .await;
+    monitor_subscribe(
+        connection_id,
+        poller::DEFAULT_SUBSCRIBER.to_string(),
+        interval_ms,
+        sections,
+        manager,
+        monitor_poller,
+        throttler,
+        history,
+        app_handle,
+    )
+    .await?;
     Ok(())
 }
 
-/// Stop background polling for a connection.
+/// Subscribe a view to `monitor:stats` for a connection.
+///
+/// Subscribers of one connection share a single polling task that runs at
+/// the fastest requested interval, fetching every INFO section any of them
+/// asked for (`sections`, default [`InfoSection::LIGHTWEIGHT`]).
+/// Subscribing again with the same ID changes that view's request.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn monitor_subscribe(
+    connection_id: String,
+    subscriber_id: String,
+    interval_ms: u64,
+    sections: Option<Vec<InfoSection>>,
+    manager: State<'_, ConnectionManager>,
+    monitor_poller: State<'_, poller::MonitorPoller>,
+    throttler: State<'_, EventThrottler>,
+    history: State<'_, MetricsHistory>,
+    app_handle: tauri::AppHandle,
+) -> Result<MonitorSubscription, AppError> {
+    if subscriber_id.trim().is_empty() {
+        return Err(AppError::InvalidInput("Subscriber ID is required".into()));
+    }
+    let sections = sections.unwrap_or_else(|| InfoSection::LIGHTWEIGHT.to_vec());
+    if sections.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Select at least one INFO section".into(),
+        ));
+    }
+    let thresholds = resolve_thresholds(&connection_id, &manager, &app_handle).await?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let rules = manager.get_profile(&uuid).await?.alert_rules;
+    let throttle = manager.get_throttle(&uuid).await?;
+    let interval = if interval_ms < 500 { 2000 } else { interval_ms };
+    let source = poller::PollSource {
+        throttle,
+        thresholds,
+        rules,
+        throttler: throttler.inner().clone(),
+        history: history.inner().clone(),
+        app_handle,
+    };
+    Ok(monitor_poller
+        .subscribe(
+            &connection_id,
+            &subscriber_id,
+            Duration::from_millis(interval),
+            sections,
+            source,
+        )
+        .await)
+}
+
+/// Remove a view's subscription. Polling stops with the last subscriber;
+/// returns the remaining shared poller otherwise.
+#[tauri::command]
+pub async fn monitor_unsubscribe(
+    connection_id: String,
+    subscriber_id: String,
+    monitor_poller: State<'_, poller::MonitorPoller>,
+) -> Result<Option<MonitorSubscription>, AppError> {
+    Ok(monitor_poller
+        .unsubscribe(&connection_id, &subscriber_id)
+        .await)
+}
+
+/// List fired alerts for a connection, newest first, with their bundles.
+#[tauri::command]
+pub async fn monitor_alerts_history(
+    connection_id: String,
+    limit: Option<u64>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<AlertRecord>, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    #[allow(clippy::cast_possible_truncation)]
+    let limit = limit.unwrap_or(50) as usize;
+    alert_store::list_alerts(&app_handle, &connection_id, limit).await
+}
+
+/// The metrics recorded by this session's pollers for a connection, oldest
+/// first. Only the most recent points are kept.
+#[tauri::command]
+pub async fn monitor_metrics_history(
+    connection_id: String,
+    history: State<'_, MetricsHistory>,
+) -> Result<Vec<MetricPoint>, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    Ok(history.points(&connection_id).await)
+}
+
+/// Export a connection's metrics history as CSV or JSON text.
+#[tauri::command]
+pub async fn monitor_export_metrics(
+    connection_id: String,
+    format: MetricsExportFormat,
+    history: State<'_, MetricsHistory>,
+) -> Result<String, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    metrics::export(&history.points(&connection_id).await, format)
+}
+
+/// Longest accepted label for a pinned INFO snapshot.
+const MAX_PIN_LABEL_CHARS: usize = 100;
+
+/// Read INFO ALL into a snapshot labeled `label`, without saving it.
+async fn capture_info(
+    connection_id: &str,
+    label: String,
+    manager: &State<'_, ConnectionManager>,
+) -> Result<PinnedInfo, AppError> {
+    let pool = resolve_pool(connection_id, manager).await?;
+    let mut conn = pool.get().await?;
+    let raw: String = redis::cmd("INFO").arg("ALL").query_async(&mut conn).await?;
+    Ok(PinnedInfo {
+        id: Uuid::new_v4(),
+        connection_id: connection_id.to_string(),
+        label,
+        captured_at: chrono::Utc::now(),
+        sections: info_diff::parse_sections(&raw),
+    })
+}
+
+/// Pin the server's INFO ALL under a label such as "before deploy", to
+/// diff against later with `monitor_diff_snapshots`.
+#[tauri::command]
+pub async fn monitor_pin_snapshot(
+    connection_id: String,
+    label: String,
+    manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<PinnedInfo, AppError> {
+    let label = label.trim().to_string();
+    if label.is_empty() || label.chars().count() > MAX_PIN_LABEL_CHARS {
+        return Err(AppError::InvalidInput(format!(
+            "Label must be 1 to {MAX_PIN_LABEL_CHARS} characters"
+        )));
+    }
+    let pin = capture_info(&connection_id, label, &manager).await?;
+    info_pin_store::save_pin(&app_handle, &pin).await?;
+    tracing::info!(connection_id = %connection_id, label = %pin.label, "INFO snapshot pinned");
+    Ok(pin)
+}
+
+/// List a connection's pinned INFO snapshots, oldest first.
+#[tauri::command]
+pub async fn monitor_pinned_snapshots(
+    connection_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<PinnedInfo>, AppError> {
+    Uuid::parse_str(&connection_id)?;
+    info_pin_store::list_pins(&app_handle, &connection_id).await
+}
+
+/// Delete a pinned INFO snapshot.
+#[tauri::command]
+pub async fn monitor_delete_snapshot(
+    snapshot_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let id = Uuid::parse_str(&snapshot_id)?;
+    info_pin_store::delete_pin(&app_handle, &id).await
+}
+
+/// Diff every INFO field of a pinned snapshot against another pinned one,
+/// or against the server's INFO right now when `after_id` is omitted.
+/// Numeric fields get their delta and percentage change.
+#[tauri::command]
+pub async fn monitor_diff_snapshots(
+    connection_id: String,
+    before_id: String,
+    after_id: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<InfoDiff, AppError> {
+    let before = info_pin_store::load_pin(&app_handle, &Uuid::parse_str(&before_id)?).await?;
+    let after = match after_id {
+        Some(id) => info_pin_store::load_pin(&app_handle, &Uuid::parse_str(&id)?).await?,
+        None => capture_info(&connection_id, "now".into(), &manager).await?,
+    };
+    Ok(info_diff::diff(&before, &after))
+}
+
+/// Stop background polling for the default view. Other subscribers keep
+/// polling.
 #[tauri::command]
 pub async fn monitor_stop_polling(
     connection_id: String,
     monitor_poller: State<'_, poller::MonitorPoller>,
 ) -> Result<(), AppError> {
-    monitor_poller.stop(&connection_id).await;
+    monitor_poller
+        .unsubscribe(&connection_id, poller::DEFAULT_SUBSCRIBER)
+        .await;
     Ok(())
 }
 
@@ -59,29 +276,139 @@ pub async fn monitor_slow_log(
     slow_log::get_slow_log(&pool, count).await
 }
 
-/// Fetch the client list (on demand).
+/// Number of entries in the slow log.
+#[tauri::command]
+pub async fn monitor_slow_log_len(
+    connection_id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<u64, AppError> {
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    slow_log::get_slow_log_len(&pool).await
+}
+
+/// Empty the slow log (SLOWLOG RESET); refused on read-only profiles and
+/// when the command policy blocks it.
+#[tauri::command]
+pub async fn monitor_slow_log_reset(
+    connection_id: String,
+    manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    command_policy::check_blocked(&policy, &["SLOWLOG".to_string(), "RESET".to_string()])?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    slow_log::reset_slow_log(&pool).await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Server,
+            "slowlogReset",
+            None,
+            vec![],
+        ))
+        .await;
+    tracing::info!(connection_id = %connection_id, "Slow log reset");
+    Ok(())
+}
+
+/// Slow log grouped by command shape (command + key pattern) with count,
+/// average and maximum duration. With `since_last`, only entries logged
+/// since the previous call for this connection are grouped.
+#[tauri::command]
+pub async fn monitor_slow_log_groups(
+    connection_id: String,
+    count: u64,
+    since_last: Option<bool>,
+    manager: State<'_, ConnectionManager>,
+    tracker: State<'_, SlowLogTracker>,
+) -> Result<SlowLogDigest, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let catalog = manager.get_command_catalog(&uuid).await?;
+    let count = if count == 0 { 128 } else { count };
+    let entries = slow_log::get_slow_log(&pool, count).await?;
+    let length = slow_log::get_slow_log_len(&pool).await?;
+    let entries = tracker
+        .advance(uuid, entries, since_last.unwrap_or(false))
+        .await;
+    Ok(slow_log::digest(&entries, length, &catalog))
+}
+
+/// Fetch a page of the client list (on demand), optionally filtered.
 #[tauri::command]
 pub async fn monitor_client_list(
     connection_id: String,
+    filter: Option<ClientFilter>,
+    offset: Option<usize>,
+    limit: Option<usize>,
     manager: State<'_, ConnectionManager>,
-) -> Result<Vec<ClientInfo>, AppError> {
+) -> Result<ClientPage, AppError> {
     let pool = resolve_pool(&connection_id, &manager).await?;
-    client_list::get_client_list(&pool).await
+    client_list::get_client_page(
+        &pool,
+        &filter.unwrap_or_default(),
+        offset.unwrap_or(0),
+        limit,
+    )
+    .await
 }
 
-/// Kill a connected client by ID.
+/// Kill a connected client by ID; refused on read-only profiles and when
+/// the command policy blocks CLIENT KILL.
 #[tauri::command]
 pub async fn monitor_kill_client(
     connection_id: String,
     client_id: u64,
     manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), AppError> {
-    let pool = resolve_pool(&connection_id, &manager).await?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    command_policy::check_blocked(&policy, &["CLIENT".to_string(), "KILL".to_string()])?;
+    let pool = manager.get_write_pool(&uuid).await?;
     client_list::kill_client(&pool, client_id).await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Server,
+            "killClient",
+            None,
+            vec![client_id.to_string()],
+        ))
+        .await;
     tracing::info!(connection_id = %connection_id, client_id = client_id, "Client killed");
     Ok(())
 }
 
+/// Kill every client matching a CLIENT KILL filter (address, local address,
+/// type, user). Returns the number of clients killed. Refused like
+/// `monitor_kill_client`.
+#[tauri::command]
+pub async fn monitor_kill_clients(
+    connection_id: String,
+    filter: ClientKillFilter,
+    manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    command_policy::check_blocked(&policy, &["CLIENT".to_string(), "KILL".to_string()])?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    let killed = client_list::kill_clients(&pool, &filter).await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Server,
+            "killClients",
+            None,
+            vec![serde_json::to_string(&filter).unwrap_or_default()],
+        ))
+        .await;
+    tracing::info!(connection_id = %connection_id, killed = killed, "Clients killed by filter");
+    Ok(killed)
+}
+
 /// Fetch MEMORY STATS + MEMORY DOCTOR (on demand).
 #[tauri::command]
 pub async fn monitor_memory_stats(
@@ -92,6 +419,156 @@ pub async fn monitor_memory_stats(
     poller::get_memory_stats(&pool).await
 }
 
+/// Build a pre-upgrade checklist from the server's version and configuration.
+///
+/// `target_version` defaults to the latest release the advisor knows about.
+#[tauri::command]
+pub async fn monitor_upgrade_advisor(
+    connection_id: String,
+    target_version: Option<String>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<UpgradeReport, AppError> {
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    upgrade_advisor::build_report(&pool, target_version.as_deref()).await
+}
+
+/// Persistence health: INFO persistence (RDB/AOF status, loading progress)
+/// and LASTSAVE.
+#[tauri::command]
+pub async fn monitor_persistence_status(
+    connection_id: String,
+    manager: State<'_, ConnectionManager>,
+) -> Result<PersistenceStatus, AppError> {
+    let pool = resolve_pool(&connection_id, &manager).await?;
+    persistence::get_status(&pool).await
+}
+
+/// Trigger an RDB snapshot (BGSAVE). Confirmation-gated, since the fork can
+/// stall a large instance; refused on read-only profiles and when the
+/// command policy blocks BGSAVE.
+#[tauri::command]
+pub async fn monitor_bgsave(
+    connection_id: String,
+    schedule: Option<bool>,
+    confirmation_token: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<Confirmable<String>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    command_policy::check_blocked(&policy, &["BGSAVE".to_string()])?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    let schedule = schedule.unwrap_or(false);
+    let op = DestructiveOp::BackgroundSave { schedule };
+    if let Some(confirmation) = confirmations
+        .gate(&pool, &connection_id, op, confirmation_token.as_deref())
+        .await?
+    {
+        return Ok(Confirmable::Pending {
+            confirmation: Box::new(confirmation),
+        });
+    }
+
+    let reply = persistence::background_save(&pool, schedule).await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Server,
+            "bgsave",
+            None,
+            vec![],
+        ))
+        .await;
+    tracing::info!(connection_id = %connection_id, "Background save triggered");
+    Ok(Confirmable::Done { result: reply })
+}
+
+/// Trigger an AOF rewrite (BGREWRITEAOF). Confirmation-gated and subject to
+/// the command policy like `monitor_bgsave`.
+#[tauri::command]
+pub async fn monitor_bgrewriteaof(
+    connection_id: String,
+    confirmation_token: Option<String>,
+    manager: State<'_, ConnectionManager>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<Confirmable<String>, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let policy = manager.get_command_policy(&uuid).await?;
+    command_policy::check_blocked(&policy, &["BGREWRITEAOF".to_string()])?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    if let Some(confirmation) = confirmations
+        .gate(
+            &pool,
+            &connection_id,
+            DestructiveOp::RewriteAof,
+            confirmation_token.as_deref(),
+        )
+        .await?
+    {
+        return Ok(Confirmable::Pending {
+            confirmation: Box::new(confirmation),
+        });
+    }
+
+    let reply = persistence::rewrite_aof(&pool).await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Server,
+            "bgrewriteaof",
+            None,
+            vec![],
+        ))
+        .await;
+    tracing::info!(connection_id = %connection_id, "AOF rewrite triggered");
+    Ok(Confirmable::Done { result: reply })
+}
+
+/// History lines printed when a log tail starts, unless the caller asks.
+const DEFAULT_LOG_BACKLOG: u32 = 200;
+
+/// Start tailing the redis-server log over the profile's SSH connection.
+///
+/// Runs `tail -F` on `log_path`, or the SSH config's log path, and streams
+/// lines as `server:log` events until stopped. Needs an enabled SSH config.
+#[tauri::command]
+pub async fn monitor_start_log_tail(
+    connection_id: String,
+    log_path: Option<String>,
+    backlog: Option<u32>,
+    manager: State<'_, ConnectionManager>,
+    tailer: State<'_, LogTailer>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let Some(ssh) = manager.get_profile(&uuid).await?.ssh.filter(|s| s.enabled) else {
+        return Err(AppError::InvalidInput(
+            "Log tailing needs an SSH configuration on the profile".into(),
+        ));
+    };
+    let Some(path) = log_path.or_else(|| ssh.log_path.clone()) else {
+        return Err(AppError::InvalidInput(
+            "No log path given and none configured for this profile".into(),
+        ));
+    };
+    let command = log_tail::tail_command(&path, backlog.unwrap_or(DEFAULT_LOG_BACKLOG))?;
+    tailer.start(uuid, &ssh, &command, app_handle).await?;
+    tracing::info!(connection_id = %connection_id, path = %path, "Tailing server log");
+    Ok(())
+}
+
+/// Stop tailing the server log. Returns whether a tail was running.
+#[tauri::command]
+pub async fn monitor_stop_log_tail(
+    connection_id: String,
+    tailer: State<'_, LogTailer>,
+) -> Result<bool, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    Ok(tailer.stop(&uuid).await)
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -103,3 +580,15 @@ async fn resolve_pool(
     let uuid = Uuid::parse_str(connection_id)?;
     manager.get_pool(&uuid).await
 }
+
+/// Metric thresholds for a connection: the profile override, else app settings.
+async fn resolve_thresholds(
+    connection_id: &str,
+    manager: &State<'_, ConnectionManager>,
+    app_handle: &tauri::AppHandle,
+) -> Result<MetricThresholds, AppError> {
+    let uuid = Uuid::parse_str(connection_id)?;
+    let profile = manager.get_profile(&uuid).await?;
+    let settings = settings_store::load_settings(app_handle).await?;
+    Ok(settings.thresholds_for(&profile))
+}
@@ -3,36 +3,59 @@
 use tauri::State;
 use uuid::Uuid;
 
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
 use crate::redis::connection::manager::ConnectionManager;
-use crate::redis::pubsub::{discovery, model::ChannelInfo, subscriber::PubSubManager};
+use crate::redis::pubsub::{
+    delivery::DeliveryOptions,
+    discovery,
+    model::{self, ChannelInfo},
+    recording::{self, RecordingInfo},
+    subscriber::PubSubManager,
+};
 use crate::utils::errors::AppError;
 
 /// Subscribe to literal channel names. Returns a subscription ID.
+///
+/// Messages arrive in batches on `pubsub:messages`, paced by `options`
+/// (default: every 100 ms, at most 1000 messages/s).
 #[tauri::command]
 pub async fn pubsub_subscribe(
     connection_id: String,
     channels: Vec<String>,
+    options: Option<DeliveryOptions>,
     manager: State<'_, ConnectionManager>,
     pubsub: State<'_, PubSubManager>,
     app: tauri::AppHandle,
 ) -> Result<String, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
     let uuid = Uuid::parse_str(&connection_id)?;
     let url = manager.get_connection_url(&uuid).await?;
-    pubsub.subscribe(connection_id, url, channels, app).await
+    pubsub
+        .subscribe(connection_id, url, channels, options, app)
+        .await
 }
 
 /// Subscribe to pattern-matched channels. Returns a subscription ID.
+///
+/// Delivery works as for `pubsub_subscribe`.
 #[tauri::command]
 pub async fn pubsub_psubscribe(
     connection_id: String,
     patterns: Vec<String>,
+    options: Option<DeliveryOptions>,
     manager: State<'_, ConnectionManager>,
     pubsub: State<'_, PubSubManager>,
     app: tauri::AppHandle,
 ) -> Result<String, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
     let uuid = Uuid::parse_str(&connection_id)?;
     let url = manager.get_connection_url(&uuid).await?;
-    pubsub.psubscribe(connection_id, url, patterns, app).await
+    pubsub
+        .psubscribe(connection_id, url, patterns, options, app)
+        .await
 }
 
 /// Unsubscribe and tear down a subscription.
@@ -44,6 +67,24 @@ pub async fn pubsub_unsubscribe(
     pubsub.unsubscribe(&subscription_id).await
 }
 
+/// Stop delivering a subscription's messages; they are buffered until resumed.
+#[tauri::command]
+pub async fn pubsub_pause(
+    subscription_id: String,
+    pubsub: State<'_, PubSubManager>,
+) -> Result<(), AppError> {
+    pubsub.set_paused(&subscription_id, true).await
+}
+
+/// Resume delivering a paused subscription's messages.
+#[tauri::command]
+pub async fn pubsub_resume(
+    subscription_id: String,
+    pubsub: State<'_, PubSubManager>,
+) -> Result<(), AppError> {
+    pubsub.set_paused(&subscription_id, false).await
+}
+
 /// Publish a message to a channel (uses the regular pool).
 #[tauri::command]
 pub async fn pubsub_publish(
@@ -51,18 +92,126 @@ pub async fn pubsub_publish(
     channel: String,
     message: String,
     manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
 ) -> Result<u64, AppError> {
     let uuid = Uuid::parse_str(&connection_id)?;
-    let pool = manager.get_pool(&uuid).await?;
+    let pool = manager.get_write_pool(&uuid).await?;
     let mut conn = pool.get().await?;
     let count: u64 = redis::cmd("PUBLISH")
         .arg(&channel)
         .arg(&message)
         .query_async(&mut conn)
         .await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::PubSub,
+            "publish",
+            None,
+            vec![channel, message],
+        ))
+        .await;
+    Ok(count)
+}
+
+/// Publish a binary message given as base64 (uses the regular pool).
+#[tauri::command]
+pub async fn pubsub_publish_binary(
+    connection_id: String,
+    channel: String,
+    payload_base64: String,
+    manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
+) -> Result<u64, AppError> {
+    let payload = model::decode_base64(&payload_base64)?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    let mut conn = pool.get().await?;
+    let count: u64 = redis::cmd("PUBLISH")
+        .arg(&channel)
+        .arg(payload)
+        .query_async(&mut conn)
+        .await?;
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::PubSub,
+            "publishBinary",
+            None,
+            vec![channel, payload_base64],
+        ))
+        .await;
     Ok(count)
 }
 
+/// Start appending a subscription's messages to a new recording file
+/// (newline-delimited JSON, split into parts as it grows).
+#[tauri::command]
+pub async fn pubsub_start_recording(
+    subscription_id: String,
+    pubsub: State<'_, PubSubManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<RecordingInfo, AppError> {
+    let dir = recording::recordings_dir(&app_handle)?;
+    pubsub.start_recording(&subscription_id, dir).await
+}
+
+/// Stop recording a subscription. Returns the recording's final summary.
+#[tauri::command]
+pub async fn pubsub_stop_recording(
+    subscription_id: String,
+    pubsub: State<'_, PubSubManager>,
+) -> Result<RecordingInfo, AppError> {
+    pubsub.stop_recording(&subscription_id).await
+}
+
+/// Republish a recording to its original channels in the background.
+///
+/// Messages keep their recorded spacing divided by `speed` (default 1, the
+/// original timing; 10 replays ten times faster). Returns a replay ID for
+/// `pubsub_stop_replay`; `pubsub:replay-finished` reports the outcome.
+#[tauri::command]
+pub async fn pubsub_replay_recording(
+    connection_id: String,
+    recording_id: String,
+    speed: Option<f64>,
+    manager: State<'_, ConnectionManager>,
+    pubsub: State<'_, PubSubManager>,
+    audit: State<'_, AuditLog>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, AppError> {
+    let speed = speed.unwrap_or(1.0);
+    if !(speed.is_finite() && speed > 0.0) {
+        return Err(AppError::InvalidInput(
+            "Replay speed must be a positive number".into(),
+        ));
+    }
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+    let dir = recording::recordings_dir(&app_handle)?;
+    let messages = recording::read_recording(&dir, &recording_id).await?;
+
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::PubSub,
+            "replayRecording",
+            None,
+            vec![recording_id, messages.len().to_string()],
+        ))
+        .await;
+    Ok(pubsub.start_replay(pool, messages, speed, app_handle).await)
+}
+
+/// Stop a running replay. Returns `false` if it had already finished.
+#[tauri::command]
+pub async fn pubsub_stop_replay(
+    replay_id: String,
+    pubsub: State<'_, PubSubManager>,
+) -> Result<bool, AppError> {
+    Ok(pubsub.stop_replay(&replay_id).await)
+}
+
 /// Get active channels (with optional pattern filter).
 #[tauri::command]
 pub async fn pubsub_get_active_channels(
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::connection::durability;
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::queues::model::{JobMove, QueueDashboard, DEFAULT_BULL_PREFIX};
+use crate::redis::queues::{dashboard, ops};
+use crate::utils::errors::AppError;
+
+/// Depth, oldest job age, failures and pause state of every Sidekiq queue
+/// and every Bull/BullMQ queue under `bull_prefix` (default `bull`).
+#[tauri::command]
+pub async fn queue_dashboard(
+    connection_id: String,
+    bull_prefix: Option<String>,
+    manager: State<'_, ConnectionManager>,
+) -> Result<QueueDashboard, AppError> {
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    let prefix = bull_prefix
+        .filter(|prefix| !prefix.is_empty())
+        .unwrap_or_else(|| DEFAULT_BULL_PREFIX.to_string());
+    dashboard::dashboard(&pool, &prefix).await
+}
+
+/// Move a job to another Sidekiq queue, or a Bull job back to waiting.
+/// The move is atomic and only happens if the job is still where it was
+/// found; returns whether it was moved.
+#[tauri::command]
+pub async fn queue_move_job(
+    connection_id: String,
+    job_move: JobMove,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    audit: State<'_, AuditLog>,
+) -> Result<bool, AppError> {
+    job_move.validate()?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_write_pool(&uuid).await?;
+
+    let moved = ops::move_job(&pool, &job_move).await?;
+    let (key, mut args) = match &job_move {
+        JobMove::Sidekiq {
+            from,
+            jid,
+            to_queue,
+        } => (
+            from.clone(),
+            vec![format!("jid={jid}"), format!("to=queue:{to_queue}")],
+        ),
+        JobMove::Bull {
+            prefix,
+            queue,
+            job_id,
+            from,
+        } => (
+            format!("{prefix}:{queue}:{}", from.as_str()),
+            vec![format!("jobId={job_id}")],
+        ),
+    };
+    args.push(format!("moved={moved}"));
+    audit
+        .record(AuditEntry::new(
+            &connection_id,
+            AuditSource::Queues,
+            "moveJob",
+            Some(&key),
+            args,
+        ))
+        .await;
+    if moved {
+        durability::acknowledge(
+            &app_handle,
+            &manager,
+            &connection_id,
+            None,
+            "moveJob",
+            Some(&key),
+        )
+        .await;
+    }
+    tracing::info!(connection_id = %connection_id, key = %key, moved, "Queue job moved");
+    Ok(moved)
+}
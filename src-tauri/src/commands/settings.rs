@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+
+use crate::config::settings_store::{self, AppSettings};
+use crate::redis::editor::write_queue::KeyWriteQueue;
+use crate::redis::monitor::metrics::MetricsHistory;
+use crate::redis::monitor::prometheus::PrometheusExporter;
+use crate::utils::errors::AppError;
+use crate::utils::event_throttle::{self, EventThrottler};
+
+/// Load application settings (defaults if none are saved).
+#[tauri::command]
+pub async fn settings_get(app_handle: tauri::AppHandle) -> Result<AppSettings, AppError> {
+    settings_store::load_settings(&app_handle).await
+}
+
+/// Replace application settings.
+///
+/// Monitor pollers pick up new metric thresholds the next time a view subscribes;
+/// event throttle limits, key write serialization and the Prometheus endpoint
+/// apply immediately. Settings aren't saved if the endpoint's port can't be
+/// bound.
+#[tauri::command]
+pub async fn settings_update(
+    settings: AppSettings,
+    throttler: State<'_, EventThrottler>,
+    write_queue: State<'_, KeyWriteQueue>,
+    exporter: State<'_, PrometheusExporter>,
+    history: State<'_, MetricsHistory>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    settings_store::validate_thresholds(&settings.metric_thresholds)?;
+    event_throttle::validate_policies(&settings.event_throttle)?;
+    exporter
+        .configure(settings.prometheus_port, history.inner().clone())
+        .await?;
+    settings_store::save_settings(&app_handle, &settings).await?;
+    throttler.configure(settings.event_throttle).await;
+    write_queue.configure(settings.serialize_key_writes);
+    tracing::info!("Settings updated");
+    Ok(())
+}
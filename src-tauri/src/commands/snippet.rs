@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::commands::cli::cli_execute;
+use crate::config::snippet_store;
+use crate::redis::audit::log::AuditLog;
+use crate::redis::cli::history::CliHistory;
+use crate::redis::cli::model::{ExecuteResponse, Snippet};
+use crate::redis::cli::requests::CliRequests;
+use crate::redis::cli::snippets;
+use crate::redis::confirm::registry::ConfirmationRegistry;
+use crate::redis::connection::manager::ConnectionManager;
+use crate::utils::errors::AppError;
+
+/// List snippets usable on a connection (global ones plus its own), or only
+/// global ones when no connection is given. `filter` matches name,
+/// description or template, ignoring case.
+#[tauri::command]
+pub async fn snippet_list(
+    connection_id: Option<String>,
+    filter: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<Snippet>, AppError> {
+    let connection_id = connection_id.as_deref().map(Uuid::parse_str).transpose()?;
+    let mut list = snippet_store::load_snippets(&app_handle).await?;
+    list.retain(|s| snippets::matches(s, connection_id.as_ref(), filter.as_deref()));
+    Ok(list)
+}
+
+/// Create or update a snippet.
+#[tauri::command]
+pub async fn snippet_save(
+    snippet: Snippet,
+    app_handle: tauri::AppHandle,
+) -> Result<Snippet, AppError> {
+    snippets::validate(&snippet)?;
+    let mut snippet = snippet;
+    snippet.name = snippet.name.trim().to_string();
+    snippet.updated_at = chrono::Utc::now();
+    snippet_store::save_snippet(&app_handle, &snippet).await?;
+    Ok(snippet)
+}
+
+/// Delete a snippet.
+#[tauri::command]
+pub async fn snippet_delete(id: String, app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    snippet_store::delete_snippet(&app_handle, &uuid).await
+}
+
+/// Parameter names a snippet expects, in order of first appearance.
+#[tauri::command]
+pub async fn snippet_get_parameters(
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<String>, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let snippet = snippet_store::get_snippet(&app_handle, &uuid).await?;
+    snippets::placeholders(&snippet.template)
+}
+
+/// Fill in a snippet's parameters and run it as a CLI command.
+///
+/// Runs through `cli_execute`, so read-only checks, dangerous-command
+/// confirmation, history and auditing apply as if it had been typed.
+#[tauri::command]
+#[allow(clippy::too_many_arguments, clippy::implicit_hasher)]
+pub async fn snippet_execute(
+    id: String,
+    connection_id: String,
+    args: HashMap<String, String>,
+    confirmation_token: Option<String>,
+    db: Option<u8>,
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ConnectionManager>,
+    history: State<'_, CliHistory>,
+    confirmations: State<'_, ConfirmationRegistry>,
+    requests: State<'_, CliRequests>,
+    audit: State<'_, AuditLog>,
+) -> Result<ExecuteResponse, AppError> {
+    let uuid = Uuid::parse_str(&id)?;
+    let snippet = snippet_store::get_snippet(&app_handle, &uuid).await?;
+    let target = Uuid::parse_str(&connection_id)?;
+    if snippet.connection_id.is_some_and(|own| own != target) {
+        return Err(AppError::InvalidInput(format!(
+            "Snippet \"{}\" belongs to another connection",
+            snippet.name
+        )));
+    }
+
+    let command = snippets::render(&snippet.template, &args)?;
+    cli_execute(
+        connection_id,
+        command,
+        confirmation_token,
+        db,
+        None,
+        None,
+        None,
+        None,
+        app_handle,
+        manager,
+        history,
+        confirmations,
+        requests,
+        audit,
+    )
+    .await
+}
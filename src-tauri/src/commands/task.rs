@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+
+use crate::utils::errors::AppError;
+use crate::utils::tasks::{TaskInfo, TaskRegistry};
+
+/// Abort a long-running command started with a `task_id`. Returns whether
+/// it was still running; its `task:done` event reports it as cancelled.
+#[tauri::command]
+pub async fn task_cancel(
+    task_id: String,
+    tasks: State<'_, TaskRegistry>,
+) -> Result<bool, AppError> {
+    Ok(tasks.cancel(&task_id).await)
+}
+
+/// List running long-running commands, oldest first.
+#[tauri::command]
+pub async fn task_list(tasks: State<'_, TaskRegistry>) -> Result<Vec<TaskInfo>, AppError> {
+    Ok(tasks.list().await)
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::config::template_store;
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::templates::builtin::BUILTIN_IDS;
+use crate::redis::templates::model::{AppTemplate, TemplateScan, TemplateView, MAX_PAGE};
+use crate::redis::templates::{decode, inspect};
+use crate::utils::errors::AppError;
+
+/// Default number of jobs read per inspection.
+const DEFAULT_PAGE: u64 = 100;
+/// Default and largest number of keys a template scan returns.
+const DEFAULT_SCAN_LIMIT: usize = 1_000;
+const MAX_SCAN_LIMIT: usize = 10_000;
+
+/// List the key templates: the user's own, then the built-in ones.
+#[tauri::command]
+pub async fn template_list(app_handle: tauri::AppHandle) -> Result<Vec<AppTemplate>, AppError> {
+    template_store::load_templates(&app_handle).await
+}
+
+/// Create or update a user template. Built-in templates can't be changed.
+#[tauri::command]
+pub async fn template_save(
+    template: AppTemplate,
+    app_handle: tauri::AppHandle,
+) -> Result<AppTemplate, AppError> {
+    let mut template = template;
+    if template.builtin || BUILTIN_IDS.contains(&template.id.as_str()) {
+        return Err(AppError::PermissionDenied(
+            "Built-in templates can't be changed; save a copy under a new name".into(),
+        ));
+    }
+    template.validate()?;
+    template.name = template.name.trim().to_string();
+    if template.id.is_empty() {
+        template.id = Uuid::new_v4().to_string();
+    }
+    template_store::save_template(&app_handle, &template).await?;
+    Ok(template)
+}
+
+/// Delete a user template.
+#[tauri::command]
+pub async fn template_delete(id: String, app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    if BUILTIN_IDS.contains(&id.as_str()) {
+        return Err(AppError::PermissionDenied(
+            "Built-in templates can't be deleted".into(),
+        ));
+    }
+    template_store::delete_template(&app_handle, &id).await
+}
+
+/// Read a key through the template that describes it (`template_id`, or
+/// the first whose rules match the key): a decoded record, or a page of
+/// `count` (default 100) decoded jobs from `offset` for queue keys.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn template_inspect_key(
+    connection_id: String,
+    key: String,
+    template_id: Option<String>,
+    offset: Option<u64>,
+    count: Option<u64>,
+    manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<TemplateView, AppError> {
+    let count = count.unwrap_or(DEFAULT_PAGE);
+    if !(1..=MAX_PAGE).contains(&count) {
+        return Err(AppError::InvalidInput(format!(
+            "Count must be between 1 and {MAX_PAGE}"
+        )));
+    }
+    let mut templates = template_store::load_templates(&app_handle).await?;
+    if let Some(id) = &template_id {
+        templates.retain(|t| &t.id == id);
+        if templates.is_empty() {
+            return Err(AppError::NotFound(format!("Template {id} not found")));
+        }
+    }
+    let Some((template, rule)) = decode::find_rule(&templates, &key) else {
+        return Err(AppError::NotFound(format!(
+            "No template describes key '{key}'"
+        )));
+    };
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    inspect::inspect_key(&pool, template, rule, &key, offset.unwrap_or(0), count).await
+}
+
+/// Find a template's keys (up to `limit`, default 1000), e.g. every queue
+/// with its state and length.
+#[tauri::command]
+pub async fn template_scan(
+    connection_id: String,
+    template_id: String,
+    limit: Option<usize>,
+    manager: State<'_, ConnectionManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<TemplateScan, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_SCAN_LIMIT);
+    if !(1..=MAX_SCAN_LIMIT).contains(&limit) {
+        return Err(AppError::InvalidInput(format!(
+            "Limit must be between 1 and {MAX_SCAN_LIMIT}"
+        )));
+    }
+    let template = template_store::get_template(&app_handle, &template_id).await?;
+    let uuid = Uuid::parse_str(&connection_id)?;
+    let pool = manager.get_pool(&uuid).await?;
+    inspect::scan_template(&pool, &template, limit).await
+}
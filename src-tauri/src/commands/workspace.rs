@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MIT
+
+use uuid::Uuid;
+
+use crate::config::workspace_store::{self, ProfileWorkspace, Workspace};
+use crate::utils::errors::AppError;
+
+/// Load the saved workspace (empty if none is saved).
+#[tauri::command]
+pub async fn workspace_get(app_handle: tauri::AppHandle) -> Result<Workspace, AppError> {
+    workspace_store::load_workspace(&app_handle).await
+}
+
+/// Save one profile's tabs, scan patterns, tree delimiter and editor view
+/// preferences. Returns the state as stored, after duplicates and
+/// overflow are dropped.
+#[tauri::command]
+pub async fn workspace_save_profile(
+    profile_id: String,
+    state: ProfileWorkspace,
+    app_handle: tauri::AppHandle,
+) -> Result<ProfileWorkspace, AppError> {
+    Uuid::parse_str(&profile_id)?;
+    let mut state = state;
+    state.normalize();
+    workspace_store::save_profile_workspace(&app_handle, &profile_id, state.clone()).await?;
+    Ok(state)
+}
+
+/// Remember the selected connection, or clear it.
+#[tauri::command]
+pub async fn workspace_set_last_connection(
+    connection_id: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    if let Some(id) = &connection_id {
+        Uuid::parse_str(id)?;
+    }
+    workspace_store::set_last_connection(&app_handle, connection_id).await
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use tokio::sync::Mutex;
+
+use crate::redis::monitor::model::AlertRecord;
+use crate::utils::errors::AppError;
+
+/// Alert records kept per connection; the oldest are dropped beyond this.
+const MAX_ALERTS_PER_CONNECTION: usize = 200;
+
+/// Serializes access to the alerts file, so alerts fired together on
+/// several connections can't drop each other's records.
+static STORE_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Resolve the path to the alert history JSON file.
+fn alerts_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("alerts.json"))
+}
+
+/// Load all persisted alert records.
+async fn load_all(app_handle: &tauri::AppHandle) -> Result<Vec<AlertRecord>, AppError> {
+    let path = alerts_path(app_handle)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read alert history: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse alert history: {e}")))
+}
+
+/// List a connection's alerts, newest first, up to `limit`.
+pub async fn list_alerts(
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+    limit: usize,
+) -> Result<Vec<AlertRecord>, AppError> {
+    let mut alerts = {
+        let _guard = STORE_LOCK.lock().await;
+        load_all(app_handle).await?
+    };
+    alerts.retain(|a| a.connection_id == connection_id);
+    alerts.sort_by_key(|a| std::cmp::Reverse(a.fired_at));
+    alerts.truncate(limit);
+    Ok(alerts)
+}
+
+/// Persist an alert, pruning the connection's oldest beyond the retention cap.
+pub async fn save_alert(
+    app_handle: &tauri::AppHandle,
+    alert: &AlertRecord,
+) -> Result<(), AppError> {
+    let _guard = STORE_LOCK.lock().await;
+    let mut alerts = load_all(app_handle).await?;
+    alerts.push(alert.clone());
+    prune(&mut alerts, &alert.connection_id);
+    write_all(app_handle, &alerts).await
+}
+
+/// Drop the oldest alerts for `connection_id` beyond the retention cap.
+fn prune(alerts: &mut Vec<AlertRecord>, connection_id: &str) {
+    let mut own: Vec<_> = alerts
+        .iter()
+        .filter(|a| a.connection_id == connection_id)
+        .map(|a| (a.fired_at, a.id))
+        .collect();
+    if own.len() <= MAX_ALERTS_PER_CONNECTION {
+        return;
+    }
+    own.sort();
+    let expired: Vec<uuid::Uuid> = own[..own.len() - MAX_ALERTS_PER_CONNECTION]
+        .iter()
+        .map(|(_, id)| *id)
+        .collect();
+    alerts.retain(|a| !expired.contains(&a.id));
+}
+
+/// Write alerts to disk, creating the directory if needed.
+async fn write_all(app_handle: &tauri::AppHandle, alerts: &[AlertRecord]) -> Result<(), AppError> {
+    let path = alerts_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string(alerts)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize alert history: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write alert history: {e}")))?;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+
+use crate::redis::backup::model::{BackupArchive, BackupRecord};
+use crate::utils::errors::AppError;
+
+/// File name timestamp format; sorts lexically in chronological order.
+const ID_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+/// Resolve the backup directory for a connection.
+fn backups_dir(app_handle: &tauri::AppHandle, connection_id: &str) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("backups").join(connection_id))
+}
+
+/// Write an archive to disk and return its record.
+pub async fn write_archive(
+    app_handle: &tauri::AppHandle,
+    archive: &BackupArchive,
+) -> Result<BackupRecord, AppError> {
+    let dir = backups_dir(app_handle, &archive.connection_id)?;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to create backup dir: {e}")))?;
+
+    let id = archive.created_at.format(ID_FORMAT).to_string();
+    let data = serde_json::to_vec(archive)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize backup: {e}")))?;
+
+    tokio::fs::write(dir.join(format!("{id}.json")), &data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write backup: {e}")))?;
+
+    Ok(BackupRecord {
+        id,
+        connection_id: archive.connection_id.clone(),
+        created_at: archive.created_at,
+        size_bytes: data.len() as u64,
+    })
+}
+
+/// Read an archive by ID.
+pub async fn read_archive(
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+    id: &str,
+) -> Result<BackupArchive, AppError> {
+    validate_id(id)?;
+    let path = backups_dir(app_handle, connection_id)?.join(format!("{id}.json"));
+    if !path.exists() {
+        return Err(AppError::NotFound(format!("Backup {id} not found")));
+    }
+    let data = tokio::fs::read(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read backup: {e}")))?;
+    serde_json::from_slice(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse backup: {e}")))
+}
+
+/// List archives for a connection, newest first.
+pub async fn list_archives(
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+) -> Result<Vec<BackupRecord>, AppError> {
+    let dir = backups_dir(app_handle, connection_id)?;
+    list_in_dir(&dir, connection_id).await
+}
+
+/// Delete all but the newest `keep_last` archives. Returns how many were removed.
+pub async fn apply_retention(
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+    keep_last: u32,
+) -> Result<usize, AppError> {
+    let dir = backups_dir(app_handle, connection_id)?;
+    let records = list_in_dir(&dir, connection_id).await?;
+    let keep = (keep_last as usize).max(1);
+
+    let mut removed = 0;
+    for record in records.iter().skip(keep) {
+        tokio::fs::remove_file(dir.join(format!("{}.json", record.id)))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to delete old backup: {e}")))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+async fn list_in_dir(dir: &Path, connection_id: &str) -> Result<Vec<BackupRecord>, AppError> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut read_dir = tokio::fs::read_dir(dir)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to list backups: {e}")))?;
+
+    let mut records = Vec::new();
+    while let Some(entry) = read_dir
+        .next_entry()
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to list backups: {e}")))?
+    {
+        let path = entry.path();
+        let Some(id) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .filter(|_| path.extension().is_some_and(|e| e == "json"))
+        else {
+            continue;
+        };
+        let Some(created_at) = parse_id(id) else {
+            continue;
+        };
+        let size_bytes = entry.metadata().await.map_or(0, |m| m.len());
+        records.push(BackupRecord {
+            id: id.to_string(),
+            connection_id: connection_id.to_string(),
+            created_at,
+            size_bytes,
+        });
+    }
+
+    records.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+    Ok(records)
+}
+
+/// Parse an archive ID back into its timestamp.
+fn parse_id(id: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::NaiveDateTime::parse_from_str(id, ID_FORMAT)
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Reject IDs that are not archive timestamps (guards against path traversal).
+fn validate_id(id: &str) -> Result<(), AppError> {
+    if parse_id(id).is_none() {
+        return Err(AppError::InvalidInput(format!("Invalid backup id '{id}'")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_roundtrip() {
+        let now = chrono::Utc::now();
+        let id = now.format(ID_FORMAT).to_string();
+        let parsed = parse_id(&id).unwrap();
+        assert_eq!(parsed.timestamp_millis(), now.timestamp_millis());
+    }
+
+    #[test]
+    fn test_validate_id_rejects_paths() {
+        assert!(validate_id("../../etc/passwd").is_err());
+        assert!(validate_id("20261016T120000123Z").is_ok());
+    }
+}
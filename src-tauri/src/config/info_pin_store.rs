@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::redis::monitor::model::PinnedInfo;
+use crate::utils::errors::AppError;
+
+/// Pinned snapshots kept per connection; the oldest are dropped beyond this.
+const MAX_PINS_PER_CONNECTION: usize = 50;
+
+/// Resolve the path to the pinned INFO snapshots JSON file.
+fn pins_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("info_snapshots.json"))
+}
+
+/// Load all pinned snapshots.
+async fn load_all(app_handle: &tauri::AppHandle) -> Result<Vec<PinnedInfo>, AppError> {
+    let path = pins_path(app_handle)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read INFO snapshots: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse INFO snapshots: {e}")))
+}
+
+/// List a connection's pinned snapshots, oldest first.
+pub async fn list_pins(
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+) -> Result<Vec<PinnedInfo>, AppError> {
+    let mut pins = load_all(app_handle).await?;
+    pins.retain(|p| p.connection_id == connection_id);
+    pins.sort_by_key(|p| p.captured_at);
+    Ok(pins)
+}
+
+/// Load a single pinned snapshot by its ID.
+pub async fn load_pin(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<PinnedInfo, AppError> {
+    load_all(app_handle)
+        .await?
+        .into_iter()
+        .find(|p| &p.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("INFO snapshot {id} not found")))
+}
+
+/// Persist a pinned snapshot, pruning the connection's oldest beyond the
+/// retention cap.
+pub async fn save_pin(app_handle: &tauri::AppHandle, pin: &PinnedInfo) -> Result<(), AppError> {
+    let mut pins = load_all(app_handle).await?;
+    pins.push(pin.clone());
+    prune(&mut pins, &pin.connection_id);
+    write_all(app_handle, &pins).await
+}
+
+/// Delete a pinned snapshot by ID.
+pub async fn delete_pin(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<(), AppError> {
+    let mut pins = load_all(app_handle).await?;
+    let original_len = pins.len();
+    pins.retain(|p| &p.id != id);
+
+    if pins.len() == original_len {
+        return Err(AppError::NotFound(format!("INFO snapshot {id} not found")));
+    }
+
+    write_all(app_handle, &pins).await
+}
+
+/// Drop the oldest pins for `connection_id` beyond the retention cap.
+fn prune(pins: &mut Vec<PinnedInfo>, connection_id: &str) {
+    let mut own: Vec<_> = pins
+        .iter()
+        .filter(|p| p.connection_id == connection_id)
+        .map(|p| (p.captured_at, p.id))
+        .collect();
+    if own.len() <= MAX_PINS_PER_CONNECTION {
+        return;
+    }
+    own.sort();
+    let expired: Vec<Uuid> = own[..own.len() - MAX_PINS_PER_CONNECTION]
+        .iter()
+        .map(|(_, id)| *id)
+        .collect();
+    pins.retain(|p| !expired.contains(&p.id));
+}
+
+/// Write pinned snapshots to disk, creating the directory if needed.
+async fn write_all(app_handle: &tauri::AppHandle, pins: &[PinnedInfo]) -> Result<(), AppError> {
+    let path = pins_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string(pins)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize INFO snapshots: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write INFO snapshots: {e}")))?;
+
+    Ok(())
+}
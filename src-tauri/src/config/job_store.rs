@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::redis::jobs::model::{JobRun, ScheduledJob};
+use crate::utils::errors::AppError;
+
+/// Runs kept per job; the oldest are dropped beyond this.
+const MAX_RUNS_PER_JOB: usize = 100;
+
+/// Resolve a file in the app data directory.
+fn data_path(app_handle: &tauri::AppHandle, file: &str) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join(file))
+}
+
+/// Load all scheduled jobs, sorted by name.
+pub async fn load_jobs(app_handle: &tauri::AppHandle) -> Result<Vec<ScheduledJob>, AppError> {
+    let mut jobs: Vec<ScheduledJob> =
+        read_json(&data_path(app_handle, "jobs.json")?, "jobs").await?;
+    jobs.sort_by_key(|j| j.name.to_lowercase());
+    Ok(jobs)
+}
+
+/// Load a single job by ID.
+pub async fn get_job(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<ScheduledJob, AppError> {
+    load_jobs(app_handle)
+        .await?
+        .into_iter()
+        .find(|j| j.id == *id)
+        .ok_or_else(|| AppError::NotFound(format!("Job {id} not found")))
+}
+
+/// Insert or replace a job.
+pub async fn save_job(app_handle: &tauri::AppHandle, job: &ScheduledJob) -> Result<(), AppError> {
+    let mut jobs = load_jobs(app_handle).await?;
+    match jobs.iter_mut().find(|j| j.id == job.id) {
+        Some(existing) => *existing = job.clone(),
+        None => jobs.push(job.clone()),
+    }
+    write_json(&data_path(app_handle, "jobs.json")?, &jobs, "jobs").await
+}
+
+/// Delete a job and its run history.
+pub async fn delete_job(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<(), AppError> {
+    let mut jobs = load_jobs(app_handle).await?;
+    let before = jobs.len();
+    jobs.retain(|j| j.id != *id);
+    if jobs.len() == before {
+        return Err(AppError::NotFound(format!("Job {id} not found")));
+    }
+    write_json(&data_path(app_handle, "jobs.json")?, &jobs, "jobs").await?;
+
+    let runs_path = data_path(app_handle, "job_runs.json")?;
+    let mut runs: Vec<JobRun> = read_json(&runs_path, "job runs").await?;
+    runs.retain(|r| r.job_id != *id);
+    write_json(&runs_path, &runs, "job runs").await
+}
+
+/// A job's runs, newest first, up to `limit`.
+pub async fn list_runs(
+    app_handle: &tauri::AppHandle,
+    job_id: &Uuid,
+    limit: usize,
+) -> Result<Vec<JobRun>, AppError> {
+    let mut runs: Vec<JobRun> =
+        read_json(&data_path(app_handle, "job_runs.json")?, "job runs").await?;
+    runs.retain(|r| r.job_id == *job_id);
+    runs.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+    runs.truncate(limit);
+    Ok(runs)
+}
+
+/// Persist a run, pruning the job's oldest beyond the retention cap.
+pub async fn save_run(app_handle: &tauri::AppHandle, run: &JobRun) -> Result<(), AppError> {
+    let path = data_path(app_handle, "job_runs.json")?;
+    let mut runs: Vec<JobRun> = read_json(&path, "job runs").await?;
+    runs.push(run.clone());
+    prune(&mut runs, &run.job_id);
+    write_json(&path, &runs, "job runs").await
+}
+
+/// Drop the oldest runs of `job_id` beyond the retention cap.
+fn prune(runs: &mut Vec<JobRun>, job_id: &Uuid) {
+    let mut own: Vec<_> = runs
+        .iter()
+        .filter(|r| r.job_id == *job_id)
+        .map(|r| (r.started_at, r.id))
+        .collect();
+    if own.len() <= MAX_RUNS_PER_JOB {
+        return;
+    }
+    own.sort();
+    let expired: Vec<Uuid> = own[..own.len() - MAX_RUNS_PER_JOB]
+        .iter()
+        .map(|(_, id)| *id)
+        .collect();
+    runs.retain(|r| !expired.contains(&r.id));
+}
+
+async fn read_json<T: serde::de::DeserializeOwned>(
+    path: &PathBuf,
+    what: &str,
+) -> Result<Vec<T>, AppError> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read {what}: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse {what}: {e}")))
+}
+
+/// Write a JSON file, creating the directory if needed.
+async fn write_json<T: serde::Serialize>(
+    path: &PathBuf,
+    items: &[T],
+    what: &str,
+) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string_pretty(items)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize {what}: {e}")))?;
+
+    tokio::fs::write(path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write {what}: {e}")))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::jobs::model::JobRunStatus;
+
+    fn run(job_id: Uuid, minutes_ago: i64) -> JobRun {
+        let at = chrono::Utc::now() - chrono::Duration::minutes(minutes_ago);
+        JobRun {
+            id: Uuid::new_v4(),
+            job_id,
+            connection_id: "conn-1".into(),
+            started_at: at,
+            finished_at: at,
+            status: JobRunStatus::Succeeded,
+            output: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_per_job() {
+        let job = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut runs: Vec<_> = (0..MAX_RUNS_PER_JOB + 2)
+            .map(|i| run(job, i64::try_from(i).unwrap()))
+            .collect();
+        runs.push(run(other, 10_000));
+        let oldest = runs[MAX_RUNS_PER_JOB + 1].id;
+
+        prune(&mut runs, &job);
+
+        assert_eq!(runs.len(), MAX_RUNS_PER_JOB + 1);
+        assert!(runs.iter().all(|r| r.id != oldest));
+        assert!(runs.iter().any(|r| r.job_id == other));
+    }
+}
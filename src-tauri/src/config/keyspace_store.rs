@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::redis::browser::model::{KeyspaceSnapshot, SnapshotKey};
+use crate::utils::errors::AppError;
+
+/// Snapshots kept per connection; the oldest are dropped beyond this.
+const MAX_SNAPSHOTS_PER_CONNECTION: usize = 200;
+
+/// Serializes access to the snapshots file, so concurrent saves (the
+/// scheduler and a manual capture) can't drop each other's entries.
+static STORE_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Resolve the path to the keyspace snapshots JSON file.
+fn snapshots_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("keyspace_snapshots.json"))
+}
+
+/// Resolve the path to a snapshot's key index file.
+fn key_index_path(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<PathBuf, AppError> {
+    let snapshots = snapshots_path(app_handle)?;
+    let dir = snapshots.with_file_name("keyspace_indexes");
+    Ok(dir.join(format!("{id}.json")))
+}
+
+/// Load all persisted keyspace snapshots.
+async fn load_all(app_handle: &tauri::AppHandle) -> Result<Vec<KeyspaceSnapshot>, AppError> {
+    let path = snapshots_path(app_handle)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read keyspace snapshots: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse keyspace snapshots: {e}")))
+}
+
+/// List snapshots for a connection, oldest first.
+pub async fn list_snapshots(
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+) -> Result<Vec<KeyspaceSnapshot>, AppError> {
+    let mut snapshots = {
+        let _guard = STORE_LOCK.lock().await;
+        load_all(app_handle).await?
+    };
+    snapshots.retain(|s| s.connection_id == connection_id);
+    snapshots.sort_by_key(|s| s.captured_at);
+    Ok(snapshots)
+}
+
+/// Load a single snapshot by its ID.
+pub async fn load_snapshot(
+    app_handle: &tauri::AppHandle,
+    id: &Uuid,
+) -> Result<KeyspaceSnapshot, AppError> {
+    let _guard = STORE_LOCK.lock().await;
+    load_all(app_handle)
+        .await?
+        .into_iter()
+        .find(|s| &s.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Keyspace snapshot {id} not found")))
+}
+
+/// Load a snapshot's key index; `None` if it was taken without one.
+pub async fn load_key_index(
+    app_handle: &tauri::AppHandle,
+    id: &Uuid,
+) -> Result<Option<Vec<SnapshotKey>>, AppError> {
+    let path = key_index_path(app_handle, id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read keyspace index: {e}")))?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| AppError::Internal(format!("Failed to parse keyspace index: {e}")))
+}
+
+/// Persist a snapshot and its key index, pruning the connection's oldest
+/// beyond the retention cap.
+pub async fn save_snapshot(
+    app_handle: &tauri::AppHandle,
+    snapshot: &KeyspaceSnapshot,
+    keys: &[SnapshotKey],
+) -> Result<(), AppError> {
+    if !keys.is_empty() {
+        write_key_index(app_handle, &snapshot.id, keys).await?;
+    }
+    let _guard = STORE_LOCK.lock().await;
+    let mut snapshots = load_all(app_handle).await?;
+    snapshots.push(snapshot.clone());
+    let expired = prune(&mut snapshots, &snapshot.connection_id);
+    write_all(app_handle, &snapshots).await?;
+    for id in &expired {
+        remove_key_index(app_handle, id).await;
+    }
+    Ok(())
+}
+
+/// Delete a snapshot by ID.
+pub async fn delete_snapshot(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<(), AppError> {
+    let _guard = STORE_LOCK.lock().await;
+    let mut snapshots = load_all(app_handle).await?;
+    let original_len = snapshots.len();
+    snapshots.retain(|s| &s.id != id);
+
+    if snapshots.len() == original_len {
+        return Err(AppError::NotFound(format!(
+            "Keyspace snapshot {id} not found"
+        )));
+    }
+
+    write_all(app_handle, &snapshots).await?;
+    remove_key_index(app_handle, id).await;
+    Ok(())
+}
+
+/// Delete a snapshot's key index file, if any. Failures are only logged:
+/// an orphaned index is never read again.
+async fn remove_key_index(app_handle: &tauri::AppHandle, id: &Uuid) {
+    let Ok(path) = key_index_path(app_handle, id) else {
+        return;
+    };
+    if let Err(e) = tokio::fs::remove_file(&path).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!(snapshot_id = %id, "Failed to delete keyspace index: {e}");
+        }
+    }
+}
+
+/// Drop the oldest snapshots for `connection_id` beyond the retention cap,
+/// returning their IDs.
+fn prune(snapshots: &mut Vec<KeyspaceSnapshot>, connection_id: &str) -> Vec<Uuid> {
+    let mut own: Vec<_> = snapshots
+        .iter()
+        .filter(|s| s.connection_id == connection_id)
+        .map(|s| (s.captured_at, s.id))
+        .collect();
+    if own.len() <= MAX_SNAPSHOTS_PER_CONNECTION {
+        return Vec::new();
+    }
+    own.sort();
+    let expired: Vec<Uuid> = own[..own.len() - MAX_SNAPSHOTS_PER_CONNECTION]
+        .iter()
+        .map(|(_, id)| *id)
+        .collect();
+    snapshots.retain(|s| !expired.contains(&s.id));
+    expired
+}
+
+/// Write a snapshot's key index, creating the directory if needed.
+async fn write_key_index(
+    app_handle: &tauri::AppHandle,
+    id: &Uuid,
+    keys: &[SnapshotKey],
+) -> Result<(), AppError> {
+    let path = key_index_path(app_handle, id)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create index dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string(keys)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize keyspace index: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write keyspace index: {e}")))
+}
+
+/// Write snapshots to disk, creating the directory if needed.
+async fn write_all(
+    app_handle: &tauri::AppHandle,
+    snapshots: &[KeyspaceSnapshot],
+) -> Result<(), AppError> {
+    let path = snapshots_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string(snapshots)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize keyspace snapshots: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write keyspace snapshots: {e}")))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(connection_id: &str, minutes_ago: i64) -> KeyspaceSnapshot {
+        KeyspaceSnapshot {
+            id: Uuid::new_v4(),
+            connection_id: connection_id.into(),
+            name: None,
+            captured_at: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+            delimiter: ":".into(),
+            total_keys: 0,
+            total_memory_bytes: 0,
+            namespaces: vec![],
+            indexed_keys: 0,
+            index_cutoff: None,
+        }
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_per_connection() {
+        let mut snapshots: Vec<_> = (0..MAX_SNAPSHOTS_PER_CONNECTION + 2)
+            .map(|i| snapshot("a", i64::try_from(i).unwrap()))
+            .collect();
+        snapshots.push(snapshot("b", 10_000));
+        let oldest = snapshots[MAX_SNAPSHOTS_PER_CONNECTION + 1].id;
+
+        let expired = prune(&mut snapshots, "a");
+
+        assert_eq!(expired.len(), 2);
+        assert_eq!(snapshots.len(), MAX_SNAPSHOTS_PER_CONNECTION + 1);
+        assert!(snapshots.iter().all(|s| s.id != oldest));
+        assert!(snapshots.iter().any(|s| s.connection_id == "b"));
+    }
+}
@@ -1,3 +1,13 @@
 // SPDX-License-Identifier: MIT
 
+pub mod alert_store;
+pub mod backup_store;
+pub mod info_pin_store;
+pub mod job_store;
+pub mod keyspace_store;
 pub mod profile_store;
+pub mod profile_vault;
+pub mod settings_store;
+pub mod snippet_store;
+pub mod template_store;
+pub mod workspace_store;
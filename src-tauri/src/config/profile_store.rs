@@ -4,7 +4,12 @@ use std::path::PathBuf;
 
 use uuid::Uuid;
 
-use crate::redis::connection::model::ConnectionProfile;
+use super::profile_vault::{self, ProfileStoreStatus, ProfileVault, StoredProfiles};
+use super::settings_store;
+use crate::redis::browser::model::RedisKeyType;
+use crate::redis::connection::command_policy;
+use crate::redis::connection::model::{ConnectionGroup, ConnectionProfile, ScanPreset};
+use crate::redis::monitor::alerts;
 use crate::utils::errors::AppError;
 
 /// Resolve the path to the connections JSON file.
@@ -17,20 +22,50 @@ fn profiles_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
     Ok(dir.join("connections.json"))
 }
 
-/// Load all saved connection profiles from disk.
-pub async fn load_all_profiles(
-    app_handle: &tauri::AppHandle,
-) -> Result<Vec<ConnectionProfile>, AppError> {
+/// Resolve the path to the connection groups JSON file.
+fn groups_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(profiles_path(app_handle)?.with_file_name("connection_groups.json"))
+}
+
+/// The vault holding the key of an encrypted store.
+fn vault(app_handle: &tauri::AppHandle) -> Result<ProfileVault, AppError> {
+    use tauri::Manager;
+    app_handle
+        .try_state::<ProfileVault>()
+        .map(|v| v.inner().clone())
+        .ok_or_else(|| AppError::Internal("Profile vault is not initialized".into()))
+}
+
+/// Read `connections.json` as stored, if it exists.
+async fn read_stored(app_handle: &tauri::AppHandle) -> Result<Option<StoredProfiles>, AppError> {
     let path = profiles_path(app_handle)?;
     if !path.exists() {
-        return Ok(vec![]);
+        return Ok(None);
     }
     let data = tokio::fs::read_to_string(&path)
         .await
         .map_err(|e| AppError::Internal(format!("Failed to read profiles: {e}")))?;
-    let profiles: Vec<ConnectionProfile> = serde_json::from_str(&data)
-        .map_err(|e| AppError::Internal(format!("Failed to parse profiles: {e}")))?;
-    Ok(profiles)
+    StoredProfiles::parse(data).map(Some)
+}
+
+fn parse_profiles(data: &[u8]) -> Result<Vec<ConnectionProfile>, AppError> {
+    serde_json::from_slice(data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse profiles: {e}")))
+}
+
+/// Load all saved connection profiles from disk.
+///
+/// Fails with `AppError::Locked` if the store is encrypted and locked.
+pub async fn load_all_profiles(
+    app_handle: &tauri::AppHandle,
+) -> Result<Vec<ConnectionProfile>, AppError> {
+    match read_stored(app_handle).await? {
+        None => Ok(vec![]),
+        Some(StoredProfiles::Plain(data)) => parse_profiles(data.as_bytes()),
+        Some(StoredProfiles::Encrypted(envelope)) => {
+            parse_profiles(&vault(app_handle)?.open(&envelope).await?)
+        }
+    }
 }
 
 /// Load a single profile by its ID.
@@ -59,6 +94,16 @@ pub async fn save_profile(
     write_profiles(app_handle, &profiles).await
 }
 
+/// Append several new profiles in one write.
+pub async fn insert_profiles(
+    app_handle: &tauri::AppHandle,
+    new_profiles: &[ConnectionProfile],
+) -> Result<(), AppError> {
+    let mut profiles = load_all_profiles(app_handle).await?;
+    profiles.extend_from_slice(new_profiles);
+    write_profiles(app_handle, &profiles).await
+}
+
 /// Delete a connection profile by ID.
 pub async fn delete_profile(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<(), AppError> {
     let mut profiles = load_all_profiles(app_handle).await?;
@@ -90,9 +135,580 @@ async fn write_profiles(
     let data = serde_json::to_string_pretty(profiles)
         .map_err(|e| AppError::Internal(format!("Failed to serialize profiles: {e}")))?;
 
+    // Encrypt whenever a key is held; never fall back to plaintext over an
+    // encrypted store.
+    let data = match vault(app_handle)?.seal(data.as_bytes()).await? {
+        Some(envelope) => serialize_envelope(&envelope)?,
+        None if matches!(
+            read_stored(app_handle).await?,
+            Some(StoredProfiles::Encrypted(_))
+        ) =>
+        {
+            return Err(profile_vault::locked_error());
+        }
+        None => data,
+    };
+
     tokio::fs::write(&path, data)
         .await
         .map_err(|e| AppError::Internal(format!("Failed to write profiles: {e}")))?;
 
     Ok(())
 }
+
+fn serialize_envelope(envelope: &profile_vault::EncryptedProfiles) -> Result<String, AppError> {
+    serde_json::to_string_pretty(envelope)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize encrypted profiles: {e}")))
+}
+
+async fn write_raw(app_handle: &tauri::AppHandle, data: String) -> Result<(), AppError> {
+    let path = profiles_path(app_handle)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write profiles: {e}")))
+}
+
+// ─── Encryption at rest ──────────────────────────────────────────
+
+/// Whether the store is encrypted and, if so, unlocked.
+pub async fn store_status(app_handle: &tauri::AppHandle) -> Result<ProfileStoreStatus, AppError> {
+    let encrypted = matches!(
+        read_stored(app_handle).await?,
+        Some(StoredProfiles::Encrypted(_))
+    );
+    let locked = encrypted && !vault(app_handle)?.is_unlocked().await;
+    Ok(ProfileStoreStatus { encrypted, locked })
+}
+
+/// Migrate a plaintext store to encrypted, leaving it unlocked.
+pub async fn enable_encryption(
+    app_handle: &tauri::AppHandle,
+    passphrase: &str,
+) -> Result<(), AppError> {
+    let profiles = match read_stored(app_handle).await? {
+        Some(StoredProfiles::Encrypted(_)) => {
+            return Err(AppError::InvalidInput(
+                "Connection profiles are already encrypted".into(),
+            ));
+        }
+        Some(StoredProfiles::Plain(data)) => parse_profiles(data.as_bytes())?,
+        None => vec![],
+    };
+
+    let key = ProfileVault::new_key(passphrase)?;
+    let data = serde_json::to_vec(&profiles)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize profiles: {e}")))?;
+    let envelope = profile_vault::envelope(&key, &data)?;
+    write_raw(app_handle, serialize_envelope(&envelope)?).await?;
+    vault(app_handle)?.unlock(key).await;
+    Ok(())
+}
+
+/// Unlock an encrypted store with its passphrase.
+pub async fn unlock(app_handle: &tauri::AppHandle, passphrase: &str) -> Result<(), AppError> {
+    let Some(StoredProfiles::Encrypted(envelope)) = read_stored(app_handle).await? else {
+        return Err(AppError::InvalidInput(
+            "Connection profiles are not encrypted".into(),
+        ));
+    };
+    let (key, _) = ProfileVault::derive_key(passphrase, &envelope).await?;
+    vault(app_handle)?.unlock(key).await;
+    Ok(())
+}
+
+/// Forget the key of an encrypted store.
+pub async fn lock(app_handle: &tauri::AppHandle) -> Result<(), AppError> {
+    vault(app_handle)?.lock().await;
+    Ok(())
+}
+
+/// Decrypt the store back to plaintext; the passphrase is required even when unlocked.
+pub async fn disable_encryption(
+    app_handle: &tauri::AppHandle,
+    passphrase: &str,
+) -> Result<(), AppError> {
+    let Some(StoredProfiles::Encrypted(envelope)) = read_stored(app_handle).await? else {
+        return Err(AppError::InvalidInput(
+            "Connection profiles are not encrypted".into(),
+        ));
+    };
+    let (_, plaintext) = ProfileVault::derive_key(passphrase, &envelope).await?;
+    let profiles = parse_profiles(&plaintext)?;
+    let data = serde_json::to_string_pretty(&profiles)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize profiles: {e}")))?;
+    write_raw(app_handle, data).await?;
+    vault(app_handle)?.lock().await;
+    Ok(())
+}
+
+/// Order profiles for display: by group position, then name.
+pub fn sort_profiles(profiles: &mut [ConnectionProfile]) {
+    profiles.sort_by(|a, b| {
+        a.sort_order
+            .cmp(&b.sort_order)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+/// Move `ids` into `group_id` in the given order; other profiles are untouched.
+fn apply_order(
+    profiles: &mut [ConnectionProfile],
+    group_id: Option<Uuid>,
+    ids: &[Uuid],
+) -> Result<(), AppError> {
+    for (position, id) in ids.iter().enumerate() {
+        let profile = profiles
+            .iter_mut()
+            .find(|p| &p.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Connection profile {id} not found")))?;
+        profile.group_id = group_id;
+        profile.sort_order = u32::try_from(position).unwrap_or(u32::MAX);
+    }
+    Ok(())
+}
+
+/// Trim tags, dropping empty ones and case-insensitive duplicates.
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// Check the fields of a profile to save or import: a name and host, a
+/// database index Redis accepts, and valid thresholds, alert rules, command
+/// policy and scan presets.
+pub fn validate_profile(profile: &ConnectionProfile) -> Result<(), AppError> {
+    if profile.name.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Connection name must not be empty".into(),
+        ));
+    }
+    if profile.host.is_empty() {
+        return Err(AppError::InvalidInput("Host must not be empty".into()));
+    }
+    if profile.database > 15 {
+        return Err(AppError::InvalidInput(
+            "Database must be between 0 and 15".into(),
+        ));
+    }
+    if let Some(thresholds) = &profile.metric_thresholds {
+        settings_store::validate_thresholds(thresholds)?;
+    }
+    alerts::validate_rules(&profile.alert_rules)?;
+    command_policy::validate(&profile.command_policy)?;
+    validate_scan_presets(profile)
+}
+
+/// Place profiles in a group (or the top level) in the given order.
+pub async fn reorder_profiles(
+    app_handle: &tauri::AppHandle,
+    group_id: Option<Uuid>,
+    ids: &[Uuid],
+) -> Result<(), AppError> {
+    if let Some(group_id) = group_id {
+        let groups = load_groups(app_handle).await?;
+        if !groups.iter().any(|g| g.id == group_id) {
+            return Err(AppError::NotFound(format!(
+                "Connection group {group_id} not found"
+            )));
+        }
+    }
+    let mut profiles = load_all_profiles(app_handle).await?;
+    apply_order(&mut profiles, group_id, ids)?;
+    write_profiles(app_handle, &profiles).await
+}
+
+// ─── Scan presets ────────────────────────────────────────────────
+
+/// Largest SCAN COUNT hint a preset may save.
+const MAX_PRESET_COUNT: u32 = 100_000;
+
+/// Check a profile's scan presets: names present and unique (ignoring
+/// case), a pattern and delimiter, a sane COUNT, a type SCAN can filter by,
+/// and a default that is one of them.
+pub fn validate_scan_presets(profile: &ConnectionProfile) -> Result<(), AppError> {
+    for (i, preset) in profile.scan_presets.iter().enumerate() {
+        let name = preset.name.trim();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Scan preset name must not be empty".into(),
+            ));
+        }
+        if profile.scan_presets[..i]
+            .iter()
+            .any(|other| other.name.trim().eq_ignore_ascii_case(name))
+        {
+            return Err(AppError::InvalidInput(format!(
+                "A scan preset named {name} already exists"
+            )));
+        }
+        if preset.pattern.is_empty() || preset.delimiter.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Scan preset {name} needs a pattern and a delimiter"
+            )));
+        }
+        if preset.count == 0 || preset.count > MAX_PRESET_COUNT {
+            return Err(AppError::InvalidInput(format!(
+                "Scan preset count must be between 1 and {MAX_PRESET_COUNT}"
+            )));
+        }
+        if let Some(RedisKeyType::Unknown(other)) = &preset.key_type {
+            return Err(AppError::InvalidInput(format!(
+                "Unknown key type {other} in scan preset {name}"
+            )));
+        }
+    }
+    if let Some(default) = profile.default_scan_preset {
+        if !profile.scan_presets.iter().any(|p| p.id == default) {
+            return Err(AppError::NotFound(format!(
+                "Scan preset {default} not found"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Insert `preset`, or replace the one with its ID. A nil ID gets a fresh one.
+fn upsert_scan_preset(profile: &mut ConnectionProfile, mut preset: ScanPreset) -> Uuid {
+    if preset.id.is_nil() {
+        preset.id = Uuid::new_v4();
+    }
+    preset.name = preset.name.trim().to_string();
+    let id = preset.id;
+    match profile.scan_presets.iter_mut().find(|p| p.id == id) {
+        Some(existing) => *existing = preset,
+        None => profile.scan_presets.push(preset),
+    }
+    id
+}
+
+/// Remove a preset, clearing the default if it was that one.
+fn remove_scan_preset(profile: &mut ConnectionProfile, id: &Uuid) -> Result<(), AppError> {
+    let original_len = profile.scan_presets.len();
+    profile.scan_presets.retain(|p| &p.id != id);
+    if profile.scan_presets.len() == original_len {
+        return Err(AppError::NotFound(format!("Scan preset {id} not found")));
+    }
+    if profile.default_scan_preset.as_ref() == Some(id) {
+        profile.default_scan_preset = None;
+    }
+    Ok(())
+}
+
+/// Load a profile, change it with `apply`, validate its presets and save it.
+async fn update_profile(
+    app_handle: &tauri::AppHandle,
+    id: &Uuid,
+    apply: impl FnOnce(&mut ConnectionProfile) -> Result<(), AppError>,
+) -> Result<ConnectionProfile, AppError> {
+    let mut profiles = load_all_profiles(app_handle).await?;
+    let profile = profiles
+        .iter_mut()
+        .find(|p| &p.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Connection profile {id} not found")))?;
+    apply(profile)?;
+    validate_scan_presets(profile)?;
+    profile.updated_at = chrono::Utc::now();
+    let updated = profile.clone();
+    write_profiles(app_handle, &profiles).await?;
+    Ok(updated)
+}
+
+/// Save a scan preset on a profile, optionally as its default.
+pub async fn save_scan_preset(
+    app_handle: &tauri::AppHandle,
+    profile_id: &Uuid,
+    preset: ScanPreset,
+    make_default: bool,
+) -> Result<ConnectionProfile, AppError> {
+    update_profile(app_handle, profile_id, |profile| {
+        let id = upsert_scan_preset(profile, preset);
+        if make_default {
+            profile.default_scan_preset = Some(id);
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Delete a scan preset from a profile.
+pub async fn delete_scan_preset(
+    app_handle: &tauri::AppHandle,
+    profile_id: &Uuid,
+    preset_id: &Uuid,
+) -> Result<ConnectionProfile, AppError> {
+    update_profile(app_handle, profile_id, |profile| {
+        remove_scan_preset(profile, preset_id)
+    })
+    .await
+}
+
+/// Choose the preset a profile's key browser opens with, or none for `*`.
+pub async fn set_default_scan_preset(
+    app_handle: &tauri::AppHandle,
+    profile_id: &Uuid,
+    preset_id: Option<Uuid>,
+) -> Result<ConnectionProfile, AppError> {
+    update_profile(app_handle, profile_id, |profile| {
+        profile.default_scan_preset = preset_id;
+        Ok(())
+    })
+    .await
+}
+
+// ─── Groups ──────────────────────────────────────────────────────
+
+/// Load all connection groups, in display order.
+pub async fn load_groups(app_handle: &tauri::AppHandle) -> Result<Vec<ConnectionGroup>, AppError> {
+    let path = groups_path(app_handle)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read connection groups: {e}")))?;
+    let mut groups: Vec<ConnectionGroup> = serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse connection groups: {e}")))?;
+    groups.sort_by_key(|g| g.sort_order);
+    Ok(groups)
+}
+
+/// Reject empty names and names already used by another group.
+fn validate_group_name(
+    groups: &[ConnectionGroup],
+    name: &str,
+    own_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Group name must not be empty".into(),
+        ));
+    }
+    let taken = groups
+        .iter()
+        .any(|g| Some(g.id) != own_id && g.name.eq_ignore_ascii_case(name.trim()));
+    if taken {
+        return Err(AppError::InvalidInput(format!(
+            "A group named {} already exists",
+            name.trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Create a group at the end of the list.
+pub async fn create_group(
+    app_handle: &tauri::AppHandle,
+    name: &str,
+) -> Result<ConnectionGroup, AppError> {
+    let mut groups = load_groups(app_handle).await?;
+    validate_group_name(&groups, name, None)?;
+
+    let group = ConnectionGroup {
+        id: Uuid::new_v4(),
+        name: name.trim().to_string(),
+        sort_order: groups.iter().map(|g| g.sort_order + 1).max().unwrap_or(0),
+    };
+    groups.push(group.clone());
+    write_groups(app_handle, &groups).await?;
+    Ok(group)
+}
+
+/// Rename a group.
+pub async fn rename_group(
+    app_handle: &tauri::AppHandle,
+    id: &Uuid,
+    name: &str,
+) -> Result<ConnectionGroup, AppError> {
+    let mut groups = load_groups(app_handle).await?;
+    validate_group_name(&groups, name, Some(*id))?;
+
+    let group = groups
+        .iter_mut()
+        .find(|g| &g.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Connection group {id} not found")))?;
+    group.name = name.trim().to_string();
+    let renamed = group.clone();
+
+    write_groups(app_handle, &groups).await?;
+    Ok(renamed)
+}
+
+/// Delete a group, moving its profiles to the top level.
+pub async fn delete_group(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<(), AppError> {
+    let mut groups = load_groups(app_handle).await?;
+    let original_len = groups.len();
+    groups.retain(|g| &g.id != id);
+    if groups.len() == original_len {
+        return Err(AppError::NotFound(format!(
+            "Connection group {id} not found"
+        )));
+    }
+
+    let mut profiles = load_all_profiles(app_handle).await?;
+    let mut orphaned = false;
+    for profile in profiles.iter_mut().filter(|p| p.group_id == Some(*id)) {
+        profile.group_id = None;
+        orphaned = true;
+    }
+    if orphaned {
+        write_profiles(app_handle, &profiles).await?;
+    }
+    write_groups(app_handle, &groups).await
+}
+
+/// Set the display order of groups; groups not listed keep their position after them.
+pub async fn reorder_groups(app_handle: &tauri::AppHandle, ids: &[Uuid]) -> Result<(), AppError> {
+    let mut groups = load_groups(app_handle).await?;
+    for id in ids {
+        if !groups.iter().any(|g| &g.id == id) {
+            return Err(AppError::NotFound(format!(
+                "Connection group {id} not found"
+            )));
+        }
+    }
+    groups.sort_by_key(|g| ids.iter().position(|id| id == &g.id).unwrap_or(usize::MAX));
+    for (position, group) in groups.iter_mut().enumerate() {
+        group.sort_order = u32::try_from(position).unwrap_or(u32::MAX);
+    }
+    write_groups(app_handle, &groups).await
+}
+
+async fn write_groups(
+    app_handle: &tauri::AppHandle,
+    groups: &[ConnectionGroup],
+) -> Result<(), AppError> {
+    let path = groups_path(app_handle)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+    let data = serde_json::to_string_pretty(groups)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize connection groups: {e}")))?;
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write connection groups: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> ConnectionProfile {
+        ConnectionProfile::new_standalone(name.into(), "localhost".into(), 6379)
+    }
+
+    #[test]
+    fn test_apply_order_and_sort() {
+        let mut profiles = vec![profile("b"), profile("a"), profile("c")];
+        let group = Uuid::new_v4();
+        let ids = [profiles[2].id, profiles[0].id];
+        apply_order(&mut profiles, Some(group), &ids).unwrap();
+
+        assert_eq!(profiles[2].group_id, Some(group));
+        assert_eq!(profiles[2].sort_order, 0);
+        assert_eq!(profiles[0].sort_order, 1);
+        assert!(profiles[1].group_id.is_none());
+
+        sort_profiles(&mut profiles);
+        let names: Vec<_> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn test_apply_order_unknown_id() {
+        let mut profiles = vec![profile("a")];
+        let err = apply_order(&mut profiles, None, &[Uuid::new_v4()]).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    fn preset(name: &str) -> ScanPreset {
+        ScanPreset {
+            id: Uuid::nil(),
+            name: name.into(),
+            pattern: format!("{name}:*"),
+            count: 500,
+            delimiter: ":".into(),
+            key_type: None,
+        }
+    }
+
+    #[test]
+    fn test_scan_preset_upsert_and_remove() {
+        let mut profile = profile("a");
+        let orders = upsert_scan_preset(&mut profile, preset(" orders "));
+        assert!(!orders.is_nil());
+        assert_eq!(profile.scan_presets[0].name, "orders");
+
+        let mut edited = profile.scan_presets[0].clone();
+        edited.pattern = "orders:2024:*".into();
+        assert_eq!(upsert_scan_preset(&mut profile, edited), orders);
+        assert_eq!(profile.scan_presets.len(), 1);
+        assert_eq!(profile.scan_presets[0].pattern, "orders:2024:*");
+
+        profile.default_scan_preset = Some(orders);
+        remove_scan_preset(&mut profile, &orders).unwrap();
+        assert!(profile.scan_presets.is_empty());
+        assert!(profile.default_scan_preset.is_none());
+        assert!(matches!(
+            remove_scan_preset(&mut profile, &orders),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_scan_presets() {
+        let mut profile = profile("a");
+        upsert_scan_preset(&mut profile, preset("users"));
+        assert!(validate_scan_presets(&profile).is_ok());
+
+        upsert_scan_preset(&mut profile, preset("USERS"));
+        assert!(validate_scan_presets(&profile).is_err());
+        profile.scan_presets.pop();
+
+        profile.scan_presets[0].count = 0;
+        assert!(validate_scan_presets(&profile).is_err());
+        profile.scan_presets[0].count = 500;
+
+        profile.scan_presets[0].key_type = Some(RedisKeyType::Unknown("blob".into()));
+        assert!(validate_scan_presets(&profile).is_err());
+        profile.scan_presets[0].key_type = Some(RedisKeyType::Hash);
+
+        profile.default_scan_preset = Some(Uuid::new_v4());
+        assert!(matches!(
+            validate_scan_presets(&profile),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec![" prod ".into(), String::new(), "eu".into(), "PROD".into()];
+        assert_eq!(normalize_tags(tags), ["prod", "eu"]);
+    }
+
+    #[test]
+    fn test_validate_group_name() {
+        let existing = ConnectionGroup {
+            id: Uuid::new_v4(),
+            name: "Production".into(),
+            sort_order: 0,
+        };
+        let groups = [existing.clone()];
+        assert!(validate_group_name(&groups, "  ", None).is_err());
+        assert!(validate_group_name(&groups, "production", None).is_err());
+        assert!(validate_group_name(&groups, "Production", Some(existing.id)).is_ok());
+        assert!(validate_group_name(&groups, "Staging", None).is_ok());
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::utils::crypto::{KdfParams, SealedData, SealingKey};
+use crate::utils::errors::AppError;
+
+/// Identifies an encrypted `connections.json`.
+const ENVELOPE_FORMAT: &str = "redis-lens/encrypted-profiles";
+
+/// On-disk form of the profile list when encryption at rest is enabled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedProfiles {
+    pub format: String,
+    pub sealed: SealedData,
+}
+
+/// Whether the profile store is encrypted, and if so whether it is unlocked.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileStoreStatus {
+    pub encrypted: bool,
+    pub locked: bool,
+}
+
+/// Contents of `connections.json`, which is either a plain profile array or
+/// an encrypted envelope.
+pub enum StoredProfiles {
+    Plain(String),
+    Encrypted(EncryptedProfiles),
+}
+
+impl StoredProfiles {
+    /// Tell the two on-disk forms apart.
+    pub fn parse(data: String) -> Result<Self, AppError> {
+        if !data.trim_start().starts_with('{') {
+            return Ok(Self::Plain(data));
+        }
+        let envelope: EncryptedProfiles = serde_json::from_str(&data)
+            .map_err(|e| AppError::Internal(format!("Failed to parse encrypted profiles: {e}")))?;
+        if envelope.format != ENVELOPE_FORMAT {
+            return Err(AppError::Internal(format!(
+                "Unrecognized profile store format: {}",
+                envelope.format
+            )));
+        }
+        Ok(Self::Encrypted(envelope))
+    }
+}
+
+/// Holds the key for an encrypted profile store while it is unlocked.
+///
+/// Only the derived key is kept, never the passphrase. Locking drops it;
+/// reads and writes of an encrypted store then fail with `AppError::Locked`.
+#[derive(Clone, Default)]
+pub struct ProfileVault {
+    key: Arc<RwLock<Option<SealingKey>>>,
+}
+
+impl ProfileVault {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a key is held.
+    pub async fn is_unlocked(&self) -> bool {
+        self.key.read().await.is_some()
+    }
+
+    /// Derive a new key for migrating a plaintext store, without holding it yet.
+    pub fn new_key(passphrase: &str) -> Result<SealingKey, AppError> {
+        SealingKey::generate(passphrase, KdfParams::default())
+    }
+
+    /// Derive the key for `envelope` and check it by decrypting; returns the plaintext.
+    pub async fn derive_key(
+        passphrase: &str,
+        envelope: &EncryptedProfiles,
+    ) -> Result<(SealingKey, Vec<u8>), AppError> {
+        let key = SealingKey::for_sealed(passphrase, &envelope.sealed).await?;
+        let plaintext = key.open(&envelope.sealed)?;
+        Ok((key, plaintext))
+    }
+
+    /// Hold `key` until [`lock`](Self::lock).
+    pub async fn unlock(&self, key: SealingKey) {
+        *self.key.write().await = Some(key);
+    }
+
+    /// Forget the key.
+    pub async fn lock(&self) {
+        *self.key.write().await = None;
+    }
+
+    /// Decrypt a stored envelope with the held key.
+    pub async fn open(&self, envelope: &EncryptedProfiles) -> Result<Vec<u8>, AppError> {
+        match self.key.read().await.as_ref() {
+            Some(key) => key.open(&envelope.sealed),
+            None => Err(locked_error()),
+        }
+    }
+
+    /// Encrypt profile JSON with the held key, or `None` if no key is held.
+    pub async fn seal(&self, plaintext: &[u8]) -> Result<Option<EncryptedProfiles>, AppError> {
+        match self.key.read().await.as_ref() {
+            Some(key) => Ok(Some(envelope(key, plaintext)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Wrap profile JSON in an encrypted envelope.
+pub fn envelope(key: &SealingKey, plaintext: &[u8]) -> Result<EncryptedProfiles, AppError> {
+    Ok(EncryptedProfiles {
+        format: ENVELOPE_FORMAT.into(),
+        sealed: key.seal(plaintext)?,
+    })
+}
+
+/// The error returned while the encrypted store is locked.
+pub fn locked_error() -> AppError {
+    AppError::Locked(
+        "Connection profiles are encrypted; unlock them with the master passphrase".into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_profiles_detects_form() {
+        assert!(matches!(
+            StoredProfiles::parse("[]".into()).unwrap(),
+            StoredProfiles::Plain(_)
+        ));
+        assert!(StoredProfiles::parse(r#"{"format":"other","sealed":{}}"#.into()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_locked_vault_refuses_open() {
+        let vault = ProfileVault::new();
+        let envelope = EncryptedProfiles {
+            format: ENVELOPE_FORMAT.into(),
+            sealed: SealedData {
+                kdf: KdfParams::default(),
+                salt: String::new(),
+                nonce: String::new(),
+                ciphertext: String::new(),
+            },
+        };
+        assert!(matches!(
+            vault.open(&envelope).await,
+            Err(AppError::Locked(_))
+        ));
+        assert!(vault.seal(b"[]").await.unwrap().is_none());
+    }
+}
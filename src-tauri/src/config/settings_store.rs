@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::redis::browser::model::NamingRules;
+use crate::redis::connection::model::ConnectionProfile;
+use crate::redis::devtools::locks;
+use crate::redis::monitor::model::MetricThresholds;
+use crate::utils::errors::AppError;
+use crate::utils::event_throttle::{self, ThrottlePolicy};
+
+/// Application-wide settings persisted to disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    /// Default cutoffs for derived monitor metrics.
+    pub metric_thresholds: MetricThresholds,
+    /// Per-event rate limits for high-frequency frontend events.
+    pub event_throttle: BTreeMap<String, ThrottlePolicy>,
+    /// Run concurrent editor writes to the same key one at a time.
+    pub serialize_key_writes: bool,
+    /// Serve current monitor metrics for Prometheus on this localhost port;
+    /// off when unset.
+    pub prometheus_port: Option<u16>,
+    /// Key patterns the lock inspector scans by default.
+    pub lock_patterns: Vec<String>,
+    /// Naming conventions the key linter checks by default.
+    pub key_naming_rules: NamingRules,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            metric_thresholds: MetricThresholds::default(),
+            event_throttle: event_throttle::default_policies(),
+            serialize_key_writes: true,
+            prometheus_port: None,
+            lock_patterns: locks::DEFAULT_LOCK_PATTERNS
+                .iter()
+                .map(|p| (*p).to_string())
+                .collect(),
+            key_naming_rules: NamingRules::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Thresholds for a profile: its own override, else the app default.
+    pub fn thresholds_for(&self, profile: &ConnectionProfile) -> MetricThresholds {
+        profile
+            .metric_thresholds
+            .clone()
+            .unwrap_or_else(|| self.metric_thresholds.clone())
+    }
+}
+
+/// Reject thresholds whose warning and critical cutoffs are inverted.
+pub fn validate_thresholds(t: &MetricThresholds) -> Result<(), AppError> {
+    if t.fragmentation_warning > t.fragmentation_critical {
+        return Err(AppError::InvalidInput(
+            "Fragmentation warning threshold must not exceed the critical threshold".into(),
+        ));
+    }
+    if t.hit_rate_critical_percent > t.hit_rate_warning_percent {
+        return Err(AppError::InvalidInput(
+            "Hit-rate critical threshold must not exceed the warning threshold".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Resolve the path to the settings JSON file.
+fn settings_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("settings.json"))
+}
+
+/// Load settings, falling back to defaults if none are saved yet.
+pub async fn load_settings(app_handle: &tauri::AppHandle) -> Result<AppSettings, AppError> {
+    let path = settings_path(app_handle)?;
+    if !path.exists() {
+        return Ok(AppSettings::default());
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read settings: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse settings: {e}")))
+}
+
+/// Persist settings, creating the directory if needed.
+pub async fn save_settings(
+    app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+) -> Result<(), AppError> {
+    let path = settings_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string_pretty(settings)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write settings: {e}")))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: AppSettings = serde_json::from_str("{}").expect("deserialize");
+        assert_eq!(settings.metric_thresholds, MetricThresholds::default());
+        assert_eq!(settings.event_throttle, event_throttle::default_policies());
+    }
+
+    #[test]
+    fn test_validate_thresholds() {
+        assert!(validate_thresholds(&MetricThresholds::default()).is_ok());
+        let inverted = MetricThresholds {
+            fragmentation_warning: 2.5,
+            ..MetricThresholds::default()
+        };
+        assert!(validate_thresholds(&inverted).is_err());
+    }
+
+    #[test]
+    fn test_profile_override_wins() {
+        let settings = AppSettings::default();
+        let mut profile =
+            ConnectionProfile::new_standalone("test".into(), "localhost".into(), 6379);
+        assert_eq!(
+            settings.thresholds_for(&profile),
+            MetricThresholds::default()
+        );
+
+        let custom = MetricThresholds {
+            hit_rate_warning_percent: 99.0,
+            ..MetricThresholds::default()
+        };
+        profile.metric_thresholds = Some(custom.clone());
+        assert_eq!(settings.thresholds_for(&profile), custom);
+    }
+}
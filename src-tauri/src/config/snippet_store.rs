@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::redis::cli::model::Snippet;
+use crate::utils::errors::AppError;
+
+/// Resolve the path to the snippets JSON file.
+fn snippets_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("snippets.json"))
+}
+
+/// Load all saved snippets, sorted by name.
+pub async fn load_snippets(app_handle: &tauri::AppHandle) -> Result<Vec<Snippet>, AppError> {
+    let path = snippets_path(app_handle)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read snippets: {e}")))?;
+    let mut snippets: Vec<Snippet> = serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse snippets: {e}")))?;
+    snippets.sort_by_key(|s| s.name.to_lowercase());
+    Ok(snippets)
+}
+
+/// Load a single snippet by ID.
+pub async fn get_snippet(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<Snippet, AppError> {
+    load_snippets(app_handle)
+        .await?
+        .into_iter()
+        .find(|s| s.id == *id)
+        .ok_or_else(|| AppError::NotFound(format!("Snippet {id} not found")))
+}
+
+/// Insert or replace a snippet.
+pub async fn save_snippet(
+    app_handle: &tauri::AppHandle,
+    snippet: &Snippet,
+) -> Result<(), AppError> {
+    let mut snippets = load_snippets(app_handle).await?;
+    match snippets.iter_mut().find(|s| s.id == snippet.id) {
+        Some(existing) => *existing = snippet.clone(),
+        None => snippets.push(snippet.clone()),
+    }
+    write_all(app_handle, &snippets).await
+}
+
+/// Delete a snippet by ID.
+pub async fn delete_snippet(app_handle: &tauri::AppHandle, id: &Uuid) -> Result<(), AppError> {
+    let mut snippets = load_snippets(app_handle).await?;
+    let before = snippets.len();
+    snippets.retain(|s| s.id != *id);
+    if snippets.len() == before {
+        return Err(AppError::NotFound(format!("Snippet {id} not found")));
+    }
+    write_all(app_handle, &snippets).await
+}
+
+/// Write snippets to disk, creating the directory if needed.
+async fn write_all(app_handle: &tauri::AppHandle, snippets: &[Snippet]) -> Result<(), AppError> {
+    let path = snippets_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string_pretty(snippets)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize snippets: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write snippets: {e}")))?;
+
+    Ok(())
+}
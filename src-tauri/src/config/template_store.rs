@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use crate::redis::templates::builtin;
+use crate::redis::templates::model::AppTemplate;
+use crate::utils::errors::AppError;
+
+/// Resolve the path to the user templates JSON file.
+fn templates_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("templates.json"))
+}
+
+/// Load the templates the user defined, in the order they were added.
+async fn load_user_templates(app_handle: &tauri::AppHandle) -> Result<Vec<AppTemplate>, AppError> {
+    let path = templates_path(app_handle)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read templates: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse templates: {e}")))
+}
+
+/// All templates: user templates first, so they can take over keys a
+/// built-in one also matches, then the built-in ones.
+pub async fn load_templates(app_handle: &tauri::AppHandle) -> Result<Vec<AppTemplate>, AppError> {
+    let mut templates = load_user_templates(app_handle).await?;
+    templates.extend(builtin::templates());
+    Ok(templates)
+}
+
+/// Load a single template by ID.
+pub async fn get_template(
+    app_handle: &tauri::AppHandle,
+    id: &str,
+) -> Result<AppTemplate, AppError> {
+    load_templates(app_handle)
+        .await?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Template {id} not found")))
+}
+
+/// Insert or replace a user template.
+pub async fn save_template(
+    app_handle: &tauri::AppHandle,
+    template: &AppTemplate,
+) -> Result<(), AppError> {
+    let mut templates = load_user_templates(app_handle).await?;
+    match templates.iter_mut().find(|t| t.id == template.id) {
+        Some(existing) => *existing = template.clone(),
+        None => templates.push(template.clone()),
+    }
+    write_all(app_handle, &templates).await
+}
+
+/// Delete a user template by ID.
+pub async fn delete_template(app_handle: &tauri::AppHandle, id: &str) -> Result<(), AppError> {
+    let mut templates = load_user_templates(app_handle).await?;
+    let before = templates.len();
+    templates.retain(|t| t.id != id);
+    if templates.len() == before {
+        return Err(AppError::NotFound(format!("Template {id} not found")));
+    }
+    write_all(app_handle, &templates).await
+}
+
+/// Write user templates to disk, creating the directory if needed.
+async fn write_all(
+    app_handle: &tauri::AppHandle,
+    templates: &[AppTemplate],
+) -> Result<(), AppError> {
+    let path = templates_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string_pretty(templates)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize templates: {e}")))?;
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write templates: {e}")))?;
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::AppError;
+
+/// Open tabs remembered per profile.
+const MAX_TABS: usize = 50;
+/// Recent scan patterns remembered per profile.
+const MAX_SCAN_PATTERNS: usize = 20;
+
+/// What was open when the app last ran, restored on the next start.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Workspace {
+    /// Profile selected in the connection list.
+    pub last_connection_id: Option<String>,
+    /// Keyed by profile ID.
+    pub profiles: BTreeMap<String, ProfileWorkspace>,
+}
+
+/// Per-profile session state.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProfileWorkspace {
+    pub open_tabs: Vec<WorkspaceTab>,
+    /// Index into `open_tabs`.
+    pub active_tab: Option<usize>,
+    /// Recent browser scan patterns, newest first.
+    pub scan_patterns: Vec<String>,
+    /// Key tree delimiter, when changed from the default.
+    pub tree_delimiter: Option<String>,
+    pub editor: EditorViewPrefs,
+}
+
+/// A key open in an editor tab.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTab {
+    pub key: String,
+    /// Database the key was opened from; `None` for the profile's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<u8>,
+}
+
+/// How the value editor displays values.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EditorViewPrefs {
+    /// Display format, e.g. `"json"`, `"raw"` or `"hex"`.
+    pub value_format: Option<String>,
+    pub word_wrap: Option<bool>,
+    /// Elements per page for collection types.
+    pub page_size: Option<u32>,
+}
+
+impl ProfileWorkspace {
+    /// Drop empty and duplicate entries and cap list sizes, keeping
+    /// `active_tab` pointing at the same tab (or nothing).
+    pub fn normalize(&mut self) {
+        let active = self.active_tab.and_then(|i| self.open_tabs.get(i)).cloned();
+        let mut tabs: Vec<WorkspaceTab> = Vec::new();
+        for tab in self.open_tabs.drain(..) {
+            if !tab.key.is_empty() && !tabs.contains(&tab) {
+                tabs.push(tab);
+            }
+        }
+        self.open_tabs = tabs;
+        self.open_tabs.truncate(MAX_TABS);
+        self.active_tab = active.and_then(|tab| self.open_tabs.iter().position(|t| *t == tab));
+
+        let mut patterns: Vec<String> = Vec::new();
+        for pattern in self.scan_patterns.drain(..) {
+            if !pattern.is_empty() && !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+        patterns.truncate(MAX_SCAN_PATTERNS);
+        self.scan_patterns = patterns;
+
+        if self.tree_delimiter.as_deref() == Some("") {
+            self.tree_delimiter = None;
+        }
+    }
+}
+
+/// Resolve the path to the workspace JSON file.
+fn workspace_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("workspace.json"))
+}
+
+/// Load the workspace, empty if none is saved yet.
+pub async fn load_workspace(app_handle: &tauri::AppHandle) -> Result<Workspace, AppError> {
+    let path = workspace_path(app_handle)?;
+    if !path.exists() {
+        return Ok(Workspace::default());
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read workspace: {e}")))?;
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse workspace: {e}")))
+}
+
+/// Replace one profile's state.
+pub async fn save_profile_workspace(
+    app_handle: &tauri::AppHandle,
+    profile_id: &str,
+    state: ProfileWorkspace,
+) -> Result<(), AppError> {
+    let mut workspace = load_workspace(app_handle).await?;
+    workspace.profiles.insert(profile_id.to_string(), state);
+    save_workspace(app_handle, &workspace).await
+}
+
+/// Remember the selected profile.
+pub async fn set_last_connection(
+    app_handle: &tauri::AppHandle,
+    connection_id: Option<String>,
+) -> Result<(), AppError> {
+    let mut workspace = load_workspace(app_handle).await?;
+    workspace.last_connection_id = connection_id;
+    save_workspace(app_handle, &workspace).await
+}
+
+/// Forget a deleted profile's state.
+pub async fn remove_profile(
+    app_handle: &tauri::AppHandle,
+    profile_id: &str,
+) -> Result<(), AppError> {
+    let mut workspace = load_workspace(app_handle).await?;
+    let removed = workspace.profiles.remove(profile_id).is_some();
+    let was_last = workspace.last_connection_id.as_deref() == Some(profile_id);
+    if was_last {
+        workspace.last_connection_id = None;
+    }
+    if removed || was_last {
+        save_workspace(app_handle, &workspace).await?;
+    }
+    Ok(())
+}
+
+/// Persist the workspace, creating the directory if needed.
+async fn save_workspace(
+    app_handle: &tauri::AppHandle,
+    workspace: &Workspace,
+) -> Result<(), AppError> {
+    let path = workspace_path(app_handle)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create config dir: {e}")))?;
+    }
+
+    let data = serde_json::to_string_pretty(workspace)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize workspace: {e}")))?;
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write workspace: {e}")))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(key: &str) -> WorkspaceTab {
+        WorkspaceTab {
+            key: key.into(),
+            db: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let workspace: Workspace =
+            serde_json::from_str(r#"{"profiles":{"p1":{"treeDelimiter":"/"}}}"#).unwrap();
+        let profile = &workspace.profiles["p1"];
+        assert_eq!(profile.tree_delimiter.as_deref(), Some("/"));
+        assert!(profile.open_tabs.is_empty());
+        assert_eq!(profile.editor, EditorViewPrefs::default());
+    }
+
+    #[test]
+    fn test_normalize_dedupes_and_tracks_active_tab() {
+        let mut state = ProfileWorkspace {
+            open_tabs: vec![tab("a"), tab(""), tab("a"), tab("b")],
+            active_tab: Some(3),
+            scan_patterns: vec!["user:*".into(), String::new(), "user:*".into()],
+            tree_delimiter: Some(String::new()),
+            ..ProfileWorkspace::default()
+        };
+        state.normalize();
+        assert_eq!(state.open_tabs, [tab("a"), tab("b")]);
+        assert_eq!(state.active_tab, Some(1));
+        assert_eq!(state.scan_patterns, ["user:*"]);
+        assert_eq!(state.tree_delimiter, None);
+
+        state.active_tab = Some(9);
+        state.normalize();
+        assert_eq!(state.active_tab, None);
+    }
+}
@@ -11,15 +11,41 @@ pub mod config;
 pub mod redis;
 pub mod utils;
 
+use config::profile_vault::ProfileVault;
+use redis::audit::log::AuditLog;
+use redis::backup::scheduler::BackupScheduler;
+use redis::browser::invalidation::KeyspaceWatcher;
+use redis::browser::key_index::KeyIndex;
+use redis::browser::keyspace::KeyspaceSnapshotter;
+use redis::cli::history::CliHistory;
+use redis::cli::requests::CliRequests;
+use redis::confirm::registry::ConfirmationRegistry;
+use redis::connection::latency_probe::LatencyProber;
 use redis::connection::manager::ConnectionManager;
+use redis::connection::push::PushChannels;
+use redis::connection::supervisor::ConnectionSupervisor;
+use redis::devtools::benchmark::Benchmarks;
+use redis::devtools::tracking::TrackingSessions;
+use redis::editor::expiry_watch::ExpiryWatcher;
+use redis::editor::key_watch::KeyWatcher;
+use redis::editor::undo::UndoJournal;
+use redis::editor::write_queue::KeyWriteQueue;
+use redis::jobs::scheduler::JobScheduler;
+use redis::monitor::log_tail::LogTailer;
+use redis::monitor::metrics::MetricsHistory;
 use redis::monitor::poller::MonitorPoller;
+use redis::monitor::prometheus::PrometheusExporter;
+use redis::monitor::slow_log::SlowLogTracker;
 use tracing_subscriber::EnvFilter;
+use utils::event_throttle::EventThrottler;
+use utils::tasks::TaskRegistry;
 
 /// Initialize the Tauri application.
 ///
 /// Registers all IPC command handlers, sets up managed state,
 /// and launches the native window.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
+#[allow(clippy::too_many_lines)] // the IPC handler list grows with every command
 pub fn run() {
     // Initialize structured logging
     tracing_subscriber::fmt()
@@ -32,12 +58,76 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(ConnectionManager::new())
+        .manage(ProfileVault::new())
+        .manage(ConnectionSupervisor::new())
+        .manage(LatencyProber::new())
+        .manage(PushChannels::new())
         .manage(MonitorPoller::new())
-        .manage(commands::cli::CliHistory::new())
+        .manage(MetricsHistory::new())
+        .manage(PrometheusExporter::new())
+        .manage(KeyspaceSnapshotter::new())
+        .manage(KeyIndex::new())
+        .manage(KeyspaceWatcher::new())
+        .manage(BackupScheduler::new())
+        .manage(ConfirmationRegistry::new())
+        .manage(UndoJournal::new())
+        .manage(KeyWriteQueue::new())
+        .manage(CliRequests::new())
+        .manage(KeyWatcher::new())
+        .manage(ExpiryWatcher::new())
+        .manage(LogTailer::new())
+        .manage(SlowLogTracker::new())
+        .manage(JobScheduler::new())
+        .manage(Benchmarks::new())
+        .manage(TrackingSessions::new())
         .manage(redis::pubsub::subscriber::PubSubManager::new())
+        .manage(EventThrottler::new())
+        .manage(TaskRegistry::new())
+        .setup(|app| {
+            use tauri::Manager;
+            let data_dir = app.path().app_data_dir()?;
+            app.manage(AuditLog::new(data_dir.join("audit")));
+            app.manage(CliHistory::new(data_dir.join("cli_history")));
+
+            // Apply saved throttle limits, write serialization and the
+            // Prometheus endpoint; the built-in defaults stay until then.
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                match config::settings_store::load_settings(&handle).await {
+                    Ok(settings) => {
+                        handle
+                            .state::<EventThrottler>()
+                            .configure(settings.event_throttle)
+                            .await;
+                        handle
+                            .state::<KeyWriteQueue>()
+                            .configure(settings.serialize_key_writes);
+                        let history = handle.state::<MetricsHistory>().inner().clone();
+                        if let Err(e) = handle
+                            .state::<PrometheusExporter>()
+                            .configure(settings.prometheus_port, history)
+                            .await
+                        {
+                            tracing::warn!("Failed to start Prometheus endpoint: {e}");
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to load settings: {e}"),
+                }
+            });
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = handle.state::<JobScheduler>().restore(&handle).await {
+                    tracing::warn!("Failed to restore scheduled jobs: {e}");
+                }
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::health::health_check,
+            commands::health::health_diagnostics,
             commands::connection::connection_test,
             commands::connection::connection_parse_uri,
             commands::connection::connection_save,
@@ -46,28 +136,99 @@ pub fn run() {
             commands::connection::connection_connect,
             commands::connection::connection_disconnect,
             commands::connection::connection_state,
+            commands::connection::connection_start_latency_probe,
+            commands::connection::connection_stop_latency_probe,
+            commands::connection::connection_latency_stats,
+            commands::connection::connection_open_push_channel,
+            commands::connection::connection_push_execute,
+            commands::connection::connection_close_push_channel,
+            commands::connection::connection_set_write_concern,
+            commands::connection::connection_write_concern,
+            commands::connection::connection_capabilities,
+            commands::connection::connection_config_drift,
+            commands::connection::connection_list_databases,
+            commands::connection::connection_flush_db,
+            commands::connection::connection_flush_all,
+            commands::connection::connection_export,
+            commands::connection::connection_import,
+            commands::connection::connection_reorder,
+            commands::connection::connection_save_scan_preset,
+            commands::connection::connection_delete_scan_preset,
+            commands::connection::connection_set_default_scan_preset,
+            commands::connection::connection_group_list,
+            commands::connection::connection_group_create,
+            commands::connection::connection_group_rename,
+            commands::connection::connection_group_delete,
+            commands::connection::connection_group_reorder,
+            commands::connection::connection_store_status,
+            commands::connection::connection_store_enable_encryption,
+            commands::connection::connection_store_unlock,
+            commands::connection::connection_store_lock,
+            commands::connection::connection_store_disable_encryption,
+            // Backup commands
+            commands::backup::backup_run_now,
+            commands::backup::backup_list,
+            commands::backup::backup_verify,
             // Browser commands
             commands::browser::browser_scan_keys,
+            commands::browser::browser_scan_stream,
             commands::browser::browser_build_tree,
             commands::browser::browser_get_children,
             commands::browser::browser_get_keys_info,
+            commands::browser::browser_list_keys,
+            commands::browser::browser_filter_keys,
+            commands::browser::browser_namespace_stats,
             commands::browser::browser_get_key_info,
+            commands::browser::browser_introspect_key,
             commands::browser::browser_delete_keys,
             commands::browser::browser_rename_key,
+            commands::browser::browser_rename_by_pattern,
+            commands::browser::browser_delete_by_pattern,
+            commands::browser::browser_set_ttl_by_pattern,
+            commands::browser::browser_capture_keyspace_snapshot,
+            commands::browser::browser_sample_keyspace,
+            commands::browser::browser_tenant_report,
+            commands::browser::browser_lint_keys,
+            commands::browser::browser_infer_schema,
+            commands::browser::browser_search_values,
+            commands::browser::browser_cancel_value_search,
+            commands::browser::browser_start_keyspace_snapshots,
+            commands::browser::browser_stop_keyspace_snapshots,
+            commands::browser::browser_list_keyspace_snapshots,
+            commands::browser::browser_compare_keyspace_snapshots,
+            commands::browser::browser_delete_keyspace_snapshot,
+            commands::browser::browser_watch_keyspace,
+            commands::browser::browser_unwatch_keyspace,
             // Editor commands — string
             commands::editor::editor_get_string_value,
             commands::editor::editor_set_string_value,
             commands::editor::editor_get_string_range,
+            commands::editor::editor_incr_string,
+            commands::editor::editor_incr_string_float,
+            commands::editor::editor_append_string,
+            commands::editor::editor_set_string_range,
+            commands::editor::editor_set_string_binary,
+            commands::editor::editor_set_string_range_binary,
             // Editor commands — hash
             commands::editor::editor_get_hash_all,
             commands::editor::editor_scan_hash_fields,
             commands::editor::editor_set_hash_field,
             commands::editor::editor_delete_hash_fields,
+            commands::editor::editor_set_hash_fields,
+            commands::editor::editor_get_hash_fields,
+            commands::editor::editor_incr_hash_field,
+            commands::editor::editor_incr_hash_field_float,
+            commands::editor::editor_sample_hash_fields,
             // Editor commands — list
             commands::editor::editor_get_list_range,
             commands::editor::editor_push_list_element,
             commands::editor::editor_set_list_element,
             commands::editor::editor_remove_list_element,
+            commands::editor::editor_insert_list_element,
+            commands::editor::editor_delete_list_index,
+            commands::editor::editor_move_list_element,
+            commands::editor::editor_list_positions,
+            commands::editor::editor_search_in_key,
             // Editor commands — set
             commands::editor::editor_get_set_members,
             commands::editor::editor_scan_set_members,
@@ -78,51 +239,201 @@ pub fn run() {
             commands::editor::editor_scan_zset_members,
             commands::editor::editor_add_zset_member,
             commands::editor::editor_remove_zset_members,
+            commands::editor::editor_add_zset_members_bulk,
             commands::editor::editor_incr_zset_score,
+            commands::editor::editor_get_zset_range_by,
+            commands::editor::editor_get_zset_member_rank,
+            commands::editor::editor_zset_count,
             commands::editor::editor_zset_card,
             // Editor commands — stream
             commands::editor::editor_get_stream_range,
             commands::editor::editor_get_stream_range_rev,
             commands::editor::editor_add_stream_entry,
             commands::editor::editor_delete_stream_entries,
+            commands::editor::editor_trim_stream,
             commands::editor::editor_get_stream_info,
             // Editor commands — JSON
             commands::editor::editor_get_json_value,
             commands::editor::editor_set_json_value,
+            commands::editor::editor_diff_json_value,
+            commands::editor::editor_patch_json_value,
             // Editor commands — HyperLogLog
             commands::editor::editor_get_hll_info,
             commands::editor::editor_add_hll_elements,
             // Editor commands — bitmap
             commands::editor::editor_get_bitmap_info,
             commands::editor::editor_set_bitmap_bit,
+            commands::editor::editor_bitfield_ops,
             // Editor commands — geospatial
             commands::editor::editor_get_geo_members,
             commands::editor::editor_add_geo_member,
             commands::editor::editor_geo_distance,
             commands::editor::editor_remove_geo_members,
+            commands::editor::editor_geo_search,
             // Editor commands — TTL
             commands::editor::editor_get_ttl,
             commands::editor::editor_set_ttl,
+            commands::editor::editor_set_expiry,
             commands::editor::editor_persist_key,
+            // Editor commands — undo
+            commands::editor::editor_stream_value,
+            commands::editor::editor_read_full_value,
+            commands::editor::editor_export_key_value,
+            commands::editor::editor_stream_string,
+            commands::editor::editor_cancel_string_stream,
+            commands::editor::editor_watch_key,
+            commands::editor::editor_unwatch_key,
+            commands::editor::editor_watched_keys,
+            commands::editor::editor_watch_expiry,
+            commands::editor::editor_unwatch_expiry,
+            commands::editor::editor_expiry_watches,
+            commands::editor::editor_undo_last,
+            commands::editor::editor_undo_history,
+            commands::editor::editor_write_queue_depths,
+            // Diff commands
+            commands::diff::diff_keys,
+            commands::diff::diff_capture_config,
+            commands::diff::diff_config,
+            // Dev tools commands
+            commands::devtools::devtools_generate,
+            commands::devtools::devtools_cancel_generate,
+            commands::devtools::devtools_benchmark,
+            commands::devtools::devtools_stop_benchmark,
+            commands::devtools::devtools_validate_replay,
+            commands::devtools::devtools_replay,
+            commands::devtools::devtools_cancel_replay,
+            commands::devtools::devtools_start_tracking,
+            commands::devtools::devtools_tracking_stats,
+            commands::devtools::devtools_stop_tracking,
+            commands::devtools::devtools_scan_locks,
+            commands::devtools::devtools_release_lock,
             // Monitor commands
             commands::monitor::monitor_server_info,
             commands::monitor::monitor_start_polling,
             commands::monitor::monitor_stop_polling,
+            commands::monitor::monitor_subscribe,
+            commands::monitor::monitor_unsubscribe,
             commands::monitor::monitor_slow_log,
+            commands::monitor::monitor_slow_log_len,
+            commands::monitor::monitor_slow_log_reset,
+            commands::monitor::monitor_slow_log_groups,
             commands::monitor::monitor_client_list,
             commands::monitor::monitor_kill_client,
+            commands::monitor::monitor_kill_clients,
             commands::monitor::monitor_memory_stats,
+            commands::monitor::monitor_upgrade_advisor,
+            commands::monitor::monitor_persistence_status,
+            commands::monitor::monitor_bgsave,
+            commands::monitor::monitor_bgrewriteaof,
+            commands::monitor::monitor_start_log_tail,
+            commands::monitor::monitor_stop_log_tail,
+            commands::monitor::monitor_alerts_history,
+            commands::monitor::monitor_metrics_history,
+            commands::monitor::monitor_export_metrics,
+            commands::monitor::monitor_pin_snapshot,
+            commands::monitor::monitor_pinned_snapshots,
+            commands::monitor::monitor_delete_snapshot,
+            commands::monitor::monitor_diff_snapshots,
             // CLI commands
             commands::cli::cli_execute,
+            commands::cli::cli_check_permission,
+            commands::cli::cli_execute_broadcast,
+            commands::cli::cli_cancel,
             commands::cli::cli_get_command_suggestions,
+            commands::cli::cli_get_command_help,
+            commands::cli::cli_complete,
             commands::cli::cli_get_command_history,
+            commands::cli::cli_search_history,
+            commands::cli::cli_get_pinned_commands,
+            commands::cli::cli_set_command_pinned,
+            // Snippet commands
+            commands::snippet::snippet_list,
+            commands::snippet::snippet_save,
+            commands::snippet::snippet_delete,
+            commands::snippet::snippet_get_parameters,
+            commands::snippet::snippet_execute,
+            commands::template::template_list,
+            commands::template::template_save,
+            commands::template::template_delete,
+            commands::template::template_inspect_key,
+            commands::template::template_scan,
+            // Job queue commands
+            commands::queues::queue_dashboard,
+            commands::queues::queue_move_job,
+            // Scheduled job commands
+            commands::job::job_list,
+            commands::job::job_save,
+            commands::job::job_delete,
+            commands::job::job_set_enabled,
+            commands::job::job_run_now,
+            commands::job::job_runs,
+            // Settings commands
+            commands::settings::settings_get,
+            commands::settings::settings_update,
+            // Workspace commands
+            commands::workspace::workspace_get,
+            commands::workspace::workspace_save_profile,
+            commands::workspace::workspace_set_last_connection,
+            // Confirmation commands
+            commands::confirm::confirm_cancel,
             // Pub/Sub commands
             commands::pubsub::pubsub_subscribe,
             commands::pubsub::pubsub_psubscribe,
             commands::pubsub::pubsub_unsubscribe,
+            commands::pubsub::pubsub_pause,
+            commands::pubsub::pubsub_resume,
             commands::pubsub::pubsub_publish,
+            commands::pubsub::pubsub_publish_binary,
             commands::pubsub::pubsub_get_active_channels,
+            commands::pubsub::pubsub_start_recording,
+            commands::pubsub::pubsub_stop_recording,
+            commands::pubsub::pubsub_replay_recording,
+            commands::pubsub::pubsub_stop_replay,
+            // Audit commands
+            commands::audit::audit_query,
+            // Task commands
+            commands::task::task_cancel,
+            commands::task::task_list,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running RedisLens");
+        .build(tauri::generate_context!())
+        .expect("error while building RedisLens")
+        .run(|app, event| {
+            if let tauri::RunEvent::ExitRequested { .. } = event {
+                tauri::async_runtime::block_on(shutdown(app));
+            }
+        });
+}
+
+/// Stop background work before the process exits: supervisors first so
+/// nothing reconnects, then per-connection tasks, app-wide jobs and
+/// long-running analyses, and finally the pools and SSH tunnels.
+async fn shutdown(app: &tauri::AppHandle) {
+    use tauri::Manager;
+    tracing::info!("Shutting down background tasks");
+
+    app.state::<ConnectionSupervisor>().stop_all().await;
+    let manager = app.state::<ConnectionManager>();
+    for (id, _) in manager.list_active().await {
+        app.state::<LatencyProber>().stop(&id).await;
+        app.state::<PushChannels>().close(&id).await;
+        app.state::<TrackingSessions>().stop(&id).await;
+        app.state::<KeyspaceWatcher>().stop(&id).await;
+        app.state::<LogTailer>().stop(&id).await;
+        app.state::<BackupScheduler>().stop(&id.to_string()).await;
+    }
+
+    app.state::<MonitorPoller>().stop_all().await;
+    app.state::<PrometheusExporter>().stop().await;
+    app.state::<KeyspaceSnapshotter>().stop_all().await;
+    app.state::<JobScheduler>().stop_all().await;
+    app.state::<redis::pubsub::subscriber::PubSubManager>()
+        .shutdown()
+        .await;
+    app.state::<Benchmarks>().stop_all().await;
+    let aborted = app.state::<TaskRegistry>().cancel_all().await;
+    if aborted > 0 {
+        tracing::info!(count = aborted, "Background tasks aborted (shutdown)");
+    }
+
+    manager.disconnect_all().await;
 }
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use super::model::{AuditEntry, AuditQuery};
+use crate::utils::errors::AppError;
+
+/// Size at which a connection's log is rotated to `<id>.1.jsonl`.
+const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+/// Entries returned by a query when no limit is given.
+const DEFAULT_QUERY_LIMIT: usize = 200;
+
+/// Append-only log of mutations, one JSON-lines file per connection.
+///
+/// Recording never fails the operation being audited; write errors are
+/// logged and dropped.
+#[derive(Clone)]
+pub struct AuditLog {
+    dir: PathBuf,
+    /// Serializes appends and rotation.
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl AuditLog {
+    /// Create a log that stores its files in `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    fn path(&self, connection_id: &str, generation: u8) -> Result<PathBuf, AppError> {
+        // Connection IDs are UUIDs; anything else could escape the directory.
+        uuid::Uuid::parse_str(connection_id)?;
+        Ok(match generation {
+            0 => self.dir.join(format!("{connection_id}.jsonl")),
+            n => self.dir.join(format!("{connection_id}.{n}.jsonl")),
+        })
+    }
+
+    /// Append an entry.
+    pub async fn record(&self, entry: AuditEntry) {
+        if let Err(e) = self.append(&entry).await {
+            tracing::warn!(
+                connection_id = %entry.connection_id,
+                operation = %entry.operation,
+                "Failed to write audit entry: {e}"
+            );
+        }
+    }
+
+    async fn append(&self, entry: &AuditEntry) -> Result<(), AppError> {
+        let path = self.path(&entry.connection_id, 0)?;
+        let mut line = serde_json::to_string(entry)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize audit entry: {e}")))?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create audit dir: {e}")))?;
+
+        if let Ok(meta) = tokio::fs::metadata(&path).await {
+            if meta.len() >= MAX_FILE_BYTES {
+                let rotated = self.path(&entry.connection_id, 1)?;
+                tokio::fs::rename(&path, &rotated)
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to rotate audit log: {e}")))?;
+            }
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to open audit log: {e}")))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write audit log: {e}")))
+    }
+
+    /// Entries for a connection matching `query`, newest first.
+    pub async fn query(
+        &self,
+        connection_id: &str,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let limit = query
+            .limit
+            .map_or(DEFAULT_QUERY_LIMIT, |l| l as usize)
+            .max(1);
+
+        let mut matched = Vec::new();
+        // Current file first (newest), then the rotated one.
+        for generation in [0, 1] {
+            let entries = read_entries(&self.path(connection_id, generation)?).await?;
+            for entry in entries.into_iter().rev() {
+                if query.matches(&entry) {
+                    matched.push(entry);
+                    if matched.len() >= limit {
+                        return Ok(matched);
+                    }
+                }
+            }
+        }
+        Ok(matched)
+    }
+}
+
+/// Read a log file, skipping lines that don't parse (e.g. a torn final write).
+async fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, AppError> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let data = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read audit log: {e}")))?;
+    Ok(data
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::audit::model::AuditSource;
+
+    #[tokio::test]
+    async fn test_record_and_query_newest_first() {
+        let dir = std::env::temp_dir().join(format!("redis-lens-audit-{}", uuid::Uuid::new_v4()));
+        let log = AuditLog::new(dir.clone());
+        let id = uuid::Uuid::new_v4().to_string();
+
+        for key in ["a", "b", "c"] {
+            log.record(AuditEntry::new(
+                &id,
+                AuditSource::Browser,
+                "deleteKeys",
+                Some(key),
+                vec![],
+            ))
+            .await;
+        }
+        log.record(AuditEntry::new(
+            &id,
+            AuditSource::Cli,
+            "SET",
+            Some("a"),
+            vec!["1".into()],
+        ))
+        .await;
+
+        let all = log.query(&id, &AuditQuery::default()).await.unwrap();
+        let keys: Vec<_> = all.iter().map(|e| e.key.as_deref().unwrap()).collect();
+        assert_eq!(keys, ["a", "c", "b", "a"]);
+
+        let deletes = AuditQuery {
+            source: Some(AuditSource::Browser),
+            limit: Some(2),
+            ..AuditQuery::default()
+        };
+        let found = log.query(&id, &deletes).await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].key.as_deref(), Some("c"));
+
+        tokio::fs::remove_dir_all(dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_uuid_connection() {
+        let log = AuditLog::new(std::env::temp_dir());
+        assert!(log.query("../etc", &AuditQuery::default()).await.is_err());
+    }
+}
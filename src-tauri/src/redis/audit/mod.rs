@@ -0,0 +1,4 @@
+// SPDX-License-Identifier: MIT
+
+pub mod log;
+pub mod model;
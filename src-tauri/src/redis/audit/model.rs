@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+
+/// Longest argument kept in an audit entry; longer values are cut.
+const MAX_ARG_CHARS: usize = 256;
+
+/// Where in the app a mutation came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditSource {
+    Editor,
+    Browser,
+    Cli,
+    Server,
+    PubSub,
+    /// A scheduled job, with no user action behind it.
+    Scheduler,
+    /// Developer tools such as the test data generator.
+    DevTools,
+    /// The job queue dashboard.
+    Queues,
+}
+
+/// One mutating operation performed through the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub connection_id: String,
+    pub source: AuditSource,
+    /// Operation name, e.g. `setHashField` or the CLI command name.
+    pub operation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+impl AuditEntry {
+    /// Stamp a new entry, truncating long arguments.
+    pub fn new(
+        connection_id: &str,
+        source: AuditSource,
+        operation: &str,
+        key: Option<&str>,
+        args: Vec<String>,
+    ) -> Self {
+        Self {
+            timestamp: chrono::Utc::now(),
+            connection_id: connection_id.to_string(),
+            source,
+            operation: operation.to_string(),
+            key: key.map(String::from),
+            args: args.into_iter().map(truncate).collect(),
+        }
+    }
+}
+
+fn truncate(arg: String) -> String {
+    match arg.char_indices().nth(MAX_ARG_CHARS) {
+        Some((cut, _)) => format!("{}…", &arg[..cut]),
+        None => arg,
+    }
+}
+
+/// Filter for `audit_query`. Unset fields match everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQuery {
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    pub source: Option<AuditSource>,
+    /// Case-insensitive operation name.
+    pub operation: Option<String>,
+    /// Substring of the key, or of any argument (CLI entries carry keys there).
+    pub key: Option<String>,
+    /// Most entries returned (newest first); defaults to 200.
+    pub limit: Option<u32>,
+}
+
+impl AuditQuery {
+    /// Whether an entry passes every set filter.
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.since.is_none_or(|t| entry.timestamp >= t)
+            && self.until.is_none_or(|t| entry.timestamp <= t)
+            && self.source.is_none_or(|s| entry.source == s)
+            && self
+                .operation
+                .as_ref()
+                .is_none_or(|op| entry.operation.eq_ignore_ascii_case(op))
+            && self.key.as_ref().is_none_or(|needle| {
+                entry
+                    .key
+                    .iter()
+                    .chain(&entry.args)
+                    .any(|value| value.contains(needle.as_str()))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(operation: &str, key: &str) -> AuditEntry {
+        AuditEntry::new("c1", AuditSource::Editor, operation, Some(key), vec![])
+    }
+
+    #[test]
+    fn test_new_truncates_long_args() {
+        let long = "x".repeat(MAX_ARG_CHARS + 10);
+        let e = AuditEntry::new("c1", AuditSource::Cli, "SET", None, vec![long, "v".into()]);
+        assert_eq!(e.args[0].chars().count(), MAX_ARG_CHARS + 1);
+        assert!(e.args[0].ends_with('…'));
+        assert_eq!(e.args[1], "v");
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let e = entry("setHashField", "user:42");
+        assert!(AuditQuery::default().matches(&e));
+
+        let q = AuditQuery {
+            operation: Some("SETHASHFIELD".into()),
+            key: Some("user:".into()),
+            source: Some(AuditSource::Editor),
+            ..AuditQuery::default()
+        };
+        assert!(q.matches(&e));
+
+        let cli = AuditEntry::new("c1", AuditSource::Cli, "DEL", None, vec!["user:42".into()]);
+        let by_key = AuditQuery {
+            key: Some("user:42".into()),
+            ..AuditQuery::default()
+        };
+        assert!(by_key.matches(&cli));
+
+        let q = AuditQuery {
+            source: Some(AuditSource::Cli),
+            ..AuditQuery::default()
+        };
+        assert!(!q.matches(&e));
+
+        let q = AuditQuery {
+            since: Some(e.timestamp + chrono::Duration::seconds(1)),
+            ..AuditQuery::default()
+        };
+        assert!(!q.matches(&e));
+    }
+}
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt::Write;
+
+use base64::Engine;
+use deadpool_redis::Pool;
+use sha2::{Digest, Sha256};
+
+use super::model::{BackupArchive, BackupEntry, ARCHIVE_FORMAT_VERSION};
+use crate::utils::errors::AppError;
+
+/// SCAN COUNT hint used while collecting keys.
+const SCAN_BATCH: u32 = 500;
+
+/// Take a logical backup of every key matching `pattern` using DUMP + PTTL.
+///
+/// The whole archive is built in memory, which suits the small self-hosted
+/// instances this is aimed at; it is not a replacement for RDB snapshots.
+pub async fn create_backup(
+    pool: &Pool,
+    connection_id: &str,
+    redis_version: &str,
+    pattern: &str,
+) -> Result<BackupArchive, AppError> {
+    let mut conn = pool.get().await?;
+    let mut entries = Vec::new();
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(SCAN_BATCH)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("SCAN failed: {e}")))?;
+
+        if !keys.is_empty() {
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                pipe.cmd("DUMP").arg(key);
+                pipe.cmd("PTTL").arg(key);
+            }
+            let results: Vec<redis::Value> = pipe
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("DUMP pipeline failed: {e}")))?;
+
+            for (key, pair) in keys.into_iter().zip(results.chunks(2)) {
+                if let Some(entry) = to_entry(key, pair) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    Ok(BackupArchive {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        connection_id: connection_id.to_string(),
+        created_at: chrono::Utc::now(),
+        redis_version: redis_version.to_string(),
+        entries,
+    })
+}
+
+/// Build an entry from a `[DUMP, PTTL]` reply pair.
+///
+/// Returns `None` for keys that expired or were deleted mid-backup.
+fn to_entry(key: String, pair: &[redis::Value]) -> Option<BackupEntry> {
+    let redis::Value::BulkString(payload) = pair.first()? else {
+        return None;
+    };
+    let ttl_ms = match pair.get(1) {
+        Some(redis::Value::Int(ms)) if *ms > 0 => ms.unsigned_abs(),
+        Some(redis::Value::Int(-2)) => return None,
+        _ => 0,
+    };
+
+    Some(BackupEntry {
+        key,
+        ttl_ms,
+        payload: base64::engine::general_purpose::STANDARD.encode(payload),
+        checksum: Some(payload_checksum(payload)),
+    })
+}
+
+/// SHA-256 of a raw DUMP payload, hex-encoded.
+pub fn payload_checksum(payload: &[u8]) -> String {
+    Sha256::digest(payload)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_entry_persistent_key() {
+        let pair = [
+            redis::Value::BulkString(vec![0, 1, 2]),
+            redis::Value::Int(-1),
+        ];
+        let entry = to_entry("k".into(), &pair).unwrap();
+        assert_eq!(entry.ttl_ms, 0);
+        assert_eq!(entry.payload, "AAEC");
+        assert_eq!(
+            entry.checksum.as_deref(),
+            Some(payload_checksum(&[0, 1, 2]).as_str())
+        );
+    }
+
+    #[test]
+    fn test_payload_checksum_is_sha256_hex() {
+        assert_eq!(
+            payload_checksum(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_to_entry_with_ttl() {
+        let pair = [redis::Value::BulkString(vec![1]), redis::Value::Int(5_000)];
+        assert_eq!(to_entry("k".into(), &pair).unwrap().ttl_ms, 5_000);
+    }
+
+    #[test]
+    fn test_to_entry_skips_missing_key() {
+        let pair = [redis::Value::Nil, redis::Value::Int(-2)];
+        assert!(to_entry("k".into(), &pair).is_none());
+    }
+}
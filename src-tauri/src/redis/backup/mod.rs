@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: MIT
+
+pub mod archive;
+pub mod model;
+pub mod scheduler;
+pub mod verify;
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+
+/// Current on-disk archive format version.
+///
+/// v2 added per-entry checksums.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 2;
+
+/// Per-profile backup cadence and retention.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSchedule {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    /// Number of archives to keep; older ones are deleted after each run.
+    pub keep_last: u32,
+    /// SCAN MATCH pattern selecting the keys to back up.
+    #[serde(default = "default_pattern")]
+    pub pattern: String,
+}
+
+fn default_pattern() -> String {
+    "*".into()
+}
+
+/// A single key serialized with DUMP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    pub key: String,
+    /// Remaining TTL in milliseconds; 0 means no expiry.
+    pub ttl_ms: u64,
+    /// DUMP payload, base64-encoded.
+    pub payload: String,
+    /// SHA-256 of the raw DUMP payload, hex-encoded. Absent in v1 archives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+/// A logical backup: every matching key's DUMP payload plus metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupArchive {
+    pub format_version: u32,
+    pub connection_id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Version of the server the archive was taken from. DUMP payloads are
+    /// only guaranteed to RESTORE on the same or a newer version.
+    pub redis_version: String,
+    pub entries: Vec<BackupEntry>,
+}
+
+/// An archive file on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRecord {
+    /// File name without extension; unique per connection.
+    pub id: String,
+    pub connection_id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub size_bytes: u64,
+}
+
+/// A key that failed verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupKeyIssue {
+    pub key: String,
+    pub reason: String,
+}
+
+/// Result of verifying an archive against a target server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupVerifyReport {
+    pub backup_id: String,
+    pub source_version: String,
+    pub target_version: String,
+    pub total_keys: u64,
+    /// Keys whose checksum matched (or had none) and restored cleanly.
+    pub verified_keys: u64,
+    /// Entries from archives that predate checksums.
+    pub unchecked_keys: u64,
+    pub checksum_failures: Vec<BackupKeyIssue>,
+    pub restore_failures: Vec<BackupKeyIssue>,
+    /// Set when the target runs an older version than the source.
+    pub version_warning: Option<String>,
+}
+
+impl BackupVerifyReport {
+    /// True if every key passed both checks.
+    pub fn is_ok(&self) -> bool {
+        self.checksum_failures.is_empty() && self.restore_failures.is_empty()
+    }
+}
+
+/// Payload of the `backup:failed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFailure {
+    pub connection_id: String,
+    pub error: String,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_schedule_default_pattern() {
+        let json = r#"{"enabled":true,"intervalMinutes":60,"keepLast":7}"#;
+        let schedule: BackupSchedule = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(schedule.pattern, "*");
+        assert_eq!(schedule.keep_last, 7);
+    }
+
+    #[test]
+    fn test_backup_entry_serialization() {
+        let entry = BackupEntry {
+            key: "user:1".into(),
+            ttl_ms: 0,
+            payload: "AAEC".into(),
+            checksum: None,
+        };
+        let json = serde_json::to_string(&entry).expect("serialize");
+        assert!(json.contains("\"ttlMs\":0"));
+        assert!(!json.contains("checksum"));
+    }
+
+    #[test]
+    fn test_backup_entry_v1_without_checksum() {
+        let json = r#"{"key":"k","ttlMs":0,"payload":"AAEC"}"#;
+        let entry: BackupEntry = serde_json::from_str(json).expect("deserialize");
+        assert!(entry.checksum.is_none());
+    }
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use deadpool_redis::Pool;
+use tauri::{Emitter, Manager};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::archive;
+use super::model::{BackupFailure, BackupRecord, BackupSchedule};
+use crate::config::backup_store;
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::throttle::Throttle;
+use crate::utils::errors::AppError;
+
+/// Shortest allowed interval between scheduled backups.
+const MIN_INTERVAL_MINUTES: u64 = 5;
+
+/// Manages scheduled backup tasks, one per connection.
+pub struct BackupScheduler {
+    handles: Arc<RwLock<HashMap<String, AbortHandle>>>,
+}
+
+impl Default for BackupScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackupScheduler {
+    /// Create a scheduler with no active schedules.
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Run backups for a connection on the profile's cadence.
+    ///
+    /// The first backup runs one interval after start. Successful runs emit
+    /// `backup:completed`; failures emit `backup:failed` and the schedule
+    /// keeps going. A run that comes due while the connection's circuit
+    /// breaker is open waits for it to close. Each run goes through the
+    /// connection's current pool, so the schedule survives a reconnect.
+    /// Replaces any schedule already running for the connection.
+    pub async fn start(
+        &self,
+        connection_id: Uuid,
+        throttle: Arc<Throttle>,
+        redis_version: String,
+        schedule: BackupSchedule,
+        app_handle: tauri::AppHandle,
+    ) {
+        let conn_id = connection_id.to_string();
+        self.stop(&conn_id).await;
+
+        let key = conn_id.clone();
+        let task = tokio::spawn(async move {
+            let interval =
+                Duration::from_secs(schedule.interval_minutes.max(MIN_INTERVAL_MINUTES) * 60);
+            loop {
+                tokio::time::sleep(interval).await;
+                throttle.pause_while_open().await;
+
+                let result = async {
+                    let pool = app_handle
+                        .state::<ConnectionManager>()
+                        .get_pool(&connection_id)
+                        .await?;
+                    run_backup(&app_handle, &pool, &conn_id, &redis_version, &schedule).await
+                };
+                match result.await {
+                    Ok(record) => {
+                        let _ = app_handle.emit("backup:completed", &record);
+                    }
+                    Err(e) => {
+                        tracing::warn!(connection_id = %conn_id, "Scheduled backup failed: {e}");
+                        let failure = BackupFailure {
+                            connection_id: conn_id.clone(),
+                            error: e.to_string(),
+                            failed_at: chrono::Utc::now(),
+                        };
+                        let _ = app_handle.emit("backup:failed", &failure);
+                    }
+                }
+            }
+        });
+
+        let mut h = self.handles.write().await;
+        h.insert(key, task.abort_handle());
+    }
+
+    /// Stop scheduled backups for a connection.
+    pub async fn stop(&self, connection_id: &str) {
+        let mut h = self.handles.write().await;
+        if let Some(handle) = h.remove(connection_id) {
+            handle.abort();
+            tracing::info!(connection_id = %connection_id, "Backup schedule stopped");
+        }
+    }
+
+    /// Check if backups are scheduled for a connection.
+    pub async fn is_scheduled(&self, connection_id: &str) -> bool {
+        let h = self.handles.read().await;
+        h.contains_key(connection_id)
+    }
+}
+
+/// Take a backup, write it to disk, and apply the retention policy.
+pub async fn run_backup(
+    app_handle: &tauri::AppHandle,
+    pool: &Pool,
+    connection_id: &str,
+    redis_version: &str,
+    schedule: &BackupSchedule,
+) -> Result<BackupRecord, AppError> {
+    let archive =
+        archive::create_backup(pool, connection_id, redis_version, &schedule.pattern).await?;
+    let key_count = archive.entries.len();
+    let record = backup_store::write_archive(app_handle, &archive).await?;
+    let pruned =
+        backup_store::apply_retention(app_handle, connection_id, schedule.keep_last).await?;
+
+    tracing::info!(
+        connection_id = %connection_id,
+        backup = %record.id,
+        keys = key_count,
+        pruned = pruned,
+        "Backup written"
+    );
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scheduler_new_not_scheduled() {
+        let scheduler = BackupScheduler::new();
+        assert!(!scheduler.is_scheduled("conn-1").await);
+        scheduler.stop("conn-1").await;
+    }
+}
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MIT
+
+use base64::Engine;
+use deadpool_redis::Pool;
+
+use super::archive::payload_checksum;
+use super::model::{BackupArchive, BackupEntry, BackupKeyIssue, BackupVerifyReport};
+use crate::redis::connection::capabilities::parse_version;
+use crate::utils::errors::AppError;
+
+/// TTL of the scratch key, so it expires on its own if the final DEL never
+/// runs (connection lost, app closed mid-verify).
+const SCRATCH_TTL_MS: u64 = 60_000;
+
+/// Verify an archive's checksums and RESTORE-compatibility against a server.
+///
+/// Each payload is restored into a single scratch key (with REPLACE and a
+/// short TTL) and the scratch key is deleted at the end, so the target's
+/// data is never touched.
+pub async fn verify_archive(
+    pool: &Pool,
+    backup_id: &str,
+    archive: &BackupArchive,
+    target_version: &str,
+) -> Result<BackupVerifyReport, AppError> {
+    let mut conn = pool.get().await?;
+    let scratch_key = format!("redislens:verify:{}", uuid::Uuid::new_v4());
+
+    let mut report = BackupVerifyReport {
+        backup_id: backup_id.to_string(),
+        source_version: archive.redis_version.clone(),
+        target_version: target_version.to_string(),
+        total_keys: archive.entries.len() as u64,
+        verified_keys: 0,
+        unchecked_keys: 0,
+        checksum_failures: Vec::new(),
+        restore_failures: Vec::new(),
+        version_warning: version_warning(&archive.redis_version, target_version),
+    };
+
+    for entry in &archive.entries {
+        let payload = match check_entry(entry) {
+            Ok((payload, checked)) => {
+                if !checked {
+                    report.unchecked_keys += 1;
+                }
+                payload
+            }
+            Err(reason) => {
+                report.checksum_failures.push(BackupKeyIssue {
+                    key: entry.key.clone(),
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        let restored: Result<(), _> = redis::cmd("RESTORE")
+            .arg(&scratch_key)
+            .arg(SCRATCH_TTL_MS)
+            .arg(payload)
+            .arg("REPLACE")
+            .query_async(&mut conn)
+            .await;
+
+        match restored {
+            Ok(()) => report.verified_keys += 1,
+            Err(e) => report.restore_failures.push(BackupKeyIssue {
+                key: entry.key.clone(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    let _: Result<(), _> = redis::cmd("DEL")
+        .arg(&scratch_key)
+        .query_async(&mut conn)
+        .await;
+
+    Ok(report)
+}
+
+/// Decode an entry and check its checksum.
+///
+/// Returns the raw payload and whether a checksum was present to compare.
+fn check_entry(entry: &BackupEntry) -> Result<(Vec<u8>, bool), String> {
+    let payload = base64::engine::general_purpose::STANDARD
+        .decode(&entry.payload)
+        .map_err(|e| format!("Payload is not valid base64: {e}"))?;
+
+    match entry.checksum {
+        Some(ref expected) if *expected != payload_checksum(&payload) => {
+            Err("Checksum mismatch".into())
+        }
+        Some(_) => Ok((payload, true)),
+        None => Ok((payload, false)),
+    }
+}
+
+/// Warn when restoring onto an older server than the archive came from.
+fn version_warning(source: &str, target: &str) -> Option<String> {
+    (parse_version(target) < parse_version(source)).then(|| {
+        format!("Archive was taken from Redis {source}; target runs {target}. DUMP payloads are not guaranteed to restore on older versions.")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(payload: &[u8], checksum: Option<String>) -> BackupEntry {
+        BackupEntry {
+            key: "k".into(),
+            ttl_ms: 0,
+            payload: base64::engine::general_purpose::STANDARD.encode(payload),
+            checksum,
+        }
+    }
+
+    #[test]
+    fn test_check_entry_valid_checksum() {
+        let e = entry(b"abc", Some(payload_checksum(b"abc")));
+        let (payload, checked) = check_entry(&e).unwrap();
+        assert_eq!(payload, b"abc");
+        assert!(checked);
+    }
+
+    #[test]
+    fn test_check_entry_mismatch() {
+        let e = entry(b"abc", Some(payload_checksum(b"abd")));
+        assert_eq!(check_entry(&e).unwrap_err(), "Checksum mismatch");
+    }
+
+    #[test]
+    fn test_check_entry_without_checksum() {
+        let (_, checked) = check_entry(&entry(b"abc", None)).unwrap();
+        assert!(!checked);
+    }
+
+    #[test]
+    fn test_check_entry_bad_base64() {
+        let mut e = entry(b"abc", None);
+        e.payload = "***".into();
+        assert!(check_entry(&e).is_err());
+    }
+
+    #[test]
+    fn test_version_warning() {
+        assert!(version_warning("7.2.4", "6.2.0").is_some());
+        assert!(version_warning("6.2.0", "7.2.4").is_none());
+        assert!(version_warning("7.2.4", "7.2.4").is_none());
+    }
+}
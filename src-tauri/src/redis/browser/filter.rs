@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::model::RedisKeyType;
+use crate::utils::errors::AppError;
+use crate::utils::glob;
+
+/// Compiled size a filter regex may reach; keeps pathological patterns from
+/// stalling the backend.
+pub const MAX_REGEX_SIZE: usize = 1 << 20;
+
+/// Criteria for `browser_filter_keys`. Every set criterion must hold.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KeyFilter {
+    /// Glob keys must match, as in SCAN MATCH.
+    pub pattern: Option<String>,
+    /// Regular expression keys must match somewhere (anchor with `^`/`$`;
+    /// `(?i)` for case-insensitive).
+    pub regex: Option<String>,
+    /// Globs whose matches are left out, e.g. `cache:*`.
+    pub exclude: Vec<String>,
+    /// Key types to keep; empty keeps every type.
+    pub types: Vec<RedisKeyType>,
+}
+
+/// Result of `browser_filter_keys`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyFilterResult {
+    /// Matching keys in name order, at most the requested limit.
+    pub keys: Vec<String>,
+    /// Keys matching in total.
+    pub matched: u64,
+    /// Keys in the index that were checked.
+    pub scanned: u64,
+    /// Keys whose type had to be loaded from the server for a type filter.
+    pub types_loaded: u64,
+}
+
+/// A [`KeyFilter`] ready to test key names.
+#[derive(Debug)]
+pub struct KeyMatcher {
+    pattern: Option<String>,
+    regex: Option<Regex>,
+    exclude: Vec<String>,
+    types: Vec<RedisKeyType>,
+}
+
+impl KeyMatcher {
+    pub fn compile(filter: KeyFilter) -> Result<Self, AppError> {
+        let regex = filter
+            .regex
+            .filter(|r| !r.is_empty())
+            .map(|r| {
+                RegexBuilder::new(&r)
+                    .size_limit(MAX_REGEX_SIZE)
+                    .build()
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid regex: {e}")))
+            })
+            .transpose()?;
+        Ok(Self {
+            pattern: filter.pattern.filter(|p| !p.is_empty() && p != "*"),
+            regex,
+            exclude: filter
+                .exclude
+                .into_iter()
+                .filter(|p| !p.is_empty())
+                .collect(),
+            types: filter.types,
+        })
+    }
+
+    /// Whether the name passes the glob, regex and exclusion criteria.
+    pub fn matches_name(&self, key: &str) -> bool {
+        self.pattern.as_ref().is_none_or(|p| glob::matches(p, key))
+            && self.regex.as_ref().is_none_or(|r| r.is_match(key))
+            && !self.exclude.iter().any(|p| glob::matches(p, key))
+    }
+
+    /// Whether the filter restricts key types, which needs metadata.
+    pub fn filters_types(&self) -> bool {
+        !self.types.is_empty()
+    }
+
+    pub fn matches_type(&self, key_type: &RedisKeyType) -> bool {
+        self.types.is_empty() || self.types.contains(key_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(filter: KeyFilter) -> KeyMatcher {
+        KeyMatcher::compile(filter).unwrap()
+    }
+
+    #[test]
+    fn test_name_criteria_combine() {
+        let m = matcher(KeyFilter {
+            pattern: Some("user:*".into()),
+            regex: Some(r":\d+$".into()),
+            exclude: vec!["user:0*".into()],
+            types: Vec::new(),
+        });
+        assert!(m.matches_name("user:42"));
+        assert!(!m.matches_name("user:42:profile"));
+        assert!(!m.matches_name("user:007"));
+        assert!(!m.matches_name("session:42"));
+
+        let everything_but_cache = matcher(KeyFilter {
+            exclude: vec!["cache:*".into()],
+            ..KeyFilter::default()
+        });
+        assert!(everything_but_cache.matches_name("user:1"));
+        assert!(!everything_but_cache.matches_name("cache:page"));
+    }
+
+    #[test]
+    fn test_type_criteria_and_invalid_regex() {
+        let m = matcher(KeyFilter {
+            types: vec![RedisKeyType::Hash],
+            ..KeyFilter::default()
+        });
+        assert!(m.filters_types());
+        assert!(m.matches_type(&RedisKeyType::Hash));
+        assert!(!m.matches_type(&RedisKeyType::String));
+
+        let bad = KeyMatcher::compile(KeyFilter {
+            regex: Some("(".into()),
+            ..KeyFilter::default()
+        });
+        assert!(matches!(bad, Err(AppError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+use redis::FromRedisValue;
+
+use super::model::KeyIntrospection;
+use crate::utils::errors::AppError;
+
+/// Read OBJECT ENCODING, REFCOUNT, IDLETIME and FREQ and DEBUG OBJECT's
+/// serialized length for `key`.
+///
+/// The subcommands run one at a time so that one refused by the server
+/// (ACLs, `enable-debug-command no`, the wrong maxmemory policy) only
+/// leaves its own figure empty. Without `debug`, DEBUG OBJECT isn't sent and
+/// the serialized length is reported unavailable. Fails with `NotFound` if
+/// the key doesn't exist.
+pub async fn introspect_key(
+    pool: &Pool,
+    key: &str,
+    debug: bool,
+) -> Result<KeyIntrospection, AppError> {
+    let mut conn = pool.get().await?;
+    let mut info = KeyIntrospection {
+        key: key.to_string(),
+        ..KeyIntrospection::default()
+    };
+
+    match object::<String>(&mut conn, "ENCODING", key).await {
+        Ok(Some(encoding)) => info.encoding = Some(encoding),
+        Ok(None) => return Err(AppError::NotFound(format!("Key '{key}' not found"))),
+        Err(reason) => info.unavailable.push(format!("OBJECT ENCODING: {reason}")),
+    }
+    match object(&mut conn, "REFCOUNT", key).await {
+        Ok(refcount) => info.refcount = refcount,
+        Err(reason) => info.unavailable.push(format!("OBJECT REFCOUNT: {reason}")),
+    }
+    match object(&mut conn, "IDLETIME", key).await {
+        Ok(idle) => info.idle_secs = idle,
+        Err(reason) => info.unavailable.push(format!("OBJECT IDLETIME: {reason}")),
+    }
+    match object(&mut conn, "FREQ", key).await {
+        Ok(freq) => info.freq = freq,
+        Err(reason) => info.unavailable.push(format!("OBJECT FREQ: {reason}")),
+    }
+
+    if !debug {
+        info.unavailable
+            .push("DEBUG OBJECT: blocked on this connection".into());
+        return Ok(info);
+    }
+    let debug: Result<String, _> = redis::cmd("DEBUG")
+        .arg("OBJECT")
+        .arg(key)
+        .query_async(&mut conn)
+        .await;
+    match debug {
+        Ok(reply) => {
+            info.serialized_length =
+                debug_field(&reply, "serializedlength").and_then(|v| v.parse().ok());
+        }
+        Err(e) => info
+            .unavailable
+            .push(format!("DEBUG OBJECT: {}", refusal_reason(&e))),
+    }
+    Ok(info)
+}
+
+/// Run `OBJECT <subcommand> key`, returning the server's reason on failure.
+async fn object<T: FromRedisValue>(
+    conn: &mut deadpool_redis::Connection,
+    subcommand: &str,
+    key: &str,
+) -> Result<Option<T>, String> {
+    redis::cmd("OBJECT")
+        .arg(subcommand)
+        .arg(key)
+        .query_async(conn)
+        .await
+        .map_err(|e| refusal_reason(&e))
+}
+
+/// The server's message for a refused command, without redis-rs framing.
+fn refusal_reason(e: &redis::RedisError) -> String {
+    e.detail()
+        .map_or_else(|| e.to_string(), ToString::to_string)
+}
+
+/// Value of `name` in a DEBUG OBJECT reply such as
+/// `Value at:0x7f refcount:1 encoding:listpack serializedlength:25 ...`.
+fn debug_field<'a>(reply: &'a str, name: &str) -> Option<&'a str> {
+    reply
+        .split_whitespace()
+        .filter_map(|token| token.split_once(':'))
+        .find_map(|(field, value)| (field == name).then_some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_field() {
+        let reply = "Value at:0x7f3c refcount:1 encoding:listpack serializedlength:25 lru:123 lru_seconds_idle:4";
+        assert_eq!(debug_field(reply, "serializedlength"), Some("25"));
+        assert_eq!(debug_field(reply, "encoding"), Some("listpack"));
+        assert_eq!(debug_field(reply, "at"), Some("0x7f3c"));
+        assert_eq!(debug_field(reply, "ql_nodes"), None);
+    }
+}
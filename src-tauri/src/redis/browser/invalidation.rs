@@ -0,0 +1,327 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::key_index::KeyRecorder;
+use crate::utils::errors::AppError;
+
+/// Prefixes reported per hint; beyond this the hint asks for a full refresh.
+const MAX_PREFIXES_PER_HINT: usize = 200;
+
+/// Events after which a key no longer exists under its name.
+const REMOVAL_EVENTS: &[&str] = &["del", "expired", "evicted", "rename_from", "move_from"];
+
+/// Changes under one namespace prefix within a hint's window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefixChange {
+    /// Namespace the changed keys live under; empty for root-level keys.
+    pub prefix: String,
+    pub events: u64,
+    /// Distinct event names seen (`set`, `del`, `expired`, `hset`, ...).
+    pub operations: Vec<String>,
+}
+
+/// Payload of `browser:invalidate`: which parts of the key tree changed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidationHint {
+    pub connection_id: String,
+    pub db: u8,
+    /// Length of the window the events were collected over.
+    pub window_ms: u64,
+    pub total_events: u64,
+    /// Set when too many prefixes changed to list; rescan everything.
+    pub full_refresh: bool,
+    /// Changed prefixes, busiest first; empty when `full_refresh` is set.
+    pub prefixes: Vec<PrefixChange>,
+}
+
+/// Namespace a key belongs to for invalidation: its first `depth` segments,
+/// or all but the last segment for shallower keys.
+pub fn namespace_of<'a>(key: &'a str, delimiter: &str, depth: usize) -> &'a str {
+    if delimiter.is_empty() {
+        return "";
+    }
+    let mut end = 0;
+    for (taken, (index, _)) in key.match_indices(delimiter).enumerate() {
+        if taken == depth {
+            break;
+        }
+        end = index;
+    }
+    &key[..end]
+}
+
+/// Events collected since the last hint, per prefix.
+#[derive(Debug, Default)]
+struct Pending {
+    prefixes: BTreeMap<String, (u64, BTreeSet<String>)>,
+    total: u64,
+}
+
+impl Pending {
+    fn add(&mut self, prefix: &str, event: &str) {
+        let (count, operations) = self.prefixes.entry(prefix.to_string()).or_default();
+        *count += 1;
+        operations.insert(event.to_string());
+        self.total += 1;
+    }
+
+    /// Build a hint from the collected events and reset, or `None` if
+    /// nothing happened.
+    fn take(&mut self, connection_id: &str, db: u8, window: Duration) -> Option<InvalidationHint> {
+        if self.total == 0 {
+            return None;
+        }
+        let pending = std::mem::take(self);
+        let full_refresh = pending.prefixes.len() > MAX_PREFIXES_PER_HINT;
+        let mut prefixes: Vec<PrefixChange> = if full_refresh {
+            Vec::new()
+        } else {
+            pending
+                .prefixes
+                .into_iter()
+                .map(|(prefix, (events, operations))| PrefixChange {
+                    prefix,
+                    events,
+                    operations: operations.into_iter().collect(),
+                })
+                .collect()
+        };
+        prefixes.sort_by(|a, b| {
+            b.events
+                .cmp(&a.events)
+                .then_with(|| a.prefix.cmp(&b.prefix))
+        });
+        Some(InvalidationHint {
+            connection_id: connection_id.to_string(),
+            db,
+            window_ms: u64::try_from(window.as_millis()).unwrap_or(u64::MAX),
+            total_events: pending.total,
+            full_refresh,
+            prefixes,
+        })
+    }
+}
+
+/// How a watch groups and paces its hints.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub db: u8,
+    pub delimiter: String,
+    /// Namespace depth changes are reported at (1 = top-level prefix).
+    pub depth: usize,
+    pub debounce: Duration,
+}
+
+/// Watches keyspace notifications per connection and turns them into
+/// debounced `browser:invalidate` hints.
+///
+/// Each watch holds a dedicated subscriber connection, like Pub/Sub
+/// subscriptions. Keys seen in events also keep the key index current.
+pub struct KeyspaceWatcher {
+    handles: Arc<RwLock<HashMap<Uuid, AbortHandle>>>,
+}
+
+impl Default for KeyspaceWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyspaceWatcher {
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Start watching a connection, replacing any watch already running.
+    pub async fn start(
+        &self,
+        connection_id: Uuid,
+        connection_url: String,
+        options: WatchOptions,
+        recorder: KeyRecorder,
+        app: AppHandle,
+    ) -> Result<(), AppError> {
+        let client = redis::Client::open(connection_url)
+            .map_err(|e| AppError::Connection(format!("Failed to create PubSub client: {e}")))?;
+        let mut pubsub = tokio::time::timeout(Duration::from_secs(10), client.get_async_pubsub())
+            .await
+            .map_err(|_| AppError::Timeout("PubSub connection timed out".into()))?
+            .map_err(|e| AppError::Connection(format!("PubSub connection failed: {e}")))?;
+        let channel_prefix = format!("__keyspace@{}__:", options.db);
+        pubsub
+            .psubscribe(format!("{channel_prefix}*"))
+            .await
+            .map_err(|e| AppError::Redis(format!("Pattern subscribe failed: {e}")))?;
+
+        self.stop(&connection_id).await;
+        let id = connection_id.to_string();
+        let task = tokio::spawn(async move {
+            let mut stream = pubsub.on_message();
+            let mut pending = Pending::default();
+            let mut flush = tokio::time::interval(options.debounce);
+            flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    msg = futures::StreamExt::next(&mut stream) => {
+                        let Some(msg) = msg else { break };
+                        let Some(key) = msg.get_channel_name().strip_prefix(&channel_prefix) else {
+                            continue;
+                        };
+                        let event: String = msg.get_payload().unwrap_or_default();
+                        pending.add(namespace_of(key, &options.delimiter, options.depth), &event);
+                        if REMOVAL_EVENTS.contains(&event.as_str()) {
+                            recorder.remove(&[key.to_string()]).await;
+                        } else {
+                            recorder.record(&[key.to_string()]).await;
+                        }
+                    }
+                    _ = flush.tick() => {
+                        if let Some(hint) = pending.take(&id, options.db, options.debounce) {
+                            let _ = app.emit("browser:invalidate", &hint);
+                        }
+                    }
+                }
+            }
+            tracing::info!(connection_id = %id, "Keyspace watch connection closed");
+        });
+
+        self.handles
+            .write()
+            .await
+            .insert(connection_id, task.abort_handle());
+        tracing::info!(connection_id = %connection_id, "Keyspace watch started");
+        Ok(())
+    }
+
+    /// Stop watching a connection.
+    pub async fn stop(&self, connection_id: &Uuid) {
+        if let Some(handle) = self.handles.write().await.remove(connection_id) {
+            handle.abort();
+            tracing::info!(connection_id = %connection_id, "Keyspace watch stopped");
+        }
+    }
+}
+
+/// Make sure the server publishes keyspace events.
+///
+/// Returns the `notify-keyspace-events` flags before and after. When
+/// keyspace (`K`) events or all event classes (`A`) are missing, they are
+/// added if `enable` is set, and an error explains how to enable them
+/// otherwise. Returns `None` when the server doesn't allow CONFIG GET (as on
+/// some managed services) and `enable` isn't set; the watch then relies on
+/// the server's own configuration.
+pub async fn ensure_notifications(
+    conn: &mut deadpool_redis::Connection,
+    enable: bool,
+) -> Result<Option<(String, String)>, AppError> {
+    let reply: Result<(String, String), _> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("notify-keyspace-events")
+        .query_async(conn)
+        .await;
+    let flags = match reply {
+        Ok((_, flags)) => flags,
+        Err(e) if !enable => {
+            tracing::warn!("Cannot read notify-keyspace-events, watching anyway: {e}");
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(AppError::Redis(format!(
+                "CONFIG GET notify-keyspace-events failed: {e}"
+            )))
+        }
+    };
+    let wanted = missing_flags(&flags);
+    if wanted.is_empty() {
+        return Ok(Some((flags.clone(), flags)));
+    }
+    if !enable {
+        return Err(AppError::InvalidInput(format!(
+            "Keyspace notifications are not fully enabled on the server \
+             (notify-keyspace-events=\"{flags}\"); enable them to watch for changes"
+        )));
+    }
+    let updated = format!("{flags}{wanted}");
+    redis::cmd("CONFIG")
+        .arg("SET")
+        .arg("notify-keyspace-events")
+        .arg(&updated)
+        .query_async::<()>(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("CONFIG SET notify-keyspace-events failed: {e}")))?;
+    Ok(Some((flags, updated)))
+}
+
+/// Flags to add so keyspace events cover every event class.
+fn missing_flags(flags: &str) -> String {
+    let mut missing = String::new();
+    if !flags.contains('K') {
+        missing.push('K');
+    }
+    if !flags.contains('A') {
+        missing.push('A');
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_of() {
+        assert_eq!(namespace_of("user:1", ":", 1), "user");
+        assert_eq!(namespace_of("user:1:profile", ":", 1), "user");
+        assert_eq!(namespace_of("user:1:profile", ":", 2), "user:1");
+        assert_eq!(namespace_of("user:1:profile", ":", 5), "user:1");
+        assert_eq!(namespace_of("plain", ":", 1), "");
+        assert_eq!(namespace_of("a::b", "::", 1), "a");
+    }
+
+    #[test]
+    fn test_pending_coalesces_by_prefix() {
+        let mut pending = Pending::default();
+        for _ in 0..3 {
+            pending.add("user", "set");
+        }
+        pending.add("user", "del");
+        pending.add("session", "expired");
+
+        let hint = pending.take("c", 0, Duration::from_secs(2)).unwrap();
+        assert_eq!(hint.total_events, 5);
+        assert_eq!(hint.window_ms, 2000);
+        assert!(!hint.full_refresh);
+        assert_eq!(hint.prefixes[0].prefix, "user");
+        assert_eq!(hint.prefixes[0].events, 4);
+        assert_eq!(hint.prefixes[0].operations, ["del", "set"]);
+        assert!(pending.take("c", 0, Duration::from_secs(2)).is_none());
+
+        for i in 0..=MAX_PREFIXES_PER_HINT {
+            pending.add(&format!("p{i}"), "set");
+        }
+        let hint = pending.take("c", 0, Duration::from_secs(2)).unwrap();
+        assert!(hint.full_refresh);
+        assert!(hint.prefixes.is_empty());
+    }
+
+    #[test]
+    fn test_missing_flags() {
+        assert_eq!(missing_flags(""), "KA");
+        assert_eq!(missing_flags("Ex"), "KA");
+        assert_eq!(missing_flags("KA"), "");
+        assert_eq!(missing_flags("AKE"), "");
+    }
+}
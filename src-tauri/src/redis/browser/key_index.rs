@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: MIT
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::filter::KeyMatcher;
+use super::model::{KeyInfo, KeyListQuery, KeySort, RedisKeyType, Ttl};
+use crate::utils::glob;
+
+/// Keys remembered per connection and database; later scans add nothing
+/// new once this is reached.
+const MAX_KEYS_PER_DB: usize = 1_000_000;
+
+/// Identifies one database of a connection. `None` is the profile's own
+/// database, matching the `db` argument of the browser and CLI commands.
+type Scope = (Uuid, Option<u8>);
+
+/// Known keys of one scope and their metadata, if loaded.
+type Keys = BTreeMap<String, Option<KeyMeta>>;
+
+/// Metadata last fetched for a key, used to sort flat listings.
+#[derive(Debug, Clone)]
+struct KeyMeta {
+    key_type: RedisKeyType,
+    ttl: Ttl,
+    size_bytes: Option<u64>,
+}
+
+/// Key names seen by browser scans, kept sorted for prefix lookups, with
+/// whatever metadata has been loaded for them.
+///
+/// This is a cache of what the app has already fetched, not a mirror of the
+/// server: it fills as the user scans and drops keys the app itself deletes.
+#[derive(Clone, Default)]
+pub struct KeyIndex {
+    indexes: Arc<RwLock<HashMap<Scope, Keys>>>,
+}
+
+/// Keys selected for one page of a flat listing.
+#[derive(Debug, Default)]
+pub struct KeyListing {
+    pub keys: Vec<String>,
+    pub total_keys: u64,
+    pub matched: u64,
+    pub unsorted: u64,
+}
+
+impl KeyIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A handle that records keys for one connection and database.
+    pub fn recorder(&self, connection_id: Uuid, db: Option<u8>) -> KeyRecorder {
+        KeyRecorder {
+            index: self.clone(),
+            scope: (connection_id, db),
+        }
+    }
+
+    /// Add scanned keys.
+    pub async fn record(&self, connection_id: Uuid, db: Option<u8>, keys: &[String]) {
+        let mut indexes = self.indexes.write().await;
+        let index = indexes.entry((connection_id, db)).or_default();
+        for key in keys {
+            if index.len() >= MAX_KEYS_PER_DB {
+                break;
+            }
+            index.entry(key.clone()).or_insert(None);
+        }
+    }
+
+    /// Add keys with freshly loaded metadata. Keys reported missing are
+    /// forgotten, and a size already known is kept when `infos` has none.
+    pub async fn record_info(&self, connection_id: Uuid, db: Option<u8>, infos: &[KeyInfo]) {
+        let mut indexes = self.indexes.write().await;
+        let index = indexes.entry((connection_id, db)).or_default();
+        for info in infos {
+            if info.ttl == Ttl::Missing {
+                index.remove(&info.key);
+                continue;
+            }
+            if index.len() >= MAX_KEYS_PER_DB && !index.contains_key(&info.key) {
+                continue;
+            }
+            let entry = index.entry(info.key.clone()).or_insert(None);
+            let size_bytes = info
+                .size_bytes
+                .or_else(|| entry.as_ref().and_then(|meta| meta.size_bytes));
+            *entry = Some(KeyMeta {
+                key_type: info.key_type.clone(),
+                ttl: info.ttl.clone(),
+                size_bytes,
+            });
+        }
+    }
+
+    /// Forget deleted keys.
+    pub async fn remove(&self, connection_id: Uuid, db: Option<u8>, keys: &[String]) {
+        if let Some(index) = self.indexes.write().await.get_mut(&(connection_id, db)) {
+            for key in keys {
+                index.remove(key);
+            }
+        }
+    }
+
+    /// Forget keys matching a glob, after a delete by pattern.
+    pub async fn remove_matching(&self, connection_id: Uuid, db: Option<u8>, pattern: &str) {
+        if let Some(index) = self.indexes.write().await.get_mut(&(connection_id, db)) {
+            index.retain(|key, _| !glob::matches(pattern, key));
+        }
+    }
+
+    /// Follow a rename.
+    pub async fn rename(&self, connection_id: Uuid, db: Option<u8>, old: &str, new: &str) {
+        if let Some(index) = self.indexes.write().await.get_mut(&(connection_id, db)) {
+            if let Some(meta) = index.remove(old) {
+                index.insert(new.to_string(), meta);
+            }
+        }
+    }
+
+    /// Drop everything known about a connection, e.g. on disconnect.
+    pub async fn clear(&self, connection_id: &Uuid) {
+        self.indexes
+            .write()
+            .await
+            .retain(|(id, _), _| id != connection_id);
+    }
+
+    /// Up to `limit` known keys starting with `prefix`, in sorted order.
+    pub async fn complete(
+        &self,
+        connection_id: Uuid,
+        db: Option<u8>,
+        prefix: &str,
+        limit: usize,
+    ) -> Vec<String> {
+        let indexes = self.indexes.read().await;
+        let Some(index) = indexes.get(&(connection_id, db)) else {
+            return Vec::new();
+        };
+        index
+            .range::<str, _>((
+                std::ops::Bound::Included(prefix),
+                std::ops::Bound::Unbounded,
+            ))
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(prefix))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of known keys starting with `prefix`, and up to `sample` of
+    /// them spread evenly over the sorted range.
+    pub async fn sample_prefix(
+        &self,
+        connection_id: Uuid,
+        db: Option<u8>,
+        prefix: &str,
+        sample: usize,
+    ) -> (u64, Vec<String>) {
+        let indexes = self.indexes.read().await;
+        let Some(index) = indexes.get(&(connection_id, db)) else {
+            return (0, Vec::new());
+        };
+        let range = || {
+            index
+                .range::<str, _>((
+                    std::ops::Bound::Included(prefix),
+                    std::ops::Bound::Unbounded,
+                ))
+                .map(|(key, _)| key)
+                .take_while(|key| key.starts_with(prefix))
+        };
+        let count = range().count();
+        let step = count.div_ceil(sample.max(1)).max(1);
+        let keys = range().step_by(step).take(sample).cloned().collect();
+        (count as u64, keys)
+    }
+
+    /// Select one page of known keys, filtered and sorted as `query` asks.
+    ///
+    /// Sorting by type, TTL or size uses cached metadata; keys without it
+    /// come last (in name order) whichever the direction.
+    pub async fn list(
+        &self,
+        connection_id: Uuid,
+        db: Option<u8>,
+        query: &KeyListQuery,
+    ) -> KeyListing {
+        let indexes = self.indexes.read().await;
+        let Some(index) = indexes.get(&(connection_id, db)) else {
+            return KeyListing::default();
+        };
+        let filter = query.filter.to_lowercase();
+        let mut matched: Vec<(&String, &Option<KeyMeta>)> = index
+            .iter()
+            .filter(|(key, _)| filter.is_empty() || key.to_lowercase().contains(&filter))
+            .collect();
+
+        let mut unsorted = 0;
+        if query.sort != KeySort::Name {
+            // Stable sort: keys with equal values stay in name order.
+            matched.sort_by(|(_, a), (_, b)| {
+                compare_meta(a.as_ref(), b.as_ref(), query.sort, query.descending)
+            });
+            unsorted = matched
+                .iter()
+                .filter(|(_, meta)| sort_value(meta.as_ref(), query.sort).is_none())
+                .count();
+        } else if query.descending {
+            matched.reverse();
+        }
+
+        let offset = usize::try_from(query.offset).unwrap_or(usize::MAX);
+        KeyListing {
+            keys: matched
+                .iter()
+                .skip(offset)
+                .take(query.limit as usize)
+                .map(|(key, _)| (*key).clone())
+                .collect(),
+            total_keys: index.len() as u64,
+            matched: matched.len() as u64,
+            unsorted: unsorted as u64,
+        }
+    }
+
+    /// Known keys whose names pass `matcher`, in name order, with their
+    /// cached type if any. Returns the matches and the number of keys checked.
+    pub async fn filter(
+        &self,
+        connection_id: Uuid,
+        db: Option<u8>,
+        matcher: &KeyMatcher,
+    ) -> (Vec<(String, Option<RedisKeyType>)>, u64) {
+        let indexes = self.indexes.read().await;
+        let Some(index) = indexes.get(&(connection_id, db)) else {
+            return (Vec::new(), 0);
+        };
+        let found = index
+            .iter()
+            .filter(|(key, _)| matcher.matches_name(key))
+            .map(|(key, meta)| (key.clone(), meta.as_ref().map(|m| m.key_type.clone())))
+            .collect();
+        (found, index.len() as u64)
+    }
+}
+
+/// Comparable value of a key's sort column, `None` when not loaded.
+fn sort_value(meta: Option<&KeyMeta>, sort: KeySort) -> Option<(u8, u64, &str)> {
+    let meta = meta?;
+    match sort {
+        KeySort::Name => Some((0, 0, "")),
+        KeySort::Type => Some((0, 0, meta.key_type.as_type_str())),
+        // Expiring keys first, soonest first; then keys without expiry.
+        KeySort::Ttl => match meta.ttl {
+            Ttl::Seconds { value } => Some((0, value.max(0).unsigned_abs(), "")),
+            Ttl::Persistent => Some((1, 0, "")),
+            Ttl::Missing => None,
+        },
+        KeySort::Size => meta.size_bytes.map(|size| (0, size, "")),
+    }
+}
+
+fn compare_meta(
+    a: Option<&KeyMeta>,
+    b: Option<&KeyMeta>,
+    sort: KeySort,
+    descending: bool,
+) -> Ordering {
+    match (sort_value(a, sort), sort_value(b, sort)) {
+        (Some(a), Some(b)) if descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Records keys into a [`KeyIndex`] for a fixed connection and database.
+pub struct KeyRecorder {
+    index: KeyIndex,
+    scope: Scope,
+}
+
+impl KeyRecorder {
+    pub async fn record(&self, keys: &[String]) {
+        let (id, db) = self.scope;
+        self.index.record(id, db, keys).await;
+    }
+
+    pub async fn remove(&self, keys: &[String]) {
+        let (id, db) = self.scope;
+        self.index.remove(id, db, keys).await;
+    }
+
+    pub async fn rename(&self, old: &str, new: &str) {
+        let (id, db) = self.scope;
+        self.index.rename(id, db, old, new).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(|k| (*k).to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_complete_by_prefix() {
+        let index = KeyIndex::new();
+        let id = Uuid::new_v4();
+        index
+            .record(id, None, &keys(&["user:2", "session:1", "user:1", "users"]))
+            .await;
+
+        assert_eq!(
+            index.complete(id, None, "user:", 10).await,
+            ["user:1", "user:2"]
+        );
+        assert_eq!(
+            index.complete(id, None, "us", 2).await,
+            ["user:1", "user:2"]
+        );
+        assert!(index.complete(id, Some(3), "us", 10).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sample_prefix_spreads_over_range() {
+        let index = KeyIndex::new();
+        let id = Uuid::new_v4();
+        let names: Vec<String> = (0..100).map(|i| format!("user:{i:03}")).collect();
+        index.record(id, None, &names).await;
+        index.record(id, None, &keys(&["session:1"])).await;
+
+        let (count, sample) = index.sample_prefix(id, None, "user:", 4).await;
+        assert_eq!(count, 100);
+        assert_eq!(sample, ["user:000", "user:025", "user:050", "user:075"]);
+        assert_eq!(index.sample_prefix(id, None, "", 1000).await.0, 101);
+    }
+
+    #[tokio::test]
+    async fn test_tracks_deletes_and_renames() {
+        let index = KeyIndex::new();
+        let id = Uuid::new_v4();
+        index
+            .record(id, None, &keys(&["a:1", "a:2", "b:1", "c"]))
+            .await;
+        index.remove(id, None, &keys(&["c"])).await;
+        index.remove_matching(id, None, "a:*").await;
+        index.rename(id, None, "b:1", "b:9").await;
+        assert_eq!(index.complete(id, None, "", 10).await, ["b:9"]);
+
+        index.clear(&id).await;
+        assert!(index.complete(id, None, "", 10).await.is_empty());
+    }
+
+    fn info(key: &str, key_type: RedisKeyType, ttl: Ttl, size_bytes: Option<u64>) -> KeyInfo {
+        KeyInfo {
+            key: key.into(),
+            key_type,
+            ttl,
+            size_bytes,
+            encoding: None,
+            length: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_sorts_and_pages() {
+        let index = KeyIndex::new();
+        let id = Uuid::new_v4();
+        index
+            .record(
+                id,
+                None,
+                &keys(&["user:1", "user:2", "User:3", "session:1"]),
+            )
+            .await;
+        index
+            .record_info(
+                id,
+                None,
+                &[
+                    info("user:1", RedisKeyType::Hash, Ttl::Persistent, Some(300)),
+                    info(
+                        "user:2",
+                        RedisKeyType::String,
+                        Ttl::Seconds { value: 5 },
+                        Some(100),
+                    ),
+                    info("gone", RedisKeyType::String, Ttl::Missing, None),
+                ],
+            )
+            .await;
+
+        let query = |sort, descending, offset, limit| KeyListQuery {
+            filter: "USER".into(),
+            sort,
+            descending,
+            offset,
+            limit,
+            with_memory: false,
+        };
+
+        let page = index
+            .list(id, None, &query(KeySort::Name, false, 0, 10))
+            .await;
+        assert_eq!(page.keys, ["User:3", "user:1", "user:2"]);
+        assert_eq!((page.total_keys, page.matched, page.unsorted), (4, 3, 0));
+
+        let page = index
+            .list(id, None, &query(KeySort::Size, true, 0, 10))
+            .await;
+        assert_eq!(page.keys, ["user:1", "user:2", "User:3"]);
+        assert_eq!(page.unsorted, 1);
+
+        let page = index
+            .list(id, None, &query(KeySort::Ttl, false, 1, 1))
+            .await;
+        assert_eq!(page.keys, ["user:1"]);
+
+        let page = index
+            .list(id, None, &query(KeySort::Type, false, 0, 10))
+            .await;
+        assert_eq!(page.keys, ["user:1", "user:2", "User:3"]);
+    }
+}
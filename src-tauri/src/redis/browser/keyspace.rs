@@ -0,0 +1,601 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use deadpool_redis::Pool;
+use tauri::{Emitter, Manager};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::model::{
+    KeyGrowth, KeyspaceComparison, KeyspaceSnapshot, NamespaceDelta, NamespaceSummary, SnapshotKey,
+};
+use crate::config::keyspace_store;
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::throttle::Throttle;
+use crate::utils::errors::AppError;
+
+/// SCAN COUNT hint used while capturing a snapshot.
+const SCAN_BATCH: u32 = 1000;
+/// Most keys recorded in a snapshot's key index.
+pub const MAX_INDEXED_KEYS: usize = 50_000;
+/// Keys listed per namespace for each kind of change in a comparison.
+const CHANGE_SAMPLE_SIZE: usize = 20;
+
+/// Keys recorded while capturing, keeping the first ones in name order so
+/// two capped indexes of the same keyspace cover the same range.
+#[derive(Debug)]
+struct KeyCollector {
+    keys: BTreeMap<String, (String, u64)>,
+    limit: usize,
+    truncated: bool,
+}
+
+impl KeyCollector {
+    fn new(limit: usize) -> Self {
+        Self {
+            keys: BTreeMap::new(),
+            limit,
+            truncated: false,
+        }
+    }
+
+    fn add(&mut self, key: &str, key_type: String, memory_bytes: u64) {
+        if self.truncated
+            && self
+                .keys
+                .last_key_value()
+                .is_some_and(|(last, _)| key > last.as_str())
+        {
+            return;
+        }
+        self.keys.insert(key.to_string(), (key_type, memory_bytes));
+        if self.keys.len() > self.limit {
+            self.keys.pop_last();
+            self.truncated = true;
+        }
+    }
+
+    /// The recorded keys, and the last of them if some were left out.
+    fn finish(self) -> (Vec<SnapshotKey>, Option<String>) {
+        let cutoff = if self.truncated {
+            self.keys.last_key_value().map(|(key, _)| key.clone())
+        } else {
+            None
+        };
+        let keys = self
+            .keys
+            .into_iter()
+            .map(|(key, (key_type, memory_bytes))| SnapshotKey {
+                key,
+                key_type,
+                memory_bytes,
+            })
+            .collect();
+        (keys, cutoff)
+    }
+}
+
+/// Walk the whole keyspace and summarize key count and memory per prefix.
+///
+/// Type and memory come from a pipelined TYPE and MEMORY USAGE per SCAN
+/// batch. Besides the summary, returns the key index: type and memory of
+/// up to [`MAX_INDEXED_KEYS`] keys, the first ones in name order.
+///
+/// Each batch waits for the connection's `throttle`, pausing while its
+/// circuit breaker is open.
+pub async fn capture_snapshot(
+    pool: &Pool,
+    throttle: &Throttle,
+    connection_id: &str,
+    delimiter: &str,
+    name: Option<String>,
+) -> Result<(KeyspaceSnapshot, Vec<SnapshotKey>), AppError> {
+    capture_snapshot_with_progress(pool, throttle, connection_id, delimiter, name, |_| {}).await
+}
+
+/// Like [`capture_snapshot`], calling `on_batch` with the number of keys
+/// scanned so far after each SCAN batch.
+pub async fn capture_snapshot_with_progress(
+    pool: &Pool,
+    throttle: &Throttle,
+    connection_id: &str,
+    delimiter: &str,
+    name: Option<String>,
+    mut on_batch: impl FnMut(u64),
+) -> Result<(KeyspaceSnapshot, Vec<SnapshotKey>), AppError> {
+    let mut conn = pool.get().await?;
+    let mut scanned: u64 = 0;
+    let mut totals: HashMap<String, (u64, u64)> = HashMap::new();
+    let mut collector = KeyCollector::new(MAX_INDEXED_KEYS);
+    let mut cursor: u64 = 0;
+
+    loop {
+        throttle.batch(1).await;
+        let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(SCAN_BATCH)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("SCAN failed: {e}")))?;
+
+        if !keys.is_empty() {
+            throttle.acquire(keys.len() * 2).await;
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                pipe.cmd("TYPE").arg(key);
+                pipe.cmd("MEMORY").arg("USAGE").arg(key);
+            }
+            // Keys that expire after SCAN come back as type "none" and a nil size.
+            let infos: Vec<(String, Option<u64>)> = pipe
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("MEMORY USAGE failed: {e}")))?;
+
+            for (key, (key_type, size)) in keys.iter().zip(infos) {
+                let entry = totals
+                    .entry(prefix_of(key, delimiter).to_string())
+                    .or_default();
+                entry.0 += 1;
+                entry.1 += size.unwrap_or(0);
+                if key_type != "none" {
+                    collector.add(key, key_type, size.unwrap_or(0));
+                }
+            }
+        }
+
+        scanned += keys.len() as u64;
+        on_batch(scanned);
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    let mut snapshot = build_snapshot(connection_id, delimiter, totals);
+    let (keys, cutoff) = collector.finish();
+    snapshot.name = name;
+    snapshot.indexed_keys = keys.len() as u64;
+    snapshot.index_cutoff = cutoff;
+    Ok((snapshot, keys))
+}
+
+/// Assemble a snapshot from per-prefix `(key_count, memory_bytes)` totals.
+fn build_snapshot(
+    connection_id: &str,
+    delimiter: &str,
+    totals: HashMap<String, (u64, u64)>,
+) -> KeyspaceSnapshot {
+    let mut namespaces: Vec<NamespaceSummary> = totals
+        .into_iter()
+        .map(|(prefix, (key_count, memory_bytes))| NamespaceSummary {
+            prefix,
+            key_count,
+            memory_bytes,
+        })
+        .collect();
+    namespaces.sort_by(|a, b| {
+        b.memory_bytes
+            .cmp(&a.memory_bytes)
+            .then_with(|| a.prefix.cmp(&b.prefix))
+    });
+
+    KeyspaceSnapshot {
+        id: uuid::Uuid::new_v4(),
+        connection_id: connection_id.to_string(),
+        name: None,
+        captured_at: chrono::Utc::now(),
+        delimiter: delimiter.to_string(),
+        total_keys: namespaces.iter().map(|n| n.key_count).sum(),
+        total_memory_bytes: namespaces.iter().map(|n| n.memory_bytes).sum(),
+        namespaces,
+        indexed_keys: 0,
+        index_cutoff: None,
+    }
+}
+
+/// Compare two snapshots namespace by namespace.
+///
+/// Namespaces present in only one snapshot count as zero in the other.
+pub fn compare(before: &KeyspaceSnapshot, after: &KeyspaceSnapshot) -> KeyspaceComparison {
+    let lookup = |snapshot: &KeyspaceSnapshot, prefix: &str| {
+        snapshot
+            .namespaces
+            .iter()
+            .find(|n| n.prefix == prefix)
+            .map_or((0, 0), |n| (n.key_count, n.memory_bytes))
+    };
+
+    let prefixes: BTreeSet<&str> = before
+        .namespaces
+        .iter()
+        .chain(&after.namespaces)
+        .map(|n| n.prefix.as_str())
+        .collect();
+
+    let mut namespaces: Vec<NamespaceDelta> = prefixes
+        .into_iter()
+        .map(|prefix| {
+            let (keys_before, memory_before) = lookup(before, prefix);
+            let (keys_after, memory_after) = lookup(after, prefix);
+            NamespaceDelta {
+                prefix: prefix.to_string(),
+                keys_before,
+                keys_after,
+                key_delta: delta(keys_before, keys_after),
+                memory_before,
+                memory_after,
+                memory_delta: delta(memory_before, memory_after),
+                ..empty_delta(prefix)
+            }
+        })
+        .collect();
+    namespaces.sort_by_key(|n| std::cmp::Reverse(n.memory_delta.unsigned_abs()));
+
+    KeyspaceComparison {
+        before: before.id,
+        after: after.id,
+        key_delta: delta(before.total_keys, after.total_keys),
+        memory_delta: delta(before.total_memory_bytes, after.total_memory_bytes),
+        namespaces,
+        key_level: false,
+        index_cutoff: None,
+    }
+}
+
+/// Like [`compare`], adding the keys each namespace gained, lost or grew
+/// from the two snapshots' key indexes.
+///
+/// When either index was cut short, only keys up to the earlier cutoff are
+/// compared, so keys beyond it aren't reported as added or removed.
+pub fn compare_with_keys(
+    before: &KeyspaceSnapshot,
+    before_keys: &[SnapshotKey],
+    after: &KeyspaceSnapshot,
+    after_keys: &[SnapshotKey],
+) -> KeyspaceComparison {
+    let mut comparison = compare(before, after);
+    let cutoff = match (&before.index_cutoff, &after.index_cutoff) {
+        (Some(b), Some(a)) => Some(b.min(a).clone()),
+        (b, a) => b.clone().or_else(|| a.clone()),
+    };
+    let in_range = |key: &SnapshotKey| cutoff.as_ref().is_none_or(|c| key.key <= *c);
+    let old: HashMap<&str, u64> = before_keys
+        .iter()
+        .filter(|k| in_range(k))
+        .map(|k| (k.key.as_str(), k.memory_bytes))
+        .collect();
+    let new: HashMap<&str, u64> = after_keys
+        .iter()
+        .filter(|k| in_range(k))
+        .map(|k| (k.key.as_str(), k.memory_bytes))
+        .collect();
+
+    let mut by_prefix: HashMap<&str, NamespaceDelta> = HashMap::new();
+    let delimiter = after.delimiter.as_str();
+    for (&key, &memory_after) in &new {
+        match old.get(key) {
+            None => {
+                let delta = changes_for(&mut by_prefix, key, delimiter);
+                delta.added_keys += 1;
+                delta.added_sample.push(key.to_string());
+            }
+            Some(&memory_before) if memory_after > memory_before => {
+                let delta = changes_for(&mut by_prefix, key, delimiter);
+                delta.grown_keys += 1;
+                delta.grown_sample.push(KeyGrowth {
+                    key: key.to_string(),
+                    memory_before,
+                    memory_after,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for &key in old.keys().filter(|key| !new.contains_key(*key)) {
+        let delta = changes_for(&mut by_prefix, key, delimiter);
+        delta.removed_keys += 1;
+        delta.removed_sample.push(key.to_string());
+    }
+
+    for changes in by_prefix.into_values() {
+        // Missing only when the snapshots were taken with different delimiters.
+        let namespaces = &mut comparison.namespaces;
+        let index = namespaces
+            .iter()
+            .position(|n| n.prefix == changes.prefix)
+            .unwrap_or_else(|| {
+                namespaces.push(empty_delta(&changes.prefix));
+                namespaces.len() - 1
+            });
+        let target = &mut namespaces[index];
+        target.added_keys = changes.added_keys;
+        target.removed_keys = changes.removed_keys;
+        target.grown_keys = changes.grown_keys;
+        target.added_sample = sample(changes.added_sample, Ord::cmp);
+        target.removed_sample = sample(changes.removed_sample, Ord::cmp);
+        target.grown_sample = sample(changes.grown_sample, |a, b| {
+            let growth = |g: &KeyGrowth| g.memory_after - g.memory_before;
+            growth(b).cmp(&growth(a)).then_with(|| a.key.cmp(&b.key))
+        });
+    }
+
+    comparison.key_level = true;
+    comparison.index_cutoff = cutoff;
+    comparison
+}
+
+/// The changes collected for `key`'s namespace.
+fn changes_for<'a, 'k>(
+    by_prefix: &'a mut HashMap<&'k str, NamespaceDelta>,
+    key: &'k str,
+    delimiter: &str,
+) -> &'a mut NamespaceDelta {
+    let prefix = prefix_of(key, delimiter);
+    by_prefix
+        .entry(prefix)
+        .or_insert_with(|| empty_delta(prefix))
+}
+
+fn empty_delta(prefix: &str) -> NamespaceDelta {
+    NamespaceDelta {
+        prefix: prefix.to_string(),
+        keys_before: 0,
+        keys_after: 0,
+        key_delta: 0,
+        memory_before: 0,
+        memory_after: 0,
+        memory_delta: 0,
+        added_keys: 0,
+        removed_keys: 0,
+        grown_keys: 0,
+        added_sample: Vec::new(),
+        removed_sample: Vec::new(),
+        grown_sample: Vec::new(),
+    }
+}
+
+/// The first [`CHANGE_SAMPLE_SIZE`] items in `order`.
+fn sample<T>(mut items: Vec<T>, order: impl FnMut(&T, &T) -> std::cmp::Ordering) -> Vec<T> {
+    items.sort_by(order);
+    items.truncate(CHANGE_SAMPLE_SIZE);
+    items
+}
+
+/// First key segment before `delimiter`, or empty if the key has none.
+pub fn prefix_of<'a>(key: &'a str, delimiter: &str) -> &'a str {
+    key.split_once(delimiter).map_or("", |(prefix, _)| prefix)
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn delta(before: u64, after: u64) -> i64 {
+    after as i64 - before as i64
+}
+
+/// Manages periodic keyspace snapshot tasks, one per connection.
+pub struct KeyspaceSnapshotter {
+    handles: Arc<RwLock<HashMap<String, AbortHandle>>>,
+}
+
+impl Default for KeyspaceSnapshotter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyspaceSnapshotter {
+    /// Create a snapshotter with no scheduled captures.
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Capture and persist a snapshot every `interval_secs`.
+    ///
+    /// Each capture is emitted as `browser:keyspace-snapshot`. Captures
+    /// follow `throttle` and pause while its breaker is open, and go through
+    /// the connection's current pool, so they survive a reconnect. Replaces
+    /// any schedule already running for this connection.
+    pub async fn start(
+        &self,
+        connection_id: Uuid,
+        throttle: Arc<Throttle>,
+        delimiter: String,
+        interval_secs: u64,
+        app_handle: tauri::AppHandle,
+    ) {
+        let conn_id = connection_id.to_string();
+        self.stop(&conn_id).await;
+
+        let key = conn_id.clone();
+        let task = tokio::spawn(async move {
+            let interval = Duration::from_secs(interval_secs);
+            loop {
+                let captured = async {
+                    let pool = app_handle
+                        .state::<ConnectionManager>()
+                        .get_pool(&connection_id)
+                        .await?;
+                    capture_snapshot(&pool, &throttle, &conn_id, &delimiter, None).await
+                };
+                match captured.await {
+                    Ok((snapshot, keys)) => {
+                        if let Err(e) =
+                            keyspace_store::save_snapshot(&app_handle, &snapshot, &keys).await
+                        {
+                            tracing::warn!(connection_id = %conn_id, "Failed to persist keyspace snapshot: {e}");
+                        }
+                        let _ = app_handle.emit("browser:keyspace-snapshot", &snapshot);
+                    }
+                    Err(e) => {
+                        tracing::warn!(connection_id = %conn_id, "Keyspace snapshot failed: {e}");
+                    }
+                }
+
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        let mut h = self.handles.write().await;
+        h.insert(key, task.abort_handle());
+    }
+
+    /// Stop periodic captures for a connection.
+    pub async fn stop(&self, connection_id: &str) {
+        let mut h = self.handles.write().await;
+        if let Some(handle) = h.remove(connection_id) {
+            handle.abort();
+            tracing::info!(connection_id = %connection_id, "Keyspace snapshots stopped");
+        }
+    }
+
+    /// Stop all periodic captures (e.g., on app shutdown).
+    pub async fn stop_all(&self) {
+        let mut h = self.handles.write().await;
+        for (id, handle) in h.drain() {
+            handle.abort();
+            tracing::info!(connection_id = %id, "Keyspace snapshots stopped (shutdown)");
+        }
+    }
+
+    /// Check if periodic captures are running for a connection.
+    pub async fn is_running(&self, connection_id: &str) -> bool {
+        let h = self.handles.read().await;
+        h.contains_key(connection_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(namespaces: &[(&str, u64, u64)]) -> KeyspaceSnapshot {
+        let totals = namespaces
+            .iter()
+            .map(|(p, k, m)| ((*p).to_string(), (*k, *m)))
+            .collect();
+        build_snapshot("conn-1", ":", totals)
+    }
+
+    #[test]
+    fn test_prefix_of() {
+        assert_eq!(prefix_of("user:1:name", ":"), "user");
+        assert_eq!(prefix_of("standalone", ":"), "");
+        assert_eq!(prefix_of("a/b", "/"), "a");
+    }
+
+    #[test]
+    fn test_build_snapshot_sorts_and_totals() {
+        let snap = snapshot(&[("user", 10, 1_000), ("session", 5, 9_000)]);
+        assert_eq!(snap.total_keys, 15);
+        assert_eq!(snap.total_memory_bytes, 10_000);
+        assert_eq!(snap.namespaces[0].prefix, "session");
+    }
+
+    #[test]
+    fn test_compare_growth_and_new_namespace() {
+        let before = snapshot(&[("user", 10, 1_000), ("cache", 100, 50_000)]);
+        let after = snapshot(&[("user", 12, 1_200), ("cache", 40, 20_000), ("tmp", 3, 300)]);
+        let cmp = compare(&before, &after);
+
+        assert_eq!(cmp.key_delta, -55);
+        assert_eq!(cmp.memory_delta, -29_500);
+        assert_eq!(cmp.namespaces[0].prefix, "cache");
+        assert_eq!(cmp.namespaces[0].memory_delta, -30_000);
+
+        let tmp = cmp.namespaces.iter().find(|n| n.prefix == "tmp").unwrap();
+        assert_eq!(tmp.keys_before, 0);
+        assert_eq!(tmp.key_delta, 3);
+    }
+
+    fn index(keys: &[(&str, u64)]) -> Vec<SnapshotKey> {
+        keys.iter()
+            .map(|(key, memory_bytes)| SnapshotKey {
+                key: (*key).to_string(),
+                key_type: "string".into(),
+                memory_bytes: *memory_bytes,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_key_collector_keeps_first_keys_by_name() {
+        let mut collector = KeyCollector::new(2);
+        for key in ["c", "a", "d", "b"] {
+            collector.add(key, "string".into(), 1);
+        }
+        let (keys, cutoff) = collector.finish();
+        let names: Vec<_> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(cutoff.as_deref(), Some("b"));
+
+        let mut collector = KeyCollector::new(5);
+        collector.add("a", "hash".into(), 10);
+        assert_eq!(collector.finish().1, None);
+    }
+
+    #[test]
+    fn test_compare_with_keys_reports_added_removed_grown() {
+        let before = snapshot(&[("user", 3, 300), ("cache", 1, 100)]);
+        let after = snapshot(&[("user", 3, 500), ("session", 1, 50)]);
+        let cmp = compare_with_keys(
+            &before,
+            &index(&[
+                ("cache:1", 100),
+                ("user:1", 100),
+                ("user:2", 100),
+                ("user:3", 100),
+            ]),
+            &after,
+            &index(&[
+                ("session:9", 50),
+                ("user:1", 300),
+                ("user:2", 100),
+                ("user:4", 100),
+            ]),
+        );
+        assert!(cmp.key_level);
+        let ns = |prefix: &str| cmp.namespaces.iter().find(|n| n.prefix == prefix).unwrap();
+
+        let user = ns("user");
+        assert_eq!(
+            (user.added_keys, user.removed_keys, user.grown_keys),
+            (1, 1, 1)
+        );
+        assert_eq!(user.added_sample, ["user:4"]);
+        assert_eq!(user.removed_sample, ["user:3"]);
+        assert_eq!(user.grown_sample[0].memory_after, 300);
+        assert_eq!(ns("cache").removed_sample, ["cache:1"]);
+        assert_eq!(ns("session").added_keys, 1);
+    }
+
+    #[test]
+    fn test_compare_with_keys_stops_at_cutoff() {
+        let before = KeyspaceSnapshot {
+            index_cutoff: Some("b".into()),
+            ..snapshot(&[("", 3, 3)])
+        };
+        let after = snapshot(&[("", 3, 3)]);
+        let cmp = compare_with_keys(
+            &before,
+            &index(&[("a", 1), ("b", 1)]),
+            &after,
+            &index(&[("a", 1), ("b", 1), ("c", 1)]),
+        );
+        assert_eq!(cmp.index_cutoff.as_deref(), Some("b"));
+        assert_eq!(cmp.namespaces[0].added_keys, 0);
+    }
+
+    #[tokio::test]
+    async fn test_snapshotter_new_not_running() {
+        let snapshotter = KeyspaceSnapshotter::new();
+        assert!(!snapshotter.is_running("conn-1").await);
+        snapshotter.stop("conn-1").await;
+    }
+}
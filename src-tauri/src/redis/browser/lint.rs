@@ -0,0 +1,274 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use deadpool_redis::Pool;
+
+use super::model::{CasePolicy, LintReport, LintRule, LintViolation, NamingRules, RuleViolations};
+use super::scanner;
+use crate::redis::connection::throttle::Throttle;
+use crate::utils::errors::AppError;
+use crate::utils::glob;
+
+/// Violations listed per rule; the rest are only counted.
+const MAX_EXAMPLES: usize = 100;
+
+/// Reject rules that can't be applied.
+pub fn validate(rules: &NamingRules) -> Result<(), AppError> {
+    if rules.delimiter.is_empty() {
+        return Err(AppError::InvalidInput("Delimiter must not be empty".into()));
+    }
+    if rules
+        .foreign_delimiters
+        .iter()
+        .any(|d| d.is_empty() || rules.delimiter.contains(d.as_str()))
+    {
+        return Err(AppError::InvalidInput(
+            "Foreign delimiters must be non-empty and differ from the delimiter".into(),
+        ));
+    }
+    if rules.max_depth == Some(0) {
+        return Err(AppError::InvalidInput(
+            "Max depth must be at least 1".into(),
+        ));
+    }
+    if rules.ttl_required.iter().any(String::is_empty) {
+        return Err(AppError::InvalidInput(
+            "TTL patterns must not be empty".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// The name-only rules `key` breaks; the TTL rule needs a round trip and is
+/// checked separately.
+pub fn check_name(rules: &NamingRules, key: &str) -> Vec<(LintRule, String)> {
+    let mut found = Vec::new();
+    if let Some(foreign) = rules
+        .foreign_delimiters
+        .iter()
+        .find(|d| key.contains(d.as_str()))
+    {
+        found.push((
+            LintRule::MixedDelimiter,
+            format!("uses '{foreign}' instead of '{}'", rules.delimiter),
+        ));
+    }
+    let segments: Vec<&str> = key.split(rules.delimiter.as_str()).collect();
+    if let Some(max) = rules.max_depth {
+        if segments.len() > max as usize {
+            found.push((
+                LintRule::MaxDepth,
+                format!("{} segments, at most {max} allowed", segments.len()),
+            ));
+        }
+    }
+    if let Some(c) = key
+        .chars()
+        .find(|c| c.is_control() || rules.forbidden_chars.contains(*c))
+    {
+        found.push((LintRule::ForbiddenChar, format!("contains {c:?}")));
+    }
+    let namespace = &segments[..segments.len() - 1];
+    let wrong_case = match rules.case {
+        CasePolicy::Any => None,
+        CasePolicy::Lower => namespace
+            .iter()
+            .find(|s| s.chars().any(char::is_uppercase))
+            .map(|s| format!("segment '{s}' is not lowercase")),
+        CasePolicy::Upper => namespace
+            .iter()
+            .find(|s| s.chars().any(char::is_lowercase))
+            .map(|s| format!("segment '{s}' is not uppercase")),
+    };
+    if let Some(detail) = wrong_case {
+        found.push((LintRule::Case, detail));
+    }
+    found
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    count: u64,
+    examples: Vec<LintViolation>,
+}
+
+#[derive(Debug, Default)]
+struct Findings {
+    rules: BTreeMap<LintRule, Tally>,
+    violating_keys: u64,
+}
+
+impl Findings {
+    fn add(&mut self, key: &str, violations: Vec<(LintRule, String)>) {
+        if violations.is_empty() {
+            return;
+        }
+        self.violating_keys += 1;
+        for (rule, detail) in violations {
+            let tally = self.rules.entry(rule).or_default();
+            tally.count += 1;
+            if tally.examples.len() < MAX_EXAMPLES {
+                tally.examples.push(LintViolation {
+                    key: key.to_string(),
+                    detail,
+                });
+            }
+        }
+    }
+
+    fn finish(self, scanned: u64, elapsed_ms: u64) -> LintReport {
+        let mut rules: Vec<RuleViolations> = self
+            .rules
+            .into_iter()
+            .map(|(rule, tally)| RuleViolations {
+                rule,
+                count: tally.count,
+                examples: tally.examples,
+            })
+            .collect();
+        rules.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.rule.cmp(&b.rule)));
+        LintReport {
+            scanned,
+            violating_keys: self.violating_keys,
+            rules,
+            elapsed_ms,
+        }
+    }
+}
+
+/// Check every key matching `pattern` against `rules`.
+///
+/// Key names are checked as SCAN returns them; keys matching a
+/// `ttl_required` glob get a pipelined TTL per batch. Each batch waits for
+/// the connection's `throttle`, and `on_batch` gets the keys scanned so far.
+pub async fn lint_keys(
+    pool: &Pool,
+    throttle: &Throttle,
+    rules: &NamingRules,
+    pattern: &str,
+    mut on_batch: impl FnMut(u64),
+) -> Result<LintReport, AppError> {
+    let started = Instant::now();
+    let mut conn = pool.get().await?;
+    let mut findings = Findings::default();
+    let mut scanned = 0u64;
+    let mut cursor = 0;
+
+    loop {
+        throttle.batch(1).await;
+        let (next, keys) = scanner::scan_match(&mut conn, cursor, pattern).await?;
+
+        let mut violations: Vec<Vec<(LintRule, String)>> =
+            keys.iter().map(|key| check_name(rules, key)).collect();
+        let needs_ttl: Vec<usize> = (0..keys.len())
+            .filter(|&i| {
+                rules
+                    .ttl_required
+                    .iter()
+                    .any(|p| glob::matches(p, &keys[i]))
+            })
+            .collect();
+        if !needs_ttl.is_empty() {
+            throttle.acquire(needs_ttl.len()).await;
+            let mut pipe = redis::pipe();
+            for &i in &needs_ttl {
+                pipe.cmd("TTL").arg(&keys[i]);
+            }
+            let ttls: Vec<i64> = pipe
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("TTL failed: {e}")))?;
+            for (i, ttl) in needs_ttl.into_iter().zip(ttls) {
+                // -2: expired since the scan.
+                if ttl == -1 {
+                    violations[i].push((LintRule::MissingTtl, "has no TTL".into()));
+                }
+            }
+        }
+        for (key, found) in keys.iter().zip(violations) {
+            findings.add(key, found);
+        }
+
+        scanned += keys.len() as u64;
+        on_batch(scanned);
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    let elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    Ok(findings.finish(scanned, elapsed_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_of(found: &[(LintRule, String)]) -> Vec<LintRule> {
+        found.iter().map(|(rule, _)| *rule).collect()
+    }
+
+    #[test]
+    fn test_check_name_default_rules() {
+        let rules = NamingRules::default();
+        assert!(check_name(&rules, "user:42:profile").is_empty());
+        // The last segment is an ID and may use any case.
+        assert!(check_name(&rules, "session:AbC123").is_empty());
+
+        assert_eq!(
+            rules_of(&check_name(&rules, "User/42")),
+            vec![LintRule::MixedDelimiter]
+        );
+        assert_eq!(
+            rules_of(&check_name(&rules, "Users:42")),
+            vec![LintRule::Case]
+        );
+        assert_eq!(
+            rules_of(&check_name(&rules, "a:b:c:d:e:f:g")),
+            vec![LintRule::MaxDepth]
+        );
+        let found = check_name(&rules, "user:john doe");
+        assert_eq!(rules_of(&found), vec![LintRule::ForbiddenChar]);
+        assert_eq!(found[0].1, "contains ' '");
+        assert_eq!(
+            rules_of(&check_name(&rules, "user:\u{7}")),
+            vec![LintRule::ForbiddenChar]
+        );
+    }
+
+    #[test]
+    fn test_findings_group_by_rule() {
+        let rules = NamingRules {
+            case: CasePolicy::Upper,
+            ..NamingRules::default()
+        };
+        let mut findings = Findings::default();
+        for key in ["user:1", "ORDER:2", "cart:3|x"] {
+            findings.add(key, check_name(&rules, key));
+        }
+        let report = findings.finish(3, 0);
+        assert_eq!(report.violating_keys, 2);
+        assert_eq!(report.rules[0].rule, LintRule::Case);
+        assert_eq!(report.rules[0].count, 2);
+        assert_eq!(report.rules[1].rule, LintRule::MixedDelimiter);
+        assert_eq!(report.rules[1].examples[0].key, "cart:3|x");
+    }
+
+    #[test]
+    fn test_validate_rejects_unusable_rules() {
+        assert!(validate(&NamingRules::default()).is_ok());
+        let bad = NamingRules {
+            foreign_delimiters: vec![":".into()],
+            ..NamingRules::default()
+        };
+        assert!(validate(&bad).is_err());
+        let bad = NamingRules {
+            max_depth: Some(0),
+            ..NamingRules::default()
+        };
+        assert!(validate(&bad).is_err());
+    }
+}
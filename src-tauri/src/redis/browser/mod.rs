@@ -1,5 +1,17 @@
 // SPDX-License-Identifier: MIT
 
+pub mod filter;
+pub mod introspect;
+pub mod invalidation;
+pub mod key_index;
+pub mod keyspace;
+pub mod lint;
 pub mod model;
+pub mod rename;
+pub mod sampling;
 pub mod scanner;
+pub mod schema;
+pub mod stats;
+pub mod tenants;
 pub mod tree;
+pub mod value_search;
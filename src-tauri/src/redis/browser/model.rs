@@ -79,6 +79,28 @@ pub struct KeyInfo {
     pub length: Option<u64>,
 }
 
+/// Low-level storage details of one key, from OBJECT and DEBUG OBJECT.
+///
+/// Each figure is `None` when the server refused or doesn't support the
+/// subcommand; `unavailable` then says why. IDLETIME and FREQ are mutually
+/// exclusive: which one works depends on the maxmemory policy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyIntrospection {
+    pub key: std::string::String,
+    pub encoding: Option<std::string::String>,
+    pub refcount: Option<i64>,
+    /// Seconds since the key was last accessed (LRU policies).
+    pub idle_secs: Option<u64>,
+    /// Logarithmic access frequency counter (LFU policies).
+    pub freq: Option<u64>,
+    /// Size of the value when serialized (as RDB would write it), from
+    /// DEBUG OBJECT, which many servers disable.
+    pub serialized_length: Option<u64>,
+    /// One `SUBCOMMAND: reason` line per figure that could not be read.
+    pub unavailable: Vec<std::string::String>,
+}
+
 /// Result of a single SCAN iteration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -110,6 +132,434 @@ pub struct KeyNode {
     pub depth: u32,
 }
 
+/// Key count and memory for one namespace prefix in a keyspace snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceSummary {
+    /// First key segment before the delimiter; empty for keys without one.
+    pub prefix: std::string::String,
+    pub key_count: u64,
+    pub memory_bytes: u64,
+}
+
+/// A persisted summary of the scanned keyset at a point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyspaceSnapshot {
+    pub id: uuid::Uuid,
+    pub connection_id: std::string::String,
+    /// Label given when the snapshot was taken by hand, e.g. "before deploy".
+    #[serde(default)]
+    pub name: Option<std::string::String>,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+    pub delimiter: std::string::String,
+    pub total_keys: u64,
+    pub total_memory_bytes: u64,
+    /// Sorted by memory, largest first.
+    pub namespaces: Vec<NamespaceSummary>,
+    /// Keys recorded in the snapshot's key index; 0 for snapshots taken
+    /// before key indexes existed.
+    #[serde(default)]
+    pub indexed_keys: u64,
+    /// Set when the keyspace was too large to index in full: the index
+    /// holds the keys up to and including this one, in name order.
+    #[serde(default)]
+    pub index_cutoff: Option<std::string::String>,
+}
+
+/// One key in a snapshot's key index.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotKey {
+    pub key: std::string::String,
+    pub key_type: std::string::String,
+    pub memory_bytes: u64,
+}
+
+/// A key whose memory grew between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyGrowth {
+    pub key: std::string::String,
+    pub memory_before: u64,
+    pub memory_after: u64,
+}
+
+/// Change in a single namespace between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceDelta {
+    pub prefix: std::string::String,
+    pub keys_before: u64,
+    pub keys_after: u64,
+    pub key_delta: i64,
+    pub memory_before: u64,
+    pub memory_after: u64,
+    pub memory_delta: i64,
+    /// Keys only in the later snapshot's index.
+    pub added_keys: u64,
+    /// Keys only in the earlier snapshot's index.
+    pub removed_keys: u64,
+    /// Keys in both indexes that use more memory than before.
+    pub grown_keys: u64,
+    pub added_sample: Vec<std::string::String>,
+    pub removed_sample: Vec<std::string::String>,
+    /// Largest growth first.
+    pub grown_sample: Vec<KeyGrowth>,
+}
+
+/// Result of comparing two keyspace snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyspaceComparison {
+    pub before: uuid::Uuid,
+    pub after: uuid::Uuid,
+    pub key_delta: i64,
+    pub memory_delta: i64,
+    /// Sorted by absolute memory change, largest first.
+    pub namespaces: Vec<NamespaceDelta>,
+    /// Both snapshots have key indexes, so per-key changes are filled in.
+    pub key_level: bool,
+    /// Per-key changes only cover keys up to this one, in name order,
+    /// because an index was cut short.
+    pub index_cutoff: Option<std::string::String>,
+}
+
+/// Column a flat key listing is sorted by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySort {
+    #[default]
+    Name,
+    Type,
+    Ttl,
+    Size,
+}
+
+/// One page request for `browser_list_keys`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KeyListQuery {
+    /// Case-insensitive substring keys must contain; empty matches all.
+    pub filter: std::string::String,
+    pub sort: KeySort,
+    pub descending: bool,
+    pub offset: u64,
+    pub limit: u32,
+    /// Fetch MEMORY USAGE for the page so `size` sorting has data.
+    pub with_memory: bool,
+}
+
+impl Default for KeyListQuery {
+    fn default() -> Self {
+        Self {
+            filter: std::string::String::new(),
+            sort: KeySort::Name,
+            descending: false,
+            offset: 0,
+            limit: 100,
+            with_memory: false,
+        }
+    }
+}
+
+/// One page of the flat key listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyListPage {
+    /// Keys on this page with fresh type, TTL and (optionally) size. Keys
+    /// that no longer exist on the server are left out.
+    pub items: Vec<KeyInfo>,
+    /// Keys in the index for this connection and database.
+    pub total_keys: u64,
+    /// Keys matching the filter.
+    pub matched: u64,
+    pub offset: u64,
+    /// Matching keys with no cached metadata for the sort column; they are
+    /// listed after the others, so the order is partial until they are loaded.
+    pub unsorted: u64,
+}
+
+/// Number of sampled keys of one type in a namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeCount {
+    pub key_type: RedisKeyType,
+    pub count: u64,
+}
+
+/// Sampled keys per TTL band in a namespace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TtlDistribution {
+    pub persistent: u64,
+    pub under_minute: u64,
+    pub under_hour: u64,
+    pub under_day: u64,
+    pub over_day: u64,
+}
+
+/// Summary of the keys under one tree folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceStats {
+    pub prefix: std::string::String,
+    /// Known keys under the prefix in the key index.
+    pub key_count: u64,
+    /// Keys whose metadata was fetched; the distributions cover only these.
+    pub sampled: u64,
+    /// Most common type first.
+    pub types: Vec<TypeCount>,
+    pub ttl: TtlDistribution,
+    /// MEMORY USAGE summed over the sampled keys.
+    pub sampled_memory_bytes: u64,
+    /// Sampled memory scaled up to `key_count`.
+    pub estimated_memory_bytes: u64,
+}
+
+/// Estimated share of the keyspace under one prefix, from random samples.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceEstimate {
+    /// First key segment before the delimiter; empty for keys without one.
+    pub prefix: std::string::String,
+    /// Samples that fell under the prefix.
+    pub hits: u64,
+    /// Estimated fraction of all keys, 0 to 1.
+    pub proportion: f64,
+    /// Bounds of the confidence interval around `proportion`.
+    pub low: f64,
+    pub high: f64,
+    /// `proportion` applied to the database's key count.
+    pub estimated_keys: u64,
+}
+
+/// Namespace distribution estimated from RANDOMKEY samples.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyspaceSample {
+    pub delimiter: std::string::String,
+    /// DBSIZE when sampling started.
+    pub total_keys: u64,
+    /// Keys drawn; the same key may be drawn more than once.
+    pub samples: u64,
+    /// Confidence level of the intervals, e.g. 0.95.
+    pub confidence: f64,
+    /// Most sampled first.
+    pub namespaces: Vec<NamespaceEstimate>,
+    pub elapsed_ms: u64,
+}
+
+/// One tenant's keys and estimated memory in a tenant report.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantUsage {
+    /// The part of the key that filled `{id}` in the template.
+    pub tenant_id: std::string::String,
+    pub key_count: u64,
+    /// Keys whose MEMORY USAGE was read.
+    pub sampled: u64,
+    pub sampled_memory_bytes: u64,
+    /// Sampled memory scaled up to `key_count`.
+    pub estimated_memory_bytes: u64,
+    /// Fraction of all tenants' estimated memory, 0 to 1.
+    pub memory_share: f64,
+}
+
+/// Key count and memory per tenant of a namespace template such as
+/// `tenant:{id}:*`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantReport {
+    pub template: std::string::String,
+    /// Keys matching the template.
+    pub matched_keys: u64,
+    pub tenant_count: u64,
+    /// Sum over all tenants, including those past `limit`.
+    pub estimated_memory_bytes: u64,
+    /// Largest estimated memory first, at most `limit` of them.
+    pub tenants: Vec<TenantUsage>,
+    /// Tenants were left out of `tenants`.
+    pub truncated: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Letter case required of key segments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CasePolicy {
+    Any,
+    #[default]
+    Lower,
+    Upper,
+}
+
+/// A team's key naming conventions, checked by `browser_lint_keys`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NamingRules {
+    /// The delimiter keys should use between segments.
+    pub delimiter: std::string::String,
+    /// Delimiters that mean a key doesn't use `delimiter` consistently.
+    pub foreign_delimiters: Vec<std::string::String>,
+    /// Most segments a key may have; unlimited when unset.
+    pub max_depth: Option<u32>,
+    /// Characters keys must not contain; control characters are always
+    /// reported.
+    pub forbidden_chars: std::string::String,
+    /// Case of every segment but the last, which usually holds an ID.
+    pub case: CasePolicy,
+    /// Keys matching these globs must have a TTL.
+    pub ttl_required: Vec<std::string::String>,
+}
+
+impl Default for NamingRules {
+    fn default() -> Self {
+        Self {
+            delimiter: ":".into(),
+            foreign_delimiters: vec!["/".into(), "|".into(), ";".into()],
+            max_depth: Some(6),
+            forbidden_chars: " \"'\\".into(),
+            case: CasePolicy::Lower,
+            ttl_required: vec!["cache:*".into()],
+        }
+    }
+}
+
+/// A naming rule a key can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintRule {
+    MixedDelimiter,
+    MaxDepth,
+    ForbiddenChar,
+    Case,
+    MissingTtl,
+}
+
+/// A key breaking a rule, and how.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LintViolation {
+    pub key: std::string::String,
+    pub detail: std::string::String,
+}
+
+/// The keys breaking one rule.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleViolations {
+    pub rule: LintRule,
+    pub count: u64,
+    /// The first violations found, at most 100.
+    pub examples: Vec<LintViolation>,
+}
+
+/// Result of checking the keyspace against [`NamingRules`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub scanned: u64,
+    /// Keys breaking at least one rule.
+    pub violating_keys: u64,
+    /// Rules with violations, most violated first.
+    pub rules: Vec<RuleViolations>,
+    pub elapsed_ms: u64,
+}
+
+/// What `browser_search_values` looks for, and how much it may read.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueSearchSpec {
+    /// Text to find in values (and hash fields, set members, stream fields).
+    pub query: std::string::String,
+    /// Treat `query` as a regular expression (`(?i)` for case-insensitive).
+    #[serde(default)]
+    pub regex: bool,
+    /// Glob keys must match, as in SCAN MATCH; every key when unset.
+    pub pattern: Option<std::string::String>,
+    /// Key types to inspect; empty inspects every supported type.
+    #[serde(default)]
+    pub types: Vec<RedisKeyType>,
+    /// Bytes of a string value read; longer values are searched in part.
+    pub max_value_bytes: Option<u64>,
+    /// Elements of a collection read; the rest are not searched.
+    pub max_elements: Option<u32>,
+    /// Stop after this many hits.
+    pub max_matches: Option<u32>,
+}
+
+/// One match of a value search.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueSearchHit {
+    pub key: std::string::String,
+    pub key_type: RedisKeyType,
+    /// Where in the value: hash field, member, list index or stream
+    /// `id field`; unset for strings.
+    pub location: Option<std::string::String>,
+    /// The matched text with some context around it.
+    pub snippet: std::string::String,
+}
+
+/// Payload of `browser:value-search-hits`, emitted after each SCAN batch
+/// that found something. How far the search got and how it ended arrive
+/// as `task:progress` under the search ID.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueSearchHits {
+    pub search_id: std::string::String,
+    pub connection_id: std::string::String,
+    /// Hits found in this batch only.
+    pub hits: Vec<ValueSearchHit>,
+    pub total_hits: u64,
+    /// The match limit was reached, so no more hits follow.
+    pub truncated: bool,
+}
+
+/// How often one value type was seen for a field.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldTypeCount {
+    /// `integer`, `float`, `boolean`, `json`, `string` or `empty` for hash
+    /// values; the JSON type (`string`, `array`, `null`, ...) for documents.
+    pub value_type: std::string::String,
+    pub count: u64,
+}
+
+/// One field seen in the sampled hashes or JSON documents.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSchema {
+    /// Hash field, or dotted path of a JSON document member.
+    pub name: std::string::String,
+    /// Sampled keys that have the field.
+    pub present: u64,
+    /// `present` over the sampled keys, 0 to 1.
+    pub fill_rate: f64,
+    /// Most common type first.
+    pub types: Vec<FieldTypeCount>,
+    /// A few distinct values, shortened.
+    pub examples: Vec<std::string::String>,
+}
+
+/// Field schema inferred from the hashes and JSON documents under a pattern.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaReport {
+    pub pattern: std::string::String,
+    /// Keys returned by SCAN, of any type.
+    pub scanned_keys: u64,
+    pub hash_keys: u64,
+    pub json_keys: u64,
+    /// Keys of other types, left out of the schema.
+    pub skipped_keys: u64,
+    /// Most often present first.
+    pub fields: Vec<FieldSchema>,
+    /// Sampling stopped at the key budget before the pattern was covered.
+    pub truncated: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
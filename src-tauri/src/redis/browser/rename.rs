@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+use serde::{Deserialize, Serialize};
+
+use super::key_index::KeyRecorder;
+use super::scanner;
+use crate::utils::errors::AppError;
+use crate::utils::glob;
+
+/// Renamed and colliding pairs included in a report.
+const SAMPLE_SIZE: usize = 20;
+
+/// Renames one SCAN batch atomically. Per pair: 1 renamed, 0 target exists,
+/// -1 source gone since it was scanned.
+const RENAME_BATCH_SCRIPT: &str = r"
+local out = {}
+for i = 1, #KEYS, 2 do
+  if redis.call('EXISTS', KEYS[i]) == 0 then
+    out[#out + 1] = -1
+  else
+    out[#out + 1] = redis.call('RENAMENX', KEYS[i], KEYS[i + 1])
+  end
+end
+return out
+";
+
+/// A prefix/suffix rewrite such as `cache:v1:*` → `cache:v2:*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRewrite {
+    from_prefix: String,
+    from_suffix: String,
+    to_prefix: String,
+    to_suffix: String,
+}
+
+impl KeyRewrite {
+    /// Parse a pattern and replacement that each hold exactly one `*`;
+    /// everything else in them is literal.
+    pub fn parse(pattern: &str, replacement: &str) -> Result<Self, AppError> {
+        let (Some((from_prefix, from_suffix)), Some((to_prefix, to_suffix))) =
+            (split_wildcard(pattern), split_wildcard(replacement))
+        else {
+            return Err(AppError::InvalidInput(
+                "Pattern and replacement must each contain exactly one '*'".into(),
+            ));
+        };
+        // When renamed keys can match the pattern again, SCAN could return
+        // them and rename them twice.
+        let prefixes_overlap =
+            from_prefix.starts_with(to_prefix) || to_prefix.starts_with(from_prefix);
+        let suffixes_overlap = from_suffix.ends_with(to_suffix) || to_suffix.ends_with(from_suffix);
+        if prefixes_overlap && suffixes_overlap {
+            return Err(AppError::InvalidInput(format!(
+                "Keys renamed to '{replacement}' could match '{pattern}' again"
+            )));
+        }
+        Ok(Self {
+            from_prefix: from_prefix.to_string(),
+            from_suffix: from_suffix.to_string(),
+            to_prefix: to_prefix.to_string(),
+            to_suffix: to_suffix.to_string(),
+        })
+    }
+
+    /// SCAN MATCH pattern selecting the keys to rename.
+    pub fn scan_pattern(&self) -> String {
+        format!(
+            "{}*{}",
+            glob::escape(&self.from_prefix),
+            glob::escape(&self.from_suffix)
+        )
+    }
+
+    /// New name for `key`, or `None` if it doesn't match.
+    pub fn apply(&self, key: &str) -> Option<String> {
+        let middle = key
+            .strip_prefix(self.from_prefix.as_str())?
+            .strip_suffix(self.from_suffix.as_str())?;
+        Some(format!("{}{middle}{}", self.to_prefix, self.to_suffix))
+    }
+}
+
+fn split_wildcard(pattern: &str) -> Option<(&str, &str)> {
+    let (prefix, suffix) = pattern.split_once('*')?;
+    (!suffix.contains('*')).then_some((prefix, suffix))
+}
+
+/// One key's old and new name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenamePair {
+    pub from: String,
+    pub to: String,
+}
+
+/// Outcome of a rename by pattern, also sent as progress after each batch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameReport {
+    pub dry_run: bool,
+    /// Keys matching the pattern seen so far.
+    pub matched: u64,
+    /// Keys renamed (or, in a dry run, that would be).
+    pub renamed: u64,
+    /// Keys left alone because the new name already exists.
+    pub collisions: u64,
+    /// Keys that disappeared between SCAN and rename.
+    pub missing: u64,
+    pub sample: Vec<RenamePair>,
+    pub collision_sample: Vec<RenamePair>,
+}
+
+impl RenameReport {
+    fn add(&mut self, pair: RenamePair, outcome: i64) {
+        match outcome {
+            1 => {
+                self.renamed += 1;
+                if self.sample.len() < SAMPLE_SIZE {
+                    self.sample.push(pair);
+                }
+            }
+            0 => {
+                self.collisions += 1;
+                if self.collision_sample.len() < SAMPLE_SIZE {
+                    self.collision_sample.push(pair);
+                }
+            }
+            _ => self.missing += 1,
+        }
+    }
+}
+
+/// Rename every key matching `rewrite`, one SCAN batch at a time.
+///
+/// Each batch runs as a single script, so it applies atomically; RENAMENX
+/// never overwrites an existing key, and collisions are reported instead.
+/// With `dry_run` nothing changes: targets are only checked with EXISTS.
+/// `on_batch` receives the running report after every batch.
+pub async fn rename_by_pattern(
+    pool: &Pool,
+    rewrite: &KeyRewrite,
+    dry_run: bool,
+    recorder: &KeyRecorder,
+    mut on_batch: impl FnMut(&RenameReport),
+) -> Result<RenameReport, AppError> {
+    let mut conn = pool.get().await?;
+    let pattern = rewrite.scan_pattern();
+    let mut report = RenameReport {
+        dry_run,
+        ..RenameReport::default()
+    };
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, keys) = scanner::scan_match(&mut conn, cursor, &pattern).await?;
+        let pairs: Vec<RenamePair> = keys
+            .into_iter()
+            .filter_map(|from| rewrite.apply(&from).map(|to| RenamePair { from, to }))
+            .collect();
+        report.matched += pairs.len() as u64;
+
+        if !pairs.is_empty() {
+            let outcomes = if dry_run {
+                check_targets(&mut conn, &pairs).await?
+            } else {
+                rename_batch(&mut conn, &pairs).await?
+            };
+            for (pair, outcome) in pairs.into_iter().zip(outcomes) {
+                if !dry_run && outcome == 1 {
+                    recorder.rename(&pair.from, &pair.to).await;
+                }
+                report.add(pair, outcome);
+            }
+            on_batch(&report);
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            return Ok(report);
+        }
+    }
+}
+
+/// Dry-run outcomes: 1 if the target is free, 0 if it exists.
+async fn check_targets(
+    conn: &mut deadpool_redis::Connection,
+    pairs: &[RenamePair],
+) -> Result<Vec<i64>, AppError> {
+    let mut pipe = redis::pipe();
+    for pair in pairs {
+        pipe.cmd("EXISTS").arg(&pair.to);
+    }
+    let exists: Vec<bool> = pipe
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("EXISTS check failed: {e}")))?;
+    Ok(exists.into_iter().map(|e| i64::from(!e)).collect())
+}
+
+async fn rename_batch(
+    conn: &mut deadpool_redis::Connection,
+    pairs: &[RenamePair],
+) -> Result<Vec<i64>, AppError> {
+    let mut cmd = redis::cmd("EVAL");
+    cmd.arg(RENAME_BATCH_SCRIPT).arg(pairs.len() * 2);
+    for pair in pairs {
+        cmd.arg(&pair.from).arg(&pair.to);
+    }
+    cmd.query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Batch rename failed: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_prefix_and_suffix() {
+        let rewrite = KeyRewrite::parse("cache:v1:*", "cache:v2:*").unwrap();
+        assert_eq!(rewrite.scan_pattern(), "cache:v1:*");
+        assert_eq!(
+            rewrite.apply("cache:v1:page").as_deref(),
+            Some("cache:v2:page")
+        );
+        assert_eq!(rewrite.apply("cache:v3:page"), None);
+
+        let rewrite = KeyRewrite::parse("user:*:name", "user:*:display_name").unwrap();
+        assert_eq!(
+            rewrite.apply("user:42:name").as_deref(),
+            Some("user:42:display_name")
+        );
+
+        let rewrite = KeyRewrite::parse("[old]*", "new*").unwrap();
+        assert_eq!(rewrite.scan_pattern(), r"\[old\]*");
+    }
+
+    #[test]
+    fn test_rewrite_rejects_ambiguous_input() {
+        assert!(KeyRewrite::parse("cache:*", "cache:v2").is_err());
+        assert!(KeyRewrite::parse("a*b*", "c*").is_err());
+        // cache:x would become cache:v2:x, which matches cache:* again.
+        assert!(KeyRewrite::parse("cache:*", "cache:v2:*").is_err());
+        assert!(KeyRewrite::parse("ab*", "a*").is_err());
+        assert!(KeyRewrite::parse("*:a", "*:b").is_ok());
+    }
+
+    #[test]
+    fn test_report_counts_outcomes() {
+        let mut report = RenameReport::default();
+        let pair = |n: &str| RenamePair {
+            from: format!("a:{n}"),
+            to: format!("b:{n}"),
+        };
+        report.add(pair("1"), 1);
+        report.add(pair("2"), 0);
+        report.add(pair("3"), -1);
+        assert_eq!(
+            (report.renamed, report.collisions, report.missing),
+            (1, 1, 1)
+        );
+        assert_eq!(report.collision_sample, [pair("2")]);
+    }
+}
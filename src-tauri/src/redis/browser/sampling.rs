@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use deadpool_redis::Pool;
+
+use super::keyspace::prefix_of;
+use super::model::{KeyspaceSample, NamespaceEstimate};
+use crate::utils::errors::AppError;
+
+pub const DEFAULT_SAMPLES: u32 = 2_000;
+pub const MAX_SAMPLES: u32 = 100_000;
+/// RANDOMKEY calls pipelined together.
+const BURST: u32 = 200;
+/// Pause between bursts, so sampling never monopolizes the server.
+const BURST_PAUSE: Duration = Duration::from_millis(20);
+/// z for a 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Estimate how keys are spread over namespace prefixes by drawing
+/// `samples` random keys, without walking the keyspace.
+///
+/// RANDOMKEY is O(1) and draws keys about uniformly, so each prefix's share
+/// of the draws estimates its share of the keyspace, with a Wilson score
+/// interval at 95% confidence. Rare prefixes may not be drawn at all; use
+/// a full snapshot when they matter.
+pub async fn sample_namespaces(
+    pool: &Pool,
+    delimiter: &str,
+    samples: u32,
+) -> Result<KeyspaceSample, AppError> {
+    let started = Instant::now();
+    let mut conn = pool.get().await?;
+    let total_keys: u64 = redis::cmd("DBSIZE").query_async(&mut conn).await?;
+
+    let mut hits: HashMap<String, u64> = HashMap::new();
+    let mut drawn = 0u64;
+    let mut requested = 0;
+    while total_keys > 0 && requested < samples {
+        let burst = BURST.min(samples - requested);
+        let mut pipe = redis::pipe();
+        for _ in 0..burst {
+            pipe.cmd("RANDOMKEY");
+        }
+        // Nil when the database emptied meanwhile; keys need not be UTF-8.
+        let keys: Vec<Option<Vec<u8>>> = pipe
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("RANDOMKEY failed: {e}")))?;
+        for key in keys.into_iter().flatten() {
+            let key = String::from_utf8_lossy(&key);
+            *hits
+                .entry(prefix_of(&key, delimiter).to_string())
+                .or_default() += 1;
+            drawn += 1;
+        }
+        requested += burst;
+        if requested < samples {
+            tokio::time::sleep(BURST_PAUSE).await;
+        }
+    }
+
+    Ok(KeyspaceSample {
+        delimiter: delimiter.to_string(),
+        total_keys,
+        samples: drawn,
+        confidence: 0.95,
+        namespaces: estimate(hits, drawn, total_keys),
+        elapsed_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    })
+}
+
+/// Turn per-prefix hit counts into estimates, most sampled first.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn estimate(hits: HashMap<String, u64>, drawn: u64, total_keys: u64) -> Vec<NamespaceEstimate> {
+    let mut estimates: Vec<NamespaceEstimate> = hits
+        .into_iter()
+        .map(|(prefix, hits)| {
+            let proportion = hits as f64 / drawn as f64;
+            let (low, high) = wilson_interval(hits, drawn, Z_95);
+            NamespaceEstimate {
+                prefix,
+                hits,
+                proportion,
+                low,
+                high,
+                estimated_keys: (proportion * total_keys as f64).round() as u64,
+            }
+        })
+        .collect();
+    estimates.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.prefix.cmp(&b.prefix)));
+    estimates
+}
+
+/// Wilson score interval for `hits` successes out of `n` draws. Unlike the
+/// normal approximation, it stays within 0..=1 and is usable for small
+/// shares and few samples.
+#[allow(clippy::cast_precision_loss)]
+fn wilson_interval(hits: u64, n: u64, z: f64) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    let n = n as f64;
+    let p = hits as f64 / n;
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denom;
+    let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denom;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilson_interval() {
+        let (low, high) = wilson_interval(50, 100, Z_95);
+        assert!((low - 0.4038).abs() < 1e-3);
+        assert!((high - 0.5962).abs() < 1e-3);
+
+        // Never drawn: the interval still admits a small share.
+        let (low, high) = wilson_interval(0, 1000, Z_95);
+        assert!(low.abs() < f64::EPSILON);
+        assert!(high > 0.0 && high < 0.01);
+
+        assert_eq!(wilson_interval(0, 0, Z_95), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_estimate_scales_to_dbsize() {
+        let hits = HashMap::from([("user".to_string(), 750), ("session".to_string(), 250)]);
+        let estimates = estimate(hits, 1000, 40_000);
+        assert_eq!(estimates[0].prefix, "user");
+        assert_eq!(estimates[0].estimated_keys, 30_000);
+        assert!((estimates[1].proportion - 0.25).abs() < 1e-9);
+        assert!(estimates[1].low < 0.25 && estimates[1].high > 0.25);
+    }
+}
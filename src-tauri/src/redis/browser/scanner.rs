@@ -2,7 +2,9 @@
 
 use deadpool_redis::Pool;
 
+use super::key_index::KeyRecorder;
 use super::model::{KeyInfo, RedisKeyType, ScanResult, Ttl};
+use crate::utils::chunked::ChunkSender;
 use crate::utils::errors::AppError;
 
 /// Execute a single SCAN iteration and return results.
@@ -43,47 +45,150 @@ pub async fn scan_keys(
     })
 }
 
-/// Get metadata (type + TTL) for a batch of keys using a single pipeline.
+/// SCAN the whole keyspace for `pattern`, streaming keys to `sender`.
+///
+/// Replaces the cursor round trips of [`scan_keys`] when the frontend wants
+/// every match; keys leave the process as each SCAN batch arrives. Each
+/// batch is also added to the key index through `recorder`.
+pub async fn stream_keys(
+    pool: &Pool,
+    pattern: &str,
+    sender: &mut ChunkSender<String>,
+    recorder: &KeyRecorder,
+) -> Result<(), AppError> {
+    let mut conn = pool.get().await?;
+    let mut cursor: u64 = 0;
+    loop {
+        let (next, keys) = scan_match(&mut conn, cursor, pattern).await?;
+        recorder.record(&keys).await;
+        sender.extend(keys)?;
+        cursor = next;
+        if cursor == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Keys per sub-pipeline in `get_keys_info`.
+const KEYS_INFO_CHUNK: usize = 500;
+
+/// Get metadata (type + TTL) for a batch of keys using pipelines.
 ///
 /// This is much more efficient than issuing individual TYPE and TTL commands.
-pub async fn get_keys_info(pool: &Pool, keys: &[String]) -> Result<Vec<KeyInfo>, AppError> {
+/// Large batches are split into pipelines of [`KEYS_INFO_CHUNK`] keys, run
+/// `concurrency` at a time on separate pooled connections (capped by the
+/// pool size). A chunk that fails is logged and its keys are left out, so
+/// the caller gets everything that could be read; only when every chunk
+/// fails is the error returned.
+///
+/// With `memory_samples`, MEMORY USAGE (with that many SAMPLES for aggregate
+/// types, 0 for all) fills in `size_bytes`; if the server refuses it (old
+/// version, ACL), sizes are left empty rather than failing the batch.
+pub async fn get_keys_info(
+    pool: &Pool,
+    keys: &[String],
+    memory_samples: Option<u32>,
+    concurrency: usize,
+) -> Result<Vec<KeyInfo>, AppError> {
+    use futures::StreamExt;
+
     if keys.is_empty() {
         return Ok(Vec::new());
     }
 
+    let concurrency = concurrency.clamp(1, pool.status().max_size.max(1));
+    // Build the futures up front: a stream `map` closure here trips the
+    // `Send` check on the Tauri command future.
+    let chunks: Vec<_> = keys
+        .chunks(KEYS_INFO_CHUNK)
+        .map(|chunk| chunk_keys_info(pool, chunk, memory_samples))
+        .collect();
+    let results: Vec<Result<Vec<KeyInfo>, AppError>> = futures::stream::iter(chunks)
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let mut infos = Vec::with_capacity(keys.len());
+    let mut last_error = None;
+    for result in results {
+        match result {
+            Ok(chunk) => infos.extend(chunk),
+            Err(e) => {
+                tracing::warn!("Keys info chunk failed, returning partial results: {e}");
+                last_error = Some(e);
+            }
+        }
+    }
+    match last_error {
+        Some(e) if infos.is_empty() => Err(e),
+        _ => Ok(infos),
+    }
+}
+
+/// Keys info for one chunk on its own connection.
+async fn chunk_keys_info(
+    pool: &Pool,
+    keys: &[String],
+    memory_samples: Option<u32>,
+) -> Result<Vec<KeyInfo>, AppError> {
     let mut conn = pool.get().await?;
 
-    // Pipeline: TYPE + TTL for each key
+    if let Some(samples) = memory_samples {
+        match query_keys_info(&mut conn, keys, Some(samples)).await {
+            Ok(infos) => return Ok(infos),
+            Err(e) => {
+                tracing::warn!("MEMORY USAGE unavailable, returning keys info without sizes: {e}");
+            }
+        }
+    }
+    query_keys_info(&mut conn, keys, None).await
+}
+
+async fn query_keys_info(
+    conn: &mut deadpool_redis::Connection,
+    keys: &[String],
+    memory_samples: Option<u32>,
+) -> Result<Vec<KeyInfo>, AppError> {
+    // Pipeline: TYPE + TTL (+ MEMORY USAGE) for each key
     let mut pipe = redis::pipe();
     for key in keys {
         pipe.cmd("TYPE").arg(key);
         pipe.cmd("TTL").arg(key);
+        if let Some(samples) = memory_samples {
+            pipe.cmd("MEMORY")
+                .arg("USAGE")
+                .arg(key)
+                .arg("SAMPLES")
+                .arg(samples);
+        }
     }
 
     let results: Vec<redis::Value> = pipe
-        .query_async(&mut conn)
+        .query_async(conn)
         .await
         .map_err(|e| AppError::Redis(format!("Pipeline query failed: {e}")))?;
 
-    let mut infos = Vec::with_capacity(keys.len());
-    for (i, key) in keys.iter().enumerate() {
-        let type_val = results.get(i * 2);
-        let ttl_val = results.get(i * 2 + 1);
-
-        let key_type = parse_type_value(type_val);
-        let ttl = parse_ttl_value(ttl_val);
+    Ok(build_keys_info(keys, &results, memory_samples.is_some()))
+}
 
-        infos.push(KeyInfo {
+/// Assemble `KeyInfo`s from pipelined TYPE, TTL and optional MEMORY USAGE replies.
+fn build_keys_info(keys: &[String], results: &[redis::Value], with_memory: bool) -> Vec<KeyInfo> {
+    let stride = if with_memory { 3 } else { 2 };
+    keys.iter()
+        .enumerate()
+        .map(|(i, key)| KeyInfo {
             key: key.clone(),
-            key_type,
-            ttl,
-            size_bytes: None,
+            key_type: parse_type_value(results.get(i * stride)),
+            ttl: parse_ttl_value(results.get(i * stride + 1)),
+            size_bytes: if with_memory {
+                parse_size_value(results.get(i * stride + 2))
+            } else {
+                None
+            },
             encoding: None,
             length: None,
-        });
-    }
-
-    Ok(infos)
+        })
+        .collect()
 }
 
 /// Get detailed info for a single key including encoding and element count.
@@ -135,6 +240,109 @@ pub async fn delete_keys(pool: &Pool, keys: &[String]) -> Result<u64, AppError>
     Ok(count)
 }
 
+/// SCAN COUNT hint used by the pattern-wide operations below.
+const PATTERN_SCAN_BATCH: u32 = 1000;
+
+/// Count keys matching `pattern`, returning the total and up to `sample` names.
+pub async fn count_matching(
+    pool: &Pool,
+    pattern: &str,
+    sample: usize,
+) -> Result<(u64, Vec<String>), AppError> {
+    let mut conn = pool.get().await?;
+    let mut total = 0u64;
+    let mut sample_keys = Vec::new();
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, keys) = scan_match(&mut conn, cursor, pattern).await?;
+        total += keys.len() as u64;
+        let room = sample.saturating_sub(sample_keys.len());
+        sample_keys.extend(keys.into_iter().take(room));
+
+        cursor = next;
+        if cursor == 0 {
+            return Ok((total, sample_keys));
+        }
+    }
+}
+
+/// UNLINK every key matching `pattern`, one SCAN batch at a time.
+pub async fn delete_by_pattern(pool: &Pool, pattern: &str) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    let mut deleted = 0u64;
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, keys) = scan_match(&mut conn, cursor, pattern).await?;
+        if !keys.is_empty() {
+            let count: u64 = redis::cmd("UNLINK")
+                .arg(&keys)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("UNLINK failed: {e}")))?;
+            deleted += count;
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            return Ok(deleted);
+        }
+    }
+}
+
+/// Set the TTL of every key matching `pattern`, or PERSIST them when
+/// `ttl_seconds` is `None`. Returns the number of keys updated.
+pub async fn set_ttl_by_pattern(
+    pool: &Pool,
+    pattern: &str,
+    ttl_seconds: Option<i64>,
+) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    let mut updated = 0u64;
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, keys) = scan_match(&mut conn, cursor, pattern).await?;
+        if !keys.is_empty() {
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                match ttl_seconds {
+                    Some(secs) => pipe.cmd("EXPIRE").arg(key).arg(secs),
+                    None => pipe.cmd("PERSIST").arg(key),
+                };
+            }
+            let results: Vec<bool> = pipe
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("Bulk TTL update failed: {e}")))?;
+            updated += results.into_iter().filter(|changed| *changed).count() as u64;
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            return Ok(updated);
+        }
+    }
+}
+
+/// One SCAN MATCH iteration used by the pattern-wide operations.
+pub async fn scan_match(
+    conn: &mut deadpool_redis::Connection,
+    cursor: u64,
+    pattern: &str,
+) -> Result<(u64, Vec<String>), AppError> {
+    redis::cmd("SCAN")
+        .arg(cursor)
+        .arg("MATCH")
+        .arg(pattern)
+        .arg("COUNT")
+        .arg(PATTERN_SCAN_BATCH)
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("SCAN failed: {e}")))
+}
+
 /// Rename a key, failing if the new name already exists.
 pub async fn rename_key(pool: &Pool, old_name: &str, new_name: &str) -> Result<(), AppError> {
     let mut conn = pool.get().await?;
@@ -182,6 +390,14 @@ fn parse_ttl_value(value: Option<&redis::Value>) -> Ttl {
     }
 }
 
+/// Parse a MEMORY USAGE reply; nil (key gone) becomes `None`.
+fn parse_size_value(value: Option<&redis::Value>) -> Option<u64> {
+    match value {
+        Some(redis::Value::Int(n)) => u64::try_from(*n).ok(),
+        _ => None,
+    }
+}
+
 /// Extract a string from a Redis value.
 fn extract_string_value(value: Option<&redis::Value>) -> Option<String> {
     match value {
@@ -250,6 +466,29 @@ mod tests {
         assert_eq!(parse_ttl_value(Some(&value)), Ttl::Seconds { value: 120 });
     }
 
+    #[test]
+    fn test_build_keys_info_with_memory() {
+        use redis::Value;
+        let keys = vec!["a".to_string(), "gone".to_string()];
+        let results = vec![
+            Value::SimpleString("hash".into()),
+            Value::Int(-1),
+            Value::Int(312),
+            Value::SimpleString("none".into()),
+            Value::Int(-2),
+            Value::Nil,
+        ];
+        let infos = build_keys_info(&keys, &results, true);
+        assert_eq!(infos[0].key_type, RedisKeyType::Hash);
+        assert_eq!(infos[0].size_bytes, Some(312));
+        assert!(matches!(infos[1].ttl, Ttl::Missing));
+        assert_eq!(infos[1].size_bytes, None);
+
+        let infos = build_keys_info(&keys, &results[..4], false);
+        assert!(matches!(infos[1].ttl, Ttl::Missing));
+        assert_eq!(infos[1].key_type, RedisKeyType::Unknown("none".into()));
+    }
+
     #[test]
     fn test_extract_string_value_simple() {
         let value = redis::Value::SimpleString("ziplist".into());
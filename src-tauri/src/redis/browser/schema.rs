@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, HashMap};
+
+use deadpool_redis::{Connection, Pool};
+use serde_json::Value;
+
+use super::model::{FieldSchema, FieldTypeCount, SchemaReport};
+use crate::utils::errors::AppError;
+
+pub const DEFAULT_SAMPLES: u32 = 200;
+pub const MAX_SAMPLES: u32 = 10_000;
+/// SCAN COUNT hint.
+const SCAN_BATCH: u32 = 500;
+/// Most keys SCAN may return before sampling gives up on finding more
+/// hashes or documents.
+const MAX_SCANNED: u64 = 100_000;
+/// Fields read per hash, and members flattened per JSON document.
+const MAX_FIELDS_PER_KEY: usize = 1_000;
+/// Distinct field names tracked; hashes keyed by IDs would grow without end.
+const MAX_TRACKED_FIELDS: usize = 500;
+/// JSON object nesting flattened into dotted paths.
+const MAX_DEPTH: usize = 4;
+const MAX_EXAMPLES: usize = 3;
+/// Characters kept of an example value.
+const EXAMPLE_CHARS: usize = 60;
+
+/// Type of a hash field value, guessed from its text.
+fn classify(value: &str) -> &'static str {
+    if value.is_empty() {
+        "empty"
+    } else if value.parse::<i64>().is_ok() {
+        "integer"
+    } else if value.bytes().any(|b| b.is_ascii_digit()) && value.parse::<f64>().is_ok() {
+        "float"
+    } else if value == "true" || value == "false" {
+        "boolean"
+    } else if (value.starts_with('{') || value.starts_with('['))
+        && serde_json::from_str::<Value>(value).is_ok()
+    {
+        "json"
+    } else {
+        "string"
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "float",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn shorten(value: &str) -> String {
+    match value.char_indices().nth(EXAMPLE_CHARS) {
+        Some((at, _)) => format!("{}…", &value[..at]),
+        None => value.to_string(),
+    }
+}
+
+#[derive(Debug, Default)]
+struct FieldStats {
+    present: u64,
+    types: HashMap<&'static str, u64>,
+    examples: Vec<String>,
+}
+
+/// Accumulates the fields of sampled keys into a schema.
+#[derive(Debug, Default)]
+struct SchemaBuilder {
+    keys: u64,
+    fields: BTreeMap<String, FieldStats>,
+}
+
+impl SchemaBuilder {
+    /// Count one key with the given (field, type, text) entries.
+    fn add_key<'a>(&mut self, entries: impl IntoIterator<Item = (String, &'static str, &'a str)>) {
+        self.keys += 1;
+        for (name, value_type, text) in entries {
+            if !self.fields.contains_key(&name) && self.fields.len() == MAX_TRACKED_FIELDS {
+                continue;
+            }
+            let stats = self.fields.entry(name).or_default();
+            stats.present += 1;
+            *stats.types.entry(value_type).or_default() += 1;
+            let example = shorten(text);
+            if stats.examples.len() < MAX_EXAMPLES && !stats.examples.contains(&example) {
+                stats.examples.push(example);
+            }
+        }
+    }
+
+    fn add_hash(&mut self, fields: &[(String, String)]) {
+        self.add_key(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), classify(value), value.as_str())),
+        );
+    }
+
+    fn add_json(&mut self, document: &Value) {
+        let mut members = Vec::new();
+        flatten(document, "", 0, &mut members);
+        let texts: Vec<(String, &'static str, String)> = members
+            .into_iter()
+            .map(|(path, value)| {
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (path, json_type(value), text)
+            })
+            .collect();
+        self.add_key(
+            texts
+                .iter()
+                .map(|(path, value_type, text)| (path.clone(), *value_type, text.as_str())),
+        );
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish(self) -> Vec<FieldSchema> {
+        let keys = self.keys;
+        let mut fields: Vec<FieldSchema> = self
+            .fields
+            .into_iter()
+            .map(|(name, stats)| {
+                let mut types: Vec<FieldTypeCount> = stats
+                    .types
+                    .into_iter()
+                    .map(|(value_type, count)| FieldTypeCount {
+                        value_type: value_type.to_string(),
+                        count,
+                    })
+                    .collect();
+                types.sort_by(|a, b| {
+                    b.count
+                        .cmp(&a.count)
+                        .then_with(|| a.value_type.cmp(&b.value_type))
+                });
+                FieldSchema {
+                    name,
+                    present: stats.present,
+                    fill_rate: stats.present as f64 / keys.max(1) as f64,
+                    types,
+                    examples: stats.examples,
+                }
+            })
+            .collect();
+        // Stable, so equally common fields stay in name order.
+        fields.sort_by_key(|field| std::cmp::Reverse(field.present));
+        fields
+    }
+}
+
+/// Leaf members of a JSON document as dotted paths. Objects nested deeper
+/// than [`MAX_DEPTH`] and arrays are kept whole; a non-object document is a
+/// single member named `$`.
+fn flatten<'a>(value: &'a Value, path: &str, depth: usize, out: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(members) if depth < MAX_DEPTH => {
+            for (name, member) in members {
+                if out.len() == MAX_FIELDS_PER_KEY {
+                    return;
+                }
+                let child = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}.{name}")
+                };
+                flatten(member, &child, depth + 1, out);
+            }
+        }
+        _ => out.push((
+            if path.is_empty() {
+                "$".to_string()
+            } else {
+                path.to_string()
+            },
+            value,
+        )),
+    }
+}
+
+async fn read_hash(conn: &mut Connection, key: &str) -> Result<Vec<(String, String)>, AppError> {
+    let mut fields = Vec::new();
+    let mut cursor: u64 = 0;
+    loop {
+        let (next, batch): (u64, Vec<Vec<u8>>) = redis::cmd("HSCAN")
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(SCAN_BATCH)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("HSCAN failed: {e}")))?;
+        // Field, value pairs.
+        fields.extend(batch.chunks_exact(2).map(|pair| {
+            (
+                String::from_utf8_lossy(&pair[0]).into_owned(),
+                String::from_utf8_lossy(&pair[1]).into_owned(),
+            )
+        }));
+        cursor = next;
+        if cursor == 0 || fields.len() >= MAX_FIELDS_PER_KEY {
+            break;
+        }
+    }
+    fields.truncate(MAX_FIELDS_PER_KEY);
+    Ok(fields)
+}
+
+async fn read_json(conn: &mut Connection, key: &str) -> Result<Option<Value>, AppError> {
+    let raw: Option<String> = redis::cmd("JSON.GET")
+        .arg(key)
+        .arg("$")
+        .query_async(&mut *conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("JSON.GET failed: {e}")))?;
+    // `$` wraps the root in a one-element array; the key may be gone by now.
+    Ok(raw
+        .and_then(|raw| serde_json::from_str::<Vec<Value>>(&raw).ok())
+        .and_then(|mut roots| roots.pop()))
+}
+
+/// Infer the field schema of the hashes and `RedisJSON` documents matching
+/// `pattern` from up to `samples` of them.
+///
+/// Keys come from SCAN in server order, so the sample is the first keys
+/// found rather than a random draw. Each field gets its fill rate over the
+/// sampled keys, its value types and a few example values. JSON documents
+/// are flattened into dotted member paths.
+pub async fn infer_schema(
+    pool: &Pool,
+    pattern: &str,
+    samples: u32,
+) -> Result<SchemaReport, AppError> {
+    let mut conn = pool.get().await?;
+    let mut builder = SchemaBuilder::default();
+    let mut report = SchemaReport {
+        pattern: pattern.to_string(),
+        scanned_keys: 0,
+        hash_keys: 0,
+        json_keys: 0,
+        skipped_keys: 0,
+        fields: Vec::new(),
+        truncated: false,
+    };
+    let mut cursor: u64 = 0;
+
+    'scan: loop {
+        let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(SCAN_BATCH)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("SCAN failed: {e}")))?;
+        cursor = next;
+        report.scanned_keys += keys.len() as u64;
+
+        let types: Vec<String> = if keys.is_empty() {
+            Vec::new()
+        } else {
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                pipe.cmd("TYPE").arg(key);
+            }
+            pipe.query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?
+        };
+
+        for (key, key_type) in keys.iter().zip(types) {
+            if builder.keys == u64::from(samples) {
+                report.truncated = true;
+                break 'scan;
+            }
+            match key_type.as_str() {
+                "hash" => {
+                    let fields = read_hash(&mut conn, key).await?;
+                    builder.add_hash(&fields);
+                    report.hash_keys += 1;
+                }
+                "ReJSON-RL" => {
+                    if let Some(document) = read_json(&mut conn, key).await? {
+                        builder.add_json(&document);
+                        report.json_keys += 1;
+                    }
+                }
+                _ => report.skipped_keys += 1,
+            }
+        }
+
+        if cursor == 0 {
+            break;
+        }
+        if report.scanned_keys >= MAX_SCANNED {
+            report.truncated = true;
+            break;
+        }
+    }
+
+    report.fields = builder.finish();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_hash_values() {
+        assert_eq!(classify(""), "empty");
+        assert_eq!(classify("-42"), "integer");
+        assert_eq!(classify("3.14"), "float");
+        assert_eq!(classify("inf"), "string");
+        assert_eq!(classify("true"), "boolean");
+        assert_eq!(classify(r#"{"a":1}"#), "json");
+        assert_eq!(classify("{not json"), "string");
+    }
+
+    #[test]
+    fn test_builder_reports_fill_rate_and_types() {
+        let mut builder = SchemaBuilder::default();
+        let hash = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(f, v)| ((*f).to_string(), (*v).to_string()))
+                .collect()
+        };
+        builder.add_hash(&hash(&[("name", "ann"), ("age", "31")]));
+        builder.add_hash(&hash(&[("name", "bob"), ("age", "n/a")]));
+        builder.add_hash(&hash(&[("name", "ann"), ("email", "a@x.io")]));
+        let fields = builder.finish();
+
+        assert_eq!(fields[0].name, "name");
+        assert!((fields[0].fill_rate - 1.0).abs() < f64::EPSILON);
+        assert_eq!(fields[0].examples, ["ann", "bob"]);
+        assert_eq!(fields[1].name, "age");
+        assert_eq!(fields[1].present, 2);
+        assert_eq!(
+            fields[1].types,
+            [
+                FieldTypeCount {
+                    value_type: "integer".into(),
+                    count: 1
+                },
+                FieldTypeCount {
+                    value_type: "string".into(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(fields[2].name, "email");
+    }
+
+    #[test]
+    fn test_json_documents_are_flattened() {
+        let mut builder = SchemaBuilder::default();
+        builder.add_json(&json!({"id": 7, "profile": {"city": "Oslo", "tags": ["a"]}}));
+        builder.add_json(&json!({"id": 8.5, "profile": null}));
+        let fields = builder.finish();
+
+        let names: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["id", "profile", "profile.city", "profile.tags"]);
+        assert_eq!(fields[0].types.len(), 2);
+        assert_eq!(fields[1].types[0].value_type, "null");
+        assert_eq!(fields[3].types[0].value_type, "array");
+        assert!((fields[2].fill_rate - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_shorten_examples() {
+        assert_eq!(shorten("short"), "short");
+        let long = "é".repeat(100);
+        assert_eq!(shorten(&long).chars().count(), EXAMPLE_CHARS + 1);
+    }
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use super::model::{KeyInfo, NamespaceStats, Ttl, TtlDistribution, TypeCount};
+
+/// Summarize sampled key metadata for a namespace of `key_count` keys.
+///
+/// Keys reported missing (deleted since they were indexed) are left out of
+/// the sample. Memory is scaled up by the share of sampled keys that reported
+/// a size.
+pub fn summarize(prefix: &str, key_count: u64, infos: &[KeyInfo]) -> NamespaceStats {
+    let mut types: HashMap<&str, TypeCount> = HashMap::new();
+    let mut ttl = TtlDistribution::default();
+    let mut sampled = 0u64;
+    let mut sized = 0u64;
+    let mut sampled_memory_bytes = 0u64;
+
+    for info in infos {
+        match info.ttl {
+            Ttl::Missing => continue,
+            Ttl::Persistent => ttl.persistent += 1,
+            Ttl::Seconds { value } if value < 60 => ttl.under_minute += 1,
+            Ttl::Seconds { value } if value < 3600 => ttl.under_hour += 1,
+            Ttl::Seconds { value } if value < 86_400 => ttl.under_day += 1,
+            Ttl::Seconds { .. } => ttl.over_day += 1,
+        }
+        sampled += 1;
+        types
+            .entry(info.key_type.as_type_str())
+            .or_insert_with(|| TypeCount {
+                key_type: info.key_type.clone(),
+                count: 0,
+            })
+            .count += 1;
+        if let Some(size) = info.size_bytes {
+            sized += 1;
+            sampled_memory_bytes += size;
+        }
+    }
+
+    let mut types: Vec<TypeCount> = types.into_values().collect();
+    types.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.key_type.as_type_str().cmp(b.key_type.as_type_str()))
+    });
+
+    let estimated_memory_bytes = if sized == 0 {
+        0
+    } else {
+        let scaled = u128::from(sampled_memory_bytes) * u128::from(key_count) / u128::from(sized);
+        u64::try_from(scaled).unwrap_or(u64::MAX)
+    };
+
+    NamespaceStats {
+        prefix: prefix.to_string(),
+        key_count,
+        sampled,
+        types,
+        ttl,
+        sampled_memory_bytes,
+        estimated_memory_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::browser::model::RedisKeyType;
+
+    fn info(key_type: RedisKeyType, ttl: Ttl, size_bytes: Option<u64>) -> KeyInfo {
+        KeyInfo {
+            key: "k".into(),
+            key_type,
+            ttl,
+            size_bytes,
+            encoding: None,
+            length: None,
+        }
+    }
+
+    #[test]
+    fn test_summarize_extrapolates_sample() {
+        let infos = [
+            info(RedisKeyType::Hash, Ttl::Persistent, Some(100)),
+            info(RedisKeyType::Hash, Ttl::Seconds { value: 30 }, Some(300)),
+            info(RedisKeyType::String, Ttl::Seconds { value: 7200 }, None),
+            info(RedisKeyType::Hash, Ttl::Missing, Some(999)),
+        ];
+        let stats = summarize("user:", 1000, &infos);
+
+        assert_eq!(stats.sampled, 3);
+        assert_eq!(stats.types[0].key_type, RedisKeyType::Hash);
+        assert_eq!(stats.types[0].count, 2);
+        assert_eq!(stats.types[1].count, 1);
+        assert_eq!(
+            stats.ttl,
+            TtlDistribution {
+                persistent: 1,
+                under_minute: 1,
+                under_hour: 0,
+                under_day: 1,
+                over_day: 0,
+            }
+        );
+        assert_eq!(stats.sampled_memory_bytes, 400);
+        assert_eq!(stats.estimated_memory_bytes, 200_000);
+        assert_eq!(summarize("x", 5, &[]).estimated_memory_bytes, 0);
+    }
+}
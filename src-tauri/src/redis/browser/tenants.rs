@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use deadpool_redis::Pool;
+
+use super::model::{TenantReport, TenantUsage};
+use super::scanner;
+use crate::redis::connection::throttle::Throttle;
+use crate::utils::errors::AppError;
+
+/// Placeholder for the tenant ID in a namespace template.
+const ID_PLACEHOLDER: &str = "{id}";
+pub const DEFAULT_SAMPLES_PER_TENANT: u32 = 20;
+pub const MAX_SAMPLES_PER_TENANT: u32 = 1_000;
+pub const DEFAULT_TENANT_LIMIT: u32 = 100;
+pub const MAX_TENANT_LIMIT: u32 = 10_000;
+
+/// A namespace template such as `tenant:{id}:*`, split into the SCAN glob
+/// it matches and the text around the tenant ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantTemplate {
+    glob: String,
+    prefix: String,
+    /// Literal text right after `{id}`, ending the ID; empty when the ID
+    /// runs to the end of the key.
+    terminator: String,
+}
+
+impl TenantTemplate {
+    /// Parse a template with exactly one `{id}`, preceded by literal text and
+    /// followed by literal text or glob patterns.
+    pub fn parse(template: &str) -> Result<Self, AppError> {
+        let Some((prefix, rest)) = template.split_once(ID_PLACEHOLDER) else {
+            return Err(AppError::InvalidInput(format!(
+                "Template must contain {ID_PLACEHOLDER}, e.g. tenant:{ID_PLACEHOLDER}:*"
+            )));
+        };
+        if rest.contains(ID_PLACEHOLDER) {
+            return Err(AppError::InvalidInput(format!(
+                "Template must contain {ID_PLACEHOLDER} only once"
+            )));
+        }
+        if prefix.contains(['*', '?', '[', ']', '\\']) {
+            return Err(AppError::InvalidInput(format!(
+                "The part before {ID_PLACEHOLDER} must not contain glob patterns"
+            )));
+        }
+        Ok(Self {
+            glob: format!("{prefix}*{rest}"),
+            prefix: prefix.to_string(),
+            terminator: rest
+                .chars()
+                .take_while(|c| !matches!(c, '*' | '?' | '[' | '\\'))
+                .collect(),
+        })
+    }
+
+    /// The tenant ID in a key matching the template's glob.
+    pub fn tenant_of<'a>(&self, key: &'a str) -> Option<&'a str> {
+        let rest = key.strip_prefix(&self.prefix)?;
+        let id = if self.terminator.is_empty() {
+            rest
+        } else {
+            rest.split_once(&self.terminator)?.0
+        };
+        (!id.is_empty()).then_some(id)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    keys: u64,
+    sampled: u64,
+    sampled_bytes: u64,
+}
+
+/// Count every key matching `template` per tenant ID and read MEMORY USAGE
+/// of the first `samples_per_tenant` keys SCAN returns for each tenant,
+/// scaling their memory up to the tenant's key count. Returns the `limit`
+/// tenants with the most estimated memory.
+///
+/// Each SCAN batch waits for the connection's `throttle`; `on_batch` gets
+/// the number of matching keys seen so far.
+pub async fn tenant_report(
+    pool: &Pool,
+    throttle: &Throttle,
+    template: &str,
+    samples_per_tenant: u32,
+    limit: u32,
+    mut on_batch: impl FnMut(u64),
+) -> Result<TenantReport, AppError> {
+    let started = Instant::now();
+    let parsed = TenantTemplate::parse(template)?;
+    let mut conn = pool.get().await?;
+    let mut tallies: HashMap<String, Tally> = HashMap::new();
+    let mut matched = 0u64;
+    let mut cursor = 0;
+
+    loop {
+        throttle.batch(1).await;
+        let (next, keys) = scanner::scan_match(&mut conn, cursor, &parsed.glob).await?;
+
+        let mut to_sample = Vec::new();
+        for key in &keys {
+            let Some(id) = parsed.tenant_of(key) else {
+                continue;
+            };
+            matched += 1;
+            let tally = tallies.entry(id.to_string()).or_default();
+            tally.keys += 1;
+            // Counted as sampled now, so later keys in this batch see it.
+            if tally.sampled < u64::from(samples_per_tenant) {
+                tally.sampled += 1;
+                to_sample.push((id, key));
+            }
+        }
+        if !to_sample.is_empty() {
+            throttle.acquire(to_sample.len()).await;
+            let mut pipe = redis::pipe();
+            for (_, key) in &to_sample {
+                pipe.cmd("MEMORY").arg("USAGE").arg(*key);
+            }
+            // Nil for keys that expired since the scan.
+            let sizes: Vec<Option<u64>> = pipe
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("MEMORY USAGE failed: {e}")))?;
+            for ((id, _), size) in to_sample.into_iter().zip(sizes) {
+                let Some(tally) = tallies.get_mut(id) else {
+                    continue;
+                };
+                match size {
+                    Some(bytes) => tally.sampled_bytes += bytes,
+                    None => tally.sampled -= 1,
+                }
+            }
+        }
+
+        on_batch(matched);
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    let (tenants, estimated_memory_bytes, tenant_count) = rank(tallies, limit as usize);
+    Ok(TenantReport {
+        template: template.to_string(),
+        matched_keys: matched,
+        tenant_count,
+        estimated_memory_bytes,
+        truncated: (tenants.len() as u64) < tenant_count,
+        tenants,
+        elapsed_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    })
+}
+
+/// Estimate each tenant's memory and keep the `limit` largest; also
+/// returns the estimated total and the number of tenants.
+#[allow(clippy::cast_precision_loss)]
+fn rank(tallies: HashMap<String, Tally>, limit: usize) -> (Vec<TenantUsage>, u64, u64) {
+    let tenant_count = tallies.len() as u64;
+    let mut tenants: Vec<TenantUsage> = tallies
+        .into_iter()
+        .map(|(tenant_id, tally)| {
+            let estimated_memory_bytes = if tally.sampled == 0 {
+                0
+            } else {
+                let scaled = u128::from(tally.sampled_bytes) * u128::from(tally.keys)
+                    / u128::from(tally.sampled);
+                u64::try_from(scaled).unwrap_or(u64::MAX)
+            };
+            TenantUsage {
+                tenant_id,
+                key_count: tally.keys,
+                sampled: tally.sampled,
+                sampled_memory_bytes: tally.sampled_bytes,
+                estimated_memory_bytes,
+                memory_share: 0.0,
+            }
+        })
+        .collect();
+    let total = tenants
+        .iter()
+        .fold(0u64, |sum, t| sum.saturating_add(t.estimated_memory_bytes));
+    tenants.sort_by(|a, b| {
+        b.estimated_memory_bytes
+            .cmp(&a.estimated_memory_bytes)
+            .then_with(|| b.key_count.cmp(&a.key_count))
+            .then_with(|| a.tenant_id.cmp(&b.tenant_id))
+    });
+    tenants.truncate(limit);
+    if total > 0 {
+        for tenant in &mut tenants {
+            tenant.memory_share = tenant.estimated_memory_bytes as f64 / total as f64;
+        }
+    }
+    (tenants, total, tenant_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_extracts_tenant_id() {
+        let template = TenantTemplate::parse("tenant:{id}:*").unwrap();
+        assert_eq!(template.glob, "tenant:*:*");
+        assert_eq!(template.tenant_of("tenant:acme:users:1"), Some("acme"));
+        assert_eq!(template.tenant_of("tenant::users"), None);
+        assert_eq!(template.tenant_of("other:acme:x"), None);
+
+        let template = TenantTemplate::parse("quota:{id}").unwrap();
+        assert_eq!(template.tenant_of("quota:acme"), Some("acme"));
+
+        assert!(TenantTemplate::parse("tenant:*").is_err());
+        assert!(TenantTemplate::parse("{id}:{id}").is_err());
+        assert!(TenantTemplate::parse("*:{id}:x").is_err());
+    }
+
+    #[test]
+    fn test_rank_orders_by_estimated_memory() {
+        let tallies = HashMap::from([
+            (
+                "small".to_string(),
+                Tally {
+                    keys: 10,
+                    sampled: 10,
+                    sampled_bytes: 1_000,
+                },
+            ),
+            (
+                "big".to_string(),
+                Tally {
+                    keys: 1_000,
+                    sampled: 20,
+                    sampled_bytes: 2_000,
+                },
+            ),
+            (
+                "gone".to_string(),
+                Tally {
+                    keys: 3,
+                    sampled: 0,
+                    sampled_bytes: 0,
+                },
+            ),
+        ]);
+        let (tenants, total, count) = rank(tallies, 2);
+        assert_eq!(count, 3);
+        assert_eq!(total, 101_000);
+        assert_eq!(tenants.len(), 2);
+        assert_eq!(tenants[0].tenant_id, "big");
+        assert_eq!(tenants[0].estimated_memory_bytes, 100_000);
+        assert!((tenants[1].memory_share - 1_000.0 / 101_000.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,514 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt::Write;
+
+use deadpool_redis::{Connection, Pool};
+use regex::{Regex, RegexBuilder};
+
+use super::filter::MAX_REGEX_SIZE;
+use super::model::{RedisKeyType, ValueSearchHit, ValueSearchSpec};
+use crate::redis::connection::throttle::Throttle;
+use crate::redis::editor::stream_ops::parse_stream_entries;
+use crate::utils::errors::AppError;
+
+const DEFAULT_MAX_VALUE_BYTES: u64 = 1024 * 1024;
+const MAX_VALUE_BYTES: u64 = 64 * 1024 * 1024;
+const DEFAULT_MAX_ELEMENTS: u32 = 1_000;
+const MAX_ELEMENTS: u32 = 100_000;
+const DEFAULT_MAX_MATCHES: u32 = 1_000;
+const MAX_MATCHES: u32 = 10_000;
+/// SCAN COUNT hint, and the keys handled per progress event.
+const SCAN_BATCH: u32 = 500;
+/// Characters of context kept on each side of a match in a snippet.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Compiled form of a search query.
+#[derive(Debug)]
+pub enum ValueMatcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl ValueMatcher {
+    /// Byte range of the first match in `text`.
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        match self {
+            Self::Substring(needle) => text.find(needle.as_str()).map(|at| (at, at + needle.len())),
+            Self::Regex(regex) => regex.find(text).map(|m| (m.start(), m.end())),
+        }
+    }
+}
+
+/// A validated search: what to match and how much of each key to read.
+#[derive(Debug)]
+pub struct ValueSearch {
+    matcher: ValueMatcher,
+    pattern: String,
+    types: Vec<RedisKeyType>,
+    max_value_bytes: u64,
+    max_elements: u32,
+    max_matches: u64,
+}
+
+impl ValueSearch {
+    pub fn new(spec: ValueSearchSpec) -> Result<Self, AppError> {
+        if spec.query.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Search text must not be empty".into(),
+            ));
+        }
+        let matcher = if spec.regex {
+            ValueMatcher::Regex(
+                RegexBuilder::new(&spec.query)
+                    .size_limit(MAX_REGEX_SIZE)
+                    .build()
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid regex: {e}")))?,
+            )
+        } else {
+            ValueMatcher::Substring(spec.query)
+        };
+        if let Some(unsupported) = spec
+            .types
+            .iter()
+            .find(|t| matches!(t, RedisKeyType::Unknown(_)))
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Values of type {} can't be searched",
+                unsupported.as_type_str()
+            )));
+        }
+        Ok(Self {
+            matcher,
+            pattern: spec
+                .pattern
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| "*".into()),
+            types: spec.types,
+            max_value_bytes: bounded(
+                spec.max_value_bytes,
+                DEFAULT_MAX_VALUE_BYTES,
+                MAX_VALUE_BYTES,
+                "Value size cap",
+            )?,
+            max_elements: bounded(
+                spec.max_elements.map(u64::from),
+                u64::from(DEFAULT_MAX_ELEMENTS),
+                u64::from(MAX_ELEMENTS),
+                "Elements per key",
+            )?
+            .try_into()
+            .unwrap_or(MAX_ELEMENTS),
+            max_matches: bounded(
+                spec.max_matches.map(u64::from),
+                u64::from(DEFAULT_MAX_MATCHES),
+                u64::from(MAX_MATCHES),
+                "Match limit",
+            )?,
+        })
+    }
+
+    fn wants(&self, key_type: &RedisKeyType) -> bool {
+        !matches!(key_type, RedisKeyType::Unknown(_))
+            && (self.types.is_empty() || self.types.contains(key_type))
+    }
+}
+
+fn bounded(value: Option<u64>, default: u64, max: u64, what: &str) -> Result<u64, AppError> {
+    match value {
+        None => Ok(default),
+        Some(v) if (1..=max).contains(&v) => Ok(v),
+        Some(_) => Err(AppError::InvalidInput(format!(
+            "{what} must be between 1 and {max}"
+        ))),
+    }
+}
+
+/// Running figures of a search.
+#[derive(Debug, Clone, Default)]
+pub struct SearchTally {
+    pub scanned_keys: u64,
+    pub inspected_keys: u64,
+    pub total_hits: u64,
+    pub partial_values: u64,
+    pub truncated: bool,
+}
+
+impl SearchTally {
+    /// Progress message, e.g. `12 hits in 340 values (3 partly searched)`.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} hits in {} values", self.total_hits, self.inspected_keys);
+        if self.partial_values > 0 {
+            let _ = write!(summary, " ({} partly searched)", self.partial_values);
+        }
+        if self.truncated {
+            summary.push_str("; match limit reached");
+        }
+        summary
+    }
+}
+
+/// Walk the keyspace with SCAN and search the values of matching keys,
+/// handing each batch's hits to `on_batch`.
+///
+/// Per-key cost is bounded: strings are read up to the byte cap with
+/// GETRANGE and collections up to the element cap, so one huge key can't
+/// stall the search or the server. Stops at the match limit. Each SCAN
+/// batch waits for the connection's `throttle`, pausing while its circuit
+/// breaker is open.
+pub async fn search(
+    pool: &Pool,
+    throttle: &Throttle,
+    search: &ValueSearch,
+    mut on_batch: impl FnMut(&SearchTally, Vec<ValueSearchHit>),
+) -> Result<SearchTally, AppError> {
+    let mut conn = pool.get().await?;
+    let mut tally = SearchTally::default();
+    // A single type filter is applied by SCAN itself.
+    let scan_type = match search.types.as_slice() {
+        [only] => Some(only.as_type_str().to_string()),
+        _ => None,
+    };
+    let mut cursor: u64 = 0;
+
+    loop {
+        throttle.batch(1).await;
+        let mut cmd = redis::cmd("SCAN");
+        cmd.arg(cursor)
+            .arg("MATCH")
+            .arg(&search.pattern)
+            .arg("COUNT")
+            .arg(SCAN_BATCH);
+        if let Some(key_type) = &scan_type {
+            cmd.arg("TYPE").arg(key_type);
+        }
+        let (next, keys): (u64, Vec<String>) = cmd
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("SCAN failed: {e}")))?;
+        tally.scanned_keys += keys.len() as u64;
+
+        let types: Vec<String> = if keys.is_empty() {
+            Vec::new()
+        } else {
+            throttle.acquire(keys.len()).await;
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                pipe.cmd("TYPE").arg(key);
+            }
+            pipe.query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?
+        };
+
+        let mut hits = Vec::new();
+        for (key, key_type) in keys.iter().zip(types) {
+            let key_type = RedisKeyType::from_type_str(&key_type);
+            if !search.wants(&key_type) {
+                continue;
+            }
+            throttle.acquire(1).await;
+            let (elements, partial) = read_value(&mut conn, key, &key_type, search).await?;
+            tally.inspected_keys += 1;
+            tally.partial_values += u64::from(partial);
+
+            for (location, text) in elements {
+                let Some(range) = search.matcher.find(&text) else {
+                    continue;
+                };
+                if tally.total_hits == search.max_matches {
+                    tally.truncated = true;
+                    break;
+                }
+                tally.total_hits += 1;
+                hits.push(ValueSearchHit {
+                    key: key.clone(),
+                    key_type: key_type.clone(),
+                    location,
+                    snippet: snippet(&text, range),
+                });
+            }
+            if tally.truncated {
+                break;
+            }
+        }
+        on_batch(&tally, hits);
+
+        cursor = next;
+        if cursor == 0 || tally.truncated {
+            break;
+        }
+    }
+    Ok(tally)
+}
+
+/// Searchable texts of a value, each with its location.
+type Elements = Vec<(Option<String>, String)>;
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// The searchable texts of a value and whether the caps left part of the
+/// value unread.
+async fn read_value(
+    conn: &mut Connection,
+    key: &str,
+    key_type: &RedisKeyType,
+    search: &ValueSearch,
+) -> Result<(Elements, bool), AppError> {
+    match key_type {
+        RedisKeyType::String => {
+            let (len, head): (u64, Vec<u8>) = redis::pipe()
+                .cmd("STRLEN")
+                .arg(key)
+                .cmd("GETRANGE")
+                .arg(key)
+                .arg(0)
+                .arg(search.max_value_bytes - 1)
+                .query_async(&mut *conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("GETRANGE failed: {e}")))?;
+            Ok((vec![(None, lossy(&head))], len > search.max_value_bytes))
+        }
+        RedisKeyType::Hash | RedisKeyType::Set => {
+            scan_elements(conn, key, key_type, search.max_elements).await
+        }
+        RedisKeyType::Zset | RedisKeyType::List => {
+            range_elements(conn, key, key_type, search.max_elements).await
+        }
+        RedisKeyType::Stream => stream_elements(conn, key, search.max_elements).await,
+        RedisKeyType::Unknown(_) => Ok((Vec::new(), false)),
+    }
+}
+
+/// Hash fields and values, or set members, read with HSCAN/SSCAN until
+/// `max` elements. A hash field matches on its name or its value.
+async fn scan_elements(
+    conn: &mut Connection,
+    key: &str,
+    key_type: &RedisKeyType,
+    max: u32,
+) -> Result<(Elements, bool), AppError> {
+    let (command, per_element) = if *key_type == RedisKeyType::Hash {
+        ("HSCAN", 2)
+    } else {
+        ("SSCAN", 1)
+    };
+    let wanted = max as usize * per_element;
+    let mut items: Vec<Vec<u8>> = Vec::new();
+    let mut cursor: u64 = 0;
+    loop {
+        let (next, batch): (u64, Vec<Vec<u8>>) = redis::cmd(command)
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(max.min(SCAN_BATCH))
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("{command} failed: {e}")))?;
+        items.extend(batch);
+        cursor = next;
+        if cursor == 0 || items.len() >= wanted {
+            break;
+        }
+    }
+    let partial = cursor != 0 || items.len() > wanted;
+    items.truncate(wanted);
+
+    let elements = if per_element == 2 {
+        items
+            .chunks_exact(2)
+            .flat_map(|pair| {
+                let field = lossy(&pair[0]);
+                [
+                    (Some(field.clone()), field.clone()),
+                    (Some(field), lossy(&pair[1])),
+                ]
+            })
+            .collect()
+    } else {
+        items
+            .iter()
+            .map(|member| {
+                let member = lossy(member);
+                (Some(member.clone()), member)
+            })
+            .collect()
+    };
+    Ok((elements, partial))
+}
+
+/// The first `max` sorted set members (located by member) or list elements
+/// (located by index).
+async fn range_elements(
+    conn: &mut Connection,
+    key: &str,
+    key_type: &RedisKeyType,
+    max: u32,
+) -> Result<(Elements, bool), AppError> {
+    let is_list = *key_type == RedisKeyType::List;
+    let (card, range) = if is_list {
+        ("LLEN", "LRANGE")
+    } else {
+        ("ZCARD", "ZRANGE")
+    };
+    let (len, items): (u64, Vec<Vec<u8>>) = redis::pipe()
+        .cmd(card)
+        .arg(key)
+        .cmd(range)
+        .arg(key)
+        .arg(0)
+        .arg(i64::from(max) - 1)
+        .query_async(&mut *conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("{range} failed: {e}")))?;
+    let elements = (0u32..)
+        .zip(items)
+        .map(|(index, item)| {
+            let text = lossy(&item);
+            let location = if is_list {
+                index.to_string()
+            } else {
+                text.clone()
+            };
+            (Some(location), text)
+        })
+        .collect();
+    Ok((elements, len > u64::from(max)))
+}
+
+/// Field names and values of the first `max` stream entries, located by
+/// `"<id> <field>"`.
+async fn stream_elements(
+    conn: &mut Connection,
+    key: &str,
+    max: u32,
+) -> Result<(Elements, bool), AppError> {
+    let (len, raw): (u64, Vec<redis::Value>) = redis::pipe()
+        .cmd("XLEN")
+        .arg(key)
+        .cmd("XRANGE")
+        .arg(key)
+        .arg("-")
+        .arg("+")
+        .arg("COUNT")
+        .arg(max)
+        .query_async(&mut *conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("XRANGE failed: {e}")))?;
+    let elements = parse_stream_entries(&raw)
+        .into_iter()
+        .flat_map(|entry| {
+            entry.fields.into_iter().flat_map(move |(field, value)| {
+                let location = format!("{} {field}", entry.id);
+                [(Some(location.clone()), field), (Some(location), value)]
+            })
+        })
+        .collect();
+    Ok((elements, len > u64::from(max)))
+}
+
+/// `text` around the byte range of a match, with about [`SNIPPET_CONTEXT`]
+/// characters on each side and `…` where it was cut.
+fn snippet(text: &str, (start, end): (usize, usize)) -> String {
+    let from = text[..start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT - 1)
+        .map_or(0, |(at, _)| at);
+    let to = text[end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT)
+        .map_or(text.len(), |(at, _)| end + at);
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(&text[from..to]);
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(query: &str) -> ValueSearchSpec {
+        ValueSearchSpec {
+            query: query.into(),
+            regex: false,
+            pattern: None,
+            types: Vec::new(),
+            max_value_bytes: None,
+            max_elements: None,
+            max_matches: None,
+        }
+    }
+
+    #[test]
+    fn test_tally_summary() {
+        let mut tally = SearchTally {
+            total_hits: 12,
+            inspected_keys: 340,
+            ..SearchTally::default()
+        };
+        assert_eq!(tally.summary(), "12 hits in 340 values");
+        tally.partial_values = 3;
+        tally.truncated = true;
+        assert_eq!(
+            tally.summary(),
+            "12 hits in 340 values (3 partly searched); match limit reached"
+        );
+    }
+
+    #[test]
+    fn test_new_validates_spec() {
+        let search = ValueSearch::new(spec("token")).unwrap();
+        assert_eq!(search.pattern, "*");
+        assert_eq!(search.max_elements, DEFAULT_MAX_ELEMENTS);
+
+        assert!(ValueSearch::new(spec("")).is_err());
+        assert!(ValueSearch::new(ValueSearchSpec {
+            regex: true,
+            ..spec("(unclosed")
+        })
+        .is_err());
+        assert!(ValueSearch::new(ValueSearchSpec {
+            max_matches: Some(0),
+            ..spec("x")
+        })
+        .is_err());
+        assert!(ValueSearch::new(ValueSearchSpec {
+            types: vec![RedisKeyType::Unknown("ReJSON-RL".into())],
+            ..spec("x")
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_matcher_finds_byte_range() {
+        let regex = ValueSearch::new(ValueSearchSpec {
+            regex: true,
+            ..spec("(?i)user-\\d+")
+        })
+        .unwrap();
+        assert_eq!(regex.matcher.find("id=USER-42;"), Some((3, 10)));
+
+        let plain = ValueSearch::new(spec("42")).unwrap();
+        assert_eq!(plain.matcher.find("id=USER-42;"), Some((8, 10)));
+        assert_eq!(plain.matcher.find("none"), None);
+    }
+
+    #[test]
+    fn test_snippet_keeps_context_on_char_boundaries() {
+        assert_eq!(snippet("short match here", (6, 11)), "short match here");
+
+        let text = format!("{}needle{}", "é".repeat(60), "x".repeat(60));
+        let start = text.find("needle").unwrap();
+        let cut = snippet(&text, (start, start + 6));
+        assert!(cut.starts_with('…') && cut.ends_with('…'));
+        assert_eq!(cut.chars().count(), 2 * SNIPPET_CONTEXT + 6 + 2);
+    }
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::Instant;
+
+use uuid::Uuid;
+
+use super::model::BroadcastResult;
+use super::{executor, parser};
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::{command_policy, timeout, write_guard};
+use crate::utils::errors::AppError;
+
+/// Most connections one broadcast may target.
+pub const MAX_TARGETS: usize = 64;
+
+/// Commands that hold the connection open and never give a single reply.
+pub const BLOCKING_COMMANDS: &[&str] = &[
+    "SUBSCRIBE",
+    "PSUBSCRIBE",
+    "SSUBSCRIBE",
+    "MONITOR",
+    "BLPOP",
+    "BRPOP",
+    "BLMOVE",
+    "BLMPOP",
+    "BZPOPMIN",
+    "BZPOPMAX",
+    "BZMPOP",
+    "BRPOPLPUSH",
+    "WAIT",
+    "WAITAOF",
+];
+
+/// Check a command may be broadcast at all, before any connection is used.
+///
+/// Writes need `allow_writes`; dangerous commands are never broadcast, since
+/// each target would need its own confirmation.
+pub fn check_command(args: &[String], allow_writes: bool) -> Result<(), AppError> {
+    let Some(name) = args.first().map(|a| a.to_uppercase()) else {
+        return Err(AppError::InvalidInput("Empty command".into()));
+    };
+    if BLOCKING_COMMANDS.contains(&name.as_str()) {
+        return Err(AppError::InvalidInput(format!(
+            "{name} blocks and cannot be broadcast"
+        )));
+    }
+    if parser::check_dangerous(args).is_some() {
+        return Err(AppError::PermissionDenied(format!(
+            "{name} is dangerous and cannot be broadcast"
+        )));
+    }
+    if !allow_writes && write_guard::is_write_command(args) {
+        return Err(AppError::PermissionDenied(format!(
+            "{name} writes; enable writes to broadcast it"
+        )));
+    }
+    Ok(())
+}
+
+/// Distinct, well-formed target IDs in the order given.
+pub fn parse_targets(connection_ids: &[String]) -> Result<Vec<Uuid>, AppError> {
+    let mut targets = Vec::new();
+    for id in connection_ids {
+        let uuid = Uuid::parse_str(id)?;
+        if !targets.contains(&uuid) {
+            targets.push(uuid);
+        }
+    }
+    if targets.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Select at least one connection".into(),
+        ));
+    }
+    if targets.len() > MAX_TARGETS {
+        return Err(AppError::InvalidInput(format!(
+            "At most {MAX_TARGETS} connections can be targeted at once"
+        )));
+    }
+    Ok(targets)
+}
+
+/// Run `command` on one target. Failures, including the connection not
+/// being open, are reported in the result rather than returned. Commands
+/// the target's policy blocks or wants confirmed are not run there.
+pub async fn run_on(
+    manager: &ConnectionManager,
+    id: Uuid,
+    command: &str,
+    args: &[String],
+    db: Option<u8>,
+) -> BroadcastResult {
+    let started = Instant::now();
+    let profile = manager.get_profile(&id).await;
+    let connection_name = profile.as_ref().map(|p| p.name.clone()).unwrap_or_default();
+    let outcome = async {
+        let profile = profile?;
+        write_guard::check_command(profile.readonly, args)?;
+        if let Some(warning) = command_policy::check(&profile.command_policy, args)? {
+            return Err(AppError::PermissionDenied(format!(
+                "{} It cannot be broadcast.",
+                warning.message
+            )));
+        }
+        manager.get_command_catalog(&id).await?.validate(args)?;
+        let pool = manager.get_pool_for_db(&id, db).await?;
+        let limit = timeout::for_command(&profile.timeout, args);
+        let operation = args.first().map(|a| a.to_uppercase()).unwrap_or_default();
+        let work = executor::execute(&pool, command, true, false, None, None);
+        timeout::with_timeout(limit, &operation, work).await
+    }
+    .await;
+
+    match outcome {
+        Ok(response) => BroadcastResult {
+            connection_id: id.to_string(),
+            connection_name,
+            result: Some(response.result),
+            error: None,
+            duration_ms: response.duration_ms,
+        },
+        Err(e) => BroadcastResult {
+            connection_id: id.to_string(),
+            connection_name,
+            result: None,
+            error: Some(e.to_string()),
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        parser::parse_command(input)
+    }
+
+    #[test]
+    fn test_check_command() {
+        assert!(check_command(&args("CONFIG GET maxmemory"), false).is_ok());
+        assert!(check_command(&args("dbsize"), false).is_ok());
+        assert!(check_command(&args("SET k v"), false).is_err());
+        assert!(check_command(&args("SET k v"), true).is_ok());
+        assert!(check_command(&args("FLUSHALL"), true).is_err());
+        assert!(check_command(&args("SUBSCRIBE news"), false).is_err());
+        assert!(check_command(&[], false).is_err());
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        let a = Uuid::new_v4().to_string();
+        let b = Uuid::new_v4().to_string();
+        let targets = parse_targets(&[a.clone(), b, a]).unwrap();
+        assert_eq!(targets.len(), 2);
+        assert!(parse_targets(&[]).is_err());
+        assert!(parse_targets(&["nope".into()]).is_err());
+    }
+}
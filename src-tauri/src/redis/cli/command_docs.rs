@@ -0,0 +1,609 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use redis::Value;
+
+use super::model::CommandSuggestion;
+use super::suggestions;
+use crate::utils::errors::AppError;
+
+/// What the server reports about one command (or `CONTAINER|SUB` subcommand).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandSpec {
+    /// Arity as COMMAND reports it: `n` means exactly `n` tokens including
+    /// the name, `-n` at least `n`.
+    pub arity: i64,
+    pub keys: KeyPositions,
+    pub summary: String,
+    pub syntax: String,
+    pub group: String,
+}
+
+/// Where a command's key arguments sit, as token indexes counted from the
+/// command name (COMMAND's first key, last key and step).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyPositions {
+    /// 0 when the command takes no keys.
+    pub first: i64,
+    /// Negative values count from the end; `-1` is the last token.
+    pub last: i64,
+    pub step: i64,
+}
+
+impl KeyPositions {
+    /// Whether token `index` is a key. A negative `last` is open-ended here,
+    /// since completion happens before the line is finished.
+    pub fn contains(&self, index: usize) -> bool {
+        let Ok(index) = i64::try_from(index) else {
+            return false;
+        };
+        if self.first <= 0 || index < self.first || (self.last > 0 && index > self.last) {
+            return false;
+        }
+        self.step <= 0 || (index - self.first) % self.step == 0
+    }
+}
+
+/// Commands known to a connected server, from COMMAND and COMMAND DOCS.
+///
+/// Empty when the server refused both (old version, ACL, renamed COMMAND);
+/// validation is then skipped and suggestions fall back to the static table.
+#[derive(Debug, Clone, Default)]
+pub struct CommandCatalog {
+    /// Keyed by uppercase name; subcommands as `CONFIG|GET`.
+    specs: HashMap<String, CommandSpec>,
+}
+
+impl CommandCatalog {
+    /// Fetch the catalog over a fresh connection. Never fails; see the type docs.
+    pub async fn fetch<C>(conn: &mut C) -> Self
+    where
+        C: redis::aio::ConnectionLike + Send,
+    {
+        let info: Value = redis::cmd("COMMAND")
+            .query_async(conn)
+            .await
+            .unwrap_or(Value::Nil);
+        // COMMAND DOCS needs Redis 7; older servers just go without help text.
+        let docs: Value = redis::cmd("COMMAND")
+            .arg("DOCS")
+            .query_async(conn)
+            .await
+            .unwrap_or(Value::Nil);
+        Self::from_replies(&info, &docs)
+    }
+
+    fn from_replies(info: &Value, docs: &Value) -> Self {
+        let mut specs = HashMap::new();
+        for (name, arity, keys) in parse_command_info(info) {
+            specs.insert(
+                name,
+                CommandSpec {
+                    arity,
+                    keys,
+                    ..CommandSpec::default()
+                },
+            );
+        }
+        for (name, doc) in parse_command_docs(docs) {
+            let spec = specs.entry(name).or_default();
+            spec.summary = doc.summary;
+            spec.syntax = doc.syntax;
+            spec.group = doc.group;
+        }
+        Self { specs }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.specs.is_empty()
+    }
+
+    /// The spec for a command line, preferring the subcommand's.
+    fn lookup(&self, args: &[String]) -> Option<(String, &CommandSpec)> {
+        let name = args.first()?.to_uppercase();
+        if let Some(sub) = args.get(1) {
+            let full = format!("{name}|{}", sub.to_uppercase());
+            if let Some(spec) = self.specs.get(&full) {
+                return Some((full.replace('|', " "), spec));
+            }
+        }
+        self.specs.get(&name).map(|spec| (name, spec))
+    }
+
+    /// Reject unknown commands and wrong argument counts before sending.
+    pub fn validate(&self, args: &[String]) -> Result<(), AppError> {
+        let Some(first) = args.first() else {
+            return Ok(());
+        };
+        if self.is_empty() {
+            return Ok(());
+        }
+        let Some((name, spec)) = self.lookup(args) else {
+            let message = match self.closest(first) {
+                Some(guess) => format!("Unknown command '{first}'; did you mean {guess}?"),
+                None => format!("Unknown command '{first}'"),
+            };
+            return Err(AppError::InvalidInput(message));
+        };
+
+        // Arity counts every token, including the (sub)command name.
+        let given = i64::try_from(args.len()).unwrap_or(i64::MAX);
+        let ok = match spec.arity {
+            0 => true,
+            n if n > 0 => given == n,
+            n => given >= -n,
+        };
+        if ok {
+            return Ok(());
+        }
+        let message = if spec.syntax.is_empty() {
+            format!("Wrong number of arguments for '{name}'")
+        } else {
+            format!(
+                "Wrong number of arguments for '{name}'; usage: {}",
+                spec.syntax
+            )
+        };
+        Err(AppError::InvalidInput(message))
+    }
+
+    /// Whether token `index` of a partly typed line is a key argument.
+    ///
+    /// `tokens` are the tokens before `index`. Uses the server's key
+    /// positions, or for commands it didn't report, the built-in syntax
+    /// (`key`, `key [key ...]`).
+    pub fn is_key_position(&self, tokens: &[String], index: usize) -> bool {
+        if index == 0 {
+            return false;
+        }
+        if let Some((_, spec)) = self.lookup(tokens) {
+            if spec.arity != 0 {
+                return spec.keys.contains(index);
+            }
+        }
+        let Some(name) = tokens.first().map(|t| t.to_uppercase()) else {
+            return false;
+        };
+        let Some(syntax) = suggestions::get_suggestions(&name)
+            .into_iter()
+            .find(|s| s.command == name)
+            .map(|s| s.syntax)
+        else {
+            return false;
+        };
+        let words: Vec<&str> = syntax.split_whitespace().collect();
+        if words.get(index) == Some(&"key") {
+            return true;
+        }
+        // `key [key ...]`: every position from the first key on.
+        words
+            .windows(3)
+            .position(|w| w == ["key", "[key", "...]"])
+            .is_some_and(|first| index >= first)
+    }
+
+    /// Top-level command names (uppercase) with their summaries.
+    pub fn commands(&self) -> impl Iterator<Item = (&str, &CommandSpec)> {
+        self.specs
+            .iter()
+            .filter(|(name, _)| !name.contains('|'))
+            .map(|(name, spec)| (name.as_str(), spec))
+    }
+
+    /// Subcommand names (uppercase) of a container command such as CONFIG.
+    pub fn subcommands(&self, command: &str) -> Vec<(String, &CommandSpec)> {
+        let prefix = format!("{}|", command.to_uppercase());
+        self.specs
+            .iter()
+            .filter_map(|(name, spec)| Some((name.strip_prefix(&prefix)?.to_string(), spec)))
+            .collect()
+    }
+
+    /// Top-level command within two edits of `input`, if any.
+    fn closest(&self, input: &str) -> Option<&str> {
+        let input = input.to_uppercase();
+        self.specs
+            .keys()
+            .filter(|name| !name.contains('|'))
+            .map(|name| (edit_distance(&input, name), name))
+            .filter(|(d, _)| *d <= 2)
+            .min()
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Help for a command line, from the server if it documented the command
+    /// and from the static table otherwise.
+    pub fn help(&self, command: &str) -> Option<CommandSuggestion> {
+        let args: Vec<String> = command.split_whitespace().map(String::from).collect();
+        if let Some((name, spec)) = self.lookup(&args) {
+            if !spec.syntax.is_empty() {
+                return Some(suggestion(&name, spec));
+            }
+        }
+        let name = args.first()?.to_uppercase();
+        suggestions::get_suggestions(&name)
+            .into_iter()
+            .find(|s| s.command == name)
+    }
+
+    /// Static suggestions for `prefix`, followed by documented server
+    /// commands the static table lacks (module commands, newer commands).
+    pub fn suggestions(&self, prefix: &str) -> Vec<CommandSuggestion> {
+        let mut results = suggestions::get_suggestions(prefix);
+        if prefix.is_empty() {
+            return results;
+        }
+        let upper = prefix.to_uppercase();
+        let mut extra: Vec<CommandSuggestion> = self
+            .specs
+            .iter()
+            .filter(|(name, spec)| {
+                !name.contains('|')
+                    && !spec.syntax.is_empty()
+                    && name.starts_with(&upper)
+                    && !results.iter().any(|s| &s.command == *name)
+            })
+            .map(|(name, spec)| suggestion(name, spec))
+            .collect();
+        extra.sort_by(|a, b| a.command.cmp(&b.command));
+        results.extend(extra);
+        results
+    }
+}
+
+fn suggestion(name: &str, spec: &CommandSpec) -> CommandSuggestion {
+    CommandSuggestion {
+        command: name.to_string(),
+        syntax: spec.syntax.clone(),
+        summary: spec.summary.clone(),
+        group: spec.group.clone(),
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            let next = (row[j + 1] + 1).min(row[j] + 1).min(prev + cost);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+// ─── Reply parsing ─────────────────────────────────────────────
+
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(s) => Some(s.clone()),
+        Value::VerbatimString { text, .. } => Some(text.clone()),
+        Value::Okay => Some("OK".into()),
+        _ => None,
+    }
+}
+
+/// Key/value pairs of a RESP3 map or a flat RESP2 array.
+fn pairs(value: &Value) -> Vec<(&Value, &Value)> {
+    match value {
+        Value::Map(items) => items.iter().map(|(k, v)| (k, v)).collect(),
+        Value::Array(items) => items
+            .chunks(2)
+            .filter_map(|c| Some((c.first()?, c.get(1)?)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `(NAME, arity, keys)` for every command and subcommand in a COMMAND reply.
+///
+/// Each entry is `[name, arity, flags, first, last, step, ...]`; Redis 7
+/// adds subcommands, in the same shape, at index 9.
+fn parse_command_info(value: &Value) -> Vec<(String, i64, KeyPositions)> {
+    let Value::Array(entries) = value else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for entry in entries {
+        let Value::Array(fields) = entry else {
+            continue;
+        };
+        let (Some(name), Some(Value::Int(arity))) = (fields.first().and_then(text), fields.get(1))
+        else {
+            continue;
+        };
+        let int = |i: usize| match fields.get(i) {
+            Some(Value::Int(n)) => *n,
+            _ => 0,
+        };
+        let keys = KeyPositions {
+            first: int(3),
+            last: int(4),
+            step: int(5),
+        };
+        out.push((name.to_uppercase(), *arity, keys));
+        if let Some(subcommands) = fields.get(9) {
+            out.extend(parse_command_info(subcommands));
+        }
+    }
+    out
+}
+
+/// Help text parsed from COMMAND DOCS.
+struct CommandDoc {
+    summary: String,
+    syntax: String,
+    group: String,
+}
+
+/// `(NAME, doc)` for every command and subcommand in a COMMAND DOCS reply.
+fn parse_command_docs(value: &Value) -> Vec<(String, CommandDoc)> {
+    let mut out = Vec::new();
+    for (name, doc) in pairs(value) {
+        let Some(name) = text(name) else {
+            continue;
+        };
+        let mut summary = String::new();
+        let mut group = String::new();
+        let mut arguments = Vec::new();
+        let mut subcommands = None;
+        for (field, value) in pairs(doc) {
+            match text(field).as_deref() {
+                Some("summary") => summary = text(value).unwrap_or_default(),
+                Some("group") => group = text(value).unwrap_or_default(),
+                Some("arguments") => {
+                    if let Value::Array(args) = value {
+                        arguments = args.iter().map(render_argument).collect();
+                    }
+                }
+                Some("subcommands") => subcommands = Some(value),
+                _ => {}
+            }
+        }
+
+        let display = name.replace('|', " ").to_uppercase();
+        let syntax = std::iter::once(display)
+            .chain(arguments)
+            .collect::<Vec<_>>()
+            .join(" ");
+        out.push((
+            name.to_uppercase(),
+            CommandDoc {
+                summary,
+                syntax,
+                group,
+            },
+        ));
+        if let Some(subcommands) = subcommands {
+            out.extend(parse_command_docs(subcommands));
+        }
+    }
+    out
+}
+
+/// Render one COMMAND DOCS argument the way the Redis docs write syntax,
+/// e.g. `[EX seconds]`, `key [key ...]`, `<NX | XX>`.
+fn render_argument(arg: &Value) -> String {
+    let mut name = String::new();
+    let mut display = None;
+    let mut kind = String::new();
+    let mut token = None;
+    let mut flags: Vec<String> = Vec::new();
+    let mut children: Vec<&Value> = Vec::new();
+    for (field, value) in pairs(arg) {
+        match text(field).as_deref() {
+            Some("name") => name = text(value).unwrap_or_default(),
+            Some("display_text") => display = text(value),
+            Some("type") => kind = text(value).unwrap_or_default(),
+            Some("token") => token = text(value),
+            Some("flags") => {
+                if let Value::Array(items) | Value::Set(items) = value {
+                    flags = items.iter().filter_map(text).collect();
+                }
+            }
+            Some("arguments") => {
+                if let Value::Array(items) = value {
+                    children = items.iter().collect();
+                }
+            }
+            _ => {}
+        }
+    }
+    let has = |flag: &str| flags.iter().any(|f| f == flag);
+
+    let inner = match kind.as_str() {
+        "oneof" => children
+            .iter()
+            .map(|c| render_argument(c))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        "block" => children
+            .iter()
+            .map(|c| render_argument(c))
+            .collect::<Vec<_>>()
+            .join(" "),
+        "pure-token" => String::new(),
+        _ => display.unwrap_or(name),
+    };
+    let inner = if kind == "oneof" && !has("optional") {
+        format!("<{inner}>")
+    } else {
+        inner
+    };
+    let single = match (&token, inner.is_empty()) {
+        (Some(token), true) => token.clone(),
+        (Some(token), false) => format!("{token} {inner}"),
+        (None, _) => inner.clone(),
+    };
+    let rendered = if has("multiple") {
+        let repeated = if has("multiple_token") {
+            &single
+        } else {
+            &inner
+        };
+        format!("{single} [{repeated} ...]")
+    } else {
+        single
+    };
+    if has("optional") {
+        format!("[{rendered}]")
+    } else {
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> Value {
+        Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    fn arg(fields: &[(&str, Value)]) -> Value {
+        Value::Array(
+            fields
+                .iter()
+                .flat_map(|(k, v)| [bulk(k), v.clone()])
+                .collect(),
+        )
+    }
+
+    fn catalog() -> CommandCatalog {
+        let info = Value::Array(vec![
+            Value::Array(vec![bulk("get"), Value::Int(2)]),
+            Value::Array(vec![bulk("del"), Value::Int(-2)]),
+            Value::Array(vec![
+                bulk("config"),
+                Value::Int(-2),
+                Value::Nil,
+                Value::Int(0),
+                Value::Int(0),
+                Value::Int(0),
+                Value::Nil,
+                Value::Nil,
+                Value::Nil,
+                Value::Array(vec![Value::Array(vec![bulk("config|get"), Value::Int(-3)])]),
+            ]),
+            Value::Array(vec![bulk("json.get"), Value::Int(-2)]),
+        ]);
+        let docs = Value::Array(vec![
+            bulk("get"),
+            Value::Array(vec![
+                bulk("summary"),
+                bulk("Returns the string value of a key."),
+                bulk("group"),
+                bulk("string"),
+                bulk("arguments"),
+                Value::Array(vec![arg(&[("name", bulk("key")), ("type", bulk("key"))])]),
+            ]),
+            bulk("json.get"),
+            Value::Array(vec![
+                bulk("summary"),
+                bulk("Gets the value at one or more paths"),
+                bulk("group"),
+                bulk("module"),
+                bulk("arguments"),
+                Value::Array(vec![
+                    arg(&[("name", bulk("key")), ("type", bulk("key"))]),
+                    arg(&[
+                        ("name", bulk("path")),
+                        ("type", bulk("string")),
+                        (
+                            "flags",
+                            Value::Array(vec![bulk("optional"), bulk("multiple")]),
+                        ),
+                    ]),
+                ]),
+            ]),
+        ]);
+        CommandCatalog::from_replies(&info, &docs)
+    }
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_validate_arity() {
+        let catalog = catalog();
+        assert!(catalog.validate(&args("GET k")).is_ok());
+        assert!(catalog.validate(&args("DEL a b c")).is_ok());
+        assert!(catalog.validate(&args("config get maxmemory")).is_ok());
+
+        let err = catalog.validate(&args("get")).unwrap_err().to_string();
+        assert!(err.contains("usage: GET key"), "{err}");
+        assert!(catalog.validate(&args("GET a b")).is_err());
+        assert!(catalog.validate(&args("CONFIG GET")).is_err());
+    }
+
+    #[test]
+    fn test_validate_unknown_command() {
+        let err = catalog().validate(&args("GTE k")).unwrap_err().to_string();
+        assert!(err.contains("did you mean GET"), "{err}");
+        assert!(CommandCatalog::default().validate(&args("GTE k")).is_ok());
+    }
+
+    #[test]
+    fn test_suggestions_include_server_commands() {
+        let catalog = catalog();
+        let json = catalog.suggestions("JSON.G");
+        assert!(json.iter().any(|s| s.command == "JSON.GET"));
+
+        let help = catalog.help("json.get doc $").unwrap();
+        assert_eq!(help.syntax, "JSON.GET key [path [path ...]]");
+        assert_eq!(catalog.help("GET").unwrap().group, "string");
+        assert_eq!(catalog.help("HGET").unwrap().command, "HGET");
+    }
+
+    #[test]
+    fn test_render_argument() {
+        let expiration = arg(&[
+            ("name", bulk("expiration")),
+            ("type", bulk("oneof")),
+            ("flags", Value::Array(vec![bulk("optional")])),
+            (
+                "arguments",
+                Value::Array(vec![
+                    arg(&[
+                        ("name", bulk("seconds")),
+                        ("type", bulk("integer")),
+                        ("token", bulk("EX")),
+                    ]),
+                    arg(&[
+                        ("name", bulk("keepttl")),
+                        ("type", bulk("pure-token")),
+                        ("token", bulk("KEEPTTL")),
+                    ]),
+                ]),
+            ),
+        ]);
+        assert_eq!(render_argument(&expiration), "[EX seconds | KEEPTTL]");
+
+        let condition = arg(&[
+            ("name", bulk("condition")),
+            ("type", bulk("oneof")),
+            (
+                "arguments",
+                Value::Array(vec![
+                    arg(&[("type", bulk("pure-token")), ("token", bulk("NX"))]),
+                    arg(&[("type", bulk("pure-token")), ("token", bulk("XX"))]),
+                ]),
+            ),
+        ]);
+        assert_eq!(render_argument(&condition), "<NX | XX>");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("GTE", "GET"), 2);
+        assert_eq!(edit_distance("HSET", "HSET"), 0);
+        assert_eq!(edit_distance("", "DEL"), 3);
+    }
+}
@@ -0,0 +1,297 @@
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+use super::command_docs::CommandCatalog;
+use super::suggestions;
+
+/// What a completion inserts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompletionKind {
+    Command,
+    Subcommand,
+    Key,
+}
+
+/// One candidate for the token under the cursor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Completion {
+    /// Replacement text for the token, quoted if needed.
+    pub value: String,
+    pub kind: CompletionKind,
+    /// Syntax or summary shown next to the candidate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Completions for a command line.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionResult {
+    /// Character offset where the token being completed starts; the chosen
+    /// value replaces everything from there to the end of the line.
+    pub replace_from: usize,
+    pub items: Vec<Completion>,
+}
+
+/// What the token under the cursor is.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Target {
+    Command,
+    Subcommand(String),
+    Key,
+    /// Nothing we can complete (a value, a count...).
+    Other,
+}
+
+/// The token being completed and what it is.
+#[derive(Debug)]
+pub struct LineContext {
+    pub replace_from: usize,
+    /// Unquoted text typed so far.
+    pub partial: String,
+    pub target: Target,
+}
+
+/// A token with the character offset it starts at.
+struct Token {
+    text: String,
+    start: usize,
+}
+
+/// Split a line like the CLI parser does, keeping token offsets. A line
+/// ending in whitespace (or an empty line) ends with an empty token.
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    let mut quote: Option<char> = None;
+    let mut escape = false;
+    let mut len = 0;
+
+    for (i, c) in line.chars().enumerate() {
+        len = i + 1;
+        if escape {
+            current
+                .get_or_insert(Token {
+                    text: String::new(),
+                    start: i,
+                })
+                .text
+                .push(c);
+            escape = false;
+            continue;
+        }
+        match (c, quote) {
+            ('\\', Some('"')) => escape = true,
+            ('"' | '\'', Some(q)) if q == c => quote = None,
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                current.get_or_insert(Token {
+                    text: String::new(),
+                    start: i,
+                });
+            }
+            (' ' | '\t', None) => tokens.extend(current.take()),
+            _ => current
+                .get_or_insert(Token {
+                    text: String::new(),
+                    start: i,
+                })
+                .text
+                .push(c),
+        }
+    }
+    match current {
+        Some(token) => tokens.push(token),
+        None => tokens.push(Token {
+            text: String::new(),
+            start: len,
+        }),
+    }
+    tokens
+}
+
+/// Work out what the last token of `line` is.
+pub fn analyze(line: &str, catalog: &CommandCatalog) -> LineContext {
+    let mut tokens = tokenize(line);
+    let current = tokens.pop().expect("tokenize always yields a token");
+    let before: Vec<String> = tokens.into_iter().map(|t| t.text).collect();
+    let index = before.len();
+
+    let target = if index == 0 {
+        Target::Command
+    } else if index == 1 && has_subcommands(catalog, &before[0]) {
+        Target::Subcommand(before[0].to_uppercase())
+    } else if catalog.is_key_position(&before, index) {
+        Target::Key
+    } else {
+        Target::Other
+    };
+
+    LineContext {
+        replace_from: current.start,
+        partial: current.text,
+        target,
+    }
+}
+
+fn has_subcommands(catalog: &CommandCatalog, command: &str) -> bool {
+    !catalog.subcommands(command).is_empty() || !static_subcommands(command).is_empty()
+}
+
+/// Subcommands listed in the built-in syntax, e.g. `CONFIG GET|SET|...`.
+fn static_subcommands(command: &str) -> Vec<String> {
+    let name = command.to_uppercase();
+    suggestions::get_suggestions(&name)
+        .into_iter()
+        .find(|s| s.command == name)
+        .and_then(|s| {
+            let second = s.syntax.split_whitespace().nth(1)?.to_string();
+            let subs: Vec<String> = second.split('|').map(String::from).collect();
+            subs.iter()
+                .all(|sub| !sub.is_empty() && sub.chars().all(|c| c.is_ascii_uppercase()))
+                .then_some(subs)
+        })
+        .unwrap_or_default()
+}
+
+/// How well `query` matches `candidate`, lower being better; `None` if the
+/// query's characters don't appear in order.
+///
+/// Prefix matches beat everything else; other matches are ranked by how
+/// spread out the matched characters are.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<(u8, usize, usize)> {
+    let query = query.to_uppercase();
+    let candidate_upper = candidate.to_uppercase();
+    if candidate_upper.starts_with(&query) {
+        return Some((0, 0, candidate.len()));
+    }
+    let mut gaps = 0;
+    let mut last: Option<usize> = None;
+    let mut chars = candidate_upper.char_indices();
+    for q in query.chars() {
+        let (i, _) = chars.by_ref().find(|&(_, c)| c == q)?;
+        gaps += last.map_or(i, |l| i - l - 1);
+        last = Some(i);
+    }
+    Some((1, gaps, candidate.len()))
+}
+
+/// Rank `(name, detail)` candidates against `partial` and keep the best.
+fn rank(
+    partial: &str,
+    candidates: Vec<(String, Option<String>)>,
+    kind: CompletionKind,
+    limit: usize,
+) -> Vec<Completion> {
+    let mut scored: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|(name, detail)| Some((fuzzy_score(partial, &name)?, name, detail)))
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, value, detail)| Completion {
+            value,
+            kind,
+            detail,
+        })
+        .collect()
+}
+
+/// Fuzzy-matched command names, from the built-in table and the server.
+pub fn complete_command(catalog: &CommandCatalog, partial: &str, limit: usize) -> Vec<Completion> {
+    let mut candidates: Vec<(String, Option<String>)> = suggestions::all()
+        .map(|s| (s.command, Some(s.syntax)))
+        .collect();
+    candidates.extend(catalog.commands().map(|(name, spec)| {
+        let detail = (!spec.syntax.is_empty()).then(|| spec.syntax.clone());
+        (name.to_string(), detail)
+    }));
+    // Prefer the built-in entry when both exist; it is listed first.
+    let mut seen = std::collections::HashSet::new();
+    candidates.retain(|(name, _)| seen.insert(name.clone()));
+    rank(partial, candidates, CompletionKind::Command, limit)
+}
+
+/// Fuzzy-matched subcommands of a container command.
+pub fn complete_subcommand(
+    catalog: &CommandCatalog,
+    command: &str,
+    partial: &str,
+    limit: usize,
+) -> Vec<Completion> {
+    let mut candidates: Vec<(String, Option<String>)> = catalog
+        .subcommands(command)
+        .into_iter()
+        .map(|(name, spec)| {
+            let detail = (!spec.syntax.is_empty()).then(|| spec.syntax.clone());
+            (name, detail)
+        })
+        .collect();
+    if candidates.is_empty() {
+        candidates = static_subcommands(command)
+            .into_iter()
+            .map(|name| (name, None))
+            .collect();
+    }
+    rank(partial, candidates, CompletionKind::Subcommand, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_offsets() {
+        let tokens = tokenize(r#"SET "a b" c"#);
+        let parts: Vec<_> = tokens.iter().map(|t| (t.text.as_str(), t.start)).collect();
+        assert_eq!(parts, [("SET", 0), ("a b", 4), ("c", 10)]);
+
+        let tokens = tokenize("GET ");
+        assert_eq!(tokens.len(), 2);
+        assert_eq!((tokens[1].text.as_str(), tokens[1].start), ("", 4));
+        assert_eq!(tokenize("").len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_targets() {
+        let catalog = CommandCatalog::default();
+        assert_eq!(analyze("HG", &catalog).target, Target::Command);
+        assert_eq!(
+            analyze("config g", &catalog).target,
+            Target::Subcommand("CONFIG".into())
+        );
+
+        let ctx = analyze("GET us", &catalog);
+        assert_eq!(ctx.target, Target::Key);
+        assert_eq!((ctx.replace_from, ctx.partial.as_str()), (4, "us"));
+
+        assert_eq!(analyze("DEL a b ", &catalog).target, Target::Key);
+        assert_eq!(analyze("SET k ", &catalog).target, Target::Other);
+        assert_eq!(analyze("XINFO STREAM s", &catalog).target, Target::Key);
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranks_prefix_first() {
+        assert_eq!(fuzzy_score("hg", "HGET"), Some((0, 0, 4)));
+        assert!(fuzzy_score("hga", "HGETALL").is_some());
+        assert!(fuzzy_score("zz", "HGET").is_none());
+        assert!(fuzzy_score("hga", "HGETALL") < fuzzy_score("hga", "HGETRANDOMALL"));
+    }
+
+    #[test]
+    fn test_complete_command_and_subcommand() {
+        let catalog = CommandCatalog::default();
+        let items = complete_command(&catalog, "hgal", 5);
+        assert_eq!(items[0].value, "HGETALL");
+
+        let subs = complete_subcommand(&catalog, "config", "se", 5);
+        assert_eq!(subs[0].value, "SET");
+        assert!(subs.iter().all(|s| s.kind == CompletionKind::Subcommand));
+    }
+}
@@ -4,16 +4,42 @@ use std::time::Instant;
 
 use deadpool_redis::Pool;
 
-use super::model::{CommandResult, DangerousWarning, ExecuteResponse};
-use super::parser;
+use super::model::{CommandResult, DangerousWarning, ExecuteResponse, ResultChunk, StreamedReply};
+use super::resp::RespView;
+use super::{parser, redirect};
 use crate::utils::errors::AppError;
 
+/// Where large array replies go instead of the response.
+pub struct ResultStream {
+    pub app_handle: tauri::AppHandle,
+    pub request_id: String,
+    /// Elements per `cli:result-chunk` event; longer replies are streamed.
+    pub chunk_size: usize,
+}
+
 /// Execute a raw Redis command string.
 ///
 /// Parses the input into arguments, checks for dangerous commands (unless
 /// `force` is true), then executes via `redis::cmd()` and converts the
-/// response to a `CommandResult`.
-pub async fn execute(pool: &Pool, input: &str, force: bool) -> Result<ExecuteResponse, AppError> {
+/// response to a `CommandResult`. With `raw`, the response also carries the
+/// reply's RESP representation.
+///
+/// With a `stream`, an array, set or map reply longer than its chunk size is
+/// converted and emitted chunk by chunk instead of returned, and the
+/// response only records how much was sent. Streamed replies have no raw view.
+///
+/// A MOVED or ASK reply from a cluster node is returned as an error result
+/// annotated with the redirect. With `follow` (the connection URL), the
+/// command is instead re-sent once to the node named, whose reply is
+/// returned.
+pub async fn execute(
+    pool: &Pool,
+    input: &str,
+    force: bool,
+    raw: bool,
+    stream: Option<&ResultStream>,
+    follow: Option<&str>,
+) -> Result<ExecuteResponse, AppError> {
     let args = parser::parse_command(input);
 
     if args.is_empty() {
@@ -30,6 +56,10 @@ pub async fn execute(pool: &Pool, input: &str, force: bool) -> Result<ExecuteRes
                 )),
                 duration_ms: 0.0,
                 command: input.to_string(),
+                confirmation: None,
+                raw: None,
+                streamed: None,
+                redirect: None,
             });
         }
     }
@@ -43,20 +73,118 @@ pub async fn execute(pool: &Pool, input: &str, force: bool) -> Result<ExecuteRes
     }
 
     let start = Instant::now();
-    let value: redis::Value = cmd.query_async(&mut conn).await?;
+    let (value, redirect) = match cmd.query_async::<redis::Value>(&mut conn).await {
+        Ok(value) => (value, None),
+        Err(e) => {
+            let Some(mut redirect) = redirect::from_error(&e) else {
+                return Err(e.into());
+            };
+            let Some(url) = follow else {
+                return Ok(ExecuteResponse {
+                    result: CommandResult::Error(e.to_string()),
+                    duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+                    command: input.to_string(),
+                    confirmation: None,
+                    raw: None,
+                    streamed: None,
+                    redirect: Some(redirect),
+                });
+            };
+            let value = redirect::follow(url, &redirect, &cmd).await?;
+            redirect.followed = true;
+            (value, Some(redirect))
+        }
+    };
     let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
 
+    if let Some(stream) = stream {
+        if let Some(items) = large_collection(&value, stream.chunk_size) {
+            let streamed = emit_chunks(stream, items).await?;
+            return Ok(ExecuteResponse {
+                result: CommandResult::Array(Vec::new()),
+                duration_ms,
+                command: input.to_string(),
+                confirmation: None,
+                raw: None,
+                streamed: Some(streamed),
+                redirect,
+            });
+        }
+    }
+
+    let raw = raw.then(|| RespView::new(&value));
     let result = value_to_result(value);
 
     Ok(ExecuteResponse {
         result,
         duration_ms,
         command: input.to_string(),
+        confirmation: None,
+        raw,
+        streamed: None,
+        redirect,
     })
 }
 
+/// The elements of an array, set or map reply (maps flattened to key,
+/// value, ...), if there are more than `chunk_size` of them.
+fn large_collection(value: &redis::Value, chunk_size: usize) -> Option<Vec<&redis::Value>> {
+    let items: Vec<&redis::Value> = match value {
+        redis::Value::Array(items) | redis::Value::Set(items) => {
+            if items.len() <= chunk_size {
+                return None;
+            }
+            items.iter().collect()
+        }
+        redis::Value::Map(pairs) => {
+            if pairs.len() * 2 <= chunk_size {
+                return None;
+            }
+            pairs.iter().flat_map(|(k, v)| [k, v]).collect()
+        }
+        _ => return None,
+    };
+    Some(items)
+}
+
+/// Convert and emit `items` as `cli:result-chunk` events.
+///
+/// Yields between chunks so a cancelled request stops promptly.
+async fn emit_chunks(
+    stream: &ResultStream,
+    items: Vec<&redis::Value>,
+) -> Result<StreamedReply, AppError> {
+    use tauri::Emitter;
+
+    let total = items.len() as u64;
+    let mut chunks = 0;
+    for (seq, chunk) in items.chunks(stream.chunk_size).enumerate() {
+        let seq = seq as u64;
+        let payload = ResultChunk {
+            request_id: stream.request_id.clone(),
+            seq,
+            offset: seq * stream.chunk_size as u64,
+            total,
+            items: chunk
+                .iter()
+                .map(|v| value_to_result((*v).clone()))
+                .collect(),
+        };
+        stream
+            .app_handle
+            .emit("cli:result-chunk", &payload)
+            .map_err(|e| AppError::Internal(format!("Failed to emit result chunk: {e}")))?;
+        chunks += 1;
+        tokio::task::yield_now().await;
+    }
+    Ok(StreamedReply { total, chunks })
+}
+
 /// Convert a `redis::Value` into our serializable `CommandResult`.
-fn value_to_result(value: redis::Value) -> CommandResult {
+///
+/// RESP3 types keep their own variants instead of being flattened to arrays
+/// and strings. Attributes are metadata about the reply and are dropped.
+pub fn value_to_result(value: redis::Value) -> CommandResult {
     match value {
         redis::Value::Nil => CommandResult::Nil,
         redis::Value::Int(i) => CommandResult::Integer(i),
@@ -75,23 +203,26 @@ fn value_to_result(value: redis::Value) -> CommandResult {
             };
             CommandResult::Error(msg)
         }
-        redis::Value::Double(f) => CommandResult::BulkString(f.to_string()),
-        redis::Value::Boolean(b) => CommandResult::Integer(i64::from(b)),
-        redis::Value::Map(pairs) => {
-            let items: Vec<CommandResult> = pairs
+        redis::Value::Double(f) => CommandResult::Double(f.to_string()),
+        redis::Value::Boolean(b) => CommandResult::Boolean(b),
+        redis::Value::Map(pairs) => CommandResult::Map(
+            pairs
                 .into_iter()
-                .flat_map(|(k, v)| vec![value_to_result(k), value_to_result(v)])
-                .collect();
-            CommandResult::Array(items)
-        }
+                .map(|(k, v)| (value_to_result(k), value_to_result(v)))
+                .collect(),
+        ),
         redis::Value::Set(items) => {
-            CommandResult::Array(items.into_iter().map(value_to_result).collect())
-        }
-        redis::Value::VerbatimString { text, .. } => CommandResult::BulkString(text),
-        redis::Value::BigNumber(n) => CommandResult::BulkString(n.to_string()),
-        redis::Value::Push { data, .. } => {
-            CommandResult::Array(data.into_iter().map(value_to_result).collect())
+            CommandResult::Set(items.into_iter().map(value_to_result).collect())
         }
+        redis::Value::VerbatimString { format, text } => CommandResult::Verbatim {
+            format: format.to_string(),
+            text,
+        },
+        redis::Value::BigNumber(n) => CommandResult::BigNumber(n.to_string()),
+        redis::Value::Push { kind, data } => CommandResult::Push {
+            kind: kind.to_string(),
+            data: data.into_iter().map(value_to_result).collect(),
+        },
         redis::Value::Attribute { data, .. } => value_to_result(*data),
     }
 }
@@ -176,4 +307,44 @@ mod tests {
         let warning = check_dangerous_command("GET mykey");
         assert!(warning.is_none());
     }
+
+    #[test]
+    fn test_large_collection() {
+        let array = redis::Value::Array(vec![redis::Value::Int(1); 3]);
+        assert!(large_collection(&array, 3).is_none());
+        assert_eq!(large_collection(&array, 2).unwrap().len(), 3);
+
+        let map = redis::Value::Map(vec![(redis::Value::Int(1), redis::Value::Nil); 2]);
+        assert_eq!(large_collection(&map, 3).unwrap().len(), 4);
+        assert!(large_collection(&redis::Value::Int(1), 0).is_none());
+    }
+
+    #[test]
+    fn test_value_to_result_keeps_resp3_types() {
+        let map = redis::Value::Map(vec![(
+            redis::Value::SimpleString("pi".into()),
+            redis::Value::Double(3.5),
+        )]);
+        let CommandResult::Map(pairs) = value_to_result(map) else {
+            panic!("Expected Map");
+        };
+        assert!(matches!(&pairs[0].0, CommandResult::Ok(k) if k == "pi"));
+        assert!(matches!(&pairs[0].1, CommandResult::Double(d) if d == "3.5"));
+
+        let set = value_to_result(redis::Value::Set(vec![redis::Value::Boolean(true)]));
+        assert!(
+            matches!(&set, CommandResult::Set(items) if matches!(items[0], CommandResult::Boolean(true)))
+        );
+
+        let inf = value_to_result(redis::Value::Double(f64::INFINITY));
+        assert!(matches!(inf, CommandResult::Double(d) if d == "inf"));
+
+        let push = value_to_result(redis::Value::Push {
+            kind: redis::PushKind::Invalidate,
+            data: vec![redis::Value::Array(vec![redis::Value::BulkString(
+                b"k".to_vec(),
+            )])],
+        });
+        assert!(matches!(push, CommandResult::Push { kind, .. } if kind == "invalidate"));
+    }
 }
@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::model::HistoryEntry;
+use crate::utils::errors::AppError;
+
+/// Entries kept per connection; the oldest are dropped first.
+const MAX_ENTRIES: usize = 500;
+/// Commands longer than this are kept for the session but not written to disk,
+/// as are commands carrying credentials.
+const MAX_PERSISTED_COMMAND_CHARS: usize = 4096;
+
+/// On-disk form of one connection's history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryFile {
+    /// Oldest first.
+    #[serde(default)]
+    entries: Vec<HistoryEntry>,
+    #[serde(default)]
+    pinned: Vec<String>,
+}
+
+impl HistoryFile {
+    /// Append `entry`, dropping an earlier run of the same command.
+    fn push(&mut self, entry: HistoryEntry) {
+        let command = entry.command.trim();
+        self.entries.retain(|e| e.command.trim() != command);
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+
+    /// Entries containing every whitespace-separated term (case-insensitive), newest first.
+    fn search(&self, query: &str, limit: usize) -> Vec<HistoryEntry> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| {
+                let command = e.command.to_lowercase();
+                terms.iter().all(|t| command.contains(t.as_str()))
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn set_pinned(&mut self, command: &str, pinned: bool) {
+        let command = command.trim();
+        self.pinned.retain(|c| c != command);
+        if pinned {
+            self.pinned.push(command.to_string());
+        }
+    }
+}
+
+/// Per-connection command history and pinned commands, persisted as
+/// `<connection id>.json` under the history directory.
+///
+/// Files are read on first use and rewritten after every change.
+#[derive(Clone)]
+pub struct CliHistory {
+    dir: PathBuf,
+    histories: Arc<Mutex<HashMap<Uuid, HistoryFile>>>,
+}
+
+impl CliHistory {
+    /// Create a history that stores its files in `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            histories: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Record a command run. Persisting failures are logged, not returned.
+    pub async fn push(&self, id: &Uuid, entry: HistoryEntry) {
+        let mut map = self.histories.lock().await;
+        let file = self.loaded(&mut map, id).await;
+        file.push(entry);
+        self.persist(id, file).await;
+    }
+
+    /// The most recent `limit` entries, oldest first.
+    pub async fn get(&self, id: &Uuid, limit: usize) -> Vec<HistoryEntry> {
+        let mut map = self.histories.lock().await;
+        let entries = &self.loaded(&mut map, id).await.entries;
+        let start = entries.len().saturating_sub(limit);
+        entries[start..].to_vec()
+    }
+
+    /// Entries whose command contains every term in `query`, newest first.
+    pub async fn search(&self, id: &Uuid, query: &str, limit: usize) -> Vec<HistoryEntry> {
+        let mut map = self.histories.lock().await;
+        self.loaded(&mut map, id).await.search(query, limit)
+    }
+
+    /// Pinned commands, in the order they were pinned.
+    pub async fn pinned(&self, id: &Uuid) -> Vec<String> {
+        let mut map = self.histories.lock().await;
+        self.loaded(&mut map, id).await.pinned.clone()
+    }
+
+    /// Pin or unpin a command; returns the updated pinned list.
+    pub async fn set_pinned(
+        &self,
+        id: &Uuid,
+        command: &str,
+        pinned: bool,
+    ) -> Result<Vec<String>, AppError> {
+        if command.trim().is_empty() {
+            return Err(AppError::InvalidInput("Command must not be empty".into()));
+        }
+        let mut map = self.histories.lock().await;
+        let file = self.loaded(&mut map, id).await;
+        file.set_pinned(command, pinned);
+        self.persist(id, file).await;
+        Ok(file.pinned.clone())
+    }
+
+    fn path(&self, id: &Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// The cached history for `id`, reading it from disk on first use.
+    async fn loaded<'a>(
+        &self,
+        map: &'a mut HashMap<Uuid, HistoryFile>,
+        id: &Uuid,
+    ) -> &'a mut HistoryFile {
+        if !map.contains_key(id) {
+            let file = self.read(id).await.unwrap_or_else(|e| {
+                tracing::warn!(connection_id = %id, "Failed to read CLI history: {e}");
+                HistoryFile::default()
+            });
+            map.insert(*id, file);
+        }
+        map.get_mut(id).expect("inserted above")
+    }
+
+    async fn read(&self, id: &Uuid) -> Result<HistoryFile, AppError> {
+        let path = self.path(id);
+        if !path.exists() {
+            return Ok(HistoryFile::default());
+        }
+        let data = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read CLI history: {e}")))?;
+        serde_json::from_str(&data)
+            .map_err(|e| AppError::Internal(format!("Failed to parse CLI history: {e}")))
+    }
+
+    async fn persist(&self, id: &Uuid, file: &HistoryFile) {
+        if let Err(e) = self.write(id, file).await {
+            tracing::warn!(connection_id = %id, "Failed to write CLI history: {e}");
+        }
+    }
+
+    async fn write(&self, id: &Uuid, file: &HistoryFile) -> Result<(), AppError> {
+        let on_disk = HistoryFile {
+            entries: file
+                .entries
+                .iter()
+                .filter(|e| {
+                    e.command.chars().count() <= MAX_PERSISTED_COMMAND_CHARS
+                        && !carries_credentials(&e.command)
+                })
+                .cloned()
+                .collect(),
+            pinned: file.pinned.clone(),
+        };
+        let data = serde_json::to_string(&on_disk)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize CLI history: {e}")))?;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create history dir: {e}")))?;
+        tokio::fs::write(self.path(id), data)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write CLI history: {e}")))
+    }
+}
+
+/// Whether a command would put a password on disk (AUTH, HELLO .. AUTH,
+/// ACL SETUSER, CONFIG SET of an auth parameter).
+fn carries_credentials(command: &str) -> bool {
+    let words: Vec<String> = command
+        .split_whitespace()
+        .take(3)
+        .map(str::to_lowercase)
+        .collect();
+    match words.first().map(String::as_str) {
+        Some("auth") => true,
+        Some("hello") => command.to_lowercase().contains(" auth "),
+        Some("acl") => words.get(1).is_some_and(|w| w == "setuser"),
+        Some("config") => {
+            words.get(1).is_some_and(|w| w == "set")
+                && words
+                    .get(2)
+                    .is_some_and(|p| p == "requirepass" || p == "masterauth")
+        }
+        _ => false,
+    }
+}
+
+/// `args` (command name first) with the secrets of a command that carries
+/// credentials replaced, keeping the words that say what it changed (the
+/// ACL user, the CONFIG parameter), e.g. for the audit log.
+pub fn redact_credentials(args: &[String]) -> Vec<String> {
+    if !carries_credentials(&args.join(" ")) {
+        return args.to_vec();
+    }
+    let keep = match args[0].to_lowercase().as_str() {
+        "acl" | "config" => 3,
+        _ => 1,
+    };
+    let mut redacted: Vec<String> = args.iter().take(keep).cloned().collect();
+    if args.len() > keep {
+        redacted.push("[redacted]".into());
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(command: &str, timestamp_ms: i64) -> HistoryEntry {
+        HistoryEntry {
+            command: command.into(),
+            timestamp_ms,
+            success: true,
+            duration_ms: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_push_dedupes_and_bounds() {
+        let mut file = HistoryFile::default();
+        file.push(entry("GET a", 1));
+        file.push(entry("GET b", 2));
+        file.push(entry(" GET a ", 3));
+        let commands: Vec<_> = file.entries.iter().map(|e| e.command.trim()).collect();
+        assert_eq!(commands, ["GET b", "GET a"]);
+
+        for i in 0..MAX_ENTRIES + 5 {
+            file.push(entry(&format!("SET k{i} v"), 10));
+        }
+        assert_eq!(file.entries.len(), MAX_ENTRIES);
+        assert_eq!(file.entries[0].command, "SET k5 v");
+    }
+
+    #[test]
+    fn test_search_matches_all_terms_newest_first() {
+        let mut file = HistoryFile::default();
+        file.push(entry("HGET user:1 name", 1));
+        file.push(entry("GET session:9", 2));
+        file.push(entry("hset USER:2 name bob", 3));
+
+        let found = file.search("user name", 10);
+        let commands: Vec<_> = found.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, ["hset USER:2 name bob", "HGET user:1 name"]);
+        assert_eq!(file.search("", 1).len(), 1);
+    }
+
+    #[test]
+    fn test_carries_credentials() {
+        assert!(carries_credentials("AUTH secret"));
+        assert!(carries_credentials("hello 3 AUTH default secret"));
+        assert!(carries_credentials("ACL SETUSER bob on >pw"));
+        assert!(carries_credentials("config set requirepass pw"));
+        assert!(!carries_credentials("CONFIG SET maxmemory 1gb"));
+        assert!(!carries_credentials("GET auth"));
+    }
+
+    #[test]
+    fn test_redact_credentials() {
+        let args =
+            |input: &str| -> Vec<String> { input.split_whitespace().map(String::from).collect() };
+        assert_eq!(
+            redact_credentials(&args("CONFIG SET requirepass pw")),
+            args("CONFIG SET requirepass [redacted]")
+        );
+        assert_eq!(
+            redact_credentials(&args("ACL SETUSER bob on >pw")),
+            args("ACL SETUSER bob [redacted]")
+        );
+        assert_eq!(
+            redact_credentials(&args("AUTH u pw")),
+            args("AUTH [redacted]")
+        );
+        assert_eq!(redact_credentials(&args("SET k v")), args("SET k v"));
+    }
+
+    #[tokio::test]
+    async fn test_history_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("redis-lens-history-{}", Uuid::new_v4()));
+        let id = Uuid::new_v4();
+
+        let history = CliHistory::new(dir.clone());
+        history.push(&id, entry("PING", 1)).await;
+        history
+            .push(&id, entry(&"x".repeat(MAX_PERSISTED_COMMAND_CHARS + 1), 2))
+            .await;
+        history.set_pinned(&id, "INFO memory", true).await.unwrap();
+        assert_eq!(history.get(&id, 10).await.len(), 2);
+
+        let reopened = CliHistory::new(dir.clone());
+        let entries = reopened.get(&id, 10).await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, "PING");
+        assert_eq!(reopened.pinned(&id).await, ["INFO memory"]);
+
+        tokio::fs::remove_dir_all(dir).await.unwrap();
+    }
+}
@@ -1,6 +1,15 @@
 // SPDX-License-Identifier: MIT
 
+pub mod broadcast;
+pub mod command_docs;
+pub mod completion;
 pub mod executor;
+pub mod history;
 pub mod model;
 pub mod parser;
+pub mod permissions;
+pub mod redirect;
+pub mod requests;
+pub mod resp;
+pub mod snippets;
 pub mod suggestions;
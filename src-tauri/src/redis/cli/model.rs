@@ -1,8 +1,14 @@
 // SPDX-License-Identifier: MIT
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use super::resp::RespView;
+use crate::redis::confirm::model::PendingConfirmation;
 
 /// Recursive result type mirroring Redis RESP responses.
+///
+/// The variants after `Nil` only occur on RESP3 connections; RESP2 servers
+/// send maps and sets as arrays and doubles as bulk strings.
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", content = "data", rename_all = "camelCase")]
 pub enum CommandResult {
@@ -12,6 +18,23 @@ pub enum CommandResult {
     Array(Vec<CommandResult>),
     Error(String),
     Nil,
+    /// Field/value pairs in server order.
+    Map(Vec<(CommandResult, CommandResult)>),
+    Set(Vec<CommandResult>),
+    /// Kept as text so `inf`, `-inf` and `nan` survive JSON.
+    Double(String),
+    Boolean(bool),
+    BigNumber(String),
+    /// Text with its format hint (`txt`, `mkd`), as returned by INFO or LATENCY DOCTOR.
+    Verbatim {
+        format: String,
+        text: String,
+    },
+    /// An out-of-band message, such as an invalidation or Pub/Sub message.
+    Push {
+        kind: String,
+        data: Vec<CommandResult>,
+    },
 }
 
 /// Full response from command execution including timing.
@@ -21,6 +44,87 @@ pub struct ExecuteResponse {
     pub result: CommandResult,
     pub duration_ms: f64,
     pub command: String,
+    /// Set when a dangerous command was held back pending confirmation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation: Option<PendingConfirmation>,
+    /// Protocol-level view of the reply, when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<RespView>,
+    /// Set when a large array reply was sent as `cli:result-chunk` events;
+    /// `result` is then an empty array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streamed: Option<StreamedReply>,
+    /// Set when a cluster node answered with MOVED or ASK.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<Redirect>,
+}
+
+/// A cluster redirect met by a CLI command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Redirect {
+    /// `MOVED` or `ASK`.
+    pub kind: String,
+    pub slot: u16,
+    /// `host:port` of the node the slot lives on.
+    pub node: String,
+    /// Whether the command was re-sent there; the reply then came from `node`.
+    pub followed: bool,
+}
+
+/// One connection's outcome of `cli_execute_broadcast`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastResult {
+    pub connection_id: String,
+    /// Profile name; empty when the connection is not open.
+    pub connection_name: String,
+    /// The reply, unless the command could not run here.
+    pub result: Option<CommandResult>,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+}
+
+/// Outcome of an ACL DRYRUN pre-flight for a command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCheck {
+    /// The connection's ACL user, when the server would say.
+    pub user: Option<String>,
+    /// `None` when the server couldn't check (before Redis 7, or no ACL access).
+    pub allowed: Option<bool>,
+    /// Why the command would be rejected, or why it couldn't be checked.
+    pub reason: Option<String>,
+}
+
+/// Options for a cancellable `cli_execute` call whose large replies are streamed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamOptions {
+    /// Caller-chosen ID, used to match chunk events and to cancel.
+    pub request_id: String,
+    /// Elements per chunk; array replies longer than this are streamed.
+    pub chunk_size: Option<u32>,
+}
+
+/// Summary of a reply that was streamed instead of returned.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamedReply {
+    pub total: u64,
+    pub chunks: u64,
+}
+
+/// Payload of the `cli:result-chunk` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultChunk {
+    pub request_id: String,
+    pub seq: u64,
+    /// Index of the first item in the full reply.
+    pub offset: u64,
+    pub total: u64,
+    pub items: Vec<CommandResult>,
 }
 
 /// Warning returned when a dangerous command is detected (force=false).
@@ -50,7 +154,7 @@ pub struct CommandSuggestion {
 }
 
 /// A single entry in command history.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HistoryEntry {
     pub command: String,
@@ -58,3 +162,20 @@ pub struct HistoryEntry {
     pub success: bool,
     pub duration_ms: f64,
 }
+
+/// A saved, parameterized command template such as `DEL session:{id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    pub id: uuid::Uuid,
+    pub name: String,
+    /// Command text; `{name}` marks a parameter, `{{` and `}}` are literal braces.
+    pub template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Connection the snippet belongs to; `None` makes it available everywhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<uuid::Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
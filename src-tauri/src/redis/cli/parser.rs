@@ -71,6 +71,29 @@ pub fn parse_command(input: &str) -> Vec<String> {
     args
 }
 
+/// Quote a value so [`parse_command`] reads it back as one argument.
+///
+/// Plain tokens are returned unchanged.
+pub fn quote_arg(value: &str) -> String {
+    let plain = !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\'));
+    if plain {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// Dangerous commands and their warning levels/messages.
 static DANGEROUS_COMMANDS: &[(&str, DangerLevel, &str)] = &[
     (
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::model::PermissionCheck;
+use crate::utils::errors::AppError;
+
+/// Ask the server whether the connection's ACL user may run `args`, without
+/// running it.
+///
+/// Uses ACL WHOAMI and ACL DRYRUN (Redis 7). When the server can't answer,
+/// because it is older or the user may not run ACL itself, `allowed` is
+/// `None` and `reason` says why; the command may still be allowed.
+pub async fn check_permission(pool: &Pool, args: &[String]) -> Result<PermissionCheck, AppError> {
+    if args.is_empty() {
+        return Err(AppError::InvalidInput("Empty command".into()));
+    }
+    let mut conn = pool.get().await?;
+
+    let user: String = match redis::cmd("ACL").arg("WHOAMI").query_async(&mut conn).await {
+        Ok(user) => user,
+        Err(e) => return Ok(PermissionCheck::unknown(None, &e)),
+    };
+
+    let mut cmd = redis::cmd("ACL");
+    cmd.arg("DRYRUN").arg(&user);
+    for arg in args {
+        cmd.arg(arg.as_str());
+    }
+    let reply = cmd.query_async::<redis::Value>(&mut conn).await;
+    Ok(interpret_dryrun(user, reply))
+}
+
+/// DRYRUN answers OK, or a bulk string explaining the denial.
+fn interpret_dryrun(
+    user: String,
+    reply: Result<redis::Value, redis::RedisError>,
+) -> PermissionCheck {
+    match reply {
+        Ok(redis::Value::Okay) => PermissionCheck {
+            user: Some(user),
+            allowed: Some(true),
+            reason: None,
+        },
+        Ok(redis::Value::SimpleString(s)) if s == "OK" => PermissionCheck {
+            user: Some(user),
+            allowed: Some(true),
+            reason: None,
+        },
+        Ok(redis::Value::BulkString(bytes)) => PermissionCheck {
+            user: Some(user),
+            allowed: Some(false),
+            reason: Some(String::from_utf8_lossy(&bytes).into_owned()),
+        },
+        Ok(other) => PermissionCheck {
+            user: Some(user),
+            allowed: None,
+            reason: Some(format!("Unexpected ACL DRYRUN reply: {other:?}")),
+        },
+        Err(e) => PermissionCheck::unknown(Some(user), &e),
+    }
+}
+
+impl PermissionCheck {
+    fn unknown(user: Option<String>, err: &redis::RedisError) -> Self {
+        let reason = if err.code() == Some("NOPERM") {
+            "This user may not run ACL, so permissions can't be checked in advance".to_string()
+        } else if err.to_string().contains("unknown") {
+            "ACL DRYRUN needs Redis 7 or newer".to_string()
+        } else {
+            format!("Permission check failed: {err}")
+        };
+        Self {
+            user,
+            allowed: None,
+            reason: Some(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpret_dryrun() {
+        let allowed = interpret_dryrun("app".into(), Ok(redis::Value::Okay));
+        assert_eq!(allowed.allowed, Some(true));
+        assert_eq!(allowed.user.as_deref(), Some("app"));
+
+        let denied = interpret_dryrun(
+            "app".into(),
+            Ok(redis::Value::BulkString(
+                b"User app has no permissions to run the 'flushall' command".to_vec(),
+            )),
+        );
+        assert_eq!(denied.allowed, Some(false));
+        assert!(denied.reason.unwrap().contains("flushall"));
+
+        let old = interpret_dryrun(
+            "default".into(),
+            Err(redis::RedisError::from((
+                redis::ErrorKind::ResponseError,
+                "An error was signalled by the server",
+                "unknown subcommand 'DRYRUN'".to_string(),
+            ))),
+        );
+        assert_eq!(old.allowed, None);
+        assert!(old.reason.unwrap().contains("Redis 7"));
+    }
+}
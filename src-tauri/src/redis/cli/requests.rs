@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+
+use crate::utils::errors::AppError;
+
+/// In-flight CLI commands that can be cancelled by request ID.
+pub struct CliRequests {
+    handles: Arc<RwLock<HashMap<String, AbortHandle>>>,
+}
+
+impl Default for CliRequests {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CliRequests {
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Run `work` as a task that [`cancel`](Self::cancel) can abort.
+    ///
+    /// Aborting drops the pending reply; the multiplexed connection discards
+    /// it when it arrives, so the pooled connection stays usable.
+    pub async fn run<T, F>(&self, request_id: String, work: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: Future<Output = Result<T, AppError>> + Send + 'static,
+    {
+        let task = tokio::spawn(work);
+        {
+            let mut handles = self.handles.write().await;
+            if handles.contains_key(&request_id) {
+                task.abort();
+                return Err(AppError::InvalidInput(format!(
+                    "Request {request_id} is already running"
+                )));
+            }
+            handles.insert(request_id.clone(), task.abort_handle());
+        }
+
+        let outcome = task.await;
+        self.handles.write().await.remove(&request_id);
+        match outcome {
+            Ok(result) => result,
+            Err(e) if e.is_cancelled() => Err(AppError::Internal("Command was cancelled".into())),
+            Err(e) => Err(AppError::Internal(format!("Command task failed: {e}"))),
+        }
+    }
+
+    /// Abort a running request. Returns whether it was still running.
+    pub async fn cancel(&self, request_id: &str) -> bool {
+        match self.handles.write().await.remove(request_id) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancel_aborts_running_request() {
+        let requests = Arc::new(CliRequests::new());
+        let runner = requests.clone();
+        let running = tokio::spawn(async move {
+            runner
+                .run("r1".into(), async {
+                    tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                    Ok(())
+                })
+                .await
+        });
+
+        // Wait for the task to register.
+        while !requests.handles.read().await.contains_key("r1") {
+            tokio::task::yield_now().await;
+        }
+        assert!(requests.cancel("r1").await);
+        assert!(running.await.unwrap().is_err());
+        assert!(!requests.cancel("r1").await);
+    }
+
+    #[tokio::test]
+    async fn test_run_returns_result() {
+        let requests = CliRequests::new();
+        assert_eq!(requests.run("r2".into(), async { Ok(7) }).await.unwrap(), 7);
+        assert!(requests.handles.read().await.is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+/// Protocol-level view of a reply, for debugging.
+///
+/// The client library hands back parsed values rather than the receive
+/// buffer, so `wire` is re-encoded from the parsed reply. It matches what
+/// the server sent except for insignificant differences such as the
+/// formatting of doubles.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RespView {
+    /// The reply in RESP wire format. Bytes outside printable ASCII (other
+    /// than CR and LF) are escaped as `\xNN`.
+    pub wire: String,
+    pub tree: RespNode,
+}
+
+impl RespView {
+    pub fn new(value: &redis::Value) -> Self {
+        let mut wire = Vec::new();
+        encode(value, &mut wire);
+        Self {
+            wire: escape(&wire),
+            tree: RespNode::from(value),
+        }
+    }
+}
+
+/// A reply with every RESP2/RESP3 type kept distinct.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RespNode {
+    SimpleString {
+        value: String,
+    },
+    Error {
+        message: String,
+    },
+    Integer {
+        value: i64,
+    },
+    /// `value` is lossy UTF-8; `binary` flags content that wasn't valid UTF-8.
+    BulkString {
+        value: String,
+        binary: bool,
+    },
+    Null,
+    Array {
+        items: Vec<RespNode>,
+    },
+    Map {
+        entries: Vec<RespEntry>,
+    },
+    Set {
+        items: Vec<RespNode>,
+    },
+    Double {
+        value: f64,
+    },
+    Boolean {
+        value: bool,
+    },
+    VerbatimString {
+        format: String,
+        text: String,
+    },
+    /// Kept as a decimal string; it doesn't fit in any JSON number.
+    BigNumber {
+        value: String,
+    },
+    Push {
+        kind: String,
+        items: Vec<RespNode>,
+    },
+    Attribute {
+        attributes: Vec<RespEntry>,
+        data: Box<RespNode>,
+    },
+}
+
+/// A key/value pair of a map or attribute reply.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RespEntry {
+    pub key: RespNode,
+    pub value: RespNode,
+}
+
+fn entries(pairs: &[(redis::Value, redis::Value)]) -> Vec<RespEntry> {
+    pairs
+        .iter()
+        .map(|(k, v)| RespEntry {
+            key: k.into(),
+            value: v.into(),
+        })
+        .collect()
+}
+
+fn error_message(code: &str, details: Option<&str>) -> String {
+    match details {
+        Some(details) => format!("{code} {details}"),
+        None => code.to_string(),
+    }
+}
+
+impl From<&redis::Value> for RespNode {
+    fn from(value: &redis::Value) -> Self {
+        use redis::Value;
+        match value {
+            Value::Nil => Self::Null,
+            Value::Int(i) => Self::Integer { value: *i },
+            Value::BulkString(bytes) => Self::BulkString {
+                value: String::from_utf8_lossy(bytes).into_owned(),
+                binary: std::str::from_utf8(bytes).is_err(),
+            },
+            Value::Array(items) => Self::Array {
+                items: items.iter().map(Self::from).collect(),
+            },
+            Value::SimpleString(s) => Self::SimpleString { value: s.clone() },
+            Value::Okay => Self::SimpleString { value: "OK".into() },
+            Value::Map(pairs) => Self::Map {
+                entries: entries(pairs),
+            },
+            Value::Attribute { data, attributes } => Self::Attribute {
+                attributes: entries(attributes),
+                data: Box::new(data.as_ref().into()),
+            },
+            Value::Set(items) => Self::Set {
+                items: items.iter().map(Self::from).collect(),
+            },
+            Value::Double(f) => Self::Double { value: *f },
+            Value::Boolean(b) => Self::Boolean { value: *b },
+            Value::VerbatimString { format, text } => Self::VerbatimString {
+                format: format.to_string(),
+                text: text.clone(),
+            },
+            Value::BigNumber(n) => Self::BigNumber {
+                value: n.to_string(),
+            },
+            Value::Push { kind, data } => Self::Push {
+                kind: kind.to_string(),
+                items: data.iter().map(Self::from).collect(),
+            },
+            Value::ServerError(e) => Self::Error {
+                message: error_message(e.code(), e.details()),
+            },
+        }
+    }
+}
+
+/// Append the RESP encoding of `value` to `out`.
+fn encode(value: &redis::Value, out: &mut Vec<u8>) {
+    use redis::Value;
+
+    fn header(out: &mut Vec<u8>, prefix: char, body: &str) {
+        let mut line = String::with_capacity(body.len() + 3);
+        line.push(prefix);
+        line.push_str(body);
+        line.push_str("\r\n");
+        out.extend_from_slice(line.as_bytes());
+    }
+
+    fn blob(out: &mut Vec<u8>, prefix: char, bytes: &[u8]) {
+        header(out, prefix, &bytes.len().to_string());
+        out.extend_from_slice(bytes);
+        out.extend_from_slice(b"\r\n");
+    }
+
+    fn pairs(out: &mut Vec<u8>, prefix: char, pairs: &[(Value, Value)]) {
+        header(out, prefix, &pairs.len().to_string());
+        for (k, v) in pairs {
+            encode(k, out);
+            encode(v, out);
+        }
+    }
+
+    match value {
+        // RESP3 null; a RESP2 server sends `$-1` or `*-1`, which parse the same.
+        Value::Nil => header(out, '_', ""),
+        Value::Int(i) => header(out, ':', &i.to_string()),
+        Value::BulkString(bytes) => blob(out, '$', bytes),
+        Value::Array(items) => {
+            header(out, '*', &items.len().to_string());
+            for item in items {
+                encode(item, out);
+            }
+        }
+        Value::SimpleString(s) => header(out, '+', s),
+        Value::Okay => header(out, '+', "OK"),
+        Value::Map(entries) => pairs(out, '%', entries),
+        Value::Attribute { data, attributes } => {
+            pairs(out, '|', attributes);
+            encode(data, out);
+        }
+        Value::Set(items) => {
+            header(out, '~', &items.len().to_string());
+            for item in items {
+                encode(item, out);
+            }
+        }
+        Value::Double(f) => {
+            let body = if f.is_nan() {
+                "nan".to_string()
+            } else if f.is_infinite() {
+                if f.is_sign_positive() { "inf" } else { "-inf" }.to_string()
+            } else {
+                f.to_string()
+            };
+            header(out, ',', &body);
+        }
+        Value::Boolean(b) => header(out, '#', if *b { "t" } else { "f" }),
+        Value::VerbatimString { format, text } => {
+            blob(out, '=', format!("{format}:{text}").as_bytes());
+        }
+        Value::BigNumber(n) => header(out, '(', &n.to_string()),
+        Value::Push { kind, data } => {
+            header(out, '>', &(data.len() + 1).to_string());
+            blob(out, '$', kind.to_string().as_bytes());
+            for item in data {
+                encode(item, out);
+            }
+        }
+        Value::ServerError(e) => header(out, '-', &error_message(e.code(), e.details())),
+    }
+}
+
+/// Render wire bytes as text, keeping CR/LF and escaping other
+/// non-printable bytes.
+fn escape(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for &b in bytes {
+        match b {
+            b'\r' | b'\n' | b' '..=b'~' => text.push(char::from(b)),
+            _ => {
+                let _ = write!(text, "\\x{b:02x}");
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redis::Value;
+
+    #[test]
+    fn test_wire_encoding() {
+        let value = Value::Array(vec![
+            Value::Okay,
+            Value::Int(-3),
+            Value::BulkString(b"hi\xff".to_vec()),
+            Value::Nil,
+        ]);
+        assert_eq!(
+            RespView::new(&value).wire,
+            "*4\r\n+OK\r\n:-3\r\n$3\r\nhi\\xff\r\n_\r\n"
+        );
+
+        let map = Value::Map(vec![(
+            Value::SimpleString("a".into()),
+            Value::Boolean(true),
+        )]);
+        assert_eq!(RespView::new(&map).wire, "%1\r\n+a\r\n#t\r\n");
+    }
+
+    #[test]
+    fn test_tree_keeps_resp3_types() {
+        let value = Value::Attribute {
+            data: Box::new(Value::VerbatimString {
+                format: redis::VerbatimFormat::Text,
+                text: "hello".into(),
+            }),
+            attributes: vec![(Value::SimpleString("ttl".into()), Value::Double(1.5))],
+        };
+        let view = RespView::new(&value);
+        assert_eq!(view.wire, "|1\r\n+ttl\r\n,1.5\r\n=9\r\ntxt:hello\r\n");
+
+        let json = serde_json::to_value(&view.tree).unwrap();
+        assert_eq!(json["type"], "attribute");
+        assert_eq!(json["attributes"][0]["value"]["type"], "double");
+        assert_eq!(json["data"]["type"], "verbatimString");
+        assert_eq!(json["data"]["format"], "txt");
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use uuid::Uuid;
+
+use super::model::Snippet;
+use super::parser;
+use crate::utils::errors::AppError;
+
+/// A piece of a parsed template.
+#[derive(Debug, PartialEq, Eq)]
+enum Part<'a> {
+    Text(String),
+    Param(&'a str),
+}
+
+fn is_param_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Split a template into literal text and `{param}` references.
+///
+/// `{{` and `}}` produce literal braces; any other brace that does not
+/// enclose a parameter name is an error, so mistyped templates are caught
+/// when saved rather than sent to the server.
+fn parse(template: &str) -> Result<Vec<Part<'_>>, AppError> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("{{") {
+            text.push('{');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("}}") {
+            text.push('}');
+            rest = after;
+        } else if c == '{' {
+            let end = rest.find('}').ok_or_else(|| unbalanced(template))?;
+            let name = &rest[1..end];
+            if name.is_empty() || !name.chars().all(is_param_char) {
+                return Err(AppError::InvalidInput(format!(
+                    "Invalid parameter name \"{{{name}}}\"; use letters, digits, '_' or '-', \
+                     or write literal braces as {{{{ and }}}}"
+                )));
+            }
+            if !text.is_empty() {
+                parts.push(Part::Text(std::mem::take(&mut text)));
+            }
+            parts.push(Part::Param(name));
+            rest = &rest[end + 1..];
+        } else if c == '}' {
+            return Err(unbalanced(template));
+        } else {
+            text.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+fn unbalanced(template: &str) -> AppError {
+    AppError::InvalidInput(format!("Unbalanced braces in snippet template: {template}"))
+}
+
+/// Parameter names in order of first appearance.
+pub fn placeholders(template: &str) -> Result<Vec<String>, AppError> {
+    let mut names: Vec<String> = Vec::new();
+    for part in parse(template)? {
+        if let Part::Param(name) = part {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Fill in a template. Values are quoted where needed so each stays a
+/// single argument, whatever spaces or quotes it contains.
+pub fn render<S: BuildHasher>(
+    template: &str,
+    args: &HashMap<String, String, S>,
+) -> Result<String, AppError> {
+    let mut command = String::new();
+    for part in parse(template)? {
+        match part {
+            Part::Text(text) => command.push_str(&text),
+            Part::Param(name) => {
+                let value = args.get(name).ok_or_else(|| {
+                    AppError::InvalidInput(format!("Missing value for parameter \"{name}\""))
+                })?;
+                command.push_str(&parser::quote_arg(value));
+            }
+        }
+    }
+    Ok(command)
+}
+
+/// Check a snippet before it is saved.
+pub fn validate(snippet: &Snippet) -> Result<(), AppError> {
+    if snippet.name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Snippet name must not be empty".into(),
+        ));
+    }
+    if snippet.template.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Snippet template must not be empty".into(),
+        ));
+    }
+    parse(&snippet.template).map(|_| ())
+}
+
+/// Whether a snippet is usable on `connection_id` (global or scoped to it)
+/// and, if `filter` is set, mentions it in its name, description or template.
+pub fn matches(snippet: &Snippet, connection_id: Option<&Uuid>, filter: Option<&str>) -> bool {
+    let in_scope = snippet
+        .connection_id
+        .is_none_or(|own| connection_id == Some(&own));
+    let filter = filter.map(str::trim).filter(|f| !f.is_empty());
+    in_scope
+        && filter.is_none_or(|f| {
+            let f = f.to_lowercase();
+            [
+                Some(&snippet.name),
+                snippet.description.as_ref(),
+                Some(&snippet.template),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|text| text.to_lowercase().contains(&f))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::cli::parser::parse_command;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("HSET user:{id} {field} {value} {{tag}} {id}").unwrap(),
+            ["id", "field", "value"]
+        );
+        assert!(placeholders("GET {bad name}").is_err());
+        assert!(placeholders("GET {id").is_err());
+        assert!(placeholders("GET id}").is_err());
+    }
+
+    #[test]
+    fn test_render_keeps_values_single_arguments() {
+        let command = render(
+            "SET session:{id} {value} {{x}}",
+            &args(&[("id", "a b"), ("value", r#"say "hi""#)]),
+        )
+        .unwrap();
+        assert_eq!(
+            parse_command(&command),
+            ["SET", "session:a b", r#"say "hi""#, "{x}"]
+        );
+        assert!(render("DEL {id}", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_matches_scope_and_filter() {
+        let own = Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let mut snippet = Snippet {
+            id: Uuid::new_v4(),
+            name: "Purge session".into(),
+            template: "DEL session:{id}".into(),
+            description: None,
+            connection_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(matches(&snippet, None, Some("purge")));
+        assert!(matches(&snippet, Some(&own), Some("SESSION:")));
+        assert!(!matches(&snippet, None, Some("flush")));
+
+        snippet.connection_id = Some(own);
+        assert!(matches(&snippet, Some(&own), None));
+        assert!(!matches(&snippet, Some(&Uuid::new_v4()), None));
+        assert!(!matches(&snippet, None, None));
+    }
+}
@@ -455,16 +455,19 @@ pub fn get_suggestions(prefix: &str) -> Vec<CommandSuggestion> {
 
     let upper = prefix.to_uppercase();
 
+    all().filter(|s| s.command.starts_with(&upper)).collect()
+}
+
+/// Every command in the static table.
+pub fn all() -> impl Iterator<Item = CommandSuggestion> {
     COMMAND_TABLE
         .iter()
-        .filter(|(cmd, _, _, _)| cmd.starts_with(&upper))
         .map(|(cmd, syntax, summary, group)| CommandSuggestion {
             command: (*cmd).into(),
             syntax: (*syntax).into(),
             summary: (*summary).into(),
             group: (*group).into(),
         })
-        .collect()
 }
 
 #[cfg(test)]
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::model::{DestructiveOp, ImpactEstimate};
+use crate::redis::browser::scanner;
+use crate::redis::cli::parser;
+use crate::redis::connection::databases;
+use crate::redis::devtools::locks;
+use crate::redis::editor::stream_ops;
+use crate::redis::monitor::info_parser;
+use crate::utils::errors::AppError;
+
+/// Number of affected key names included in an estimate.
+const SAMPLE_SIZE: usize = 10;
+
+/// Estimate what a destructive operation would do, without running it.
+pub async fn estimate(pool: &Pool, op: &DestructiveOp) -> Result<ImpactEstimate, AppError> {
+    match op {
+        DestructiveOp::DeleteByPattern { pattern } => {
+            let (count, sample_keys) = scanner::count_matching(pool, pattern, SAMPLE_SIZE).await?;
+            Ok(ImpactEstimate {
+                affected_keys: Some(count),
+                affected_entries: None,
+                sample_keys,
+                exact: true,
+                summary: format!("{count} keys matching '{pattern}' will be deleted"),
+            })
+        }
+        DestructiveOp::BulkTtl {
+            pattern,
+            ttl_seconds,
+        } => {
+            let (count, sample_keys) = scanner::count_matching(pool, pattern, SAMPLE_SIZE).await?;
+            let summary = match ttl_seconds {
+                Some(secs) => format!("{count} keys matching '{pattern}' will expire in {secs}s"),
+                None => format!("{count} keys matching '{pattern}' will be made persistent"),
+            };
+            Ok(ImpactEstimate {
+                affected_keys: Some(count),
+                affected_entries: None,
+                sample_keys,
+                exact: true,
+                summary,
+            })
+        }
+        DestructiveOp::StreamTrim {
+            key,
+            strategy,
+            threshold,
+            approximate,
+        } => {
+            let (entries, exact) =
+                stream_ops::estimate_trim(pool, key, *strategy, threshold).await?;
+            // With `~` Redis trims whole nodes only, so this is an upper bound.
+            let exact = exact && !approximate;
+            let summary = if exact {
+                format!("{entries} entries will be trimmed from '{key}'")
+            } else {
+                format!("Up to {entries} entries will be trimmed from '{key}'")
+            };
+            Ok(ImpactEstimate {
+                affected_keys: Some(1),
+                affected_entries: Some(entries),
+                sample_keys: vec![key.clone()],
+                exact,
+                summary,
+            })
+        }
+        DestructiveOp::CliCommand { command } => estimate_cli(pool, command).await,
+        DestructiveOp::FlushDb { db, .. } => {
+            let keys = databases::db_size(pool).await?;
+            Ok(ImpactEstimate {
+                affected_keys: Some(keys),
+                affected_entries: None,
+                sample_keys: vec![],
+                exact: true,
+                summary: format!("All {keys} keys in database {db} will be deleted"),
+            })
+        }
+        DestructiveOp::BackgroundSave { .. } => estimate_fork(pool, "An RDB snapshot").await,
+        DestructiveOp::RewriteAof => estimate_fork(pool, "An AOF rewrite").await,
+        DestructiveOp::ReleaseLock { key, owner } => {
+            estimate_release(pool, key, owner.as_deref()).await
+        }
+        DestructiveOp::FlushAll { .. } => {
+            let keys = databases::total_keys(pool).await?;
+            Ok(ImpactEstimate {
+                affected_keys: Some(keys),
+                affected_entries: None,
+                sample_keys: vec![],
+                exact: true,
+                summary: format!("All {keys} keys in every database will be deleted"),
+            })
+        }
+    }
+}
+
+/// Describe a BGSAVE or BGREWRITEAOF: both fork, so memory use is what
+/// matters, and whether a save is already running.
+async fn estimate_fork(pool: &Pool, what: &str) -> Result<ImpactEstimate, AppError> {
+    let mut conn = pool.get().await?;
+    let raw: String = redis::cmd("INFO")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INFO failed: {e}")))?;
+    let info = info_parser::parse_info(&raw);
+    let busy = info.persistence.rdb_bgsave_in_progress || info.persistence.aof_rewrite_in_progress;
+    let mut summary = format!(
+        "{what} will fork the server ({} in use); {} changes since the last save",
+        info.memory.used_memory_human, info.persistence.rdb_changes_since_last_save
+    );
+    if busy {
+        summary.push_str("; another background save is already running");
+    }
+    Ok(ImpactEstimate {
+        affected_keys: None,
+        affected_entries: None,
+        sample_keys: vec![],
+        exact: false,
+        summary,
+    })
+}
+
+/// Describe who holds a lock about to be force-released.
+async fn estimate_release(
+    pool: &Pool,
+    key: &str,
+    owner: Option<&str>,
+) -> Result<ImpactEstimate, AppError> {
+    let Some(lock) = locks::inspect_lock(pool, key).await? else {
+        return Err(AppError::NotFound(format!("Lock '{key}' not found")));
+    };
+    let held_by = lock
+        .owner
+        .as_deref()
+        .map_or_else(|| "an unknown owner".to_string(), |o| format!("'{o}'"));
+    let ttl = lock
+        .ttl_ms
+        .map_or_else(|| "no TTL".to_string(), |ms| format!("{ms} ms left"));
+    let summary = match owner {
+        Some(owner) if lock.owner.as_deref() != Some(owner) => format!(
+            "Lock '{key}' is now held by {held_by} ({ttl}), not '{owner}'; it will be left alone"
+        ),
+        Some(_) => format!("Lock '{key}' held by {held_by} ({ttl}) will be deleted if still held"),
+        None => format!("Lock '{key}' held by {held_by} ({ttl}) will be deleted"),
+    };
+    Ok(ImpactEstimate {
+        affected_keys: Some(1),
+        affected_entries: None,
+        sample_keys: vec![key.to_string()],
+        exact: owner.is_none(),
+        summary,
+    })
+}
+
+/// Estimate a dangerous CLI command. Only the flush commands have a
+/// measurable key count; the rest report the parser's warning.
+async fn estimate_cli(pool: &Pool, command: &str) -> Result<ImpactEstimate, AppError> {
+    let args = parser::parse_command(command);
+    let summary = parser::check_dangerous(&args)
+        .map_or_else(|| format!("'{command}' will be executed"), |w| w.message);
+
+    let affected_keys = match args.first().map(|a| a.to_uppercase()).as_deref() {
+        Some("FLUSHDB") => Some(databases::db_size(pool).await?),
+        Some("FLUSHALL") => Some(databases::total_keys(pool).await?),
+        _ => None,
+    };
+
+    Ok(ImpactEstimate {
+        affected_keys,
+        affected_entries: None,
+        sample_keys: vec![],
+        exact: affected_keys.is_some(),
+        summary,
+    })
+}
@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: MIT
+
+pub mod impact;
+pub mod model;
+pub mod registry;
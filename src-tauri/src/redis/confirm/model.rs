@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+
+use crate::redis::editor::model::StreamTrimStrategy;
+
+/// A destructive operation that must be confirmed before it runs.
+///
+/// The exact operation is bound to its confirmation token, so a token issued
+/// for one pattern or key cannot be replayed against another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DestructiveOp {
+    /// UNLINK every key matching a glob pattern.
+    DeleteByPattern { pattern: String },
+    /// Set (or remove, when `None`) the TTL of every key matching a pattern.
+    #[serde(rename_all = "camelCase")]
+    BulkTtl {
+        pattern: String,
+        ttl_seconds: Option<i64>,
+    },
+    /// XTRIM a stream by MAXLEN or MINID.
+    StreamTrim {
+        key: String,
+        strategy: StreamTrimStrategy,
+        threshold: String,
+        approximate: bool,
+    },
+    /// A CLI command flagged as dangerous (FLUSHDB, FLUSHALL, SHUTDOWN, ...).
+    CliCommand { command: String },
+    /// FLUSHDB on one logical database.
+    #[serde(rename_all = "camelCase")]
+    FlushDb { db: u8, async_flush: bool },
+    /// FLUSHALL across every database.
+    #[serde(rename_all = "camelCase")]
+    FlushAll { async_flush: bool },
+    /// BGSAVE; forks the server, which can stall a large instance.
+    BackgroundSave { schedule: bool },
+    /// BGREWRITEAOF; forks like BGSAVE.
+    RewriteAof,
+    /// Force-release a distributed lock, only while `owner` holds it if set.
+    ReleaseLock { key: String, owner: Option<String> },
+}
+
+/// Estimated effect of a destructive operation, shown before confirming.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactEstimate {
+    /// Number of keys touched, if it can be determined up front.
+    pub affected_keys: Option<u64>,
+    /// Number of entries removed inside a single key (stream trims).
+    pub affected_entries: Option<u64>,
+    /// A few affected key names for display.
+    pub sample_keys: Vec<String>,
+    /// False when the counts are a bound rather than an exact figure.
+    pub exact: bool,
+    pub summary: String,
+}
+
+/// A destructive operation waiting for the frontend to re-submit its token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingConfirmation {
+    pub token: String,
+    pub connection_id: String,
+    pub operation: DestructiveOp,
+    pub impact: ImpactEstimate,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Result of a command guarded by a confirmation token.
+///
+/// Without a token the command returns `Pending` and does nothing; with a
+/// valid token it runs and returns `Done`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum Confirmable<T> {
+    Pending {
+        confirmation: Box<PendingConfirmation>,
+    },
+    Done {
+        result: T,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirmable_serialization() {
+        let done: Confirmable<u64> = Confirmable::Done { result: 3 };
+        let json = serde_json::to_string(&done).expect("serialize");
+        assert_eq!(json, r#"{"status":"done","result":3}"#);
+    }
+
+    #[test]
+    fn test_destructive_op_serialization() {
+        let op = DestructiveOp::BulkTtl {
+            pattern: "session:*".into(),
+            ttl_seconds: Some(60),
+        };
+        let json = serde_json::to_string(&op).expect("serialize");
+        assert!(json.contains(r#""type":"bulkTtl""#));
+        assert!(json.contains(r#""ttlSeconds":60"#));
+
+        let flush = DestructiveOp::FlushDb {
+            db: 2,
+            async_flush: true,
+        };
+        let json = serde_json::to_string(&flush).expect("serialize");
+        assert_eq!(json, r#"{"type":"flushDb","db":2,"asyncFlush":true}"#);
+    }
+}
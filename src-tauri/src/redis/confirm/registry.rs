@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use deadpool_redis::Pool;
+use tokio::sync::RwLock;
+
+use super::impact;
+use super::model::{DestructiveOp, ImpactEstimate, PendingConfirmation};
+use crate::utils::errors::AppError;
+
+/// How long an issued confirmation token stays valid.
+const CONFIRMATION_TTL_SECS: i64 = 120;
+
+/// Tracks pending confirmation tokens for destructive operations.
+///
+/// Tokens are single-use and bound to the connection and exact operation
+/// they were issued for.
+pub struct ConfirmationRegistry {
+    pending: Arc<RwLock<HashMap<String, PendingConfirmation>>>,
+}
+
+impl Default for ConfirmationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfirmationRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            pending: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Either consume a re-submitted token or issue a new pending confirmation.
+    ///
+    /// Returns `None` when the caller may proceed with the operation, or the
+    /// pending confirmation (with its impact estimate) to hand back to the
+    /// frontend.
+    pub async fn gate(
+        &self,
+        pool: &Pool,
+        connection_id: &str,
+        op: DestructiveOp,
+        token: Option<&str>,
+    ) -> Result<Option<PendingConfirmation>, AppError> {
+        if let Some(token) = token {
+            self.consume(token, connection_id, &op).await?;
+            return Ok(None);
+        }
+
+        let impact = impact::estimate(pool, &op).await?;
+        Ok(Some(self.issue(connection_id, op, impact).await))
+    }
+
+    /// Register a pending confirmation and return it.
+    pub async fn issue(
+        &self,
+        connection_id: &str,
+        op: DestructiveOp,
+        impact: ImpactEstimate,
+    ) -> PendingConfirmation {
+        let now = chrono::Utc::now();
+        let pending = PendingConfirmation {
+            token: uuid::Uuid::new_v4().to_string(),
+            connection_id: connection_id.to_string(),
+            operation: op,
+            impact,
+            expires_at: now + chrono::Duration::seconds(CONFIRMATION_TTL_SECS),
+        };
+
+        let mut map = self.pending.write().await;
+        map.retain(|_, p| p.expires_at > now);
+        map.insert(pending.token.clone(), pending.clone());
+        pending
+    }
+
+    /// Validate and spend a token for `op` on `connection_id`.
+    pub async fn consume(
+        &self,
+        token: &str,
+        connection_id: &str,
+        op: &DestructiveOp,
+    ) -> Result<(), AppError> {
+        let pending = self
+            .pending
+            .write()
+            .await
+            .remove(token)
+            .filter(|p| p.expires_at > chrono::Utc::now())
+            .ok_or_else(|| {
+                AppError::InvalidInput("Confirmation token is invalid or has expired".into())
+            })?;
+
+        if pending.connection_id != connection_id || &pending.operation != op {
+            return Err(AppError::InvalidInput(
+                "Confirmation token does not match this operation".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Discard a pending confirmation. Returns whether it existed.
+    pub async fn cancel(&self, token: &str) -> bool {
+        self.pending.write().await.remove(token).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delete_op(pattern: &str) -> DestructiveOp {
+        DestructiveOp::DeleteByPattern {
+            pattern: pattern.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_is_single_use() {
+        let registry = ConfirmationRegistry::new();
+        let pending = registry
+            .issue("conn-1", delete_op("tmp:*"), ImpactEstimate::default())
+            .await;
+
+        assert!(registry
+            .consume(&pending.token, "conn-1", &delete_op("tmp:*"))
+            .await
+            .is_ok());
+        assert!(registry
+            .consume(&pending.token, "conn-1", &delete_op("tmp:*"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_token_bound_to_operation_and_connection() {
+        let registry = ConfirmationRegistry::new();
+        let pending = registry
+            .issue("conn-1", delete_op("tmp:*"), ImpactEstimate::default())
+            .await;
+        assert!(registry
+            .consume(&pending.token, "conn-1", &delete_op("*"))
+            .await
+            .is_err());
+
+        let pending = registry
+            .issue("conn-1", delete_op("tmp:*"), ImpactEstimate::default())
+            .await;
+        assert!(registry
+            .consume(&pending.token, "conn-2", &delete_op("tmp:*"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_rejected() {
+        let registry = ConfirmationRegistry::new();
+        let mut pending = registry
+            .issue("conn-1", delete_op("tmp:*"), ImpactEstimate::default())
+            .await;
+        pending.expires_at = chrono::Utc::now() - chrono::Duration::seconds(1);
+        registry
+            .pending
+            .write()
+            .await
+            .insert(pending.token.clone(), pending.clone());
+
+        assert!(registry
+            .consume(&pending.token, "conn-1", &delete_op("tmp:*"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let registry = ConfirmationRegistry::new();
+        let pending = registry
+            .issue("conn-1", delete_op("tmp:*"), ImpactEstimate::default())
+            .await;
+        assert!(registry.cancel(&pending.token).await);
+        assert!(!registry.cancel(&pending.token).await);
+    }
+}
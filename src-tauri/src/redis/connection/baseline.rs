@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use super::capabilities::parse_version;
+use super::model::{ConfigBaseline, ConfigDrift};
+
+/// Check the live server against a profile's baseline, if it has one.
+///
+/// Only issues CONFIG GET for the parameters the baseline actually sets.
+/// Servers that disable or rename CONFIG (common on managed offerings)
+/// are only checked for version drift.
+pub async fn check_drift<C>(
+    conn: &mut C,
+    baseline: Option<&ConfigBaseline>,
+    redis_version: &str,
+) -> Vec<ConfigDrift>
+where
+    C: redis::aio::ConnectionLike + Send,
+{
+    let Some(baseline) = baseline else {
+        return Vec::new();
+    };
+
+    let mut config = HashMap::new();
+
+    if baseline.maxmemory_policy.is_some() {
+        config.extend(config_get(conn, "maxmemory-policy").await);
+    }
+    if baseline.appendonly.is_some() {
+        config.extend(config_get(conn, "appendonly").await);
+    }
+
+    compare(baseline, redis_version, &config)
+}
+
+/// Compare a baseline against a server version and CONFIG values.
+///
+/// Parameters missing from `config` are skipped rather than reported.
+pub fn compare<S: BuildHasher>(
+    baseline: &ConfigBaseline,
+    redis_version: &str,
+    config: &HashMap<String, String, S>,
+) -> Vec<ConfigDrift> {
+    let mut drift = Vec::new();
+    let version = parse_version(redis_version);
+
+    if let Some(ref min) = baseline.min_version {
+        if version < parse_version(min) {
+            drift.push(ConfigDrift {
+                parameter: "redis_version".into(),
+                expected: format!(">= {min}"),
+                actual: redis_version.to_string(),
+            });
+        }
+    }
+
+    if let Some(ref max) = baseline.max_version {
+        if version > parse_version(max) {
+            drift.push(ConfigDrift {
+                parameter: "redis_version".into(),
+                expected: format!("<= {max}"),
+                actual: redis_version.to_string(),
+            });
+        }
+    }
+
+    if let Some(ref expected) = baseline.maxmemory_policy {
+        if let Some(actual) = config.get("maxmemory-policy") {
+            if !actual.eq_ignore_ascii_case(expected) {
+                drift.push(ConfigDrift {
+                    parameter: "maxmemory-policy".into(),
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+    }
+
+    if let Some(expected) = baseline.appendonly {
+        if let Some(actual) = config.get("appendonly") {
+            if actual.eq_ignore_ascii_case("yes") != expected {
+                drift.push(ConfigDrift {
+                    parameter: "appendonly".into(),
+                    expected: if expected { "yes" } else { "no" }.into(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+    }
+
+    drift
+}
+
+/// Run CONFIG GET for a single parameter, returning an empty map on failure.
+async fn config_get<C>(conn: &mut C, parameter: &str) -> HashMap<String, String>
+where
+    C: redis::aio::ConnectionLike + Send,
+{
+    redis::cmd("CONFIG")
+        .arg("GET")
+        .arg(parameter)
+        .query_async(conn)
+        .await
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_no_baseline_fields_no_drift() {
+        let drift = compare(&ConfigBaseline::default(), "7.2.4", &HashMap::new());
+        assert!(drift.is_empty());
+    }
+
+    #[test]
+    fn test_compare_version_range() {
+        let baseline = ConfigBaseline {
+            min_version: Some("7.0".into()),
+            max_version: Some("7.2.99".into()),
+            ..ConfigBaseline::default()
+        };
+        assert!(compare(&baseline, "7.2.4", &HashMap::new()).is_empty());
+
+        let drift = compare(&baseline, "6.2.14", &HashMap::new());
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].expected, ">= 7.0");
+
+        let drift = compare(&baseline, "7.4.0", &HashMap::new());
+        assert_eq!(drift[0].expected, "<= 7.2.99");
+    }
+
+    #[test]
+    fn test_compare_maxmemory_policy() {
+        let baseline = ConfigBaseline {
+            maxmemory_policy: Some("allkeys-lru".into()),
+            ..ConfigBaseline::default()
+        };
+        let drift = compare(
+            &baseline,
+            "7.2.4",
+            &config(&[("maxmemory-policy", "noeviction")]),
+        );
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].parameter, "maxmemory-policy");
+        assert_eq!(drift[0].actual, "noeviction");
+    }
+
+    #[test]
+    fn test_compare_appendonly() {
+        let baseline = ConfigBaseline {
+            appendonly: Some(true),
+            ..ConfigBaseline::default()
+        };
+        let drift = compare(&baseline, "7.2.4", &config(&[("appendonly", "no")]));
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].expected, "yes");
+
+        let drift = compare(&baseline, "7.2.4", &config(&[("appendonly", "yes")]));
+        assert!(drift.is_empty());
+    }
+
+    #[test]
+    fn test_compare_missing_config_is_skipped() {
+        let baseline = ConfigBaseline {
+            maxmemory_policy: Some("allkeys-lru".into()),
+            appendonly: Some(true),
+            ..ConfigBaseline::default()
+        };
+        assert!(compare(&baseline, "7.2.4", &HashMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT
+
+use redis::Value;
+
+use super::model::{ModuleInfo, ServerCapabilities};
+
+/// Module names (lowercased) that provide JSON support.
+const JSON_MODULES: &[&str] = &["rejson", "json"];
+/// Module names (lowercased) that provide `RediSearch` support.
+const SEARCH_MODULES: &[&str] = &["search", "ft", "searchlight"];
+/// Module names (lowercased) that provide `TimeSeries` support.
+const TIMESERIES_MODULES: &[&str] = &["timeseries"];
+
+/// Probe a freshly opened connection for server capabilities.
+///
+/// Runs MODULE LIST and COMMAND COUNT. Both are optional: a server that
+/// disables MODULE (or an ACL user without access) simply reports no modules.
+pub async fn probe<C>(conn: &mut C, redis_version: &str) -> ServerCapabilities
+where
+    C: redis::aio::ConnectionLike + Send,
+{
+    let modules_raw: Value = redis::cmd("MODULE")
+        .arg("LIST")
+        .query_async(conn)
+        .await
+        .unwrap_or(Value::Nil);
+
+    let command_count: u64 = redis::cmd("COMMAND")
+        .arg("COUNT")
+        .query_async(conn)
+        .await
+        .unwrap_or(0);
+
+    build_capabilities(
+        redis_version,
+        parse_module_list(&modules_raw),
+        command_count,
+    )
+}
+
+/// Derive feature flags from the server version and loaded modules.
+pub fn build_capabilities(
+    redis_version: &str,
+    modules: Vec<ModuleInfo>,
+    command_count: u64,
+) -> ServerCapabilities {
+    let has_module = |names: &[&str]| {
+        modules
+            .iter()
+            .any(|m| names.contains(&m.name.to_lowercase().as_str()))
+    };
+    let version = parse_version(redis_version);
+
+    ServerCapabilities {
+        redis_version: redis_version.to_string(),
+        command_count,
+        json: has_module(JSON_MODULES),
+        search: has_module(SEARCH_MODULES),
+        time_series: has_module(TIMESERIES_MODULES),
+        acl: version >= (6, 0, 0),
+        functions: version >= (7, 0, 0),
+        modules,
+    }
+}
+
+/// Parse a `major.minor.patch` version string, treating missing parts as 0.
+///
+/// Unparseable input yields `(0, 0, 0)` so feature checks fail closed.
+pub fn parse_version(version: &str) -> (u32, u32, u32) {
+    let mut parts = version
+        .trim()
+        .split('.')
+        .map(|p| p.parse::<u32>().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// Parse a MODULE LIST reply.
+///
+/// Each module is a flat `[name, <name>, ver, <ver>, ...]` array under RESP2,
+/// or a map under RESP3.
+fn parse_module_list(value: &Value) -> Vec<ModuleInfo> {
+    let Value::Array(entries) = value else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let pairs: Vec<(&Value, &Value)> = match entry {
+                Value::Array(items) => items
+                    .chunks(2)
+                    .filter_map(|c| Some((c.first()?, c.get(1)?)))
+                    .collect(),
+                Value::Map(items) => items.iter().map(|(k, v)| (k, v)).collect(),
+                _ => return None,
+            };
+
+            let mut name = None;
+            let mut version = 0;
+            for (k, v) in pairs {
+                match value_to_string(k).as_str() {
+                    "name" => name = Some(value_to_string(v)),
+                    "ver" => version = value_to_i64(v),
+                    _ => {}
+                }
+            }
+            name.map(|name| ModuleInfo { name, version })
+        })
+        .collect()
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::BulkString(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::SimpleString(s) => s.clone(),
+        Value::Int(n) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn value_to_i64(value: &Value) -> i64 {
+    match value {
+        Value::Int(n) => *n,
+        other => value_to_string(other).parse().unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> Value {
+        Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("7.2.4"), (7, 2, 4));
+        assert_eq!(parse_version("6.0"), (6, 0, 0));
+        assert_eq!(parse_version("unknown"), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_parse_module_list_resp2() {
+        let raw = Value::Array(vec![
+            Value::Array(vec![
+                bulk("name"),
+                bulk("ReJSON"),
+                bulk("ver"),
+                Value::Int(20_609),
+            ]),
+            Value::Array(vec![
+                bulk("name"),
+                bulk("search"),
+                bulk("ver"),
+                Value::Int(20_814),
+            ]),
+        ]);
+        let modules = parse_module_list(&raw);
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].name, "ReJSON");
+        assert_eq!(modules[0].version, 20_609);
+    }
+
+    #[test]
+    fn test_parse_module_list_resp3_map() {
+        let raw = Value::Array(vec![Value::Map(vec![
+            (bulk("name"), bulk("timeseries")),
+            (bulk("ver"), Value::Int(11_000)),
+        ])]);
+        let modules = parse_module_list(&raw);
+        assert_eq!(modules[0].name, "timeseries");
+    }
+
+    #[test]
+    fn test_parse_module_list_nil() {
+        assert!(parse_module_list(&Value::Nil).is_empty());
+    }
+
+    #[test]
+    fn test_build_capabilities_flags() {
+        let modules = vec![ModuleInfo {
+            name: "ReJSON".into(),
+            version: 20_609,
+        }];
+        let caps = build_capabilities("7.2.0", modules, 240);
+        assert!(caps.json);
+        assert!(!caps.search);
+        assert!(caps.acl);
+        assert!(caps.functions);
+        assert_eq!(caps.command_count, 240);
+    }
+
+    #[test]
+    fn test_build_capabilities_old_server() {
+        let caps = build_capabilities("5.0.14", Vec::new(), 0);
+        assert!(!caps.acl);
+        assert!(!caps.functions);
+        assert!(!caps.json);
+    }
+}
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT
+
+use super::model::CommandPolicy;
+use crate::redis::cli::model::{DangerLevel, DangerousWarning};
+use crate::redis::cli::parser;
+use crate::utils::errors::AppError;
+
+/// Reject entries that can't match a command.
+pub fn validate(policy: &CommandPolicy) -> Result<(), AppError> {
+    let lists = [
+        ("block", &policy.block),
+        ("confirm", &policy.confirm),
+        ("allow", &policy.allow),
+    ];
+    for (list, entries) in lists {
+        for entry in entries {
+            let words = entry.split_whitespace().count();
+            if words == 0 || words > 2 {
+                return Err(AppError::InvalidInput(format!(
+                    "Invalid {list} entry '{entry}': expected a command, optionally with a subcommand"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `entry` (`KEYS`, `CONFIG SET`) names the command in `args`.
+fn matches(entry: &str, args: &[String]) -> bool {
+    let mut words = entry.split_whitespace();
+    let Some(name) = words.next() else {
+        return false;
+    };
+    if !args.first().is_some_and(|a| a.eq_ignore_ascii_case(name)) {
+        return false;
+    }
+    match words.next() {
+        Some(sub) => args.get(1).is_some_and(|a| a.eq_ignore_ascii_case(sub)),
+        None => true,
+    }
+}
+
+fn listed<'a>(entries: &'a [String], args: &[String]) -> Option<&'a String> {
+    entries.iter().find(|entry| matches(entry, args))
+}
+
+/// Refuse a command the policy blocks.
+pub fn check_blocked(policy: &CommandPolicy, args: &[String]) -> Result<(), AppError> {
+    match listed(&policy.block, args) {
+        Some(entry) => Err(AppError::PermissionDenied(format!(
+            "{} is blocked on this connection",
+            entry.to_uppercase()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Apply `policy` on top of the built-in dangerous command table: blocked
+/// commands fail with a permission error, and the returned warning says
+/// the command needs confirmation.
+pub fn check(
+    policy: &CommandPolicy,
+    args: &[String],
+) -> Result<Option<DangerousWarning>, AppError> {
+    check_blocked(policy, args)?;
+    if listed(&policy.allow, args).is_some() {
+        return Ok(None);
+    }
+    if let Some(warning) = parser::check_dangerous(args) {
+        return Ok(Some(warning));
+    }
+    Ok(listed(&policy.confirm, args).map(|entry| DangerousWarning {
+        command: args.join(" "),
+        level: DangerLevel::Warning,
+        message: format!(
+            "{} needs confirmation on this connection.",
+            entry.to_uppercase()
+        ),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    fn policy(block: &[&str], confirm: &[&str], allow: &[&str]) -> CommandPolicy {
+        let list = |entries: &[&str]| entries.iter().map(|e| (*e).to_string()).collect();
+        CommandPolicy {
+            block: list(block),
+            confirm: list(confirm),
+            allow: list(allow),
+        }
+    }
+
+    #[test]
+    fn test_empty_policy_uses_builtin_table() {
+        let empty = CommandPolicy::default();
+        assert!(check(&empty, &args("FLUSHALL")).unwrap().is_some());
+        assert!(check(&empty, &args("KEYS *")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_block_wins_over_allow() {
+        let prod = policy(&["keys", "FLUSHALL"], &[], &["FLUSHALL"]);
+        let err = check(&prod, &args("keys *")).unwrap_err();
+        assert!(matches!(err, AppError::PermissionDenied(ref m) if m.starts_with("KEYS")));
+        assert!(check(&prod, &args("FLUSHALL ASYNC")).is_err());
+        assert!(check(&prod, &args("GET k")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_allow_and_confirm_override_table() {
+        let local = policy(&[], &["KEYS"], &["FLUSHALL", "CONFIG SET"]);
+        assert!(check(&local, &args("FLUSHALL")).unwrap().is_none());
+        assert!(check(&local, &args("config set hz 20")).unwrap().is_none());
+        assert!(check(&local, &args("FLUSHDB")).unwrap().is_some());
+        let warning = check(&local, &args("keys user:*")).unwrap().unwrap();
+        assert_eq!(
+            warning.message,
+            "KEYS needs confirmation on this connection."
+        );
+    }
+
+    #[test]
+    fn test_subcommand_entries() {
+        let p = policy(&["CONFIG SET"], &[], &[]);
+        assert!(check_blocked(&p, &args("CONFIG SET hz 20")).is_err());
+        assert!(check_blocked(&p, &args("CONFIG GET hz")).is_ok());
+        assert!(check_blocked(&p, &args("CONFIG")).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_entries() {
+        assert!(validate(&policy(&["KEYS"], &["CONFIG SET"], &[])).is_ok());
+        assert!(validate(&policy(&[" "], &[], &[])).is_err());
+        assert!(validate(&policy(&[], &[], &["CONFIG SET hz"])).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::model::DatabaseSummary;
+use crate::redis::monitor::info_parser;
+use crate::redis::monitor::model::DatabaseInfo;
+use crate::utils::errors::AppError;
+
+/// Database count assumed when CONFIG GET is unavailable (the Redis default).
+const DEFAULT_DATABASES: u16 = 16;
+
+/// Summarize every logical database on the server.
+///
+/// The database count comes from CONFIG GET databases; managed services that
+/// disable CONFIG fall back to 16, widened to cover any db listed by INFO.
+pub async fn list_databases(pool: &Pool, active: u8) -> Result<Vec<DatabaseSummary>, AppError> {
+    let mut conn = pool.get().await?;
+
+    let raw: String = redis::cmd("INFO")
+        .arg("keyspace")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INFO keyspace failed: {e}")))?;
+    let keyspace = info_parser::parse_info(&raw).keyspace;
+
+    let configured: Option<u16> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("databases")
+        .query_async::<Vec<String>>(&mut conn)
+        .await
+        .ok()
+        .and_then(|pair| pair.get(1).and_then(|v| v.parse().ok()));
+
+    Ok(merge_databases(configured, &keyspace, active))
+}
+
+/// Number of keys in the pool's database (DBSIZE).
+pub async fn db_size(pool: &Pool) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    redis::cmd("DBSIZE")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("DBSIZE failed: {e}")))
+}
+
+/// Number of keys across every database, from INFO keyspace.
+pub async fn total_keys(pool: &Pool) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    let raw: String = redis::cmd("INFO")
+        .arg("keyspace")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INFO keyspace failed: {e}")))?;
+    Ok(info_parser::parse_info(&raw)
+        .keyspace
+        .iter()
+        .map(|db| db.keys)
+        .sum())
+}
+
+/// Delete every key in the pool's database. With `async_flush` the server
+/// frees memory in the background (FLUSHDB ASYNC, Redis 4.0+).
+pub async fn flush_db(pool: &Pool, async_flush: bool) -> Result<(), AppError> {
+    let mut conn = pool.get().await?;
+    let mut cmd = redis::cmd("FLUSHDB");
+    if async_flush {
+        cmd.arg("ASYNC");
+    }
+    cmd.query_async::<()>(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("FLUSHDB failed: {e}")))
+}
+
+/// Delete every key in every database, like [`flush_db`].
+pub async fn flush_all(pool: &Pool, async_flush: bool) -> Result<(), AppError> {
+    let mut conn = pool.get().await?;
+    let mut cmd = redis::cmd("FLUSHALL");
+    if async_flush {
+        cmd.arg("ASYNC");
+    }
+    cmd.query_async::<()>(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("FLUSHALL failed: {e}")))
+}
+
+/// Combine the configured database count with INFO keyspace entries.
+///
+/// Databases missing from INFO are empty and reported with zero keys.
+fn merge_databases(
+    configured: Option<u16>,
+    keyspace: &[DatabaseInfo],
+    active: u8,
+) -> Vec<DatabaseSummary> {
+    let listed = keyspace
+        .iter()
+        .map(|db| u16::from(db.index) + 1)
+        .max()
+        .unwrap_or(0);
+    let count = configured
+        .unwrap_or(DEFAULT_DATABASES)
+        .max(listed)
+        .max(u16::from(active) + 1)
+        .min(256);
+
+    (0..count)
+        .filter_map(|i| u8::try_from(i).ok())
+        .map(|index| {
+            let info = keyspace.iter().find(|db| db.index == index);
+            DatabaseSummary {
+                index,
+                keys: info.map_or(0, |db| db.keys),
+                expires: info.map_or(0, |db| db.expires),
+                avg_ttl: info.map_or(0, |db| db.avg_ttl),
+                active: index == active,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db(index: u8, keys: u64) -> DatabaseInfo {
+        DatabaseInfo {
+            index,
+            keys,
+            expires: 1,
+            avg_ttl: 500,
+        }
+    }
+
+    #[test]
+    fn test_merge_databases_fills_empty_dbs() {
+        let dbs = merge_databases(Some(4), &[db(0, 10), db(2, 3)], 2);
+        assert_eq!(dbs.len(), 4);
+        assert_eq!(dbs[0].keys, 10);
+        assert_eq!(dbs[1].keys, 0);
+        assert_eq!(dbs[1].avg_ttl, 0);
+        assert!(dbs[2].active);
+        assert!(!dbs[0].active);
+    }
+
+    #[test]
+    fn test_merge_databases_without_config() {
+        let dbs = merge_databases(None, &[db(0, 1)], 0);
+        assert_eq!(dbs.len(), 16);
+
+        // INFO can list a db beyond the assumed default.
+        let dbs = merge_databases(None, &[db(20, 5)], 0);
+        assert_eq!(dbs.len(), 21);
+        assert_eq!(dbs[20].keys, 5);
+    }
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::Instant;
+
+use deadpool_redis::Pool;
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+use super::manager::ConnectionManager;
+use super::model::{WriteAck, WriteConcern};
+use crate::utils::errors::AppError;
+
+/// WAIT for `concern` on a connection from `pool`, returning how many
+/// replicas acknowledged.
+///
+/// WAIT only covers writes made by the same client, so `pool` must be the
+/// single-connection pool the write went through.
+pub async fn wait_for_replicas(pool: &Pool, concern: &WriteConcern) -> Result<u32, AppError> {
+    let mut conn = pool.get().await?;
+    let acknowledged: u32 = redis::cmd("WAIT")
+        .arg(concern.replicas)
+        .arg(concern.timeout_ms)
+        .query_async(&mut conn)
+        .await?;
+    Ok(acknowledged)
+}
+
+/// After an editor or bulk write, wait for the connection's write concern
+/// (if one is set) and emit the outcome as `connection:write-ack`.
+///
+/// The write has already happened, so a failed or short WAIT is reported
+/// rather than returned as an error.
+pub async fn acknowledge(
+    app: &AppHandle,
+    manager: &ConnectionManager,
+    connection_id: &str,
+    db: Option<u8>,
+    operation: &str,
+    key: Option<&str>,
+) {
+    let Ok(uuid) = Uuid::parse_str(connection_id) else {
+        return;
+    };
+    let Some(concern) = manager.write_concern(&uuid).await else {
+        return;
+    };
+
+    let start = Instant::now();
+    let outcome = match manager.get_write_pool_for_db(&uuid, db).await {
+        Ok(pool) => wait_for_replicas(&pool, &concern).await,
+        Err(e) => Err(e),
+    };
+    let (acknowledged, error) = match outcome {
+        Ok(n) => (n, None),
+        Err(e) => (0, Some(e.to_string())),
+    };
+    if acknowledged < concern.replicas {
+        tracing::warn!(
+            connection_id = %connection_id,
+            operation = %operation,
+            requested = concern.replicas,
+            acknowledged = acknowledged,
+            "Write not acknowledged by all requested replicas"
+        );
+    }
+
+    let ack = WriteAck {
+        connection_id: connection_id.to_string(),
+        operation: operation.to_string(),
+        key: key.map(ToString::to_string),
+        requested: concern.replicas,
+        acknowledged,
+        elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+        error,
+    };
+    let _ = app.emit("connection:write-ack", &ack);
+}
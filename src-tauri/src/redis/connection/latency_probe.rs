@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use deadpool_redis::Pool;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::utils::errors::AppError;
+
+/// Probe results kept per connection for the rolling figures.
+const WINDOW: usize = 60;
+/// A PING slower than this counts as lost.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Consecutive lost probes after which a connection is shown as down.
+const DOWN_AFTER: usize = 3;
+pub const DEFAULT_INTERVAL_MS: u64 = 1_000;
+const MIN_INTERVAL_MS: u64 = 200;
+const MAX_INTERVAL_MS: u64 = 60_000;
+
+/// Badge-level summary of how a connection is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionQuality {
+    /// Not enough probes yet.
+    Unknown,
+    Good,
+    Fair,
+    Poor,
+    /// The last few probes were all lost.
+    Down,
+}
+
+/// Rolling round-trip figures for one connection.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub connection_id: String,
+    pub quality: ConnectionQuality,
+    /// Probes in the window, answered or not.
+    pub samples: u64,
+    /// Share of probes in the window that failed or timed out, 0–100.
+    pub loss_percent: f64,
+    pub last_us: Option<u64>,
+    pub min_us: Option<u64>,
+    pub avg_us: Option<u64>,
+    pub p99_us: Option<u64>,
+}
+
+/// Payload of `connection:quality`, emitted when a connection's quality
+/// changes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityEvent {
+    pub connection_id: String,
+    pub quality: ConnectionQuality,
+    pub stats: LatencyStats,
+}
+
+/// The last [`WINDOW`] probe results; `None` is a lost probe.
+#[derive(Debug, Default)]
+struct ProbeWindow {
+    samples: VecDeque<Option<u64>>,
+}
+
+impl ProbeWindow {
+    fn push(&mut self, sample: Option<u64>) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn stats(&self, connection_id: &str) -> LatencyStats {
+        let mut answered: Vec<u64> = self.samples.iter().flatten().copied().collect();
+        answered.sort_unstable();
+        let total = self.samples.len();
+        let lost = total - answered.len();
+        let loss_percent = if total == 0 {
+            0.0
+        } else {
+            lost as f64 * 100.0 / total as f64
+        };
+        let p99_us = (!answered.is_empty()).then(|| {
+            let rank = (answered.len() * 99).div_ceil(100);
+            answered[rank.max(1) - 1]
+        });
+        let avg_us =
+            (!answered.is_empty()).then(|| answered.iter().sum::<u64>() / answered.len() as u64);
+
+        LatencyStats {
+            connection_id: connection_id.to_string(),
+            quality: self.quality(loss_percent, p99_us),
+            samples: total as u64,
+            loss_percent,
+            last_us: self.samples.back().copied().flatten(),
+            min_us: answered.first().copied(),
+            avg_us,
+            p99_us,
+        }
+    }
+
+    fn quality(&self, loss_percent: f64, p99_us: Option<u64>) -> ConnectionQuality {
+        let recent_lost = self.samples.iter().rev().take(DOWN_AFTER);
+        if self.samples.len() >= DOWN_AFTER && recent_lost.clone().all(Option::is_none) {
+            return ConnectionQuality::Down;
+        }
+        let Some(p99) = p99_us else {
+            return ConnectionQuality::Unknown;
+        };
+        if loss_percent < 1.0 && p99 <= 50_000 {
+            ConnectionQuality::Good
+        } else if loss_percent < 5.0 && p99 <= 250_000 {
+            ConnectionQuality::Fair
+        } else {
+            ConnectionQuality::Poor
+        }
+    }
+}
+
+/// Clamp a caller-supplied probe interval.
+pub fn probe_interval(interval_ms: Option<u64>) -> Duration {
+    Duration::from_millis(
+        interval_ms
+            .unwrap_or(DEFAULT_INTERVAL_MS)
+            .clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS),
+    )
+}
+
+/// Round trip of one PING in microseconds, or `None` if it failed.
+async fn probe(pool: &Pool) -> Option<u64> {
+    let started = Instant::now();
+    let ping = async {
+        let mut conn = pool.get().await?;
+        let _: String = redis::cmd("PING").query_async(&mut conn).await?;
+        Ok::<_, AppError>(())
+    };
+    match tokio::time::timeout(PROBE_TIMEOUT, ping).await {
+        Ok(Ok(())) => Some(u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX)),
+        _ => None,
+    }
+}
+
+struct Probe {
+    handle: AbortHandle,
+    window: Arc<Mutex<ProbeWindow>>,
+}
+
+/// PINGs connections in the background to track round-trip times and
+/// failures, separately from monitor polling and the health supervisor.
+pub struct LatencyProber {
+    probes: Arc<RwLock<HashMap<Uuid, Probe>>>,
+}
+
+impl Default for LatencyProber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyProber {
+    pub fn new() -> Self {
+        Self {
+            probes: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Probe every `interval`, replacing any probe already running for the
+    /// connection. Quality changes are emitted as `connection:quality`.
+    pub async fn start(&self, connection_id: Uuid, pool: Pool, interval: Duration, app: AppHandle) {
+        self.stop(&connection_id).await;
+
+        let window = Arc::new(Mutex::new(ProbeWindow::default()));
+        let shared = Arc::clone(&window);
+        let id = connection_id.to_string();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut quality = ConnectionQuality::Unknown;
+            loop {
+                ticker.tick().await;
+                let sample = probe(&pool).await;
+                let stats = {
+                    let mut window = shared.lock().await;
+                    window.push(sample);
+                    window.stats(&id)
+                };
+                if stats.quality != quality {
+                    quality = stats.quality;
+                    tracing::debug!(connection_id = %id, quality = ?quality, "Connection quality changed");
+                    let event = QualityEvent {
+                        connection_id: id.clone(),
+                        quality,
+                        stats,
+                    };
+                    let _ = app.emit("connection:quality", &event);
+                }
+            }
+        });
+
+        self.probes.write().await.insert(
+            connection_id,
+            Probe {
+                handle: task.abort_handle(),
+                window,
+            },
+        );
+        tracing::info!(connection_id = %connection_id, "Latency probe started");
+    }
+
+    /// Stop probing a connection. Returns whether a probe was running.
+    pub async fn stop(&self, connection_id: &Uuid) -> bool {
+        match self.probes.write().await.remove(connection_id) {
+            Some(probe) => {
+                probe.handle.abort();
+                tracing::info!(connection_id = %connection_id, "Latency probe stopped");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Current figures, or `None` if the connection isn't being probed.
+    pub async fn stats(&self, connection_id: &Uuid) -> Option<LatencyStats> {
+        let window = Arc::clone(&self.probes.read().await.get(connection_id)?.window);
+        let stats = window.lock().await.stats(&connection_id.to_string());
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(samples: &[Option<u64>]) -> ProbeWindow {
+        let mut window = ProbeWindow::default();
+        for sample in samples {
+            window.push(*sample);
+        }
+        window
+    }
+
+    #[test]
+    fn test_stats_and_quality() {
+        let stats = window(&[]).stats("c");
+        assert_eq!(stats.quality, ConnectionQuality::Unknown);
+        assert_eq!(stats.avg_us, None);
+
+        let stats = window(&[Some(300), Some(100), Some(200)]).stats("c");
+        assert_eq!(stats.quality, ConnectionQuality::Good);
+        assert_eq!(
+            (stats.min_us, stats.avg_us, stats.p99_us, stats.last_us),
+            (Some(100), Some(200), Some(300), Some(200))
+        );
+
+        let mut samples = vec![Some(1_000); 18];
+        samples.extend([None, Some(1_000)]);
+        let stats = window(&samples).stats("c");
+        assert!((stats.loss_percent - 5.0).abs() < f64::EPSILON);
+        assert_eq!(stats.quality, ConnectionQuality::Poor);
+
+        let stats = window(&[Some(1_000), Some(100_000)]).stats("c");
+        assert_eq!(stats.quality, ConnectionQuality::Fair);
+
+        let stats = window(&[Some(1_000), None, None, None]).stats("c");
+        assert_eq!(stats.quality, ConnectionQuality::Down);
+    }
+
+    #[test]
+    fn test_window_is_bounded() {
+        let samples: Vec<_> = (0..WINDOW as u64 + 10).map(Some).collect();
+        let stats = window(&samples).stats("c");
+        assert_eq!(stats.samples, WINDOW as u64);
+        assert_eq!(stats.min_us, Some(10));
+    }
+
+    #[test]
+    fn test_probe_interval_is_clamped() {
+        assert_eq!(probe_interval(None), Duration::from_secs(1));
+        assert_eq!(
+            probe_interval(Some(1)),
+            Duration::from_millis(MIN_INTERVAL_MS)
+        );
+        assert_eq!(
+            probe_interval(Some(u64::MAX)),
+            Duration::from_millis(MAX_INTERVAL_MS)
+        );
+    }
+}
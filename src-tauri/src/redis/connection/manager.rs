@@ -4,15 +4,24 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
-use deadpool_redis::{Config, Pool, Runtime};
+use deadpool_redis::{Config, Hook, Pool, Runtime};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use super::model::{ConnectionProfile, ConnectionState, ServerInfoSummary};
+use super::model::{
+    CommandPolicy, ConfigDrift, ConnectionProfile, ConnectionState, ServerCapabilities,
+    ServerInfoSummary, WriteConcern,
+};
 use super::ssh_tunnel::SshTunnel;
-use super::uri::build_connection_url;
+use super::throttle::{self, Throttle};
+use super::uri::{build_connection_url, pool_connection_url};
+use super::{baseline, capabilities, write_guard};
+use crate::redis::cli::command_docs::CommandCatalog;
 use crate::utils::errors::AppError;
 
+/// Upper bound on connections in a per-database override pool.
+const DB_OVERRIDE_POOL_SIZE: u32 = 2;
+
 /// Holds all active connections, keyed by profile ID.
 pub struct ConnectionManager {
     connections: Arc<RwLock<HashMap<Uuid, ActiveConnection>>>,
@@ -20,7 +29,6 @@ pub struct ConnectionManager {
 
 /// A single active connection with its pool and metadata.
 struct ActiveConnection {
-    #[allow(dead_code)]
     pub profile: ConnectionProfile,
     pub pool: Pool,
     pub state: ConnectionState,
@@ -28,6 +36,22 @@ struct ActiveConnection {
     pub connected_at: chrono::DateTime<chrono::Utc>,
     /// SSH tunnel, if one was established for this connection.
     pub tunnel: Option<SshTunnel>,
+    /// Capabilities probed at connect time.
+    pub capabilities: ServerCapabilities,
+    /// Commands the server reported at connect time, for CLI help and validation.
+    pub commands: Arc<CommandCatalog>,
+    /// Deviations from the profile's baseline found at connect time.
+    pub drift: Vec<ConfigDrift>,
+    /// Small pools bound to other databases, created on first use.
+    pub db_pools: HashMap<u8, Pool>,
+    /// Replicas writes wait for; kept across reconnects.
+    pub write_concern: Option<WriteConcern>,
+    /// Single-connection pools, by database, that writes use while a write
+    /// concern is set, so WAIT runs on the client that wrote.
+    pub pinned_pools: HashMap<u8, Pool>,
+    /// Rate limit and circuit breaker shared by all of the above pools; kept
+    /// across reconnects.
+    pub throttle: Arc<Throttle>,
 }
 
 impl Default for ConnectionManager {
@@ -61,118 +85,167 @@ impl ConnectionManager {
             .ok_or_else(|| AppError::Connection("Not connected".into()))
     }
 
-    /// Establish a connection for the given profile.
+    /// Get a pool handle for a mutating operation.
     ///
-    /// Creates a deadpool-redis pool, verifies connectivity with PING,
-    /// retrieves server INFO, and stores the active connection.
-    pub async fn connect(&self, profile: ConnectionProfile) -> Result<ServerInfoSummary, AppError> {
-        let id = profile.id;
+    /// Fails with `PermissionDenied` if the profile is marked read-only.
+    /// While a write concern is set, this is a single-connection pool.
+    pub async fn get_write_pool(&self, id: &Uuid) -> Result<Pool, AppError> {
+        self.pool_for_db(id, None, true).await
+    }
 
-        // Disconnect existing connection for this profile if any
-        self.disconnect(&id).await;
+    /// Get a pool whose connections are bound to database `db`.
+    ///
+    /// `None` or the profile's own database returns the main pool. Other
+    /// databases get a small dedicated pool, created on first use and kept
+    /// until disconnect, so callers can switch databases without reconnecting.
+    pub async fn get_pool_for_db(&self, id: &Uuid, db: Option<u8>) -> Result<Pool, AppError> {
+        self.pool_for_db(id, db, false).await
+    }
 
-        // Establish SSH tunnel if configured
-        let (effective_profile, tunnel) = if let Some(ref ssh) = profile.ssh {
-            if ssh.enabled {
-                let tunnel =
-                    super::ssh_tunnel::establish_tunnel(ssh, &profile.host, profile.port).await?;
-                let mut tunneled = profile.clone();
-                tunneled.host = "127.0.0.1".to_string();
-                tunneled.port = tunnel.local_port;
-                (tunneled, Some(tunnel))
-            } else {
-                (profile.clone(), None)
+    /// Like [`Self::get_pool_for_db`], but for a mutating operation.
+    ///
+    /// Fails with `PermissionDenied` if the profile is marked read-only.
+    pub async fn get_write_pool_for_db(&self, id: &Uuid, db: Option<u8>) -> Result<Pool, AppError> {
+        self.pool_for_db(id, db, true).await
+    }
+
+    async fn pool_for_db(&self, id: &Uuid, db: Option<u8>, write: bool) -> Result<Pool, AppError> {
+        let mut conns = self.connections.write().await;
+        let active = conns
+            .get_mut(id)
+            .ok_or_else(|| AppError::Connection("Not connected".into()))?;
+        if write && active.profile.readonly {
+            return Err(write_guard::readonly_error());
+        }
+
+        if write && active.write_concern.is_some() {
+            let db = db.unwrap_or(active.profile.database);
+            if let Some(pool) = active.pinned_pools.get(&db) {
+                return Ok(pool.clone());
             }
-        } else {
-            (profile.clone(), None)
+            let pool = create_pool(&override_profile(active, db, 1), &active.throttle)?;
+            active.pinned_pools.insert(db, pool.clone());
+            tracing::debug!(id = %id, db = db, "Created pinned write pool");
+            return Ok(pool);
+        }
+
+        let db = match db {
+            Some(db) if db != active.profile.database => db,
+            _ => return Ok(active.pool.clone()),
         };
+        if let Some(pool) = active.db_pools.get(&db) {
+            return Ok(pool.clone());
+        }
 
-        let pool = create_pool(&effective_profile)?;
+        let pool = create_pool(
+            &override_profile(active, db, DB_OVERRIDE_POOL_SIZE),
+            &active.throttle,
+        )?;
+        active.db_pools.insert(db, pool.clone());
+        tracing::debug!(id = %id, db = db, "Created database override pool");
+        Ok(pool)
+    }
 
-        // Verify the connection works by sending PING
-        let mut conn = pool.get().await.map_err(|e| {
-            AppError::Connection(format!("Failed to get connection from pool: {e}"))
-        })?;
+    /// Make editor and bulk writes wait for `concern` (WAIT) and report the
+    /// acknowledgements, or stop waiting with `None`.
+    pub async fn set_write_concern(
+        &self,
+        id: &Uuid,
+        concern: Option<WriteConcern>,
+    ) -> Result<(), AppError> {
+        let mut conns = self.connections.write().await;
+        let active = conns
+            .get_mut(id)
+            .ok_or_else(|| AppError::Connection("Not connected".into()))?;
+        active.write_concern = concern;
+        if concern.is_none() {
+            active.pinned_pools.clear();
+        }
+        Ok(())
+    }
 
-        let pong: String = redis::cmd("PING")
-            .query_async(&mut conn)
-            .await
-            .map_err(|e| AppError::Connection(format!("PING failed: {e}")))?;
+    /// The write concern set on a connection, if any.
+    pub async fn write_concern(&self, id: &Uuid) -> Option<WriteConcern> {
+        let conns = self.connections.read().await;
+        conns.get(id).and_then(|c| c.write_concern)
+    }
 
-        if pong != "PONG" {
-            return Err(AppError::Connection(format!(
-                "Unexpected PING response: {pong}"
-            )));
-        }
+    /// Get the profile an active connection was established with.
+    pub async fn get_profile(&self, id: &Uuid) -> Result<ConnectionProfile, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| c.profile.clone())
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
 
-        // Fetch server info
-        let info_raw: String = redis::cmd("INFO")
-            .arg("server")
-            .query_async(&mut conn)
-            .await
-            .map_err(|e| AppError::Redis(format!("INFO command failed: {e}")))?;
-
-        let server_info = parse_server_info(&info_raw);
-
-        // Get DB size
-        let dbsize: u64 = redis::cmd("DBSIZE")
-            .query_async(&mut conn)
-            .await
-            .unwrap_or(0);
-
-        let summary = ServerInfoSummary {
-            redis_version: server_info
-                .get("redis_version")
-                .cloned()
-                .unwrap_or_else(|| "unknown".into()),
-            mode: server_info
-                .get("redis_mode")
-                .cloned()
-                .unwrap_or_else(|| "standalone".into()),
-            os: server_info
-                .get("os")
-                .cloned()
-                .unwrap_or_else(|| "unknown".into()),
-            uptime_in_seconds: server_info
-                .get("uptime_in_seconds")
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(0),
-            connected_clients: 0, // Will be enriched from INFO clients
-            used_memory_human: "unknown".into(),
-            db_size: dbsize,
-        };
+    /// Whether the connection's profile is marked read-only.
+    pub async fn is_readonly(&self, id: &Uuid) -> Result<bool, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| c.profile.readonly)
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
 
-        // Fetch memory + client info
-        let info_all: String = redis::cmd("INFO")
-            .arg("all")
-            .query_async(&mut conn)
-            .await
-            .unwrap_or_default();
-        let all_info = parse_server_info(&info_all);
-
-        let summary = ServerInfoSummary {
-            connected_clients: all_info
-                .get("connected_clients")
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(0),
-            used_memory_human: all_info
-                .get("used_memory_human")
-                .cloned()
-                .unwrap_or_else(|| "unknown".into()),
-            ..summary
-        };
+    /// The connection profile's command policy.
+    pub async fn get_command_policy(&self, id: &Uuid) -> Result<CommandPolicy, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| c.profile.command_policy.clone())
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
 
-        let state = ConnectionState::Connected {
-            server_info: summary.clone(),
-        };
+    /// Get the server capabilities probed when the connection was established.
+    pub async fn get_capabilities(&self, id: &Uuid) -> Result<ServerCapabilities, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| c.capabilities.clone())
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
 
-        let active = ActiveConnection {
-            profile,
-            pool,
-            state,
-            connected_at: chrono::Utc::now(),
-            tunnel,
-        };
+    /// Get the command catalog fetched when the connection was established.
+    pub async fn get_command_catalog(&self, id: &Uuid) -> Result<Arc<CommandCatalog>, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| c.commands.clone())
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
+
+    /// Get the rate limit and circuit breaker of an active connection.
+    pub async fn get_throttle(&self, id: &Uuid) -> Result<Arc<Throttle>, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| Arc::clone(&c.throttle))
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
+
+    /// Get the baseline drift detected when the connection was established.
+    pub async fn get_config_drift(&self, id: &Uuid) -> Result<Vec<ConfigDrift>, AppError> {
+        let conns = self.connections.read().await;
+        conns
+            .get(id)
+            .map(|c| c.drift.clone())
+            .ok_or_else(|| AppError::Connection("Not connected".into()))
+    }
+
+    /// Establish a connection for the given profile.
+    ///
+    /// Creates a deadpool-redis pool, verifies connectivity with PING,
+    /// retrieves server INFO, probes capabilities, checks the profile baseline,
+    /// and stores the active connection.
+    pub async fn connect(&self, profile: ConnectionProfile) -> Result<ServerInfoSummary, AppError> {
+        let id = profile.id;
+
+        // Disconnect existing connection for this profile if any
+        self.disconnect(&id).await;
+
+        let throttle = Arc::new(Throttle::new(&profile.throttle));
+        let (active, summary) = establish(profile, throttle).await?;
 
         {
             let mut conns = self.connections.write().await;
@@ -183,6 +256,54 @@ impl ConnectionManager {
         Ok(summary)
     }
 
+    /// Rebuild the pool (and SSH tunnel) of a connection whose server went away.
+    ///
+    /// The entry keeps its current state until the new connection succeeds.
+    /// Fails if the connection was removed while reconnecting.
+    pub async fn reconnect(&self, id: &Uuid) -> Result<ServerInfoSummary, AppError> {
+        let (profile, throttle) = {
+            let mut conns = self.connections.write().await;
+            let active = conns
+                .get_mut(id)
+                .ok_or_else(|| AppError::Connection("Not connected".into()))?;
+            if let Some(tunnel) = active.tunnel.take() {
+                tunnel.shutdown();
+            }
+            (active.profile.clone(), Arc::clone(&active.throttle))
+        };
+
+        let (mut active, summary) = establish(profile, throttle).await?;
+
+        let mut conns = self.connections.write().await;
+        if let Some(slot) = conns.get_mut(id) {
+            active.write_concern = slot.write_concern;
+            *slot = active;
+            tracing::info!(id = %id, "Connection re-established");
+            Ok(summary)
+        } else {
+            if let Some(tunnel) = active.tunnel {
+                tunnel.shutdown();
+            }
+            Err(AppError::Connection(
+                "Disconnected while reconnecting".into(),
+            ))
+        }
+    }
+
+    /// Update the state of an active connection.
+    ///
+    /// Returns `false` if the connection no longer exists.
+    pub async fn set_state(&self, id: &Uuid, state: ConnectionState) -> bool {
+        let mut conns = self.connections.write().await;
+        match conns.get_mut(id) {
+            Some(active) => {
+                active.state = state;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Get the connection URL for a connected profile (used by `PubSub` for dedicated connections).
     ///
     /// When an SSH tunnel is active, returns a URL pointing at the local tunnel
@@ -235,28 +356,170 @@ impl ConnectionManager {
     }
 }
 
+/// Open a tunnel (if configured) and pool for `profile`, and verify the server.
+async fn establish(
+    profile: ConnectionProfile,
+    throttle: Arc<Throttle>,
+) -> Result<(ActiveConnection, ServerInfoSummary), AppError> {
+    // Establish SSH tunnel if configured
+    let (effective_profile, tunnel) = if let Some(ref ssh) = profile.ssh {
+        if ssh.enabled {
+            let tunnel =
+                super::ssh_tunnel::establish_tunnel(ssh, &profile.host, profile.port).await?;
+            let mut tunneled = profile.clone();
+            tunneled.host = "127.0.0.1".to_string();
+            tunneled.port = tunnel.local_port;
+            (tunneled, Some(tunnel))
+        } else {
+            (profile.clone(), None)
+        }
+    } else {
+        (profile.clone(), None)
+    };
+
+    let pool = create_pool(&effective_profile, &throttle)?;
+
+    // Verify the connection works by sending PING
+    let mut conn = pool
+        .get()
+        .await
+        .map_err(|e| AppError::Connection(format!("Failed to get connection from pool: {e}")))?;
+
+    let pong: String = redis::cmd("PING")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Connection(format!("PING failed: {e}")))?;
+
+    if pong != "PONG" {
+        return Err(AppError::Connection(format!(
+            "Unexpected PING response: {pong}"
+        )));
+    }
+
+    // Fetch server info
+    let info_raw: String = redis::cmd("INFO")
+        .arg("server")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INFO command failed: {e}")))?;
+
+    let mut server_info = parse_server_info(&info_raw);
+
+    // Get DB size
+    let dbsize: u64 = redis::cmd("DBSIZE")
+        .query_async(&mut conn)
+        .await
+        .unwrap_or(0);
+
+    // Enrich with memory + client info
+    let info_all: String = redis::cmd("INFO")
+        .arg("all")
+        .query_async(&mut conn)
+        .await
+        .unwrap_or_default();
+    server_info.extend(parse_server_info(&info_all));
+
+    let summary = summary_from_info(&server_info, dbsize);
+
+    let capabilities = capabilities::probe(&mut conn, &summary.redis_version).await;
+    let commands = CommandCatalog::fetch(&mut conn).await;
+    let drift =
+        baseline::check_drift(&mut conn, profile.baseline.as_ref(), &summary.redis_version).await;
+    drop(conn);
+
+    let state = ConnectionState::Connected {
+        server_info: summary.clone(),
+    };
+
+    let active = ActiveConnection {
+        profile,
+        pool,
+        state,
+        connected_at: chrono::Utc::now(),
+        tunnel,
+        capabilities,
+        commands: Arc::new(commands),
+        drift,
+        db_pools: HashMap::new(),
+        write_concern: None,
+        pinned_pools: HashMap::new(),
+        throttle,
+    };
+    Ok((active, summary))
+}
+
+/// The active connection's profile bound to `db` (through its tunnel, if
+/// any) with at most `max_size` pooled connections.
+fn override_profile(active: &ActiveConnection, db: u8, max_size: u32) -> ConnectionProfile {
+    let mut profile = active.profile.clone();
+    if let Some(ref tunnel) = active.tunnel {
+        profile.host = "127.0.0.1".to_string();
+        profile.port = tunnel.local_port;
+    }
+    profile.database = db;
+    profile.pool.max_size = profile.pool.max_size.min(max_size);
+    profile
+}
+
 /// Create a deadpool-redis pool from a connection profile.
-fn create_pool(profile: &ConnectionProfile) -> Result<Pool, AppError> {
-    let url = build_connection_url(profile);
+///
+/// Checkouts wait for `throttle`'s rate limit, and new connections for room
+/// under its concurrency limit, which all of the connection's pools share.
+fn create_pool(profile: &ConnectionProfile, throttle: &Arc<Throttle>) -> Result<Pool, AppError> {
+    let url = pool_connection_url(profile);
+    let wait_timeout = Duration::from_secs(profile.pool.connection_timeout_secs);
 
     let cfg = Config::from_url(url);
 
-    let pool = cfg
+    let mut builder = cfg
         .builder()
         .map_err(|e| AppError::Pool(format!("Failed to create pool builder: {e}")))?
         .max_size(profile.pool.max_size as usize)
-        .wait_timeout(Some(Duration::from_secs(
-            profile.pool.connection_timeout_secs,
-        )))
+        .wait_timeout(Some(wait_timeout))
         .create_timeout(Some(Duration::from_secs(profile.timeout.connect_secs)))
         .recycle_timeout(Some(Duration::from_secs(5)))
         .runtime(Runtime::Tokio1)
+        .post_create(client_setup_hook(profile));
+    let slots = throttle.connection_slots();
+    if let Some(ref slots) = slots {
+        builder = builder.post_create(throttle::admission_hook(slots, wait_timeout));
+    }
+    let pool = builder
+        .post_create(throttle::checkout_hook(throttle))
+        .pre_recycle(throttle::checkout_hook(throttle))
         .build()
         .map_err(|e| AppError::Pool(format!("Failed to build pool: {e}")))?;
+    if let Some(slots) = slots {
+        slots.attach(&pool);
+    }
 
     Ok(pool)
 }
 
+/// Applies the profile's command timeout and CLIENT options to each new
+/// pooled connection.
+///
+/// Best effort: servers too old for NO-TOUCH/NO-EVICT, or ACLs that deny
+/// CLIENT, only log a warning instead of making the pool unusable.
+fn client_setup_hook(profile: &ConnectionProfile) -> Hook {
+    let commands = Arc::new(profile.client.setup_commands());
+    let timeout = profile.timeout.command_timeout();
+    Hook::async_fn(move |conn, _| {
+        let commands = Arc::clone(&commands);
+        Box::pin(async move {
+            if let Some(timeout) = timeout {
+                conn.set_response_timeout(timeout);
+            }
+            for cmd in commands.iter() {
+                if let Err(e) = cmd.query_async::<()>(conn).await {
+                    tracing::warn!(error = %e, "Client setup command failed");
+                }
+            }
+            Ok(())
+        })
+    })
+}
+
 /// Parse Redis INFO output into a key-value map.
 ///
 /// INFO output format is:
@@ -301,7 +564,7 @@ pub async fn test_connection(profile: &ConnectionProfile) -> Result<ServerInfoSu
     };
     // _tunnel is dropped (and shut down) at the end of this function
 
-    let url = build_connection_url(&effective_profile);
+    let url = pool_connection_url(&effective_profile);
 
     let client = redis::Client::open(url)
         .map_err(|e| AppError::Connection(format!("Failed to create client: {e}")))?;
@@ -342,7 +605,12 @@ pub async fn test_connection(profile: &ConnectionProfile) -> Result<ServerInfoSu
         .await
         .unwrap_or(0);
 
-    Ok(ServerInfoSummary {
+    Ok(summary_from_info(&info, dbsize))
+}
+
+/// Build a [`ServerInfoSummary`] from parsed INFO fields.
+fn summary_from_info(info: &HashMap<String, String>, db_size: u64) -> ServerInfoSummary {
+    ServerInfoSummary {
         redis_version: info
             .get("redis_version")
             .cloned()
@@ -364,8 +632,8 @@ pub async fn test_connection(profile: &ConnectionProfile) -> Result<ServerInfoSu
             .get("used_memory_human")
             .cloned()
             .unwrap_or_else(|| "unknown".into()),
-        db_size: dbsize,
-    })
+        db_size,
+    }
 }
 
 #[cfg(test)]
@@ -397,10 +665,22 @@ mod tests {
         assert_eq!(info.get("os").unwrap(), "Linux 5.15.0-1:custom");
     }
 
+    #[test]
+    fn test_summary_from_info_defaults() {
+        let info = parse_server_info("redis_version:7.2.0\nconnected_clients:3\n");
+        let summary = summary_from_info(&info, 42);
+        assert_eq!(summary.redis_version, "7.2.0");
+        assert_eq!(summary.mode, "standalone");
+        assert_eq!(summary.connected_clients, 3);
+        assert_eq!(summary.used_memory_human, "unknown");
+        assert_eq!(summary.db_size, 42);
+    }
+
     #[test]
     fn test_create_pool_from_profile() {
         let profile = ConnectionProfile::new_standalone("test".into(), "localhost".into(), 6379);
-        let pool = create_pool(&profile);
+        let throttle = Arc::new(Throttle::new(&profile.throttle));
+        let pool = create_pool(&profile, &throttle);
         assert!(pool.is_ok());
     }
 
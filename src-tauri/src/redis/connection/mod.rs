@@ -1,6 +1,18 @@
 // SPDX-License-Identifier: MIT
 
+pub mod baseline;
+pub mod capabilities;
+pub mod command_policy;
+pub mod databases;
+pub mod durability;
+pub mod latency_probe;
 pub mod manager;
 pub mod model;
+pub mod portable;
+pub mod push;
 pub mod ssh_tunnel;
+pub mod supervisor;
+pub mod throttle;
+pub mod timeout;
 pub mod uri;
+pub mod write_guard;
@@ -1,8 +1,15 @@
 // SPDX-License-Identifier: MIT
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::redis::backup::model::BackupSchedule;
+use crate::redis::browser::model::RedisKeyType;
+use crate::redis::monitor::model::{AlertRule, MetricThresholds};
+use crate::utils::errors::AppError;
+
 /// Top-level connection profile persisted to disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,11 +29,76 @@ pub struct ConnectionProfile {
     pub ssh: Option<SshConfig>,
     pub pool: PoolConfig,
     pub timeout: TimeoutConfig,
+    /// Limits that keep the app's own traffic from overloading the server.
+    #[serde(default)]
+    pub throttle: ThrottleConfig,
+    /// CLIENT settings applied to every pooled connection.
+    #[serde(default)]
+    pub client: ClientOptions,
+    /// Wire protocol negotiated with HELLO; RESP3 needs Redis 6+.
+    #[serde(default)]
+    pub protocol: RespProtocol,
     pub readonly: bool,
+    /// Expected server configuration; deviations are reported as drift on connect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<ConfigBaseline>,
+    /// Overrides of the built-in dangerous command table for this connection.
+    #[serde(default, skip_serializing_if = "CommandPolicy::is_empty")]
+    pub command_policy: CommandPolicy,
+    /// Scheduled DUMP-based backups, started when the profile connects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<BackupSchedule>,
+    /// Overrides the app-wide metric thresholds for this connection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric_thresholds: Option<MetricThresholds>,
+    /// Alert rules evaluated while the monitor is polling this connection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alert_rules: Vec<AlertRule>,
+    /// Folder the profile is listed under; `None` for the top level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<Uuid>,
+    /// Position within its group (ascending).
+    #[serde(default)]
+    pub sort_order: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Saved key browser filters for this connection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scan_presets: Vec<ScanPreset>,
+    /// Preset the key browser opens with; a `*` scan when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_scan_preset: Option<Uuid>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A folder in the connection list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionGroup {
+    pub id: Uuid,
+    pub name: String,
+    /// Position among groups (ascending).
+    pub sort_order: u32,
+}
+
+/// A saved key browser filter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanPreset {
+    pub id: Uuid,
+    pub name: String,
+    /// SCAN MATCH pattern, e.g. `orders:*`.
+    pub pattern: String,
+    /// SCAN COUNT hint.
+    pub count: u32,
+    /// Separator the key tree splits names on.
+    pub delimiter: String,
+    /// Only keys of this type; every type when unset.
+    #[serde(default)]
+    pub key_type: Option<RedisKeyType>,
+}
+
 /// Connection topology mode.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -57,6 +129,9 @@ pub struct SshConfig {
     pub username: String,
     pub auth: SshAuth,
     pub local_port: Option<u16>,
+    /// redis-server log file on the SSH host, for log tailing.
+    #[serde(default)]
+    pub log_path: Option<String>,
 }
 
 /// SSH authentication method.
@@ -84,7 +159,8 @@ pub struct PoolConfig {
     pub connection_timeout_secs: u64,
 }
 
-/// Per-operation timeout configuration.
+/// Per-operation timeout configuration. A read or write limit of 0 means
+/// no limit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeoutConfig {
@@ -93,6 +169,72 @@ pub struct TimeoutConfig {
     pub write_secs: u64,
 }
 
+/// Per-connection throttle and circuit breaker. A limit of 0 means no limit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleConfig {
+    /// Commands per second across the connection's pools.
+    pub max_commands_per_sec: u32,
+    /// Connections open at once across all of the connection's pools, i.e.
+    /// concurrent commands and pipelines.
+    pub max_concurrent: u32,
+    /// Health-check round trip above which background polling and scans
+    /// pause until the server answers faster again.
+    pub breaker_latency_ms: u64,
+}
+
+/// How the app's own connections present themselves to the server.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOptions {
+    /// CLIENT SETNAME `redislens-<version>`, so the app's connections are
+    /// easy to spot in CLIENT LIST.
+    pub set_name: bool,
+    /// CLIENT NO-TOUCH (Redis 7.2+): reads don't update LRU/LFU stats, so
+    /// browsing doesn't change what gets evicted.
+    pub no_touch: bool,
+    /// CLIENT NO-EVICT (Redis 7.0+): exempt from client eviction.
+    pub no_evict: bool,
+}
+
+impl ClientOptions {
+    /// Name given to pooled connections when `set_name` is on.
+    pub fn client_name() -> String {
+        format!("redislens-{}", env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Commands to run on each new connection.
+    pub fn setup_commands(&self) -> Vec<redis::Cmd> {
+        let mut cmds = Vec::new();
+        if self.set_name {
+            cmds.push(
+                redis::cmd("CLIENT")
+                    .arg("SETNAME")
+                    .arg(Self::client_name())
+                    .clone(),
+            );
+        }
+        if self.no_touch {
+            cmds.push(redis::cmd("CLIENT").arg("NO-TOUCH").arg("ON").clone());
+        }
+        if self.no_evict {
+            cmds.push(redis::cmd("CLIENT").arg("NO-EVICT").arg("ON").clone());
+        }
+        cmds
+    }
+}
+
+/// RESP version spoken with the server.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RespProtocol {
+    #[default]
+    Resp2,
+    /// Typed replies (maps, sets, doubles, booleans) and out-of-band push
+    /// messages such as client-side caching invalidations.
+    Resp3,
+}
+
 /// State of a connection at any point in time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "status", rename_all = "snake_case")]
@@ -103,6 +245,14 @@ pub enum ConnectionState {
     Error { message: String, retry_count: u32 },
 }
 
+/// Payload of the `connection:state-changed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionStateEvent {
+    pub connection_id: String,
+    pub state: ConnectionState,
+}
+
 /// Summary of Redis server info returned after a successful connection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -116,6 +266,166 @@ pub struct ServerInfoSummary {
     pub db_size: u64,
 }
 
+/// Expected server configuration for a profile.
+///
+/// Every field is optional; only the ones set are checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBaseline {
+    /// Minimum accepted `redis_version` (inclusive), e.g. `"7.0"`.
+    pub min_version: Option<String>,
+    /// Maximum accepted `redis_version` (inclusive), e.g. `"7.2.99"`.
+    pub max_version: Option<String>,
+    pub maxmemory_policy: Option<String>,
+    pub appendonly: Option<bool>,
+}
+
+/// Which commands a connection blocks, confirms or runs freely.
+///
+/// Entries are a command name, optionally followed by a subcommand
+/// (`KEYS`, `CONFIG SET`), matched case-insensitively. A blocked command is
+/// always refused; an allowed one runs without confirmation even if the
+/// built-in table calls it dangerous; a confirmed one needs confirmation
+/// even if the table doesn't. Block wins over allow, allow over confirm.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandPolicy {
+    #[serde(default)]
+    pub block: Vec<String>,
+    #[serde(default)]
+    pub confirm: Vec<String>,
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl CommandPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.block.is_empty() && self.confirm.is_empty() && self.allow.is_empty()
+    }
+}
+
+/// A single deviation between a profile's baseline and the live server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDrift {
+    pub parameter: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Payload of the `connection:drift` event emitted after connecting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDriftEvent {
+    pub connection_id: String,
+    pub drift: Vec<ConfigDrift>,
+}
+
+/// Most replicas a write concern may ask for.
+const MAX_WRITE_CONCERN_REPLICAS: u32 = 64;
+/// Longest WAIT a write concern may ask for.
+const MAX_WRITE_CONCERN_TIMEOUT_MS: u64 = 60_000;
+
+/// Replicas a connection's editor and bulk writes wait for (WAIT) before
+/// they are reported as done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteConcern {
+    pub replicas: u32,
+    /// How long WAIT blocks at most. Zero (block forever) is not allowed.
+    pub timeout_ms: u64,
+}
+
+impl WriteConcern {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.replicas == 0 || self.replicas > MAX_WRITE_CONCERN_REPLICAS {
+            return Err(AppError::InvalidInput(format!(
+                "Replicas must be between 1 and {MAX_WRITE_CONCERN_REPLICAS}"
+            )));
+        }
+        if self.timeout_ms == 0 || self.timeout_ms > MAX_WRITE_CONCERN_TIMEOUT_MS {
+            return Err(AppError::InvalidInput(format!(
+                "WAIT timeout must be between 1 and {MAX_WRITE_CONCERN_TIMEOUT_MS} ms"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of the `connection:write-ack` event, emitted after a write made
+/// under a write concern.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteAck {
+    pub connection_id: String,
+    /// Operation name as recorded in the audit log.
+    pub operation: String,
+    pub key: Option<String>,
+    pub requested: u32,
+    /// Replicas that acknowledged the write before the timeout.
+    pub acknowledged: u32,
+    pub elapsed_ms: f64,
+    /// Set when WAIT itself failed; `acknowledged` is then 0.
+    pub error: Option<String>,
+}
+
+/// Server capabilities detected when a connection is established.
+///
+/// Lets the frontend and backend gate module- and version-specific features
+/// (JSON, Search, `TimeSeries`, ACL, Functions) up front instead of failing at call time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ServerCapabilities {
+    pub redis_version: String,
+    pub command_count: u64,
+    pub modules: Vec<ModuleInfo>,
+    pub json: bool,
+    pub search: bool,
+    pub time_series: bool,
+    pub acl: bool,
+    pub functions: bool,
+}
+
+/// A loaded server module as reported by MODULE LIST.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleInfo {
+    pub name: String,
+    pub version: i64,
+}
+
+/// Keyspace summary for one logical database, as listed by the quick switcher.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSummary {
+    pub index: u8,
+    pub keys: u64,
+    pub expires: u64,
+    pub avg_ttl: u64,
+    /// Whether this is the database the profile connects to.
+    pub active: bool,
+}
+
+/// Outcome of importing connection profiles.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileImportResult {
+    /// Profiles saved, with freshly assigned IDs.
+    pub imported: Vec<ConnectionProfile>,
+    pub skipped: Vec<SkippedProfile>,
+    /// Non-fatal notes, e.g. secrets that could not be carried over.
+    pub warnings: Vec<String>,
+}
+
+/// An import entry that was not saved.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedProfile {
+    pub name: String,
+    pub reason: String,
+}
+
 impl Default for PoolConfig {
     fn default() -> Self {
         Self {
@@ -138,6 +448,24 @@ impl Default for TimeoutConfig {
     }
 }
 
+impl TimeoutConfig {
+    /// How long a read command may wait for its reply.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        (self.read_secs > 0).then_some(Duration::from_secs(self.read_secs))
+    }
+
+    /// How long a write command may wait for its reply.
+    pub fn write_timeout(&self) -> Option<Duration> {
+        (self.write_secs > 0).then_some(Duration::from_secs(self.write_secs))
+    }
+
+    /// The limit for a connection shared by reads and writes: the longer of
+    /// the two, or none if either is unlimited.
+    pub fn command_timeout(&self) -> Option<Duration> {
+        Some(self.read_timeout()?.max(self.write_timeout()?))
+    }
+}
+
 impl ConnectionProfile {
     /// Create a new profile with defaults for a standalone Redis server.
     pub fn new_standalone(name: String, host: String, port: u16) -> Self {
@@ -156,7 +484,20 @@ impl ConnectionProfile {
             ssh: None,
             pool: PoolConfig::default(),
             timeout: TimeoutConfig::default(),
+            throttle: ThrottleConfig::default(),
+            client: ClientOptions::default(),
+            protocol: RespProtocol::default(),
             readonly: false,
+            baseline: None,
+            command_policy: CommandPolicy::default(),
+            backup: None,
+            metric_thresholds: None,
+            alert_rules: Vec::new(),
+            group_id: None,
+            sort_order: 0,
+            tags: Vec::new(),
+            scan_presets: Vec::new(),
+            default_scan_preset: None,
             created_at: now,
             updated_at: now,
         }
@@ -179,6 +520,46 @@ mod tests {
         assert!(profile.ssh.is_none());
     }
 
+    #[test]
+    fn test_client_setup_commands() {
+        assert!(ClientOptions::default().setup_commands().is_empty());
+
+        let options = ClientOptions {
+            set_name: true,
+            no_touch: true,
+            no_evict: false,
+        };
+        let packed: Vec<Vec<u8>> = options
+            .setup_commands()
+            .iter()
+            .map(redis::Cmd::get_packed_command)
+            .collect();
+        assert_eq!(
+            packed,
+            [
+                redis::cmd("CLIENT")
+                    .arg("SETNAME")
+                    .arg(format!("redislens-{}", env!("CARGO_PKG_VERSION")))
+                    .get_packed_command(),
+                redis::cmd("CLIENT")
+                    .arg("NO-TOUCH")
+                    .arg("ON")
+                    .get_packed_command(),
+            ]
+        );
+
+        // Profiles saved before the option existed keep the old behaviour.
+        let mut json = serde_json::to_value(ConnectionProfile::new_standalone(
+            "old".into(),
+            "localhost".into(),
+            6379,
+        ))
+        .unwrap();
+        json.as_object_mut().unwrap().remove("client");
+        let profile: ConnectionProfile = serde_json::from_value(json).unwrap();
+        assert_eq!(profile.client, ClientOptions::default());
+    }
+
     #[test]
     fn test_profile_serialization_roundtrip() {
         let profile = ConnectionProfile::new_standalone("dev".into(), "127.0.0.1".into(), 6379);
@@ -213,4 +594,36 @@ mod tests {
         let json = serde_json::to_string(&profile).expect("serialize");
         assert!(!json.contains("password"));
     }
+
+    #[test]
+    fn test_write_concern_validation() {
+        let concern = |replicas, timeout_ms| WriteConcern {
+            replicas,
+            timeout_ms,
+        };
+        assert!(concern(1, 1_000).validate().is_ok());
+        assert!(concern(0, 1_000).validate().is_err());
+        assert!(concern(1, 0).validate().is_err());
+        assert!(concern(MAX_WRITE_CONCERN_REPLICAS + 1, 1_000)
+            .validate()
+            .is_err());
+        assert!(concern(2, MAX_WRITE_CONCERN_TIMEOUT_MS + 1)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_command_timeout_is_the_longer_limit() {
+        let config = |read_secs, write_secs| TimeoutConfig {
+            connect_secs: 5,
+            read_secs,
+            write_secs,
+        };
+        assert_eq!(
+            config(10, 30).command_timeout(),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(config(0, 30).read_timeout(), None);
+        assert_eq!(config(0, 30).command_timeout(), None);
+    }
 }
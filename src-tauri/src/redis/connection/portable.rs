@@ -0,0 +1,509 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::model::{
+    ConnectionProfile, ConnectionType, ProfileImportResult, SkippedProfile, SshAuth, SshConfig,
+};
+use super::uri::parse_redis_uri;
+use crate::config::profile_store;
+use crate::utils::crypto::{self, SealedData};
+use crate::utils::errors::AppError;
+
+/// Identifies a `RedisLens` profile export.
+const BUNDLE_FORMAT: &str = "redis-lens/profiles";
+const BUNDLE_VERSION: u32 = 1;
+
+/// Portable file written by `connection_export`.
+///
+/// Profiles never carry secrets in the clear; when a passphrase is given the
+/// secrets travel alongside them in `secrets`, encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub profiles: Vec<ConnectionProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<SealedData>,
+}
+
+/// Secrets removed from one profile on export.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct ProfileSecrets {
+    password: Option<String>,
+    ssh_password: Option<String>,
+    ssh_passphrase: Option<String>,
+}
+
+impl ProfileSecrets {
+    fn is_empty(&self) -> bool {
+        self.password.is_none() && self.ssh_password.is_none() && self.ssh_passphrase.is_none()
+    }
+}
+
+/// Remove every secret from a profile, returning what was removed.
+fn strip_secrets(profile: &mut ConnectionProfile) -> ProfileSecrets {
+    let mut secrets = ProfileSecrets {
+        password: profile.password.take(),
+        ..ProfileSecrets::default()
+    };
+    if let Some(ssh) = &mut profile.ssh {
+        match &mut ssh.auth {
+            SshAuth::Password { password } => {
+                secrets.ssh_password = Some(std::mem::take(password)).filter(|p| !p.is_empty());
+            }
+            SshAuth::PrivateKey { passphrase, .. } => secrets.ssh_passphrase = passphrase.take(),
+            SshAuth::Agent => {}
+        }
+    }
+    secrets
+}
+
+fn restore_secrets(profile: &mut ConnectionProfile, secrets: ProfileSecrets) {
+    profile.password = secrets.password;
+    if let Some(ssh) = &mut profile.ssh {
+        match &mut ssh.auth {
+            SshAuth::Password { password } => *password = secrets.ssh_password.unwrap_or_default(),
+            SshAuth::PrivateKey { passphrase, .. } => *passphrase = secrets.ssh_passphrase,
+            SshAuth::Agent => {}
+        }
+    }
+}
+
+/// Build an export bundle, encrypting secrets with `passphrase` or omitting them.
+pub fn export_profiles(
+    mut profiles: Vec<ConnectionProfile>,
+    passphrase: Option<&str>,
+) -> Result<ProfileBundle, AppError> {
+    let mut secrets = BTreeMap::new();
+    for profile in &mut profiles {
+        let removed = strip_secrets(profile);
+        if !removed.is_empty() {
+            secrets.insert(profile.id.to_string(), removed);
+        }
+    }
+
+    let secrets = match passphrase {
+        Some(passphrase) if !secrets.is_empty() => {
+            let plaintext = serde_json::to_vec(&secrets)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize secrets: {e}")))?;
+            Some(crypto::seal(passphrase, &plaintext)?)
+        }
+        _ => None,
+    };
+
+    Ok(ProfileBundle {
+        format: BUNDLE_FORMAT.into(),
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now(),
+        profiles,
+        secrets,
+    })
+}
+
+/// Profiles read from an import file, before duplicate detection.
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    pub profiles: Vec<ConnectionProfile>,
+    pub skipped: Vec<SkippedProfile>,
+    pub warnings: Vec<String>,
+}
+
+/// Parse an import file, detecting its format.
+///
+/// Accepted formats:
+/// - a `RedisLens` export (JSON object), secrets restored with `passphrase`
+/// - a `RedisInsight` database export (JSON array)
+/// - a list of `redis://` / `rediss://` URIs, one per line (`#` comments allowed)
+pub async fn parse_import(
+    content: &str,
+    passphrase: Option<&str>,
+) -> Result<ParsedImport, AppError> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') {
+        parse_bundle(trimmed, passphrase).await
+    } else if trimmed.starts_with('[') {
+        parse_redis_insight(trimmed)
+    } else {
+        Ok(parse_uri_list(content))
+    }
+}
+
+async fn parse_bundle(content: &str, passphrase: Option<&str>) -> Result<ParsedImport, AppError> {
+    let bundle: ProfileBundle = serde_json::from_str(content)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid profile export: {e}")))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "Unrecognized export format: {}",
+            bundle.format
+        )));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Export version {} is newer than this app supports",
+            bundle.version
+        )));
+    }
+
+    let mut parsed = ParsedImport::default();
+    for mut profile in bundle.profiles {
+        profile.tags = profile_store::normalize_tags(profile.tags);
+        match profile_store::validate_profile(&profile) {
+            Ok(()) => parsed.profiles.push(profile),
+            Err(e) => parsed.skipped.push(SkippedProfile {
+                name: profile.name,
+                reason: e.to_string(),
+            }),
+        }
+    }
+    match (bundle.secrets, passphrase) {
+        (Some(sealed), Some(passphrase)) => {
+            let plaintext = crypto::open(passphrase, &sealed).await?;
+            let mut secrets: BTreeMap<String, ProfileSecrets> = serde_json::from_slice(&plaintext)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid encrypted secrets: {e}")))?;
+            for profile in &mut parsed.profiles {
+                if let Some(s) = secrets.remove(&profile.id.to_string()) {
+                    restore_secrets(profile, s);
+                }
+            }
+        }
+        (Some(_), None) => parsed.warnings.push(
+            "Export contains encrypted secrets; no passphrase given, so they were not restored"
+                .into(),
+        ),
+        (None, _) => {}
+    }
+    Ok(parsed)
+}
+
+/// One entry of a `RedisInsight` "Export databases" file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InsightDatabase {
+    name: Option<String>,
+    host: String,
+    port: u16,
+    db: Option<u8>,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default)]
+    tls: bool,
+    connection_type: Option<String>,
+    #[serde(default)]
+    ssh: bool,
+    ssh_options: Option<InsightSshOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InsightSshOptions {
+    host: String,
+    port: u16,
+    username: String,
+    password: Option<String>,
+    private_key: Option<String>,
+}
+
+fn parse_redis_insight(content: &str) -> Result<ParsedImport, AppError> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(content)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid JSON array: {e}")))?;
+
+    let mut parsed = ParsedImport::default();
+    for (i, entry) in entries.into_iter().enumerate() {
+        let fallback = format!("entry {}", i + 1);
+        let db: InsightDatabase = match serde_json::from_value(entry) {
+            Ok(db) => db,
+            Err(e) => {
+                parsed.skipped.push(SkippedProfile {
+                    name: fallback,
+                    reason: format!("Not a RedisInsight database: {e}"),
+                });
+                continue;
+            }
+        };
+
+        let name = db.name.clone().unwrap_or(fallback);
+        let database = db.db.unwrap_or(0);
+        if database > 15 {
+            parsed.skipped.push(SkippedProfile {
+                name,
+                reason: format!("Database index must be 0-15, got {database}"),
+            });
+            continue;
+        }
+
+        let mut profile = ConnectionProfile::new_standalone(name.clone(), db.host, db.port);
+        profile.connection_type = match db.connection_type.as_deref() {
+            Some("CLUSTER") => ConnectionType::Cluster,
+            Some("SENTINEL") => ConnectionType::Sentinel,
+            _ => ConnectionType::Standalone,
+        };
+        profile.database = database;
+        profile.username = db.username.filter(|u| !u.is_empty() && u != "default");
+        profile.password = db.password.filter(|p| !p.is_empty());
+        profile.tls.enabled = db.tls;
+
+        if let Some(ssh) = db.ssh_options.filter(|_| db.ssh) {
+            let auth = if ssh.private_key.is_some() {
+                // Keys are embedded in the export; profiles reference key files.
+                parsed.warnings.push(format!(
+                    "{name}: embedded SSH private key not imported; using the SSH agent instead"
+                ));
+                SshAuth::Agent
+            } else {
+                SshAuth::Password {
+                    password: ssh.password.unwrap_or_default(),
+                }
+            };
+            profile.ssh = Some(SshConfig {
+                enabled: true,
+                host: ssh.host,
+                port: ssh.port,
+                username: ssh.username,
+                auth,
+                local_port: None,
+                log_path: None,
+            });
+        }
+        parsed.profiles.push(profile);
+    }
+    Ok(parsed)
+}
+
+fn parse_uri_list(content: &str) -> ParsedImport {
+    let mut parsed = ParsedImport::default();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_redis_uri(line) {
+            Ok(partial) => {
+                let name = format!("{}:{}/{}", partial.host, partial.port, partial.database);
+                let mut profile =
+                    ConnectionProfile::new_standalone(name, partial.host, partial.port);
+                profile.username = partial.username;
+                profile.password = partial.password;
+                profile.database = partial.database;
+                profile.tls.enabled = partial.tls_enabled;
+                parsed.profiles.push(profile);
+            }
+            Err(e) => parsed.skipped.push(SkippedProfile {
+                name: format!("line {}", i + 1),
+                reason: e.to_string(),
+            }),
+        }
+    }
+    parsed
+}
+
+/// Host, port and database, plus the SSH host and port when tunnelled, which
+/// identify the same server across tools.
+fn endpoint(profile: &ConnectionProfile) -> (String, u16, u8, Option<(String, u16)>) {
+    let tunnel = profile
+        .ssh
+        .as_ref()
+        .filter(|ssh| ssh.enabled)
+        .map(|ssh| (ssh.host.to_ascii_lowercase(), ssh.port));
+    (
+        profile.host.to_ascii_lowercase(),
+        profile.port,
+        profile.database,
+        tunnel,
+    )
+}
+
+/// Drop imports that duplicate an existing profile (or an earlier import)
+/// by endpoint, and give the rest fresh IDs.
+pub fn dedupe(existing: &[ConnectionProfile], parsed: ParsedImport) -> ProfileImportResult {
+    let mut seen: HashSet<_> = existing.iter().map(endpoint).collect();
+    let mut result = ProfileImportResult {
+        skipped: parsed.skipped,
+        warnings: parsed.warnings,
+        ..ProfileImportResult::default()
+    };
+
+    let now = chrono::Utc::now();
+    for mut profile in parsed.profiles {
+        if !seen.insert(endpoint(&profile)) {
+            result.skipped.push(SkippedProfile {
+                reason: format!(
+                    "Duplicate of an existing connection ({}:{}/{})",
+                    profile.host, profile.port, profile.database
+                ),
+                name: profile.name,
+            });
+            continue;
+        }
+        profile.id = Uuid::new_v4();
+        // Groups are local to the machine that exported them.
+        profile.group_id = None;
+        profile.created_at = now;
+        profile.updated_at = now;
+        result.imported.push(profile);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile_with_secrets() -> ConnectionProfile {
+        let mut profile = ConnectionProfile::new_standalone("prod".into(), "db.io".into(), 6379);
+        profile.password = Some("redis-pass".into());
+        profile.ssh = Some(SshConfig {
+            enabled: true,
+            host: "bastion".into(),
+            port: 22,
+            username: "ops".into(),
+            auth: SshAuth::Password {
+                password: "ssh-pass".into(),
+            },
+            local_port: None,
+            log_path: None,
+        });
+        profile
+    }
+
+    #[test]
+    fn test_export_without_passphrase_omits_secrets() {
+        let bundle = export_profiles(vec![profile_with_secrets()], None).unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("redis-pass"));
+        assert!(!json.contains("ssh-pass"));
+        assert!(bundle.secrets.is_none());
+    }
+
+    #[test]
+    fn test_strip_and_restore_secrets() {
+        let mut profile = profile_with_secrets();
+        let secrets = strip_secrets(&mut profile);
+        assert!(profile.password.is_none());
+        assert_eq!(secrets.password.as_deref(), Some("redis-pass"));
+        assert_eq!(secrets.ssh_password.as_deref(), Some("ssh-pass"));
+
+        restore_secrets(&mut profile, secrets);
+        assert_eq!(profile.password.as_deref(), Some("redis-pass"));
+        assert!(matches!(
+            &profile.ssh.as_ref().unwrap().auth,
+            SshAuth::Password { password } if password == "ssh-pass"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parse_bundle_without_passphrase_warns() {
+        let mut bundle = export_profiles(vec![profile_with_secrets()], None).unwrap();
+        bundle.secrets = Some(SealedData {
+            kdf: crypto::KdfParams::default(),
+            salt: String::new(),
+            nonce: String::new(),
+            ciphertext: String::new(),
+        });
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed = parse_import(&json, None).await.unwrap();
+        assert_eq!(parsed.profiles.len(), 1);
+        assert!(parsed.profiles[0].password.is_none());
+        assert_eq!(parsed.warnings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parse_redis_insight() {
+        let json = r#"[
+            {"name": "cache", "host": "10.0.0.5", "port": 6380, "db": 2,
+             "username": "default", "password": "pw", "tls": true,
+             "connectionType": "STANDALONE", "provider": "REDIS"},
+            {"name": "cluster", "host": "c1", "port": 7000, "connectionType": "CLUSTER",
+             "ssh": true, "sshOptions": {"host": "jump", "port": 22, "username": "me",
+             "privateKey": "-----BEGIN KEY-----"}},
+            {"name": "bad"}
+        ]"#;
+        let parsed = parse_import(json, None).await.unwrap();
+        assert_eq!(parsed.profiles.len(), 2);
+        assert_eq!(parsed.skipped.len(), 1);
+
+        let cache = &parsed.profiles[0];
+        assert_eq!((cache.port, cache.database), (6380, 2));
+        assert!(cache.username.is_none());
+        assert_eq!(cache.password.as_deref(), Some("pw"));
+        assert!(cache.tls.enabled);
+
+        let cluster = &parsed.profiles[1];
+        assert_eq!(cluster.connection_type, ConnectionType::Cluster);
+        assert!(matches!(cluster.ssh.as_ref().unwrap().auth, SshAuth::Agent));
+        assert_eq!(parsed.warnings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parse_uri_list() {
+        let text = "# staging\nredis://:pw@stage:6379/1\n\nrediss://prod.io\nhttp://nope\n";
+        let parsed = parse_import(text, None).await.unwrap();
+        assert_eq!(parsed.profiles.len(), 2);
+        assert_eq!(parsed.profiles[0].name, "stage:6379/1");
+        assert!(parsed.profiles[1].tls.enabled);
+        assert_eq!(parsed.skipped.len(), 1);
+        assert_eq!(parsed.skipped[0].name, "line 5");
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_by_endpoint() {
+        let existing = vec![ConnectionProfile::new_standalone(
+            "local".into(),
+            "LOCALHOST".into(),
+            6379,
+        )];
+        let parsed = parse_import(
+            "redis://localhost:6379/0\nredis://localhost:6379/1\nredis://localhost:6379/1",
+            None,
+        )
+        .await
+        .unwrap();
+        let result = dedupe(&existing, parsed);
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.imported[0].database, 1);
+        assert_eq!(result.skipped.len(), 2);
+    }
+
+    #[test]
+    fn test_dedupe_tells_tunnels_apart() {
+        let direct = ConnectionProfile::new_standalone("local".into(), "127.0.0.1".into(), 6379);
+        let mut tunnelled = direct.clone();
+        tunnelled.ssh = Some(SshConfig {
+            enabled: true,
+            host: "bastion".into(),
+            port: 22,
+            username: "me".into(),
+            auth: SshAuth::Agent,
+            local_port: None,
+            log_path: None,
+        });
+        let parsed = ParsedImport {
+            profiles: vec![tunnelled.clone(), tunnelled],
+            ..ParsedImport::default()
+        };
+        let result = dedupe(&[direct], parsed);
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.skipped.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parse_bundle_skips_invalid_profiles() {
+        let mut bad_db = profile_with_secrets();
+        bad_db.name = "bad db".into();
+        bad_db.database = 16;
+        let mut no_host = profile_with_secrets();
+        no_host.host = String::new();
+        let bundle = export_profiles(vec![profile_with_secrets(), bad_db, no_host], None).unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed = parse_import(&json, None).await.unwrap();
+        assert_eq!(parsed.profiles.len(), 1);
+        assert_eq!(parsed.skipped.len(), 2);
+        assert_eq!(parsed.skipped[0].name, "bad db");
+    }
+}
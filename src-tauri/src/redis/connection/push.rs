@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncConnectionConfig, PushInfo, PushKind};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::uri::resp3_url;
+use crate::redis::cli::executor::value_to_result;
+use crate::redis::cli::model::CommandResult;
+use crate::utils::errors::AppError;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Payload of the `connection:push` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushEvent {
+    pub connection_id: String,
+    /// `invalidate`, `message`, `pmessage`, `subscribe`, ..., or
+    /// `disconnection` when the channel's connection closed.
+    pub kind: String,
+    pub data: Vec<CommandResult>,
+}
+
+impl PushEvent {
+    fn new(connection_id: &str, info: PushInfo) -> Self {
+        Self {
+            connection_id: connection_id.to_string(),
+            kind: info.kind.to_string(),
+            data: info.data.into_iter().map(value_to_result).collect(),
+        }
+    }
+}
+
+/// An open push channel: the connection commands run on, and the task
+/// forwarding what the server pushes to it.
+struct PushChannel {
+    conn: MultiplexedConnection,
+    handle: AbortHandle,
+}
+
+/// RESP3 connections whose out-of-band messages are surfaced as
+/// `connection:push` events, one per app connection.
+///
+/// Pushes belong to the connection that asked for them (CLIENT TRACKING,
+/// SUBSCRIBE), and pooled connections drop them, so commands that should
+/// produce pushes run on this dedicated connection via `execute`.
+pub struct PushChannels {
+    channels: Arc<RwLock<HashMap<Uuid, PushChannel>>>,
+}
+
+impl Default for PushChannels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PushChannels {
+    pub fn new() -> Self {
+        Self {
+            channels: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Open a RESP3 connection to `connection_url` and forward its pushes,
+    /// replacing any channel already open for the connection.
+    pub async fn open(
+        &self,
+        connection_id: Uuid,
+        connection_url: &str,
+        app: AppHandle,
+    ) -> Result<(), AppError> {
+        let client = redis::Client::open(resp3_url(connection_url))
+            .map_err(|e| AppError::Connection(format!("Failed to create push client: {e}")))?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<PushInfo>();
+        let config = AsyncConnectionConfig::new()
+            .set_connection_timeout(CONNECT_TIMEOUT)
+            .set_response_timeout(RESPONSE_TIMEOUT)
+            .set_push_sender(tx);
+        let conn = client
+            .get_multiplexed_async_connection_with_config(&config)
+            .await
+            .map_err(|e| AppError::Connection(format!("Push connection failed: {e}")))?;
+
+        self.close(&connection_id).await;
+        let id = connection_id.to_string();
+        let task = tokio::spawn(async move {
+            while let Some(info) = rx.recv().await {
+                let closed = info.kind == PushKind::Disconnection;
+                let _ = app.emit("connection:push", &PushEvent::new(&id, info));
+                if closed {
+                    break;
+                }
+            }
+            tracing::info!(connection_id = %id, "Push channel closed");
+        });
+
+        self.channels.write().await.insert(
+            connection_id,
+            PushChannel {
+                conn,
+                handle: task.abort_handle(),
+            },
+        );
+        tracing::info!(connection_id = %connection_id, "Push channel opened");
+        Ok(())
+    }
+
+    /// Run a command on the push channel's connection.
+    pub async fn execute(
+        &self,
+        connection_id: &Uuid,
+        args: &[String],
+    ) -> Result<CommandResult, AppError> {
+        let (name, rest) = args
+            .split_first()
+            .ok_or_else(|| AppError::InvalidInput("Empty command".into()))?;
+        let mut conn = self
+            .channels
+            .read()
+            .await
+            .get(connection_id)
+            .map(|channel| channel.conn.clone())
+            .ok_or_else(|| AppError::NotFound("No push channel is open".into()))?;
+
+        let mut cmd = redis::cmd(&name.to_uppercase());
+        for arg in rest {
+            cmd.arg(arg.as_str());
+        }
+        let value: redis::Value = cmd.query_async(&mut conn).await?;
+        Ok(value_to_result(value))
+    }
+
+    /// Close a connection's push channel. Returns whether one was open.
+    pub async fn close(&self, connection_id: &Uuid) -> bool {
+        match self.channels.write().await.remove(connection_id) {
+            Some(channel) => {
+                channel.handle.abort();
+                tracing::info!(connection_id = %connection_id, "Push channel stopped");
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_event_from_invalidation() {
+        let info = PushInfo {
+            kind: PushKind::Invalidate,
+            data: vec![redis::Value::Array(vec![redis::Value::BulkString(
+                b"user:1".to_vec(),
+            )])],
+        };
+        let event = PushEvent::new("conn", info);
+        assert_eq!(event.kind, "invalidate");
+        let CommandResult::Array(keys) = &event.data[0] else {
+            panic!("Expected Array");
+        };
+        assert!(matches!(&keys[0], CommandResult::BulkString(k) if k == "user:1"));
+    }
+}
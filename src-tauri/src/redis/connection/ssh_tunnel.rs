@@ -76,33 +76,7 @@ pub async fn establish_tunnel(
     remote_host: &str,
     remote_port: u16,
 ) -> Result<SshTunnel, AppError> {
-    if ssh_config.host.is_empty() {
-        return Err(AppError::InvalidInput("SSH host must not be empty".into()));
-    }
-    if ssh_config.username.is_empty() {
-        return Err(AppError::InvalidInput(
-            "SSH username must not be empty".into(),
-        ));
-    }
-    if matches!(ssh_config.auth, SshAuth::Agent) {
-        return Err(AppError::Connection(
-            "SSH agent authentication is not yet supported".into(),
-        ));
-    }
-
-    // Connect to SSH server
-    let config = Arc::new(client::Config::default());
-    let ssh_addr = format!("{}:{}", ssh_config.host, ssh_config.port);
-
-    let mut session = tokio::time::timeout(
-        std::time::Duration::from_secs(10),
-        client::connect(config, &ssh_addr, TunnelHandler),
-    )
-    .await
-    .map_err(|_| AppError::Timeout("SSH connection timed out".into()))?
-    .map_err(|e| AppError::Connection(format!("SSH connection failed: {e}")))?;
-
-    authenticate(&mut session, ssh_config).await?;
+    let session = connect(ssh_config).await?;
 
     // Bind local listener
     let bind_addr = format!("127.0.0.1:{}", ssh_config.local_port.unwrap_or(0));
@@ -166,6 +140,69 @@ pub async fn establish_tunnel(
     })
 }
 
+/// Connect and authenticate to the SSH server in `ssh_config`.
+async fn connect(ssh_config: &SshConfig) -> Result<client::Handle<TunnelHandler>, AppError> {
+    if ssh_config.host.is_empty() {
+        return Err(AppError::InvalidInput("SSH host must not be empty".into()));
+    }
+    if ssh_config.username.is_empty() {
+        return Err(AppError::InvalidInput(
+            "SSH username must not be empty".into(),
+        ));
+    }
+    if matches!(ssh_config.auth, SshAuth::Agent) {
+        return Err(AppError::Connection(
+            "SSH agent authentication is not yet supported".into(),
+        ));
+    }
+
+    let config = Arc::new(client::Config::default());
+    let ssh_addr = format!("{}:{}", ssh_config.host, ssh_config.port);
+
+    let mut session = tokio::time::timeout(
+        std::time::Duration::from_secs(10),
+        client::connect(config, &ssh_addr, TunnelHandler),
+    )
+    .await
+    .map_err(|_| AppError::Timeout("SSH connection timed out".into()))?
+    .map_err(|e| AppError::Connection(format!("SSH connection failed: {e}")))?;
+
+    authenticate(&mut session, ssh_config).await?;
+    Ok(session)
+}
+
+/// A command running on the SSH host. The SSH session stays open until this
+/// is dropped.
+pub struct RemoteCommand {
+    _session: client::Handle<TunnelHandler>,
+    channel: russh::Channel<client::Msg>,
+}
+
+impl RemoteCommand {
+    /// Next message from the command (output, exit status, EOF); `None` once
+    /// the channel is closed.
+    pub async fn next(&mut self) -> Option<russh::ChannelMsg> {
+        self.channel.wait().await
+    }
+}
+
+/// Run `command` on the SSH host in `ssh_config`, over its own session.
+pub async fn exec(ssh_config: &SshConfig, command: &str) -> Result<RemoteCommand, AppError> {
+    let session = connect(ssh_config).await?;
+    let channel = session
+        .channel_open_session()
+        .await
+        .map_err(|e| AppError::Connection(format!("SSH session channel failed: {e}")))?;
+    channel
+        .exec(true, command)
+        .await
+        .map_err(|e| AppError::Connection(format!("SSH exec failed: {e}")))?;
+    Ok(RemoteCommand {
+        _session: session,
+        channel,
+    })
+}
+
 /// Authenticate the SSH session based on the configured auth method.
 async fn authenticate(
     session: &mut client::Handle<TunnelHandler>,
@@ -260,6 +297,7 @@ mod tests {
                 password: "pass".into(),
             },
             local_port: None,
+            log_path: None,
         };
 
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -280,6 +318,7 @@ mod tests {
                 password: "pass".into(),
             },
             local_port: None,
+            log_path: None,
         };
 
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -298,6 +337,7 @@ mod tests {
             username: "user".into(),
             auth: SshAuth::Agent,
             local_port: None,
+            log_path: None,
         };
 
         let rt = tokio::runtime::Runtime::new().unwrap();
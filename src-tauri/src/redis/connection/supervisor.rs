@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use deadpool_redis::Pool;
+use tauri::{Emitter, Manager};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::manager::ConnectionManager;
+use super::model::{ConnectionState, ConnectionStateEvent};
+use super::throttle::BreakerEvent;
+use crate::utils::errors::AppError;
+
+/// Time between health checks of a healthy connection.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a health-check PING may take before the server counts as down.
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+/// First reconnect delay; doubles per failed attempt.
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// Upper bound on the reconnect delay.
+const BACKOFF_MAX: Duration = Duration::from_mins(1);
+/// Reconnect attempts before the supervisor gives up and leaves the error state.
+const MAX_RECONNECT_ATTEMPTS: u32 = 20;
+
+/// Delay before reconnect attempt `attempt` (1-based).
+fn backoff_delay(attempt: u32) -> Duration {
+    let exp = attempt.saturating_sub(1).min(16);
+    BACKOFF_BASE.saturating_mul(1 << exp).min(BACKOFF_MAX)
+}
+
+/// PING through the pool, bounded by [`PING_TIMEOUT`]. Returns the round
+/// trip, not counting the wait for a pooled connection.
+async fn ping(pool: &Pool) -> Result<Duration, AppError> {
+    let check = async {
+        let mut conn = pool.get().await?;
+        let started = Instant::now();
+        let _: String = redis::cmd("PING").query_async(&mut conn).await?;
+        Ok(started.elapsed())
+    };
+    tokio::time::timeout(PING_TIMEOUT, check)
+        .await
+        .map_err(|_| AppError::Timeout("Health check timed out".into()))?
+}
+
+/// The health-check task of one connection.
+struct Supervised {
+    abort: AbortHandle,
+    /// Tells this run apart from a later `start` of the same connection.
+    run: u64,
+}
+
+type Handles = RwLock<HashMap<String, Supervised>>;
+
+/// Watches active connections and reconnects them when the server goes away.
+pub struct ConnectionSupervisor {
+    handles: Arc<Handles>,
+    next_run: AtomicU64,
+}
+
+impl Default for ConnectionSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectionSupervisor {
+    /// Create a supervisor watching no connections.
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+            next_run: AtomicU64::new(0),
+        }
+    }
+
+    /// Health-check a connection until it is disconnected.
+    ///
+    /// A failed PING moves the connection to `Error`, then alternates
+    /// `Connecting` and `Error` (with the attempt count as `retry_count`)
+    /// while reconnecting with exponential backoff. Every transition is
+    /// emitted as `connection:state-changed`. Each PING's round trip feeds
+    /// the connection's circuit breaker. Supervision ends, and
+    /// [`Self::is_running`] turns false, once reconnecting gives up or the
+    /// connection is gone.
+    pub async fn start(&self, id: Uuid, app_handle: tauri::AppHandle) {
+        let run = self.next_run.fetch_add(1, Ordering::Relaxed);
+        let handles = Arc::clone(&self.handles);
+        // Held until the handle is stored, so a run that ends right away
+        // still finds and removes it.
+        let mut h = self.handles.write().await;
+        if let Some(previous) = h.remove(&id.to_string()) {
+            previous.abort.abort();
+        }
+
+        let task = tokio::spawn(async move {
+            let manager = app_handle.state::<ConnectionManager>();
+            loop {
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+                let Ok(pool) = manager.get_pool(&id).await else {
+                    break;
+                };
+                let e = match ping(&pool).await {
+                    Ok(latency) => {
+                        record_latency(&manager, &app_handle, id, latency).await;
+                        continue;
+                    }
+                    Err(e) => e,
+                };
+
+                tracing::warn!(id = %id, "Health check failed: {e}");
+                let failure = ConnectionState::Error {
+                    message: e.to_string(),
+                    retry_count: 0,
+                };
+                if !transition(&manager, &app_handle, id, failure).await {
+                    break;
+                }
+                if !reconnect_with_backoff(&manager, &app_handle, id).await {
+                    break;
+                }
+            }
+            let mut h = handles.write().await;
+            if h.get(&id.to_string()).is_some_and(|s| s.run == run) {
+                h.remove(&id.to_string());
+            }
+        });
+
+        h.insert(
+            id.to_string(),
+            Supervised {
+                abort: task.abort_handle(),
+                run,
+            },
+        );
+    }
+
+    /// Stop supervising a connection.
+    pub async fn stop(&self, connection_id: &str) {
+        let mut h = self.handles.write().await;
+        if let Some(supervised) = h.remove(connection_id) {
+            supervised.abort.abort();
+        }
+    }
+
+    /// Stop supervising every connection (e.g., on app shutdown).
+    pub async fn stop_all(&self) {
+        let mut h = self.handles.write().await;
+        for (_, supervised) in h.drain() {
+            supervised.abort.abort();
+        }
+    }
+
+    /// Check if a connection is being supervised.
+    pub async fn is_running(&self, connection_id: &str) -> bool {
+        let h = self.handles.read().await;
+        h.contains_key(connection_id)
+    }
+}
+
+/// Retry until reconnected. Returns `false` if supervision should end.
+async fn reconnect_with_backoff(
+    manager: &ConnectionManager,
+    app_handle: &tauri::AppHandle,
+    id: Uuid,
+) -> bool {
+    for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+        tokio::time::sleep(backoff_delay(attempt)).await;
+
+        if !transition(manager, app_handle, id, ConnectionState::Connecting).await {
+            return false;
+        }
+        let next = match manager.reconnect(&id).await {
+            Ok(server_info) => ConnectionState::Connected { server_info },
+            Err(e) => {
+                tracing::warn!(id = %id, attempt = attempt, "Reconnect failed: {e}");
+                ConnectionState::Error {
+                    message: e.to_string(),
+                    retry_count: attempt,
+                }
+            }
+        };
+        let reconnected = matches!(next, ConnectionState::Connected { .. });
+        if !transition(manager, app_handle, id, next).await {
+            return false;
+        }
+        if reconnected {
+            return true;
+        }
+    }
+
+    tracing::warn!(id = %id, "Giving up reconnecting after {MAX_RECONNECT_ATTEMPTS} attempts");
+    false
+}
+
+/// Feed a health-check round trip to the connection's breaker and emit
+/// `connection:breaker` if it opened or closed.
+async fn record_latency(
+    manager: &ConnectionManager,
+    app_handle: &tauri::AppHandle,
+    id: Uuid,
+    latency: Duration,
+) {
+    let Ok(throttle) = manager.get_throttle(&id).await else {
+        return;
+    };
+    let Some(open) = throttle.record_latency(latency) else {
+        return;
+    };
+    let event = BreakerEvent {
+        connection_id: id.to_string(),
+        open,
+        latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+        threshold_ms: throttle
+            .breaker_threshold()
+            .map_or(0, |t| u64::try_from(t.as_millis()).unwrap_or(u64::MAX)),
+    };
+    if open {
+        tracing::warn!(id = %id, latency_ms = event.latency_ms, "Circuit breaker opened; pausing background work");
+    } else {
+        tracing::info!(id = %id, latency_ms = event.latency_ms, "Circuit breaker closed");
+    }
+    let _ = app_handle.emit("connection:breaker", &event);
+}
+
+/// Store and emit a state change. Returns `false` if the connection is gone.
+async fn transition(
+    manager: &ConnectionManager,
+    app_handle: &tauri::AppHandle,
+    id: Uuid,
+    state: ConnectionState,
+) -> bool {
+    if !manager.set_state(&id, state.clone()).await {
+        return false;
+    }
+    let event = ConnectionStateEvent {
+        connection_id: id.to_string(),
+        state,
+    };
+    if let Err(e) = app_handle.emit("connection:state-changed", &event) {
+        tracing::warn!(error = %e, "Failed to emit connection state event");
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_doubles_and_caps() {
+        assert_eq!(backoff_delay(1), Duration::from_secs(1));
+        assert_eq!(backoff_delay(2), Duration::from_secs(2));
+        assert_eq!(backoff_delay(4), Duration::from_secs(8));
+        assert_eq!(backoff_delay(7), BACKOFF_MAX);
+        assert_eq!(backoff_delay(u32::MAX), BACKOFF_MAX);
+    }
+
+    #[tokio::test]
+    async fn test_supervisor_new_not_running() {
+        let supervisor = ConnectionSupervisor::new();
+        assert!(!supervisor.is_running("conn-1").await);
+        supervisor.stop("conn-1").await;
+    }
+}
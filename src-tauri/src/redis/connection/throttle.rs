@@ -0,0 +1,348 @@
+// SPDX-License-Identifier: MIT
+
+//! Per-connection throttle and circuit breaker, so the GUI itself can't
+//! overload a production server.
+//!
+//! Every checkout from one of the connection's pools, new connection or
+//! reused, takes a token from the command rate limit (a pool hook). Long
+//! scans keep one connection checked out, so they call [`Throttle::batch`]
+//! for the commands of each batch instead.
+//!
+//! `max_concurrent` is a semaphore shared by all of the connection's pools
+//! (default, database override and pinned write pools). deadpool has no hook
+//! for a connection going away, so each new connection takes a permit for
+//! good and its pool's [`ConnectionSlots`] hands permits back once the pool
+//! holds fewer connections than it has permits, or is dropped.
+//!
+//! The breaker opens when a health-check PING takes longer than the
+//! profile's threshold and closes on the first one that doesn't; background
+//! polling and scans wait while it is open.
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use deadpool_redis::{Hook, HookError, Pool};
+use serde::Serialize;
+use tokio::sync::{watch, Semaphore};
+
+use super::model::ThrottleConfig;
+
+/// Payload of `connection:breaker`, emitted when a breaker opens or closes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakerEvent {
+    pub connection_id: String,
+    pub open: bool,
+    /// The health-check round trip that changed the state.
+    pub latency_ms: u64,
+    pub threshold_ms: u64,
+}
+
+/// Token bucket holding up to one second of commands.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, now: Instant) -> Self {
+        Self {
+            rate: f64::from(rate),
+            tokens: f64::from(rate),
+            refilled: now,
+        }
+    }
+
+    /// Take `commands` tokens and return how long to wait before using them.
+    ///
+    /// The bucket may go negative, so callers queue behind each other
+    /// instead of racing for the next refill.
+    #[allow(clippy::cast_precision_loss)]
+    fn take(&mut self, commands: usize, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.refilled = now;
+        self.tokens -= commands as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Rate limit and circuit breaker of one active connection.
+#[derive(Debug)]
+pub struct Throttle {
+    bucket: Option<Mutex<TokenBucket>>,
+    breaker_threshold: Option<Duration>,
+    open: watch::Sender<bool>,
+    connections: Option<Arc<Semaphore>>,
+}
+
+impl Throttle {
+    pub fn new(config: &ThrottleConfig) -> Self {
+        Self {
+            bucket: (config.max_commands_per_sec > 0).then(|| {
+                Mutex::new(TokenBucket::new(
+                    config.max_commands_per_sec,
+                    Instant::now(),
+                ))
+            }),
+            breaker_threshold: (config.breaker_latency_ms > 0)
+                .then(|| Duration::from_millis(config.breaker_latency_ms)),
+            open: watch::Sender::new(false),
+            connections: (config.max_concurrent > 0)
+                .then(|| Arc::new(Semaphore::new(config.max_concurrent as usize))),
+        }
+    }
+
+    /// Admission state for a new pool's connections, or `None` without a
+    /// `max_concurrent` limit.
+    pub fn connection_slots(&self) -> Option<Arc<ConnectionSlots>> {
+        self.connections.as_ref().map(|semaphore| {
+            Arc::new(ConnectionSlots {
+                semaphore: Arc::clone(semaphore),
+                held: Mutex::new(0),
+                pool_size: OnceLock::new(),
+            })
+        })
+    }
+
+    /// Wait until `commands` more commands fit in the rate limit.
+    pub async fn acquire(&self, commands: usize) {
+        let Some(bucket) = &self.bucket else {
+            return;
+        };
+        let wait = bucket
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take(commands, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Whether the breaker is open.
+    pub fn is_open(&self) -> bool {
+        *self.open.borrow()
+    }
+
+    /// Wait while the breaker is open.
+    pub async fn pause_while_open(&self) {
+        let mut open = self.open.subscribe();
+        let _ = open.wait_for(|open| !open).await;
+    }
+
+    /// Before each batch of a background scan or poll: wait for the breaker
+    /// to close, then for room for the batch's `commands`.
+    pub async fn batch(&self, commands: usize) {
+        self.pause_while_open().await;
+        self.acquire(commands).await;
+    }
+
+    /// Feed a health-check round trip to the breaker. Returns the new state
+    /// if it changed.
+    pub fn record_latency(&self, latency: Duration) -> Option<bool> {
+        let threshold = self.breaker_threshold?;
+        let open = latency > threshold;
+        self.open
+            .send_if_modified(|state| std::mem::replace(state, open) != open)
+            .then_some(open)
+    }
+
+    pub fn breaker_threshold(&self) -> Option<Duration> {
+        self.breaker_threshold
+    }
+}
+
+/// The `max_concurrent` permits held by one pool's connections.
+pub struct ConnectionSlots {
+    semaphore: Arc<Semaphore>,
+    held: Mutex<usize>,
+    /// Connections in the pool, counting ones being created; `None` once
+    /// the pool is gone.
+    pool_size: OnceLock<Box<dyn Fn() -> Option<usize> + Send + Sync>>,
+}
+
+impl std::fmt::Debug for ConnectionSlots {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionSlots")
+            .field("held", &self.held())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConnectionSlots {
+    /// Track `pool`, whose post-create hook admits through these slots.
+    pub fn attach(&self, pool: &Pool) {
+        let weak = pool.weak();
+        let _ = self
+            .pool_size
+            .set(Box::new(move || weak.upgrade().map(|p| p.status().size)));
+    }
+
+    fn held(&self) -> std::sync::MutexGuard<'_, usize> {
+        self.held
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Give back the permits of connections the pool has dropped. Called
+    /// while the pool is creating one, which its size already counts.
+    fn reclaim(&self) {
+        let Some(size) = self.pool_size.get().and_then(|size| size()) else {
+            return;
+        };
+        let mut held = self.held();
+        let gone = held.saturating_sub(size.saturating_sub(1));
+        if gone > 0 {
+            *held -= gone;
+            self.semaphore.add_permits(gone);
+        }
+    }
+
+    /// Wait up to `timeout` for a permit for one more connection.
+    async fn admit(&self, timeout: Duration) -> Result<(), HookError> {
+        self.reclaim();
+        let permit = tokio::time::timeout(timeout, self.semaphore.acquire())
+            .await
+            .map_err(|_| HookError::message("Concurrent connection limit reached"))?
+            .map_err(|_| HookError::message("Connection closed"))?;
+        permit.forget();
+        *self.held() += 1;
+        Ok(())
+    }
+}
+
+impl Drop for ConnectionSlots {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(*self.held());
+    }
+}
+
+/// Post-create pool hook that admits each new connection against the
+/// connection's `max_concurrent` limit, waiting up to `timeout` for one of
+/// its pools to give a connection up.
+pub fn admission_hook(slots: &Arc<ConnectionSlots>, timeout: Duration) -> Hook {
+    let slots = Arc::clone(slots);
+    Hook::async_fn(move |_, _| {
+        let slots = Arc::clone(&slots);
+        Box::pin(async move { slots.admit(timeout).await })
+    })
+}
+
+/// Pool hook that takes one rate-limit token per connection checkout; used
+/// for both new and recycled connections.
+pub fn checkout_hook(throttle: &Arc<Throttle>) -> Hook {
+    let throttle = Arc::clone(throttle);
+    Hook::async_fn(move |_, _| {
+        let throttle = Arc::clone(&throttle);
+        Box::pin(async move {
+            throttle.acquire(1).await;
+            Ok(())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_queues_callers() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(10, start);
+        assert_eq!(bucket.take(10, start), Duration::ZERO);
+        // Empty: five more commands wait half a second.
+        assert_eq!(bucket.take(5, start), Duration::from_millis(500));
+        // The next caller queues behind them.
+        assert_eq!(bucket.take(5, start), Duration::from_secs(1));
+
+        // A second later the debt is paid off; idle time refills at most one second's worth.
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.take(0, later), Duration::ZERO);
+        let idle = later + Duration::from_mins(1);
+        assert_eq!(bucket.take(10, idle), Duration::ZERO);
+        assert!(bucket.take(1, idle) > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_breaker_opens_and_closes_on_latency() {
+        let throttle = Throttle::new(&ThrottleConfig {
+            breaker_latency_ms: 200,
+            ..ThrottleConfig::default()
+        });
+        assert!(!throttle.is_open());
+        assert_eq!(throttle.record_latency(Duration::from_millis(50)), None);
+        assert_eq!(
+            throttle.record_latency(Duration::from_millis(500)),
+            Some(true)
+        );
+        assert!(throttle.is_open());
+        assert_eq!(throttle.record_latency(Duration::from_millis(900)), None);
+        assert_eq!(
+            throttle.record_latency(Duration::from_millis(20)),
+            Some(false)
+        );
+        assert!(!throttle.is_open());
+
+        let disabled = Throttle::new(&ThrottleConfig::default());
+        assert_eq!(disabled.record_latency(Duration::from_secs(10)), None);
+    }
+
+    #[tokio::test]
+    async fn test_connection_slots_share_one_limit() {
+        let throttle = Throttle::new(&ThrottleConfig {
+            max_concurrent: 2,
+            ..ThrottleConfig::default()
+        });
+        let first = throttle.connection_slots().unwrap();
+        let second = throttle.connection_slots().unwrap();
+        let size = Arc::new(Mutex::new(0usize));
+        let tracked = Arc::clone(&size);
+        let _ = first
+            .pool_size
+            .set(Box::new(move || Some(*tracked.lock().unwrap())));
+
+        let wait = Duration::from_millis(20);
+        *size.lock().unwrap() = 1;
+        first.admit(wait).await.unwrap();
+        second.admit(wait).await.unwrap();
+        // Both pools together are at the limit.
+        *size.lock().unwrap() = 2;
+        assert!(first.admit(wait).await.is_err());
+
+        // The first pool dropped its connection: the next create reclaims it.
+        *size.lock().unwrap() = 1;
+        first.admit(wait).await.unwrap();
+        assert_eq!(*first.held(), 1);
+
+        // Dropping a pool frees its connections for the others.
+        drop(second);
+        *size.lock().unwrap() = 2;
+        first.admit(wait).await.unwrap();
+
+        assert!(Throttle::new(&ThrottleConfig::default())
+            .connection_slots()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_while_open_resumes_on_close() {
+        let throttle = Arc::new(Throttle::new(&ThrottleConfig {
+            breaker_latency_ms: 100,
+            ..ThrottleConfig::default()
+        }));
+        throttle.record_latency(Duration::from_secs(1));
+        let waiter = Arc::clone(&throttle);
+        let paused = tokio::spawn(async move { waiter.pause_while_open().await });
+        tokio::task::yield_now().await;
+        assert!(!paused.is_finished());
+
+        throttle.record_latency(Duration::from_millis(10));
+        paused.await.unwrap();
+    }
+}
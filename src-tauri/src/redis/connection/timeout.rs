@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT
+
+//! Enforcement of a profile's read and write timeouts.
+//!
+//! Every pooled connection gets a response timeout (the longer of the two
+//! limits, see [`TimeoutConfig::command_timeout`]) when the pool creates it,
+//! so a blocked server fails each command with `AppError::Timeout` in every
+//! ops module. Callers that know whether a command writes, such as the CLI,
+//! additionally wrap it in [`with_timeout`] with the matching limit.
+
+use std::future::Future;
+use std::time::Duration;
+
+use super::model::TimeoutConfig;
+use super::write_guard;
+use crate::utils::errors::AppError;
+
+/// The limit for a parsed command (name + arguments): the write timeout for
+/// commands that mutate, the read timeout otherwise.
+pub fn for_command(config: &TimeoutConfig, args: &[String]) -> Option<Duration> {
+    if write_guard::is_write_command(args) {
+        config.write_timeout()
+    } else {
+        config.read_timeout()
+    }
+}
+
+/// Run `work`, failing with `AppError::Timeout` if it takes longer than
+/// `limit`. `None` runs it unbounded.
+///
+/// On timeout the future is dropped, which cancels it: a pending reply is
+/// discarded by the multiplexed connection when it arrives, so the pooled
+/// connection stays usable.
+pub async fn with_timeout<T, F>(
+    limit: Option<Duration>,
+    operation: &str,
+    work: F,
+) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let Some(limit) = limit else {
+        return work.await;
+    };
+    tokio::time::timeout(limit, work).await.map_err(|_| {
+        AppError::Timeout(format!(
+            "{operation} did not complete within {}",
+            format_limit(limit)
+        ))
+    })?
+}
+
+fn format_limit(limit: Duration) -> String {
+    if limit.subsec_millis() == 0 {
+        format!("{}s", limit.as_secs())
+    } else {
+        format!("{}ms", limit.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_for_command_picks_read_or_write_limit() {
+        let config = TimeoutConfig {
+            connect_secs: 5,
+            read_secs: 3,
+            write_secs: 20,
+        };
+        assert_eq!(
+            for_command(&config, &args(&["GET", "k"])),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            for_command(&config, &args(&["SET", "k", "v"])),
+            Some(Duration::from_secs(20))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout_expires() {
+        let result: Result<(), AppError> = with_timeout(
+            Some(Duration::from_millis(10)),
+            "BLPOP",
+            std::future::pending(),
+        )
+        .await;
+        match result {
+            Err(AppError::Timeout(message)) => {
+                assert_eq!(message, "BLPOP did not complete within 10ms");
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout_passes_results_through() {
+        let ok = with_timeout(Some(Duration::from_secs(1)), "GET", async { Ok(7) }).await;
+        assert_eq!(ok.unwrap(), 7);
+
+        let unbounded: Result<u8, AppError> =
+            with_timeout(None, "GET", async { Err(AppError::NotFound("k".into())) }).await;
+        assert!(matches!(unbounded, Err(AppError::NotFound(_))));
+    }
+}
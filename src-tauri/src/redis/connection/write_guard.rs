@@ -0,0 +1,301 @@
+// SPDX-License-Identifier: MIT
+
+use crate::utils::errors::AppError;
+
+/// Commands that modify data or server state.
+///
+/// Scripting entry points (EVAL, FCALL) are treated as writes because their
+/// effects can't be known up front; their `_RO` variants are allowed.
+static WRITE_COMMANDS: &[&str] = &[
+    // Keyspace
+    "DEL",
+    "UNLINK",
+    "RENAME",
+    "RENAMENX",
+    "MOVE",
+    "COPY",
+    "RESTORE",
+    "MIGRATE",
+    "EXPIRE",
+    "PEXPIRE",
+    "EXPIREAT",
+    "PEXPIREAT",
+    "PERSIST",
+    "TOUCH",
+    "FLUSHDB",
+    "FLUSHALL",
+    "SWAPDB",
+    // Strings
+    "SET",
+    "SETNX",
+    "SETEX",
+    "PSETEX",
+    "MSET",
+    "MSETNX",
+    "GETSET",
+    "GETDEL",
+    "GETEX",
+    "APPEND",
+    "SETRANGE",
+    "INCR",
+    "INCRBY",
+    "INCRBYFLOAT",
+    "DECR",
+    "DECRBY",
+    "SETBIT",
+    "BITOP",
+    "BITFIELD",
+    // Hashes
+    "HSET",
+    "HSETNX",
+    "HSETEX",
+    "HGETDEL",
+    "HGETEX",
+    "HMSET",
+    "HDEL",
+    "HINCRBY",
+    "HINCRBYFLOAT",
+    "HEXPIRE",
+    "HPEXPIRE",
+    "HEXPIREAT",
+    "HPEXPIREAT",
+    "HPERSIST",
+    // Lists
+    "LPUSH",
+    "RPUSH",
+    "LPUSHX",
+    "RPUSHX",
+    "LINSERT",
+    "LSET",
+    "LREM",
+    "LTRIM",
+    "LPOP",
+    "RPOP",
+    "RPOPLPUSH",
+    "LMOVE",
+    "LMPOP",
+    "BLPOP",
+    "BRPOP",
+    "BRPOPLPUSH",
+    "BLMOVE",
+    "BLMPOP",
+    // Sets
+    "SADD",
+    "SREM",
+    "SPOP",
+    "SMOVE",
+    "SINTERSTORE",
+    "SUNIONSTORE",
+    "SDIFFSTORE",
+    // Sorted sets
+    "ZADD",
+    "ZINCRBY",
+    "ZREM",
+    "ZREMRANGEBYRANK",
+    "ZREMRANGEBYSCORE",
+    "ZREMRANGEBYLEX",
+    "ZPOPMIN",
+    "ZPOPMAX",
+    "BZPOPMIN",
+    "BZPOPMAX",
+    "ZMPOP",
+    "BZMPOP",
+    "ZUNIONSTORE",
+    "ZINTERSTORE",
+    "ZDIFFSTORE",
+    "ZRANGESTORE",
+    // Streams
+    "XADD",
+    "XDEL",
+    "XTRIM",
+    "XACK",
+    "XCLAIM",
+    "XAUTOCLAIM",
+    "XGROUP",
+    "XSETID",
+    "XREADGROUP",
+    // HyperLogLog and geo
+    "PFADD",
+    "PFMERGE",
+    "GEOADD",
+    "GEOSEARCHSTORE",
+    "GEORADIUS",
+    "GEORADIUSBYMEMBER",
+    // Pub/Sub and scripting
+    "PUBLISH",
+    "SPUBLISH",
+    "EVAL",
+    "EVALSHA",
+    "FCALL",
+    // Server state
+    "SHUTDOWN",
+    "DEBUG",
+    "REPLICAOF",
+    "SLAVEOF",
+    "FAILOVER",
+    "SAVE",
+    "BGSAVE",
+    "BGREWRITEAOF",
+    // Modules (RedisJSON, RediSearch, RedisTimeSeries, RedisBloom)
+    "JSON.SET",
+    "JSON.MSET",
+    "JSON.MERGE",
+    "JSON.DEL",
+    "JSON.FORGET",
+    "JSON.CLEAR",
+    "JSON.TOGGLE",
+    "JSON.NUMINCRBY",
+    "JSON.NUMMULTBY",
+    "JSON.STRAPPEND",
+    "JSON.ARRAPPEND",
+    "JSON.ARRINSERT",
+    "JSON.ARRPOP",
+    "JSON.ARRTRIM",
+    "FT.CREATE",
+    "FT.ALTER",
+    "FT.DROPINDEX",
+    "FT.ALIASADD",
+    "FT.ALIASDEL",
+    "FT.ALIASUPDATE",
+    "FT.SUGADD",
+    "FT.SUGDEL",
+    "FT.DICTADD",
+    "FT.DICTDEL",
+    "FT.SYNUPDATE",
+    "TS.CREATE",
+    "TS.ALTER",
+    "TS.ADD",
+    "TS.MADD",
+    "TS.INCRBY",
+    "TS.DECRBY",
+    "TS.DEL",
+    "TS.CREATERULE",
+    "TS.DELETERULE",
+    "BF.ADD",
+    "BF.MADD",
+    "BF.INSERT",
+    "BF.RESERVE",
+    "CF.ADD",
+    "CF.ADDNX",
+    "CF.INSERT",
+    "CF.INSERTNX",
+    "CF.DEL",
+    "CF.RESERVE",
+];
+
+/// Container commands whose mutating behaviour depends on the subcommand.
+static WRITE_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    ("CONFIG", &["SET", "REWRITE", "RESETSTAT"]),
+    ("CLIENT", &["KILL", "PAUSE", "UNPAUSE", "NO-EVICT"]),
+    ("ACL", &["SETUSER", "DELUSER", "LOAD", "SAVE"]),
+    ("SCRIPT", &["FLUSH", "KILL", "LOAD"]),
+    ("FUNCTION", &["LOAD", "DELETE", "FLUSH", "RESTORE", "KILL"]),
+    ("MODULE", &["LOAD", "LOADEX", "UNLOAD"]),
+    ("SLOWLOG", &["RESET"]),
+    ("LATENCY", &["RESET"]),
+    ("MEMORY", &["PURGE"]),
+    (
+        "CLUSTER",
+        &[
+            "ADDSLOTS",
+            "ADDSLOTSRANGE",
+            "DELSLOTS",
+            "DELSLOTSRANGE",
+            "FAILOVER",
+            "FORGET",
+            "MEET",
+            "REPLICATE",
+            "RESET",
+            "SETSLOT",
+            "FLUSHSLOTS",
+            "SAVECONFIG",
+        ],
+    ),
+];
+
+/// Commands that only write when given one of these arguments.
+static WRITE_ARGUMENTS: &[(&str, &str)] = &[("SORT", "STORE")];
+
+/// The error returned for writes on a read-only connection.
+pub fn readonly_error() -> AppError {
+    AppError::PermissionDenied("Connection is read-only".into())
+}
+
+/// Whether a parsed command (name + arguments) mutates data or server state.
+pub fn is_write_command(args: &[String]) -> bool {
+    let Some(name) = args.first().map(|a| a.to_uppercase()) else {
+        return false;
+    };
+
+    if WRITE_COMMANDS.contains(&name.as_str()) {
+        return true;
+    }
+
+    if WRITE_ARGUMENTS
+        .iter()
+        .any(|(cmd, arg)| *cmd == name && args[1..].iter().any(|a| a.eq_ignore_ascii_case(arg)))
+    {
+        return true;
+    }
+
+    let sub = args.get(1).map(|a| a.to_uppercase()).unwrap_or_default();
+    WRITE_SUBCOMMANDS
+        .iter()
+        .any(|(cmd, subs)| *cmd == name && subs.contains(&sub.as_str()))
+}
+
+/// Reject a command on a read-only connection if it would write.
+pub fn check_command(readonly: bool, args: &[String]) -> Result<(), AppError> {
+    if readonly && is_write_command(args) {
+        let name = args.first().map(|a| a.to_uppercase()).unwrap_or_default();
+        return Err(AppError::PermissionDenied(format!(
+            "{name} is not allowed on a read-only connection"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_is_write_command_basic() {
+        assert!(is_write_command(&args("set k v")));
+        assert!(is_write_command(&args("DEL a b")));
+        assert!(is_write_command(&args("json.set k $ 1")));
+        assert!(!is_write_command(&args("GET k")));
+        assert!(!is_write_command(&args("EVAL_RO script 0")));
+        assert!(!is_write_command(&[]));
+    }
+
+    #[test]
+    fn test_is_write_command_subcommands() {
+        assert!(is_write_command(&args("CONFIG SET maxmemory 1gb")));
+        assert!(!is_write_command(&args("CONFIG GET maxmemory")));
+        assert!(is_write_command(&args("client kill id 5")));
+        assert!(!is_write_command(&args("CLIENT LIST")));
+        assert!(!is_write_command(&args("CONFIG")));
+    }
+
+    #[test]
+    fn test_is_write_command_store_argument() {
+        assert!(is_write_command(&args("SORT list LIMIT 0 10 STORE dest")));
+        assert!(is_write_command(&args("sort list store dest")));
+        assert!(!is_write_command(&args("SORT list ALPHA DESC")));
+        assert!(!is_write_command(&args("SORT_RO list")));
+        assert!(is_write_command(&args("HGETDEL h FIELDS 1 f")));
+    }
+
+    #[test]
+    fn test_check_command() {
+        assert!(check_command(false, &args("FLUSHALL")).is_ok());
+        assert!(check_command(true, &args("GET k")).is_ok());
+        let err = check_command(true, &args("flushall")).unwrap_err();
+        assert!(matches!(err, AppError::PermissionDenied(ref m) if m.starts_with("FLUSHALL")));
+    }
+}
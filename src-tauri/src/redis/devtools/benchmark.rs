@@ -0,0 +1,506 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+use tokio::sync::RwLock;
+
+use super::generator::Rng;
+use super::model::{BenchCommand, BenchFigures, BenchSpec, LatencySummary};
+use crate::utils::errors::AppError;
+
+/// Every key the benchmark touches starts with this, and is deleted after
+/// the run.
+pub const BENCH_KEY_PREFIX: &str = "redislens:bench:";
+/// How often progress is reported.
+const TICK: Duration = Duration::from_secs(1);
+/// Latencies below this many microseconds get a bucket each.
+const EXACT_BUCKETS: u64 = 1024;
+/// Sub-buckets per power of two above that, for about 1.5% precision.
+const SUB_BUCKET_BITS: u32 = 6;
+const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+
+/// Round-trip latencies in microseconds, log-bucketed so a long run needs
+/// constant memory.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+    sum: u128,
+    min: u64,
+    max: u64,
+}
+
+fn bucket_of(us: u64) -> usize {
+    let index = if us < EXACT_BUCKETS {
+        us
+    } else {
+        let exp = u64::from(us.ilog2());
+        let sub = (us >> (exp - u64::from(SUB_BUCKET_BITS))) - SUB_BUCKETS;
+        EXACT_BUCKETS + (exp - 10) * SUB_BUCKETS + sub
+    };
+    usize::try_from(index).unwrap_or(usize::MAX)
+}
+
+/// Largest latency that falls into bucket `index`.
+fn bucket_upper(index: usize) -> u64 {
+    let index = index as u64;
+    if index < EXACT_BUCKETS {
+        return index;
+    }
+    let offset = index - EXACT_BUCKETS;
+    let exp = 10 + offset / SUB_BUCKETS;
+    let sub = offset % SUB_BUCKETS;
+    ((SUB_BUCKETS + sub + 1) << (exp - u64::from(SUB_BUCKET_BITS))) - 1
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, us: u64) {
+        let bucket = bucket_of(us);
+        if self.counts.len() <= bucket {
+            self.counts.resize(bucket + 1, 0);
+        }
+        self.counts[bucket] += 1;
+        self.min = if self.total == 0 {
+            us
+        } else {
+            self.min.min(us)
+        };
+        self.max = self.max.max(us);
+        self.total += 1;
+        self.sum += u128::from(us);
+    }
+
+    /// Latency at or below which `percent` of the samples fall.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn percentile(&self, percent: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let rank = ((percent / 100.0) * self.total as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_upper(index).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn summary(&self) -> LatencySummary {
+        if self.total == 0 {
+            return LatencySummary::default();
+        }
+        LatencySummary {
+            min_us: self.min,
+            mean_us: self.sum as f64 / self.total as f64,
+            p50_us: self.percentile(50.0),
+            p90_us: self.percentile(90.0),
+            p99_us: self.percentile(99.0),
+            p999_us: self.percentile(99.9),
+            max_us: self.max,
+        }
+    }
+}
+
+/// Weighted random choice of the next command.
+#[derive(Debug, Clone)]
+struct Workload {
+    /// Commands with their cumulative weights.
+    steps: Vec<(BenchCommand, u64)>,
+    total: u64,
+}
+
+impl Workload {
+    fn new(spec: &BenchSpec) -> Self {
+        let mut total = 0;
+        let steps = spec
+            .mix
+            .iter()
+            .filter(|w| w.weight > 0)
+            .map(|w| {
+                total += u64::from(w.weight);
+                (w.command, total)
+            })
+            .collect();
+        Self { steps, total }
+    }
+
+    fn pick(&self, rng: &mut Rng) -> BenchCommand {
+        let roll = rng.between(1, self.total);
+        self.steps
+            .iter()
+            .find(|(_, upto)| roll <= *upto)
+            .map_or(BenchCommand::Ping, |(command, _)| *command)
+    }
+}
+
+fn queue_command(
+    pipe: &mut redis::Pipeline,
+    command: BenchCommand,
+    spec: &BenchSpec,
+    value: &str,
+    rng: &mut Rng,
+) {
+    let n = rng.between(0, spec.keyspace - 1);
+    let key = |kind: &str| format!("{BENCH_KEY_PREFIX}{kind}:{n}");
+    match command {
+        BenchCommand::Ping => pipe.cmd("PING"),
+        BenchCommand::Get => pipe.cmd("GET").arg(key("str")),
+        BenchCommand::Set => pipe.cmd("SET").arg(key("str")).arg(value),
+        BenchCommand::Incr => pipe.cmd("INCR").arg(key("counter")),
+        BenchCommand::Lpush => pipe.cmd("LPUSH").arg(key("list")).arg(value),
+        BenchCommand::Lpop => pipe.cmd("LPOP").arg(key("list")),
+        BenchCommand::Sadd => pipe
+            .cmd("SADD")
+            .arg(key("set"))
+            .arg(rng.between(0, spec.keyspace - 1)),
+        BenchCommand::Hset => pipe
+            .cmd("HSET")
+            .arg(key("hash"))
+            .arg(format!("field:{}", rng.between(0, 99)))
+            .arg(value),
+        BenchCommand::Zadd => pipe
+            .cmd("ZADD")
+            .arg(key("zset"))
+            .arg(rng.unit() * 1000.0)
+            .arg(rng.between(0, spec.keyspace - 1)),
+    };
+}
+
+/// Counters shared by the client tasks.
+#[derive(Debug, Default)]
+struct Totals {
+    ops: AtomicU64,
+    errors: AtomicU64,
+    latency: Mutex<LatencyHistogram>,
+}
+
+/// Open one connection per benchmark client, bound to `db` if given.
+pub async fn connect(
+    url: &str,
+    clients: u32,
+    db: Option<u8>,
+) -> Result<Vec<MultiplexedConnection>, AppError> {
+    let client = redis::Client::open(url)
+        .map_err(|e| AppError::Connection(format!("Failed to create client: {e}")))?;
+    let mut conns = Vec::new();
+    for _ in 0..clients {
+        let mut conn = tokio::time::timeout(
+            Duration::from_secs(10),
+            client.get_multiplexed_async_connection(),
+        )
+        .await
+        .map_err(|_| AppError::Timeout("Benchmark connection timed out".into()))?
+        .map_err(|e| AppError::Connection(format!("Benchmark connection failed: {e}")))?;
+        if let Some(db) = db {
+            redis::cmd("SELECT")
+                .arg(db)
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("SELECT failed: {e}")))?;
+        }
+        conns.push(conn);
+    }
+    Ok(conns)
+}
+
+/// Send pipelines until the deadline or `stop`, recording each round trip.
+/// Ends early only if the connection breaks.
+async fn run_client(
+    mut conn: MultiplexedConnection,
+    spec: Arc<BenchSpec>,
+    totals: Arc<Totals>,
+    stop: Arc<AtomicBool>,
+    deadline: Instant,
+    seed: u64,
+) -> Result<(), AppError> {
+    let mut rng = Rng::new(seed);
+    let workload = Workload::new(&spec);
+    let value = "x".repeat(spec.value_bytes as usize);
+    let depth = u64::from(spec.pipeline);
+
+    while !stop.load(Ordering::Relaxed) && Instant::now() < deadline {
+        let mut pipe = redis::pipe();
+        for _ in 0..spec.pipeline {
+            queue_command(&mut pipe, workload.pick(&mut rng), &spec, &value, &mut rng);
+        }
+        let started = Instant::now();
+        let outcome = pipe.query_async::<Vec<redis::Value>>(&mut conn).await;
+        let micros = u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX);
+        match outcome {
+            Ok(_) => {
+                totals.ops.fetch_add(depth, Ordering::Relaxed);
+                if let Ok(mut latency) = totals.latency.lock() {
+                    latency.record(micros);
+                }
+            }
+            Err(e) if e.is_io_error() || e.is_connection_dropped() => {
+                return Err(AppError::Connection(format!(
+                    "Benchmark connection lost: {e}"
+                )));
+            }
+            Err(_) => {
+                totals.ops.fetch_add(depth, Ordering::Relaxed);
+                totals.errors.fetch_add(depth, Ordering::Relaxed);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run the workload on `conns` for the spec's duration or until `stop` is
+/// set, handing the running figures to `on_tick` about once a second.
+/// Returns the final figures and the error that ended the run early, if
+/// any; keys the run wrote are deleted afterwards.
+pub async fn run(
+    conns: Vec<MultiplexedConnection>,
+    spec: BenchSpec,
+    stop: Arc<AtomicBool>,
+    (benchmark_id, connection_id): (String, String),
+    mut on_tick: impl FnMut(&BenchFigures),
+) -> (BenchFigures, Option<String>) {
+    let spec = Arc::new(spec);
+    let totals = Arc::new(Totals::default());
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(u64::from(spec.duration_secs));
+    let cleanup = conns.first().cloned();
+    let seed = chrono::Utc::now()
+        .timestamp_nanos_opt()
+        .map_or(0, i64::unsigned_abs);
+
+    let mut tasks: tokio::task::JoinSet<Result<(), AppError>> = tokio::task::JoinSet::new();
+    for (i, conn) in conns.into_iter().enumerate() {
+        tasks.spawn(run_client(
+            conn,
+            Arc::clone(&spec),
+            Arc::clone(&totals),
+            Arc::clone(&stop),
+            deadline,
+            seed.wrapping_add(i as u64),
+        ));
+    }
+
+    let figures = |elapsed: Duration, ops_per_sec: f64| {
+        let latency = totals
+            .latency
+            .lock()
+            .map(|l| l.summary())
+            .unwrap_or_default();
+        BenchFigures {
+            benchmark_id: benchmark_id.clone(),
+            connection_id: connection_id.clone(),
+            elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            total_ops: totals.ops.load(Ordering::Relaxed),
+            errors: totals.errors.load(Ordering::Relaxed),
+            ops_per_sec,
+            latency,
+        }
+    };
+
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + TICK, TICK);
+    let mut last_ops = 0;
+    let mut error = None;
+    loop {
+        tokio::select! {
+            joined = tasks.join_next() => match joined {
+                Some(Ok(Ok(()))) => {}
+                Some(Ok(Err(e))) => {
+                    // One broken connection ends the whole run.
+                    stop.store(true, Ordering::Relaxed);
+                    error.get_or_insert(e.to_string());
+                }
+                Some(Err(e)) => {
+                    stop.store(true, Ordering::Relaxed);
+                    error.get_or_insert(format!("Benchmark client failed: {e}"));
+                }
+                None => break,
+            },
+            _ = ticker.tick() => {
+                let ops = totals.ops.load(Ordering::Relaxed);
+                #[allow(clippy::cast_precision_loss)]
+                let rate = (ops - last_ops) as f64 / TICK.as_secs_f64();
+                last_ops = ops;
+                on_tick(&figures(started.elapsed(), rate));
+            }
+        }
+    }
+
+    let elapsed = started.elapsed();
+    #[allow(clippy::cast_precision_loss)]
+    let rate = totals.ops.load(Ordering::Relaxed) as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    if spec.writes() {
+        if let Some(mut conn) = cleanup {
+            if let Err(e) = delete_bench_keys(&mut conn).await {
+                tracing::warn!(benchmark_id = %benchmark_id, "Benchmark cleanup failed: {e}");
+            }
+        }
+    }
+    (figures(elapsed, rate), error)
+}
+
+/// UNLINK every key under [`BENCH_KEY_PREFIX`].
+async fn delete_bench_keys(conn: &mut MultiplexedConnection) -> Result<u64, AppError> {
+    let pattern = format!("{BENCH_KEY_PREFIX}*");
+    let mut cursor: u64 = 0;
+    let mut deleted = 0;
+    loop {
+        let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(1000)
+            .query_async(conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("SCAN failed: {e}")))?;
+        if !keys.is_empty() {
+            deleted += redis::cmd("UNLINK")
+                .arg(&keys)
+                .query_async::<u64>(conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("UNLINK failed: {e}")))?;
+        }
+        cursor = next;
+        if cursor == 0 {
+            return Ok(deleted);
+        }
+    }
+}
+
+/// Stop flags of running benchmarks, by benchmark ID.
+pub struct Benchmarks {
+    flags: Arc<RwLock<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+impl Default for Benchmarks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Benchmarks {
+    pub fn new() -> Self {
+        Self {
+            flags: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Register a run and return the flag that stops it.
+    pub async fn register(&self, benchmark_id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.flags
+            .write()
+            .await
+            .insert(benchmark_id.to_string(), Arc::clone(&flag));
+        flag
+    }
+
+    /// Forget a finished run.
+    pub async fn finish(&self, benchmark_id: &str) {
+        self.flags.write().await.remove(benchmark_id);
+    }
+
+    /// Ask a run to stop early; it still reports and cleans up. Returns
+    /// whether it was running.
+    pub async fn stop(&self, benchmark_id: &str) -> bool {
+        match self.flags.read().await.get(benchmark_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Ask every running benchmark to stop (e.g., on app shutdown).
+    pub async fn stop_all(&self) {
+        for flag in self.flags.read().await.values() {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::devtools::model::BenchWeight;
+
+    #[test]
+    fn test_buckets_round_trip() {
+        for us in [0, 1, 1023, 1024, 1039, 1040, 5_000, 123_456, 10_000_000] {
+            let bucket = bucket_of(us);
+            assert!(bucket_upper(bucket) >= us, "{us}");
+            if bucket > 0 {
+                assert!(bucket_upper(bucket - 1) < us, "{us}");
+            }
+        }
+        assert_eq!(bucket_upper(bucket_of(1024)), 1039);
+    }
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.summary(), LatencySummary::default());
+        for us in 1..=1000 {
+            histogram.record(us);
+        }
+        histogram.record(50_000);
+        let summary = histogram.summary();
+        assert_eq!(summary.min_us, 1);
+        assert_eq!(summary.max_us, 50_000);
+        assert_eq!(summary.p50_us, 501);
+        assert_eq!(summary.p99_us, 991);
+        assert_eq!(histogram.percentile(100.0), 50_000);
+    }
+
+    #[test]
+    fn test_workload_follows_weights() {
+        let spec = BenchSpec {
+            mix: vec![
+                BenchWeight {
+                    command: BenchCommand::Get,
+                    weight: 3,
+                },
+                BenchWeight {
+                    command: BenchCommand::Set,
+                    weight: 1,
+                },
+                BenchWeight {
+                    command: BenchCommand::Incr,
+                    weight: 0,
+                },
+            ],
+            clients: 1,
+            pipeline: 1,
+            duration_secs: 1,
+            keyspace: 10,
+            value_bytes: 3,
+        };
+        let workload = Workload::new(&spec);
+        let mut rng = Rng::new(9);
+        let picks: Vec<_> = (0..4000).map(|_| workload.pick(&mut rng)).collect();
+        let gets = picks.iter().filter(|c| **c == BenchCommand::Get).count();
+        assert!(!picks.contains(&BenchCommand::Incr));
+        assert!((2700..3300).contains(&gets), "{gets}");
+    }
+
+    #[tokio::test]
+    async fn test_stop_flag() {
+        let benchmarks = Benchmarks::new();
+        let flag = benchmarks.register("b1").await;
+        assert!(benchmarks.stop("b1").await);
+        assert!(flag.load(Ordering::Relaxed));
+        benchmarks.finish("b1").await;
+        assert!(!benchmarks.stop("b1").await);
+    }
+}
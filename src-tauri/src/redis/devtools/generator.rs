@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt::Write;
+
+use deadpool_redis::Pool;
+
+use super::model::{GeneratedType, GeneratorSpec};
+use crate::utils::errors::AppError;
+
+/// Rough payload size of one pipeline.
+const PIPELINE_BYTES: u64 = 4 * 1024 * 1024;
+/// Most keys written per pipeline, however small they are.
+const MAX_KEYS_PER_PIPELINE: u64 = 1_000;
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Small seeded generator (`SplitMix64`); enough for test data, and the same
+/// seed always yields the same keyspace.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `min..=max`.
+    pub fn between(&mut self, min: u64, max: u64) -> u64 {
+        match (max - min).checked_add(1) {
+            Some(span) => min + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Uniform in `[0, 1)`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn alphanumeric(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| {
+                let i = usize::try_from(self.next_u64() % ALPHANUMERIC.len() as u64).unwrap_or(0);
+                char::from(ALPHANUMERIC[i])
+            })
+            .collect()
+    }
+
+    fn uuid(&mut self) -> uuid::Uuid {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Number,
+    Random,
+    Uuid,
+}
+
+/// A key name template such as `user:{n}` or `session:{uuid}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyTemplate(Vec<Segment>);
+
+impl KeyTemplate {
+    /// Parse a template, which must contain at least one placeholder so
+    /// keys differ from each other.
+    pub fn parse(template: &str) -> Result<Self, AppError> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                segments.push(Segment::Literal(rest[..open].to_string()));
+            }
+            let Some(close) = rest[open..].find('}') else {
+                return Err(AppError::InvalidInput(format!(
+                    "Unclosed '{{' in key template '{template}'"
+                )));
+            };
+            segments.push(match &rest[open + 1..open + close] {
+                "n" => Segment::Number,
+                "rand" => Segment::Random,
+                "uuid" => Segment::Uuid,
+                other => {
+                    return Err(AppError::InvalidInput(format!(
+                        "Unknown placeholder '{{{other}}}'; use {{n}}, {{rand}} or {{uuid}}"
+                    )))
+                }
+            });
+            rest = &rest[open + close + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        if segments.iter().all(|s| matches!(s, Segment::Literal(_))) {
+            return Err(AppError::InvalidInput(
+                "Key template needs a {n}, {rand} or {uuid} placeholder".into(),
+            ));
+        }
+        Ok(Self(segments))
+    }
+
+    pub fn render(&self, n: u64, rng: &mut Rng) -> String {
+        let mut key = String::new();
+        for segment in &self.0 {
+            let _ = match segment {
+                Segment::Literal(text) => write!(key, "{text}"),
+                Segment::Number => write!(key, "{n}"),
+                Segment::Random => write!(key, "{:08}", rng.next_u64() % 100_000_000),
+                Segment::Uuid => write!(key, "{}", rng.uuid()),
+            };
+        }
+        key
+    }
+}
+
+/// Keys written per pipeline for `spec`.
+pub fn keys_per_pipeline(spec: &GeneratorSpec) -> u64 {
+    (PIPELINE_BYTES / spec.bytes_per_key().max(1)).clamp(1, MAX_KEYS_PER_PIPELINE)
+}
+
+/// Queue the commands writing key number `n`. Collections are replaced,
+/// not appended to, so each ends up with exactly `spec.elements`.
+fn queue_key(
+    pipe: &mut redis::Pipeline,
+    spec: &GeneratorSpec,
+    template: &KeyTemplate,
+    n: u64,
+    rng: &mut Rng,
+) {
+    let key = template.render(n, rng);
+    let size = spec.value_bytes as usize;
+    let elements = spec.elements;
+    if spec.key_type != GeneratedType::String {
+        pipe.cmd("UNLINK").arg(&key).ignore();
+    }
+    match spec.key_type {
+        GeneratedType::String => {
+            pipe.cmd("SET")
+                .arg(&key)
+                .arg(rng.alphanumeric(size))
+                .ignore();
+        }
+        GeneratedType::Hash => {
+            let cmd = pipe.cmd("HSET").arg(&key);
+            for i in 0..elements {
+                cmd.arg(format!("field:{i}")).arg(rng.alphanumeric(size));
+            }
+            cmd.ignore();
+        }
+        GeneratedType::List => {
+            let cmd = pipe.cmd("RPUSH").arg(&key);
+            for _ in 0..elements {
+                cmd.arg(rng.alphanumeric(size));
+            }
+            cmd.ignore();
+        }
+        GeneratedType::Set => {
+            let cmd = pipe.cmd("SADD").arg(&key);
+            for i in 0..elements {
+                cmd.arg(unique_member(i, size, rng));
+            }
+            cmd.ignore();
+        }
+        GeneratedType::Zset => {
+            let cmd = pipe.cmd("ZADD").arg(&key);
+            for i in 0..elements {
+                let score = spec.score_min + rng.unit() * (spec.score_max - spec.score_min);
+                cmd.arg(score).arg(unique_member(i, size, rng));
+            }
+            cmd.ignore();
+        }
+        GeneratedType::Stream => {
+            for _ in 0..elements {
+                pipe.cmd("XADD")
+                    .arg(&key)
+                    .arg("*")
+                    .arg("value")
+                    .arg(rng.alphanumeric(size))
+                    .ignore();
+            }
+        }
+    }
+    if let Some(ttl) = &spec.ttl {
+        if rng.between(1, 100) <= u64::from(ttl.percent) {
+            pipe.cmd("EXPIRE")
+                .arg(&key)
+                .arg(rng.between(ttl.min_secs, ttl.max_secs))
+                .ignore();
+        }
+    }
+}
+
+/// A member that differs from the key's other members: its number, padded
+/// with random characters up to `size`.
+fn unique_member(i: u32, size: usize, rng: &mut Rng) -> String {
+    let prefix = format!("{i}:");
+    let pad = size.saturating_sub(prefix.len());
+    prefix + &rng.alphanumeric(pad)
+}
+
+/// Write the keys `spec` describes, one pipeline at a time, handing the
+/// running count to `on_batch` after each. Returns the keys written.
+pub async fn generate(
+    pool: &Pool,
+    spec: &GeneratorSpec,
+    mut on_batch: impl FnMut(u64),
+) -> Result<u64, AppError> {
+    let template = KeyTemplate::parse(&spec.key_template)?;
+    let mut rng = Rng::new(spec.seed.unwrap_or_else(|| {
+        chrono::Utc::now()
+            .timestamp_nanos_opt()
+            .map_or(0, i64::unsigned_abs)
+    }));
+    let batch = keys_per_pipeline(spec);
+    let mut conn = pool.get().await?;
+    let mut written = 0;
+
+    while written < spec.count {
+        let in_batch = batch.min(spec.count - written);
+        let mut pipe = redis::pipe();
+        for n in written..written + in_batch {
+            queue_key(&mut pipe, spec, &template, spec.start + n, &mut rng);
+        }
+        pipe.query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("Writing generated keys failed: {e}")))?;
+        written += in_batch;
+        on_batch(written);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::devtools::model::TtlSpec;
+
+    #[test]
+    fn test_template_placeholders() {
+        let mut rng = Rng::new(7);
+        let template = KeyTemplate::parse("user:{n}:profile").unwrap();
+        assert_eq!(template.render(42, &mut rng), "user:42:profile");
+
+        let key = KeyTemplate::parse("s:{rand}").unwrap().render(0, &mut rng);
+        assert_eq!(key.len(), "s:".len() + 8);
+        let key = KeyTemplate::parse("{uuid}").unwrap().render(0, &mut rng);
+        assert!(uuid::Uuid::parse_str(&key).is_ok());
+
+        assert!(KeyTemplate::parse("static").is_err());
+        assert!(KeyTemplate::parse("a:{x}").is_err());
+        assert!(KeyTemplate::parse("a:{n").is_err());
+    }
+
+    #[test]
+    fn test_rng_is_seeded() {
+        let (mut a, mut b) = (Rng::new(1), Rng::new(1));
+        assert_eq!(a.alphanumeric(16), b.alphanumeric(16));
+        for _ in 0..1000 {
+            let v = a.between(5, 9);
+            assert!((5..=9).contains(&v));
+            assert!((0.0..1.0).contains(&a.unit()));
+        }
+        // The full range must not overflow.
+        a.between(0, u64::MAX);
+    }
+
+    #[test]
+    fn test_pipeline_size_follows_value_size() {
+        let spec = |key_type, value_bytes, elements| GeneratorSpec {
+            key_type,
+            count: 10,
+            key_template: "k:{n}".into(),
+            start: 0,
+            value_bytes,
+            elements,
+            score_min: 0.0,
+            score_max: 1.0,
+            ttl: Some(TtlSpec {
+                percent: 100,
+                min_secs: 1,
+                max_secs: 1,
+            }),
+            seed: Some(1),
+        };
+        assert_eq!(
+            keys_per_pipeline(&spec(GeneratedType::String, 16, 0)),
+            MAX_KEYS_PER_PIPELINE
+        );
+        assert_eq!(keys_per_pipeline(&spec(GeneratedType::Hash, 1024, 1024)), 4);
+        assert_eq!(
+            keys_per_pipeline(&spec(GeneratedType::List, 1024 * 1024, 64)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unique_member_pads_to_size() {
+        let mut rng = Rng::new(3);
+        let member = unique_member(12, 10, &mut rng);
+        assert!(member.starts_with("12:"));
+        assert_eq!(member.len(), 10);
+        assert_eq!(unique_member(12345, 2, &mut rng), "12345:");
+    }
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT
+
+//! Lock inspector: finds distributed lock keys (Redlock-style string locks
+//! and Redisson-style hash locks) and releases them on request.
+
+use std::collections::BTreeSet;
+
+use deadpool_redis::Pool;
+use redis::Value;
+
+use super::model::{LockInfo, LockScan};
+use crate::redis::browser::scanner;
+use crate::utils::errors::AppError;
+
+/// Patterns scanned when the user hasn't configured their own.
+pub const DEFAULT_LOCK_PATTERNS: &[&str] = &["lock:*", "locks:*", "*:lock", "*:lock:*"];
+
+/// Bytes of a string lock's value returned as its owner.
+const OWNER_MAX_BYTES: u32 = 256;
+
+/// Keys inspected per pipeline.
+const INSPECT_BATCH: usize = 500;
+
+/// Reads a lock's type, PTTL, idle time and owner in one round trip. The
+/// idle time is read first, so the inspector's own read doesn't reset it.
+const INSPECT_SCRIPT: &str = r"
+local t = redis.call('TYPE', KEYS[1]).ok
+if t == 'none' then return {t} end
+local ok, idle = pcall(redis.call, 'OBJECT', 'IDLETIME', KEYS[1])
+if not ok then idle = false end
+local owner, holds = false, false
+if t == 'string' then
+  owner = redis.call('GETRANGE', KEYS[1], 0, tonumber(ARGV[1]) - 1)
+elseif t == 'hash' and redis.call('HLEN', KEYS[1]) == 1 then
+  local entry = redis.call('HGETALL', KEYS[1])
+  owner = entry[1]
+  holds = tonumber(entry[2]) or false
+end
+return {t, redis.call('PTTL', KEYS[1]), idle, owner, holds}
+";
+
+/// Deletes a lock only while `ARGV[1]` still owns it.
+const RELEASE_IF_OWNER_SCRIPT: &str = r"
+local t = redis.call('TYPE', KEYS[1]).ok
+if (t == 'string' and redis.call('GET', KEYS[1]) == ARGV[1])
+  or (t == 'hash' and redis.call('HEXISTS', KEYS[1], ARGV[1]) == 1) then
+  return redis.call('DEL', KEYS[1])
+end
+return 0
+";
+
+/// Find keys matching any of `patterns`, up to `limit`, and inspect them.
+///
+/// With `lease_ms` (the TTL locks are taken with), each lock's age is
+/// derived from its remaining TTL.
+pub async fn scan_locks(
+    pool: &Pool,
+    patterns: &[String],
+    lease_ms: Option<u64>,
+    limit: u32,
+) -> Result<LockScan, AppError> {
+    let limit = limit as usize;
+    let mut keys = BTreeSet::new();
+    let mut truncated = false;
+    {
+        let mut conn = pool.get().await?;
+        'patterns: for pattern in patterns {
+            let mut cursor = 0;
+            loop {
+                let (next, batch) = scanner::scan_match(&mut conn, cursor, pattern).await?;
+                for key in batch {
+                    if keys.len() == limit && !keys.contains(&key) {
+                        truncated = true;
+                        break 'patterns;
+                    }
+                    keys.insert(key);
+                }
+                cursor = next;
+                if cursor == 0 {
+                    break;
+                }
+            }
+        }
+    }
+
+    let keys: Vec<String> = keys.into_iter().collect();
+    let mut locks = Vec::with_capacity(keys.len());
+    for batch in keys.chunks(INSPECT_BATCH) {
+        // Keys deleted or expired since the scan drop out here.
+        locks.extend(inspect(pool, batch, lease_ms).await?.into_iter().flatten());
+    }
+    locks.sort_by_key(|lock| (lock.ttl_ms.is_none(), lock.ttl_ms));
+    Ok(LockScan { locks, truncated })
+}
+
+/// Inspect one lock; `None` if it no longer exists.
+pub async fn inspect_lock(pool: &Pool, key: &str) -> Result<Option<LockInfo>, AppError> {
+    Ok(inspect(pool, &[key.to_string()], None)
+        .await?
+        .pop()
+        .flatten())
+}
+
+async fn inspect(
+    pool: &Pool,
+    keys: &[String],
+    lease_ms: Option<u64>,
+) -> Result<Vec<Option<LockInfo>>, AppError> {
+    let mut conn = pool.get().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("EVAL")
+            .arg(INSPECT_SCRIPT)
+            .arg(1)
+            .arg(key)
+            .arg(OWNER_MAX_BYTES);
+    }
+    let replies: Vec<Vec<Value>> = pipe
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Lock inspection failed: {e}")))?;
+    keys.iter()
+        .zip(replies)
+        .map(|(key, reply)| lock_from_reply(key, &reply, lease_ms))
+        .collect()
+}
+
+/// Parse an [`INSPECT_SCRIPT`] reply; `None` if the key is missing.
+fn lock_from_reply(
+    key: &str,
+    reply: &[Value],
+    lease_ms: Option<u64>,
+) -> Result<Option<LockInfo>, AppError> {
+    let parse_err = |e: redis::RedisError| AppError::Redis(format!("Bad lock reply: {e}"));
+    let [key_type, pttl, idle, owner, holds] = reply else {
+        return Ok(None);
+    };
+    let pttl: i64 = redis::from_redis_value(pttl).map_err(parse_err)?;
+    let owner: Option<Vec<u8>> = redis::from_redis_value(owner).map_err(parse_err)?;
+    let ttl_ms = (pttl >= 0).then_some(pttl);
+    Ok(Some(LockInfo {
+        key: key.to_string(),
+        key_type: redis::from_redis_value(key_type).map_err(parse_err)?,
+        owner: owner.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+        holds: redis::from_redis_value(holds).map_err(parse_err)?,
+        ttl_ms,
+        idle_secs: redis::from_redis_value(idle).map_err(parse_err)?,
+        age_ms: lease_ms
+            .and_then(|lease| i64::try_from(lease).ok())
+            .zip(ttl_ms)
+            .map(|(lease, ttl)| (lease - ttl).max(0)),
+    }))
+}
+
+/// Delete a lock. With `owner`, only while that token still holds it, so a
+/// lock re-acquired by someone else in the meantime is left alone. Returns
+/// whether the lock was deleted.
+pub async fn release_lock(pool: &Pool, key: &str, owner: Option<&str>) -> Result<bool, AppError> {
+    let mut conn = pool.get().await?;
+    let deleted: u64 = match owner {
+        Some(owner) => redis::cmd("EVAL")
+            .arg(RELEASE_IF_OWNER_SCRIPT)
+            .arg(1)
+            .arg(key)
+            .arg(owner)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("Lock release failed: {e}")))?,
+        None => redis::cmd("DEL")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("DEL failed: {e}")))?,
+    };
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_from_reply_string_lock() {
+        let reply = [
+            Value::SimpleString("string".into()),
+            Value::Int(7_000),
+            Value::Int(3),
+            Value::BulkString(b"token-1".to_vec()),
+            Value::Nil,
+        ];
+        let lock = lock_from_reply("lock:a", &reply, Some(30_000))
+            .unwrap()
+            .unwrap();
+        assert_eq!(lock.owner.as_deref(), Some("token-1"));
+        assert_eq!(lock.ttl_ms, Some(7_000));
+        assert_eq!(lock.idle_secs, Some(3));
+        assert_eq!(lock.age_ms, Some(23_000));
+        assert_eq!(lock.holds, None);
+    }
+
+    #[test]
+    fn test_lock_from_reply_hash_lock_without_ttl() {
+        let reply = [
+            Value::SimpleString("hash".into()),
+            Value::Int(-1),
+            Value::Nil,
+            Value::BulkString(b"uuid:1".to_vec()),
+            Value::Int(2),
+        ];
+        let lock = lock_from_reply("lock:b", &reply, Some(30_000))
+            .unwrap()
+            .unwrap();
+        assert_eq!(lock.holds, Some(2));
+        assert_eq!(lock.ttl_ms, None);
+        assert_eq!(lock.age_ms, None);
+        assert_eq!(lock.idle_secs, None);
+
+        let missing = [Value::SimpleString("none".into())];
+        assert!(lock_from_reply("lock:c", &missing, None).unwrap().is_none());
+    }
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: MIT
+
+pub mod benchmark;
+pub mod generator;
+pub mod locks;
+pub mod model;
+pub mod replay;
+pub mod tracking;
@@ -0,0 +1,540 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::AppError;
+
+/// Most keys one generation may write.
+pub const MAX_KEYS: u64 = 5_000_000;
+/// Largest generated string value or element.
+pub const MAX_VALUE_BYTES: u32 = 1024 * 1024;
+/// Most elements per generated collection.
+pub const MAX_ELEMENTS: u32 = 100_000;
+/// Most data one generated key may hold (elements × value size).
+pub const MAX_BYTES_PER_KEY: u64 = 64 * 1024 * 1024;
+
+/// Type of the keys to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeneratedType {
+    String,
+    Hash,
+    List,
+    Set,
+    Zset,
+    Stream,
+}
+
+impl GeneratedType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Hash => "hash",
+            Self::List => "list",
+            Self::Set => "set",
+            Self::Zset => "zset",
+            Self::Stream => "stream",
+        }
+    }
+}
+
+/// Expiry given to a share of the generated keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TtlSpec {
+    /// Share of keys that get a TTL, 0–100.
+    pub percent: u8,
+    /// TTLs are drawn uniformly from `min_secs..=max_secs`.
+    pub min_secs: u64,
+    pub max_secs: u64,
+}
+
+/// What `devtools_generate` writes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratorSpec {
+    pub key_type: GeneratedType,
+    pub count: u64,
+    /// Key name with placeholders: `{n}` is the key's number (from
+    /// `start`), `{rand}` a random 8-digit number, `{uuid}` a random UUID.
+    pub key_template: String,
+    #[serde(default)]
+    pub start: u64,
+    /// Size of each string value, field value, element or member.
+    pub value_bytes: u32,
+    /// Fields, elements, members or entries per collection key.
+    #[serde(default = "default_elements")]
+    pub elements: u32,
+    #[serde(default)]
+    pub score_min: f64,
+    #[serde(default = "default_score_max")]
+    pub score_max: f64,
+    #[serde(default)]
+    pub ttl: Option<TtlSpec>,
+    /// Seed for the random values; the same seed generates the same data.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_elements() -> u32 {
+    10
+}
+
+fn default_score_max() -> f64 {
+    1000.0
+}
+
+impl GeneratorSpec {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.count == 0 || self.count > MAX_KEYS {
+            return Err(AppError::InvalidInput(format!(
+                "Key count must be between 1 and {MAX_KEYS}"
+            )));
+        }
+        if self.value_bytes == 0 || self.value_bytes > MAX_VALUE_BYTES {
+            return Err(AppError::InvalidInput(format!(
+                "Value size must be between 1 and {MAX_VALUE_BYTES} bytes"
+            )));
+        }
+        if self.key_type != GeneratedType::String {
+            if self.elements == 0 || self.elements > MAX_ELEMENTS {
+                return Err(AppError::InvalidInput(format!(
+                    "Elements per key must be between 1 and {MAX_ELEMENTS}"
+                )));
+            }
+            if u64::from(self.elements) * u64::from(self.value_bytes) > MAX_BYTES_PER_KEY {
+                return Err(AppError::InvalidInput(
+                    "Elements × value size is over 64 MiB per key".into(),
+                ));
+            }
+        }
+        if !self.score_min.is_finite()
+            || !self.score_max.is_finite()
+            || self.score_min > self.score_max
+        {
+            return Err(AppError::InvalidInput(
+                "Score range must be finite, with min at most max".into(),
+            ));
+        }
+        if let Some(ttl) = &self.ttl {
+            if ttl.percent > 100 {
+                return Err(AppError::InvalidInput(
+                    "TTL share must be between 0 and 100 percent".into(),
+                ));
+            }
+            if ttl.min_secs == 0 || ttl.min_secs > ttl.max_secs {
+                return Err(AppError::InvalidInput(
+                    "TTL range must start at 1 second or more, with min at most max".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Approximate bytes written per key, used to size pipelines.
+    pub fn bytes_per_key(&self) -> u64 {
+        let elements = match self.key_type {
+            GeneratedType::String => 1,
+            _ => u64::from(self.elements),
+        };
+        elements * u64::from(self.value_bytes)
+    }
+}
+
+/// Longest benchmark run.
+pub const MAX_BENCH_SECS: u32 = 300;
+/// Most concurrent benchmark clients.
+pub const MAX_BENCH_CLIENTS: u32 = 64;
+/// Deepest benchmark pipeline.
+pub const MAX_BENCH_PIPELINE: u32 = 1_000;
+
+/// A command the benchmark can issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BenchCommand {
+    Ping,
+    Get,
+    Set,
+    Incr,
+    Lpush,
+    Lpop,
+    Sadd,
+    Hset,
+    Zadd,
+}
+
+impl BenchCommand {
+    pub fn is_write(self) -> bool {
+        !matches!(self, Self::Ping | Self::Get)
+    }
+}
+
+/// One command of the workload and its share of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchWeight {
+    pub command: BenchCommand,
+    pub weight: u32,
+}
+
+/// Workload for `devtools_benchmark`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchSpec {
+    /// Commands are picked at random in proportion to their weights.
+    pub mix: Vec<BenchWeight>,
+    #[serde(default = "default_clients")]
+    pub clients: u32,
+    /// Commands sent per round trip.
+    #[serde(default = "default_pipeline")]
+    pub pipeline: u32,
+    #[serde(default = "default_duration_secs")]
+    pub duration_secs: u32,
+    /// Distinct keys per data type the commands spread over.
+    #[serde(default = "default_keyspace")]
+    pub keyspace: u64,
+    #[serde(default = "default_value_bytes")]
+    pub value_bytes: u32,
+}
+
+fn default_clients() -> u32 {
+    4
+}
+
+fn default_pipeline() -> u32 {
+    1
+}
+
+fn default_duration_secs() -> u32 {
+    10
+}
+
+fn default_keyspace() -> u64 {
+    10_000
+}
+
+fn default_value_bytes() -> u32 {
+    3
+}
+
+impl BenchSpec {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.mix.is_empty() || self.mix.iter().all(|w| w.weight == 0) {
+            return Err(AppError::InvalidInput(
+                "The workload needs at least one command with a weight".into(),
+            ));
+        }
+        if self.clients == 0 || self.clients > MAX_BENCH_CLIENTS {
+            return Err(AppError::InvalidInput(format!(
+                "Clients must be between 1 and {MAX_BENCH_CLIENTS}"
+            )));
+        }
+        if self.pipeline == 0 || self.pipeline > MAX_BENCH_PIPELINE {
+            return Err(AppError::InvalidInput(format!(
+                "Pipeline depth must be between 1 and {MAX_BENCH_PIPELINE}"
+            )));
+        }
+        if self.duration_secs == 0 || self.duration_secs > MAX_BENCH_SECS {
+            return Err(AppError::InvalidInput(format!(
+                "Duration must be between 1 and {MAX_BENCH_SECS} seconds"
+            )));
+        }
+        if self.keyspace == 0 {
+            return Err(AppError::InvalidInput(
+                "Keyspace must hold at least one key".into(),
+            ));
+        }
+        if self.value_bytes == 0 || self.value_bytes > MAX_VALUE_BYTES {
+            return Err(AppError::InvalidInput(format!(
+                "Value size must be between 1 and {MAX_VALUE_BYTES} bytes"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether any weighted command writes.
+    pub fn writes(&self) -> bool {
+        self.mix
+            .iter()
+            .any(|w| w.weight > 0 && w.command.is_write())
+    }
+}
+
+/// Latency percentiles of a benchmark, in microseconds per round trip.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    pub min_us: u64,
+    pub mean_us: f64,
+    pub p50_us: u64,
+    pub p90_us: u64,
+    pub p99_us: u64,
+    pub p999_us: u64,
+    pub max_us: u64,
+}
+
+/// Payload of `devtools:bench-figures`, emitted about once a second and
+/// once more with the final figures, just before the run's last
+/// `task:progress` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchFigures {
+    pub benchmark_id: String,
+    pub connection_id: String,
+    pub elapsed_ms: u64,
+    /// Commands answered so far.
+    pub total_ops: u64,
+    /// Commands answered with an error.
+    pub errors: u64,
+    /// Over the last second, or the whole run on the last event.
+    pub ops_per_sec: f64,
+    pub latency: LatencySummary,
+}
+
+/// How often one key was invalidated while tracking.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyInvalidations {
+    pub key: String,
+    pub count: u64,
+    /// Milliseconds since tracking started.
+    pub last_at_ms: u64,
+}
+
+/// Figures of a client-side caching (CLIENT TRACKING) session.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackingStats {
+    pub connection_id: String,
+    /// Key prefixes tracked; empty means every key.
+    pub prefixes: Vec<String>,
+    pub elapsed_ms: u64,
+    /// False once the tracking connection dropped; figures stop growing.
+    pub active: bool,
+    pub total_invalidations: u64,
+    pub distinct_keys: u64,
+    /// Invalidations of keys beyond the per-session key limit, counted in
+    /// the total but not per key.
+    pub untracked_invalidations: u64,
+    /// Whole-keyspace invalidations (FLUSHDB/FLUSHALL).
+    pub flushes: u64,
+    /// Over the whole session.
+    pub invalidations_per_sec: f64,
+    /// Over the last 10 seconds.
+    pub recent_invalidations_per_sec: f64,
+    /// Most-invalidated keys, busiest first.
+    pub top_keys: Vec<KeyInvalidations>,
+}
+
+/// Fastest replay rate, in commands per second.
+pub const MAX_REPLAY_RATE: u32 = 100_000;
+
+/// How a replay file is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayFormat {
+    /// RESP-encoded commands, as in an append-only file.
+    Aof,
+    /// One command per line in `redis-cli` syntax, or MONITOR output.
+    Lines,
+}
+
+/// What to replay and how fast.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaySpec {
+    /// The command file to read.
+    pub path: String,
+    /// Detected from the content when unset.
+    pub format: Option<ReplayFormat>,
+    /// Commands per second; as fast as the server answers when unset.
+    pub rate_per_sec: Option<u32>,
+    /// Stop at the first command the server rejects.
+    #[serde(default)]
+    pub stop_on_error: bool,
+}
+
+impl ReplaySpec {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.path.trim().is_empty() {
+            return Err(AppError::InvalidInput("Choose a file to replay".into()));
+        }
+        if self
+            .rate_per_sec
+            .is_some_and(|rate| rate == 0 || rate > MAX_REPLAY_RATE)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Replay rate must be between 1 and {MAX_REPLAY_RATE} commands per second"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A command that was not replayed, or that the server rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayError {
+    /// Position of the command in the file, from 0.
+    pub index: u64,
+    /// Line of the file the command starts on, from 1.
+    pub line: u64,
+    pub command: String,
+    pub error: String,
+}
+
+/// Outcome of validating or replaying a command file.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReport {
+    /// Commands read from the file.
+    pub total: u64,
+    /// Commands refused before sending (dangerous, blocking, unknown,
+    /// wrong arity, or writes on a read-only connection).
+    pub rejected: u64,
+    pub sent: u64,
+    /// Sent commands the server answered with an error.
+    pub failed: u64,
+    /// The file ended in the middle of a command, which was dropped.
+    pub truncated: bool,
+    /// The first rejected commands, then the first the server failed, each
+    /// in file order.
+    pub errors: Vec<ReplayError>,
+}
+
+/// Payload of `devtools:replay-report`, emitted once when a replay ends,
+/// also when it stopped early, just before its last `task:progress` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFinished {
+    pub replay_id: String,
+    pub connection_id: String,
+    pub report: ReplayReport,
+}
+
+/// Most locks a lock scan returns.
+pub const MAX_LOCK_RESULTS: u32 = 10_000;
+
+/// A lock key found by the lock inspector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockInfo {
+    pub key: String,
+    pub key_type: String,
+    /// The owner token: a string lock's value (its first 256 bytes), or the
+    /// single field of a hash lock (Redisson-style `uuid:threadId`).
+    pub owner: Option<String>,
+    /// Re-entrant hold count of a hash lock.
+    pub holds: Option<u64>,
+    /// Remaining TTL; `None` if the lock never expires.
+    pub ttl_ms: Option<i64>,
+    /// Seconds since the key was last written or read (OBJECT IDLETIME),
+    /// read before the inspector touches it. `None` under an LFU eviction
+    /// policy, where Redis doesn't track it.
+    pub idle_secs: Option<u64>,
+    /// Time since the lock was taken, when the lease it was taken with is
+    /// known: the lease minus the remaining TTL.
+    pub age_ms: Option<i64>,
+}
+
+/// Result of a lock scan.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockScan {
+    /// Sorted by remaining TTL, soonest to expire first; locks without a
+    /// TTL last.
+    pub locks: Vec<LockInfo>,
+    /// More keys matched than the scan's limit.
+    pub truncated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(key_type: GeneratedType) -> GeneratorSpec {
+        GeneratorSpec {
+            key_type,
+            count: 100,
+            key_template: "user:{n}".into(),
+            start: 0,
+            value_bytes: 32,
+            elements: default_elements(),
+            score_min: 0.0,
+            score_max: default_score_max(),
+            ttl: None,
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn test_bench_spec_validation() {
+        let spec = |command, weight| BenchSpec {
+            mix: vec![BenchWeight { command, weight }],
+            clients: default_clients(),
+            pipeline: default_pipeline(),
+            duration_secs: default_duration_secs(),
+            keyspace: default_keyspace(),
+            value_bytes: default_value_bytes(),
+        };
+        assert!(spec(BenchCommand::Get, 1).validate().is_ok());
+        assert!(!spec(BenchCommand::Get, 1).writes());
+        assert!(spec(BenchCommand::Set, 1).writes());
+        assert!(!spec(BenchCommand::Set, 0).writes());
+        assert!(spec(BenchCommand::Ping, 0).validate().is_err());
+        assert!(BenchSpec {
+            clients: MAX_BENCH_CLIENTS + 1,
+            ..spec(BenchCommand::Ping, 1)
+        }
+        .validate()
+        .is_err());
+        assert!(BenchSpec {
+            duration_secs: 0,
+            ..spec(BenchCommand::Ping, 1)
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        assert!(spec(GeneratedType::Hash).validate().is_ok());
+        assert!(GeneratorSpec {
+            count: 0,
+            ..spec(GeneratedType::String)
+        }
+        .validate()
+        .is_err());
+        assert!(GeneratorSpec {
+            elements: MAX_ELEMENTS,
+            value_bytes: 1024,
+            ..spec(GeneratedType::List)
+        }
+        .validate()
+        .is_err());
+        // Strings ignore the element count.
+        assert!(GeneratorSpec {
+            elements: 0,
+            ..spec(GeneratedType::String)
+        }
+        .validate()
+        .is_ok());
+        assert!(GeneratorSpec {
+            score_min: 5.0,
+            score_max: 1.0,
+            ..spec(GeneratedType::Zset)
+        }
+        .validate()
+        .is_err());
+        assert!(GeneratorSpec {
+            ttl: Some(TtlSpec {
+                percent: 50,
+                min_secs: 60,
+                max_secs: 30
+            }),
+            ..spec(GeneratedType::String)
+        }
+        .validate()
+        .is_err());
+    }
+}
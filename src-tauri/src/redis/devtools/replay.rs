@@ -0,0 +1,434 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use redis::aio::MultiplexedConnection;
+use tokio::time::Instant;
+
+use super::model::{ReplayError, ReplayFormat, ReplayReport, ReplaySpec};
+use crate::redis::cli::broadcast::BLOCKING_COMMANDS;
+use crate::redis::cli::command_docs::CommandCatalog;
+use crate::redis::cli::parser;
+use crate::redis::connection::model::CommandPolicy;
+use crate::redis::connection::{command_policy, write_guard};
+use crate::utils::errors::AppError;
+
+/// Largest file that can be replayed.
+const MAX_FILE_BYTES: u64 = 256 * 1024 * 1024;
+/// Most commands one replay may send.
+const MAX_COMMANDS: usize = 1_000_000;
+/// Rejected and failed commands kept in a report; later ones are only counted.
+const MAX_REPORTED_ERRORS: usize = 100;
+/// Commands between progress events.
+pub const PROGRESS_EVERY: u64 = 500;
+
+/// One command read from a replay file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayCommand {
+    /// Line of the file the command starts on, from 1.
+    pub line: u64,
+    pub args: Vec<Vec<u8>>,
+}
+
+impl ReplayCommand {
+    /// The arguments as text, for validation and reports.
+    fn text_args(&self) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect()
+    }
+
+    fn display(&self) -> String {
+        self.text_args()
+            .iter()
+            .map(|arg| parser::quote_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The commands in a replay file, and whether its last command was cut off.
+#[derive(Debug, Default)]
+pub struct ReplayFile {
+    pub commands: Vec<ReplayCommand>,
+    pub truncated: bool,
+}
+
+/// Read and parse a command file. The format is detected from the first
+/// byte unless given: RESP arrays start with `*`.
+pub async fn read_file(path: &str, format: Option<ReplayFormat>) -> Result<ReplayFile, AppError> {
+    let meta = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| AppError::NotFound(format!("Cannot open {path}: {e}")))?;
+    if meta.len() > MAX_FILE_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "Replay files are limited to {} MiB",
+            MAX_FILE_BYTES / 1024 / 1024
+        )));
+    }
+    let data = tokio::fs::read(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read {path}: {e}")))?;
+    parse(&data, format)
+}
+
+/// Parse a command file already in memory.
+pub fn parse(data: &[u8], format: Option<ReplayFormat>) -> Result<ReplayFile, AppError> {
+    if data.starts_with(b"REDIS") {
+        return Err(AppError::InvalidInput(
+            "This is an RDB file (or an AOF with an RDB preamble); replay the incremental AOF instead"
+                .into(),
+        ));
+    }
+    let format = format.unwrap_or_else(|| match data.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'*') => ReplayFormat::Aof,
+        _ => ReplayFormat::Lines,
+    });
+    let file = match format {
+        ReplayFormat::Aof => parse_aof(data)?,
+        ReplayFormat::Lines => parse_lines(data),
+    };
+    if file.commands.len() > MAX_COMMANDS {
+        return Err(AppError::InvalidInput(format!(
+            "Replay files are limited to {MAX_COMMANDS} commands"
+        )));
+    }
+    Ok(file)
+}
+
+/// RESP arrays of bulk strings, one per command. A command cut off at the
+/// end of the file (a tail copied while being written) is dropped.
+fn parse_aof(data: &[u8]) -> Result<ReplayFile, AppError> {
+    let mut reader = RespReader {
+        data,
+        pos: 0,
+        line: 1,
+    };
+    let mut file = ReplayFile::default();
+
+    loop {
+        reader.skip_blank();
+        if reader.pos >= data.len() {
+            break;
+        }
+        let line = reader.line;
+        match reader.command() {
+            Ok(Some(args)) => file.commands.push(ReplayCommand { line, args }),
+            Ok(None) => {
+                file.truncated = true;
+                break;
+            }
+            Err(message) => {
+                return Err(AppError::InvalidInput(format!(
+                    "Malformed AOF at line {line}: {message}"
+                )))
+            }
+        }
+    }
+    Ok(file)
+}
+
+struct RespReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    line: u64,
+}
+
+impl RespReader<'_> {
+    fn skip_blank(&mut self) {
+        while let Some(&b) = self.data.get(self.pos) {
+            if !b.is_ascii_whitespace() {
+                break;
+            }
+            if b == b'\n' {
+                self.line += 1;
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// The next `\r\n`-terminated header line, or `None` at the end of data.
+    fn header(&mut self) -> Option<&[u8]> {
+        let rest = &self.data[self.pos..];
+        let end = rest.windows(2).position(|w| w == b"\r\n")?;
+        self.pos += end + 2;
+        self.line += 1;
+        Some(&rest[..end])
+    }
+
+    fn length(&mut self, marker: u8) -> Result<Option<usize>, String> {
+        let Some(header) = self.header() else {
+            return Ok(None);
+        };
+        match header.split_first() {
+            Some((&m, digits)) if m == marker => std::str::from_utf8(digits)
+                .ok()
+                .and_then(|d| d.parse().ok())
+                .map(Some)
+                .ok_or_else(|| format!("bad length {:?}", String::from_utf8_lossy(digits))),
+            _ => Err(format!(
+                "expected '{}', found {:?}",
+                char::from(marker),
+                String::from_utf8_lossy(header)
+            )),
+        }
+    }
+
+    /// One command, or `None` if the data ends inside it.
+    fn command(&mut self) -> Result<Option<Vec<Vec<u8>>>, String> {
+        let Some(count) = self.length(b'*')? else {
+            return Ok(None);
+        };
+        let mut args = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            let Some(len) = self.length(b'$')? else {
+                return Ok(None);
+            };
+            let end = self
+                .pos
+                .checked_add(len)
+                .ok_or_else(|| "bad length".to_string())?;
+            let Some(arg) = self.data.get(self.pos..end) else {
+                return Ok(None);
+            };
+            match self.data.get(end..end + 2) {
+                Some(b"\r\n") => {}
+                Some(_) => return Err("bulk string longer than its length".into()),
+                None => return Ok(None),
+            }
+            self.line += arg.split(|&b| b == b'\n').count() as u64;
+            args.push(arg.to_vec());
+            self.pos = end + 2;
+        }
+        Ok(Some(args))
+    }
+}
+
+/// One command per line in `redis-cli` syntax. Blank lines, `#` comments
+/// and MONITOR's `OK` are skipped; MONITOR's timestamp and client prefix
+/// (`1700000000.123456 [0 127.0.0.1:6379] "SET" "k" "v"`) is stripped.
+fn parse_lines(data: &[u8]) -> ReplayFile {
+    let text = String::from_utf8_lossy(data);
+    let mut file = ReplayFile::default();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line == "OK" {
+            continue;
+        }
+        let args = parser::parse_command(strip_monitor_prefix(line));
+        if args.is_empty() {
+            continue;
+        }
+        file.commands.push(ReplayCommand {
+            line: n as u64 + 1,
+            args: args.into_iter().map(String::into_bytes).collect(),
+        });
+    }
+    file
+}
+
+fn strip_monitor_prefix(line: &str) -> &str {
+    let Some((stamp, rest)) = line.split_once(' ') else {
+        return line;
+    };
+    if stamp.parse::<f64>().is_err() || !rest.starts_with('[') {
+        return line;
+    }
+    rest.split_once("] ").map_or(line, |(_, command)| command)
+}
+
+/// Sort commands into those that may be sent and report the rest: blocking
+/// and dangerous commands are never replayed, nor are those `policy` blocks
+/// or wants confirmed (it may also allow a dangerous one), writes are
+/// refused on read-only connections, and unknown commands or wrong argument counts
+/// are caught when the server reported its command table.
+pub fn check<'a>(
+    commands: &'a [ReplayCommand],
+    readonly: bool,
+    policy: &CommandPolicy,
+    catalog: &CommandCatalog,
+    truncated: bool,
+) -> (Vec<(u64, &'a ReplayCommand)>, ReplayReport) {
+    let mut report = ReplayReport {
+        total: commands.len() as u64,
+        truncated,
+        ..ReplayReport::default()
+    };
+    let mut runnable = Vec::new();
+
+    for (index, command) in commands.iter().enumerate() {
+        let index = index as u64;
+        let args = command.text_args();
+        let name = args[0].to_uppercase();
+        let outcome = if BLOCKING_COMMANDS.contains(&name.as_str()) {
+            Err(format!("{name} blocks and is not replayed"))
+        } else {
+            match command_policy::check(policy, &args) {
+                Ok(Some(_)) => Err(format!("{name} is dangerous and is not replayed")),
+                Ok(None) => write_guard::check_command(readonly, &args)
+                    .and_then(|()| catalog.validate(&args))
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        };
+        match outcome {
+            Ok(()) => runnable.push((index, command)),
+            Err(error) => {
+                report.rejected += 1;
+                note_error(&mut report, index, command, error);
+            }
+        }
+    }
+    (runnable, report)
+}
+
+fn note_error(report: &mut ReplayReport, index: u64, command: &ReplayCommand, error: String) {
+    if report.errors.len() < MAX_REPORTED_ERRORS {
+        report.errors.push(ReplayError {
+            index,
+            line: command.line,
+            command: command.display(),
+            error,
+        });
+    }
+}
+
+/// Send `runnable` on `conn` in file order, at most `rate_per_sec` a
+/// second, recording failures in `report` and handing it to `on_progress`
+/// every [`PROGRESS_EVERY`] commands. Ends early when the connection
+/// breaks, or at the first failure with `stop_on_error`.
+pub async fn run(
+    conn: &mut MultiplexedConnection,
+    runnable: &[(u64, &ReplayCommand)],
+    spec: &ReplaySpec,
+    report: &mut ReplayReport,
+    mut on_progress: impl FnMut(&ReplayReport),
+) -> Result<(), AppError> {
+    let pace = spec
+        .rate_per_sec
+        .map(|rate| Duration::from_secs(1) / rate.max(1));
+    let started = Instant::now();
+
+    for (n, (index, command)) in runnable.iter().enumerate() {
+        if let Some(pace) = pace {
+            tokio::time::sleep_until(started + pace * u32::try_from(n).unwrap_or(u32::MAX)).await;
+        }
+        let mut cmd = redis::cmd(&String::from_utf8_lossy(&command.args[0]).to_uppercase());
+        for arg in &command.args[1..] {
+            cmd.arg(arg.as_slice());
+        }
+        let outcome = cmd.query_async::<redis::Value>(conn).await;
+        report.sent += 1;
+        match outcome {
+            Ok(_) => {}
+            Err(e) if e.is_io_error() || e.is_connection_dropped() => {
+                return Err(AppError::Connection(format!("Replay connection lost: {e}")));
+            }
+            Err(e) => {
+                report.failed += 1;
+                note_error(report, *index, command, e.to_string());
+                if spec.stop_on_error {
+                    return Err(AppError::Redis(format!(
+                        "Stopped at line {}: {e}",
+                        command.line
+                    )));
+                }
+            }
+        }
+        if report.sent.is_multiple_of(PROGRESS_EVERY) {
+            on_progress(report);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &ReplayCommand) -> Vec<String> {
+        command.text_args()
+    }
+
+    #[test]
+    fn test_parse_aof() {
+        let data = b"*2\r\n$6\r\nSELECT\r\n$1\r\n0\r\n*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$5\r\na\r\nbc\r\n*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$3\r\nv";
+        let file = parse(data, None).unwrap();
+        assert_eq!(file.commands.len(), 2);
+        assert!(file.truncated);
+        assert_eq!(args(&file.commands[0]), ["SELECT", "0"]);
+        assert_eq!(file.commands[1].args[2], b"a\r\nbc");
+        assert_eq!(file.commands[1].line, 6);
+
+        assert!(parse(b"*1\r\n+PING\r\n", Some(ReplayFormat::Aof)).is_err());
+        assert!(parse(b"REDIS0011...", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_lines_and_monitor_output() {
+        let data = b"# seed\nSET user:1 \"Ada Lovelace\"\n\nOK\n1700000000.123456 [0 127.0.0.1:51234] \"HSET\" \"h\" \"f\" \"v\"\n";
+        let file = parse(data, None).unwrap();
+        assert!(!file.truncated);
+        assert_eq!(file.commands.len(), 2);
+        assert_eq!(args(&file.commands[0]), ["SET", "user:1", "Ada Lovelace"]);
+        assert_eq!(file.commands[1].line, 5);
+        assert_eq!(args(&file.commands[1]), ["HSET", "h", "f", "v"]);
+    }
+
+    #[test]
+    fn test_check_rejects_without_sending() {
+        let file = parse(b"GET a\nFLUSHALL\nBLPOP q 0\nSET a 1\n", None).unwrap();
+        let catalog = CommandCatalog::default();
+
+        let policy = CommandPolicy::default();
+
+        let (runnable, report) = check(&file.commands, false, &policy, &catalog, false);
+        assert_eq!(runnable.len(), 2);
+        assert_eq!(report.rejected, 2);
+        assert_eq!(report.errors[0].line, 2);
+        assert_eq!(report.errors[1].command, "BLPOP q 0");
+
+        let (runnable, report) = check(&file.commands, true, &policy, &catalog, false);
+        assert_eq!(runnable.len(), 1);
+        assert_eq!(report.rejected, 3);
+        assert_eq!(runnable[0].0, 0);
+
+        let policy = CommandPolicy {
+            block: vec!["SET".into()],
+            allow: vec!["FLUSHALL".into()],
+            ..CommandPolicy::default()
+        };
+        let (runnable, report) = check(&file.commands, false, &policy, &catalog, false);
+        assert_eq!(runnable.len(), 2);
+        assert_eq!(runnable[1].0, 1);
+        assert_eq!(
+            report.errors[1].error,
+            "Permission denied: SET is blocked on this connection"
+        );
+    }
+
+    #[test]
+    fn test_spec_validate() {
+        let spec = ReplaySpec {
+            path: "/tmp/commands.txt".into(),
+            format: None,
+            rate_per_sec: Some(0),
+            stop_on_error: false,
+        };
+        assert!(spec.validate().is_err());
+        assert!(ReplaySpec {
+            rate_per_sec: Some(1_000),
+            ..spec.clone()
+        }
+        .validate()
+        .is_ok());
+        assert!(ReplaySpec {
+            path: " ".into(),
+            rate_per_sec: None,
+            ..spec
+        }
+        .validate()
+        .is_err());
+    }
+}
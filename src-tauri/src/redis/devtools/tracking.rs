@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncConnectionConfig, PushInfo, PushKind, Value};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::model::{KeyInvalidations, TrackingStats};
+use crate::redis::connection::uri::resp3_url;
+use crate::utils::errors::AppError;
+
+/// Keys counted individually per session; later keys only add to the total.
+const MAX_TRACKED_KEYS: usize = 100_000;
+/// Most prefixes one session may track.
+const MAX_PREFIXES: usize = 32;
+/// Window of the recent invalidation rate.
+const RATE_WINDOW_SECS: u64 = 10;
+pub const DEFAULT_TOP_KEYS: usize = 20;
+const MAX_TOP_KEYS: usize = 1_000;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Validate the prefixes of a tracking session.
+pub fn validate_prefixes(prefixes: &[String]) -> Result<(), AppError> {
+    if prefixes.len() > MAX_PREFIXES {
+        return Err(AppError::InvalidInput(format!(
+            "At most {MAX_PREFIXES} prefixes can be tracked"
+        )));
+    }
+    if prefixes.iter().any(String::is_empty) {
+        return Err(AppError::InvalidInput(
+            "Prefixes must not be empty; leave the list empty to track every key".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Invalidations seen by one session.
+#[derive(Debug)]
+struct InvalidationLog {
+    prefixes: Vec<String>,
+    started: Instant,
+    active: bool,
+    keys: HashMap<String, (u64, u64)>,
+    total: u64,
+    untracked: u64,
+    flushes: u64,
+    /// Invalidations per second since start, for the last `RATE_WINDOW_SECS`.
+    recent: VecDeque<(u64, u64)>,
+}
+
+impl InvalidationLog {
+    fn new(prefixes: Vec<String>) -> Self {
+        Self {
+            prefixes,
+            started: Instant::now(),
+            active: true,
+            keys: HashMap::new(),
+            total: 0,
+            untracked: 0,
+            flushes: 0,
+            recent: VecDeque::new(),
+        }
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    /// Count an invalidation push; its payload is the key list, or nil when
+    /// the whole keyspace was flushed.
+    fn record_push(&mut self, data: &[Value], at_ms: u64) {
+        if let Some(Value::Array(keys)) = data.first() {
+            for key in keys {
+                if let Value::BulkString(bytes) = key {
+                    self.record_key(&String::from_utf8_lossy(bytes), at_ms);
+                }
+            }
+        } else {
+            self.flushes += 1;
+            self.count(at_ms);
+        }
+    }
+
+    fn record_key(&mut self, key: &str, at_ms: u64) {
+        if let Some((count, last)) = self.keys.get_mut(key) {
+            *count += 1;
+            *last = at_ms;
+        } else if self.keys.len() < MAX_TRACKED_KEYS {
+            self.keys.insert(key.to_string(), (1, at_ms));
+        } else {
+            self.untracked += 1;
+        }
+        self.count(at_ms);
+    }
+
+    fn count(&mut self, at_ms: u64) {
+        self.total += 1;
+        let second = at_ms / 1000;
+        match self.recent.back_mut() {
+            Some((last, count)) if *last == second => *count += 1,
+            _ => self.recent.push_back((second, 1)),
+        }
+        self.prune(second);
+    }
+
+    /// Drop per-second counts older than the rate window.
+    fn prune(&mut self, now_second: u64) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|(second, _)| second + RATE_WINDOW_SECS <= now_second)
+        {
+            self.recent.pop_front();
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn stats(&mut self, connection_id: &str, top: usize, now_ms: u64) -> TrackingStats {
+        self.prune(now_ms / 1000);
+        let recent: u64 = self.recent.iter().map(|(_, count)| count).sum();
+        let window_ms = now_ms.clamp(1, RATE_WINDOW_SECS * 1000);
+
+        let mut top_keys: Vec<KeyInvalidations> = self
+            .keys
+            .iter()
+            .map(|(key, (count, last))| KeyInvalidations {
+                key: key.clone(),
+                count: *count,
+                last_at_ms: *last,
+            })
+            .collect();
+        top_keys.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+        top_keys.truncate(top);
+
+        TrackingStats {
+            connection_id: connection_id.to_string(),
+            prefixes: self.prefixes.clone(),
+            elapsed_ms: now_ms,
+            active: self.active,
+            total_invalidations: self.total,
+            distinct_keys: self.keys.len() as u64,
+            untracked_invalidations: self.untracked,
+            flushes: self.flushes,
+            invalidations_per_sec: self.total as f64 * 1000.0 / now_ms.max(1) as f64,
+            recent_invalidations_per_sec: recent as f64 * 1000.0 / window_ms as f64,
+            top_keys,
+        }
+    }
+}
+
+/// A running session: the connection tracking is enabled on, kept open for
+/// as long as the session runs, and what it has seen.
+struct TrackingSession {
+    _conn: MultiplexedConnection,
+    log: Arc<Mutex<InvalidationLog>>,
+}
+
+/// Experimental client-side caching explorer.
+///
+/// Each session opens a dedicated RESP3 connection with CLIENT TRACKING in
+/// broadcast mode, so every write to a tracked key (or prefix) produces an
+/// invalidation push, and counts them per key. The figures show how often
+/// cached values would be invalidated before deciding on server-assisted
+/// caching.
+pub struct TrackingSessions {
+    sessions: Arc<RwLock<HashMap<Uuid, TrackingSession>>>,
+}
+
+impl Default for TrackingSessions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrackingSessions {
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Enable tracking of `prefixes` (every key when empty) on a new
+    /// connection to `connection_url`, replacing any session already running.
+    pub async fn start(
+        &self,
+        connection_id: Uuid,
+        connection_url: &str,
+        prefixes: Vec<String>,
+    ) -> Result<(), AppError> {
+        validate_prefixes(&prefixes)?;
+        let client = redis::Client::open(resp3_url(connection_url))
+            .map_err(|e| AppError::Connection(format!("Failed to create tracking client: {e}")))?;
+
+        let log = Arc::new(Mutex::new(InvalidationLog::new(prefixes.clone())));
+        let sink = Arc::clone(&log);
+        let config = AsyncConnectionConfig::new()
+            .set_connection_timeout(CONNECT_TIMEOUT)
+            .set_push_sender(move |info: PushInfo| {
+                let Ok(mut log) = sink.lock() else {
+                    return Err(redis::aio::SendError);
+                };
+                match info.kind {
+                    PushKind::Invalidate => {
+                        let at_ms = log.elapsed_ms();
+                        log.record_push(&info.data, at_ms);
+                    }
+                    PushKind::Disconnection => log.active = false,
+                    _ => {}
+                }
+                Ok(())
+            });
+        let mut conn = client
+            .get_multiplexed_async_connection_with_config(&config)
+            .await
+            .map_err(|e| {
+                AppError::Connection(format!(
+                    "Tracking connection failed (RESP3 needs Redis 6+): {e}"
+                ))
+            })?;
+
+        let mut cmd = redis::cmd("CLIENT");
+        cmd.arg("TRACKING").arg("ON").arg("BCAST");
+        for prefix in &prefixes {
+            cmd.arg("PREFIX").arg(prefix);
+        }
+        cmd.query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("CLIENT TRACKING failed: {e}")))?;
+
+        self.sessions
+            .write()
+            .await
+            .insert(connection_id, TrackingSession { _conn: conn, log });
+        tracing::info!(connection_id = %connection_id, "Client tracking session started");
+        Ok(())
+    }
+
+    /// Figures of a running session with its `top` busiest keys, or `None`
+    /// if the connection isn't being tracked.
+    pub async fn stats(&self, connection_id: &Uuid, top: Option<usize>) -> Option<TrackingStats> {
+        let sessions = self.sessions.read().await;
+        let session = sessions.get(connection_id)?;
+        let top = top.unwrap_or(DEFAULT_TOP_KEYS).min(MAX_TOP_KEYS);
+        let mut log = session.log.lock().ok()?;
+        let now_ms = log.elapsed_ms();
+        Some(log.stats(&connection_id.to_string(), top, now_ms))
+    }
+
+    /// End a session, closing its connection. Returns its final figures, or
+    /// `None` if none was running.
+    pub async fn stop(&self, connection_id: &Uuid) -> Option<TrackingStats> {
+        let stats = self.stats(connection_id, None).await;
+        if self.sessions.write().await.remove(connection_id).is_some() {
+            tracing::info!(connection_id = %connection_id, "Client tracking session stopped");
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(names: &[&str]) -> Vec<Value> {
+        vec![Value::Array(
+            names
+                .iter()
+                .map(|k| Value::BulkString(k.as_bytes().to_vec()))
+                .collect(),
+        )]
+    }
+
+    #[test]
+    fn test_log_counts_keys_and_flushes() {
+        let mut log = InvalidationLog::new(Vec::new());
+        log.record_push(&keys(&["a", "b"]), 100);
+        log.record_push(&keys(&["a"]), 1_500);
+        log.record_push(&[Value::Nil], 2_000);
+
+        let stats = log.stats("c", 1, 2_000);
+        assert_eq!(stats.total_invalidations, 4);
+        assert_eq!(stats.distinct_keys, 2);
+        assert_eq!(stats.flushes, 1);
+        assert_eq!(
+            stats.top_keys,
+            vec![KeyInvalidations {
+                key: "a".into(),
+                count: 2,
+                last_at_ms: 1_500,
+            }]
+        );
+        assert!((stats.invalidations_per_sec - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recent_rate_forgets_old_seconds() {
+        let mut log = InvalidationLog::new(Vec::new());
+        log.record_push(&keys(&["a", "b", "c"]), 0);
+        log.record_push(&keys(&["a"]), 15_000);
+
+        let stats = log.stats("c", 10, 15_000);
+        assert_eq!(stats.total_invalidations, 4);
+        assert!((stats.recent_invalidations_per_sec - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_validate_prefixes() {
+        assert!(validate_prefixes(&[]).is_ok());
+        assert!(validate_prefixes(&["user:".into()]).is_ok());
+        assert!(validate_prefixes(&[String::new()]).is_err());
+        assert!(validate_prefixes(&vec!["p".to_string(); MAX_PREFIXES + 1]).is_err());
+    }
+}
@@ -0,0 +1,392 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::model::{ChangeKind, DiffStatus, KeyDiff, TtlDiff, ValueChange};
+
+/// Changes listed per key before the diff is cut short.
+pub const MAX_CHANGES: usize = 200;
+/// Characters of a value shown in a change.
+const PREVIEW_CHARS: usize = 256;
+/// TTL difference below which two keys count as expiring together.
+pub const DEFAULT_TTL_TOLERANCE_MS: u64 = 5_000;
+
+/// A key's value as read for comparison.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyValue {
+    String(Vec<u8>),
+    Hash(BTreeMap<String, String>),
+    List(Vec<String>),
+    Set(BTreeSet<String>),
+    ZSet(BTreeMap<String, f64>),
+    /// Entry ID to the entry's fields, rendered as `field=value` pairs.
+    Stream(BTreeMap<String, String>),
+    Json(String),
+    /// Not read because it holds more than this many elements or bytes.
+    TooLarge(u64),
+    /// A type no reader exists for, e.g. a module type.
+    Unsupported,
+}
+
+/// Type, TTL and value of a key on one side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySample {
+    pub key_type: String,
+    /// Remaining TTL; `None` if the key never expires.
+    pub ttl_ms: Option<i64>,
+    pub value: KeyValue,
+}
+
+/// Compare one key's samples. A side is `None` when the key is missing there.
+pub fn compare(
+    key: &str,
+    left: Option<&KeySample>,
+    right: Option<&KeySample>,
+    ttl_tolerance_ms: u64,
+) -> KeyDiff {
+    let mut diff = KeyDiff {
+        key: key.to_string(),
+        status: DiffStatus::Equal,
+        left_type: left.map(|s| s.key_type.clone()),
+        right_type: right.map(|s| s.key_type.clone()),
+        ttl: None,
+        changes: Vec::new(),
+        truncated: false,
+    };
+    let (left, right) = match (left, right) {
+        (Some(l), Some(r)) => (l, r),
+        (Some(_), None) => {
+            diff.status = DiffStatus::OnlyLeft;
+            return diff;
+        }
+        (None, Some(_)) => {
+            diff.status = DiffStatus::OnlyRight;
+            return diff;
+        }
+        (None, None) => return diff,
+    };
+    if left.key_type != right.key_type {
+        diff.status = DiffStatus::TypeMismatch;
+        return diff;
+    }
+
+    if ttl_differs(left.ttl_ms, right.ttl_ms, ttl_tolerance_ms) {
+        diff.ttl = Some(TtlDiff {
+            left_ms: left.ttl_ms,
+            right_ms: right.ttl_ms,
+        });
+    }
+
+    let mut changes = Changes::default();
+    match (&left.value, &right.value) {
+        (KeyValue::String(l), KeyValue::String(r)) => {
+            if l != r {
+                changes.push(
+                    ChangeKind::Changed,
+                    None,
+                    Some(preview(&String::from_utf8_lossy(l))),
+                    Some(preview(&String::from_utf8_lossy(r))),
+                );
+            }
+        }
+        (KeyValue::Json(l), KeyValue::Json(r)) => {
+            if l != r {
+                changes.push(
+                    ChangeKind::Changed,
+                    None,
+                    Some(preview(l)),
+                    Some(preview(r)),
+                );
+            }
+        }
+        (KeyValue::Hash(l), KeyValue::Hash(r)) | (KeyValue::Stream(l), KeyValue::Stream(r)) => {
+            diff_maps(l, r, |v| preview(v), &mut changes);
+        }
+        (KeyValue::ZSet(l), KeyValue::ZSet(r)) => {
+            diff_maps(l, r, f64::to_string, &mut changes);
+        }
+        (KeyValue::Set(l), KeyValue::Set(r)) => {
+            for member in l.difference(r) {
+                changes.push(ChangeKind::Removed, Some(member.clone()), None, None);
+            }
+            for member in r.difference(l) {
+                changes.push(ChangeKind::Added, Some(member.clone()), None, None);
+            }
+        }
+        (KeyValue::List(l), KeyValue::List(r)) => diff_lists(l, r, &mut changes),
+        (l, r) => {
+            // Too large or unreadable on at least one side: only sizes can
+            // be told apart.
+            diff.truncated = true;
+            match (size(l), size(r)) {
+                (Some(ls), Some(rs)) if ls != rs => changes.push(
+                    ChangeKind::Changed,
+                    Some("size".into()),
+                    Some(ls.to_string()),
+                    Some(rs.to_string()),
+                ),
+                _ => diff.status = DiffStatus::Skipped,
+            }
+        }
+    }
+
+    diff.truncated |= changes.truncated;
+    diff.changes = changes.list;
+    if !diff.changes.is_empty() || diff.ttl.is_some() {
+        diff.status = DiffStatus::Different;
+    }
+    diff
+}
+
+fn ttl_differs(left: Option<i64>, right: Option<i64>, tolerance_ms: u64) -> bool {
+    match (left, right) {
+        (Some(l), Some(r)) => l.abs_diff(r) > tolerance_ms,
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// Element or byte count of a value, where known.
+fn size(value: &KeyValue) -> Option<u64> {
+    Some(match value {
+        KeyValue::String(v) => v.len() as u64,
+        KeyValue::Json(v) => v.len() as u64,
+        KeyValue::Hash(v) | KeyValue::Stream(v) => v.len() as u64,
+        KeyValue::List(v) => v.len() as u64,
+        KeyValue::Set(v) => v.len() as u64,
+        KeyValue::ZSet(v) => v.len() as u64,
+        KeyValue::TooLarge(n) => *n,
+        KeyValue::Unsupported => return None,
+    })
+}
+
+/// Changes collected for one key, capped at [`MAX_CHANGES`].
+#[derive(Default)]
+struct Changes {
+    list: Vec<ValueChange>,
+    truncated: bool,
+}
+
+impl Changes {
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        path: Option<String>,
+        left: Option<String>,
+        right: Option<String>,
+    ) {
+        if self.list.len() < MAX_CHANGES {
+            self.list.push(ValueChange {
+                kind,
+                path,
+                left,
+                right,
+            });
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+fn diff_maps<V: PartialEq>(
+    left: &BTreeMap<String, V>,
+    right: &BTreeMap<String, V>,
+    render: impl Fn(&V) -> String,
+    changes: &mut Changes,
+) {
+    for (field, l) in left {
+        match right.get(field) {
+            None => changes.push(
+                ChangeKind::Removed,
+                Some(field.clone()),
+                Some(render(l)),
+                None,
+            ),
+            Some(r) if r != l => changes.push(
+                ChangeKind::Changed,
+                Some(field.clone()),
+                Some(render(l)),
+                Some(render(r)),
+            ),
+            Some(_) => {}
+        }
+    }
+    for (field, r) in right {
+        if !left.contains_key(field) {
+            changes.push(
+                ChangeKind::Added,
+                Some(field.clone()),
+                None,
+                Some(render(r)),
+            );
+        }
+    }
+}
+
+/// Position-by-position comparison; an insertion shows up as every later
+/// index changing.
+fn diff_lists(left: &[String], right: &[String], changes: &mut Changes) {
+    for i in 0..left.len().max(right.len()) {
+        let (l, r) = (left.get(i), right.get(i));
+        let kind = match (l, r) {
+            (Some(l), Some(r)) if l == r => continue,
+            (Some(_), Some(_)) => ChangeKind::Changed,
+            (Some(_), None) => ChangeKind::Removed,
+            _ => ChangeKind::Added,
+        };
+        changes.push(
+            kind,
+            Some(i.to_string()),
+            l.map(|v| preview(v)),
+            r.map(|v| preview(v)),
+        );
+    }
+}
+
+fn preview(value: &str) -> String {
+    match value.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(key_type: &str, ttl_ms: Option<i64>, value: KeyValue) -> KeySample {
+        KeySample {
+            key_type: key_type.into(),
+            ttl_ms,
+            value,
+        }
+    }
+
+    fn hash(pairs: &[(&str, &str)]) -> KeyValue {
+        KeyValue::Hash(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_missing_and_mismatched_keys() {
+        let s = sample("string", None, KeyValue::String(b"a".to_vec()));
+        let h = sample("hash", None, hash(&[]));
+        assert_eq!(compare("k", Some(&s), None, 0).status, DiffStatus::OnlyLeft);
+        assert_eq!(
+            compare("k", None, Some(&s), 0).status,
+            DiffStatus::OnlyRight
+        );
+        let diff = compare("k", Some(&s), Some(&h), 0);
+        assert_eq!(diff.status, DiffStatus::TypeMismatch);
+        assert_eq!(diff.right_type.as_deref(), Some("hash"));
+        assert_eq!(
+            compare("k", Some(&s), Some(&s), 0).status,
+            DiffStatus::Equal
+        );
+    }
+
+    #[test]
+    fn test_hash_field_changes() {
+        let l = sample("hash", None, hash(&[("a", "1"), ("b", "2"), ("c", "3")]));
+        let r = sample("hash", None, hash(&[("a", "1"), ("b", "20"), ("d", "4")]));
+        let diff = compare("h", Some(&l), Some(&r), 0);
+        assert_eq!(diff.status, DiffStatus::Different);
+        let summary: Vec<_> = diff
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.path.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (ChangeKind::Changed, "b"),
+                (ChangeKind::Removed, "c"),
+                (ChangeKind::Added, "d")
+            ]
+        );
+        assert_eq!(diff.changes[0].right.as_deref(), Some("20"));
+    }
+
+    #[test]
+    fn test_set_zset_and_list_changes() {
+        let set = |m: &[&str]| KeyValue::Set(m.iter().map(ToString::to_string).collect());
+        let diff = compare(
+            "s",
+            Some(&sample("set", None, set(&["a", "b"]))),
+            Some(&sample("set", None, set(&["b", "c"]))),
+            0,
+        );
+        assert_eq!(diff.changes.len(), 2);
+        assert_eq!(diff.changes[0].kind, ChangeKind::Removed);
+
+        let zset = |s: f64| KeyValue::ZSet(BTreeMap::from([("m".to_string(), s)]));
+        let diff = compare(
+            "z",
+            Some(&sample("zset", None, zset(1.0))),
+            Some(&sample("zset", None, zset(1.5))),
+            0,
+        );
+        assert_eq!(diff.changes[0].right.as_deref(), Some("1.5"));
+
+        let list = |m: &[&str]| KeyValue::List(m.iter().map(ToString::to_string).collect());
+        let diff = compare(
+            "l",
+            Some(&sample("list", None, list(&["a", "b"]))),
+            Some(&sample("list", None, list(&["a", "x", "y"]))),
+            0,
+        );
+        let kinds: Vec<_> = diff.changes.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [ChangeKind::Changed, ChangeKind::Added]);
+        assert_eq!(diff.changes[1].path.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_ttl_tolerance() {
+        let v = || KeyValue::String(b"v".to_vec());
+        let a = sample("string", Some(60_000), v());
+        let b = sample("string", Some(58_000), v());
+        let none = sample("string", None, v());
+        assert_eq!(
+            compare("k", Some(&a), Some(&b), 5_000).status,
+            DiffStatus::Equal
+        );
+        let diff = compare("k", Some(&a), Some(&b), 1_000);
+        assert_eq!(diff.status, DiffStatus::Different);
+        assert!(diff.changes.is_empty());
+        assert_eq!(
+            compare("k", Some(&a), Some(&none), 5_000).ttl,
+            Some(TtlDiff {
+                left_ms: Some(60_000),
+                right_ms: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_values_compare_sizes_only() {
+        let big = |n| sample("list", None, KeyValue::TooLarge(n));
+        let diff = compare("l", Some(&big(20_000)), Some(&big(20_000)), 0);
+        assert_eq!(diff.status, DiffStatus::Skipped);
+        assert!(diff.truncated);
+        let diff = compare("l", Some(&big(20_000)), Some(&big(20_001)), 0);
+        assert_eq!(diff.status, DiffStatus::Different);
+        assert_eq!(diff.changes[0].path.as_deref(), Some("size"));
+    }
+
+    #[test]
+    fn test_changes_are_capped() {
+        let list = |n: usize, v: &str| KeyValue::List(vec![v.to_string(); n]);
+        let diff = compare(
+            "l",
+            Some(&sample("list", None, list(MAX_CHANGES + 5, "a"))),
+            Some(&sample("list", None, list(MAX_CHANGES + 5, "b"))),
+            0,
+        );
+        assert_eq!(diff.changes.len(), MAX_CHANGES);
+        assert!(diff.truncated);
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use deadpool_redis::Pool;
+
+use super::model::{ConfigDiffReport, ConfigParamDiff, ConfigSnapshot, DriftSeverity};
+use crate::redis::monitor::info_parser;
+use crate::utils::errors::AppError;
+
+/// Parameters whose values are never captured.
+const SECRET_PARAMETERS: &[&str] = &["requirepass", "masterauth", "masteruser"];
+/// Stands in for a secret that is set.
+const REDACTED: &str = "(redacted)";
+
+/// Parameters that change eviction, durability or replication safety, so
+/// servers meant to behave alike shouldn't disagree on them.
+const DANGEROUS_PARAMETERS: &[&str] = &[
+    "maxmemory-policy",
+    "maxmemory",
+    "appendonly",
+    "appendfsync",
+    "save",
+    "min-replicas-to-write",
+    "min-replicas-max-lag",
+    "replica-read-only",
+    "replica-serve-stale-data",
+    "stop-writes-on-bgsave-error",
+    "protected-mode",
+    "cluster-require-full-coverage",
+    "lazyfree-lazy-eviction",
+];
+
+/// Parameters expected to differ per instance.
+const INSTANCE_PARAMETERS: &[&str] = &[
+    "bind",
+    "port",
+    "tls-port",
+    "cluster-announce-ip",
+    "cluster-announce-port",
+    "replica-announce-ip",
+    "replica-announce-port",
+    "replicaof",
+    "slaveof",
+    "dir",
+    "dbfilename",
+    "appenddirname",
+    "appendfilename",
+    "logfile",
+    "pidfile",
+    "unixsocket",
+    "cluster-config-file",
+    "aclfile",
+];
+
+/// Read `CONFIG GET *` and the server's version and role.
+pub async fn capture(pool: &Pool, connection_id: &str) -> Result<ConfigSnapshot, AppError> {
+    let mut conn = pool.get().await?;
+    let parameters: BTreeMap<String, String> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("*")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| {
+            AppError::Redis(format!(
+                "CONFIG GET failed; it may be disabled or renamed on this server: {e}"
+            ))
+        })?;
+    // Plain INFO: older servers take only one section argument.
+    let info: String = redis::cmd("INFO")
+        .query_async(&mut conn)
+        .await
+        .unwrap_or_default();
+    let info = info_parser::parse_info(&info);
+    let non_empty = |s: String| (!s.is_empty()).then_some(s);
+    Ok(ConfigSnapshot {
+        connection_id: Some(connection_id.to_string()),
+        redis_version: non_empty(info.server.redis_version),
+        role: non_empty(info.replication.role),
+        captured_at: chrono::Utc::now(),
+        parameters: redact(parameters),
+    })
+}
+
+/// Replace the values of secret parameters that are set.
+fn redact(mut parameters: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    for name in SECRET_PARAMETERS {
+        if let Some(value) = parameters.get_mut(*name) {
+            if !value.is_empty() {
+                *value = REDACTED.to_string();
+            }
+        }
+    }
+    parameters
+}
+
+fn severity(parameter: &str) -> DriftSeverity {
+    if DANGEROUS_PARAMETERS.contains(&parameter) {
+        DriftSeverity::Danger
+    } else if INSTANCE_PARAMETERS.contains(&parameter) || parameter.ends_with("-file") {
+        DriftSeverity::Info
+    } else {
+        DriftSeverity::Notice
+    }
+}
+
+/// Compare two snapshots parameter by parameter, ignoring the ASCII case
+/// of values (`yes` and `YES` are the same setting).
+pub fn compare(left: ConfigSnapshot, right: ConfigSnapshot) -> ConfigDiffReport {
+    let names: BTreeSet<&String> = left
+        .parameters
+        .keys()
+        .chain(right.parameters.keys())
+        .collect();
+    let mut equal = 0;
+    let mut differences = Vec::new();
+    for name in names {
+        let l = left.parameters.get(name);
+        let r = right.parameters.get(name);
+        if l.zip(r).is_some_and(|(l, r)| l.eq_ignore_ascii_case(r)) {
+            equal += 1;
+            continue;
+        }
+        differences.push(ConfigParamDiff {
+            parameter: name.clone(),
+            left: l.cloned(),
+            right: r.cloned(),
+            severity: severity(name),
+        });
+    }
+    differences.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.parameter.cmp(&b.parameter))
+    });
+    let dangerous = differences
+        .iter()
+        .filter(|d| d.severity == DriftSeverity::Danger)
+        .count() as u64;
+    ConfigDiffReport {
+        left,
+        right,
+        equal,
+        differences,
+        dangerous,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(pairs: &[(&str, &str)]) -> ConfigSnapshot {
+        ConfigSnapshot {
+            connection_id: None,
+            redis_version: None,
+            role: Some("master".into()),
+            captured_at: chrono::Utc::now(),
+            parameters: pairs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare_ranks_dangerous_drift_first() {
+        let left = snapshot(&[
+            ("maxmemory-policy", "noeviction"),
+            ("port", "6379"),
+            ("hz", "10"),
+            ("appendonly", "yes"),
+            ("lfu-log-factor", "10"),
+        ]);
+        let right = snapshot(&[
+            ("maxmemory-policy", "allkeys-lru"),
+            ("port", "6380"),
+            ("hz", "20"),
+            ("appendonly", "YES"),
+        ]);
+        let report = compare(left, right);
+        assert_eq!(report.equal, 1);
+        assert_eq!(report.dangerous, 1);
+        let order: Vec<(&str, DriftSeverity)> = report
+            .differences
+            .iter()
+            .map(|d| (d.parameter.as_str(), d.severity))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("maxmemory-policy", DriftSeverity::Danger),
+                ("hz", DriftSeverity::Notice),
+                ("lfu-log-factor", DriftSeverity::Notice),
+                ("port", DriftSeverity::Info),
+            ]
+        );
+        assert_eq!(report.differences[2].right, None);
+    }
+
+    #[test]
+    fn test_redact_hides_set_secrets_only() {
+        let parameters = snapshot(&[("requirepass", "hunter2"), ("masterauth", "")]).parameters;
+        let redacted = redact(parameters);
+        assert_eq!(redacted["requirepass"], REDACTED);
+        assert_eq!(redacted["masterauth"], "");
+    }
+}
@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: MIT
+
+pub mod compare;
+pub mod config;
+pub mod model;
+pub mod reader;
@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// How a key compares between the left and right connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffStatus {
+    Equal,
+    Different,
+    /// The key only exists on the left connection.
+    OnlyLeft,
+    /// The key only exists on the right connection.
+    OnlyRight,
+    /// The key exists on both sides but holds different types.
+    TypeMismatch,
+    /// Values were too large or of a type that can't be read, so only
+    /// their sizes were compared, and those match.
+    Skipped,
+}
+
+/// Kind of a single value change, read from left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// Present on the right only.
+    Added,
+    /// Present on the left only.
+    Removed,
+    Changed,
+}
+
+/// One differing field, member, element or entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueChange {
+    pub kind: ChangeKind,
+    /// Hash field, set/zset member, list index or stream entry ID;
+    /// `None` for a string or JSON document as a whole.
+    pub path: Option<String>,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+/// Remaining TTLs in milliseconds; `None` means the key has no expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TtlDiff {
+    pub left_ms: Option<i64>,
+    pub right_ms: Option<i64>,
+}
+
+/// Comparison of one key between two connections.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDiff {
+    pub key: String,
+    pub status: DiffStatus,
+    pub left_type: Option<String>,
+    pub right_type: Option<String>,
+    /// Set when the TTLs differ by more than the tolerance.
+    pub ttl: Option<TtlDiff>,
+    pub changes: Vec<ValueChange>,
+    /// More changes existed than are listed, or a value was too large to
+    /// compare element by element.
+    pub truncated: bool,
+}
+
+/// What to compare and how strictly.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffOptions {
+    /// Compare a single key...
+    pub key: Option<String>,
+    /// ...or every key matching a glob on either side.
+    pub pattern: Option<String>,
+    pub left_db: Option<u8>,
+    pub right_db: Option<u8>,
+    /// TTLs closer than this are treated as equal. Defaults to 5 seconds.
+    pub ttl_tolerance_ms: Option<u64>,
+    /// Most keys compared in pattern mode.
+    pub max_keys: Option<usize>,
+}
+
+/// Outcome of a diff, by key or by pattern.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffReport {
+    pub left_connection_id: String,
+    pub right_connection_id: String,
+    pub keys_compared: u64,
+    pub equal: u64,
+    pub different: u64,
+    pub only_left: u64,
+    pub only_right: u64,
+    pub type_mismatch: u64,
+    pub skipped: u64,
+    /// Every key that is not equal, in key order.
+    pub diffs: Vec<KeyDiff>,
+    /// The pattern matched more keys than `max_keys`.
+    pub truncated: bool,
+}
+
+impl DiffReport {
+    pub fn add(&mut self, diff: KeyDiff) {
+        self.keys_compared += 1;
+        match diff.status {
+            DiffStatus::Equal => {
+                self.equal += 1;
+                return;
+            }
+            DiffStatus::Different => self.different += 1,
+            DiffStatus::OnlyLeft => self.only_left += 1,
+            DiffStatus::OnlyRight => self.only_right += 1,
+            DiffStatus::TypeMismatch => self.type_mismatch += 1,
+            DiffStatus::Skipped => self.skipped += 1,
+        }
+        self.diffs.push(diff);
+    }
+}
+
+/// A server's CONFIG GET * at one point in time, with secrets redacted.
+/// Saved as JSON, it serves as a baseline for later comparisons.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSnapshot {
+    /// The connection it was captured from.
+    pub connection_id: Option<String>,
+    pub redis_version: Option<String>,
+    /// `master` or `slave`, from INFO replication.
+    pub role: Option<String>,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// One side of a config comparison: a live connection or a saved snapshot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConfigSource {
+    #[serde(rename_all = "camelCase")]
+    Connection {
+        connection_id: String,
+    },
+    Snapshot {
+        snapshot: Box<ConfigSnapshot>,
+    },
+}
+
+/// How much a differing parameter matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftSeverity {
+    /// Expected to differ between instances, such as ports and file paths.
+    Info,
+    Notice,
+    /// Changes data safety or eviction behavior, e.g. `maxmemory-policy`.
+    Danger,
+}
+
+/// A parameter that differs between the two sides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigParamDiff {
+    pub parameter: String,
+    /// `None` when the side doesn't know the parameter, e.g. an older version.
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub severity: DriftSeverity,
+}
+
+/// Outcome of comparing two configurations.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiffReport {
+    pub left: ConfigSnapshot,
+    pub right: ConfigSnapshot,
+    pub equal: u64,
+    /// Most severe first, then by name.
+    pub differences: Vec<ConfigParamDiff>,
+    pub dangerous: u64,
+}
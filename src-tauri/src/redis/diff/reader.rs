@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use deadpool_redis::{Connection, Pool};
+
+use super::compare::{self, KeySample, KeyValue, DEFAULT_TTL_TOLERANCE_MS};
+use super::model::{DiffOptions, DiffReport};
+use crate::redis::browser::scanner;
+use crate::utils::errors::AppError;
+
+/// Collections with more elements than this are compared by size only.
+pub const MAX_ELEMENTS: u64 = 10_000;
+/// Strings longer than this are compared by length only.
+pub const MAX_STRING_BYTES: u64 = 1024 * 1024;
+/// Keys compared by pattern when the caller does not choose.
+pub const DEFAULT_MAX_KEYS: usize = 1_000;
+/// Most keys a pattern diff may compare.
+pub const MAX_KEYS_LIMIT: usize = 10_000;
+
+/// What the options ask to compare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffTarget {
+    Key(String),
+    Pattern { pattern: String, max_keys: usize },
+}
+
+impl DiffTarget {
+    /// Exactly one of `key` and `pattern` must be given.
+    pub fn from_options(options: &DiffOptions) -> Result<Self, AppError> {
+        match (&options.key, &options.pattern) {
+            (Some(key), None) if !key.is_empty() => Ok(Self::Key(key.clone())),
+            (None, Some(pattern)) if !pattern.is_empty() => {
+                let max_keys = options.max_keys.unwrap_or(DEFAULT_MAX_KEYS);
+                if max_keys == 0 || max_keys > MAX_KEYS_LIMIT {
+                    return Err(AppError::InvalidInput(format!(
+                        "Key limit must be between 1 and {MAX_KEYS_LIMIT}"
+                    )));
+                }
+                Ok(Self::Pattern {
+                    pattern: pattern.clone(),
+                    max_keys,
+                })
+            }
+            _ => Err(AppError::InvalidInput(
+                "Give either a key or a pattern to compare".into(),
+            )),
+        }
+    }
+}
+
+/// Compare a key, or every key matching a pattern on either side, between
+/// two pools.
+pub async fn diff(
+    left: &Pool,
+    right: &Pool,
+    target: &DiffTarget,
+    ttl_tolerance_ms: Option<u64>,
+) -> Result<DiffReport, AppError> {
+    let tolerance = ttl_tolerance_ms.unwrap_or(DEFAULT_TTL_TOLERANCE_MS);
+    let mut left_conn = left.get().await?;
+    let mut right_conn = right.get().await?;
+    let mut report = DiffReport::default();
+
+    let keys = match target {
+        DiffTarget::Key(key) => BTreeSet::from([key.clone()]),
+        DiffTarget::Pattern { pattern, max_keys } => {
+            let mut keys = BTreeSet::new();
+            let left_done = collect_keys(&mut left_conn, pattern, *max_keys, &mut keys).await?;
+            let right_done = collect_keys(&mut right_conn, pattern, *max_keys, &mut keys).await?;
+            report.truncated = !left_done || !right_done || keys.len() > *max_keys;
+            keys.into_iter().take(*max_keys).collect()
+        }
+    };
+
+    for key in &keys {
+        let l = read_sample(&mut left_conn, key).await?;
+        let r = read_sample(&mut right_conn, key).await?;
+        report.add(compare::compare(key, l.as_ref(), r.as_ref(), tolerance));
+    }
+    Ok(report)
+}
+
+/// SCAN `pattern` into `keys` until it holds more than `max_keys`. Returns
+/// whether the scan finished.
+async fn collect_keys(
+    conn: &mut Connection,
+    pattern: &str,
+    max_keys: usize,
+    keys: &mut BTreeSet<String>,
+) -> Result<bool, AppError> {
+    let mut cursor: u64 = 0;
+    loop {
+        let (next, batch) = scanner::scan_match(conn, cursor, pattern).await?;
+        keys.extend(batch);
+        if next == 0 {
+            return Ok(true);
+        }
+        if keys.len() > max_keys {
+            return Ok(false);
+        }
+        cursor = next;
+    }
+}
+
+/// Read a key's type, TTL and value; `None` if it doesn't exist.
+pub async fn read_sample(conn: &mut Connection, key: &str) -> Result<Option<KeySample>, AppError> {
+    let (key_type, pttl): (String, i64) = redis::pipe()
+        .cmd("TYPE")
+        .arg(key)
+        .cmd("PTTL")
+        .arg(key)
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("TYPE/PTTL failed: {e}")))?;
+    if key_type == "none" {
+        return Ok(None);
+    }
+
+    let value = match size_limit(&key_type) {
+        Some((command, limit)) => match size(conn, command, key, limit).await? {
+            Some(len) => KeyValue::TooLarge(len),
+            None => read_value(conn, &key_type, key).await?,
+        },
+        None => read_value(conn, &key_type, key).await?,
+    };
+
+    Ok(Some(KeySample {
+        key_type,
+        ttl_ms: (pttl >= 0).then_some(pttl),
+        value,
+    }))
+}
+
+/// Command reporting a type's size, and the largest size read in full.
+fn size_limit(key_type: &str) -> Option<(&'static str, u64)> {
+    Some(match key_type {
+        "string" => ("STRLEN", MAX_STRING_BYTES),
+        "hash" => ("HLEN", MAX_ELEMENTS),
+        "list" => ("LLEN", MAX_ELEMENTS),
+        "set" => ("SCARD", MAX_ELEMENTS),
+        "zset" => ("ZCARD", MAX_ELEMENTS),
+        "stream" => ("XLEN", MAX_ELEMENTS),
+        _ => return None,
+    })
+}
+
+async fn read_value(
+    conn: &mut Connection,
+    key_type: &str,
+    key: &str,
+) -> Result<KeyValue, AppError> {
+    Ok(match key_type {
+        "string" => KeyValue::String(query(conn, "GET", key, &[]).await?),
+        "hash" => {
+            let pairs: Vec<(Vec<u8>, Vec<u8>)> = query(conn, "HGETALL", key, &[]).await?;
+            KeyValue::Hash(
+                pairs
+                    .into_iter()
+                    .map(|(f, v)| (lossy(f), lossy(v)))
+                    .collect(),
+            )
+        }
+        "list" => {
+            let items: Vec<Vec<u8>> = query(conn, "LRANGE", key, &["0", "-1"]).await?;
+            KeyValue::List(items.into_iter().map(lossy).collect())
+        }
+        "set" => {
+            let members: Vec<Vec<u8>> = query(conn, "SMEMBERS", key, &[]).await?;
+            KeyValue::Set(members.into_iter().map(lossy).collect())
+        }
+        "zset" => {
+            let members: Vec<(Vec<u8>, f64)> =
+                query(conn, "ZRANGE", key, &["0", "-1", "WITHSCORES"]).await?;
+            KeyValue::ZSet(members.into_iter().map(|(m, s)| (lossy(m), s)).collect())
+        }
+        "stream" => {
+            let entries: Vec<(String, Vec<Vec<u8>>)> =
+                query(conn, "XRANGE", key, &["-", "+"]).await?;
+            KeyValue::Stream(
+                entries
+                    .into_iter()
+                    .map(|(id, fields)| (id, render_fields(&fields)))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+        }
+        "ReJSON-RL" => KeyValue::Json(query(conn, "JSON.GET", key, &["$"]).await?),
+        _ => KeyValue::Unsupported,
+    })
+}
+
+/// The size reported by `command`, if it exceeds `limit`.
+async fn size(
+    conn: &mut Connection,
+    command: &str,
+    key: &str,
+    limit: u64,
+) -> Result<Option<u64>, AppError> {
+    let len: u64 = query(conn, command, key, &[]).await?;
+    Ok((len > limit).then_some(len))
+}
+
+async fn query<T: redis::FromRedisValue>(
+    conn: &mut Connection,
+    command: &str,
+    key: &str,
+    args: &[&str],
+) -> Result<T, AppError> {
+    redis::cmd(command)
+        .arg(key)
+        .arg(args)
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("{command} failed: {e}")))
+}
+
+fn lossy(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// Stream entry fields as `field=value` pairs separated by spaces.
+fn render_fields(fields: &[Vec<u8>]) -> String {
+    fields
+        .chunks(2)
+        .map(|pair| {
+            let field = String::from_utf8_lossy(&pair[0]);
+            let value = pair
+                .get(1)
+                .map(|v| String::from_utf8_lossy(v))
+                .unwrap_or_default();
+            format!("{field}={value}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_from_options() {
+        let options = |key: Option<&str>, pattern: Option<&str>, max_keys| DiffOptions {
+            key: key.map(Into::into),
+            pattern: pattern.map(Into::into),
+            max_keys,
+            ..DiffOptions::default()
+        };
+        assert_eq!(
+            DiffTarget::from_options(&options(Some("a"), None, None)).unwrap(),
+            DiffTarget::Key("a".into())
+        );
+        assert_eq!(
+            DiffTarget::from_options(&options(None, Some("user:*"), None)).unwrap(),
+            DiffTarget::Pattern {
+                pattern: "user:*".into(),
+                max_keys: DEFAULT_MAX_KEYS
+            }
+        );
+        assert!(DiffTarget::from_options(&options(Some("a"), Some("b*"), None)).is_err());
+        assert!(DiffTarget::from_options(&options(None, None, None)).is_err());
+        assert!(DiffTarget::from_options(&options(None, Some("*"), Some(0))).is_err());
+        assert!(
+            DiffTarget::from_options(&options(None, Some("*"), Some(MAX_KEYS_LIMIT + 1))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_render_fields() {
+        let fields = vec![b"a".to_vec(), b"1".to_vec(), b"b".to_vec(), b"2".to_vec()];
+        assert_eq!(render_fields(&fields), "a=1 b=2");
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::model::ExpectedValue;
+use crate::utils::errors::AppError;
+
+/// Shared by the scripts below: ARGV[1] is the comparison mode (`value` or
+/// `sha1`) and ARGV[2] the expected value or digest. A missing value never
+/// matches.
+pub const MATCHES: &str = r"
+local function matches(current)
+  if not current then return false end
+  if ARGV[1] == 'sha1' then return redis.sha1hex(current) == ARGV[2] end
+  return current == ARGV[2]
+end
+";
+
+/// ARGV[3] new value, ARGV[4] TTL in seconds (0 keeps the current one).
+const SET_STRING: &str = r"
+if not matches(redis.call('GET', KEYS[1])) then return 0 end
+if tonumber(ARGV[4]) > 0 then
+  redis.call('SET', KEYS[1], ARGV[3], 'EX', ARGV[4])
+else
+  redis.call('SET', KEYS[1], ARGV[3], 'KEEPTTL')
+end
+return 1
+";
+
+/// ARGV[3] field, ARGV[4] new value.
+const SET_HASH_FIELD: &str = r"
+if not matches(redis.call('HGET', KEYS[1], ARGV[3])) then return 0 end
+redis.call('HSET', KEYS[1], ARGV[3], ARGV[4])
+return 1
+";
+
+/// ARGV[3] index, ARGV[4] new value.
+const SET_LIST_ELEMENT: &str = r"
+if not matches(redis.call('LINDEX', KEYS[1], ARGV[3])) then return 0 end
+redis.call('LSET', KEYS[1], ARGV[3], ARGV[4])
+return 1
+";
+
+/// Run one of the scripts above on `key`; a mismatch is a `Conflict`.
+async fn compare_and_set(
+    pool: &Pool,
+    script: &str,
+    key: &str,
+    expected: &ExpectedValue,
+    args: &[&str],
+) -> Result<(), AppError> {
+    let (mode, operand) = expected.script_args()?;
+    let mut conn = pool.get().await?;
+    let written: i64 = redis::cmd("EVAL")
+        .arg(format!("{MATCHES}{script}"))
+        .arg(1)
+        .arg(key)
+        .arg(mode)
+        .arg(operand)
+        .arg(args)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Compare-and-set failed: {e}")))?;
+    if written == 0 {
+        return Err(AppError::Conflict(format!(
+            "'{key}' changed on the server since it was read"
+        )));
+    }
+    Ok(())
+}
+
+/// Set a string, like `string_ops::set_string_value`, only if it still holds
+/// `expected`.
+pub async fn set_string_value(
+    pool: &Pool,
+    key: &str,
+    value: &str,
+    ttl: Option<i64>,
+    expected: &ExpectedValue,
+) -> Result<(), AppError> {
+    let ttl = ttl.filter(|t| *t > 0).unwrap_or(0).to_string();
+    compare_and_set(pool, SET_STRING, key, expected, &[value, &ttl]).await
+}
+
+/// Set a hash field only if it still holds `expected`.
+pub async fn set_hash_field(
+    pool: &Pool,
+    key: &str,
+    field: &str,
+    value: &str,
+    expected: &ExpectedValue,
+) -> Result<(), AppError> {
+    compare_and_set(pool, SET_HASH_FIELD, key, expected, &[field, value]).await
+}
+
+/// Set the list element at `index` only if it still holds `expected`.
+pub async fn set_list_element(
+    pool: &Pool,
+    key: &str,
+    index: i64,
+    value: &str,
+    expected: &ExpectedValue,
+) -> Result<(), AppError> {
+    let index = index.to_string();
+    compare_and_set(pool, SET_LIST_ELEMENT, key, expected, &[&index, value]).await
+}
@@ -0,0 +1,302 @@
+// SPDX-License-Identifier: MIT
+
+//! Watch list of keys whose expiry the user wants to hear about, such as
+//! session or lock keys during an incident.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use deadpool_redis::Pool;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::utils::errors::AppError;
+
+/// How often a connection's watched keys have their PTTL read.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where a watched key stands relative to its threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExpiryPhase {
+    /// Persistent, or further from expiring than the threshold.
+    Clear,
+    /// TTL below the threshold.
+    Expiring,
+    /// The key no longer exists.
+    Gone,
+}
+
+impl ExpiryPhase {
+    /// The phase a PTTL reply puts a key in.
+    fn of(pttl_ms: i64, threshold_ms: i64) -> Self {
+        match pttl_ms {
+            -2 => Self::Gone,
+            ms if ms >= 0 && ms < threshold_ms => Self::Expiring,
+            _ => Self::Clear,
+        }
+    }
+}
+
+/// A key on the expiry watch list.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiryWatch {
+    pub key: String,
+    pub threshold_secs: u64,
+    /// Also show an OS notification, not just the `key:expiring` event.
+    pub notify: bool,
+    /// PTTL at the last check; `None` if persistent or gone.
+    pub ttl_ms: Option<i64>,
+    pub phase: ExpiryPhase,
+}
+
+/// Payload of `key:expiring`: a watched key's TTL fell below its threshold,
+/// or the key expired.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyExpiring {
+    pub connection_id: String,
+    pub key: String,
+    /// Remaining TTL; `None` once the key is gone.
+    pub ttl_ms: Option<i64>,
+    pub threshold_secs: u64,
+    pub expired: bool,
+}
+
+type WatchList = Arc<RwLock<HashMap<String, ExpiryWatch>>>;
+
+struct Watch {
+    keys: WatchList,
+    task: AbortHandle,
+}
+
+/// Per-connection expiry watch lists.
+///
+/// Each connection with watched keys has one task that reads all their PTTLs
+/// in a pipeline every second. A key is reported once when its TTL drops
+/// below the threshold and once when it disappears; it is reported again
+/// only after its TTL is extended past the threshold.
+pub struct ExpiryWatcher {
+    watches: Arc<RwLock<HashMap<Uuid, Watch>>>,
+}
+
+impl Default for ExpiryWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExpiryWatcher {
+    pub fn new() -> Self {
+        Self {
+            watches: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Add `key` to a connection's watch list, or change its threshold and
+    /// notification setting. Fails if the key doesn't exist.
+    pub async fn watch(
+        &self,
+        connection_id: Uuid,
+        key: String,
+        threshold_secs: u64,
+        notify: bool,
+        pool: Pool,
+        app: AppHandle,
+    ) -> Result<ExpiryWatch, AppError> {
+        let pttl = read_pttls(&pool, std::slice::from_ref(&key))
+            .await?
+            .pop()
+            .unwrap_or(-2);
+        if pttl == -2 {
+            return Err(AppError::NotFound(format!("Key '{key}' not found")));
+        }
+        // Start clear so a key already below the threshold is reported on
+        // the first check.
+        let watch = ExpiryWatch {
+            key: key.clone(),
+            threshold_secs,
+            notify,
+            ttl_ms: (pttl >= 0).then_some(pttl),
+            phase: ExpiryPhase::Clear,
+        };
+
+        let mut watches = self.watches.write().await;
+        if let Some(existing) = watches.get(&connection_id) {
+            existing.keys.write().await.insert(key, watch.clone());
+            return Ok(watch);
+        }
+
+        let keys: WatchList = Arc::new(RwLock::new(HashMap::from([(key, watch.clone())])));
+        let task = tokio::spawn(run(connection_id.to_string(), pool, Arc::clone(&keys), app));
+        watches.insert(
+            connection_id,
+            Watch {
+                keys,
+                task: task.abort_handle(),
+            },
+        );
+        tracing::info!(connection_id = %connection_id, "Expiry watch started");
+        Ok(watch)
+    }
+
+    /// Remove `key` from the watch list; the connection's task ends with its
+    /// last key.
+    pub async fn unwatch(&self, connection_id: &Uuid, key: &str) {
+        let mut watches = self.watches.write().await;
+        let Some(watch) = watches.get(connection_id) else {
+            return;
+        };
+        let mut keys = watch.keys.write().await;
+        keys.remove(key);
+        if keys.is_empty() {
+            drop(keys);
+            if let Some(watch) = watches.remove(connection_id) {
+                watch.task.abort();
+                tracing::info!(connection_id = %connection_id, "Expiry watch stopped");
+            }
+        }
+    }
+
+    /// A connection's watch list as of the last check, sorted by key.
+    pub async fn watched(&self, connection_id: &Uuid) -> Vec<ExpiryWatch> {
+        let watches = self.watches.read().await;
+        let Some(watch) = watches.get(connection_id) else {
+            return Vec::new();
+        };
+        let mut keys: Vec<ExpiryWatch> = watch.keys.read().await.values().cloned().collect();
+        keys.sort_by(|a, b| a.key.cmp(&b.key));
+        keys
+    }
+}
+
+/// PTTL of each key in one pipeline (-2 missing, -1 persistent).
+async fn read_pttls(pool: &Pool, keys: &[String]) -> Result<Vec<i64>, AppError> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut conn = pool.get().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("PTTL").arg(key);
+    }
+    pipe.query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("PTTL failed: {e}")))
+}
+
+async fn run(connection_id: String, pool: Pool, keys: WatchList, app: AppHandle) {
+    let mut tick = tokio::time::interval(CHECK_INTERVAL);
+    tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tick.tick().await;
+        let watched: Vec<String> = keys.read().await.keys().cloned().collect();
+        let pttls = match read_pttls(&pool, &watched).await {
+            Ok(pttls) => pttls,
+            Err(e) => {
+                tracing::warn!(connection_id = %connection_id, "Expiry watch check failed: {e}");
+                continue;
+            }
+        };
+        let mut stored = keys.write().await;
+        for (key, pttl) in watched.into_iter().zip(pttls) {
+            // Unwatched while the TTLs were being read.
+            let Some(watch) = stored.get_mut(&key) else {
+                continue;
+            };
+            let Some(event) = update(&connection_id, watch, pttl) else {
+                continue;
+            };
+            let _ = app.emit("key:expiring", &event);
+            if watch.notify {
+                show_notification(&app, &event);
+            }
+        }
+    }
+}
+
+/// Record a PTTL reading; returns the event to emit when the key just
+/// crossed its threshold or disappeared.
+fn update(connection_id: &str, watch: &mut ExpiryWatch, pttl_ms: i64) -> Option<KeyExpiring> {
+    let threshold_ms = i64::try_from(watch.threshold_secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+    let phase = ExpiryPhase::of(pttl_ms, threshold_ms);
+    let previous = std::mem::replace(&mut watch.phase, phase);
+    watch.ttl_ms = (pttl_ms >= 0).then_some(pttl_ms);
+    (phase != previous && phase != ExpiryPhase::Clear).then(|| KeyExpiring {
+        connection_id: connection_id.to_string(),
+        key: watch.key.clone(),
+        ttl_ms: watch.ttl_ms,
+        threshold_secs: watch.threshold_secs,
+        expired: phase == ExpiryPhase::Gone,
+    })
+}
+
+fn show_notification(app: &AppHandle, event: &KeyExpiring) {
+    let body = match event.ttl_ms {
+        Some(ms) => format!("{} expires in {}s", event.key, (ms + 999) / 1000),
+        None => format!("{} has expired", event.key),
+    };
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Key expiring")
+        .body(body)
+        .show()
+    {
+        tracing::warn!(key = %event.key, "Failed to show expiry notification: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watch_of(threshold_secs: u64) -> ExpiryWatch {
+        ExpiryWatch {
+            key: "lock:1".into(),
+            threshold_secs,
+            notify: false,
+            ttl_ms: None,
+            phase: ExpiryPhase::Clear,
+        }
+    }
+
+    #[test]
+    fn test_phase_of_pttl() {
+        assert_eq!(ExpiryPhase::of(-2, 1000), ExpiryPhase::Gone);
+        assert_eq!(ExpiryPhase::of(-1, 1000), ExpiryPhase::Clear);
+        assert_eq!(ExpiryPhase::of(999, 1000), ExpiryPhase::Expiring);
+        assert_eq!(ExpiryPhase::of(1000, 1000), ExpiryPhase::Clear);
+    }
+
+    #[test]
+    fn test_update_reports_each_crossing_once() {
+        let mut watch = watch_of(10);
+        assert!(update("c1", &mut watch, 30_000).is_none());
+
+        let event = update("c1", &mut watch, 9_500).unwrap();
+        assert_eq!(event.ttl_ms, Some(9_500));
+        assert!(!event.expired);
+        assert!(update("c1", &mut watch, 8_500).is_none());
+
+        let event = update("c1", &mut watch, -2).unwrap();
+        assert!(event.expired);
+        assert_eq!(event.ttl_ms, None);
+        assert!(update("c1", &mut watch, -2).is_none());
+    }
+
+    #[test]
+    fn test_update_rearms_after_ttl_extended() {
+        let mut watch = watch_of(10);
+        assert!(update("c1", &mut watch, 5_000).is_some());
+        // EXPIRE pushed it back out, then it runs down again.
+        assert!(update("c1", &mut watch, 60_000).is_none());
+        assert!(update("c1", &mut watch, 4_000).is_some());
+    }
+}
@@ -2,7 +2,7 @@
 
 use deadpool_redis::Pool;
 
-use super::model::{HashField, HashScanResult};
+use super::model::{HashField, HashFieldValue, HashScanResult};
 use crate::utils::errors::AppError;
 
 /// Get all fields of a hash (for small hashes).
@@ -93,3 +93,118 @@ pub async fn delete_hash_fields(
 
     Ok(count)
 }
+
+/// Set many hash fields in one HSET. Returns the number of fields created.
+pub async fn set_hash_fields(
+    pool: &Pool,
+    key: &str,
+    fields: &[HashField],
+) -> Result<u64, AppError> {
+    if fields.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = pool.get().await?;
+
+    let mut cmd = redis::cmd("HSET");
+    cmd.arg(key);
+    for HashField { field, value } in fields {
+        cmd.arg(field).arg(value);
+    }
+    let created: u64 = cmd
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("HSET failed: {e}")))?;
+
+    Ok(created)
+}
+
+/// Get the values of selected hash fields with HMGET.
+pub async fn get_hash_fields(
+    pool: &Pool,
+    key: &str,
+    fields: &[String],
+) -> Result<Vec<HashFieldValue>, AppError> {
+    if fields.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut conn = pool.get().await?;
+
+    let values: Vec<Option<String>> = redis::cmd("HMGET")
+        .arg(key)
+        .arg(fields)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("HMGET failed: {e}")))?;
+
+    Ok(fields
+        .iter()
+        .cloned()
+        .zip(values)
+        .map(|(field, value)| HashFieldValue { field, value })
+        .collect())
+}
+
+/// Increment an integer hash field (HINCRBY), creating it at 0 if missing.
+pub async fn incr_hash_field(
+    pool: &Pool,
+    key: &str,
+    field: &str,
+    delta: i64,
+) -> Result<i64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let value: i64 = redis::cmd("HINCRBY")
+        .arg(key)
+        .arg(field)
+        .arg(delta)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("HINCRBY failed: {e}")))?;
+
+    Ok(value)
+}
+
+/// Increment a floating-point hash field (HINCRBYFLOAT).
+pub async fn incr_hash_field_float(
+    pool: &Pool,
+    key: &str,
+    field: &str,
+    delta: f64,
+) -> Result<f64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let value: f64 = redis::cmd("HINCRBYFLOAT")
+        .arg(key)
+        .arg(field)
+        .arg(delta)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("HINCRBYFLOAT failed: {e}")))?;
+
+    Ok(value)
+}
+
+/// Sample up to `count` distinct random fields with their values
+/// (HRANDFIELD), to preview hashes too large to load.
+pub async fn random_hash_fields(
+    pool: &Pool,
+    key: &str,
+    count: u32,
+) -> Result<Vec<HashField>, AppError> {
+    let mut conn = pool.get().await?;
+
+    let pairs: Vec<(String, String)> = redis::cmd("HRANDFIELD")
+        .arg(key)
+        .arg(count)
+        .arg("WITHVALUES")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("HRANDFIELD failed: {e}")))?;
+
+    Ok(pairs
+        .into_iter()
+        .map(|(field, value)| HashField { field, value })
+        .collect())
+}
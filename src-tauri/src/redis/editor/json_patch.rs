@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::{Connection, Pool};
+use serde_json::Value;
+
+use super::model::{JsonPatch, JsonPatchKind, JsonPatchOp};
+use crate::utils::errors::AppError;
+
+/// Applies the ops in ARGV[2..] as (kind, path, value) triples, only if
+/// `JSON.GET key $` still returns ARGV[1].
+const APPLY_PATCH: &str = r"
+if redis.call('JSON.GET', KEYS[1], '$') ~= ARGV[1] then return 0 end
+for i = 2, #ARGV, 3 do
+  if ARGV[i] == 'set' then
+    redis.call('JSON.SET', KEYS[1], ARGV[i + 1], ARGV[i + 2])
+  else
+    redis.call('JSON.DEL', KEYS[1], ARGV[i + 1])
+  end
+end
+return 1
+";
+
+/// Structural diff from `old` to `new` as JSON.SET/JSON.DEL path ops.
+///
+/// Objects are compared key by key and arrays index by index. An array
+/// that shrank loses its trailing elements, highest index first so earlier
+/// deletions don't shift later ones; an array that grew, or a node whose
+/// type changed, is set whole.
+pub fn diff(old: &Value, new: &Value) -> Vec<JsonPatchOp> {
+    let mut ops = Vec::new();
+    diff_at("$", old, new, &mut ops);
+    ops
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, ops: &mut Vec<JsonPatchOp>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(before), Value::Object(after)) => {
+            for name in before.keys().filter(|name| !after.contains_key(*name)) {
+                ops.push(del(child_path(path, name)));
+            }
+            for (name, value) in after {
+                let child = child_path(path, name);
+                match before.get(name) {
+                    Some(previous) => diff_at(&child, previous, value, ops),
+                    None => ops.push(set(child, value)),
+                }
+            }
+        }
+        (Value::Array(before), Value::Array(after)) if after.len() <= before.len() => {
+            for (index, (previous, value)) in before.iter().zip(after).enumerate() {
+                diff_at(&format!("{path}[{index}]"), previous, value, ops);
+            }
+            for index in (after.len()..before.len()).rev() {
+                ops.push(del(format!("{path}[{index}]")));
+            }
+        }
+        _ => ops.push(set(path.to_string(), new)),
+    }
+}
+
+fn set(path: String, value: &Value) -> JsonPatchOp {
+    JsonPatchOp {
+        op: JsonPatchKind::Set,
+        path,
+        value: Some(value.clone()),
+    }
+}
+
+fn del(path: String) -> JsonPatchOp {
+    JsonPatchOp {
+        op: JsonPatchKind::Del,
+        path,
+        value: None,
+    }
+}
+
+/// `parent.name`, or `parent["name"]` when the name isn't a plain
+/// identifier.
+fn child_path(parent: &str, name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{parent}.{name}")
+    } else {
+        // A JSON string literal is a valid quoted JSONPath member name.
+        format!("{parent}[{}]", Value::from(name))
+    }
+}
+
+/// Diff `old` to `new`, replacing the ops with one root `set` when they
+/// would send at least as many bytes as the whole document.
+pub fn plan(old: &Value, new: &Value) -> JsonPatch {
+    let ops = diff(old, new);
+    let document_bytes = new.to_string().len() as u64;
+    let patch_bytes = ops.iter().map(op_bytes).sum();
+    if !ops.is_empty() && patch_bytes >= document_bytes {
+        let ops = vec![set("$".to_string(), new)];
+        return JsonPatch {
+            patch_bytes: ops.iter().map(op_bytes).sum(),
+            ops,
+            document_bytes,
+            full_rewrite: true,
+        };
+    }
+    JsonPatch {
+        ops,
+        patch_bytes,
+        document_bytes,
+        full_rewrite: false,
+    }
+}
+
+fn op_bytes(op: &JsonPatchOp) -> u64 {
+    (op.path.len() + op.value.as_ref().map_or(0, |v| v.to_string().len())) as u64
+}
+
+/// The stored document, as the raw `JSON.GET key $` reply and parsed.
+async fn read_document(conn: &mut Connection, key: &str) -> Result<(String, Value), AppError> {
+    let raw: Option<String> = redis::cmd("JSON.GET")
+        .arg(key)
+        .arg("$")
+        .query_async(&mut *conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("JSON.GET failed: {e}")))?;
+    let raw = raw.ok_or_else(|| AppError::NotFound(format!("Key '{key}' not found")))?;
+    // `$` wraps the root in a one-element array.
+    let document = match serde_json::from_str::<Value>(&raw) {
+        Ok(Value::Array(mut roots)) if roots.len() == 1 => roots.remove(0),
+        _ => {
+            return Err(AppError::Redis(format!(
+                "Unexpected JSON.GET reply for '{key}'"
+            )))
+        }
+    };
+    Ok((raw, document))
+}
+
+fn parse_edited(edited: &str) -> Result<Value, AppError> {
+    serde_json::from_str(edited)
+        .map_err(|e| AppError::InvalidInput(format!("Edited document is not valid JSON: {e}")))
+}
+
+/// Diff the stored `RedisJSON` document at `key` against `edited`, without
+/// writing anything.
+pub async fn diff_json(pool: &Pool, key: &str, edited: &str) -> Result<JsonPatch, AppError> {
+    let edited = parse_edited(edited)?;
+    let mut conn = pool.get().await?;
+    let (_, current) = read_document(&mut conn, key).await?;
+    Ok(plan(&current, &edited))
+}
+
+/// Update the `RedisJSON` document at `key` to `edited` with the minimal
+/// path ops from [`plan`], instead of rewriting it whole.
+///
+/// When `base` (the document the edit started from) is given and the
+/// stored document no longer equals it, nothing is written and the result
+/// is a `Conflict`. The ops run in one script that re-checks the document,
+/// so a write landing between the diff and the patch is a `Conflict` too.
+pub async fn patch_json(
+    pool: &Pool,
+    key: &str,
+    edited: &str,
+    base: Option<&str>,
+) -> Result<JsonPatch, AppError> {
+    let edited = parse_edited(edited)?;
+    let base = base
+        .map(|base| {
+            serde_json::from_str::<Value>(base).map_err(|e| {
+                AppError::InvalidInput(format!("Base document is not valid JSON: {e}"))
+            })
+        })
+        .transpose()?;
+    let mut conn = pool.get().await?;
+    let (raw, current) = read_document(&mut conn, key).await?;
+    if base.is_some_and(|base| base != current) {
+        return Err(AppError::Conflict(format!(
+            "'{key}' changed on the server since it was read"
+        )));
+    }
+
+    let patch = plan(&current, &edited);
+    if patch.ops.is_empty() {
+        return Ok(patch);
+    }
+    let mut cmd = redis::cmd("EVAL");
+    cmd.arg(APPLY_PATCH).arg(1).arg(key).arg(&raw);
+    for op in &patch.ops {
+        let (kind, value) = match op.op {
+            JsonPatchKind::Set => ("set", op.value.as_ref().map(Value::to_string)),
+            JsonPatchKind::Del => ("del", None),
+        };
+        cmd.arg(kind).arg(&op.path).arg(value.unwrap_or_default());
+    }
+    let written: i64 = cmd
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("JSON patch failed: {e}")))?;
+    if written == 0 {
+        return Err(AppError::Conflict(format!(
+            "'{key}' changed on the server since it was read"
+        )));
+    }
+    Ok(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn paths(ops: &[JsonPatchOp]) -> Vec<(JsonPatchKind, &str)> {
+        ops.iter().map(|op| (op.op, op.path.as_str())).collect()
+    }
+
+    #[test]
+    fn test_diff_objects_and_nested_values() {
+        let old = json!({"name": "a", "gone": 1, "profile": {"age": 30, "tags": ["x"]}});
+        let new = json!({"name": "b", "profile": {"age": 30, "tags": ["x"], "city": "Oslo"}});
+        let ops = diff(&old, &new);
+        assert_eq!(
+            paths(&ops),
+            [
+                (JsonPatchKind::Del, "$.gone"),
+                (JsonPatchKind::Set, "$.name"),
+                (JsonPatchKind::Set, "$.profile.city"),
+            ]
+        );
+        assert_eq!(ops[2].value, Some(json!("Oslo")));
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_arrays() {
+        let old = json!({"items": [1, 2, 3, 4]});
+        let shrunk = diff(&old, &json!({"items": [1, 9]}));
+        assert_eq!(
+            paths(&shrunk),
+            [
+                (JsonPatchKind::Set, "$.items[1]"),
+                (JsonPatchKind::Del, "$.items[3]"),
+                (JsonPatchKind::Del, "$.items[2]"),
+            ]
+        );
+
+        let grown = diff(&old, &json!({"items": [1, 2, 3, 4, 5]}));
+        assert_eq!(paths(&grown), [(JsonPatchKind::Set, "$.items")]);
+
+        let retyped = diff(&json!({"v": [1]}), &json!({"v": {"a": 1}}));
+        assert_eq!(paths(&retyped), [(JsonPatchKind::Set, "$.v")]);
+    }
+
+    #[test]
+    fn test_child_path_quotes_non_identifiers() {
+        assert_eq!(child_path("$", "user_id"), "$.user_id");
+        assert_eq!(child_path("$", "full name"), r#"$["full name"]"#);
+        assert_eq!(child_path("$.a", "2nd"), r#"$.a["2nd"]"#);
+        assert_eq!(child_path("$", r#"say "hi""#), r#"$["say \"hi\""]"#);
+    }
+
+    #[test]
+    fn test_plan_falls_back_to_full_rewrite() {
+        let big = json!({"blob": "x".repeat(1000), "n": 1});
+        let patch = plan(&big, &json!({"blob": "x".repeat(1000), "n": 2}));
+        assert!(!patch.full_rewrite);
+        assert_eq!(patch.ops.len(), 1);
+        assert!(patch.patch_bytes < patch.document_bytes);
+
+        let patch = plan(&json!({"a": 1, "b": 2}), &json!({"c": 3}));
+        assert!(patch.full_rewrite);
+        assert_eq!(paths(&patch.ops), [(JsonPatchKind::Set, "$")]);
+    }
+}
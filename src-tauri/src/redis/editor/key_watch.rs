@@ -0,0 +1,430 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use deadpool_redis::Pool;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::utils::errors::AppError;
+
+/// Strings up to this size are hashed, so same-length overwrites are noticed.
+const DIGEST_MAX_BYTES: u64 = 64 * 1024;
+
+/// Expiry drift tolerated between polls; PTTL is turned into an absolute time
+/// on arrival, so round-trip jitter shifts it slightly.
+const EXPIRY_TOLERANCE_MS: i64 = 1000;
+
+/// Reads TYPE, length and PTTL of a key in one round trip, plus a marker that
+/// changes with the content where that is cheap to get: the value of small
+/// strings and the newest entry ID of streams.
+const FINGERPRINT_SCRIPT: &str = r"
+local t = redis.call('TYPE', KEYS[1]).ok
+if t == 'none' then return {t} end
+local len = 0
+local marker = false
+if t == 'string' then
+  len = redis.call('STRLEN', KEYS[1])
+  if len <= tonumber(ARGV[1]) then marker = redis.call('GET', KEYS[1]) end
+elseif t == 'list' then len = redis.call('LLEN', KEYS[1])
+elseif t == 'hash' then len = redis.call('HLEN', KEYS[1])
+elseif t == 'set' then len = redis.call('SCARD', KEYS[1])
+elseif t == 'zset' then len = redis.call('ZCARD', KEYS[1])
+elseif t == 'stream' then
+  len = redis.call('XLEN', KEYS[1])
+  local last = redis.call('XREVRANGE', KEYS[1], '+', '-', 'COUNT', 1)
+  if #last > 0 then marker = last[1][1] end
+end
+return {t, len, redis.call('PTTL', KEYS[1]), marker}
+";
+
+/// What the watcher knows about a key between polls.
+///
+/// Collections are compared by type, length and expiry only, so an in-place
+/// update (a hash field overwritten with a new value) is only seen through
+/// keyspace notifications.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyFingerprint {
+    pub key_type: String,
+    /// STRLEN, LLEN, HLEN, SCARD, ZCARD or XLEN, depending on the type.
+    pub length: u64,
+    /// Unix time in milliseconds the key expires at; `None` if persistent.
+    pub expires_at_ms: Option<i64>,
+    #[serde(skip)]
+    digest: Option<u64>,
+}
+
+impl KeyFingerprint {
+    /// Whether `other` describes a different value than `self`.
+    pub fn differs(&self, other: &Self) -> bool {
+        let expiry_moved = match (self.expires_at_ms, other.expires_at_ms) {
+            (Some(a), Some(b)) => (a - b).abs() > EXPIRY_TOLERANCE_MS,
+            (a, b) => a.is_some() != b.is_some(),
+        };
+        self.key_type != other.key_type
+            || self.length != other.length
+            || self.digest != other.digest
+            || expiry_moved
+    }
+
+    /// Parse a [`FINGERPRINT_SCRIPT`] reply; `None` if the key is missing.
+    fn from_reply(reply: &[redis::Value], now_ms: i64) -> Result<Option<Self>, AppError> {
+        let parse_err = |e: redis::RedisError| AppError::Redis(format!("Bad fingerprint: {e}"));
+        let [key_type, length, pttl, marker] = reply else {
+            return Ok(None);
+        };
+        let key_type: String = redis::from_redis_value(key_type).map_err(parse_err)?;
+        let length: u64 = redis::from_redis_value(length).map_err(parse_err)?;
+        let pttl: i64 = redis::from_redis_value(pttl).map_err(parse_err)?;
+        let marker: Option<Vec<u8>> = redis::from_redis_value(marker).map_err(parse_err)?;
+        Ok(Some(Self {
+            key_type,
+            length,
+            expires_at_ms: (pttl >= 0).then_some(now_ms + pttl),
+            digest: marker.map(|bytes| {
+                let mut hasher = DefaultHasher::new();
+                bytes.hash(&mut hasher);
+                hasher.finish()
+            }),
+        }))
+    }
+}
+
+/// Fingerprint several keys in one pipeline; `None` for missing keys.
+pub async fn fingerprint_keys(
+    pool: &Pool,
+    keys: &[String],
+) -> Result<Vec<Option<KeyFingerprint>>, AppError> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut conn = pool.get().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("EVAL")
+            .arg(FINGERPRINT_SCRIPT)
+            .arg(1)
+            .arg(key)
+            .arg(DIGEST_MAX_BYTES);
+    }
+    let replies: Vec<Vec<redis::Value>> = pipe
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Key fingerprint failed: {e}")))?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    replies
+        .iter()
+        .map(|reply| KeyFingerprint::from_reply(reply, now_ms))
+        .collect()
+}
+
+/// How a change was noticed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSource {
+    Poll,
+    Notification,
+}
+
+/// Payload of `editor:key-changed`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChanged {
+    pub connection_id: String,
+    pub key: String,
+    pub previous: Option<KeyFingerprint>,
+    /// `None` once the key was deleted or expired.
+    pub current: Option<KeyFingerprint>,
+    pub source: ChangeSource,
+    /// Keyspace event name (`set`, `hset`, `expired`, ...) for notifications.
+    pub event: Option<String>,
+}
+
+/// How a connection's watched keys are checked.
+#[derive(Debug, Clone)]
+pub struct KeyWatchOptions {
+    pub interval: Duration,
+    /// Connection URL and database to receive keyspace notifications from,
+    /// in addition to polling.
+    pub notifications: Option<(String, u8)>,
+}
+
+type WatchedKeys = Arc<RwLock<HashMap<String, Option<KeyFingerprint>>>>;
+
+struct Watch {
+    keys: WatchedKeys,
+    task: AbortHandle,
+}
+
+/// Keys open in editors, per connection, checked for changes made by other
+/// clients.
+///
+/// Each connection with watched keys has one task that polls all of them and,
+/// if asked to, listens for their keyspace notifications on a dedicated
+/// subscriber connection. Changes are emitted as `editor:key-changed`.
+pub struct KeyWatcher {
+    watches: Arc<RwLock<HashMap<Uuid, Watch>>>,
+}
+
+impl Default for KeyWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyWatcher {
+    pub fn new() -> Self {
+        Self {
+            watches: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Start watching `key` and return its current fingerprint.
+    ///
+    /// The first key watched on a connection starts its task with `options`;
+    /// later keys join that task and `options` is ignored.
+    pub async fn watch(
+        &self,
+        connection_id: Uuid,
+        key: String,
+        pool: Pool,
+        options: KeyWatchOptions,
+        app: AppHandle,
+    ) -> Result<Option<KeyFingerprint>, AppError> {
+        let current = fingerprint_keys(&pool, std::slice::from_ref(&key))
+            .await?
+            .pop()
+            .flatten();
+
+        let mut watches = self.watches.write().await;
+        if let Some(watch) = watches.get(&connection_id) {
+            watch.keys.write().await.insert(key, current.clone());
+            return Ok(current);
+        }
+
+        let messages = match &options.notifications {
+            Some((url, db)) => Some(subscribe(url, *db).await?),
+            None => None,
+        };
+        let keys: WatchedKeys = Arc::new(RwLock::new(HashMap::from([(key, current.clone())])));
+        let task = tokio::spawn(run(
+            connection_id.to_string(),
+            pool,
+            Arc::clone(&keys),
+            options.interval,
+            messages,
+            app,
+        ));
+        watches.insert(
+            connection_id,
+            Watch {
+                keys,
+                task: task.abort_handle(),
+            },
+        );
+        tracing::info!(connection_id = %connection_id, "Editor key watch started");
+        Ok(current)
+    }
+
+    /// Stop watching `key`; the connection's task ends with its last key.
+    pub async fn unwatch(&self, connection_id: &Uuid, key: &str) {
+        let mut watches = self.watches.write().await;
+        let Some(watch) = watches.get(connection_id) else {
+            return;
+        };
+        let mut keys = watch.keys.write().await;
+        keys.remove(key);
+        if keys.is_empty() {
+            drop(keys);
+            if let Some(watch) = watches.remove(connection_id) {
+                watch.task.abort();
+                tracing::info!(connection_id = %connection_id, "Editor key watch stopped");
+            }
+        }
+    }
+
+    /// Keys currently watched on a connection, sorted.
+    pub async fn watched(&self, connection_id: &Uuid) -> Vec<String> {
+        let watches = self.watches.read().await;
+        let Some(watch) = watches.get(connection_id) else {
+            return Vec::new();
+        };
+        let mut keys: Vec<String> = watch.keys.read().await.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// Keyspace events as `(key, event)` pairs.
+type Notices = std::pin::Pin<Box<dyn futures::Stream<Item = (String, String)> + Send>>;
+
+async fn subscribe(url: &str, db: u8) -> Result<Notices, AppError> {
+    let client = redis::Client::open(url)
+        .map_err(|e| AppError::Connection(format!("Failed to create PubSub client: {e}")))?;
+    let mut pubsub = tokio::time::timeout(Duration::from_secs(10), client.get_async_pubsub())
+        .await
+        .map_err(|_| AppError::Timeout("PubSub connection timed out".into()))?
+        .map_err(|e| AppError::Connection(format!("PubSub connection failed: {e}")))?;
+    let channel_prefix = format!("__keyspace@{db}__:");
+    pubsub
+        .psubscribe(format!("{channel_prefix}*"))
+        .await
+        .map_err(|e| AppError::Redis(format!("Pattern subscribe failed: {e}")))?;
+    Ok(Box::pin(pubsub.into_on_message().filter_map(move |msg| {
+        let key = msg
+            .get_channel_name()
+            .strip_prefix(&channel_prefix)
+            .map(str::to_string);
+        let event: String = msg.get_payload().unwrap_or_default();
+        std::future::ready(key.map(|key| (key, event)))
+    })))
+}
+
+async fn run(
+    connection_id: String,
+    pool: Pool,
+    keys: WatchedKeys,
+    interval: Duration,
+    mut notices: Option<Notices>,
+    app: AppHandle,
+) {
+    let mut tick = tokio::time::interval(interval);
+    tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        let changes = tokio::select! {
+            _ = tick.tick() => {
+                let watched: Vec<String> = keys.read().await.keys().cloned().collect();
+                check(&pool, &keys, watched, ChangeSource::Poll, None).await
+            }
+            notice = next_notice(&mut notices) => {
+                let Some((key, event)) = notice else {
+                    tracing::warn!(connection_id = %connection_id, "Keyspace notifications ended; polling only");
+                    notices = None;
+                    continue;
+                };
+                if !keys.read().await.contains_key(&key) {
+                    continue;
+                }
+                check(&pool, &keys, vec![key], ChangeSource::Notification, Some(event)).await
+            }
+        };
+        match changes {
+            Ok(changes) => {
+                for mut change in changes {
+                    change.connection_id.clone_from(&connection_id);
+                    let _ = app.emit("editor:key-changed", &change);
+                }
+            }
+            Err(e) => tracing::warn!(connection_id = %connection_id, "Key watch check failed: {e}"),
+        }
+    }
+}
+
+async fn next_notice(notices: &mut Option<Notices>) -> Option<(String, String)> {
+    match notices {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Re-fingerprint `watched` and update the stored fingerprints. Polls report
+/// keys whose fingerprint moved; notifications always report, since they
+/// also cover updates a fingerprint can't see.
+async fn check(
+    pool: &Pool,
+    keys: &WatchedKeys,
+    watched: Vec<String>,
+    source: ChangeSource,
+    event: Option<String>,
+) -> Result<Vec<KeyChanged>, AppError> {
+    let fingerprints = fingerprint_keys(pool, &watched).await?;
+    let mut stored = keys.write().await;
+    let mut changes = Vec::new();
+    for (key, current) in watched.into_iter().zip(fingerprints) {
+        // Unwatched while the fingerprints were being read.
+        let Some(previous) = stored.get_mut(&key) else {
+            continue;
+        };
+        let moved = match (previous.as_ref(), current.as_ref()) {
+            (Some(a), Some(b)) => a.differs(b),
+            (a, b) => a.is_some() != b.is_some(),
+        };
+        if moved || source == ChangeSource::Notification {
+            changes.push(KeyChanged {
+                connection_id: String::new(),
+                key,
+                previous: std::mem::replace(previous, current.clone()),
+                current,
+                source,
+                event: event.clone(),
+            });
+        } else {
+            *previous = current;
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint_of(
+        key_type: &str,
+        length: u64,
+        expires_at_ms: Option<i64>,
+        digest: Option<u64>,
+    ) -> KeyFingerprint {
+        KeyFingerprint {
+            key_type: key_type.into(),
+            length,
+            expires_at_ms,
+            digest,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_differs() {
+        let base = fingerprint_of("string", 5, Some(10_000), Some(1));
+        assert!(!base.differs(&fingerprint_of("string", 5, Some(10_400), Some(1))));
+        assert!(base.differs(&fingerprint_of("string", 5, Some(20_000), Some(1))));
+        assert!(base.differs(&fingerprint_of("string", 5, None, Some(1))));
+        assert!(base.differs(&fingerprint_of("string", 6, Some(10_000), Some(1))));
+        assert!(base.differs(&fingerprint_of("string", 5, Some(10_000), Some(2))));
+    }
+
+    #[test]
+    fn test_fingerprint_from_reply() {
+        use redis::Value;
+        let reply = [
+            Value::SimpleString("list".into()),
+            Value::Int(3),
+            Value::Int(-1),
+            Value::Nil,
+        ];
+        let parsed = KeyFingerprint::from_reply(&reply, 1000).unwrap().unwrap();
+        assert_eq!(parsed, fingerprint_of("list", 3, None, None));
+
+        let reply = [
+            Value::BulkString(b"string".to_vec()),
+            Value::Int(2),
+            Value::Int(500),
+            Value::BulkString(b"hi".to_vec()),
+        ];
+        let parsed = KeyFingerprint::from_reply(&reply, 1000).unwrap().unwrap();
+        assert_eq!(parsed.expires_at_ms, Some(1500));
+        assert!(parsed.digest.is_some());
+
+        let missing = [Value::SimpleString("none".into())];
+        assert!(KeyFingerprint::from_reply(&missing, 0).unwrap().is_none());
+    }
+}
@@ -2,7 +2,8 @@
 
 use deadpool_redis::Pool;
 
-use super::model::ListElement;
+use super::cas::MATCHES;
+use super::model::{ExpectedValue, ListElement};
 use crate::utils::errors::AppError;
 
 /// Get a range of list elements.
@@ -97,3 +98,180 @@ pub async fn remove_list_element(
 
     Ok(removed)
 }
+
+/// Insert an element before or after the first occurrence of `pivot`.
+/// Returns the new list length.
+pub async fn insert_list_element(
+    pool: &Pool,
+    key: &str,
+    pivot: &str,
+    value: &str,
+    before: bool,
+) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let new_length: i64 = redis::cmd("LINSERT")
+        .arg(key)
+        .arg(if before { "BEFORE" } else { "AFTER" })
+        .arg(pivot)
+        .arg(value)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("LINSERT failed: {e}")))?;
+
+    match new_length {
+        -1 => Err(AppError::NotFound(format!(
+            "Pivot '{pivot}' not found in list '{key}'"
+        ))),
+        0 => Err(AppError::NotFound(format!("List '{key}' not found"))),
+        n => Ok(n.unsigned_abs()),
+    }
+}
+
+/// Marks the element to delete, then removes the mark, in one script so no
+/// other client sees the placeholder. Returns the removed value, or nil when
+/// the index is out of range.
+const DELETE_AT_INDEX_SCRIPT: &str = r"
+local value = redis.call('LINDEX', KEYS[1], ARGV[1])
+if not value then
+  return false
+end
+redis.call('LSET', KEYS[1], ARGV[1], ARGV[2])
+redis.call('LREM', KEYS[1], 1, ARGV[2])
+return value
+";
+
+/// Remove the element at `index` (negative counts from the tail) and
+/// return its value.
+pub async fn delete_list_index(pool: &Pool, key: &str, index: i64) -> Result<String, AppError> {
+    let mut conn = pool.get().await?;
+
+    // Unique per call, so LREM can only match the slot just overwritten.
+    let sentinel = format!("__redis-lens-deleted:{}__", uuid::Uuid::new_v4());
+    let removed: Option<String> = redis::cmd("EVAL")
+        .arg(DELETE_AT_INDEX_SCRIPT)
+        .arg(1)
+        .arg(key)
+        .arg(index)
+        .arg(&sentinel)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Delete at index failed: {e}")))?;
+
+    removed.ok_or_else(|| {
+        AppError::NotFound(format!("Index {index} is out of range for list '{key}'"))
+    })
+}
+
+/// Moves the element at ARGV[3] so it ends up at ARGV[4] (both may be
+/// negative), checking it against ARGV[1]/ARGV[2] unless the mode is `none`.
+/// The element is replaced by the ARGV[5] sentinel and removed, then
+/// inserted in front of the element now at the target, which is swapped
+/// for the sentinel first so LINSERT can't pick a duplicate.
+const MOVE_SCRIPT: &str = r"
+local len = redis.call('LLEN', KEYS[1])
+local from = tonumber(ARGV[3])
+local to = tonumber(ARGV[4])
+if from < 0 then from = len + from end
+if to < 0 then to = len + to end
+if from < 0 or from >= len or to < 0 or to >= len then
+  return {'range', false}
+end
+local value = redis.call('LINDEX', KEYS[1], from)
+if ARGV[1] ~= 'none' and not matches(value) then
+  return {'conflict', false}
+end
+if from == to then
+  return {'moved', value}
+end
+redis.call('LSET', KEYS[1], from, ARGV[5])
+redis.call('LREM', KEYS[1], 1, ARGV[5])
+if to == len - 1 then
+  redis.call('RPUSH', KEYS[1], value)
+else
+  local pivot = redis.call('LINDEX', KEYS[1], to)
+  redis.call('LSET', KEYS[1], to, ARGV[5])
+  redis.call('LINSERT', KEYS[1], 'BEFORE', ARGV[5], value)
+  redis.call('LSET', KEYS[1], to + 1, pivot)
+end
+return {'moved', value}
+";
+
+/// Move the element at index `from` so it ends up at index `to` (negative
+/// indexes count from the tail), in one script so no other client sees the
+/// list half-reordered. Returns the moved value.
+///
+/// With `expected`, the element at `from` must still hold that value or the
+/// move fails with `Conflict`, so a stale drag can't move the wrong element.
+pub async fn move_list_element(
+    pool: &Pool,
+    key: &str,
+    from: i64,
+    to: i64,
+    expected: Option<&ExpectedValue>,
+) -> Result<String, AppError> {
+    let (mode, operand) = match expected {
+        Some(expected) => expected.script_args()?,
+        None => ("none", String::new()),
+    };
+    let mut conn = pool.get().await?;
+
+    // Unique per call, so LREM and LINSERT can only match the slot just overwritten.
+    let sentinel = format!("__redis-lens-moving:{}__", uuid::Uuid::new_v4());
+    let (status, value): (String, Option<String>) = redis::cmd("EVAL")
+        .arg(format!("{MATCHES}{MOVE_SCRIPT}"))
+        .arg(1)
+        .arg(key)
+        .arg(mode)
+        .arg(operand)
+        .arg(from)
+        .arg(to)
+        .arg(&sentinel)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Move list element failed: {e}")))?;
+
+    match (status.as_str(), value) {
+        ("moved", Some(value)) => Ok(value),
+        ("conflict", _) => Err(AppError::Conflict(format!(
+            "Element {from} of '{key}' changed on the server since it was read"
+        ))),
+        _ => Err(AppError::NotFound(format!(
+            "Index {from} or {to} is out of range for list '{key}'"
+        ))),
+    }
+}
+
+/// Indexes of elements equal to `element` (LPOS).
+///
+/// `rank` picks which match to start from (negative searches from the tail),
+/// `count` caps the matches returned (0 for all) and `max_len` caps how many
+/// elements are compared.
+pub async fn list_positions(
+    pool: &Pool,
+    key: &str,
+    element: &str,
+    rank: Option<i64>,
+    count: u64,
+    max_len: Option<u64>,
+) -> Result<Vec<i64>, AppError> {
+    if rank == Some(0) {
+        return Err(AppError::InvalidInput("Rank must not be 0".into()));
+    }
+
+    let mut conn = pool.get().await?;
+
+    let mut cmd = redis::cmd("LPOS");
+    cmd.arg(key).arg(element);
+    if let Some(rank) = rank {
+        cmd.arg("RANK").arg(rank);
+    }
+    cmd.arg("COUNT").arg(count);
+    if let Some(max_len) = max_len {
+        cmd.arg("MAXLEN").arg(max_len);
+    }
+
+    cmd.query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("LPOS failed: {e}")))
+}
@@ -1,11 +1,21 @@
 // SPDX-License-Identifier: MIT
 
+pub mod cas;
+pub mod expiry_watch;
 pub mod hash_ops;
+pub mod json_patch;
+pub mod key_watch;
 pub mod list_ops;
 pub mod model;
+pub mod search_ops;
 pub mod set_ops;
 pub mod special_ops;
 pub mod stream_ops;
 pub mod string_ops;
+pub mod string_stream;
 pub mod ttl_ops;
+pub mod undo;
+pub mod value_export;
+pub mod value_stream;
+pub mod write_queue;
 pub mod zset_ops;
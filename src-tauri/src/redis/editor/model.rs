@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::utils::errors::AppError;
+
 /// Result of fetching a string value from Redis.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +18,81 @@ pub struct StringValue {
     pub is_binary: bool,
 }
 
+/// Text encoding of a binary payload sent from the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryEncoding {
+    Base64,
+    /// Hex digits, optionally separated by whitespace (`de ad be ef`).
+    Hex,
+}
+
+impl BinaryEncoding {
+    /// Decode `payload` to raw bytes.
+    pub fn decode(self, payload: &str) -> Result<Vec<u8>, AppError> {
+        match self {
+            Self::Base64 => {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD
+                    .decode(payload.trim())
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid base64 payload: {e}")))
+            }
+            Self::Hex => {
+                let digits: Vec<u8> = payload
+                    .bytes()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect();
+                if !digits.len().is_multiple_of(2) {
+                    return Err(AppError::InvalidInput(
+                        "Hex payload must have an even number of digits".into(),
+                    ));
+                }
+                digits
+                    .chunks(2)
+                    .map(|pair| {
+                        std::str::from_utf8(pair)
+                            .ok()
+                            .and_then(|s| u8::from_str_radix(s, 16).ok())
+                            .ok_or_else(|| {
+                                AppError::InvalidInput(format!(
+                                    "Invalid hex byte '{}'",
+                                    String::from_utf8_lossy(pair)
+                                ))
+                            })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The value an editor last read, for compare-and-set writes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExpectedValue {
+    /// The exact value.
+    Value(String),
+    /// Hex SHA-1 of the value, so large values needn't be sent back.
+    Sha1(String),
+}
+
+impl ExpectedValue {
+    /// Mode and operand passed to the compare-and-set scripts.
+    pub fn script_args(&self) -> Result<(&'static str, String), AppError> {
+        match self {
+            Self::Value(value) => Ok(("value", value.clone())),
+            Self::Sha1(digest) => {
+                if digest.len() != 40 || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(AppError::InvalidInput(
+                        "SHA-1 digest must be 40 hex digits".into(),
+                    ));
+                }
+                Ok(("sha1", digest.to_ascii_lowercase()))
+            }
+        }
+    }
+}
+
 /// A single hash field-value pair.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -24,6 +101,14 @@ pub struct HashField {
     pub value: String,
 }
 
+/// A requested hash field and its value, `None` if the field doesn't exist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashFieldValue {
+    pub field: String,
+    pub value: Option<String>,
+}
+
 /// Result of scanning hash fields with HSCAN.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -63,10 +148,49 @@ pub struct SetScanResult {
 #[serde(rename_all = "camelCase")]
 pub struct TtlInfo {
     pub seconds: i64,
+    /// Remaining time in milliseconds (PTTL); negative like `seconds`.
+    pub millis: i64,
+    /// When the key expires, if it has a TTL.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub is_persistent: bool,
     pub is_missing: bool,
 }
 
+/// When a key should expire: relative to now (EXPIRE, PEXPIRE) or at a Unix
+/// timestamp (EXPIREAT, PEXPIREAT).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Expiry {
+    Seconds {
+        seconds: i64,
+    },
+    Millis {
+        millis: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    AtSeconds {
+        unix_seconds: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    AtMillis {
+        unix_millis: i64,
+    },
+}
+
+/// Redis 7 condition on setting an expiry.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpireCondition {
+    /// Only if the key has no expiry.
+    Nx,
+    /// Only if the key already has an expiry.
+    Xx,
+    /// Only if the new expiry is later than the current one.
+    Gt,
+    /// Only if the new expiry is earlier than the current one.
+    Lt,
+}
+
 // ─── Sorted Set Types ────────────────────────────────────────────
 
 /// A single member-score pair in a sorted set.
@@ -86,6 +210,55 @@ pub struct ZSetScanResult {
     pub finished: bool,
 }
 
+/// How a sorted set range interprets its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ZRangeBy {
+    /// Score bounds: `1.5`, `(1.5` (exclusive), `-inf`, `+inf`.
+    Score,
+    /// Member bounds for equal-score sets: `[a` (inclusive), `(a`, `-`, `+`.
+    Lex,
+}
+
+/// A ZRANGE BYSCORE/BYLEX query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZRangeQuery {
+    pub by: ZRangeBy,
+    /// Lower bound, also when `rev` is set.
+    pub min: String,
+    pub max: String,
+    /// Highest first.
+    #[serde(default)]
+    pub rev: bool,
+    pub offset: Option<u64>,
+    pub count: Option<u64>,
+}
+
+/// Position and score of one sorted set member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZSetRank {
+    pub member: String,
+    pub score: f64,
+    /// 0-based rank, lowest score first.
+    pub rank: u64,
+    /// 0-based rank, highest score first.
+    pub rev_rank: u64,
+}
+
+/// Outcome of a bulk sorted set add.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ZSetBulkAddReport {
+    /// Pairs sent, including repeated members.
+    pub total: u64,
+    /// Members that were not in the set before.
+    pub added: u64,
+    /// Existing members whose score changed.
+    pub updated: u64,
+}
+
 // ─── Stream Types ────────────────────────────────────────────────
 
 /// A single stream entry (ID + field-value pairs).
@@ -124,6 +297,14 @@ pub struct StreamInfo {
     pub groups: Vec<ConsumerGroupInfo>,
 }
 
+/// XTRIM strategy: keep at most N entries, or drop entries below an ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamTrimStrategy {
+    MaxLen,
+    MinId,
+}
+
 // ─── JSON Type ───────────────────────────────────────────────────
 
 /// JSON value from `RedisJSON` module (or string fallback).
@@ -136,6 +317,42 @@ pub struct JsonValue {
     pub is_module: bool,
 }
 
+/// Kind of a [`JsonPatchOp`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonPatchKind {
+    /// JSON.SET at the path.
+    Set,
+    /// JSON.DEL at the path.
+    Del,
+}
+
+/// One path operation of a JSON document patch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPatchOp {
+    pub op: JsonPatchKind,
+    /// `JSONPath` of the changed node, e.g. `$.items[2].name`.
+    pub path: String,
+    /// New value for `set`; unset for `del`.
+    pub value: Option<serde_json::Value>,
+}
+
+/// Structural diff between a stored JSON document and an edited version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPatch {
+    /// In application order; empty when the documents are equal.
+    pub ops: Vec<JsonPatchOp>,
+    /// Bytes of paths and values the ops send.
+    pub patch_bytes: u64,
+    /// Bytes of the edited document, i.e. what a full rewrite sends.
+    pub document_bytes: u64,
+    /// The ops would send at least as much as the document, so the patch
+    /// is a single `set` of the root instead.
+    pub full_rewrite: bool,
+}
+
 // ─── HyperLogLog Type ───────────────────────────────────────────
 
 /// `HyperLogLog` information.
@@ -164,6 +381,43 @@ pub struct BitmapInfo {
     pub bits: Vec<u8>,
     /// Start byte offset of the returned bits.
     pub offset: u64,
+    /// Set bits within the returned window (BITCOUNT with range).
+    pub range_bit_count: u64,
+    /// Absolute offset of the first set bit within the window (BITPOS).
+    pub first_set_bit: Option<u64>,
+}
+
+/// A single BITFIELD sub-operation.
+///
+/// `encoding` is a type like `u8` or `i16` (signed up to 64 bits, unsigned
+/// up to 63). `offset` is a bit offset, or `#N` to address the N-th field of
+/// the encoding's width.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum BitfieldOp {
+    Get {
+        encoding: String,
+        offset: String,
+    },
+    Set {
+        encoding: String,
+        offset: String,
+        value: i64,
+    },
+    IncrBy {
+        encoding: String,
+        offset: String,
+        increment: i64,
+    },
+}
+
+/// BITFIELD OVERFLOW behaviour for SET and INCRBY.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BitfieldOverflow {
+    Wrap,
+    Sat,
+    Fail,
 }
 
 // ─── Geospatial Types ──────────────────────────────────────────
@@ -177,17 +431,242 @@ pub struct GeoMember {
     pub latitude: f64,
 }
 
+/// Center of a GEOSEARCH query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GeoOrigin {
+    /// FROMMEMBER — an existing member of the set.
+    Member { member: String },
+    /// FROMLONLAT — an arbitrary point.
+    LonLat { longitude: f64, latitude: f64 },
+}
+
+/// Area of a GEOSEARCH query, in the query's unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GeoShape {
+    /// BYRADIUS
+    Radius { radius: f64 },
+    /// BYBOX
+    Box { width: f64, height: f64 },
+}
+
+/// Parameters for a GEOSEARCH query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoSearchQuery {
+    pub origin: GeoOrigin,
+    pub shape: GeoShape,
+    /// Distance unit: `m`, `km`, `mi` or `ft`.
+    pub unit: String,
+    #[serde(default)]
+    pub with_coord: bool,
+    #[serde(default)]
+    pub with_dist: bool,
+    /// Sort by distance ascending (`true`), descending (`false`), or unsorted.
+    #[serde(default)]
+    pub ascending: Option<bool>,
+    #[serde(default)]
+    pub count: Option<u64>,
+}
+
+/// A single GEOSEARCH match. Distance and coordinates are only present
+/// when requested with WITHDIST / WITHCOORD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoSearchMember {
+    pub member: String,
+    pub distance: Option<f64>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+}
+
 /// Result of a GEOSEARCH query with optional distances.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GeoSearchResult {
-    pub members: Vec<GeoMember>,
+    pub members: Vec<GeoSearchMember>,
+    pub unit: String,
+}
+
+// ─── Streamed Values ─────────────────────────────────────────────
+
+/// One element of a collection value delivered through a chunked stream.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CollectionItem {
+    ListElement {
+        index: i64,
+        value: String,
+    },
+    HashField {
+        field: String,
+        value: String,
+    },
+    SetMember {
+        member: String,
+    },
+    ZsetMember {
+        member: String,
+        score: f64,
+    },
+    StreamEntry {
+        id: String,
+        fields: Vec<(String, String)>,
+    },
+}
+
+/// A collection value read in one reply, up to a size limit.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullValue {
+    pub key_type: String,
+    pub items: Vec<CollectionItem>,
+    /// Elements in the value, read before the first page.
+    pub total: u64,
+    /// Text size of the returned elements.
+    pub bytes: u64,
+    /// Whether a limit stopped the read before the last element.
+    pub truncated: bool,
+}
+
+/// File format of a value export.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueExportFormat {
+    /// One record per element, with a header row.
+    Csv,
+    /// An array of one object per element.
+    Json,
+}
+
+/// Outcome of `editor_export_key_value`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueExportReport {
+    pub path: String,
+    pub format: ValueExportFormat,
+    pub key_type: String,
+    /// Elements written. SCAN may return an element more than once if the
+    /// value changes during the export.
+    pub rows: u64,
+    pub bytes: u64,
+}
+
+// ─── Undo Journal ────────────────────────────────────────────────
+
+/// A journaled editor mutation that can be reverted.
+///
+/// The DUMP payload stays in the backend; only the summary is serialized.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoEntry {
+    pub id: uuid::Uuid,
+    pub key: String,
+    /// Editor command that made the change, e.g. `deleteHashFields`.
+    pub operation: String,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the key existed before the change (undo deletes it otherwise).
+    pub existed: bool,
+    #[serde(skip)]
+    pub payload: Option<Vec<u8>>,
+    #[serde(skip)]
+    pub pttl: i64,
+}
+
+/// How `editor_search_in_key` compares elements with the query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueSearchMode {
+    /// The query appears anywhere in the element.
+    #[default]
+    Substring,
+    /// The element matches the query as a glob, as in SCAN MATCH.
+    Glob,
+    /// The element equals the query.
+    Exact,
+}
+
+/// One element found by `editor_search_in_key`, with where it sits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ValueMatch {
+    /// A hash field whose name matched.
+    HashField {
+        field: String,
+        value: String,
+    },
+    SetMember {
+        member: String,
+    },
+    /// `rank` is the member's 0-based position by ascending score.
+    ZsetMember {
+        member: String,
+        score: f64,
+        rank: Option<u64>,
+    },
+    ListElement {
+        index: i64,
+        value: String,
+    },
+    /// A stream entry with a field name or value that matched.
+    StreamEntry {
+        id: String,
+        field: String,
+        value: String,
+    },
+}
+
+/// Result of searching inside one key's value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeySearchResult {
+    pub key_type: String,
+    /// In the order the server returned them (index or ID order for lists
+    /// and streams).
+    pub matches: Vec<ValueMatch>,
+    /// List elements or stream entries read and compared; 0 for hashes,
+    /// sets and sorted sets, which the server matches during the SCAN.
+    pub scanned: u64,
+    /// The search stopped at the match limit or the scan budget before
+    /// covering the whole value.
+    pub truncated: bool,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_expected_value_script_args() {
+        let value = ExpectedValue::Value("v".into());
+        assert_eq!(value.script_args().unwrap(), ("value", "v".to_string()));
+        let digest = ExpectedValue::Sha1("A9993E364706816ABA3E25717850C26C9CD0D89D".into());
+        assert_eq!(
+            digest.script_args().unwrap(),
+            (
+                "sha1",
+                "a9993e364706816aba3e25717850c26c9cd0d89d".to_string()
+            )
+        );
+        assert!(ExpectedValue::Sha1("abc".into()).script_args().is_err());
+    }
+
+    #[test]
+    fn test_binary_encoding_decode() {
+        assert_eq!(
+            BinaryEncoding::Hex.decode("de ad\nBE EF").unwrap(),
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(BinaryEncoding::Hex.decode("abc").is_err());
+        assert!(BinaryEncoding::Hex.decode("zz").is_err());
+        assert_eq!(
+            BinaryEncoding::Base64.decode("AJ+SlgA=").unwrap(),
+            [0, 159, 146, 150, 0]
+        );
+        assert!(BinaryEncoding::Base64.decode("not base64!").is_err());
+    }
+
     #[test]
     fn test_string_value_text_serialization() {
         let val = StringValue {
@@ -276,18 +755,37 @@ mod tests {
     fn test_ttl_info_serialization() {
         let info = TtlInfo {
             seconds: 300,
+            millis: 299_950,
+            expires_at: None,
             is_persistent: false,
             is_missing: false,
         };
         let json = serde_json::to_string(&info).expect("serialize");
         assert!(json.contains("\"seconds\":300"));
+        assert!(json.contains("\"millis\":299950"));
         assert!(json.contains("\"isPersistent\":false"));
     }
 
+    #[test]
+    fn test_expiry_deserialization() {
+        let at: Expiry = serde_json::from_str(r#"{"type":"atMillis","unixMillis":1700000000000}"#)
+            .expect("deserialize");
+        assert_eq!(
+            at,
+            Expiry::AtMillis {
+                unix_millis: 1_700_000_000_000
+            }
+        );
+        let gt: ExpireCondition = serde_json::from_str("\"gt\"").expect("deserialize");
+        assert_eq!(gt, ExpireCondition::Gt);
+    }
+
     #[test]
     fn test_ttl_info_persistent() {
         let info = TtlInfo {
             seconds: -1,
+            millis: -1,
+            expires_at: None,
             is_persistent: true,
             is_missing: false,
         };
@@ -398,12 +896,25 @@ mod tests {
             byte_length: 4,
             bits: vec![1, 0, 1, 1, 0, 0, 0, 0],
             offset: 0,
+            range_bit_count: 3,
+            first_set_bit: Some(0),
         };
         let json = serde_json::to_string(&info).expect("serialize");
         assert!(json.contains("\"bitCount\":10"));
         assert!(json.contains("\"byteLength\":4"));
     }
 
+    #[test]
+    fn test_bitfield_op_deserialization() {
+        let json = r##"[
+            {"op": "get", "encoding": "u8", "offset": "0"},
+            {"op": "incrBy", "encoding": "i16", "offset": "#1", "increment": -5}
+        ]"##;
+        let ops: Vec<BitfieldOp> = serde_json::from_str(json).expect("deserialize");
+        assert!(matches!(ops[0], BitfieldOp::Get { .. }));
+        assert!(matches!(ops[1], BitfieldOp::IncrBy { increment: -5, .. }));
+    }
+
     // ─── Geo tests ──────────────────────────────────────────────
 
     #[test]
@@ -421,13 +932,32 @@ mod tests {
     #[test]
     fn test_geo_search_result_serialization() {
         let result = GeoSearchResult {
-            members: vec![GeoMember {
+            members: vec![GeoSearchMember {
                 member: "paris".into(),
-                longitude: 2.3522,
-                latitude: 48.8566,
+                distance: Some(12.5),
+                longitude: Some(2.3522),
+                latitude: Some(48.8566),
             }],
+            unit: "km".into(),
         };
         let json = serde_json::to_string(&result).expect("serialize");
         assert!(json.contains("\"paris\""));
+        assert!(json.contains("\"distance\":12.5"));
+    }
+
+    #[test]
+    fn test_geo_search_query_deserialization() {
+        let json = r#"{
+            "origin": {"type": "lonLat", "longitude": 13.4, "latitude": 52.5},
+            "shape": {"type": "box", "width": 10, "height": 20},
+            "unit": "km",
+            "withDist": true
+        }"#;
+        let query: GeoSearchQuery = serde_json::from_str(json).expect("deserialize");
+        assert!(matches!(query.origin, GeoOrigin::LonLat { .. }));
+        assert!(matches!(query.shape, GeoShape::Box { .. }));
+        assert!(query.with_dist);
+        assert!(!query.with_coord);
+        assert!(query.count.is_none());
     }
 }
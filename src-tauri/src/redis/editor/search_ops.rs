@@ -0,0 +1,305 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::{Connection, Pool};
+use redis::Value;
+
+use super::model::{KeySearchResult, ValueMatch, ValueSearchMode};
+use super::stream_ops::parse_stream_entries;
+use crate::utils::errors::AppError;
+use crate::utils::glob;
+
+pub const DEFAULT_MATCH_LIMIT: u32 = 100;
+pub const MAX_MATCH_LIMIT: u32 = 10_000;
+/// COUNT hint for HSCAN, SSCAN and ZSCAN.
+const SCAN_COUNT: u32 = 500;
+/// List elements or stream entries read per round trip.
+const CHUNK: i64 = 1_000;
+/// Most list elements or stream entries compared in one search.
+const MAX_SCANNED: u64 = 1_000_000;
+
+/// Find the elements of `key` matching `query`, server-side where possible.
+///
+/// Hash fields, set members and sorted set members are matched by the
+/// server with HSCAN/SSCAN/ZSCAN MATCH; sorted set matches also get their
+/// rank. Exact list searches use LPOS; other list searches and stream
+/// searches (field names and values) read the value in chunks and compare
+/// here, up to a budget of elements. Stops after `limit` matches.
+pub async fn search_in_key(
+    pool: &Pool,
+    key: &str,
+    query: &str,
+    mode: ValueSearchMode,
+    limit: u32,
+) -> Result<KeySearchResult, AppError> {
+    if query.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Search text must not be empty".into(),
+        ));
+    }
+    let mut conn = pool.get().await?;
+    let key_type: String = redis::cmd("TYPE")
+        .arg(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?;
+    let limit = limit as usize;
+
+    let mut result = KeySearchResult {
+        key_type: key_type.clone(),
+        matches: Vec::new(),
+        scanned: 0,
+        truncated: false,
+    };
+    match key_type.as_str() {
+        "hash" | "set" | "zset" => {
+            scan_matches(&mut conn, key, &key_type, query, mode, limit, &mut result).await?;
+        }
+        "list" if mode == ValueSearchMode::Exact => {
+            let limit = limit + 1;
+            let indexes: Vec<i64> = redis::cmd("LPOS")
+                .arg(key)
+                .arg(query)
+                .arg("COUNT")
+                .arg(limit)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("LPOS failed: {e}")))?;
+            result.truncated = indexes.len() == limit;
+            result.matches = indexes
+                .into_iter()
+                .take(limit - 1)
+                .map(|index| ValueMatch::ListElement {
+                    index,
+                    value: query.to_string(),
+                })
+                .collect();
+        }
+        "list" => list_matches(&mut conn, key, query, mode, limit, &mut result).await?,
+        "stream" => stream_matches(&mut conn, key, query, mode, limit, &mut result).await?,
+        "none" => return Err(AppError::NotFound(format!("Key '{key}' not found"))),
+        other => {
+            return Err(AppError::InvalidInput(format!(
+                "Searching inside {other} values is not supported"
+            )))
+        }
+    }
+    Ok(result)
+}
+
+fn element_matches(mode: ValueSearchMode, query: &str, text: &str) -> bool {
+    match mode {
+        ValueSearchMode::Substring => text.contains(query),
+        ValueSearchMode::Glob => glob::matches(query, text),
+        ValueSearchMode::Exact => text == query,
+    }
+}
+
+/// The SCAN MATCH pattern equivalent to `query` in `mode`.
+fn scan_pattern(mode: ValueSearchMode, query: &str) -> String {
+    match mode {
+        ValueSearchMode::Substring => format!("*{}*", glob::escape(query)),
+        ValueSearchMode::Glob => query.to_string(),
+        ValueSearchMode::Exact => glob::escape(query),
+    }
+}
+
+async fn scan_matches(
+    conn: &mut Connection,
+    key: &str,
+    key_type: &str,
+    query: &str,
+    mode: ValueSearchMode,
+    limit: usize,
+    result: &mut KeySearchResult,
+) -> Result<(), AppError> {
+    let command = match key_type {
+        "hash" => "HSCAN",
+        "set" => "SSCAN",
+        _ => "ZSCAN",
+    };
+    let pattern = scan_pattern(mode, query);
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, items): (u64, Vec<String>) = redis::cmd(command)
+            .arg(key)
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(SCAN_COUNT)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("{command} failed: {e}")))?;
+
+        let found: Vec<ValueMatch> = match key_type {
+            "set" => items
+                .into_iter()
+                .map(|member| ValueMatch::SetMember { member })
+                .collect(),
+            _ => items
+                .chunks_exact(2)
+                .map(|pair| match key_type {
+                    "hash" => ValueMatch::HashField {
+                        field: pair[0].clone(),
+                        value: pair[1].clone(),
+                    },
+                    _ => ValueMatch::ZsetMember {
+                        member: pair[0].clone(),
+                        score: pair[1].parse().unwrap_or(f64::NAN),
+                        rank: None,
+                    },
+                })
+                .collect(),
+        };
+        result.matches.extend(found);
+
+        cursor = next;
+        if result.matches.len() > limit {
+            result.matches.truncate(limit);
+            result.truncated = true;
+            break;
+        }
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    if key_type == "zset" && !result.matches.is_empty() {
+        let mut pipe = redis::pipe();
+        for m in &result.matches {
+            if let ValueMatch::ZsetMember { member, .. } = m {
+                pipe.cmd("ZRANK").arg(key).arg(member);
+            }
+        }
+        let ranks: Vec<Option<u64>> = pipe
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("ZRANK failed: {e}")))?;
+        for (m, found) in result.matches.iter_mut().zip(ranks) {
+            if let ValueMatch::ZsetMember { rank, .. } = m {
+                *rank = found;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn list_matches(
+    conn: &mut Connection,
+    key: &str,
+    query: &str,
+    mode: ValueSearchMode,
+    limit: usize,
+    result: &mut KeySearchResult,
+) -> Result<(), AppError> {
+    let mut start: i64 = 0;
+    loop {
+        let chunk: Vec<String> = redis::cmd("LRANGE")
+            .arg(key)
+            .arg(start)
+            .arg(start + CHUNK - 1)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("LRANGE failed: {e}")))?;
+        let read = chunk.len();
+
+        for (offset, value) in (0i64..).zip(chunk) {
+            result.scanned += 1;
+            if element_matches(mode, query, &value) {
+                if result.matches.len() == limit {
+                    result.truncated = true;
+                    return Ok(());
+                }
+                result.matches.push(ValueMatch::ListElement {
+                    index: start + offset,
+                    value,
+                });
+            }
+        }
+
+        if (read as u64) < CHUNK.unsigned_abs() {
+            return Ok(());
+        }
+        if result.scanned >= MAX_SCANNED {
+            result.truncated = true;
+            return Ok(());
+        }
+        start += CHUNK;
+    }
+}
+
+async fn stream_matches(
+    conn: &mut Connection,
+    key: &str,
+    query: &str,
+    mode: ValueSearchMode,
+    limit: usize,
+    result: &mut KeySearchResult,
+) -> Result<(), AppError> {
+    let mut start = "-".to_string();
+    loop {
+        let raw: Vec<Value> = redis::cmd("XRANGE")
+            .arg(key)
+            .arg(&start)
+            .arg("+")
+            .arg("COUNT")
+            .arg(CHUNK)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("XRANGE failed: {e}")))?;
+        let entries = parse_stream_entries(&raw);
+        let read = entries.len();
+
+        for entry in entries {
+            result.scanned += 1;
+            for (field, value) in &entry.fields {
+                if element_matches(mode, query, field) || element_matches(mode, query, value) {
+                    if result.matches.len() == limit {
+                        result.truncated = true;
+                        return Ok(());
+                    }
+                    result.matches.push(ValueMatch::StreamEntry {
+                        id: entry.id.clone(),
+                        field: field.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            // Exclusive start, so the next page begins after this entry.
+            start = format!("({}", entry.id);
+        }
+
+        if (read as u64) < CHUNK.unsigned_abs() {
+            return Ok(());
+        }
+        if result.scanned >= MAX_SCANNED {
+            result.truncated = true;
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_pattern_escapes_query() {
+        assert_eq!(scan_pattern(ValueSearchMode::Substring, "a*b"), "*a\\*b*");
+        assert_eq!(scan_pattern(ValueSearchMode::Glob, "user:*"), "user:*");
+        assert_eq!(scan_pattern(ValueSearchMode::Exact, "[x]"), "\\[x\\]");
+    }
+
+    #[test]
+    fn test_element_matches() {
+        assert!(element_matches(
+            ValueSearchMode::Substring,
+            "err",
+            "an error"
+        ));
+        assert!(!element_matches(ValueSearchMode::Exact, "err", "an error"));
+        assert!(element_matches(ValueSearchMode::Glob, "an*r", "an error"));
+        assert!(!element_matches(ValueSearchMode::Glob, "err", "an error"));
+    }
+}
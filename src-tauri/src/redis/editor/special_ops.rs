@@ -1,8 +1,12 @@
 // SPDX-License-Identifier: MIT
 
 use deadpool_redis::Pool;
+use redis::Value;
 
-use super::model::{BitmapInfo, GeoMember, HllInfo, JsonValue};
+use super::model::{
+    BitfieldOp, BitfieldOverflow, BitmapInfo, GeoMember, GeoOrigin, GeoSearchMember,
+    GeoSearchQuery, GeoSearchResult, GeoShape, HllInfo, JsonValue,
+};
 use crate::utils::errors::AppError;
 
 // ─── JSON Operations ────────────────────────────────────────────
@@ -127,6 +131,9 @@ pub async fn add_hll_elements(
 // ─── Bitmap Operations ──────────────────────────────────────────
 
 /// Get bitmap info and a range of bits.
+///
+/// Reads the requested byte window with a single GETRANGE and expands it
+/// locally, alongside BITCOUNT/BITPOS restricted to the same window.
 pub async fn get_bitmap_info(
     pool: &Pool,
     key: &str,
@@ -135,44 +142,67 @@ pub async fn get_bitmap_info(
 ) -> Result<BitmapInfo, AppError> {
     let mut conn = pool.get().await?;
 
-    // Total set bits
-    let bit_count: u64 = redis::cmd("BITCOUNT")
+    // Total set bits + byte length
+    let (bit_count, byte_length): (u64, u64) = redis::pipe()
+        .cmd("BITCOUNT")
+        .arg(key)
+        .cmd("STRLEN")
         .arg(key)
         .query_async(&mut conn)
         .await
-        .map_err(|e| AppError::Redis(format!("BITCOUNT failed: {e}")))?;
+        .map_err(|e| AppError::Redis(format!("BITCOUNT/STRLEN failed: {e}")))?;
+
+    let end_byte = byte_offset.saturating_add(byte_count).min(byte_length);
+    if end_byte <= byte_offset {
+        return Ok(BitmapInfo {
+            bit_count,
+            byte_length,
+            bits: Vec::new(),
+            offset: byte_offset,
+            range_bit_count: 0,
+            first_set_bit: None,
+        });
+    }
 
-    // Byte length
-    let byte_length: u64 = redis::cmd("STRLEN")
+    // GETRANGE, BITCOUNT and BITPOS all take inclusive byte ranges
+    let last_byte = end_byte - 1;
+    let (bytes, range_bit_count, first_set_bit): (Vec<u8>, u64, i64) = redis::pipe()
+        .cmd("GETRANGE")
+        .arg(key)
+        .arg(byte_offset)
+        .arg(last_byte)
+        .cmd("BITCOUNT")
+        .arg(key)
+        .arg(byte_offset)
+        .arg(last_byte)
+        .cmd("BITPOS")
         .arg(key)
+        .arg(1)
+        .arg(byte_offset)
+        .arg(last_byte)
         .query_async(&mut conn)
         .await
-        .map_err(|e| AppError::Redis(format!("STRLEN failed: {e}")))?;
-
-    // Read individual bits for the requested range
-    let end_byte = (byte_offset + byte_count).min(byte_length);
-    let total_bits = (end_byte.saturating_sub(byte_offset)) * 8;
-    let capacity = usize::try_from(total_bits).unwrap_or(0);
-    let mut bits = Vec::with_capacity(capacity);
-
-    for bit_idx in (byte_offset * 8)..(end_byte * 8) {
-        let bit: u8 = redis::cmd("GETBIT")
-            .arg(key)
-            .arg(bit_idx)
-            .query_async(&mut conn)
-            .await
-            .map_err(|e| AppError::Redis(format!("GETBIT failed: {e}")))?;
-        bits.push(bit);
-    }
+        .map_err(|e| AppError::Redis(format!("GETRANGE failed: {e}")))?;
 
     Ok(BitmapInfo {
         bit_count,
         byte_length,
-        bits,
+        bits: expand_bits(&bytes),
         offset: byte_offset,
+        range_bit_count,
+        first_set_bit: u64::try_from(first_set_bit).ok(),
     })
 }
 
+/// Expand bytes into individual bits, most significant bit first
+/// (matching GETBIT/SETBIT offsets).
+fn expand_bits(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1))
+        .collect()
+}
+
 /// Set a single bit in a bitmap.
 pub async fn set_bitmap_bit(
     pool: &Pool,
@@ -193,6 +223,116 @@ pub async fn set_bitmap_bit(
     Ok(old_value)
 }
 
+/// Run a batch of BITFIELD GET/SET/INCRBY operations in one command.
+///
+/// Returns one value per operation; INCRBY/SET results are `None` when
+/// the FAIL overflow policy rejected them.
+pub async fn bitfield_ops(
+    pool: &Pool,
+    key: &str,
+    ops: &[BitfieldOp],
+    overflow: Option<BitfieldOverflow>,
+) -> Result<Vec<Option<i64>>, AppError> {
+    let args = bitfield_args(ops, overflow)?;
+    let mut conn = pool.get().await?;
+
+    let results: Vec<Option<i64>> = redis::cmd("BITFIELD")
+        .arg(key)
+        .arg(&args)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("BITFIELD failed: {e}")))?;
+
+    Ok(results)
+}
+
+/// Validate operations and build the BITFIELD arguments (after the key).
+fn bitfield_args(
+    ops: &[BitfieldOp],
+    overflow: Option<BitfieldOverflow>,
+) -> Result<Vec<String>, AppError> {
+    if ops.is_empty() {
+        return Err(AppError::InvalidInput(
+            "At least one BITFIELD operation is required".into(),
+        ));
+    }
+
+    let mut args = Vec::new();
+    if let Some(overflow) = overflow {
+        let policy = match overflow {
+            BitfieldOverflow::Wrap => "WRAP",
+            BitfieldOverflow::Sat => "SAT",
+            BitfieldOverflow::Fail => "FAIL",
+        };
+        args.extend(["OVERFLOW".to_string(), policy.to_string()]);
+    }
+
+    for op in ops {
+        match op {
+            BitfieldOp::Get { encoding, offset } => {
+                validate_bitfield_type(encoding, offset)?;
+                args.extend(["GET".into(), encoding.clone(), offset.clone()]);
+            }
+            BitfieldOp::Set {
+                encoding,
+                offset,
+                value,
+            } => {
+                validate_bitfield_type(encoding, offset)?;
+                args.extend([
+                    "SET".into(),
+                    encoding.clone(),
+                    offset.clone(),
+                    value.to_string(),
+                ]);
+            }
+            BitfieldOp::IncrBy {
+                encoding,
+                offset,
+                increment,
+            } => {
+                validate_bitfield_type(encoding, offset)?;
+                args.extend([
+                    "INCRBY".into(),
+                    encoding.clone(),
+                    offset.clone(),
+                    increment.to_string(),
+                ]);
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+/// Check a BITFIELD encoding (`i1`..`i64`, `u1`..`u63`) and offset (`N` or `#N`).
+fn validate_bitfield_type(encoding: &str, offset: &str) -> Result<(), AppError> {
+    let max_bits = match encoding.chars().next() {
+        Some('i') => 64,
+        Some('u') => 63,
+        _ => 0,
+    };
+    let bits: u32 = encoding.get(1..).and_then(|b| b.parse().ok()).unwrap_or(0);
+    if bits == 0 || bits > max_bits {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid BITFIELD encoding '{encoding}' (expected i1-i64 or u1-u63)"
+        )));
+    }
+
+    if offset
+        .strip_prefix('#')
+        .unwrap_or(offset)
+        .parse::<u64>()
+        .is_err()
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid BITFIELD offset '{offset}' (expected N or #N)"
+        )));
+    }
+
+    Ok(())
+}
+
 // ─── Geospatial Operations ──────────────────────────────────────
 
 /// Get all geospatial members with their coordinates.
@@ -303,3 +443,360 @@ pub async fn remove_geo_members(
 
     Ok(removed)
 }
+
+/// Units accepted by the GEO* commands.
+const GEO_UNITS: &[&str] = &["m", "km", "mi", "ft"];
+
+/// Run a GEOSEARCH proximity query (radius or box, around a member or a point).
+pub async fn geo_search(
+    pool: &Pool,
+    key: &str,
+    query: &GeoSearchQuery,
+) -> Result<GeoSearchResult, AppError> {
+    let args = geo_search_args(key, query)?;
+    let mut conn = pool.get().await?;
+
+    let raw: Value = redis::cmd("GEOSEARCH")
+        .arg(&args)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("GEOSEARCH failed: {e}")))?;
+
+    Ok(GeoSearchResult {
+        members: parse_geo_search_reply(&raw, query.with_dist, query.with_coord),
+        unit: query.unit.to_lowercase(),
+    })
+}
+
+/// Validate a query and build the GEOSEARCH arguments (including the key).
+fn geo_search_args(key: &str, query: &GeoSearchQuery) -> Result<Vec<String>, AppError> {
+    let unit = query.unit.to_lowercase();
+    if !GEO_UNITS.contains(&unit.as_str()) {
+        return Err(AppError::InvalidInput(format!(
+            "Unit must be one of m, km, mi, ft (got '{}')",
+            query.unit
+        )));
+    }
+
+    let mut args = vec![key.to_string()];
+
+    match &query.origin {
+        GeoOrigin::Member { member } => {
+            args.extend(["FROMMEMBER".into(), member.clone()]);
+        }
+        GeoOrigin::LonLat {
+            longitude,
+            latitude,
+        } => {
+            if !(-180.0..=180.0).contains(longitude)
+                || !(-85.051_128_78..=85.051_128_78).contains(latitude)
+            {
+                return Err(AppError::InvalidInput(
+                    "Coordinates are outside the range Redis can index".into(),
+                ));
+            }
+            args.extend([
+                "FROMLONLAT".into(),
+                longitude.to_string(),
+                latitude.to_string(),
+            ]);
+        }
+    }
+
+    match query.shape {
+        GeoShape::Radius { radius } => {
+            if !radius.is_finite() || radius <= 0.0 {
+                return Err(AppError::InvalidInput("Radius must be positive".into()));
+            }
+            args.extend(["BYRADIUS".into(), radius.to_string(), unit]);
+        }
+        GeoShape::Box { width, height } => {
+            if !width.is_finite() || !height.is_finite() || width <= 0.0 || height <= 0.0 {
+                return Err(AppError::InvalidInput(
+                    "Box width and height must be positive".into(),
+                ));
+            }
+            args.extend(["BYBOX".into(), width.to_string(), height.to_string(), unit]);
+        }
+    }
+
+    match query.ascending {
+        Some(true) => args.push("ASC".into()),
+        Some(false) => args.push("DESC".into()),
+        None => {}
+    }
+    if let Some(count) = query.count {
+        if count == 0 {
+            return Err(AppError::InvalidInput("Count must be positive".into()));
+        }
+        args.extend(["COUNT".into(), count.to_string()]);
+    }
+    if query.with_coord {
+        args.push("WITHCOORD".into());
+    }
+    if query.with_dist {
+        args.push("WITHDIST".into());
+    }
+
+    Ok(args)
+}
+
+/// Parse a GEOSEARCH reply.
+///
+/// Without WITH* flags each entry is a bare member name. Otherwise each entry
+/// is `[member, dist?, [lon, lat]?]` — Redis always emits distance before
+/// coordinates regardless of argument order.
+fn parse_geo_search_reply(
+    value: &Value,
+    with_dist: bool,
+    with_coord: bool,
+) -> Vec<GeoSearchMember> {
+    let Value::Array(entries) = value else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let Value::Array(items) = entry else {
+                return Some(GeoSearchMember {
+                    member: value_to_string(entry)?,
+                    distance: None,
+                    longitude: None,
+                    latitude: None,
+                });
+            };
+
+            let mut items = items.iter();
+            let member = value_to_string(items.next()?)?;
+            let distance = if with_dist {
+                items.next().and_then(value_to_f64)
+            } else {
+                None
+            };
+            let (longitude, latitude) = match (with_coord, items.next()) {
+                (true, Some(Value::Array(coord))) => (
+                    coord.first().and_then(value_to_f64),
+                    coord.get(1).and_then(value_to_f64),
+                ),
+                _ => (None, None),
+            };
+
+            Some(GeoSearchMember {
+                member,
+                distance,
+                longitude,
+                latitude,
+            })
+        })
+        .collect()
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn value_to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Double(d) => Some(*d),
+        #[allow(clippy::cast_precision_loss)]
+        Value::Int(n) => Some(*n as f64),
+        other => value_to_string(other)?.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> Value {
+        Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    fn query(origin: GeoOrigin, shape: GeoShape) -> GeoSearchQuery {
+        GeoSearchQuery {
+            origin,
+            shape,
+            unit: "km".into(),
+            with_coord: false,
+            with_dist: false,
+            ascending: None,
+            count: None,
+        }
+    }
+
+    #[test]
+    fn test_bitfield_args() {
+        let ops = vec![
+            BitfieldOp::Get {
+                encoding: "u8".into(),
+                offset: "0".into(),
+            },
+            BitfieldOp::IncrBy {
+                encoding: "i16".into(),
+                offset: "#2".into(),
+                increment: 10,
+            },
+        ];
+        let args = bitfield_args(&ops, Some(BitfieldOverflow::Sat)).unwrap();
+        assert_eq!(
+            args,
+            vec!["OVERFLOW", "SAT", "GET", "u8", "0", "INCRBY", "i16", "#2", "10"]
+        );
+    }
+
+    #[test]
+    fn test_bitfield_args_rejects_invalid() {
+        assert!(bitfield_args(&[], None).is_err());
+
+        for (encoding, offset) in [("u64", "0"), ("i65", "0"), ("x8", "0"), ("u8", "#x")] {
+            let ops = vec![BitfieldOp::Get {
+                encoding: encoding.into(),
+                offset: offset.into(),
+            }];
+            assert!(bitfield_args(&ops, None).is_err(), "{encoding} {offset}");
+        }
+    }
+
+    #[test]
+    fn test_expand_bits_msb_first() {
+        assert_eq!(expand_bits(&[0b1000_0001]), vec![1, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(expand_bits(&[0xFF, 0x00]).len(), 16);
+        assert!(expand_bits(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_geo_search_args_member_radius() {
+        let mut q = query(
+            GeoOrigin::Member {
+                member: "rome".into(),
+            },
+            GeoShape::Radius { radius: 100.0 },
+        );
+        q.ascending = Some(true);
+        q.count = Some(5);
+        q.with_dist = true;
+        let args = geo_search_args("cities", &q).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "cities",
+                "FROMMEMBER",
+                "rome",
+                "BYRADIUS",
+                "100",
+                "km",
+                "ASC",
+                "COUNT",
+                "5",
+                "WITHDIST"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_geo_search_args_lonlat_box() {
+        let q = query(
+            GeoOrigin::LonLat {
+                longitude: 13.4,
+                latitude: 52.5,
+            },
+            GeoShape::Box {
+                width: 10.0,
+                height: 20.0,
+            },
+        );
+        let args = geo_search_args("cities", &q).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "cities",
+                "FROMLONLAT",
+                "13.4",
+                "52.5",
+                "BYBOX",
+                "10",
+                "20",
+                "km"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_geo_search_args_rejects_invalid_input() {
+        let mut q = query(
+            GeoOrigin::Member { member: "a".into() },
+            GeoShape::Radius { radius: 1.0 },
+        );
+        q.unit = "parsec".into();
+        assert!(geo_search_args("k", &q).is_err());
+
+        let q = query(
+            GeoOrigin::Member { member: "a".into() },
+            GeoShape::Radius { radius: 0.0 },
+        );
+        assert!(geo_search_args("k", &q).is_err());
+
+        let q = query(
+            GeoOrigin::Member { member: "a".into() },
+            GeoShape::Radius { radius: f64::NAN },
+        );
+        assert!(geo_search_args("k", &q).is_err());
+
+        let q = query(
+            GeoOrigin::Member { member: "a".into() },
+            GeoShape::Box {
+                width: f64::NAN,
+                height: 1.0,
+            },
+        );
+        assert!(geo_search_args("k", &q).is_err());
+
+        let q = query(
+            GeoOrigin::LonLat {
+                longitude: 0.0,
+                latitude: 89.0,
+            },
+            GeoShape::Radius { radius: 1.0 },
+        );
+        assert!(geo_search_args("k", &q).is_err());
+    }
+
+    #[test]
+    fn test_parse_geo_search_reply_plain() {
+        let raw = Value::Array(vec![bulk("rome"), bulk("paris")]);
+        let members = parse_geo_search_reply(&raw, false, false);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].member, "paris");
+        assert!(members[0].distance.is_none());
+    }
+
+    #[test]
+    fn test_parse_geo_search_reply_with_dist_and_coord() {
+        let raw = Value::Array(vec![Value::Array(vec![
+            bulk("rome"),
+            bulk("12.3456"),
+            Value::Array(vec![bulk("12.4964"), bulk("41.9028")]),
+        ])]);
+        let members = parse_geo_search_reply(&raw, true, true);
+        assert_eq!(members[0].member, "rome");
+        assert_eq!(members[0].distance, Some(12.3456));
+        assert_eq!(members[0].longitude, Some(12.4964));
+        assert_eq!(members[0].latitude, Some(41.9028));
+    }
+
+    #[test]
+    fn test_parse_geo_search_reply_coord_only_resp3() {
+        let raw = Value::Array(vec![Value::Array(vec![
+            bulk("paris"),
+            Value::Array(vec![Value::Double(2.3522), Value::Double(48.8566)]),
+        ])]);
+        let members = parse_geo_search_reply(&raw, false, true);
+        assert!(members[0].distance.is_none());
+        assert_eq!(members[0].longitude, Some(2.3522));
+    }
+}
@@ -3,7 +3,9 @@
 use deadpool_redis::Pool;
 use redis::Value;
 
-use super::model::{ConsumerGroupInfo, StreamEntry, StreamInfo, StreamRangeResult};
+use super::model::{
+    ConsumerGroupInfo, StreamEntry, StreamInfo, StreamRangeResult, StreamTrimStrategy,
+};
 use crate::utils::errors::AppError;
 
 /// Get a range of stream entries using XRANGE.
@@ -127,6 +129,77 @@ pub async fn delete_stream_entries(
     Ok(deleted)
 }
 
+/// Upper limit on entries counted when estimating a MINID trim.
+const TRIM_ESTIMATE_CAP: usize = 100_000;
+
+/// Trim a stream with XTRIM. Returns the number of entries removed.
+pub async fn trim_stream(
+    pool: &Pool,
+    key: &str,
+    strategy: StreamTrimStrategy,
+    threshold: &str,
+    approximate: bool,
+) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let mut cmd = redis::cmd("XTRIM");
+    cmd.arg(key).arg(match strategy {
+        StreamTrimStrategy::MaxLen => "MAXLEN",
+        StreamTrimStrategy::MinId => "MINID",
+    });
+    if approximate {
+        cmd.arg("~");
+    }
+    cmd.arg(threshold);
+
+    let removed: u64 = cmd
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("XTRIM failed: {e}")))?;
+
+    Ok(removed)
+}
+
+/// Count how many entries an exact trim would remove.
+///
+/// Returns `(entries, exact)`; `exact` is false when a MINID count hit
+/// the estimate cap.
+pub async fn estimate_trim(
+    pool: &Pool,
+    key: &str,
+    strategy: StreamTrimStrategy,
+    threshold: &str,
+) -> Result<(u64, bool), AppError> {
+    let mut conn = pool.get().await?;
+
+    match strategy {
+        StreamTrimStrategy::MaxLen => {
+            let max_len: u64 = threshold.parse().map_err(|_| {
+                AppError::InvalidInput(format!("Invalid MAXLEN threshold: {threshold}"))
+            })?;
+            let len: u64 = redis::cmd("XLEN")
+                .arg(key)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("XLEN failed: {e}")))?;
+            Ok((len.saturating_sub(max_len), true))
+        }
+        StreamTrimStrategy::MinId => {
+            // Entries strictly below the threshold ID are the ones XTRIM drops.
+            let ids: Vec<Value> = redis::cmd("XRANGE")
+                .arg(key)
+                .arg("-")
+                .arg(format!("({threshold}"))
+                .arg("COUNT")
+                .arg(TRIM_ESTIMATE_CAP)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("XRANGE failed: {e}")))?;
+            Ok((ids.len() as u64, ids.len() < TRIM_ESTIMATE_CAP))
+        }
+    }
+}
+
 /// Get stream info including length and consumer groups.
 pub async fn get_stream_info(pool: &Pool, key: &str) -> Result<StreamInfo, AppError> {
     let mut conn = pool.get().await?;
@@ -161,7 +234,7 @@ pub async fn get_stream_info(pool: &Pool, key: &str) -> Result<StreamInfo, AppEr
 
 // ─── Parsers ─────────────────────────────────────────────────────
 
-fn parse_stream_entries(raw: &[Value]) -> Vec<StreamEntry> {
+pub fn parse_stream_entries(raw: &[Value]) -> Vec<StreamEntry> {
     let mut entries = Vec::new();
 
     for item in raw {
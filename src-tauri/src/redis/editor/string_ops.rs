@@ -56,6 +56,17 @@ pub async fn set_string_value(
     key: &str,
     value: &str,
     ttl: Option<i64>,
+) -> Result<(), AppError> {
+    set_string_bytes(pool, key, value.as_bytes(), ttl).await
+}
+
+/// Set a string to raw bytes, with the same TTL handling as
+/// [`set_string_value`].
+pub async fn set_string_bytes(
+    pool: &Pool,
+    key: &str,
+    value: &[u8],
+    ttl: Option<i64>,
 ) -> Result<(), AppError> {
     let mut conn = pool.get().await?;
 
@@ -105,3 +116,76 @@ pub async fn get_string_range(
 
     Ok(value)
 }
+
+/// Largest offset SETRANGE accepts (strings are capped at 512 MB).
+const MAX_STRING_OFFSET: u64 = 512 * 1024 * 1024 - 1;
+
+/// Add `delta` to an integer string (INCRBY; negative to decrement),
+/// creating it at 0 if missing. Returns the new value.
+pub async fn incr_string(pool: &Pool, key: &str, delta: i64) -> Result<i64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let value: i64 = redis::cmd("INCRBY")
+        .arg(key)
+        .arg(delta)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INCRBY failed: {e}")))?;
+
+    Ok(value)
+}
+
+/// Add `delta` to a floating-point string (INCRBYFLOAT). Returns the new value.
+pub async fn incr_string_float(pool: &Pool, key: &str, delta: f64) -> Result<f64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let value: f64 = redis::cmd("INCRBYFLOAT")
+        .arg(key)
+        .arg(delta)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INCRBYFLOAT failed: {e}")))?;
+
+    Ok(value)
+}
+
+/// Append to a string, creating it if missing. Returns the new length.
+pub async fn append_string(pool: &Pool, key: &str, value: &str) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+
+    let length: u64 = redis::cmd("APPEND")
+        .arg(key)
+        .arg(value)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("APPEND failed: {e}")))?;
+
+    Ok(length)
+}
+
+/// Overwrite part of a string starting at `offset` (SETRANGE), padding with
+/// zero bytes if the string is shorter. Returns the new length.
+pub async fn set_string_range(
+    pool: &Pool,
+    key: &str,
+    offset: u64,
+    value: &[u8],
+) -> Result<u64, AppError> {
+    if offset > MAX_STRING_OFFSET {
+        return Err(AppError::InvalidInput(format!(
+            "Offset must be at most {MAX_STRING_OFFSET}"
+        )));
+    }
+
+    let mut conn = pool.get().await?;
+
+    let length: u64 = redis::cmd("SETRANGE")
+        .arg(key)
+        .arg(offset)
+        .arg(value)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("SETRANGE failed: {e}")))?;
+
+    Ok(length)
+}
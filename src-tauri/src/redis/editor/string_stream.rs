@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+use serde::Serialize;
+
+use crate::utils::errors::AppError;
+
+/// Bytes read per GETRANGE when the caller does not choose.
+pub const DEFAULT_CHUNK_BYTES: u64 = 64 * 1024;
+/// Largest accepted chunk; keeps each event well below IPC limits.
+const MAX_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Payload of `editor:string-chunk`.
+///
+/// A stream emits its chunks in offset order; the last event has `done` set,
+/// or `error` if reading failed part way.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StringChunk {
+    pub stream_id: String,
+    /// Byte offset of `data` within the value.
+    pub offset: u64,
+    /// Raw bytes, base64-encoded.
+    pub data: String,
+    /// STRLEN of the value when the stream started.
+    pub total_bytes: u64,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+/// Returned by `editor_stream_string` before any chunk arrives.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StringStreamInfo {
+    pub stream_id: String,
+    pub total_bytes: u64,
+    /// First and one-past-last byte that will be streamed.
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Resolve a caller-supplied chunk size in bytes.
+pub fn chunk_bytes(requested: Option<u32>) -> Result<u64, AppError> {
+    match requested {
+        None => Ok(DEFAULT_CHUNK_BYTES),
+        Some(0) => Err(AppError::InvalidInput(
+            "Chunk size must be at least 1 byte".into(),
+        )),
+        Some(n) => Ok(u64::from(n).min(MAX_CHUNK_BYTES)),
+    }
+}
+
+/// Byte range `[start, end)` to stream out of a value of `total` bytes.
+/// `length` of `None` reads to the end.
+pub fn byte_range(total: u64, offset: u64, length: Option<u64>) -> (u64, u64) {
+    let start = offset.min(total);
+    let end = length.map_or(total, |len| start.saturating_add(len).min(total));
+    (start, end)
+}
+
+/// STRLEN of a string key; `NotFound` if it doesn't exist.
+pub async fn string_length(pool: &Pool, key: &str) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    let key_type: String = redis::cmd("TYPE")
+        .arg(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?;
+    match key_type.as_str() {
+        "string" => redis::cmd("STRLEN")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("STRLEN failed: {e}"))),
+        "none" => Err(AppError::NotFound(format!("Key '{key}' not found"))),
+        other => Err(AppError::InvalidInput(format!(
+            "Key '{key}' holds a {other}, not a string"
+        ))),
+    }
+}
+
+/// Read `[start, end)` of a string with one GETRANGE per chunk, handing each
+/// chunk to `on_chunk`. Returns the number of bytes read.
+///
+/// The value may change between chunks; a shrinking value ends the stream
+/// early rather than failing.
+pub async fn stream_string(
+    pool: &Pool,
+    key: &str,
+    (start, end): (u64, u64),
+    chunk: u64,
+    mut on_chunk: impl FnMut(u64, Vec<u8>),
+) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    let mut offset = start;
+    while offset < end {
+        let last = offset.saturating_add(chunk).min(end) - 1;
+        let bytes: Vec<u8> = redis::cmd("GETRANGE")
+            .arg(key)
+            .arg(offset)
+            .arg(last)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("GETRANGE failed: {e}")))?;
+        if bytes.is_empty() {
+            break;
+        }
+        let read = bytes.len() as u64;
+        on_chunk(offset, bytes);
+        offset += read;
+    }
+    Ok(offset - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_range() {
+        assert_eq!(byte_range(100, 0, None), (0, 100));
+        assert_eq!(byte_range(100, 10, Some(20)), (10, 30));
+        assert_eq!(byte_range(100, 90, Some(20)), (90, 100));
+        assert_eq!(byte_range(100, 200, None), (100, 100));
+        assert_eq!(byte_range(100, 5, Some(u64::MAX)), (5, 100));
+    }
+
+    #[test]
+    fn test_chunk_bytes() {
+        assert_eq!(chunk_bytes(None).unwrap(), DEFAULT_CHUNK_BYTES);
+        assert!(chunk_bytes(Some(0)).is_err());
+        assert_eq!(chunk_bytes(Some(u32::MAX)).unwrap(), MAX_CHUNK_BYTES);
+    }
+}
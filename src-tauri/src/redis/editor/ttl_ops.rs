@@ -2,24 +2,33 @@
 
 use deadpool_redis::Pool;
 
-use super::model::TtlInfo;
+use super::model::{ExpireCondition, Expiry, TtlInfo};
 use crate::utils::errors::AppError;
 
 /// Get TTL information for a key.
 pub async fn get_ttl(pool: &Pool, key: &str) -> Result<TtlInfo, AppError> {
     let mut conn = pool.get().await?;
 
-    let ttl_secs: i64 = redis::cmd("TTL")
+    let (ttl_secs, ttl_millis): (i64, i64) = redis::pipe()
+        .cmd("TTL")
+        .arg(key)
+        .cmd("PTTL")
         .arg(key)
         .query_async(&mut conn)
         .await
         .map_err(|e| AppError::Redis(format!("TTL failed: {e}")))?;
 
-    Ok(TtlInfo {
-        seconds: ttl_secs,
-        is_persistent: ttl_secs == -1,
-        is_missing: ttl_secs == -2,
-    })
+    Ok(ttl_info(ttl_secs, ttl_millis, chrono::Utc::now()))
+}
+
+fn ttl_info(seconds: i64, millis: i64, now: chrono::DateTime<chrono::Utc>) -> TtlInfo {
+    TtlInfo {
+        seconds,
+        millis,
+        expires_at: (millis >= 0).then(|| now + chrono::Duration::milliseconds(millis)),
+        is_persistent: seconds == -1,
+        is_missing: seconds == -2,
+    }
 }
 
 /// Set TTL on a key (in seconds).
@@ -36,6 +45,67 @@ pub async fn set_key_ttl(pool: &Pool, key: &str, seconds: i64) -> Result<bool, A
     Ok(result)
 }
 
+/// Set a key's expiry, relative or absolute, optionally only under a Redis 7
+/// NX/XX/GT/LT `condition`. Returns whether the expiry was set.
+pub async fn set_key_expiry(
+    pool: &Pool,
+    key: &str,
+    expiry: Expiry,
+    condition: Option<ExpireCondition>,
+) -> Result<bool, AppError> {
+    let (command, value) = expiry_command(expiry);
+    let mut cmd = redis::cmd(command);
+    cmd.arg(key).arg(value);
+    if let Some(condition) = condition {
+        cmd.arg(condition_arg(condition));
+    }
+
+    let mut conn = pool.get().await?;
+    cmd.query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("{command} failed: {e}")))
+}
+
+/// Reject expiries that are not positive or, for timestamps, not in the
+/// future: Redis would delete the key instead.
+pub fn validate_expiry(expiry: Expiry, now: chrono::DateTime<chrono::Utc>) -> Result<(), AppError> {
+    let valid = match expiry {
+        Expiry::Seconds { seconds } => seconds > 0,
+        Expiry::Millis { millis } => millis > 0,
+        Expiry::AtSeconds { unix_seconds } => unix_seconds > now.timestamp(),
+        Expiry::AtMillis { unix_millis } => unix_millis > now.timestamp_millis(),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(match expiry {
+            Expiry::Seconds { .. } | Expiry::Millis { .. } => "TTL must be positive".into(),
+            Expiry::AtSeconds { .. } | Expiry::AtMillis { .. } => {
+                "Expiry time must be in the future".into()
+            }
+        }))
+    }
+}
+
+/// The command and argument that set `expiry`.
+pub fn expiry_command(expiry: Expiry) -> (&'static str, i64) {
+    match expiry {
+        Expiry::Seconds { seconds } => ("EXPIRE", seconds),
+        Expiry::Millis { millis } => ("PEXPIRE", millis),
+        Expiry::AtSeconds { unix_seconds } => ("EXPIREAT", unix_seconds),
+        Expiry::AtMillis { unix_millis } => ("PEXPIREAT", unix_millis),
+    }
+}
+
+pub fn condition_arg(condition: ExpireCondition) -> &'static str {
+    match condition {
+        ExpireCondition::Nx => "NX",
+        ExpireCondition::Xx => "XX",
+        ExpireCondition::Gt => "GT",
+        ExpireCondition::Lt => "LT",
+    }
+}
+
 /// Remove TTL from a key (make it persistent).
 pub async fn persist_key(pool: &Pool, key: &str) -> Result<bool, AppError> {
     let mut conn = pool.get().await?;
@@ -48,3 +118,41 @@ pub async fn persist_key(pool: &Pool, key: &str) -> Result<bool, AppError> {
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_info_expires_at() {
+        let now = chrono::Utc::now();
+        let info = ttl_info(90, 89_500, now);
+        assert_eq!(
+            info.expires_at,
+            Some(now + chrono::Duration::milliseconds(89_500))
+        );
+        assert!(ttl_info(-1, -1, now).expires_at.is_none());
+        assert!(ttl_info(-2, -2, now).is_missing);
+    }
+
+    #[test]
+    fn test_validate_expiry() {
+        let now = chrono::Utc::now();
+        assert!(validate_expiry(Expiry::Millis { millis: 1 }, now).is_ok());
+        assert!(validate_expiry(Expiry::Seconds { seconds: 0 }, now).is_err());
+        let future = now.timestamp() + 60;
+        assert!(validate_expiry(
+            Expiry::AtSeconds {
+                unix_seconds: future
+            },
+            now
+        )
+        .is_ok());
+        let past = now.timestamp_millis() - 1;
+        assert!(validate_expiry(Expiry::AtMillis { unix_millis: past }, now).is_err());
+        assert_eq!(
+            expiry_command(Expiry::AtMillis { unix_millis: 5 }),
+            ("PEXPIREAT", 5)
+        );
+    }
+}
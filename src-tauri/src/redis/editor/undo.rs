@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use deadpool_redis::Pool;
+use tokio::sync::RwLock;
+
+use super::model::UndoEntry;
+use crate::utils::errors::AppError;
+
+/// Entries kept per connection; the oldest are dropped beyond this.
+const MAX_ENTRIES_PER_CONNECTION: usize = 50;
+
+/// Largest DUMP payload journaled. Bigger keys are not undoable.
+const MAX_PAYLOAD_BYTES: usize = 8 * 1024 * 1024;
+
+/// A key's serialized value and expiry, taken before a mutation.
+pub struct PriorValue {
+    /// DUMP payload, or `None` if the key did not exist.
+    payload: Option<Vec<u8>>,
+    /// Remaining TTL in milliseconds (negative when none).
+    pttl: i64,
+}
+
+/// Capture a key's current value with DUMP and PTTL.
+///
+/// Keys that are too large to journal are sized first (MEMORY USAGE, or
+/// STRLEN where MEMORY is refused) and never passed to DUMP. Returns `None`
+/// for those and when the capture fails, e.g. because ACLs deny DUMP: the
+/// write goes ahead without an undo entry.
+pub async fn capture(pool: &Pool, key: &str) -> Option<PriorValue> {
+    match try_capture(pool, key).await {
+        Ok(prior) => prior,
+        Err(e) => {
+            tracing::warn!(key = %key, "Cannot capture value for undo; undo unavailable for this change: {e}");
+            None
+        }
+    }
+}
+
+async fn try_capture(pool: &Pool, key: &str) -> Result<Option<PriorValue>, AppError> {
+    let mut conn = pool.get().await?;
+    if value_size(&mut conn, key).await? > MAX_PAYLOAD_BYTES as u64 {
+        tracing::warn!(key = %key, "Value too large to journal; undo unavailable for this key");
+        return Ok(None);
+    }
+    let (payload, pttl): (Option<Vec<u8>>, i64) = redis::pipe()
+        .cmd("DUMP")
+        .arg(key)
+        .cmd("PTTL")
+        .arg(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("DUMP failed: {e}")))?;
+    Ok(Some(PriorValue { payload, pttl }))
+}
+
+/// Approximate size of a key's value in bytes; 0 if it doesn't exist.
+async fn value_size(conn: &mut deadpool_redis::Connection, key: &str) -> Result<u64, AppError> {
+    let usage: Result<Option<u64>, _> = redis::cmd("MEMORY")
+        .arg("USAGE")
+        .arg(key)
+        .query_async(&mut *conn)
+        .await;
+    if let Ok(size) = usage {
+        return Ok(size.unwrap_or(0));
+    }
+    // Fails on anything but a string, leaving its size unknown.
+    redis::cmd("STRLEN")
+        .arg(key)
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Cannot size the value: {e}")))
+}
+
+/// Put a key back to its journaled state: RESTORE the payload, or delete the
+/// key if it did not exist before the mutation.
+pub async fn restore(pool: &Pool, entry: &UndoEntry) -> Result<(), AppError> {
+    let mut conn = pool.get().await?;
+    match &entry.payload {
+        Some(payload) => {
+            redis::cmd("RESTORE")
+                .arg(&entry.key)
+                .arg(entry.pttl.max(0))
+                .arg(payload.as_slice())
+                .arg("REPLACE")
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("RESTORE failed: {e}")))?;
+        }
+        None => {
+            redis::cmd("DEL")
+                .arg(&entry.key)
+                .query_async::<u64>(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("DEL failed: {e}")))?;
+        }
+    }
+    Ok(())
+}
+
+/// Per-connection journal of editor mutations, newest last.
+pub struct UndoJournal {
+    entries: Arc<RwLock<HashMap<String, VecDeque<UndoEntry>>>>,
+}
+
+impl Default for UndoJournal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UndoJournal {
+    /// Create an empty journal.
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Record a completed mutation of `key` along with its prior value.
+    ///
+    /// If the prior value wasn't captured or is too large to keep, earlier
+    /// entries for the same key are dropped too: restoring them would
+    /// silently discard this change.
+    pub async fn record(
+        &self,
+        connection_id: &str,
+        key: &str,
+        operation: &str,
+        prior: Option<PriorValue>,
+    ) {
+        let mut map = self.entries.write().await;
+        let history = map.entry(connection_id.to_string()).or_default();
+
+        let Some(prior) = prior.filter(|p| {
+            p.payload
+                .as_ref()
+                .is_none_or(|payload| payload.len() <= MAX_PAYLOAD_BYTES)
+        }) else {
+            history.retain(|e| e.key != key);
+            tracing::warn!(connection_id = %connection_id, key = %key, "No prior value journaled; undo unavailable for this key");
+            return;
+        };
+
+        history.push_back(UndoEntry {
+            id: uuid::Uuid::new_v4(),
+            key: key.to_string(),
+            operation: operation.to_string(),
+            recorded_at: chrono::Utc::now(),
+            existed: prior.payload.is_some(),
+            payload: prior.payload,
+            pttl: prior.pttl,
+        });
+        while history.len() > MAX_ENTRIES_PER_CONNECTION {
+            history.pop_front();
+        }
+    }
+
+    /// Journaled entries for a connection, newest first.
+    pub async fn history(&self, connection_id: &str) -> Vec<UndoEntry> {
+        let map = self.entries.read().await;
+        map.get(connection_id)
+            .map(|h| h.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Key of the most recent entry.
+    pub async fn last_key(&self, connection_id: &str) -> Option<String> {
+        let map = self.entries.read().await;
+        map.get(connection_id)
+            .and_then(VecDeque::back)
+            .map(|e| e.key.clone())
+    }
+
+    /// Remove and return the most recent entry if it is for `key`.
+    pub async fn pop_last_if(&self, connection_id: &str, key: &str) -> Option<UndoEntry> {
+        let mut map = self.entries.write().await;
+        let history = map.get_mut(connection_id)?;
+        if history.back()?.key != key {
+            return None;
+        }
+        history.pop_back()
+    }
+
+    /// Put an entry back on top, e.g. after a failed restore.
+    pub async fn push_back(&self, connection_id: &str, entry: UndoEntry) {
+        let mut map = self.entries.write().await;
+        map.entry(connection_id.to_string())
+            .or_default()
+            .push_back(entry);
+    }
+
+    /// Drop the journal for a connection.
+    pub async fn clear(&self, connection_id: &str) {
+        self.entries.write().await.remove(connection_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prior(payload: Option<Vec<u8>>) -> PriorValue {
+        PriorValue { payload, pttl: -1 }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_pop_newest_first() {
+        let journal = UndoJournal::new();
+        journal
+            .record("c", "a", "hset", Some(prior(Some(vec![1]))))
+            .await;
+        journal.record("c", "b", "sadd", Some(prior(None))).await;
+
+        let history = journal.history("c").await;
+        assert_eq!(history[0].key, "b");
+        assert!(!history[0].existed);
+
+        assert_eq!(journal.last_key("c").await.as_deref(), Some("b"));
+        assert!(journal.pop_last_if("c", "a").await.is_none());
+        let last = journal.pop_last_if("c", "b").await.unwrap();
+        assert_eq!(last.key, "b");
+        assert_eq!(journal.history("c").await.len(), 1);
+        assert!(journal.pop_last_if("other", "b").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_history_is_bounded() {
+        let journal = UndoJournal::new();
+        for i in 0..MAX_ENTRIES_PER_CONNECTION + 5 {
+            journal
+                .record("c", &format!("k{i}"), "set", Some(prior(Some(vec![0]))))
+                .await;
+        }
+        let history = journal.history("c").await;
+        assert_eq!(history.len(), MAX_ENTRIES_PER_CONNECTION);
+        assert_eq!(history.last().unwrap().key, "k5");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_value_drops_key_history() {
+        let journal = UndoJournal::new();
+        journal
+            .record("c", "big", "set", Some(prior(Some(vec![0]))))
+            .await;
+        journal.record("c", "other", "set", Some(prior(None))).await;
+        journal
+            .record(
+                "c",
+                "big",
+                "set",
+                Some(prior(Some(vec![0; MAX_PAYLOAD_BYTES + 1]))),
+            )
+            .await;
+
+        let history = journal.history("c").await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].key, "other");
+
+        // A failed capture forgets the key's history the same way.
+        journal
+            .record("c", "x", "set", Some(prior(Some(vec![0]))))
+            .await;
+        journal.record("c", "x", "set", None).await;
+        assert_eq!(journal.history("c").await.len(), 1);
+    }
+}
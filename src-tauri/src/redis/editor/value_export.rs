@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: MIT
+
+//! Export of one list, hash, set or sorted set value to a CSV or JSON file.
+//!
+//! Elements are read page by page (LRANGE, HSCAN, SSCAN, ZRANGE) and written
+//! through a buffered file as they arrive, so a value with millions of
+//! elements is never held in memory. The file is written next to the target
+//! as `<path>.part` and renamed into place once complete.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use deadpool_redis::Pool;
+use serde_json::Value;
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+use super::model::{ValueExportFormat, ValueExportReport};
+use super::{hash_ops, list_ops, set_ops, zset_ops};
+use crate::redis::connection::throttle::Throttle;
+use crate::utils::errors::AppError;
+
+/// Elements fetched from Redis per round trip.
+const PAGE: u32 = 1000;
+
+/// Write every element of `key` to `path`.
+///
+/// `on_progress` gets the elements written so far and the cardinality read
+/// before the export started. Pages wait while the connection's breaker is
+/// open.
+pub async fn export_value(
+    pool: &Pool,
+    throttle: &Throttle,
+    key: &str,
+    path: &Path,
+    format: ValueExportFormat,
+    on_progress: impl Fn(u64, u64),
+) -> Result<ValueExportReport, AppError> {
+    let (key_type, total) = key_type_and_len(pool, key).await?;
+    let columns = columns(&key_type)?;
+
+    let part = part_path(path);
+    let file = tokio::fs::File::create(&part)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to create {}: {e}", part.display())))?;
+    let mut writer = ExportWriter {
+        out: BufWriter::new(file),
+        format,
+        columns,
+        rows: 0,
+        bytes: 0,
+    };
+
+    let written = async {
+        writer.begin().await?;
+        write_elements(pool, throttle, key, &key_type, &mut writer, |rows| {
+            on_progress(rows, total);
+        })
+        .await?;
+        writer.finish().await
+    }
+    .await;
+    if let Err(e) = written {
+        let _ = tokio::fs::remove_file(&part).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&part, path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write {}: {e}", path.display())))?;
+
+    Ok(ValueExportReport {
+        path: path.display().to_string(),
+        format,
+        key_type,
+        rows: writer.rows,
+        bytes: writer.bytes,
+    })
+}
+
+async fn key_type_and_len(pool: &Pool, key: &str) -> Result<(String, u64), AppError> {
+    let mut conn = pool.get().await?;
+    let key_type: String = redis::cmd("TYPE")
+        .arg(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?;
+    let len_cmd = match key_type.as_str() {
+        "list" => "LLEN",
+        "hash" => "HLEN",
+        "set" => "SCARD",
+        "zset" => "ZCARD",
+        "none" => return Err(AppError::NotFound(format!("Key '{key}' not found"))),
+        other => {
+            return Err(AppError::InvalidInput(format!(
+                "Only list, hash, set and sorted set values can be exported, not {other}"
+            )))
+        }
+    };
+    let len: u64 = redis::cmd(len_cmd)
+        .arg(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("{len_cmd} failed: {e}")))?;
+    Ok((key_type, len))
+}
+
+/// Column names of an exported value: the CSV header and the JSON object keys.
+fn columns(key_type: &str) -> Result<&'static [&'static str], AppError> {
+    match key_type {
+        "list" => Ok(&["index", "value"]),
+        "hash" => Ok(&["field", "value"]),
+        "set" => Ok(&["member"]),
+        "zset" => Ok(&["member", "score"]),
+        other => Err(AppError::InvalidInput(format!(
+            "Cannot export a {other} value"
+        ))),
+    }
+}
+
+async fn write_elements(
+    pool: &Pool,
+    throttle: &Throttle,
+    key: &str,
+    key_type: &str,
+    writer: &mut ExportWriter,
+    on_page: impl Fn(u64),
+) -> Result<(), AppError> {
+    let mut start: i64 = 0;
+    let mut cursor = 0;
+    loop {
+        throttle.pause_while_open().await;
+        let done = match key_type {
+            "list" => {
+                let page =
+                    list_ops::get_list_range(pool, key, start, start + i64::from(PAGE) - 1).await?;
+                start += i64::from(PAGE);
+                let done = page.len() < PAGE as usize;
+                for e in page {
+                    writer.row(&[e.index.into(), e.value.into()]).await?;
+                }
+                done
+            }
+            "hash" => {
+                let page = hash_ops::scan_hash_fields(pool, key, cursor, "*", PAGE).await?;
+                cursor = page.cursor;
+                for f in page.fields {
+                    writer.row(&[f.field.into(), f.value.into()]).await?;
+                }
+                page.finished
+            }
+            "set" => {
+                let page = set_ops::scan_set_members(pool, key, cursor, "*", PAGE).await?;
+                cursor = page.cursor;
+                for member in page.members {
+                    writer.row(&[member.into()]).await?;
+                }
+                page.finished
+            }
+            // Paged by rank so members are written in score order.
+            _ => {
+                let page =
+                    zset_ops::get_zset_range(pool, key, start, start + i64::from(PAGE) - 1).await?;
+                start += i64::from(PAGE);
+                let done = page.len() < PAGE as usize;
+                for m in page {
+                    writer.row(&[m.member.into(), score_value(m.score)]).await?;
+                }
+                done
+            }
+        };
+        on_page(writer.rows);
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+/// A score as a JSON number, or as Redis spells it (`inf`, `-inf`) when
+/// JSON has no number for it.
+fn score_value(score: f64) -> Value {
+    serde_json::Number::from_f64(score)
+        .map_or_else(|| Value::String(score.to_string()), Value::Number)
+}
+
+/// `<path>.part`, where the export is written until it completes.
+fn part_path(path: &Path) -> PathBuf {
+    let mut part = OsString::from(path.as_os_str());
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+struct ExportWriter {
+    out: BufWriter<tokio::fs::File>,
+    format: ValueExportFormat,
+    columns: &'static [&'static str],
+    rows: u64,
+    bytes: u64,
+}
+
+impl ExportWriter {
+    async fn begin(&mut self) -> Result<(), AppError> {
+        let head = match self.format {
+            ValueExportFormat::Csv => {
+                let header: Vec<Value> = self.columns.iter().map(|c| (*c).into()).collect();
+                csv_line(&header)
+            }
+            ValueExportFormat::Json => "[".to_string(),
+        };
+        self.write(&head).await
+    }
+
+    async fn row(&mut self, values: &[Value]) -> Result<(), AppError> {
+        let line = match self.format {
+            ValueExportFormat::Csv => csv_line(values),
+            ValueExportFormat::Json => {
+                let separator = if self.rows == 0 { "\n" } else { ",\n" };
+                format!("{separator}{}", json_object(self.columns, values))
+            }
+        };
+        self.write(&line).await?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    async fn finish(&mut self) -> Result<(), AppError> {
+        if self.format == ValueExportFormat::Json {
+            self.write("\n]\n").await?;
+        }
+        self.out
+            .flush()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write export: {e}")))
+    }
+
+    async fn write(&mut self, text: &str) -> Result<(), AppError> {
+        self.out
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write export: {e}")))?;
+        self.bytes += text.len() as u64;
+        Ok(())
+    }
+}
+
+/// One CSV record (RFC 4180): fields with a comma, quote or line break are
+/// quoted, with quotes doubled.
+fn csv_line(values: &[Value]) -> String {
+    let mut line = values
+        .iter()
+        .map(|value| {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            if text.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+fn json_object(columns: &[&str], values: &[Value]) -> String {
+    let object: serde_json::Map<String, Value> = columns
+        .iter()
+        .zip(values)
+        .map(|(column, value)| ((*column).to_string(), value.clone()))
+        .collect();
+    Value::Object(object).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_line_quotes_when_needed() {
+        assert_eq!(csv_line(&["plain".into(), 3.into()]), "plain,3\r\n");
+        assert_eq!(
+            csv_line(&["a,b".into(), "say \"hi\"".into(), "two\nlines".into()]),
+            "\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_object_and_scores() {
+        assert_eq!(
+            json_object(&["member", "score"], &["m".into(), score_value(1.5)]),
+            r#"{"member":"m","score":1.5}"#
+        );
+        assert_eq!(score_value(f64::NEG_INFINITY), Value::String("-inf".into()));
+    }
+
+    #[test]
+    fn test_part_path_keeps_extension() {
+        assert_eq!(
+            part_path(Path::new("/tmp/members.csv")),
+            PathBuf::from("/tmp/members.csv.part")
+        );
+    }
+}
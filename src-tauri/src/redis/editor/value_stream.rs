@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::model::{CollectionItem, FullValue};
+use super::{hash_ops, list_ops, set_ops, stream_ops, zset_ops};
+use crate::utils::chunked::ChunkSender;
+use crate::utils::errors::AppError;
+
+/// Elements fetched from Redis per round trip.
+const PAGE: u32 = 1000;
+
+/// Default and largest element limits of [`read_full_value`].
+pub const DEFAULT_FULL_READ_ITEMS: u64 = 10_000;
+pub const MAX_FULL_READ_ITEMS: u64 = 1_000_000;
+/// Default and largest byte limits of [`read_full_value`].
+pub const DEFAULT_FULL_READ_BYTES: u64 = 8 * 1024 * 1024;
+pub const MAX_FULL_READ_BYTES: u64 = 128 * 1024 * 1024;
+
+/// Where a paged read of a collection value is up to.
+enum Position {
+    /// LRANGE / ZRANGE start index.
+    Rank(i64),
+    /// HSCAN / SSCAN cursor.
+    Cursor(u64),
+    /// XRANGE start ID.
+    Id(String),
+    Done,
+}
+
+/// Reads a list, hash, set, sorted set or stream value one page at a time
+/// (LRANGE, HSCAN, SSCAN, ZRANGE, XRANGE).
+pub struct ValuePages<'a> {
+    pool: &'a Pool,
+    key: &'a str,
+    key_type: String,
+    position: Position,
+}
+
+impl<'a> ValuePages<'a> {
+    /// Look up the key's type. Fails for missing keys and non-collections.
+    pub async fn open(pool: &'a Pool, key: &'a str) -> Result<Self, AppError> {
+        let key_type: String = {
+            let mut conn = pool.get().await?;
+            redis::cmd("TYPE")
+                .arg(key)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?
+        };
+        let position = match key_type.as_str() {
+            "list" | "zset" => Position::Rank(0),
+            "hash" | "set" => Position::Cursor(0),
+            "stream" => Position::Id("-".to_string()),
+            "none" => return Err(AppError::NotFound(format!("Key '{key}' not found"))),
+            other => {
+                return Err(AppError::InvalidInput(format!(
+                    "Cannot stream a {other} value; only collection types are supported"
+                )))
+            }
+        };
+        Ok(Self {
+            pool,
+            key,
+            key_type,
+            position,
+        })
+    }
+
+    pub fn key_type(&self) -> &str {
+        &self.key_type
+    }
+
+    /// The value's element count (LLEN, HLEN, SCARD, ZCARD, XLEN).
+    pub async fn element_count(&self) -> Result<u64, AppError> {
+        let command = match self.key_type.as_str() {
+            "list" => "LLEN",
+            "hash" => "HLEN",
+            "set" => "SCARD",
+            "zset" => "ZCARD",
+            _ => "XLEN",
+        };
+        let mut conn = self.pool.get().await?;
+        redis::cmd(command)
+            .arg(self.key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("{command} failed: {e}")))
+    }
+
+    /// The next page of elements, or `None` once the value is exhausted.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<CollectionItem>>, AppError> {
+        let (pool, key) = (self.pool, self.key);
+        let (items, next) = match (&self.position, self.key_type.as_str()) {
+            (Position::Done, _) => return Ok(None),
+            (Position::Rank(start), "list") => {
+                let page = list_ops::get_list_range(pool, key, *start, start + i64::from(PAGE) - 1)
+                    .await?;
+                let next = next_rank(*start, page.len());
+                let items = page
+                    .into_iter()
+                    .map(|e| CollectionItem::ListElement {
+                        index: e.index,
+                        value: e.value,
+                    })
+                    .collect();
+                (items, next)
+            }
+            // Sorted sets are paged by rank so members arrive in score order.
+            (Position::Rank(start), _) => {
+                let page = zset_ops::get_zset_range(pool, key, *start, start + i64::from(PAGE) - 1)
+                    .await?;
+                let next = next_rank(*start, page.len());
+                let items = page
+                    .into_iter()
+                    .map(|m| CollectionItem::ZsetMember {
+                        member: m.member,
+                        score: m.score,
+                    })
+                    .collect();
+                (items, next)
+            }
+            (Position::Cursor(cursor), "hash") => {
+                let page = hash_ops::scan_hash_fields(pool, key, *cursor, "*", PAGE).await?;
+                let next = next_cursor(page.cursor, page.finished);
+                let items = page
+                    .fields
+                    .into_iter()
+                    .map(|f| CollectionItem::HashField {
+                        field: f.field,
+                        value: f.value,
+                    })
+                    .collect();
+                (items, next)
+            }
+            (Position::Cursor(cursor), _) => {
+                let page = set_ops::scan_set_members(pool, key, *cursor, "*", PAGE).await?;
+                let next = next_cursor(page.cursor, page.finished);
+                let items = page
+                    .members
+                    .into_iter()
+                    .map(|member| CollectionItem::SetMember { member })
+                    .collect();
+                (items, next)
+            }
+            // Stream entries are paged with an exclusive start after the last seen ID.
+            (Position::Id(start), _) => {
+                let page =
+                    stream_ops::get_stream_range(pool, key, start, "+", u64::from(PAGE)).await?;
+                let next = match page.entries.last() {
+                    Some(last) if page.entries.len() == PAGE as usize => {
+                        Position::Id(format!("({}", last.id))
+                    }
+                    _ => Position::Done,
+                };
+                let items = page
+                    .entries
+                    .into_iter()
+                    .map(|e| CollectionItem::StreamEntry {
+                        id: e.id,
+                        fields: e.fields,
+                    })
+                    .collect();
+                (items, next)
+            }
+        };
+        self.position = next;
+        Ok(Some(items))
+    }
+}
+
+fn next_rank(start: i64, fetched: usize) -> Position {
+    if fetched < PAGE as usize {
+        Position::Done
+    } else {
+        Position::Rank(start + i64::from(PAGE))
+    }
+}
+
+fn next_cursor(cursor: u64, finished: bool) -> Position {
+    if finished {
+        Position::Done
+    } else {
+        Position::Cursor(cursor)
+    }
+}
+
+/// Stream every element of a list, hash, set, sorted set or stream key.
+///
+/// Elements are read page by page and handed to `sender` as they arrive, so
+/// the whole value is never held at once.
+pub async fn stream_value(
+    pool: &Pool,
+    key: &str,
+    sender: &mut ChunkSender<CollectionItem>,
+) -> Result<(), AppError> {
+    let mut pages = ValuePages::open(pool, key).await?;
+    while let Some(page) = pages.next_page().await? {
+        sender.extend(page)?;
+    }
+    Ok(())
+}
+
+/// Read a collection value in one reply, for copying it whole.
+///
+/// Stops at `max_items` elements or once the elements' text reaches
+/// `max_bytes`, whichever comes first, and says so with `truncated`.
+pub async fn read_full_value(
+    pool: &Pool,
+    key: &str,
+    max_items: u64,
+    max_bytes: u64,
+) -> Result<FullValue, AppError> {
+    let mut pages = ValuePages::open(pool, key).await?;
+    let total = pages.element_count().await?;
+    let mut value = FullValue {
+        key_type: pages.key_type().to_string(),
+        items: Vec::new(),
+        total,
+        bytes: 0,
+        truncated: false,
+    };
+    'pages: while let Some(page) = pages.next_page().await? {
+        for item in page {
+            let size = item_bytes(&item);
+            if value.items.len() as u64 >= max_items || value.bytes + size > max_bytes {
+                value.truncated = true;
+                break 'pages;
+            }
+            value.bytes += size;
+            value.items.push(item);
+        }
+    }
+    Ok(value)
+}
+
+/// Text size of an element: its strings, plus 8 bytes per number.
+fn item_bytes(item: &CollectionItem) -> u64 {
+    let bytes = match item {
+        CollectionItem::ListElement { value, .. } => value.len() + 8,
+        CollectionItem::HashField { field, value } => field.len() + value.len(),
+        CollectionItem::SetMember { member } => member.len(),
+        CollectionItem::ZsetMember { member, .. } => member.len() + 8,
+        CollectionItem::StreamEntry { id, fields } => {
+            id.len() + fields.iter().map(|(f, v)| f.len() + v.len()).sum::<usize>()
+        }
+    };
+    bytes as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_bytes() {
+        assert_eq!(
+            item_bytes(&CollectionItem::HashField {
+                field: "name".into(),
+                value: "ada".into(),
+            }),
+            7
+        );
+        assert_eq!(
+            item_bytes(&CollectionItem::StreamEntry {
+                id: "1-0".into(),
+                fields: vec![("a".into(), "bc".into())],
+            }),
+            6
+        );
+    }
+
+    #[test]
+    fn test_next_position() {
+        assert!(matches!(next_rank(0, PAGE as usize), Position::Rank(1000)));
+        assert!(matches!(next_rank(1000, 3), Position::Done));
+        assert!(matches!(next_cursor(42, false), Position::Cursor(42)));
+        assert!(matches!(next_cursor(0, true), Position::Done));
+    }
+}
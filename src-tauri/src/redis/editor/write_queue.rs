@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::Serialize;
+use tokio::sync::OwnedMutexGuard;
+
+/// Writes waiting on, or holding, one key's queue.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyQueueDepth {
+    pub key: String,
+    /// The running write plus the ones queued behind it.
+    pub depth: usize,
+}
+
+type QueueKey = (String, String);
+
+struct KeyQueue {
+    lock: Arc<tokio::sync::Mutex<()>>,
+    depth: usize,
+}
+
+type Queues = Arc<Mutex<HashMap<QueueKey, KeyQueue>>>;
+
+/// Serializes editor mutations per key, so two panels editing the same key
+/// can't interleave the steps of multi-step edits (undo capture, CAS check,
+/// write, WAIT).
+///
+/// Writes to one key run one at a time in arrival order (tokio's mutex is
+/// FIFO); writes to different keys don't wait for each other. Disabled, every
+/// write runs immediately.
+pub struct KeyWriteQueue {
+    enabled: AtomicBool,
+    queues: Queues,
+}
+
+impl Default for KeyWriteQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyWriteQueue {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            queues: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Turn serialization on or off; writes already queued still run in order.
+    pub fn configure(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Wait for this key's turn. The write holds the queue until the
+    /// returned guard is dropped.
+    pub async fn acquire(&self, connection_id: &str, key: &str) -> KeyWriteGuard {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return KeyWriteGuard {
+                _turn: None,
+                _slot: None,
+            };
+        }
+
+        let queue_key = (connection_id.to_string(), key.to_string());
+        let lock = {
+            let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
+            let queue = queues.entry(queue_key.clone()).or_insert_with(|| KeyQueue {
+                lock: Arc::new(tokio::sync::Mutex::new(())),
+                depth: 0,
+            });
+            queue.depth += 1;
+            Arc::clone(&queue.lock)
+        };
+        // Leaves the queue even if the caller gives up while waiting.
+        let slot = QueueSlot {
+            queues: Arc::clone(&self.queues),
+            key: queue_key,
+        };
+        let turn = lock.lock_owned().await;
+        KeyWriteGuard {
+            _turn: Some(turn),
+            _slot: Some(slot),
+        }
+    }
+
+    /// Keys of a connection with writes running or queued, deepest first.
+    pub fn depths(&self, connection_id: &str) -> Vec<KeyQueueDepth> {
+        let queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut depths: Vec<KeyQueueDepth> = queues
+            .iter()
+            .filter(|((conn, _), _)| conn == connection_id)
+            .map(|((_, key), queue)| KeyQueueDepth {
+                key: key.clone(),
+                depth: queue.depth,
+            })
+            .collect();
+        depths.sort_by(|a, b| b.depth.cmp(&a.depth).then_with(|| a.key.cmp(&b.key)));
+        depths
+    }
+}
+
+/// A write's place in a key's queue; dropping it leaves the queue.
+struct QueueSlot {
+    queues: Queues,
+    key: QueueKey,
+}
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(queue) = queues.get_mut(&self.key) {
+            queue.depth -= 1;
+            if queue.depth == 0 {
+                queues.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Held for the duration of one write; the next queued write on the key
+/// starts when it is dropped.
+pub struct KeyWriteGuard {
+    // Dropped first: the key passes to the next writer before this one
+    // leaves the queue.
+    _turn: Option<OwnedMutexGuard<()>>,
+    _slot: Option<QueueSlot>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_writes_to_one_key_run_in_order() {
+        let queue = Arc::new(KeyWriteQueue::new());
+        let first = queue.acquire("c1", "k").await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut waiters = Vec::new();
+        for n in 0..3 {
+            let queue = Arc::clone(&queue);
+            let order = Arc::clone(&order);
+            waiters.push(tokio::spawn(async move {
+                let _turn = queue.acquire("c1", "k").await;
+                order.lock().unwrap().push(n);
+            }));
+            // Let each waiter join the queue before the next one.
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(
+            queue.depths("c1"),
+            vec![KeyQueueDepth {
+                key: "k".into(),
+                depth: 4
+            }]
+        );
+        // Another key doesn't wait.
+        drop(queue.acquire("c1", "other").await);
+
+        drop(first);
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+        assert!(queue.depths("c1").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_leaves_queue() {
+        let queue = KeyWriteQueue::new();
+        let held = queue.acquire("c1", "k").await;
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(10),
+            queue.acquire("c1", "k"),
+        )
+        .await;
+        assert!(waiting.is_err());
+        assert_eq!(queue.depths("c1")[0].depth, 1);
+        drop(held);
+        assert!(queue.depths("c1").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_queue_does_not_wait() {
+        let queue = KeyWriteQueue::new();
+        queue.configure(false);
+        let _a = queue.acquire("c1", "k").await;
+        let _b = queue.acquire("c1", "k").await;
+        assert!(queue.depths("c1").is_empty());
+    }
+}
@@ -2,9 +2,16 @@
 
 use deadpool_redis::Pool;
 
-use super::model::{ZSetMember, ZSetScanResult};
+use super::model::{
+    ZRangeBy, ZRangeQuery, ZSetBulkAddReport, ZSetMember, ZSetRank, ZSetScanResult,
+};
 use crate::utils::errors::AppError;
 
+/// Members sent per ZADD in a bulk add.
+const BULK_CHUNK: usize = 1_000;
+/// Most members accepted by one bulk add.
+pub const MAX_BULK_MEMBERS: usize = 100_000;
+
 /// Get sorted set members in a range (by rank), with scores.
 pub async fn get_zset_range(
     pool: &Pool,
@@ -83,6 +90,88 @@ pub async fn add_zset_member(
     Ok(added)
 }
 
+/// Parse pasted `score member` lines. The member is everything after the
+/// first run of whitespace, so it may contain spaces; blank lines are skipped.
+pub fn parse_score_lines(text: &str) -> Result<Vec<ZSetMember>, AppError> {
+    let mut members = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((score, member)) = line.split_once(char::is_whitespace) else {
+            return Err(AppError::InvalidInput(format!(
+                "Line {}: expected 'score member'",
+                number + 1
+            )));
+        };
+        let score = parse_score(score).ok_or_else(|| {
+            AppError::InvalidInput(format!("Line {}: invalid score '{score}'", number + 1))
+        })?;
+        members.push(ZSetMember {
+            member: member.trim_start().to_string(),
+            score,
+        });
+    }
+    Ok(members)
+}
+
+/// A score as Redis accepts it: a number or `inf`/`+inf`/`-inf`, never NaN.
+fn parse_score(text: &str) -> Option<f64> {
+    match text.to_ascii_lowercase().as_str() {
+        "inf" | "+inf" => Some(f64::INFINITY),
+        "-inf" => Some(f64::NEG_INFINITY),
+        _ => text.parse::<f64>().ok().filter(|s| s.is_finite()),
+    }
+}
+
+/// Add or update many members, in chunks of [`BULK_CHUNK`].
+///
+/// Each chunk runs as a MULTI of ZCARD, `ZADD CH` and ZCARD, so the added
+/// and updated counts stay exact even if other clients write meanwhile.
+pub async fn add_zset_members_bulk(
+    pool: &Pool,
+    key: &str,
+    members: &[ZSetMember],
+) -> Result<ZSetBulkAddReport, AppError> {
+    if members.len() > MAX_BULK_MEMBERS {
+        return Err(AppError::InvalidInput(format!(
+            "At most {MAX_BULK_MEMBERS} members can be added at once"
+        )));
+    }
+    if let Some(bad) = members.iter().find(|m| m.score.is_nan()) {
+        return Err(AppError::InvalidInput(format!(
+            "Score of '{}' is not a number",
+            bad.member
+        )));
+    }
+
+    let mut conn = pool.get().await?;
+    let mut report = ZSetBulkAddReport::default();
+    for chunk in members.chunks(BULK_CHUNK) {
+        let mut zadd = redis::cmd("ZADD");
+        zadd.arg(key).arg("CH");
+        for m in chunk {
+            zadd.arg(m.score).arg(&m.member);
+        }
+        let (before, changed, after): (u64, u64, u64) = redis::pipe()
+            .atomic()
+            .cmd("ZCARD")
+            .arg(key)
+            .add_command(zadd)
+            .cmd("ZCARD")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("ZADD failed: {e}")))?;
+        let added = after.saturating_sub(before);
+        report.total += chunk.len() as u64;
+        report.added += added;
+        report.updated += changed.saturating_sub(added);
+    }
+    Ok(report)
+}
+
 /// Remove one or more members from a sorted set.
 pub async fn remove_zset_members(
     pool: &Pool,
@@ -137,3 +226,196 @@ pub async fn zset_card(pool: &Pool, key: &str) -> Result<u64, AppError> {
 
     Ok(count)
 }
+
+/// Check a range bound against the syntax Redis expects for `by`.
+fn validate_bound(by: ZRangeBy, bound: &str) -> Result<(), AppError> {
+    let valid = match by {
+        ZRangeBy::Score => {
+            let value = bound.strip_prefix('(').unwrap_or(bound);
+            matches!(value, "-inf" | "+inf" | "inf") || value.parse::<f64>().is_ok()
+        }
+        ZRangeBy::Lex => {
+            matches!(bound, "-" | "+") || bound.starts_with('[') || bound.starts_with('(')
+        }
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(match by {
+            ZRangeBy::Score => format!("Invalid score bound '{bound}'"),
+            ZRangeBy::Lex => format!("Invalid lex bound '{bound}': use -, +, [value or (value"),
+        }))
+    }
+}
+
+/// Get sorted set members by score or lex range, with scores.
+///
+/// With `offset` or `count` the range is paged with LIMIT. Lex ranges can't
+/// return scores directly, so they are looked up with ZMSCORE.
+pub async fn get_zset_range_by(
+    pool: &Pool,
+    key: &str,
+    query: &ZRangeQuery,
+) -> Result<Vec<ZSetMember>, AppError> {
+    validate_bound(query.by, &query.min)?;
+    validate_bound(query.by, &query.max)?;
+
+    let mut conn = pool.get().await?;
+
+    // With REV, ZRANGE takes the higher bound first.
+    let (first, second) = if query.rev {
+        (&query.max, &query.min)
+    } else {
+        (&query.min, &query.max)
+    };
+    let mut cmd = redis::cmd("ZRANGE");
+    cmd.arg(key).arg(first).arg(second).arg(match query.by {
+        ZRangeBy::Score => "BYSCORE",
+        ZRangeBy::Lex => "BYLEX",
+    });
+    if query.rev {
+        cmd.arg("REV");
+    }
+    if query.offset.is_some() || query.count.is_some() {
+        cmd.arg("LIMIT")
+            .arg(query.offset.unwrap_or(0))
+            .arg(query.count.map_or(-1, |c| i64::try_from(c).unwrap_or(-1)));
+    }
+
+    if query.by == ZRangeBy::Score {
+        let raw: Vec<(String, f64)> = cmd
+            .arg("WITHSCORES")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("ZRANGE BYSCORE failed: {e}")))?;
+        return Ok(raw
+            .into_iter()
+            .map(|(member, score)| ZSetMember { member, score })
+            .collect());
+    }
+
+    let members: Vec<String> = cmd
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("ZRANGE BYLEX failed: {e}")))?;
+    if members.is_empty() {
+        return Ok(Vec::new());
+    }
+    let scores: Vec<Option<f64>> = redis::cmd("ZMSCORE")
+        .arg(key)
+        .arg(&members)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("ZMSCORE failed: {e}")))?;
+
+    // A member removed between the two calls has no score; leave it out.
+    Ok(members
+        .into_iter()
+        .zip(scores)
+        .filter_map(|(member, score)| score.map(|score| ZSetMember { member, score }))
+        .collect())
+}
+
+/// Rank from both ends and score of a member, or `None` if it isn't in the set.
+pub async fn get_zset_member_rank(
+    pool: &Pool,
+    key: &str,
+    member: &str,
+) -> Result<Option<ZSetRank>, AppError> {
+    let mut conn = pool.get().await?;
+
+    let (rank, rev_rank, score): (Option<u64>, Option<u64>, Option<f64>) = redis::pipe()
+        .cmd("ZRANK")
+        .arg(key)
+        .arg(member)
+        .cmd("ZREVRANK")
+        .arg(key)
+        .arg(member)
+        .cmd("ZSCORE")
+        .arg(key)
+        .arg(member)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Member rank lookup failed: {e}")))?;
+
+    Ok(match (rank, rev_rank, score) {
+        (Some(rank), Some(rev_rank), Some(score)) => Some(ZSetRank {
+            member: member.to_string(),
+            score,
+            rank,
+            rev_rank,
+        }),
+        _ => None,
+    })
+}
+
+/// Count members within a score (ZCOUNT) or lex (ZLEXCOUNT) range.
+pub async fn zset_count(
+    pool: &Pool,
+    key: &str,
+    by: ZRangeBy,
+    min: &str,
+    max: &str,
+) -> Result<u64, AppError> {
+    validate_bound(by, min)?;
+    validate_bound(by, max)?;
+
+    let mut conn = pool.get().await?;
+
+    let cmd_name = match by {
+        ZRangeBy::Score => "ZCOUNT",
+        ZRangeBy::Lex => "ZLEXCOUNT",
+    };
+    let count: u64 = redis::cmd(cmd_name)
+        .arg(key)
+        .arg(min)
+        .arg(max)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("{cmd_name} failed: {e}")))?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_score_lines() {
+        let members = parse_score_lines("1 alice\n\n  -2.5   bob smith \n+inf top\n").unwrap();
+        let pairs: Vec<_> = members
+            .iter()
+            .map(|m| (m.member.as_str(), m.score))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("alice", 1.0), ("bob smith", -2.5), ("top", f64::INFINITY)]
+        );
+
+        let err = parse_score_lines("1 a\nabc b").unwrap_err();
+        assert!(
+            err.to_string().contains("Line 2: invalid score 'abc'"),
+            "{err}"
+        );
+        assert!(parse_score_lines("42").is_err());
+        assert!(parse_score_lines("nan a").is_err());
+        assert!(parse_score_lines("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_bound() {
+        for bound in ["1", "(1.5", "-inf", "+inf", "(-3"] {
+            assert!(validate_bound(ZRangeBy::Score, bound).is_ok(), "{bound}");
+        }
+        for bound in ["abc", "[1", ""] {
+            assert!(validate_bound(ZRangeBy::Score, bound).is_err(), "{bound}");
+        }
+        for bound in ["-", "+", "[a", "(b"] {
+            assert!(validate_bound(ZRangeBy::Lex, bound).is_ok(), "{bound}");
+        }
+        for bound in ["a", "1", ""] {
+            assert!(validate_bound(ZRangeBy::Lex, bound).is_err(), "{bound}");
+        }
+    }
+}
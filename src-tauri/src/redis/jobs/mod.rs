@@ -0,0 +1,4 @@
+// SPDX-License-Identifier: MIT
+
+pub mod model;
+pub mod scheduler;
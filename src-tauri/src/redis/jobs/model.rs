@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils::errors::AppError;
+
+/// Shortest allowed interval between runs of a job.
+pub const MIN_INTERVAL_MINUTES: u64 = 1;
+/// Longest allowed interval: four weeks.
+pub const MAX_INTERVAL_MINUTES: u64 = 28 * 24 * 60;
+
+/// What a scheduled job does on each run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum JobAction {
+    /// Key count and memory per namespace, saved to the keyspace snapshot
+    /// history.
+    KeyspaceSnapshot { delimiter: String },
+    /// Headline INFO metrics, kept in the job's run history.
+    InfoSnapshot,
+    /// UNLINK every key matching `pattern`.
+    PrunePattern { pattern: String },
+}
+
+/// A recurring job registered against a connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJob {
+    pub id: Uuid,
+    pub connection_id: String,
+    pub name: String,
+    pub action: JobAction,
+    pub interval_minutes: u64,
+    pub enabled: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ScheduledJob {
+    /// Check the job can be scheduled.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput("Job name is required".into()));
+        }
+        if !(MIN_INTERVAL_MINUTES..=MAX_INTERVAL_MINUTES).contains(&self.interval_minutes) {
+            return Err(AppError::InvalidInput(format!(
+                "Interval must be between {MIN_INTERVAL_MINUTES} and {MAX_INTERVAL_MINUTES} minutes"
+            )));
+        }
+        match &self.action {
+            JobAction::KeyspaceSnapshot { delimiter } if delimiter.is_empty() => Err(
+                AppError::InvalidInput("Keyspace snapshot delimiter is required".into()),
+            ),
+            JobAction::PrunePattern { pattern } if pattern.is_empty() => {
+                Err(AppError::InvalidInput("Prune pattern is required".into()))
+            }
+            JobAction::PrunePattern { pattern } if pattern.chars().all(|c| c == '*') => Err(
+                AppError::InvalidInput("A prune job cannot match every key".into()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobRunStatus {
+    Succeeded,
+    Failed,
+    /// The connection was not open when the job came due.
+    Skipped,
+}
+
+/// What a successful run produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum JobOutput {
+    #[serde(rename_all = "camelCase")]
+    KeyspaceSnapshot {
+        snapshot_id: Uuid,
+        total_keys: u64,
+        total_memory_bytes: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    InfoSnapshot {
+        used_memory: u64,
+        connected_clients: u64,
+        ops_per_sec: u64,
+        hit_rate_percent: f64,
+        total_keys: u64,
+    },
+    PrunePattern {
+        deleted: u64,
+    },
+}
+
+/// One execution of a job; also the payload of `job:run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRun {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub connection_id: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub status: JobRunStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<JobOutput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(action: JobAction, interval_minutes: u64) -> ScheduledJob {
+        let now = chrono::Utc::now();
+        ScheduledJob {
+            id: Uuid::new_v4(),
+            connection_id: "conn-1".into(),
+            name: "nightly".into(),
+            action,
+            interval_minutes,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_validate_job() {
+        assert!(job(JobAction::InfoSnapshot, 5).validate().is_ok());
+        assert!(job(JobAction::InfoSnapshot, 0).validate().is_err());
+        assert!(job(JobAction::InfoSnapshot, MAX_INTERVAL_MINUTES + 1)
+            .validate()
+            .is_err());
+
+        let prune = |pattern: &str| {
+            job(
+                JobAction::PrunePattern {
+                    pattern: pattern.into(),
+                },
+                60,
+            )
+        };
+        assert!(prune("cache:*").validate().is_ok());
+        assert!(prune("").validate().is_err());
+        assert!(prune("**").validate().is_err());
+
+        let mut unnamed = job(JobAction::InfoSnapshot, 5);
+        unnamed.name = "  ".into();
+        assert!(unnamed.validate().is_err());
+    }
+
+    #[test]
+    fn test_action_serialization() {
+        let json = serde_json::to_value(JobAction::PrunePattern {
+            pattern: "tmp:*".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "prunePattern", "pattern": "tmp:*" })
+        );
+        let action: JobAction =
+            serde_json::from_value(serde_json::json!({ "type": "infoSnapshot" })).unwrap();
+        assert_eq!(action, JobAction::InfoSnapshot);
+    }
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::model::{JobAction, JobOutput, JobRun, JobRunStatus, ScheduledJob};
+use crate::config::{job_store, keyspace_store};
+use crate::redis::audit::log::AuditLog;
+use crate::redis::audit::model::{AuditEntry, AuditSource};
+use crate::redis::browser::key_index::KeyIndex;
+use crate::redis::browser::{keyspace, scanner};
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::monitor::model::MetricThresholds;
+use crate::redis::monitor::poller;
+use crate::utils::errors::AppError;
+
+/// Runs enabled jobs on their intervals, one task per job.
+///
+/// Jobs are independent of the connection lifecycle: a job that comes due
+/// while its connection is closed records a skipped run.
+pub struct JobScheduler {
+    handles: Arc<RwLock<HashMap<Uuid, AbortHandle>>>,
+}
+
+impl Default for JobScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobScheduler {
+    /// Create a scheduler with no active jobs.
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Schedule every enabled job saved on disk.
+    pub async fn restore(&self, app_handle: &AppHandle) -> Result<(), AppError> {
+        for job in job_store::load_jobs(app_handle).await? {
+            self.start(job, app_handle.clone()).await;
+        }
+        Ok(())
+    }
+
+    /// Run `job` every interval, the first run one interval from now. Each
+    /// run is saved to the job's history and emitted as `job:run`. Replaces
+    /// any schedule already running for the job; disabled jobs only stop.
+    pub async fn start(&self, job: ScheduledJob, app_handle: AppHandle) {
+        self.stop(&job.id).await;
+        if !job.enabled {
+            return;
+        }
+
+        let job_id = job.id;
+        let task = tokio::spawn(async move {
+            let interval = Duration::from_secs(job.interval_minutes * 60);
+            loop {
+                tokio::time::sleep(interval).await;
+                record_run(&app_handle, &job).await;
+            }
+        });
+
+        self.handles
+            .write()
+            .await
+            .insert(job_id, task.abort_handle());
+        tracing::info!(job_id = %job_id, "Job scheduled");
+    }
+
+    /// Stop scheduling a job.
+    pub async fn stop(&self, job_id: &Uuid) {
+        if let Some(handle) = self.handles.write().await.remove(job_id) {
+            handle.abort();
+            tracing::info!(job_id = %job_id, "Job unscheduled");
+        }
+    }
+
+    /// Stop scheduling every job (e.g., on app shutdown).
+    pub async fn stop_all(&self) {
+        let mut h = self.handles.write().await;
+        for (job_id, handle) in h.drain() {
+            handle.abort();
+            tracing::info!(job_id = %job_id, "Job unscheduled (shutdown)");
+        }
+    }
+
+    /// Check if a job is scheduled.
+    pub async fn is_scheduled(&self, job_id: &Uuid) -> bool {
+        self.handles.read().await.contains_key(job_id)
+    }
+}
+
+/// Run a job once, save the run and emit `job:run`.
+pub async fn record_run(app_handle: &AppHandle, job: &ScheduledJob) -> JobRun {
+    let started_at = chrono::Utc::now();
+    let (status, output, error) = match run_job(app_handle, job).await {
+        Ok(output) => (JobRunStatus::Succeeded, Some(output), None),
+        Err(AppError::Connection(e)) => (JobRunStatus::Skipped, None, Some(e)),
+        Err(e) => {
+            tracing::warn!(job_id = %job.id, "Job failed: {e}");
+            (JobRunStatus::Failed, None, Some(e.to_string()))
+        }
+    };
+    let run = JobRun {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        connection_id: job.connection_id.clone(),
+        started_at,
+        finished_at: chrono::Utc::now(),
+        status,
+        output,
+        error,
+    };
+    if let Err(e) = job_store::save_run(app_handle, &run).await {
+        tracing::warn!(job_id = %job.id, "Failed to persist job run: {e}");
+    }
+    let _ = app_handle.emit("job:run", &run);
+    run
+}
+
+async fn run_job(app_handle: &AppHandle, job: &ScheduledJob) -> Result<JobOutput, AppError> {
+    let uuid = Uuid::parse_str(&job.connection_id)?;
+    let manager = app_handle.state::<ConnectionManager>();
+    let throttle = manager.get_throttle(&uuid).await?;
+    throttle.pause_while_open().await;
+    match &job.action {
+        JobAction::KeyspaceSnapshot { delimiter } => {
+            let pool = manager.get_pool(&uuid).await?;
+            let (snapshot, keys) =
+                keyspace::capture_snapshot(&pool, &throttle, &job.connection_id, delimiter, None)
+                    .await?;
+            keyspace_store::save_snapshot(app_handle, &snapshot, &keys).await?;
+            let _ = app_handle.emit("browser:keyspace-snapshot", &snapshot);
+            Ok(JobOutput::KeyspaceSnapshot {
+                snapshot_id: snapshot.id,
+                total_keys: snapshot.total_keys,
+                total_memory_bytes: snapshot.total_memory_bytes,
+            })
+        }
+        JobAction::InfoSnapshot => {
+            let pool = manager.get_pool(&uuid).await?;
+            let snapshot = poller::fetch_info_all(&pool, &MetricThresholds::default()).await?;
+            Ok(JobOutput::InfoSnapshot {
+                used_memory: snapshot.info.memory.used_memory,
+                connected_clients: snapshot.info.clients.connected_clients,
+                ops_per_sec: snapshot.info.stats.instantaneous_ops_per_sec,
+                hit_rate_percent: snapshot.derived.hit_rate_percent,
+                total_keys: snapshot.info.keyspace.iter().map(|db| db.keys).sum(),
+            })
+        }
+        JobAction::PrunePattern { pattern } => {
+            let pool = manager.get_write_pool(&uuid).await?;
+            let deleted = scanner::delete_by_pattern(&pool, pattern).await?;
+            app_handle
+                .state::<KeyIndex>()
+                .remove_matching(uuid, None, pattern)
+                .await;
+            app_handle
+                .state::<AuditLog>()
+                .record(AuditEntry::new(
+                    &job.connection_id,
+                    AuditSource::Scheduler,
+                    "deleteByPattern",
+                    None,
+                    vec![pattern.clone(), deleted.to_string()],
+                ))
+                .await;
+            Ok(JobOutput::PrunePattern { deleted })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scheduler_new_not_scheduled() {
+        let scheduler = JobScheduler::new();
+        let id = Uuid::new_v4();
+        assert!(!scheduler.is_scheduled(&id).await);
+        scheduler.stop(&id).await;
+    }
+}
@@ -1,8 +1,16 @@
 // SPDX-License-Identifier: MIT
 
+pub mod audit;
+pub mod backup;
 pub mod browser;
 pub mod cli;
+pub mod confirm;
 pub mod connection;
+pub mod devtools;
+pub mod diff;
 pub mod editor;
+pub mod jobs;
 pub mod monitor;
 pub mod pubsub;
+pub mod queues;
+pub mod templates;
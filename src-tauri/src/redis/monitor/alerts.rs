@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use deadpool_redis::Pool;
+use tauri::Emitter;
+
+use super::model::{
+    AlertBundle, AlertComparison, AlertMetric, AlertRecord, AlertRule, ClientInfo, ClientSummary,
+    CommandStat, StatsSnapshot,
+};
+use super::{client_list, slow_log};
+use crate::config::alert_store;
+use crate::utils::errors::AppError;
+
+/// Slow log entries included in an alert bundle.
+const BUNDLE_SLOW_LOG_COUNT: u64 = 20;
+
+/// Commands and hosts listed in an alert bundle.
+const BUNDLE_TOP_N: usize = 10;
+
+/// Idle time (seconds) after which a client counts as long-idle.
+const LONG_IDLE_SECS: u64 = 60;
+
+/// Reject rules that can never fire or would collide in the history.
+pub fn validate_rules(rules: &[AlertRule]) -> Result<(), AppError> {
+    let mut seen = std::collections::HashSet::new();
+    for rule in rules {
+        if rule.id.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Alert rule ID must not be empty".into(),
+            ));
+        }
+        if !seen.insert(rule.id.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Duplicate alert rule ID '{}'",
+                rule.id
+            )));
+        }
+        if !rule.threshold.is_finite() {
+            return Err(AppError::InvalidInput(format!(
+                "Alert rule '{}' has an invalid threshold",
+                rule.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Rules breached by a snapshot, paired with the observed value.
+pub fn evaluate<'a>(rules: &'a [AlertRule], snapshot: &StatsSnapshot) -> Vec<(&'a AlertRule, f64)> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let value = metric_value(rule.metric, snapshot)?;
+            let breached = match rule.comparison {
+                AlertComparison::Above => value > rule.threshold,
+                AlertComparison::Below => value < rule.threshold,
+            };
+            breached.then_some((rule, value))
+        })
+        .collect()
+}
+
+/// Read a metric from a snapshot. `None` when the server doesn't report it.
+#[allow(clippy::cast_precision_loss)]
+fn metric_value(metric: AlertMetric, snapshot: &StatsSnapshot) -> Option<f64> {
+    let info = &snapshot.info;
+    match metric {
+        AlertMetric::UsedMemoryBytes => Some(info.memory.used_memory as f64),
+        AlertMetric::MemoryUsagePercent => snapshot.derived.memory_usage_percent,
+        AlertMetric::HitRatePercent => Some(snapshot.derived.hit_rate_percent),
+        AlertMetric::FragmentationRatio => Some(info.memory.mem_fragmentation_ratio),
+        AlertMetric::ConnectedClients => Some(info.clients.connected_clients as f64),
+        AlertMetric::BlockedClients => Some(info.clients.blocked_clients as f64),
+        AlertMetric::OpsPerSec => Some(info.stats.instantaneous_ops_per_sec as f64),
+    }
+}
+
+/// Capture the correlated bundle for a fired rule, persist it and emit
+/// `monitor:alert`.
+pub async fn record_alert(
+    pool: &Pool,
+    app_handle: &tauri::AppHandle,
+    connection_id: &str,
+    rule: &AlertRule,
+    value: f64,
+    snapshot: &StatsSnapshot,
+) -> Result<AlertRecord, AppError> {
+    let record = AlertRecord {
+        id: uuid::Uuid::new_v4(),
+        connection_id: connection_id.to_string(),
+        rule: rule.clone(),
+        value,
+        fired_at: chrono::Utc::now(),
+        bundle: capture_bundle(pool, snapshot).await,
+    };
+
+    alert_store::save_alert(app_handle, &record).await?;
+    let _ = app_handle.emit("monitor:alert", &record);
+    tracing::warn!(connection_id = %connection_id, rule = %rule.name, value = value, "Alert fired");
+    Ok(record)
+}
+
+/// Gather slow log tail, client summary and top commands around `snapshot`.
+///
+/// A slow log or client list the server refuses (ACL, renamed command)
+/// leaves that part empty rather than losing the alert.
+async fn capture_bundle(pool: &Pool, snapshot: &StatsSnapshot) -> AlertBundle {
+    let slow_log = slow_log::get_slow_log(pool, BUNDLE_SLOW_LOG_COUNT)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Alert bundle without slow log: {e}");
+            Vec::new()
+        });
+    let clients = client_list::get_client_list(pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Alert bundle without client list: {e}");
+            Vec::new()
+        });
+
+    AlertBundle {
+        snapshot: snapshot.clone(),
+        slow_log,
+        clients: summarize_clients(&clients),
+        top_commands: top_commands(&snapshot.info.raw, BUNDLE_TOP_N),
+    }
+}
+
+/// Count clients overall, blocked, long-idle and per host.
+fn summarize_clients(clients: &[ClientInfo]) -> ClientSummary {
+    let mut per_host: HashMap<&str, u64> = HashMap::new();
+    for client in clients {
+        let host = client
+            .addr
+            .rsplit_once(':')
+            .map_or(client.addr.as_str(), |(host, _)| host);
+        *per_host.entry(host).or_default() += 1;
+    }
+
+    let mut top_hosts: Vec<(String, u64)> = per_host
+        .into_iter()
+        .map(|(host, count)| (host.to_string(), count))
+        .collect();
+    top_hosts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_hosts.truncate(BUNDLE_TOP_N);
+
+    ClientSummary {
+        total: clients.len() as u64,
+        blocked: clients.iter().filter(|c| c.flags.contains('b')).count() as u64,
+        long_idle: clients.iter().filter(|c| c.idle >= LONG_IDLE_SECS).count() as u64,
+        top_hosts,
+    }
+}
+
+/// Parse `cmdstat_*` lines from INFO and return the `limit` most-called.
+///
+/// Values look like `calls=10,usec=52,usec_per_call=5.20,rejected_calls=0`.
+fn top_commands<S: std::hash::BuildHasher>(
+    raw: &HashMap<String, String, S>,
+    limit: usize,
+) -> Vec<CommandStat> {
+    let mut stats: Vec<CommandStat> = raw
+        .iter()
+        .filter_map(|(key, value)| {
+            let command = key.strip_prefix("cmdstat_")?;
+            let fields: HashMap<&str, &str> =
+                value.split(',').filter_map(|f| f.split_once('=')).collect();
+            Some(CommandStat {
+                command: command.to_string(),
+                calls: fields.get("calls")?.parse().ok()?,
+                usec: fields.get("usec").and_then(|v| v.parse().ok()).unwrap_or(0),
+                usec_per_call: fields
+                    .get("usec_per_call")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.0),
+            })
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.calls
+            .cmp(&a.calls)
+            .then_with(|| a.command.cmp(&b.command))
+    });
+    stats.truncate(limit);
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::monitor::info_parser;
+    use crate::redis::monitor::model::MetricThresholds;
+
+    const INFO: &str = "# Clients\r\nconnected_clients:42\r\nblocked_clients:3\r\n\
+# Memory\r\nused_memory:1048576\r\nmaxmemory:0\r\nmem_fragmentation_ratio:1.2\r\n\
+# Commandstats\r\ncmdstat_get:calls=900,usec=1800,usec_per_call=2.00\r\n\
+cmdstat_set:calls=100,usec=500,usec_per_call=5.00\r\n\
+cmdstat_keys:calls=2,usec=90000,usec_per_call=45000.00\r\n";
+
+    fn rule(metric: AlertMetric, comparison: AlertComparison, threshold: f64) -> AlertRule {
+        AlertRule {
+            id: "r1".into(),
+            name: "rule".into(),
+            metric,
+            comparison,
+            threshold,
+            cooldown_secs: 300,
+        }
+    }
+
+    fn client(addr: &str, flags: &str, idle: u64) -> ClientInfo {
+        ClientInfo {
+            id: 1,
+            addr: addr.into(),
+            idle,
+            flags: flags.into(),
+            cmd: "get".into(),
+            ..ClientInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_evaluate_above_and_below() {
+        let snapshot = info_parser::build_snapshot(INFO, &MetricThresholds::default());
+        let rules = vec![
+            rule(AlertMetric::ConnectedClients, AlertComparison::Above, 40.0),
+            rule(AlertMetric::BlockedClients, AlertComparison::Above, 5.0),
+            rule(AlertMetric::FragmentationRatio, AlertComparison::Below, 1.5),
+        ];
+        let fired = evaluate(&rules, &snapshot);
+        assert_eq!(fired.len(), 2);
+        assert!((fired[0].1 - 42.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_evaluate_skips_unreported_metric() {
+        let snapshot = info_parser::build_snapshot(INFO, &MetricThresholds::default());
+        let rules = vec![rule(
+            AlertMetric::MemoryUsagePercent,
+            AlertComparison::Above,
+            0.0,
+        )];
+        assert!(evaluate(&rules, &snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_top_commands() {
+        let info = info_parser::parse_info(INFO);
+        let top = top_commands(&info.raw, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].command, "get");
+        assert_eq!(top[0].calls, 900);
+        assert_eq!(top[1].command, "set");
+    }
+
+    #[test]
+    fn test_summarize_clients() {
+        let clients = vec![
+            client("10.0.0.1:5000", "N", 0),
+            client("10.0.0.1:5001", "b", 120),
+            client("10.0.0.2:6000", "N", 61),
+        ];
+        let summary = summarize_clients(&clients);
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.blocked, 1);
+        assert_eq!(summary.long_idle, 2);
+        assert_eq!(summary.top_hosts[0], ("10.0.0.1".to_string(), 2));
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let ok = rule(AlertMetric::OpsPerSec, AlertComparison::Above, 1000.0);
+        assert!(validate_rules(std::slice::from_ref(&ok)).is_ok());
+        assert!(validate_rules(&[ok.clone(), ok.clone()]).is_err());
+        let nan = rule(AlertMetric::OpsPerSec, AlertComparison::Above, f64::NAN);
+        assert!(validate_rules(&[nan]).is_err());
+    }
+}
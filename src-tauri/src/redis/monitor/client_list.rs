@@ -2,20 +2,66 @@
 
 use deadpool_redis::Pool;
 
-use super::model::ClientInfo;
+use super::model::{ClientFilter, ClientInfo, ClientKillFilter, ClientPage, ClientType};
 use crate::utils::errors::AppError;
 
 /// Fetch and parse CLIENT LIST output.
 pub async fn get_client_list(pool: &Pool) -> Result<Vec<ClientInfo>, AppError> {
+    list_clients(pool, None).await
+}
+
+/// CLIENT LIST, optionally restricted to one client type by the server.
+async fn list_clients(
+    pool: &Pool,
+    client_type: Option<ClientType>,
+) -> Result<Vec<ClientInfo>, AppError> {
     let mut conn = pool.get().await?;
-    let raw: String = redis::cmd("CLIENT")
-        .arg("LIST")
-        .query_async(&mut conn)
-        .await?;
+    let mut cmd = redis::cmd("CLIENT");
+    cmd.arg("LIST");
+    if let Some(client_type) = client_type {
+        cmd.arg("TYPE").arg(client_type.as_str());
+    }
+    let raw: String = cmd.query_async(&mut conn).await?;
 
     Ok(parse_client_list(&raw))
 }
 
+/// Fetch the clients matching `filter`, ordered by ID, and return the page
+/// starting at `offset`. Without a `limit` the page runs to the end.
+pub async fn get_client_page(
+    pool: &Pool,
+    filter: &ClientFilter,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<ClientPage, AppError> {
+    let clients = list_clients(pool, filter.client_type).await?;
+    Ok(paginate(clients, filter, offset, limit))
+}
+
+fn paginate(
+    clients: Vec<ClientInfo>,
+    filter: &ClientFilter,
+    offset: usize,
+    limit: Option<usize>,
+) -> ClientPage {
+    let mut matching: Vec<ClientInfo> = clients
+        .into_iter()
+        .filter(|client| filter.matches(client))
+        .collect();
+    matching.sort_by_key(|client| client.id);
+    let total = matching.len();
+    let clients = matching
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    ClientPage {
+        clients,
+        total,
+        offset,
+    }
+}
+
 /// Kill a client by ID.
 pub async fn kill_client(pool: &Pool, client_id: u64) -> Result<(), AppError> {
     let mut conn = pool.get().await?;
@@ -28,6 +74,55 @@ pub async fn kill_client(pool: &Pool, client_id: u64) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Kill every client matching `filter`. Returns the number killed.
+pub async fn kill_clients(pool: &Pool, filter: &ClientKillFilter) -> Result<u64, AppError> {
+    let args = kill_args(filter)?;
+    let mut conn = pool.get().await?;
+    redis::cmd("CLIENT")
+        .arg("KILL")
+        .arg(&args)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("CLIENT KILL failed: {e}")))
+}
+
+/// Arguments after `CLIENT KILL` in the filter form. At least one criterion
+/// is required, so the call can never mean "every client".
+fn kill_args(filter: &ClientKillFilter) -> Result<Vec<String>, AppError> {
+    let mut args = Vec::new();
+    if let Some(id) = filter.id {
+        args.extend(["ID".to_string(), id.to_string()]);
+    }
+    let criteria = [
+        ("ADDR", filter.addr.as_deref()),
+        ("LADDR", filter.laddr.as_deref()),
+        ("TYPE", filter.client_type.map(ClientType::as_str)),
+        ("USER", filter.user.as_deref()),
+    ];
+    for (name, value) in criteria {
+        if let Some(value) = value {
+            if value.is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "{name} filter must not be empty"
+                )));
+            }
+            args.extend([name.to_string(), value.to_string()]);
+        }
+    }
+    if args.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Specify at least one client to kill".into(),
+        ));
+    }
+    let skip_me = if filter.skip_me.unwrap_or(true) {
+        "yes"
+    } else {
+        "no"
+    };
+    args.extend(["SKIPME".to_string(), skip_me.to_string()]);
+    Ok(args)
+}
+
 /// Parse CLIENT LIST output into structured entries.
 ///
 /// CLIENT LIST returns one line per client with space-separated key=value pairs:
@@ -41,46 +136,54 @@ pub fn parse_client_list(raw: &str) -> Vec<ClientInfo> {
 
 /// Parse a single CLIENT LIST line.
 fn parse_client_line(line: &str) -> Option<ClientInfo> {
-    let mut id = 0u64;
-    let mut addr = String::new();
-    let mut age = 0u64;
-    let mut idle = 0u64;
-    let mut flags = String::new();
-    let mut db = 0i64;
-    let mut cmd = String::new();
-    let mut name = String::new();
+    let mut client = ClientInfo {
+        resp: 2,
+        multi: -1,
+        redir: -1,
+        ..ClientInfo::default()
+    };
 
     for part in line.split_whitespace() {
         if let Some((key, value)) = part.split_once('=') {
             match key {
-                "id" => id = value.parse().unwrap_or(0),
-                "addr" => addr = value.to_string(),
-                "age" => age = value.parse().unwrap_or(0),
-                "idle" => idle = value.parse().unwrap_or(0),
-                "flags" => flags = value.to_string(),
-                "db" => db = value.parse().unwrap_or(0),
-                "cmd" => cmd = value.to_string(),
-                "name" => name = value.to_string(),
+                "id" => client.id = value.parse().unwrap_or(0),
+                "addr" => client.addr = value.to_string(),
+                "laddr" => client.laddr = value.to_string(),
+                "fd" => client.fd = value.parse().unwrap_or(0),
+                "age" => client.age = value.parse().unwrap_or(0),
+                "idle" => client.idle = value.parse().unwrap_or(0),
+                "flags" => client.flags = value.to_string(),
+                "db" => client.db = value.parse().unwrap_or(0),
+                "cmd" => client.cmd = value.to_string(),
+                "name" => client.name = value.to_string(),
+                "user" => client.user = value.to_string(),
+                "resp" => client.resp = value.parse().unwrap_or(2),
+                "lib-name" => client.lib_name = value.to_string(),
+                "lib-ver" => client.lib_ver = value.to_string(),
+                "sub" => client.sub = value.parse().unwrap_or(0),
+                "psub" => client.psub = value.parse().unwrap_or(0),
+                "ssub" => client.ssub = value.parse().unwrap_or(0),
+                "multi" => client.multi = value.parse().unwrap_or(-1),
+                "qbuf" => client.qbuf = value.parse().unwrap_or(0),
+                "qbuf-free" => client.qbuf_free = value.parse().unwrap_or(0),
+                "argv-mem" => client.argv_mem = value.parse().unwrap_or(0),
+                "obl" => client.obl = value.parse().unwrap_or(0),
+                "oll" => client.oll = value.parse().unwrap_or(0),
+                "omem" => client.omem = value.parse().unwrap_or(0),
+                "tot-mem" => client.tot_mem = value.parse().unwrap_or(0),
+                "events" => client.events = value.to_string(),
+                "redir" => client.redir = value.parse().unwrap_or(-1),
                 _ => {}
             }
         }
     }
 
     // Skip entries with no ID (shouldn't happen, but be defensive)
-    if id == 0 && addr.is_empty() {
+    if client.id == 0 && client.addr.is_empty() {
         return None;
     }
 
-    Some(ClientInfo {
-        id,
-        addr,
-        age,
-        idle,
-        flags,
-        db,
-        cmd,
-        name,
-    })
+    Some(client)
 }
 
 #[cfg(test)]
@@ -114,6 +217,79 @@ mod tests {
         assert_eq!(clients[1].db, 1);
     }
 
+    #[test]
+    fn test_parse_client_list_extended_fields() {
+        let raw = "id=7 addr=10.0.0.3:4000 laddr=10.0.0.1:6379 fd=9 name=api age=3 idle=1 flags=P db=0 sub=2 psub=1 ssub=0 multi=-1 qbuf=0 qbuf-free=0 argv-mem=0 multi-mem=0 rbs=1024 rbp=0 obl=0 oll=0 omem=0 tot-mem=22298 events=r cmd=subscribe user=default redir=-1 resp=3 lib-name=redis-py lib-ver=5.0.1\n";
+        let client = &parse_client_list(raw)[0];
+        assert_eq!(client.laddr, "10.0.0.1:6379");
+        assert_eq!((client.sub, client.psub), (2, 1));
+        assert_eq!(client.tot_mem, 22298);
+        assert_eq!(client.resp, 3);
+        assert_eq!(client.lib_name, "redis-py");
+        assert_eq!(client.lib_ver, "5.0.1");
+        assert_eq!(client.user, "default");
+        assert_eq!(client.multi, -1);
+
+        // Older servers omit resp; it was always RESP2 there.
+        let client = &parse_client_list("id=1 addr=127.0.0.1:1 cmd=get\n")[0];
+        assert_eq!(client.resp, 2);
+    }
+
+    #[test]
+    fn test_paginate_filters_and_orders() {
+        let raw = "id=3 addr=10.0.0.1:1 name=worker-1 idle=100 flags=N\nid=1 addr=10.0.0.1:2 name=worker-2 idle=5 flags=N\nid=2 addr=10.0.0.2:3 name=api idle=200 flags=P\nid=4 addr=10.0.0.1:4 name=worker-3 idle=300 flags=Nx\n";
+        let clients = parse_client_list(raw);
+
+        let filter = ClientFilter {
+            addr: Some("10.0.0.1:".into()),
+            name: Some("worker-*".into()),
+            ..ClientFilter::default()
+        };
+        let page = paginate(clients.clone(), &filter, 1, Some(1));
+        assert_eq!(page.total, 3);
+        assert_eq!(page.clients.len(), 1);
+        assert_eq!(page.clients[0].id, 3);
+
+        let filter = ClientFilter {
+            min_idle: Some(99),
+            flags: Some("N".into()),
+            ..ClientFilter::default()
+        };
+        let page = paginate(clients, &filter, 0, None);
+        let ids: Vec<u64> = page.clients.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [3, 4]);
+    }
+
+    #[test]
+    fn test_kill_args() {
+        let filter = ClientKillFilter {
+            addr: Some("10.0.0.1:5000".into()),
+            client_type: Some(ClientType::Pubsub),
+            ..ClientKillFilter::default()
+        };
+        assert_eq!(
+            kill_args(&filter).unwrap(),
+            ["ADDR", "10.0.0.1:5000", "TYPE", "pubsub", "SKIPME", "yes"]
+        );
+
+        let filter = ClientKillFilter {
+            laddr: Some("10.0.0.1:6379".into()),
+            skip_me: Some(false),
+            ..ClientKillFilter::default()
+        };
+        assert_eq!(
+            kill_args(&filter).unwrap(),
+            ["LADDR", "10.0.0.1:6379", "SKIPME", "no"]
+        );
+
+        assert!(kill_args(&ClientKillFilter::default()).is_err());
+        let filter = ClientKillFilter {
+            user: Some(String::new()),
+            ..ClientKillFilter::default()
+        };
+        assert!(kill_args(&filter).is_err());
+    }
+
     #[test]
     fn test_parse_client_list_empty() {
         let clients = parse_client_list("");
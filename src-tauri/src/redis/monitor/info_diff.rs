@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT
+
+//! Pinned INFO snapshots and field-by-field diffs between two of them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::model::{InfoDiff, InfoFieldDiff, PinnedInfo};
+
+/// Split raw INFO output into sections of fields. Values made of `k=v`
+/// pairs are split into one field per pair, so each can be diffed.
+pub fn parse_sections(raw: &str) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut sections: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let mut section = String::new();
+    for line in raw.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('#') {
+            section = name.trim().to_lowercase();
+            continue;
+        }
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let fields = sections.entry(section.clone()).or_default();
+        match split_pairs(value) {
+            Some(pairs) => {
+                for (key, value) in pairs {
+                    fields.insert(format!("{field}.{key}"), value.to_string());
+                }
+            }
+            None => {
+                fields.insert(field.to_string(), value.to_string());
+            }
+        }
+    }
+    sections
+}
+
+/// `keys=1,expires=0` as pairs; `None` unless every part is a pair.
+fn split_pairs(value: &str) -> Option<Vec<(&str, &str)>> {
+    value.split(',').map(|part| part.split_once('=')).collect()
+}
+
+/// Compare every field of two snapshots, listing those that changed.
+pub fn diff(before: &PinnedInfo, after: &PinnedInfo) -> InfoDiff {
+    let empty = BTreeMap::new();
+    let section_names: BTreeSet<&String> = before
+        .sections
+        .keys()
+        .chain(after.sections.keys())
+        .collect();
+    let mut unchanged = 0;
+    let mut changes = Vec::new();
+    for section in section_names {
+        let old = before.sections.get(section).unwrap_or(&empty);
+        let new = after.sections.get(section).unwrap_or(&empty);
+        let fields: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        for field in fields {
+            let (b, a) = (old.get(field), new.get(field));
+            if b == a {
+                unchanged += 1;
+                continue;
+            }
+            changes.push(field_diff(section, field, b, a));
+        }
+    }
+    InfoDiff {
+        before_label: before.label.clone(),
+        before_at: before.captured_at,
+        after_label: after.label.clone(),
+        after_at: after.captured_at,
+        unchanged,
+        changes,
+    }
+}
+
+fn field_diff(
+    section: &str,
+    field: &str,
+    before: Option<&String>,
+    after: Option<&String>,
+) -> InfoFieldDiff {
+    let number = |v: Option<&String>| v.and_then(|v| v.parse::<f64>().ok());
+    let delta = number(before).zip(number(after)).map(|(b, a)| (b, a - b));
+    InfoFieldDiff {
+        section: section.to_string(),
+        field: field.to_string(),
+        before: before.cloned(),
+        after: after.cloned(),
+        delta: delta.map(|(_, d)| d),
+        percent_change: delta
+            .filter(|(b, _)| *b != 0.0)
+            .map(|(b, d)| d / b.abs() * 100.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEFORE: &str = "# Server\r\nredis_version:7.2.4\r\nuptime_in_seconds:100\r\n\
+        # Memory\r\nused_memory:1000\r\nmaxmemory_policy:noeviction\r\n\
+        # Keyspace\r\ndb0:keys=10,expires=0,avg_ttl=0\r\n";
+    const AFTER: &str = "# Server\r\nredis_version:7.2.4\r\nuptime_in_seconds:160\r\n\
+        # Memory\r\nused_memory:1500\r\nmaxmemory_policy:allkeys-lru\r\n\
+        # Keyspace\r\ndb0:keys=10,expires=4,avg_ttl=0\r\ndb1:keys=1,expires=0,avg_ttl=0\r\n";
+
+    fn pin(raw: &str, label: &str) -> PinnedInfo {
+        PinnedInfo {
+            id: uuid::Uuid::new_v4(),
+            connection_id: "c1".into(),
+            label: label.into(),
+            captured_at: chrono::Utc::now(),
+            sections: parse_sections(raw),
+        }
+    }
+
+    #[test]
+    fn test_parse_sections_splits_pairs() {
+        let sections = parse_sections(BEFORE);
+        assert_eq!(sections["server"]["redis_version"], "7.2.4");
+        assert_eq!(sections["keyspace"]["db0.keys"], "10");
+        assert_eq!(sections["keyspace"]["db0.avg_ttl"], "0");
+        assert!(!sections["keyspace"].contains_key("db0"));
+    }
+
+    #[test]
+    fn test_diff_reports_numeric_deltas() {
+        let diff = diff(&pin(BEFORE, "before deploy"), &pin(AFTER, "now"));
+        let find = |field: &str| diff.changes.iter().find(|c| c.field == field).unwrap();
+
+        let memory = find("used_memory");
+        assert_eq!(memory.delta, Some(500.0));
+        assert_eq!(memory.percent_change, Some(50.0));
+
+        let policy = find("maxmemory_policy");
+        assert_eq!(policy.after.as_deref(), Some("allkeys-lru"));
+        assert_eq!(policy.delta, None);
+
+        // Zero before: a delta but no percentage.
+        let expires = find("db0.expires");
+        assert_eq!(expires.delta, Some(4.0));
+        assert_eq!(expires.percent_change, None);
+
+        let added = find("db1.keys");
+        assert_eq!(added.before, None);
+        assert_eq!(added.delta, None);
+
+        // redis_version, db0.keys, db0.avg_ttl
+        assert_eq!(diff.unchanged, 3);
+        assert_eq!(diff.before_label, "before deploy");
+    }
+}
@@ -4,8 +4,9 @@ use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::model::{
-    ClientsSection, DatabaseInfo, DerivedMetrics, FragmentationHealth, MemorySection,
-    ReplicationSection, ServerInfo, ServerSection, StatsSection, StatsSnapshot,
+    ClientsSection, DatabaseInfo, DerivedMetrics, FragmentationHealth, HitRateHealth, InfoSection,
+    IntervalRates, MemorySection, MetricThresholds, PersistenceSection, ReplicationSection,
+    ServerInfo, ServerSection, StatsSection, StatsSnapshot,
 };
 
 /// Parse raw `INFO ALL` output into a structured `ServerInfo`.
@@ -44,6 +45,8 @@ pub fn parse_info(raw: &str) -> ServerInfo {
         keyspace_misses: get_u64(&map, "keyspace_misses"),
         expired_keys: get_u64(&map, "expired_keys"),
         evicted_keys: get_u64(&map, "evicted_keys"),
+        total_net_input_bytes: get_u64(&map, "total_net_input_bytes"),
+        total_net_output_bytes: get_u64(&map, "total_net_output_bytes"),
     };
 
     let replication = ReplicationSection {
@@ -52,6 +55,25 @@ pub fn parse_info(raw: &str) -> ServerInfo {
         master_repl_offset: map.get("master_repl_offset").and_then(|v| v.parse().ok()),
     };
 
+    let persistence = PersistenceSection {
+        loading: get_bool(&map, "loading"),
+        loading_loaded_perc: map.get("loading_loaded_perc").and_then(|v| v.parse().ok()),
+        loading_eta_seconds: map.get("loading_eta_seconds").and_then(|v| v.parse().ok()),
+        rdb_changes_since_last_save: get_u64(&map, "rdb_changes_since_last_save"),
+        rdb_bgsave_in_progress: get_bool(&map, "rdb_bgsave_in_progress"),
+        rdb_last_save_time: get_i64(&map, "rdb_last_save_time"),
+        rdb_last_bgsave_status: get_str(&map, "rdb_last_bgsave_status"),
+        rdb_last_bgsave_time_sec: map
+            .get("rdb_last_bgsave_time_sec")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(-1),
+        aof_enabled: get_bool(&map, "aof_enabled"),
+        aof_rewrite_in_progress: get_bool(&map, "aof_rewrite_in_progress"),
+        aof_rewrite_scheduled: get_bool(&map, "aof_rewrite_scheduled"),
+        aof_last_bgrewrite_status: get_str(&map, "aof_last_bgrewrite_status"),
+        aof_last_write_status: get_str(&map, "aof_last_write_status"),
+    };
+
     let keyspace = parse_keyspace(&map);
 
     ServerInfo {
@@ -60,14 +82,18 @@ pub fn parse_info(raw: &str) -> ServerInfo {
         memory,
         stats,
         replication,
+        persistence,
         keyspace,
         raw: map,
+        sections: None,
     }
 }
 
-/// Derive computed metrics from a `ServerInfo`.
+/// Derive computed metrics from a `ServerInfo`, classifying health with
+/// the given thresholds. A server with no keyspace lookups yet reports a
+/// 0% hit rate with good health.
 #[allow(clippy::cast_precision_loss)]
-pub fn derive_metrics(info: &ServerInfo) -> DerivedMetrics {
+pub fn derive_metrics(info: &ServerInfo, thresholds: &MetricThresholds) -> DerivedMetrics {
     let total = info.stats.keyspace_hits + info.stats.keyspace_misses;
     let hit_rate_percent = if total > 0 {
         (info.stats.keyspace_hits as f64 / total as f64) * 100.0
@@ -81,26 +107,89 @@ pub fn derive_metrics(info: &ServerInfo) -> DerivedMetrics {
         None
     };
 
-    let fragmentation_health = if info.memory.mem_fragmentation_ratio > 2.0 {
-        FragmentationHealth::Critical
-    } else if info.memory.mem_fragmentation_ratio > 1.5 {
-        FragmentationHealth::Warning
+    let fragmentation_health =
+        if info.memory.mem_fragmentation_ratio > thresholds.fragmentation_critical {
+            FragmentationHealth::Critical
+        } else if info.memory.mem_fragmentation_ratio > thresholds.fragmentation_warning {
+            FragmentationHealth::Warning
+        } else {
+            FragmentationHealth::Good
+        };
+
+    // With no lookups yet there's no hit rate to judge.
+    let hit_rate_health = if total == 0 {
+        HitRateHealth::Good
+    } else if hit_rate_percent <= thresholds.hit_rate_critical_percent {
+        HitRateHealth::Critical
+    } else if hit_rate_percent <= thresholds.hit_rate_warning_percent {
+        HitRateHealth::Warning
     } else {
-        FragmentationHealth::Good
+        HitRateHealth::Good
     };
 
     DerivedMetrics {
         hit_rate_percent,
         memory_usage_percent,
         fragmentation_health,
+        hit_rate_health,
+        thresholds: thresholds.clone(),
+        rates: None,
+    }
+}
+
+/// Per-second counter rates between two polled snapshots.
+///
+/// `None` if the snapshots lack the Stats section, aren't in time order, or
+/// a counter went down (the server restarted or ran CONFIG RESETSTAT).
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_wrap)]
+pub fn interval_rates(previous: &StatsSnapshot, current: &StatsSnapshot) -> Option<IntervalRates> {
+    if !has_section(&previous.info, InfoSection::Stats)
+        || !has_section(&current.info, InfoSection::Stats)
+    {
+        return None;
+    }
+    let interval_ms = current.timestamp_ms.checked_sub(previous.timestamp_ms)?;
+    if interval_ms == 0 {
+        return None;
     }
+    let secs = interval_ms as f64 / 1000.0;
+    let (before, after) = (&previous.info.stats, &current.info.stats);
+    let rate = |before: u64, after: u64| Some(after.checked_sub(before)? as f64 / secs);
+
+    let total_keys = |info: &ServerInfo| info.keyspace.iter().map(|db| db.keys).sum::<u64>() as i64;
+    let keys_delta = (has_section(&previous.info, InfoSection::Keyspace)
+        && has_section(&current.info, InfoSection::Keyspace))
+    .then(|| total_keys(&current.info) - total_keys(&previous.info));
+
+    Some(IntervalRates {
+        interval_ms,
+        commands_per_sec: rate(
+            before.total_commands_processed,
+            after.total_commands_processed,
+        )?,
+        expired_keys_per_sec: rate(before.expired_keys, after.expired_keys)?,
+        evicted_keys_per_sec: rate(before.evicted_keys, after.evicted_keys)?,
+        net_input_bytes_per_sec: rate(before.total_net_input_bytes, after.total_net_input_bytes)?,
+        net_output_bytes_per_sec: rate(
+            before.total_net_output_bytes,
+            after.total_net_output_bytes,
+        )?,
+        keys_delta,
+    })
+}
+
+/// Whether `info` was parsed from output that included `section`.
+pub fn has_section(info: &ServerInfo, section: InfoSection) -> bool {
+    info.sections
+        .as_ref()
+        .is_none_or(|sections| sections.contains(&section))
 }
 
 /// Build a `StatsSnapshot` from raw INFO output.
 #[allow(clippy::cast_possible_truncation)]
-pub fn build_snapshot(raw: &str) -> StatsSnapshot {
+pub fn build_snapshot(raw: &str, thresholds: &MetricThresholds) -> StatsSnapshot {
     let info = parse_info(raw);
-    let derived = derive_metrics(&info);
+    let derived = derive_metrics(&info, thresholds);
     let timestamp_ms = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_or(0, |d| d.as_millis() as u64);
@@ -109,6 +198,7 @@ pub fn build_snapshot(raw: &str) -> StatsSnapshot {
         timestamp_ms,
         info,
         derived,
+        poll_interval_ms: None,
     }
 }
 
@@ -176,6 +266,14 @@ fn get_u64(map: &HashMap<String, String>, key: &str) -> u64 {
     map.get(key).and_then(|v| v.parse().ok()).unwrap_or(0)
 }
 
+fn get_i64(map: &HashMap<String, String>, key: &str) -> i64 {
+    map.get(key).and_then(|v| v.parse().ok()).unwrap_or(0)
+}
+
+fn get_bool(map: &HashMap<String, String>, key: &str) -> bool {
+    map.get(key).is_some_and(|v| v == "1")
+}
+
 fn get_f64(map: &HashMap<String, String>, key: &str) -> f64 {
     map.get(key).and_then(|v| v.parse().ok()).unwrap_or(0.0)
 }
@@ -218,6 +316,19 @@ evicted_keys:0\r\n\
 role:master\r\n\
 master_repl_offset:123456\r\n\
 \r\n\
+# Persistence\r\n\
+loading:0\r\n\
+rdb_changes_since_last_save:17\r\n\
+rdb_bgsave_in_progress:0\r\n\
+rdb_last_save_time:1700000000\r\n\
+rdb_last_bgsave_status:ok\r\n\
+rdb_last_bgsave_time_sec:2\r\n\
+aof_enabled:1\r\n\
+aof_rewrite_in_progress:0\r\n\
+aof_rewrite_scheduled:0\r\n\
+aof_last_bgrewrite_status:ok\r\n\
+aof_last_write_status:err\r\n\
+\r\n\
 # Keyspace\r\n\
 db0:keys=1000,expires=100,avg_ttl=5000\r\n\
 db1:keys=50,expires=5,avg_ttl=3000\r\n\
@@ -250,6 +361,19 @@ db1:keys=50,expires=5,avg_ttl=3000\r\n\
         assert!((info.memory.mem_fragmentation_ratio - 1.21).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_parse_info_persistence_section() {
+        let info = parse_info(SAMPLE_INFO);
+        let p = &info.persistence;
+        assert!(!p.loading);
+        assert_eq!(p.loading_loaded_perc, None);
+        assert_eq!(p.rdb_changes_since_last_save, 17);
+        assert_eq!(p.rdb_last_save_time, 1_700_000_000);
+        assert_eq!(p.rdb_last_bgsave_time_sec, 2);
+        assert!(p.aof_enabled);
+        assert!(!p.is_healthy(), "failed AOF write is unhealthy");
+    }
+
     #[test]
     fn test_parse_info_stats_section() {
         let info = parse_info(SAMPLE_INFO);
@@ -281,14 +405,14 @@ db1:keys=50,expires=5,avg_ttl=3000\r\n\
     #[test]
     fn test_derive_metrics_hit_rate() {
         let info = parse_info(SAMPLE_INFO);
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         assert!((derived.hit_rate_percent - 90.0).abs() < f64::EPSILON);
     }
 
     #[test]
     fn test_derive_metrics_memory_usage() {
         let info = parse_info(SAMPLE_INFO);
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         let usage = derived.memory_usage_percent.unwrap();
         assert!((usage - 50.0).abs() < 0.1);
     }
@@ -296,7 +420,7 @@ db1:keys=50,expires=5,avg_ttl=3000\r\n\
     #[test]
     fn test_derive_metrics_fragmentation_good() {
         let info = parse_info(SAMPLE_INFO);
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         assert!(matches!(
             derived.fragmentation_health,
             FragmentationHealth::Good
@@ -307,7 +431,7 @@ db1:keys=50,expires=5,avg_ttl=3000\r\n\
     fn test_derive_metrics_fragmentation_warning() {
         let mut info = parse_info(SAMPLE_INFO);
         info.memory.mem_fragmentation_ratio = 1.7;
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         assert!(matches!(
             derived.fragmentation_health,
             FragmentationHealth::Warning
@@ -318,27 +442,47 @@ db1:keys=50,expires=5,avg_ttl=3000\r\n\
     fn test_derive_metrics_fragmentation_critical() {
         let mut info = parse_info(SAMPLE_INFO);
         info.memory.mem_fragmentation_ratio = 2.5;
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         assert!(matches!(
             derived.fragmentation_health,
             FragmentationHealth::Critical
         ));
     }
 
+    #[test]
+    fn test_derive_metrics_custom_thresholds() {
+        let mut info = parse_info(SAMPLE_INFO);
+        info.memory.mem_fragmentation_ratio = 1.7;
+        let thresholds = MetricThresholds {
+            fragmentation_warning: 1.8,
+            fragmentation_critical: 3.0,
+            hit_rate_warning_percent: 95.0,
+            hit_rate_critical_percent: 80.0,
+        };
+        let derived = derive_metrics(&info, &thresholds);
+        assert!(matches!(
+            derived.fragmentation_health,
+            FragmentationHealth::Good
+        ));
+        assert!(matches!(derived.hit_rate_health, HitRateHealth::Warning));
+        assert_eq!(derived.thresholds, thresholds);
+    }
+
     #[test]
     fn test_derive_metrics_zero_hits_misses() {
         let mut info = parse_info(SAMPLE_INFO);
         info.stats.keyspace_hits = 0;
         info.stats.keyspace_misses = 0;
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         assert!((derived.hit_rate_percent - 0.0).abs() < f64::EPSILON);
+        assert!(matches!(derived.hit_rate_health, HitRateHealth::Good));
     }
 
     #[test]
     fn test_derive_metrics_no_maxmemory() {
         let mut info = parse_info(SAMPLE_INFO);
         info.memory.maxmemory = 0;
-        let derived = derive_metrics(&info);
+        let derived = derive_metrics(&info, &MetricThresholds::default());
         assert!(derived.memory_usage_percent.is_none());
     }
 
@@ -349,9 +493,48 @@ db1:keys=50,expires=5,avg_ttl=3000\r\n\
         assert_eq!(info.keyspace.len(), 0);
     }
 
+    #[test]
+    fn test_interval_rates() {
+        let thresholds = MetricThresholds::default();
+        let mut previous = build_snapshot(SAMPLE_INFO, &thresholds);
+        previous.timestamp_ms = 10_000;
+        let mut current = build_snapshot(
+            &SAMPLE_INFO
+                .replace(
+                    "total_commands_processed:9999999",
+                    "total_commands_processed:10009999",
+                )
+                .replace("expired_keys:50", "expired_keys:70")
+                .replace("db1:keys=50", "db1:keys=40"),
+            &thresholds,
+        );
+        current.timestamp_ms = 12_000;
+
+        let rates = interval_rates(&previous, &current).unwrap();
+        assert_eq!(rates.interval_ms, 2_000);
+        assert!((rates.commands_per_sec - 5_000.0).abs() < f64::EPSILON);
+        assert!((rates.expired_keys_per_sec - 10.0).abs() < f64::EPSILON);
+        assert!(rates.evicted_keys_per_sec.abs() < f64::EPSILON);
+        assert_eq!(rates.keys_delta, Some(-10));
+
+        // Restart: counters went down.
+        assert!(interval_rates(&current, &previous).is_none());
+        current.timestamp_ms = 20_000;
+        previous.info.stats.expired_keys = 100;
+        assert!(interval_rates(&previous, &current).is_none());
+
+        // Without the keyspace section there's no key delta.
+        previous.info.stats.expired_keys = 50;
+        previous.info.sections = Some(vec![InfoSection::Stats]);
+        assert_eq!(
+            interval_rates(&previous, &current).unwrap().keys_delta,
+            None
+        );
+    }
+
     #[test]
     fn test_build_snapshot() {
-        let snapshot = build_snapshot(SAMPLE_INFO);
+        let snapshot = build_snapshot(SAMPLE_INFO, &MetricThresholds::default());
         assert!(snapshot.timestamp_ms > 0);
         assert_eq!(snapshot.info.server.redis_version, "7.2.4");
         assert!((snapshot.derived.hit_rate_percent - 90.0).abs() < f64::EPSILON);
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use russh::ChannelMsg;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::redis::connection::model::SshConfig;
+use crate::redis::connection::ssh_tunnel;
+use crate::utils::errors::AppError;
+
+/// Lines longer than this are cut, so a runaway line can't grow the buffer.
+const MAX_LINE_BYTES: usize = 16 * 1024;
+
+/// Most history lines `tail` may print before following.
+pub const MAX_BACKLOG_LINES: u32 = 10_000;
+
+/// Payload of `server:log`: lines read since the previous event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerLogBatch {
+    pub connection_id: String,
+    pub lines: Vec<String>,
+    /// Lines came from `tail`'s stderr (missing file, permissions).
+    pub stderr: bool,
+    /// Set on the last event, once `tail` exited or the session closed.
+    pub ended: bool,
+}
+
+/// The `tail` invocation for `path`, printing `backlog` lines first.
+pub fn tail_command(path: &str, backlog: u32) -> Result<String, AppError> {
+    if !path.starts_with('/') {
+        return Err(AppError::InvalidInput("Log path must be absolute".into()));
+    }
+    if path.contains(['\0', '\n', '\r']) {
+        return Err(AppError::InvalidInput(
+            "Log path contains invalid characters".into(),
+        ));
+    }
+    if backlog > MAX_BACKLOG_LINES {
+        return Err(AppError::InvalidInput(format!(
+            "At most {MAX_BACKLOG_LINES} history lines can be requested"
+        )));
+    }
+    let quoted = path.replace('\'', r"'\''");
+    Ok(format!("tail -n {backlog} -F -- '{quoted}'"))
+}
+
+/// Splits a byte stream into lines, holding back an unfinished last line.
+#[derive(Debug, Default)]
+struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    fn push(&mut self, data: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for &byte in data {
+            if byte == b'\n' {
+                lines.push(self.take());
+            } else if self.pending.len() < MAX_LINE_BYTES {
+                self.pending.push(byte);
+            }
+        }
+        lines
+    }
+
+    fn take(&mut self) -> String {
+        let line = String::from_utf8_lossy(&self.pending)
+            .trim_end_matches('\r')
+            .to_string();
+        self.pending.clear();
+        line
+    }
+
+    /// The unfinished line, if any, once the stream has ended.
+    fn finish(&mut self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| self.take())
+    }
+}
+
+/// Tails redis-server log files over SSH, one tail per connection.
+///
+/// Each tail runs `tail -F` on its own SSH session, separate from the
+/// connection's tunnel, and emits new lines as `server:log` events.
+pub struct LogTailer {
+    handles: Arc<RwLock<HashMap<Uuid, AbortHandle>>>,
+}
+
+impl Default for LogTailer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogTailer {
+    pub fn new() -> Self {
+        Self {
+            handles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Start tailing, replacing any tail already running for the connection.
+    pub async fn start(
+        &self,
+        connection_id: Uuid,
+        ssh: &SshConfig,
+        command: &str,
+        app: AppHandle,
+    ) -> Result<(), AppError> {
+        let mut remote = ssh_tunnel::exec(ssh, command).await?;
+
+        self.stop(&connection_id).await;
+        let id = connection_id.to_string();
+        let task = tokio::spawn(async move {
+            let mut stdout = LineBuffer::default();
+            let mut stderr = LineBuffer::default();
+            let emit = |lines: Vec<String>, is_stderr: bool, ended: bool| {
+                if !lines.is_empty() || ended {
+                    let batch = ServerLogBatch {
+                        connection_id: id.clone(),
+                        lines,
+                        stderr: is_stderr,
+                        ended,
+                    };
+                    let _ = app.emit("server:log", &batch);
+                }
+            };
+            while let Some(msg) = remote.next().await {
+                match msg {
+                    ChannelMsg::Data { data } => emit(stdout.push(&data), false, false),
+                    ChannelMsg::ExtendedData { data, .. } => emit(stderr.push(&data), true, false),
+                    ChannelMsg::ExitStatus { exit_status } => {
+                        tracing::info!(connection_id = %id, exit_status = exit_status, "Log tail exited");
+                    }
+                    _ => {}
+                }
+            }
+            emit(stderr.finish().into_iter().collect(), true, false);
+            emit(stdout.finish().into_iter().collect(), false, true);
+            tracing::info!(connection_id = %id, "Log tail session closed");
+        });
+
+        self.handles
+            .write()
+            .await
+            .insert(connection_id, task.abort_handle());
+        tracing::info!(connection_id = %connection_id, "Log tail started");
+        Ok(())
+    }
+
+    /// Stop tailing for a connection. Returns whether a tail was running.
+    pub async fn stop(&self, connection_id: &Uuid) -> bool {
+        match self.handles.write().await.remove(connection_id) {
+            Some(handle) => {
+                handle.abort();
+                tracing::info!(connection_id = %connection_id, "Log tail stopped");
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_command_quotes_path() {
+        assert_eq!(
+            tail_command("/var/log/redis/redis.log", 100).unwrap(),
+            "tail -n 100 -F -- '/var/log/redis/redis.log'"
+        );
+        assert_eq!(
+            tail_command("/logs/it's.log", 0).unwrap(),
+            r"tail -n 0 -F -- '/logs/it'\''s.log'"
+        );
+        assert!(tail_command("redis.log", 10).is_err());
+        assert!(tail_command("/a\nrm -rf /", 10).is_err());
+        assert!(tail_command("/a.log", MAX_BACKLOG_LINES + 1).is_err());
+    }
+
+    #[test]
+    fn test_line_buffer_joins_partial_lines() {
+        let mut buffer = LineBuffer::default();
+        assert_eq!(
+            buffer.push(b"1:M 01 Jan Ready\r\n2:M par"),
+            ["1:M 01 Jan Ready"]
+        );
+        assert_eq!(buffer.push(b"tial\n\n"), ["2:M partial", ""]);
+        assert!(buffer.push(b"tail").is_empty());
+        assert_eq!(buffer.finish().as_deref(), Some("tail"));
+        assert_eq!(buffer.finish(), None);
+    }
+}
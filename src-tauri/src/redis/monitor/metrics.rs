@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: MIT
+
+//! In-memory history of polled dashboard metrics, exportable as CSV or JSON
+//! and as Prometheus text exposition.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use super::info_parser;
+use super::model::{InfoSection, MetricPoint, MetricsExportFormat, StatsSnapshot};
+use crate::utils::errors::AppError;
+
+/// Points kept per connection: an hour at the default 2s interval.
+const MAX_POINTS: usize = 1_800;
+
+/// CSV columns, in [`MetricPoint`] field order.
+const CSV_HEADER: &str = "timestampMs,usedMemory,memoryUsagePercent,memFragmentationRatio,\
+connectedClients,blockedClients,opsPerSec,hitRatePercent,commandsPerSec,expiredKeysPerSec,\
+evictedKeysPerSec,netInputBytesPerSec,netOutputBytesPerSec,totalKeys";
+
+impl MetricPoint {
+    pub fn from_snapshot(snapshot: &StatsSnapshot) -> Self {
+        let info = &snapshot.info;
+        let rates = snapshot.derived.rates.as_ref();
+        Self {
+            timestamp_ms: snapshot.timestamp_ms,
+            used_memory: info.memory.used_memory,
+            memory_usage_percent: snapshot.derived.memory_usage_percent,
+            mem_fragmentation_ratio: info.memory.mem_fragmentation_ratio,
+            connected_clients: info.clients.connected_clients,
+            blocked_clients: info.clients.blocked_clients,
+            ops_per_sec: info.stats.instantaneous_ops_per_sec,
+            hit_rate_percent: snapshot.derived.hit_rate_percent,
+            commands_per_sec: rates.map(|r| r.commands_per_sec),
+            expired_keys_per_sec: rates.map(|r| r.expired_keys_per_sec),
+            evicted_keys_per_sec: rates.map(|r| r.evicted_keys_per_sec),
+            net_input_bytes_per_sec: rates.map(|r| r.net_input_bytes_per_sec),
+            net_output_bytes_per_sec: rates.map(|r| r.net_output_bytes_per_sec),
+            total_keys: info_parser::has_section(info, InfoSection::Keyspace)
+                .then(|| info.keyspace.iter().map(|db| db.keys).sum()),
+        }
+    }
+}
+
+/// The last [`MAX_POINTS`] metric points of each polled connection.
+///
+/// Cloning shares the history, so the pollers that fill it and the
+/// Prometheus endpoint that reads it see the same points.
+#[derive(Clone, Default)]
+pub struct MetricsHistory {
+    points: Arc<RwLock<HashMap<String, VecDeque<MetricPoint>>>>,
+}
+
+impl MetricsHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a point, dropping the oldest once the history is full.
+    pub async fn record(&self, connection_id: &str, point: MetricPoint) {
+        let mut points = self.points.write().await;
+        let history = points.entry(connection_id.to_string()).or_default();
+        if history.len() == MAX_POINTS {
+            history.pop_front();
+        }
+        history.push_back(point);
+    }
+
+    /// A connection's points, oldest first.
+    pub async fn points(&self, connection_id: &str) -> Vec<MetricPoint> {
+        self.points
+            .read()
+            .await
+            .get(connection_id)
+            .map(|h| h.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The newest point of every connection with a history.
+    pub async fn latest(&self) -> Vec<(String, MetricPoint)> {
+        let points = self.points.read().await;
+        let mut latest: Vec<(String, MetricPoint)> = points
+            .iter()
+            .filter_map(|(id, h)| Some((id.clone(), h.back()?.clone())))
+            .collect();
+        latest.sort_by(|a, b| a.0.cmp(&b.0));
+        latest
+    }
+
+    pub async fn clear(&self, connection_id: &str) {
+        self.points.write().await.remove(connection_id);
+    }
+}
+
+/// Serialize a history as CSV (one row per point) or a JSON array.
+pub fn export(points: &[MetricPoint], format: MetricsExportFormat) -> Result<String, AppError> {
+    match format {
+        MetricsExportFormat::Json => serde_json::to_string_pretty(points)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize metrics: {e}"))),
+        MetricsExportFormat::Csv => {
+            let mut csv = format!("{CSV_HEADER}\r\n");
+            for p in points {
+                let _ = write!(
+                    csv,
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\r\n",
+                    p.timestamp_ms,
+                    p.used_memory,
+                    cell(p.memory_usage_percent),
+                    p.mem_fragmentation_ratio,
+                    p.connected_clients,
+                    p.blocked_clients,
+                    p.ops_per_sec,
+                    p.hit_rate_percent,
+                    cell(p.commands_per_sec),
+                    cell(p.expired_keys_per_sec),
+                    cell(p.evicted_keys_per_sec),
+                    cell(p.net_input_bytes_per_sec),
+                    cell(p.net_output_bytes_per_sec),
+                    cell(p.total_keys),
+                );
+            }
+            Ok(csv)
+        }
+    }
+}
+
+/// An optional CSV cell: empty when absent.
+fn cell<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Render the newest point of each connection as Prometheus text exposition
+/// (format 0.0.4): one gauge per metric, labelled by connection, with the
+/// poll time as the sample timestamp so stale connections are visible.
+pub fn render_prometheus(latest: &[(String, MetricPoint)]) -> String {
+    type Gauge = (&'static str, &'static str, fn(&MetricPoint) -> Option<f64>);
+    #[allow(clippy::cast_precision_loss)]
+    const GAUGES: &[Gauge] = &[
+        (
+            "redislens_used_memory_bytes",
+            "Memory used by Redis.",
+            |p| Some(p.used_memory as f64),
+        ),
+        (
+            "redislens_memory_usage_percent",
+            "Used memory as a percentage of maxmemory.",
+            |p| p.memory_usage_percent,
+        ),
+        (
+            "redislens_mem_fragmentation_ratio",
+            "RSS over used memory.",
+            |p| Some(p.mem_fragmentation_ratio),
+        ),
+        ("redislens_connected_clients", "Connected clients.", |p| {
+            Some(p.connected_clients as f64)
+        }),
+        (
+            "redislens_blocked_clients",
+            "Clients blocked on a command.",
+            |p| Some(p.blocked_clients as f64),
+        ),
+        (
+            "redislens_ops_per_sec",
+            "Instantaneous operations per second.",
+            |p| Some(p.ops_per_sec as f64),
+        ),
+        ("redislens_hit_rate_percent", "Keyspace hit rate.", |p| {
+            Some(p.hit_rate_percent)
+        }),
+        (
+            "redislens_commands_per_sec",
+            "Commands processed per second since the previous poll.",
+            |p| p.commands_per_sec,
+        ),
+        (
+            "redislens_expired_keys_per_sec",
+            "Keys expired per second since the previous poll.",
+            |p| p.expired_keys_per_sec,
+        ),
+        (
+            "redislens_evicted_keys_per_sec",
+            "Keys evicted per second since the previous poll.",
+            |p| p.evicted_keys_per_sec,
+        ),
+        (
+            "redislens_net_input_bytes_per_sec",
+            "Network bytes read per second since the previous poll.",
+            |p| p.net_input_bytes_per_sec,
+        ),
+        (
+            "redislens_net_output_bytes_per_sec",
+            "Network bytes written per second since the previous poll.",
+            |p| p.net_output_bytes_per_sec,
+        ),
+        ("redislens_keys", "Keys over all databases.", |p| {
+            p.total_keys.map(|k| k as f64)
+        }),
+    ];
+
+    let mut out = String::new();
+    for (name, help, value) in GAUGES {
+        let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge");
+        for (connection_id, point) in latest {
+            if let Some(v) = value(point) {
+                let _ = writeln!(
+                    out,
+                    "{name}{{connection=\"{}\"}} {v} {}",
+                    escape_label(connection_id),
+                    point.timestamp_ms
+                );
+            }
+        }
+    }
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(timestamp_ms: u64) -> MetricPoint {
+        MetricPoint {
+            timestamp_ms,
+            used_memory: 1024,
+            memory_usage_percent: None,
+            mem_fragmentation_ratio: 1.25,
+            connected_clients: 3,
+            blocked_clients: 0,
+            ops_per_sec: 10,
+            hit_rate_percent: 90.0,
+            commands_per_sec: Some(12.5),
+            expired_keys_per_sec: None,
+            evicted_keys_per_sec: None,
+            net_input_bytes_per_sec: None,
+            net_output_bytes_per_sec: None,
+            total_keys: Some(7),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_newest_points() {
+        let history = MetricsHistory::new();
+        for t in 0..=MAX_POINTS as u64 {
+            history.record("c1", point(t)).await;
+        }
+        let points = history.points("c1").await;
+        assert_eq!(points.len(), MAX_POINTS);
+        assert_eq!(points[0].timestamp_ms, 1);
+        assert_eq!(history.latest().await[0].1.timestamp_ms, MAX_POINTS as u64);
+        history.clear("c1").await;
+        assert!(history.points("c1").await.is_empty());
+    }
+
+    #[test]
+    fn test_csv_export_leaves_missing_cells_empty() {
+        let csv = export(&[point(5)], MetricsExportFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert_eq!(lines.next(), Some("5,1024,,1.25,3,0,10,90,12.5,,,,,7"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let text = render_prometheus(&[("c\"1".to_string(), point(99))]);
+        assert!(text.contains("# TYPE redislens_used_memory_bytes gauge\n"));
+        assert!(text.contains("redislens_used_memory_bytes{connection=\"c\\\"1\"} 1024 99\n"));
+        assert!(text.contains("redislens_commands_per_sec{connection=\"c\\\"1\"} 12.5 99\n"));
+        // Absent values are left out, but the metric is still declared.
+        assert!(text.contains("# TYPE redislens_memory_usage_percent gauge\n"));
+        assert!(!text.contains("redislens_memory_usage_percent{"));
+    }
+}
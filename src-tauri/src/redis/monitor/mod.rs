@@ -1,7 +1,14 @@
 // SPDX-License-Identifier: MIT
 
+pub mod alerts;
 pub mod client_list;
+pub mod info_diff;
 pub mod info_parser;
+pub mod log_tail;
+pub mod metrics;
 pub mod model;
+pub mod persistence;
 pub mod poller;
+pub mod prometheus;
 pub mod slow_log;
+pub mod upgrade_advisor;
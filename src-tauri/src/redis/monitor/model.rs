@@ -1,9 +1,11 @@
 // SPDX-License-Identifier: MIT
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use crate::utils::glob;
+
 /// Parsed INFO output organized into typed sections.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -13,9 +15,64 @@ pub struct ServerInfo {
     pub memory: MemorySection,
     pub stats: StatsSection,
     pub replication: ReplicationSection,
+    pub persistence: PersistenceSection,
     pub keyspace: Vec<DatabaseInfo>,
     /// All raw key-value pairs from INFO for the "raw info" view.
     pub raw: HashMap<String, String>,
+    /// Sections this was parsed from; `None` for full INFO output. Fields of
+    /// other sections hold defaults.
+    #[serde(default)]
+    pub sections: Option<Vec<InfoSection>>,
+}
+
+/// A section of INFO output, as polled by the monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InfoSection {
+    Server,
+    Clients,
+    Memory,
+    Persistence,
+    Stats,
+    Replication,
+    Cpu,
+    Modules,
+    Errorstats,
+    Cluster,
+    Keyspace,
+    Commandstats,
+    Latencystats,
+}
+
+impl InfoSection {
+    /// Polled when a subscriber doesn't choose: enough for the dashboard
+    /// and alert rules, without the per-command and per-database sections.
+    pub const LIGHTWEIGHT: &'static [InfoSection] = &[
+        InfoSection::Server,
+        InfoSection::Clients,
+        InfoSection::Memory,
+        InfoSection::Persistence,
+        InfoSection::Stats,
+        InfoSection::Replication,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InfoSection::Server => "server",
+            InfoSection::Clients => "clients",
+            InfoSection::Memory => "memory",
+            InfoSection::Persistence => "persistence",
+            InfoSection::Stats => "stats",
+            InfoSection::Replication => "replication",
+            InfoSection::Cpu => "cpu",
+            InfoSection::Modules => "modules",
+            InfoSection::Errorstats => "errorstats",
+            InfoSection::Cluster => "cluster",
+            InfoSection::Keyspace => "keyspace",
+            InfoSection::Commandstats => "commandstats",
+            InfoSection::Latencystats => "latencystats",
+        }
+    }
 }
 
 /// Fields from the # Server section.
@@ -61,6 +118,10 @@ pub struct StatsSection {
     pub keyspace_misses: u64,
     pub expired_keys: u64,
     pub evicted_keys: u64,
+    #[serde(default)]
+    pub total_net_input_bytes: u64,
+    #[serde(default)]
+    pub total_net_output_bytes: u64,
 }
 
 /// Fields from the # Replication section.
@@ -72,6 +133,58 @@ pub struct ReplicationSection {
     pub master_repl_offset: Option<u64>,
 }
 
+/// Fields from the # Persistence section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PersistenceSection {
+    /// The server is loading a dataset from disk.
+    pub loading: bool,
+    /// Percentage loaded, while `loading`.
+    pub loading_loaded_perc: Option<f64>,
+    /// Estimated seconds left, while `loading`.
+    pub loading_eta_seconds: Option<u64>,
+    pub rdb_changes_since_last_save: u64,
+    pub rdb_bgsave_in_progress: bool,
+    /// Unix time of the last successful RDB save.
+    pub rdb_last_save_time: i64,
+    /// `ok` or `err`.
+    pub rdb_last_bgsave_status: String,
+    /// Duration of the last BGSAVE in seconds; -1 if none ran yet.
+    pub rdb_last_bgsave_time_sec: i64,
+    pub aof_enabled: bool,
+    pub aof_rewrite_in_progress: bool,
+    pub aof_rewrite_scheduled: bool,
+    /// `ok` or `err`.
+    pub aof_last_bgrewrite_status: String,
+    /// `ok` or `err`.
+    pub aof_last_write_status: String,
+}
+
+impl PersistenceSection {
+    /// Whether the last RDB save and AOF rewrite and write all succeeded.
+    /// Statuses the server doesn't report count as healthy.
+    pub fn is_healthy(&self) -> bool {
+        [
+            &self.rdb_last_bgsave_status,
+            &self.aof_last_bgrewrite_status,
+            &self.aof_last_write_status,
+        ]
+        .iter()
+        .all(|status| status.is_empty() || *status == "ok")
+    }
+}
+
+/// Persistence health, as shown on the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistenceStatus {
+    pub persistence: PersistenceSection,
+    /// LASTSAVE: Unix time of the last successful save.
+    pub last_save: i64,
+    pub healthy: bool,
+}
+
 /// Per-database keyspace info (e.g., `db0:keys=123,expires=10,avg_ttl=5000`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -89,6 +202,28 @@ pub struct DerivedMetrics {
     pub hit_rate_percent: f64,
     pub memory_usage_percent: Option<f64>,
     pub fragmentation_health: FragmentationHealth,
+    pub hit_rate_health: HitRateHealth,
+    /// The thresholds the health fields were computed with.
+    pub thresholds: MetricThresholds,
+    /// Counter rates since the previous polled snapshot; `None` on the first
+    /// poll, on one-shot snapshots and after a counter reset.
+    #[serde(default)]
+    pub rates: Option<IntervalRates>,
+}
+
+/// Per-second rates of INFO counters between two consecutive polls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntervalRates {
+    /// Time between the two snapshots.
+    pub interval_ms: u64,
+    pub commands_per_sec: f64,
+    pub expired_keys_per_sec: f64,
+    pub evicted_keys_per_sec: f64,
+    pub net_input_bytes_per_sec: f64,
+    pub net_output_bytes_per_sec: f64,
+    /// Change in the total key count, when both polls fetched the keyspace.
+    pub keys_delta: Option<i64>,
 }
 
 /// Fragmentation health indicator.
@@ -100,6 +235,42 @@ pub enum FragmentationHealth {
     Critical,
 }
 
+/// Hit-rate health indicator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HitRateHealth {
+    Good,
+    Warning,
+    Critical,
+}
+
+/// Cutoffs used to classify derived metrics.
+///
+/// Set globally in app settings and optionally overridden per profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricThresholds {
+    /// Fragmentation ratio above which health is `Warning`.
+    pub fragmentation_warning: f64,
+    /// Fragmentation ratio above which health is `Critical`.
+    pub fragmentation_critical: f64,
+    /// Hit rate (percent) at or below which health is `Warning`.
+    pub hit_rate_warning_percent: f64,
+    /// Hit rate (percent) at or below which health is `Critical`.
+    pub hit_rate_critical_percent: f64,
+}
+
+impl Default for MetricThresholds {
+    fn default() -> Self {
+        Self {
+            fragmentation_warning: 1.5,
+            fragmentation_critical: 2.0,
+            hit_rate_warning_percent: 90.0,
+            hit_rate_critical_percent: 50.0,
+        }
+    }
+}
+
 /// Timestamped snapshot emitted via Tauri event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +278,54 @@ pub struct StatsSnapshot {
     pub timestamp_ms: u64,
     pub info: ServerInfo,
     pub derived: DerivedMetrics,
+    /// Interval until the poller's next snapshot, after any load back-off.
+    /// Absent on one-shot snapshots.
+    #[serde(default)]
+    pub poll_interval_ms: Option<u64>,
+}
+
+/// The shared poller of a connection, as seen by its subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorSubscription {
+    pub connection_id: String,
+    /// Views currently subscribed.
+    pub subscribers: usize,
+    /// Interval the shared task polls at: the fastest one requested.
+    pub interval_ms: u64,
+    /// INFO sections polled: every one any subscriber asked for.
+    pub sections: Vec<InfoSection>,
+}
+
+/// The dashboard metrics of one polled snapshot, kept in the metrics
+/// history for export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricPoint {
+    pub timestamp_ms: u64,
+    pub used_memory: u64,
+    pub memory_usage_percent: Option<f64>,
+    pub mem_fragmentation_ratio: f64,
+    pub connected_clients: u64,
+    pub blocked_clients: u64,
+    pub ops_per_sec: u64,
+    pub hit_rate_percent: f64,
+    /// From the snapshot's interval rates, when it had them.
+    pub commands_per_sec: Option<f64>,
+    pub expired_keys_per_sec: Option<f64>,
+    pub evicted_keys_per_sec: Option<f64>,
+    pub net_input_bytes_per_sec: Option<f64>,
+    pub net_output_bytes_per_sec: Option<f64>,
+    /// Keys over all databases, when the keyspace section was polled.
+    pub total_keys: Option<u64>,
+}
+
+/// File format of a metrics history export.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsExportFormat {
+    Csv,
+    Json,
 }
 
 /// A single slow log entry.
@@ -121,18 +340,154 @@ pub struct SlowLogEntry {
     pub client_name: String,
 }
 
-/// A connected client's info.
+/// Slow log entries sharing a command shape, e.g. `HGETALL user:*`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SlowLogGroup {
+    pub shape: String,
+    /// Uppercase command name.
+    pub command: String,
+    /// First key with ID-like segments replaced by `*`; `None` for
+    /// commands without keys.
+    pub key_pattern: Option<String>,
+    pub count: u64,
+    pub total_us: u64,
+    pub avg_us: u64,
+    pub max_us: u64,
+    /// Unix time of the most recent entry.
+    pub last_seen: u64,
+    /// The slowest entry's full command line.
+    pub slowest: String,
+}
+
+/// Grouped slow log, optionally limited to entries new since the last fetch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowLogDigest {
+    /// Ordered by total time, worst first.
+    pub groups: Vec<SlowLogGroup>,
+    /// Entries that went into the groups.
+    pub entries: u64,
+    /// SLOWLOG LEN at the time of the fetch.
+    pub length: u64,
+}
+
+/// A connected client's info, one CLIENT LIST line.
+///
+/// Fields a server version doesn't report keep their defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ClientInfo {
     pub id: u64,
     pub addr: String,
+    /// Local (server-side) address the client connected to.
+    pub laddr: String,
+    pub fd: i64,
     pub age: u64,
     pub idle: u64,
     pub flags: String,
     pub db: i64,
     pub cmd: String,
     pub name: String,
+    pub user: String,
+    /// Protocol version (2 or 3).
+    pub resp: u8,
+    pub lib_name: String,
+    pub lib_ver: String,
+    /// Channel, pattern and shard channel subscriptions.
+    pub sub: u64,
+    pub psub: u64,
+    pub ssub: u64,
+    /// Commands queued in MULTI; -1 outside a transaction.
+    pub multi: i64,
+    pub qbuf: u64,
+    pub qbuf_free: u64,
+    pub argv_mem: u64,
+    pub obl: u64,
+    pub oll: u64,
+    pub omem: u64,
+    /// Total memory the client uses, buffers included.
+    pub tot_mem: u64,
+    pub events: String,
+    /// Client ID receiving tracking invalidations; -1 if none.
+    pub redir: i64,
+}
+
+/// CLIENT LIST / CLIENT KILL client type.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ClientType {
+    Normal,
+    Master,
+    Replica,
+    Pubsub,
+}
+
+impl ClientType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Master => "master",
+            Self::Replica => "replica",
+            Self::Pubsub => "pubsub",
+        }
+    }
+}
+
+/// Criteria for `monitor_client_list`; all set criteria must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientFilter {
+    /// Substring of the client's address.
+    pub addr: Option<String>,
+    /// Glob pattern for the client name.
+    pub name: Option<String>,
+    /// Only clients idle for more than this many seconds.
+    pub min_idle: Option<u64>,
+    /// Flag letters that must all be set, e.g. `"P"` or `"Sx"`.
+    pub flags: Option<String>,
+    /// Applied by the server (CLIENT LIST TYPE).
+    pub client_type: Option<ClientType>,
+}
+
+impl ClientFilter {
+    pub fn matches(&self, client: &ClientInfo) -> bool {
+        self.addr
+            .as_deref()
+            .is_none_or(|addr| client.addr.contains(addr))
+            && self
+                .name
+                .as_deref()
+                .is_none_or(|pattern| glob::matches(pattern, &client.name))
+            && self.min_idle.is_none_or(|idle| client.idle > idle)
+            && self
+                .flags
+                .as_deref()
+                .is_none_or(|flags| flags.chars().all(|f| client.flags.contains(f)))
+    }
+}
+
+/// One page of a filtered client list, ordered by client ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientPage {
+    pub clients: Vec<ClientInfo>,
+    /// Clients matching the filter, across all pages.
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// Criteria for CLIENT KILL; all set criteria must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientKillFilter {
+    pub id: Option<u64>,
+    pub addr: Option<String>,
+    pub laddr: Option<String>,
+    pub client_type: Option<ClientType>,
+    pub user: Option<String>,
+    /// Whether to spare the connection sending the command (default true).
+    pub skip_me: Option<bool>,
 }
 
 /// Memory analysis result.
@@ -142,3 +497,163 @@ pub struct MemoryStats {
     pub stats: HashMap<String, String>,
     pub doctor_advice: String,
 }
+
+/// Severity of an upgrade advisory.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum AdvisorySeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A single item on the pre-upgrade checklist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeAdvisory {
+    pub id: String,
+    pub severity: AdvisorySeverity,
+    pub title: String,
+    pub detail: String,
+    /// Version that introduced the change, if it is version-specific.
+    pub since_version: Option<String>,
+}
+
+/// Pre-upgrade checklist for a server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeReport {
+    pub current_version: String,
+    pub target_version: String,
+    /// Sorted by severity, most severe first.
+    pub advisories: Vec<UpgradeAdvisory>,
+}
+
+// ─── Alerts ──────────────────────────────────────────────────────
+
+/// Metric an alert rule watches, read from each polled snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertMetric {
+    UsedMemoryBytes,
+    MemoryUsagePercent,
+    HitRatePercent,
+    FragmentationRatio,
+    ConnectedClients,
+    BlockedClients,
+    OpsPerSec,
+}
+
+/// Direction in which a metric crosses its threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertComparison {
+    Above,
+    Below,
+}
+
+/// A threshold rule evaluated against every monitor snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRule {
+    pub id: String,
+    pub name: String,
+    pub metric: AlertMetric,
+    pub comparison: AlertComparison,
+    pub threshold: f64,
+    /// Minimum time between two firings of the same rule.
+    #[serde(default = "default_alert_cooldown")]
+    pub cooldown_secs: u64,
+}
+
+fn default_alert_cooldown() -> u64 {
+    300
+}
+
+/// Cumulative statistics for one command from INFO commandstats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStat {
+    pub command: String,
+    pub calls: u64,
+    pub usec: u64,
+    pub usec_per_call: f64,
+}
+
+/// Connected clients grouped for an alert bundle.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientSummary {
+    pub total: u64,
+    pub blocked: u64,
+    /// Clients idle for at least a minute.
+    pub long_idle: u64,
+    /// Client counts per host, largest first.
+    pub top_hosts: Vec<(String, u64)>,
+}
+
+/// Server state captured at the moment an alert fired.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertBundle {
+    pub snapshot: StatsSnapshot,
+    pub slow_log: Vec<SlowLogEntry>,
+    pub clients: ClientSummary,
+    /// Commands with the most calls since the server started.
+    pub top_commands: Vec<CommandStat>,
+}
+
+/// A fired alert with its correlated bundle, persisted for later review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRecord {
+    pub id: uuid::Uuid,
+    pub connection_id: String,
+    pub rule: AlertRule,
+    pub value: f64,
+    pub fired_at: chrono::DateTime<chrono::Utc>,
+    pub bundle: AlertBundle,
+}
+
+/// INFO ALL pinned at a moment worth comparing against later, such as
+/// right before a deploy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedInfo {
+    pub id: uuid::Uuid,
+    pub connection_id: String,
+    pub label: String,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+    /// Field values by section; `k=v,...` values such as `db0` or
+    /// `cmdstat_get` are split into `db0.keys`, `cmdstat_get.calls`, ...
+    pub sections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// One INFO field that changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InfoFieldDiff {
+    pub section: String,
+    pub field: String,
+    /// `None` when the field is missing on that side.
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// `after - before`, when both are numbers.
+    pub delta: Option<f64>,
+    /// `delta` relative to `before`, in percent; `None` when `before` is 0.
+    pub percent_change: Option<f64>,
+}
+
+/// Field-by-field comparison of two INFO snapshots.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InfoDiff {
+    pub before_label: String,
+    pub before_at: chrono::DateTime<chrono::Utc>,
+    pub after_label: String,
+    pub after_at: chrono::DateTime<chrono::Utc>,
+    /// Fields present with the same value in both.
+    pub unchanged: u64,
+    /// In section, then field order.
+    pub changes: Vec<InfoFieldDiff>,
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::info_parser;
+use super::model::PersistenceStatus;
+use crate::utils::errors::AppError;
+
+/// Persistence section of INFO together with LASTSAVE.
+pub async fn get_status(pool: &Pool) -> Result<PersistenceStatus, AppError> {
+    let mut conn = pool.get().await?;
+    let (raw, last_save): (String, i64) = redis::pipe()
+        .cmd("INFO")
+        .arg("persistence")
+        .cmd("LASTSAVE")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INFO persistence failed: {e}")))?;
+    let persistence = info_parser::parse_info(&raw).persistence;
+    Ok(PersistenceStatus {
+        healthy: persistence.is_healthy(),
+        persistence,
+        last_save,
+    })
+}
+
+/// Start an RDB snapshot in the background. With `schedule`, a snapshot
+/// requested while an AOF rewrite runs is queued instead of failing.
+/// Returns the server's status reply.
+pub async fn background_save(pool: &Pool, schedule: bool) -> Result<String, AppError> {
+    let mut conn = pool.get().await?;
+    let mut cmd = redis::cmd("BGSAVE");
+    if schedule {
+        cmd.arg("SCHEDULE");
+    }
+    cmd.query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("BGSAVE failed: {e}")))
+}
+
+/// Start an AOF rewrite in the background. Returns the server's status reply.
+pub async fn rewrite_aof(pool: &Pool) -> Result<String, AppError> {
+    let mut conn = pool.get().await?;
+    redis::cmd("BGREWRITEAOF")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("BGREWRITEAOF failed: {e}")))
+}
@@ -2,20 +2,90 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use deadpool_redis::Pool;
-use tauri::Emitter;
-use tokio::sync::RwLock;
+use tauri::Manager;
+use tokio::sync::{watch, RwLock};
 use tokio::task::AbortHandle;
+use uuid::Uuid;
 
-use super::info_parser;
-use super::model::{MemoryStats, StatsSnapshot};
+use super::metrics::MetricsHistory;
+use super::model::{
+    AlertRule, InfoSection, MemoryStats, MetricPoint, MetricThresholds, MonitorSubscription,
+    StatsSnapshot,
+};
+use super::{alerts, info_parser};
+use crate::redis::connection::manager::ConnectionManager;
+use crate::redis::connection::throttle::Throttle;
 use crate::utils::errors::AppError;
+use crate::utils::event_throttle::EventThrottler;
 
-/// Manages background polling tasks, one per connection.
+/// INFO round trip or ops/sec at which the server counts as busy: the poll
+/// interval doubles, up to [`MAX_BACKOFF_FACTOR`] times the requested one.
+const BUSY_INFO_LATENCY: Duration = Duration::from_millis(250);
+const BUSY_OPS_PER_SEC: u64 = 100_000;
+/// Below both of these the interval halves back toward the requested one.
+const IDLE_INFO_LATENCY: Duration = Duration::from_millis(50);
+const IDLE_OPS_PER_SEC: u64 = 20_000;
+const MAX_BACKOFF_FACTOR: u32 = 5;
+
+/// Subscriber ID used by `monitor_start_polling` / `monitor_stop_polling`.
+pub const DEFAULT_SUBSCRIBER: &str = "default";
+
+/// What a poller needs to poll a connection. The pool is looked up through
+/// the connection manager on each poll, so a reconnect's new one is used.
+pub struct PollSource {
+    pub throttle: Arc<Throttle>,
+    pub thresholds: MetricThresholds,
+    pub rules: Vec<AlertRule>,
+    pub throttler: EventThrottler,
+    pub history: MetricsHistory,
+    pub app_handle: tauri::AppHandle,
+}
+
+/// Settings a running poller re-reads before each poll.
+#[derive(Clone)]
+struct PollConfig {
+    interval: Duration,
+    sections: Vec<InfoSection>,
+    thresholds: MetricThresholds,
+    rules: Vec<AlertRule>,
+}
+
+/// What one subscriber asked for.
+struct Interest {
+    interval: Duration,
+    sections: Vec<InfoSection>,
+}
+
+/// The one polling task of a connection and the views sharing it.
+struct SharedPoller {
+    abort: AbortHandle,
+    subscribers: HashMap<String, Interest>,
+    config: watch::Sender<PollConfig>,
+}
+
+impl SharedPoller {
+    fn subscription(&self, connection_id: &str) -> MonitorSubscription {
+        let config = self.config.borrow();
+        MonitorSubscription {
+            connection_id: connection_id.to_string(),
+            subscribers: self.subscribers.len(),
+            interval_ms: duration_ms(config.interval),
+            sections: config.sections.clone(),
+        }
+    }
+}
+
+/// Manages background polling, one task per connection.
+///
+/// Views that want `monitor:stats` subscribe with their own ID and interval.
+/// All subscribers of a connection share one task (and one INFO per tick)
+/// that polls at the fastest requested interval for every section any of
+/// them asked for; it stops when the last subscriber leaves.
 pub struct MonitorPoller {
-    handles: Arc<RwLock<HashMap<String, AbortHandle>>>,
+    pollers: Arc<RwLock<HashMap<String, SharedPoller>>>,
 }
 
 impl Default for MonitorPoller {
@@ -28,85 +98,319 @@ impl MonitorPoller {
     /// Create a new poller manager with no active pollers.
     pub fn new() -> Self {
         Self {
-            handles: Arc::new(RwLock::new(HashMap::new())),
+            pollers: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    /// Start polling for a connection. Spawns a background tokio task.
+    /// Add (or update) `subscriber_id`'s interest in a connection's stats.
     ///
-    /// If already polling for this connection, stops the old one first.
-    pub async fn start(
+    /// Starts the connection's polling task if it isn't running. Otherwise
+    /// the running task switches to the new fastest interval and section
+    /// union, and to `source`'s thresholds and alert rules; a breached rule fires at most
+    /// once per its cooldown. Snapshots go out through the source's
+    /// throttler, so fast intervals coalesce to the latest snapshot. Polling
+    /// pauses while the connection's circuit breaker is open.
+    pub async fn subscribe(
         &self,
-        connection_id: String,
-        pool: Pool,
-        interval_ms: u64,
-        app_handle: tauri::AppHandle,
-    ) {
-        // Stop any existing poller for this connection
-        self.stop(&connection_id).await;
-
-        let handles = self.handles.clone();
-        let conn_id = connection_id.clone();
-
-        let task = tokio::spawn(async move {
-            let interval = Duration::from_millis(interval_ms);
-            loop {
-                // Fetch INFO ALL
-                match fetch_info_all(&pool).await {
-                    Ok(snapshot) => {
-                        // Emit the snapshot via Tauri event
-                        if let Err(e) = app_handle.emit("monitor:stats", &snapshot) {
-                            tracing::warn!(connection_id = %conn_id, "Failed to emit monitor event: {e}");
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!(connection_id = %conn_id, "Monitor poll failed: {e}");
-                        // Don't break — transient errors should not kill the poller.
-                        // The next iteration will retry.
-                    }
-                }
+        connection_id: &str,
+        subscriber_id: &str,
+        interval: Duration,
+        sections: Vec<InfoSection>,
+        source: PollSource,
+    ) -> MonitorSubscription {
+        let interest = Interest { interval, sections };
+        let mut pollers = self.pollers.write().await;
+        if let Some(poller) = pollers.get_mut(connection_id) {
+            poller
+                .subscribers
+                .insert(subscriber_id.to_string(), interest);
+            let (interval, sections) = merge_interests(&poller.subscribers);
+            poller.config.send_replace(PollConfig {
+                interval,
+                sections,
+                thresholds: source.thresholds,
+                rules: source.rules,
+            });
+            return poller.subscription(connection_id);
+        }
 
-                tokio::time::sleep(interval).await;
-            }
+        let subscribers = HashMap::from([(subscriber_id.to_string(), interest)]);
+        let (interval, sections) = merge_interests(&subscribers);
+        let (config, receiver) = watch::channel(PollConfig {
+            interval,
+            sections,
+            thresholds: source.thresholds,
+            rules: source.rules,
         });
+        let task = tokio::spawn(poll_loop(
+            connection_id.to_string(),
+            source.throttle,
+            receiver,
+            source.throttler,
+            source.history,
+            source.app_handle,
+        ));
+        let poller = SharedPoller {
+            abort: task.abort_handle(),
+            subscribers,
+            config,
+        };
+        let subscription = poller.subscription(connection_id);
+        pollers.insert(connection_id.to_string(), poller);
+        tracing::info!(connection_id = %connection_id, interval_ms = subscription.interval_ms, "Monitor polling started");
+        subscription
+    }
 
-        let abort_handle = task.abort_handle();
-        let mut h = handles.write().await;
-        h.insert(connection_id, abort_handle);
+    /// Remove a subscriber; the task stops with the last one, otherwise it
+    /// slows to the fastest remaining interval and drops sections nobody
+    /// else asked for. Returns `None` if the
+    /// connection is no longer polled.
+    pub async fn unsubscribe(
+        &self,
+        connection_id: &str,
+        subscriber_id: &str,
+    ) -> Option<MonitorSubscription> {
+        let mut pollers = self.pollers.write().await;
+        let poller = pollers.get_mut(connection_id)?;
+        poller.subscribers.remove(subscriber_id);
+        if poller.subscribers.is_empty() {
+            if let Some(poller) = pollers.remove(connection_id) {
+                poller.abort.abort();
+                tracing::info!(connection_id = %connection_id, "Monitor polling stopped");
+            }
+            return None;
+        }
+        let (interval, sections) = merge_interests(&poller.subscribers);
+        poller.config.send_modify(|config| {
+            config.interval = interval;
+            config.sections = sections;
+        });
+        Some(poller.subscription(connection_id))
     }
 
-    /// Stop polling for a connection.
+    /// Stop polling for a connection, whoever subscribed.
     pub async fn stop(&self, connection_id: &str) {
-        let mut h = self.handles.write().await;
-        if let Some(handle) = h.remove(connection_id) {
-            handle.abort();
+        let mut pollers = self.pollers.write().await;
+        if let Some(poller) = pollers.remove(connection_id) {
+            poller.abort.abort();
             tracing::info!(connection_id = %connection_id, "Monitor polling stopped");
         }
     }
 
     /// Stop all active pollers (e.g., on app shutdown).
     pub async fn stop_all(&self) {
-        let mut h = self.handles.write().await;
-        for (id, handle) in h.drain() {
-            handle.abort();
+        let mut pollers = self.pollers.write().await;
+        for (id, poller) in pollers.drain() {
+            poller.abort.abort();
             tracing::info!(connection_id = %id, "Monitor polling stopped (shutdown)");
         }
     }
 
     /// Check if a connection is currently being polled.
     pub async fn is_polling(&self, connection_id: &str) -> bool {
-        let h = self.handles.read().await;
-        h.contains_key(connection_id)
+        let pollers = self.pollers.read().await;
+        pollers.contains_key(connection_id)
+    }
+
+    /// The shared poller of a connection, if it is polled.
+    pub async fn subscription(&self, connection_id: &str) -> Option<MonitorSubscription> {
+        let pollers = self.pollers.read().await;
+        pollers
+            .get(connection_id)
+            .map(|p| p.subscription(connection_id))
     }
 }
 
+/// The polling task of one connection.
+async fn poll_loop(
+    conn_id: String,
+    throttle: Arc<Throttle>,
+    mut config: watch::Receiver<PollConfig>,
+    throttler: EventThrottler,
+    history: MetricsHistory,
+    app_handle: tauri::AppHandle,
+) {
+    let mut last_fired: HashMap<String, Instant> = HashMap::new();
+    let mut backoff = LoadBackoff::default();
+    let mut previous: Option<StatsSnapshot> = None;
+    loop {
+        throttle.pause_while_open().await;
+        let PollConfig {
+            sections,
+            thresholds,
+            rules,
+            ..
+        } = config.borrow_and_update().clone();
+        let polled_at = Instant::now();
+        let fetched = poll_info(&app_handle, &conn_id, &thresholds, &sections).await;
+        let latency = polled_at.elapsed();
+        match fetched {
+            Ok((pool, mut snapshot)) => {
+                backoff.observe(latency, snapshot.info.stats.instantaneous_ops_per_sec);
+                snapshot.poll_interval_ms =
+                    Some(duration_ms(backoff.apply(config.borrow().interval)));
+                snapshot.derived.rates = previous
+                    .as_ref()
+                    .and_then(|previous| info_parser::interval_rates(previous, &snapshot));
+                history
+                    .record(&conn_id, MetricPoint::from_snapshot(&snapshot))
+                    .await;
+                // Emit the snapshot via Tauri event
+                if let Err(e) = throttler
+                    .emit(&app_handle, "monitor:stats", &conn_id, &snapshot)
+                    .await
+                {
+                    tracing::warn!(connection_id = %conn_id, "Failed to emit monitor event: {e}");
+                    break;
+                }
+
+                for (rule, value) in alerts::evaluate(&rules, &snapshot) {
+                    let cooldown = Duration::from_secs(rule.cooldown_secs);
+                    if last_fired
+                        .get(&rule.id)
+                        .is_some_and(|at| at.elapsed() < cooldown)
+                    {
+                        continue;
+                    }
+                    last_fired.insert(rule.id.clone(), Instant::now());
+                    if let Err(e) =
+                        alerts::record_alert(&pool, &app_handle, &conn_id, rule, value, &snapshot)
+                            .await
+                    {
+                        tracing::warn!(connection_id = %conn_id, rule = %rule.name, "Failed to record alert: {e}");
+                    }
+                }
+                previous = Some(snapshot);
+            }
+            Err(e) => {
+                backoff.observe_failure();
+                tracing::warn!(connection_id = %conn_id, "Monitor poll failed: {e}");
+                // Don't break — transient errors should not kill the poller.
+                // The next iteration will retry.
+            }
+        }
+
+        // Sleep out the interval, re-timing it if a subscriber changes it.
+        loop {
+            let wait = backoff
+                .apply(config.borrow().interval)
+                .saturating_sub(polled_at.elapsed());
+            tokio::select! {
+                () = tokio::time::sleep(wait) => break,
+                changed = config.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Fetch INFO through the connection's current pool, returning the pool
+/// too for the alerts the snapshot may fire.
+async fn poll_info(
+    app_handle: &tauri::AppHandle,
+    conn_id: &str,
+    thresholds: &MetricThresholds,
+    sections: &[InfoSection],
+) -> Result<(Pool, StatsSnapshot), AppError> {
+    let uuid = Uuid::parse_str(conn_id)?;
+    let pool = app_handle
+        .state::<ConnectionManager>()
+        .get_pool(&uuid)
+        .await?;
+    let snapshot = fetch_info(&pool, thresholds, Some(sections)).await?;
+    Ok((pool, snapshot))
+}
+
+/// Stretches the poll interval while the server is under pressure, judged by
+/// how long the INFO poll took and by `instantaneous_ops_per_sec`.
+#[derive(Debug)]
+struct LoadBackoff {
+    factor: u32,
+}
+
+impl Default for LoadBackoff {
+    fn default() -> Self {
+        Self { factor: 1 }
+    }
+}
+
+impl LoadBackoff {
+    fn observe(&mut self, info_latency: Duration, ops_per_sec: u64) {
+        if info_latency >= BUSY_INFO_LATENCY || ops_per_sec >= BUSY_OPS_PER_SEC {
+            self.observe_failure();
+        } else if info_latency <= IDLE_INFO_LATENCY && ops_per_sec <= IDLE_OPS_PER_SEC {
+            self.factor = (self.factor / 2).max(1);
+        }
+    }
+
+    /// A failed poll (often a timeout) counts as pressure.
+    fn observe_failure(&mut self) {
+        self.factor = (self.factor * 2).min(MAX_BACKOFF_FACTOR);
+    }
+
+    fn apply(&self, interval: Duration) -> Duration {
+        interval * self.factor
+    }
+}
+
+/// The fastest interval and the union of sections over all subscribers.
+fn merge_interests(subscribers: &HashMap<String, Interest>) -> (Duration, Vec<InfoSection>) {
+    let interval = subscribers
+        .values()
+        .map(|i| i.interval)
+        .min()
+        .unwrap_or_default();
+    let mut sections: Vec<InfoSection> = subscribers
+        .values()
+        .flat_map(|i| i.sections.iter().copied())
+        .collect();
+    sections.sort_unstable();
+    sections.dedup();
+    (interval, sections)
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
 /// Fetch INFO ALL and build a `StatsSnapshot`.
-async fn fetch_info_all(pool: &Pool) -> Result<StatsSnapshot, AppError> {
+pub async fn fetch_info_all(
+    pool: &Pool,
+    thresholds: &MetricThresholds,
+) -> Result<StatsSnapshot, AppError> {
+    fetch_info(pool, thresholds, None).await
+}
+
+/// Fetch INFO for `sections` (all of them if `None`) and build a
+/// `StatsSnapshot` (one poll iteration). Fields of sections that weren't
+/// fetched keep their defaults.
+///
+/// Each section is its own `INFO <section>` in one pipeline, since servers
+/// before Redis 7 accept only one section per INFO.
+pub async fn fetch_info(
+    pool: &Pool,
+    thresholds: &MetricThresholds,
+    sections: Option<&[InfoSection]>,
+) -> Result<StatsSnapshot, AppError> {
     let mut conn = pool.get().await?;
-    let raw: String = redis::cmd("INFO").arg("ALL").query_async(&mut conn).await?;
+    let raw: String = match sections {
+        None => redis::cmd("INFO").arg("ALL").query_async(&mut conn).await?,
+        Some(sections) => {
+            let mut pipe = redis::pipe();
+            for section in sections {
+                pipe.cmd("INFO").arg(section.as_str());
+            }
+            let parts: Vec<String> = pipe.query_async(&mut conn).await?;
+            parts.join("\r\n")
+        }
+    };
 
-    Ok(info_parser::build_snapshot(&raw))
+    let mut snapshot = info_parser::build_snapshot(&raw, thresholds);
+    snapshot.info.sections = sections.map(<[InfoSection]>::to_vec);
+    Ok(snapshot)
 }
 
 /// Fetch MEMORY STATS and MEMORY DOCTOR on demand.
@@ -165,6 +469,67 @@ mod tests {
         assert!(!poller.is_polling("conn-1").await);
     }
 
+    #[tokio::test]
+    async fn test_unsubscribe_unknown_is_noop() {
+        let poller = MonitorPoller::new();
+        assert!(poller.unsubscribe("conn-1", "view-1").await.is_none());
+        assert!(poller.subscription("conn-1").await.is_none());
+    }
+
+    #[test]
+    fn test_merge_interests() {
+        let subscribers = HashMap::from([
+            (
+                "dashboard".to_string(),
+                Interest {
+                    interval: Duration::from_secs(5),
+                    sections: vec![InfoSection::Memory, InfoSection::Keyspace],
+                },
+            ),
+            (
+                "chart".to_string(),
+                Interest {
+                    interval: Duration::from_millis(750),
+                    sections: vec![InfoSection::Stats, InfoSection::Memory],
+                },
+            ),
+        ]);
+        let (interval, sections) = merge_interests(&subscribers);
+        assert_eq!(interval, Duration::from_millis(750));
+        assert_eq!(
+            sections,
+            vec![
+                InfoSection::Memory,
+                InfoSection::Stats,
+                InfoSection::Keyspace
+            ]
+        );
+        assert_eq!(merge_interests(&HashMap::new()).0, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_load_backoff() {
+        let mut backoff = LoadBackoff::default();
+        let base = Duration::from_secs(2);
+        let quick = Duration::from_millis(5);
+
+        backoff.observe(quick, 150_000);
+        assert_eq!(backoff.apply(base), Duration::from_secs(4));
+        backoff.observe(Duration::from_millis(400), 0);
+        backoff.observe_failure();
+        assert_eq!(backoff.apply(base), Duration::from_secs(10));
+
+        // In between busy and idle: hold.
+        backoff.observe(quick, 50_000);
+        assert_eq!(backoff.apply(base), Duration::from_secs(10));
+
+        backoff.observe(quick, 100);
+        assert_eq!(backoff.apply(base), Duration::from_secs(4));
+        backoff.observe(quick, 100);
+        backoff.observe(quick, 100);
+        assert_eq!(backoff.apply(base), base);
+    }
+
     #[tokio::test]
     async fn test_poller_stop_nonexistent_is_noop() {
         let poller = MonitorPoller::new();
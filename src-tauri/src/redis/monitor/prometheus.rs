@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MIT
+
+//! Opt-in Prometheus scrape endpoint serving the newest polled metrics of
+//! every monitored connection at `http://127.0.0.1:<port>/metrics`.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+use super::metrics::{self, MetricsHistory};
+use crate::utils::errors::AppError;
+
+/// Largest request head read before answering.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+/// How long a client gets to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The running endpoint, if enabled: its port and accept loop.
+#[derive(Default)]
+pub struct PrometheusExporter {
+    server: Mutex<Option<(u16, AbortHandle)>>,
+}
+
+impl PrometheusExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve on `port` (loopback only), or stop serving when `None`.
+    /// Keeps a running endpoint when the port is unchanged.
+    pub async fn configure(
+        &self,
+        port: Option<u16>,
+        history: MetricsHistory,
+    ) -> Result<(), AppError> {
+        let mut server = self.server.lock().await;
+        if server.as_ref().map(|(p, _)| *p) == port {
+            return Ok(());
+        }
+        if let Some((_, abort)) = server.take() {
+            abort.abort();
+            tracing::info!("Prometheus endpoint stopped");
+        }
+        let Some(port) = port else {
+            return Ok(());
+        };
+        if port == 0 {
+            return Err(AppError::InvalidInput(
+                "Prometheus port must be between 1 and 65535".into(),
+            ));
+        }
+
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| AppError::Conflict(format!("Cannot listen on {addr}: {e}")))?;
+        let task = tokio::spawn(serve(listener, history));
+        *server = Some((port, task.abort_handle()));
+        tracing::info!(%addr, "Prometheus endpoint started");
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        if let Some((_, abort)) = self.server.lock().await.take() {
+            abort.abort();
+        }
+    }
+}
+
+async fn serve(listener: TcpListener, history: MetricsHistory) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let history = history.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = answer(stream, &history).await {
+                        tracing::debug!("Prometheus request failed: {e}");
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Prometheus endpoint accept failed: {e}"),
+        }
+    }
+}
+
+async fn answer(mut stream: TcpStream, history: &MetricsHistory) -> std::io::Result<()> {
+    let head = tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    let request_line = head.lines().next().unwrap_or_default();
+    let response = if is_metrics_request(request_line) {
+        http_response(
+            "200 OK",
+            &metrics::render_prometheus(&history.latest().await),
+        )
+    } else {
+        http_response("404 Not Found", "Not found\n")
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read until the blank line ending the request head (the body, if any, is
+/// ignored).
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while head.len() < MAX_REQUEST_BYTES && !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// `GET /metrics`, with or without a query string.
+fn is_metrics_request(request_line: &str) -> bool {
+    let mut parts = request_line.split_whitespace();
+    parts.next() == Some("GET")
+        && parts
+            .next()
+            .is_some_and(|target| target.split('?').next() == Some("/metrics"))
+}
+
+fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_metrics_request() {
+        assert!(is_metrics_request("GET /metrics HTTP/1.1"));
+        assert!(is_metrics_request("GET /metrics?name[]=x HTTP/1.1"));
+        assert!(!is_metrics_request("POST /metrics HTTP/1.1"));
+        assert!(!is_metrics_request("GET /metrics/x HTTP/1.1"));
+        assert!(!is_metrics_request(""));
+    }
+
+    #[test]
+    fn test_http_response() {
+        assert_eq!(
+            http_response("404 Not Found", "no\n"),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+             Content-Length: 3\r\nConnection: close\r\n\r\nno\n"
+        );
+    }
+}
@@ -1,9 +1,15 @@
 // SPDX-License-Identifier: MIT
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use deadpool_redis::Pool;
 use redis::Value;
+use tokio::sync::RwLock;
+use uuid::Uuid;
 
-use super::model::SlowLogEntry;
+use super::model::{SlowLogDigest, SlowLogEntry, SlowLogGroup};
+use crate::redis::cli::command_docs::CommandCatalog;
 use crate::utils::errors::AppError;
 
 /// Fetch and parse SLOWLOG GET entries.
@@ -18,6 +24,163 @@ pub async fn get_slow_log(pool: &Pool, count: u64) -> Result<Vec<SlowLogEntry>,
     Ok(parse_slow_log_response(&raw))
 }
 
+/// Number of entries currently in the slow log.
+pub async fn get_slow_log_len(pool: &Pool) -> Result<u64, AppError> {
+    let mut conn = pool.get().await?;
+    redis::cmd("SLOWLOG")
+        .arg("LEN")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("SLOWLOG LEN failed: {e}")))
+}
+
+/// Empty the slow log.
+pub async fn reset_slow_log(pool: &Pool) -> Result<(), AppError> {
+    let mut conn = pool.get().await?;
+    redis::cmd("SLOWLOG")
+        .arg("RESET")
+        .query_async::<()>(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("SLOWLOG RESET failed: {e}")))
+}
+
+/// Remembers the newest slow log entry each connection has been shown, so
+/// repeated fetches can report only what is new.
+///
+/// Entry IDs keep increasing across SLOWLOG RESET, so a reset needs no
+/// special handling.
+pub struct SlowLogTracker {
+    last_ids: Arc<RwLock<HashMap<Uuid, u64>>>,
+}
+
+impl Default for SlowLogTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlowLogTracker {
+    pub fn new() -> Self {
+        Self {
+            last_ids: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Keep the entries newer than the last call for this connection (all
+    /// of them when `since_last` is off) and remember the newest ID.
+    pub async fn advance(
+        &self,
+        connection_id: Uuid,
+        entries: Vec<SlowLogEntry>,
+        since_last: bool,
+    ) -> Vec<SlowLogEntry> {
+        let mut last_ids = self.last_ids.write().await;
+        let previous = last_ids.get(&connection_id).copied();
+        if let Some(newest) = entries.iter().map(|e| e.id).max() {
+            let last = last_ids.entry(connection_id).or_default();
+            *last = (*last).max(newest);
+        }
+        match previous {
+            Some(previous) if since_last => {
+                entries.into_iter().filter(|e| e.id > previous).collect()
+            }
+            _ => entries,
+        }
+    }
+
+    /// Forget a connection's position, e.g. on disconnect.
+    pub async fn forget(&self, connection_id: &Uuid) {
+        self.last_ids.write().await.remove(connection_id);
+    }
+}
+
+/// Group entries by command shape: the command name plus, when the first
+/// argument is a key, that key with ID-like segments generalized.
+pub fn group_entries(entries: &[SlowLogEntry], catalog: &CommandCatalog) -> Vec<SlowLogGroup> {
+    let mut groups: HashMap<String, SlowLogGroup> = HashMap::new();
+    for entry in entries {
+        let (command, key_pattern) = command_shape(&entry.command, catalog);
+        let shape = match &key_pattern {
+            Some(pattern) => format!("{command} {pattern}"),
+            None => command.clone(),
+        };
+        let group = groups.entry(shape.clone()).or_insert_with(|| SlowLogGroup {
+            shape,
+            command,
+            key_pattern,
+            count: 0,
+            total_us: 0,
+            avg_us: 0,
+            max_us: 0,
+            last_seen: 0,
+            slowest: String::new(),
+        });
+        group.count += 1;
+        group.total_us += entry.duration_us;
+        if entry.duration_us >= group.max_us {
+            group.max_us = entry.duration_us;
+            group.slowest.clone_from(&entry.command);
+        }
+        group.last_seen = group.last_seen.max(entry.timestamp);
+    }
+
+    let mut groups: Vec<SlowLogGroup> = groups
+        .into_values()
+        .map(|mut group| {
+            group.avg_us = group.total_us / group.count;
+            group
+        })
+        .collect();
+    groups.sort_by(|a, b| b.total_us.cmp(&a.total_us).then(a.shape.cmp(&b.shape)));
+    groups
+}
+
+/// Grouped digest of the latest `count` entries.
+pub fn digest(entries: &[SlowLogEntry], length: u64, catalog: &CommandCatalog) -> SlowLogDigest {
+    SlowLogDigest {
+        groups: group_entries(entries, catalog),
+        entries: entries.len() as u64,
+        length,
+    }
+}
+
+/// Command name and generalized first key of a slow log command line.
+fn command_shape(command: &str, catalog: &CommandCatalog) -> (String, Option<String>) {
+    let tokens: Vec<String> = command.split_whitespace().map(String::from).collect();
+    let Some(name) = tokens.first() else {
+        return (String::new(), None);
+    };
+    let name = name.to_uppercase();
+    let key_pattern = tokens
+        .get(1)
+        .filter(|_| catalog.is_key_position(&tokens[..1], 1))
+        .map(|key| key_pattern(key));
+    (name, key_pattern)
+}
+
+/// Replace the ID-like segments of a `:`-delimited key with `*`:
+/// `user:1042:sessions` → `user:*:sessions`.
+pub fn key_pattern(key: &str) -> String {
+    key.split(':')
+        .map(|segment| if is_id_like(segment) { "*" } else { segment })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Numbers, UUIDs and long hex strings.
+fn is_id_like(segment: &str) -> bool {
+    if segment.is_empty() {
+        return false;
+    }
+    if segment.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+    let hex = segment.bytes().filter(u8::is_ascii_hexdigit).count();
+    let dashes = segment.bytes().filter(|&b| b == b'-').count();
+    let is_uuid = segment.len() == 36 && dashes == 4 && hex == 32;
+    is_uuid || (segment.len() >= 16 && hex == segment.len())
+}
+
 /// Parse the raw SLOWLOG GET response into typed entries.
 ///
 /// SLOWLOG GET returns an array of arrays. Each entry is:
@@ -180,6 +343,74 @@ mod tests {
         assert_eq!(result[1].client_name, "worker");
     }
 
+    fn entry(id: u64, duration_us: u64, command: &str) -> SlowLogEntry {
+        SlowLogEntry {
+            id,
+            timestamp: 1_700_000_000 + id,
+            duration_us,
+            command: command.into(),
+            client_addr: String::new(),
+            client_name: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_key_pattern() {
+        assert_eq!(key_pattern("user:1042:sessions"), "user:*:sessions");
+        assert_eq!(
+            key_pattern("order:550e8400-e29b-41d4-a716-446655440000"),
+            "order:*"
+        );
+        assert_eq!(key_pattern("blob:9f86d081884c7d65"), "blob:*");
+        assert_eq!(key_pattern("cache:feed"), "cache:feed");
+        assert_eq!(key_pattern("a::b"), "a::b");
+    }
+
+    #[test]
+    fn test_group_entries() {
+        let entries = [
+            entry(1, 100, "HGETALL user:1"),
+            entry(2, 300, "HGETALL user:2"),
+            entry(3, 1000, "KEYS *"),
+            entry(4, 50, "FLUSHALL"),
+        ];
+        let groups = group_entries(&entries, &CommandCatalog::default());
+        assert_eq!(groups.len(), 3);
+
+        assert_eq!(groups[0].shape, "KEYS");
+        assert_eq!(groups[0].key_pattern, None);
+
+        let hgetall = &groups[1];
+        assert_eq!(hgetall.shape, "HGETALL user:*");
+        assert_eq!(hgetall.count, 2);
+        assert_eq!((hgetall.avg_us, hgetall.max_us), (200, 300));
+        assert_eq!(hgetall.slowest, "HGETALL user:2");
+        assert_eq!(hgetall.last_seen, 1_700_000_002);
+
+        assert_eq!(groups[2].shape, "FLUSHALL");
+    }
+
+    #[tokio::test]
+    async fn test_tracker_returns_new_entries() {
+        let tracker = SlowLogTracker::new();
+        let id = Uuid::new_v4();
+        let first = tracker
+            .advance(id, vec![entry(2, 10, "GET a"), entry(1, 10, "GET b")], true)
+            .await;
+        assert_eq!(first.len(), 2);
+
+        let next = tracker
+            .advance(id, vec![entry(3, 10, "GET c"), entry(2, 10, "GET a")], true)
+            .await;
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].id, 3);
+
+        let all = tracker
+            .advance(id, vec![entry(3, 10, "GET c")], false)
+            .await;
+        assert_eq!(all.len(), 1);
+    }
+
     #[test]
     fn test_parse_slow_log_non_array_returns_empty() {
         let val = Value::Nil;
@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use deadpool_redis::Pool;
+
+use super::model::{AdvisorySeverity, UpgradeAdvisory, UpgradeReport};
+use crate::redis::connection::capabilities::parse_version;
+use crate::utils::errors::AppError;
+
+/// Target assumed when the caller doesn't name one.
+pub const DEFAULT_TARGET_VERSION: &str = "7.4.0";
+
+type Version = (u32, u32, u32);
+
+/// What the advisor knows about the server.
+struct ServerFacts<'a> {
+    info: &'a HashMap<String, String>,
+    config: &'a HashMap<String, String>,
+}
+
+impl ServerFacts<'_> {
+    fn config_is(&self, key: &str, value: &str) -> bool {
+        self.config
+            .get(key)
+            .is_some_and(|v| v.eq_ignore_ascii_case(value))
+    }
+
+    fn config_set(&self, key: &str) -> bool {
+        self.config.get(key).is_some_and(|v| !v.is_empty())
+    }
+}
+
+/// A known deprecation or behaviour change.
+struct Rule {
+    id: &'static str,
+    /// Version introducing the change. `None` for checks that apply to the
+    /// current configuration regardless of the upgrade path.
+    since: Option<Version>,
+    severity: AdvisorySeverity,
+    title: &'static str,
+    detail: &'static str,
+    applies: fn(&ServerFacts) -> bool,
+}
+
+const RULES: &[Rule] = &[
+    Rule {
+        id: "eol-version",
+        since: None,
+        severity: AdvisorySeverity::Warning,
+        title: "Server version is end-of-life",
+        detail: "Versions before 6.2 no longer receive security fixes. Plan the upgrade soon.",
+        applies: |f| parse_version(f.info.get("redis_version").map_or("", String::as_str)) < (6, 2, 0),
+    },
+    Rule {
+        id: "protected-mode-off",
+        since: None,
+        severity: AdvisorySeverity::Critical,
+        title: "protected-mode is disabled",
+        detail: "With protected-mode off and no password, the server accepts connections from any interface. Enable it or set requirepass / ACL users before exposing the new version.",
+        applies: |f| f.config_is("protected-mode", "no") && !f.config_set("requirepass"),
+    },
+    Rule {
+        id: "slave-config-names",
+        since: Some((5, 0, 0)),
+        severity: AdvisorySeverity::Info,
+        title: "slave-* settings renamed to replica-*",
+        detail: "SLAVEOF and slave-* config names become aliases of REPLICAOF and replica-*. Update config files and automation to the new names.",
+        applies: |f| f.config.contains_key("slave-read-only") || f.config.contains_key("slaveof"),
+    },
+    Rule {
+        id: "acl-default-user",
+        since: Some((6, 0, 0)),
+        severity: AdvisorySeverity::Info,
+        title: "requirepass maps to the ACL default user",
+        detail: "ACLs arrive in 6.0; requirepass sets the password of the 'default' user. Consider creating dedicated users with restricted command sets.",
+        applies: |f| f.config_set("requirepass"),
+    },
+    Rule {
+        id: "resp3-available",
+        since: Some((6, 0, 0)),
+        severity: AdvisorySeverity::Info,
+        title: "RESP3 protocol becomes available",
+        detail: "Clients may negotiate RESP3 with HELLO 3, changing reply shapes (maps, doubles, push messages). Verify client libraries that opt into RESP3 by default.",
+        applies: |_| true,
+    },
+    Rule {
+        id: "listpack-encoding",
+        since: Some((7, 0, 0)),
+        severity: AdvisorySeverity::Info,
+        title: "ziplist replaced by listpack",
+        detail: "Small hashes, sets and sorted sets are encoded as listpack; OBJECT ENCODING reports 'listpack' and *-max-ziplist-* settings become aliases of *-max-listpack-*.",
+        applies: |f| f.config.keys().any(|k| k.contains("ziplist")),
+    },
+    Rule {
+        id: "multipart-aof",
+        since: Some((7, 0, 0)),
+        severity: AdvisorySeverity::Warning,
+        title: "AOF becomes multi-part",
+        detail: "The append-only file is split into base and incremental files inside appenddirname. Backup and restore scripts that copy a single appendonly.aof must be updated.",
+        applies: |f| f.config_is("appendonly", "yes"),
+    },
+    Rule {
+        id: "script-effects-replication",
+        since: Some((7, 0, 0)),
+        severity: AdvisorySeverity::Info,
+        title: "Lua scripts always replicate effects",
+        detail: "Verbatim script replication is removed. Scripts relying on non-deterministic commands behave differently on replicas; consider migrating to Functions.",
+        applies: |f| {
+            f.info
+                .get("number_of_cached_scripts")
+                .and_then(|n| n.parse::<u64>().ok())
+                .is_some_and(|n| n > 0)
+        },
+    },
+    Rule {
+        id: "debug-module-commands-disabled",
+        since: Some((7, 0, 0)),
+        severity: AdvisorySeverity::Info,
+        title: "DEBUG and MODULE commands disabled by default",
+        detail: "enable-debug-command and enable-module-command default to 'no'. Tooling that calls DEBUG or MODULE LOAD at runtime needs these enabled explicitly.",
+        applies: |_| true,
+    },
+];
+
+/// Fetch INFO and CONFIG and build the pre-upgrade report.
+///
+/// CONFIG GET is optional; managed services that block it get a report
+/// based on INFO alone.
+pub async fn build_report(
+    pool: &Pool,
+    target_version: Option<&str>,
+) -> Result<UpgradeReport, AppError> {
+    let mut conn = pool.get().await?;
+
+    let raw: String = redis::cmd("INFO")
+        .arg("ALL")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("INFO failed: {e}")))?;
+    let info = parse_info_map(&raw);
+
+    let config: HashMap<String, String> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("*")
+        .query_async(&mut conn)
+        .await
+        .unwrap_or_default();
+
+    Ok(advise(
+        &info,
+        &config,
+        target_version.unwrap_or(DEFAULT_TARGET_VERSION),
+    ))
+}
+
+/// Evaluate every rule against the server facts for an upgrade to `target`.
+fn advise(
+    info: &HashMap<String, String>,
+    config: &HashMap<String, String>,
+    target: &str,
+) -> UpgradeReport {
+    let current_version = info
+        .get("redis_version")
+        .cloned()
+        .unwrap_or_else(|| "unknown".into());
+    let current = parse_version(&current_version);
+    let target_v = parse_version(target);
+    let facts = ServerFacts { info, config };
+
+    let mut advisories: Vec<UpgradeAdvisory> = RULES
+        .iter()
+        .filter(|rule| match rule.since {
+            Some(since) => current < since && since <= target_v,
+            None => true,
+        })
+        .filter(|rule| (rule.applies)(&facts))
+        .map(|rule| UpgradeAdvisory {
+            id: rule.id.to_string(),
+            severity: rule.severity,
+            title: rule.title.to_string(),
+            detail: rule.detail.to_string(),
+            since_version: rule
+                .since
+                .map(|(major, minor, _)| format!("{major}.{minor}")),
+        })
+        .collect();
+    advisories.sort_by_key(|a| std::cmp::Reverse(a.severity));
+
+    UpgradeReport {
+        current_version,
+        target_version: target.to_string(),
+        advisories,
+    }
+}
+
+/// Parse raw INFO output into a flat key-value map.
+fn parse_info_map(raw: &str) -> HashMap<String, String> {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    fn ids(report: &UpgradeReport) -> Vec<&str> {
+        report.advisories.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_advise_crossing_7_0() {
+        let info = map(&[("redis_version", "6.2.14")]);
+        let config = map(&[
+            ("appendonly", "yes"),
+            ("hash-max-ziplist-entries", "128"),
+            ("protected-mode", "yes"),
+        ]);
+        let report = advise(&info, &config, "7.2.0");
+        let ids = ids(&report);
+        assert!(ids.contains(&"multipart-aof"));
+        assert!(ids.contains(&"listpack-encoding"));
+        assert!(!ids.contains(&"resp3-available"));
+        assert!(!ids.contains(&"eol-version"));
+        assert_eq!(report.advisories[0].severity, AdvisorySeverity::Warning);
+    }
+
+    #[test]
+    fn test_advise_current_state_rules() {
+        let info = map(&[("redis_version", "5.0.7")]);
+        let config = map(&[("protected-mode", "no")]);
+        let report = advise(&info, &config, "6.0.0");
+        let ids = ids(&report);
+        assert_eq!(ids[0], "protected-mode-off");
+        assert!(ids.contains(&"eol-version"));
+        assert!(ids.contains(&"resp3-available"));
+        assert!(!ids.contains(&"multipart-aof"));
+    }
+
+    #[test]
+    fn test_advise_same_version_only_current_state() {
+        let info = map(&[("redis_version", "7.2.4")]);
+        let report = advise(&info, &HashMap::new(), "7.2.4");
+        assert!(report.advisories.is_empty());
+    }
+
+    #[test]
+    fn test_parse_info_map() {
+        let info = parse_info_map("# Server\r\nredis_version:7.2.4\r\n\r\n");
+        assert_eq!(info.get("redis_version").unwrap(), "7.2.4");
+    }
+}
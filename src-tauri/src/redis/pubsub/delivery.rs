@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::model::PubSubMessage;
+use crate::utils::errors::AppError;
+
+/// Length of the window `max_per_sec` is counted over.
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// How a subscription's messages are delivered to the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeliveryOptions {
+    /// Buffered messages are emitted as one batch this often.
+    pub flush_interval_ms: u64,
+    /// Messages delivered per second at most; the rest stay buffered.
+    pub max_per_sec: u32,
+    /// Messages held at most (while paused or over the rate); the oldest are
+    /// dropped beyond this.
+    pub max_buffered: usize,
+}
+
+impl Default for DeliveryOptions {
+    fn default() -> Self {
+        Self {
+            flush_interval_ms: 100,
+            max_per_sec: 1000,
+            max_buffered: 10_000,
+        }
+    }
+}
+
+impl DeliveryOptions {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(10..=10_000).contains(&self.flush_interval_ms) {
+            return Err(AppError::InvalidInput(
+                "Flush interval must be between 10 and 10000 ms".into(),
+            ));
+        }
+        if self.max_per_sec == 0 || self.max_buffered == 0 {
+            return Err(AppError::InvalidInput(
+                "Message rate and buffer size must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn flush_interval(&self) -> Duration {
+        Duration::from_millis(self.flush_interval_ms)
+    }
+}
+
+/// Payload of `pubsub:messages`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PubSubBatch {
+    pub subscription_id: String,
+    pub messages: Vec<PubSubMessage>,
+    /// Messages dropped since the previous batch.
+    pub dropped: u64,
+    /// Messages dropped since the subscription started.
+    pub total_dropped: u64,
+    /// Messages still buffered after this batch.
+    pub buffered: usize,
+}
+
+/// Buffer between a subscription's connection and the frontend.
+#[derive(Debug)]
+pub struct Delivery {
+    options: DeliveryOptions,
+    paused: bool,
+    buffer: VecDeque<PubSubMessage>,
+    window_start: Instant,
+    sent_in_window: u32,
+    dropped: u64,
+    total_dropped: u64,
+}
+
+impl Delivery {
+    pub fn new(options: DeliveryOptions) -> Self {
+        Self {
+            options,
+            paused: false,
+            buffer: VecDeque::new(),
+            window_start: Instant::now(),
+            sent_in_window: 0,
+            dropped: 0,
+            total_dropped: 0,
+        }
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Queue a received message, dropping the oldest if the buffer is full.
+    pub fn push(&mut self, message: PubSubMessage) {
+        self.buffer.push_back(message);
+        if self.buffer.len() > self.options.max_buffered {
+            self.buffer.pop_front();
+            self.dropped += 1;
+            self.total_dropped += 1;
+        }
+    }
+
+    /// The next batch to emit, or `None` if paused or there is nothing to
+    /// report.
+    pub fn take_batch(&mut self, subscription_id: &str, now: Instant) -> Option<PubSubBatch> {
+        if self.paused {
+            return None;
+        }
+        if now.duration_since(self.window_start) >= RATE_WINDOW {
+            self.window_start = now;
+            self.sent_in_window = 0;
+        }
+        let allowance = self.options.max_per_sec - self.sent_in_window;
+        let count = self.buffer.len().min(allowance as usize);
+        if count == 0 && self.dropped == 0 {
+            return None;
+        }
+        self.sent_in_window += u32::try_from(count).unwrap_or(allowance);
+        Some(PubSubBatch {
+            subscription_id: subscription_id.to_string(),
+            messages: self.buffer.drain(..count).collect(),
+            dropped: std::mem::take(&mut self.dropped),
+            total_dropped: self.total_dropped,
+            buffered: self.buffer.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(n: u8) -> PubSubMessage {
+        PubSubMessage::new("s".into(), "c".into(), None, &[b'0' + n], 0)
+    }
+
+    fn options(max_per_sec: u32, max_buffered: usize) -> DeliveryOptions {
+        DeliveryOptions {
+            flush_interval_ms: 100,
+            max_per_sec,
+            max_buffered,
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_keeps_excess_buffered() {
+        let mut delivery = Delivery::new(options(2, 10));
+        for n in 0..5 {
+            delivery.push(message(n));
+        }
+        let now = Instant::now();
+        let batch = delivery.take_batch("s", now).unwrap();
+        assert_eq!(batch.messages.len(), 2);
+        assert_eq!(batch.buffered, 3);
+        assert!(delivery.take_batch("s", now).is_none());
+
+        let later = delivery.take_batch("s", now + RATE_WINDOW).unwrap();
+        assert_eq!(later.messages[0].payload.as_deref(), Some("2"));
+        assert_eq!(later.dropped, 0);
+    }
+
+    #[test]
+    fn test_pause_buffers_and_counts_drops() {
+        let mut delivery = Delivery::new(options(100, 2));
+        delivery.set_paused(true);
+        for n in 0..5 {
+            delivery.push(message(n));
+        }
+        assert!(delivery.take_batch("s", Instant::now()).is_none());
+
+        delivery.set_paused(false);
+        let batch = delivery.take_batch("s", Instant::now()).unwrap();
+        let payloads: Vec<_> = batch
+            .messages
+            .iter()
+            .map(|m| m.payload.as_deref())
+            .collect();
+        assert_eq!(payloads, [Some("3"), Some("4")]);
+        assert_eq!((batch.dropped, batch.total_dropped), (3, 3));
+    }
+
+    #[test]
+    fn test_validate_options() {
+        assert!(DeliveryOptions::default().validate().is_ok());
+        assert!(options(0, 1).validate().is_err());
+        let fast = DeliveryOptions {
+            flush_interval_ms: 1,
+            ..DeliveryOptions::default()
+        };
+        assert!(fast.validate().is_err());
+    }
+}
@@ -58,6 +58,6 @@ pub async fn get_active_channels(
 
 #[cfg(test)]
 mod tests {
-    // Discovery tests require a live Redis connection.
-    // These will be integration tests with testcontainers.
+    // Discovery tests require a live Redis connection; see the
+    // `integration-tests` feature suite under tests/integration.
 }
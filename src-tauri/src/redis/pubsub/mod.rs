@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: MIT
 
+pub mod delivery;
 pub mod discovery;
 pub mod model;
+pub mod recording;
 pub mod subscriber;
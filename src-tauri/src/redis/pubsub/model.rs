@@ -1,18 +1,78 @@
 // SPDX-License-Identifier: MIT
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::AppError;
 
 /// A message received from a Pub/Sub subscription.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Text payloads are carried in `payload`; payloads that aren't UTF-8 or
+/// contain control characters are carried base64-encoded in `payload_base64`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PubSubMessage {
     pub subscription_id: String,
     pub channel: String,
     pub pattern: Option<String>,
-    pub payload: String,
+    /// The payload as text (if it is printable UTF-8).
+    pub payload: Option<String>,
+    /// Base64-encoded payload (if binary).
+    #[serde(default)]
+    pub payload_base64: Option<String>,
+    /// Size of the payload in bytes.
+    #[serde(default)]
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub is_binary: bool,
     pub timestamp_ms: i64,
 }
 
+impl PubSubMessage {
+    /// Build a message from a raw payload, detecting binary content.
+    pub fn new(
+        subscription_id: String,
+        channel: String,
+        pattern: Option<String>,
+        payload: &[u8],
+        timestamp_ms: i64,
+    ) -> Self {
+        use base64::Engine;
+        let text = std::str::from_utf8(payload).ok().filter(|s| {
+            !s.bytes()
+                .any(|b| b < 32 && b != b'\n' && b != b'\r' && b != b'\t')
+        });
+        Self {
+            subscription_id,
+            channel,
+            pattern,
+            payload_base64: text
+                .is_none()
+                .then(|| base64::engine::general_purpose::STANDARD.encode(payload)),
+            payload: text.map(String::from),
+            size_bytes: payload.len() as u64,
+            is_binary: text.is_none(),
+            timestamp_ms,
+        }
+    }
+
+    /// The original payload bytes.
+    pub fn payload_bytes(&self) -> Result<Vec<u8>, AppError> {
+        match (&self.payload, &self.payload_base64) {
+            (_, Some(encoded)) => decode_base64(encoded),
+            (Some(text), None) => Ok(text.clone().into_bytes()),
+            (None, None) => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Decode a base64 payload supplied by the frontend.
+pub fn decode_base64(encoded: &str) -> Result<Vec<u8>, AppError> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid base64 payload: {e}")))
+}
+
 /// Info about an active channel from PUBSUB CHANNELS + NUMSUB.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -20,3 +80,24 @@ pub struct ChannelInfo {
     pub name: String,
     pub subscribers: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_detects_binary_payload() {
+        let text = PubSubMessage::new("s".into(), "c".into(), None, b"hello\n", 1);
+        assert_eq!(text.payload.as_deref(), Some("hello\n"));
+        assert!(!text.is_binary);
+        assert_eq!(text.payload_bytes().unwrap(), b"hello\n");
+
+        for raw in [&b"\xff\xfe"[..], b"a\x00b"] {
+            let binary = PubSubMessage::new("s".into(), "c".into(), None, raw, 1);
+            assert!(binary.is_binary);
+            assert!(binary.payload.is_none());
+            assert_eq!(binary.size_bytes, raw.len() as u64);
+            assert_eq!(binary.payload_bytes().unwrap(), raw);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+
+use super::model::PubSubMessage;
+use crate::utils::errors::AppError;
+
+/// Size at which a recording continues in a new part file.
+const MAX_PART_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Summary of a recording session.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingInfo {
+    pub id: String,
+    pub subscription_id: String,
+    pub started_at_ms: i64,
+    pub messages: u64,
+    pub bytes: u64,
+    /// Number of part files written so far.
+    pub parts: u32,
+}
+
+/// Resolve the directory recordings are written to.
+pub fn recordings_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {e}")))?;
+    Ok(dir.join("pubsub_recordings"))
+}
+
+/// Part `n` of recording `id`: `<id>.jsonl`, then `<id>.1.jsonl`, ...
+fn part_path(dir: &Path, id: &str, part: u32) -> Result<PathBuf, AppError> {
+    // Recording IDs are UUIDs; anything else could escape the directory.
+    uuid::Uuid::parse_str(id)?;
+    Ok(match part {
+        0 => dir.join(format!("{id}.jsonl")),
+        n => dir.join(format!("{id}.{n}.jsonl")),
+    })
+}
+
+/// Appends a subscription's messages to newline-delimited JSON files,
+/// starting a new part file every [`MAX_PART_BYTES`].
+pub struct Recorder {
+    dir: PathBuf,
+    file: tokio::fs::File,
+    part_bytes: u64,
+    info: RecordingInfo,
+}
+
+impl Recorder {
+    /// Start a new recording in `dir`.
+    pub async fn create(dir: PathBuf, subscription_id: &str) -> Result<Self, AppError> {
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create recordings dir: {e}")))?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let file = open_part(&part_path(&dir, &id, 0)?).await?;
+        Ok(Self {
+            dir,
+            file,
+            part_bytes: 0,
+            info: RecordingInfo {
+                id,
+                subscription_id: subscription_id.to_string(),
+                started_at_ms: chrono::Utc::now().timestamp_millis(),
+                messages: 0,
+                bytes: 0,
+                parts: 1,
+            },
+        })
+    }
+
+    pub fn info(&self) -> &RecordingInfo {
+        &self.info
+    }
+
+    /// Append one message.
+    pub async fn append(&mut self, message: &PubSubMessage) -> Result<(), AppError> {
+        let mut line = serde_json::to_string(message)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize message: {e}")))?;
+        line.push('\n');
+
+        if self.part_bytes > 0 && self.part_bytes + line.len() as u64 > MAX_PART_BYTES {
+            self.file.flush().await.ok();
+            self.file = open_part(&part_path(&self.dir, &self.info.id, self.info.parts)?).await?;
+            self.info.parts += 1;
+            self.part_bytes = 0;
+        }
+
+        self.file
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write recording: {e}")))?;
+        self.file
+            .flush()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write recording: {e}")))?;
+        self.part_bytes += line.len() as u64;
+        self.info.bytes += line.len() as u64;
+        self.info.messages += 1;
+        Ok(())
+    }
+}
+
+async fn open_part(path: &Path) -> Result<tokio::fs::File, AppError> {
+    tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to open recording: {e}")))
+}
+
+/// Read every part of a recording in order, skipping lines that don't parse
+/// (e.g. a torn final write).
+pub async fn read_recording(dir: &Path, id: &str) -> Result<Vec<PubSubMessage>, AppError> {
+    let mut messages = Vec::new();
+    for part in 0.. {
+        let path = part_path(dir, id, part)?;
+        if !path.exists() {
+            if part == 0 {
+                return Err(AppError::NotFound(format!("Recording {id} not found")));
+            }
+            break;
+        }
+        let data = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read recording: {e}")))?;
+        messages.extend(
+            data.lines()
+                .filter_map(|line| serde_json::from_str::<PubSubMessage>(line).ok()),
+        );
+    }
+    Ok(messages)
+}
+
+/// Wait before each message so a replay keeps the recorded spacing,
+/// divided by `speed`. The first message is sent immediately; a wait too
+/// long to represent (a tiny `speed`) saturates at `Duration::MAX`.
+pub fn replay_delays(messages: &[PubSubMessage], speed: f64) -> Vec<Duration> {
+    let mut previous = messages.first().map(|m| m.timestamp_ms);
+    messages
+        .iter()
+        .map(|m| {
+            let gap = previous.map_or(0, |p| (m.timestamp_ms - p).max(0));
+            previous = Some(m.timestamp_ms);
+            #[allow(clippy::cast_precision_loss)]
+            Duration::try_from_secs_f64(gap as f64 / 1000.0 / speed).unwrap_or(Duration::MAX)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(payload: &str, timestamp_ms: i64) -> PubSubMessage {
+        PubSubMessage::new(
+            "sub".into(),
+            "news".into(),
+            None,
+            payload.as_bytes(),
+            timestamp_ms,
+        )
+    }
+
+    #[test]
+    fn test_replay_delays() {
+        let messages = [message("a", 1000), message("b", 1500), message("c", 1400)];
+        assert_eq!(
+            replay_delays(&messages, 1.0),
+            [Duration::ZERO, Duration::from_millis(500), Duration::ZERO]
+        );
+        assert_eq!(replay_delays(&messages, 10.0)[1], Duration::from_millis(50));
+        assert_eq!(replay_delays(&messages, 1e-300)[1], Duration::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_record_and_read_back() {
+        let dir = std::env::temp_dir().join(format!("redis-lens-rec-{}", uuid::Uuid::new_v4()));
+        let mut recorder = Recorder::create(dir.clone(), "sub").await.unwrap();
+        recorder.append(&message("hello", 1)).await.unwrap();
+        recorder.append(&message("world", 2)).await.unwrap();
+        let id = recorder.info().id.clone();
+        assert_eq!(recorder.info().messages, 2);
+
+        let read = read_recording(&dir, &id).await.unwrap();
+        let payloads: Vec<_> = read.iter().map(|m| m.payload.as_deref()).collect();
+        assert_eq!(payloads, [Some("hello"), Some("world")]);
+        assert!(read_recording(&dir, "../x").await.is_err());
+
+        tokio::fs::remove_dir_all(dir).await.unwrap();
+    }
+}
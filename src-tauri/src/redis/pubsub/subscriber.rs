@@ -5,10 +5,12 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tauri::{AppHandle, Emitter};
-use tokio::sync::RwLock;
-use tokio::task::JoinHandle;
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::{AbortHandle, JoinHandle};
 
+use super::delivery::{Delivery, DeliveryOptions};
 use super::model::PubSubMessage;
+use super::recording::{self, Recorder, RecordingInfo};
 use crate::utils::errors::AppError;
 
 /// Tracks a single active subscription.
@@ -19,6 +21,18 @@ struct ActiveSubscription {
     #[allow(dead_code)]
     patterns: Vec<String>,
     task_handle: JoinHandle<()>,
+    /// Set while the subscription's messages are being recorded.
+    recording: Arc<Mutex<Option<Recorder>>>,
+    delivery: Arc<Mutex<Delivery>>,
+}
+
+/// Payload of `pubsub:replay-finished`.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReplayFinished {
+    replay_id: String,
+    published: u64,
+    error: Option<String>,
 }
 
 /// Manages all active Pub/Sub subscriptions.
@@ -27,6 +41,8 @@ struct ActiveSubscription {
 /// because subscriber mode locks the connection.
 pub struct PubSubManager {
     subscriptions: Arc<RwLock<HashMap<String, ActiveSubscription>>>,
+    /// Running replays, by replay ID.
+    replays: Arc<RwLock<HashMap<String, AbortHandle>>>,
 }
 
 impl Default for PubSubManager {
@@ -39,15 +55,20 @@ impl PubSubManager {
     pub fn new() -> Self {
         Self {
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            replays: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
     /// Subscribe to literal channel names.
+    ///
+    /// Messages are buffered and emitted as batches on `pubsub:messages`
+    /// according to `options`.
     pub async fn subscribe(
         &self,
         connection_id: String,
         connection_url: String,
         channels: Vec<String>,
+        options: DeliveryOptions,
         app: AppHandle,
     ) -> Result<String, AppError> {
         let sub_id = uuid::Uuid::new_v4().to_string();
@@ -68,30 +89,24 @@ impl PubSubManager {
                 .map_err(|e| AppError::Redis(format!("Subscribe failed: {e}")))?;
         }
 
-        let sub_id_clone = sub_id.clone();
-        let task_handle = tokio::spawn(async move {
-            let mut stream = pubsub.on_message();
-            while let Some(msg) = futures::StreamExt::next(&mut stream).await {
-                let channel: String = msg.get_channel_name().to_string();
-                let payload: String = msg.get_payload().unwrap_or_default();
-
-                let ps_msg = PubSubMessage {
-                    subscription_id: sub_id_clone.clone(),
-                    channel,
-                    pattern: None,
-                    payload,
-                    timestamp_ms: chrono::Utc::now().timestamp_millis(),
-                };
-
-                let _ = app.emit("pubsub:message", &ps_msg);
-            }
-        });
+        let recording = Arc::new(Mutex::new(None));
+        let delivery = Arc::new(Mutex::new(Delivery::new(options)));
+        let task_handle = spawn_listener(
+            pubsub,
+            sub_id.clone(),
+            options,
+            app,
+            Arc::clone(&recording),
+            Arc::clone(&delivery),
+        );
 
         let active = ActiveSubscription {
             connection_id,
             channels: channels.clone(),
             patterns: Vec::new(),
             task_handle,
+            recording,
+            delivery,
         };
 
         self.subscriptions
@@ -109,6 +124,7 @@ impl PubSubManager {
         connection_id: String,
         connection_url: String,
         patterns: Vec<String>,
+        options: DeliveryOptions,
         app: AppHandle,
     ) -> Result<String, AppError> {
         let sub_id = uuid::Uuid::new_v4().to_string();
@@ -128,33 +144,24 @@ impl PubSubManager {
                 .map_err(|e| AppError::Redis(format!("Pattern subscribe failed: {e}")))?;
         }
 
-        let sub_id_clone = sub_id.clone();
-        let patterns_clone = patterns.clone();
-        let task_handle = tokio::spawn(async move {
-            let mut stream = pubsub.on_message();
-            while let Some(msg) = futures::StreamExt::next(&mut stream).await {
-                let channel: String = msg.get_channel_name().to_string();
-                let payload: String = msg.get_payload().unwrap_or_default();
-                let pattern: Option<String> = msg.get_pattern().ok();
-
-                let ps_msg = PubSubMessage {
-                    subscription_id: sub_id_clone.clone(),
-                    channel,
-                    pattern,
-                    payload,
-                    timestamp_ms: chrono::Utc::now().timestamp_millis(),
-                };
-
-                let _ = app.emit("pubsub:message", &ps_msg);
-            }
-            drop(patterns_clone);
-        });
+        let recording = Arc::new(Mutex::new(None));
+        let delivery = Arc::new(Mutex::new(Delivery::new(options)));
+        let task_handle = spawn_listener(
+            pubsub,
+            sub_id.clone(),
+            options,
+            app,
+            Arc::clone(&recording),
+            Arc::clone(&delivery),
+        );
 
         let active = ActiveSubscription {
             connection_id,
             channels: Vec::new(),
             patterns: patterns.clone(),
             task_handle,
+            recording,
+            delivery,
         };
 
         self.subscriptions
@@ -180,6 +187,123 @@ impl PubSubManager {
         }
     }
 
+    /// Stop or restart delivery of a subscription's messages to the frontend.
+    ///
+    /// While paused, messages keep being received (and recorded) and are
+    /// buffered up to the subscription's `max_buffered`.
+    pub async fn set_paused(&self, subscription_id: &str, paused: bool) -> Result<(), AppError> {
+        let delivery = self
+            .subscriptions
+            .read()
+            .await
+            .get(subscription_id)
+            .map(|s| Arc::clone(&s.delivery))
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Subscription {subscription_id} not found"))
+            })?;
+        delivery.lock().await.set_paused(paused);
+        Ok(())
+    }
+
+    /// Start recording a subscription's messages to a new recording file.
+    pub async fn start_recording(
+        &self,
+        subscription_id: &str,
+        dir: std::path::PathBuf,
+    ) -> Result<RecordingInfo, AppError> {
+        let slot = self.recording_slot(subscription_id).await?;
+        let mut recording = slot.lock().await;
+        if let Some(active) = recording.as_ref() {
+            return Err(AppError::InvalidInput(format!(
+                "Subscription {subscription_id} is already being recorded ({})",
+                active.info().id
+            )));
+        }
+        let recorder = Recorder::create(dir, subscription_id).await?;
+        let info = recorder.info().clone();
+        *recording = Some(recorder);
+        tracing::info!(sub_id = %subscription_id, recording_id = %info.id, "Recording started");
+        Ok(info)
+    }
+
+    /// Stop recording a subscription; returns the finished recording's summary.
+    pub async fn stop_recording(&self, subscription_id: &str) -> Result<RecordingInfo, AppError> {
+        let slot = self.recording_slot(subscription_id).await?;
+        let recorder = slot.lock().await.take().ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Subscription {subscription_id} is not being recorded"
+            ))
+        })?;
+        tracing::info!(sub_id = %subscription_id, recording_id = %recorder.info().id, "Recording stopped");
+        Ok(recorder.info().clone())
+    }
+
+    async fn recording_slot(
+        &self,
+        subscription_id: &str,
+    ) -> Result<Arc<Mutex<Option<Recorder>>>, AppError> {
+        self.subscriptions
+            .read()
+            .await
+            .get(subscription_id)
+            .map(|s| Arc::clone(&s.recording))
+            .ok_or_else(|| AppError::NotFound(format!("Subscription {subscription_id} not found")))
+    }
+
+    /// Republish a recording through `pool` in the background, keeping the
+    /// recorded spacing divided by `speed`. Returns a replay ID; completion
+    /// is reported on `pubsub:replay-finished`.
+    pub async fn start_replay(
+        &self,
+        pool: deadpool_redis::Pool,
+        messages: Vec<PubSubMessage>,
+        speed: f64,
+        app: AppHandle,
+    ) -> String {
+        let replay_id = uuid::Uuid::new_v4().to_string();
+        let replays = Arc::clone(&self.replays);
+        // Hold the lock until the handle is registered so a fast replay
+        // can't try to deregister itself first.
+        let mut running = self.replays.write().await;
+
+        let id = replay_id.clone();
+        let task = tokio::spawn(async move {
+            let delays = recording::replay_delays(&messages, speed);
+            let mut published = 0;
+            let mut error = None;
+            for (message, delay) in messages.iter().zip(delays) {
+                tokio::time::sleep(delay).await;
+                if let Err(e) = publish(&pool, message).await {
+                    error = Some(e.to_string());
+                    break;
+                }
+                published += 1;
+            }
+            replays.write().await.remove(&id);
+            let _ = app.emit(
+                "pubsub:replay-finished",
+                &ReplayFinished {
+                    replay_id: id,
+                    published,
+                    error,
+                },
+            );
+        });
+        running.insert(replay_id.clone(), task.abort_handle());
+        replay_id
+    }
+
+    /// Stop a running replay. Returns `false` if it already finished.
+    pub async fn stop_replay(&self, replay_id: &str) -> bool {
+        match self.replays.write().await.remove(replay_id) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Tear down all subscriptions for a given connection.
     pub async fn disconnect_all(&self, connection_id: &str) {
         let mut subs = self.subscriptions.write().await;
@@ -197,4 +321,82 @@ impl PubSubManager {
             tracing::info!(connection_id = %connection_id, count = to_remove.len(), "PubSub subscriptions cleaned up");
         }
     }
+
+    /// Tear down every subscription and replay (e.g., on app shutdown).
+    pub async fn shutdown(&self) {
+        let mut subs = self.subscriptions.write().await;
+        let count = subs.len();
+        for (_, active) in subs.drain() {
+            active.task_handle.abort();
+        }
+        for (_, handle) in self.replays.write().await.drain() {
+            handle.abort();
+        }
+        if count > 0 {
+            tracing::info!(count = count, "PubSub subscriptions cleaned up (shutdown)");
+        }
+    }
+}
+
+/// Forward a subscription's messages to the frontend (and its recording,
+/// if one is running) until the connection closes or the task is aborted.
+fn spawn_listener(
+    mut pubsub: redis::aio::PubSub,
+    sub_id: String,
+    options: DeliveryOptions,
+    app: AppHandle,
+    recording: Arc<Mutex<Option<Recorder>>>,
+    delivery: Arc<Mutex<Delivery>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut stream = pubsub.on_message();
+        let mut flush = tokio::time::interval(options.flush_interval());
+        flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                msg = futures::StreamExt::next(&mut stream) => {
+                    let Some(msg) = msg else { break };
+                    let ps_msg = PubSubMessage::new(
+                        sub_id.clone(),
+                        msg.get_channel_name().to_string(),
+                        msg.get_pattern().ok(),
+                        msg.get_payload_bytes(),
+                        chrono::Utc::now().timestamp_millis(),
+                    );
+
+                    if let Some(recorder) = recording.lock().await.as_mut() {
+                        if let Err(e) = recorder.append(&ps_msg).await {
+                            tracing::warn!(sub_id = %sub_id, "Failed to record message: {e}");
+                        }
+                    }
+                    delivery.lock().await.push(ps_msg);
+                }
+                _ = flush.tick() => flush_delivery(&app, &sub_id, &delivery).await,
+            }
+        }
+        flush_delivery(&app, &sub_id, &delivery).await;
+    })
+}
+
+async fn flush_delivery(app: &AppHandle, sub_id: &str, delivery: &Mutex<Delivery>) {
+    let batch = delivery
+        .lock()
+        .await
+        .take_batch(sub_id, std::time::Instant::now());
+    if let Some(batch) = batch {
+        if let Err(e) = app.emit("pubsub:messages", &batch) {
+            tracing::warn!(sub_id = %sub_id, "Failed to emit Pub/Sub messages: {e}");
+        }
+    }
+}
+
+async fn publish(pool: &deadpool_redis::Pool, message: &PubSubMessage) -> Result<(), AppError> {
+    let payload = message.payload_bytes()?;
+    let mut conn = pool.get().await?;
+    redis::cmd("PUBLISH")
+        .arg(&message.channel)
+        .arg(payload)
+        .query_async::<()>(&mut conn)
+        .await?;
+    Ok(())
 }
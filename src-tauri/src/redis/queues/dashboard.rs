@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeSet;
+
+use deadpool_redis::Pool;
+use redis::Value;
+
+use super::model::{QueueDashboard, QueueFramework, QueueStats, SidekiqTotals};
+use crate::redis::browser::scanner;
+use crate::utils::errors::AppError;
+use crate::utils::glob;
+
+/// Queue names probed from Bull's `<prefix>:<queue>:meta` (`BullMQ`) and
+/// `<prefix>:<queue>:id` (both) keys.
+const BULL_MARKERS: &[&str] = &["meta", "id"];
+
+/// Read every Sidekiq queue and every Bull queue under `bull_prefix`.
+pub async fn dashboard(pool: &Pool, bull_prefix: &str) -> Result<QueueDashboard, AppError> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let mut dashboard = QueueDashboard {
+        generated_at_ms: now_ms,
+        ..QueueDashboard::default()
+    };
+    if let Some((queues, totals)) = sidekiq(pool, now_ms).await? {
+        dashboard.queues.extend(queues);
+        dashboard.sidekiq = Some(totals);
+    }
+    dashboard
+        .queues
+        .extend(bull(pool, bull_prefix, now_ms).await?);
+    dashboard
+        .queues
+        .sort_by(|a, b| (a.framework, &a.name).cmp(&(b.framework, &b.name)));
+    Ok(dashboard)
+}
+
+/// Sidekiq keeps its queue names in the `queues` set; each queue is a list
+/// pushed on the left and worked from the right, so the oldest job is last.
+async fn sidekiq(
+    pool: &Pool,
+    now_ms: i64,
+) -> Result<Option<(Vec<QueueStats>, SidekiqTotals)>, AppError> {
+    let mut conn = pool.get().await?;
+    #[allow(clippy::type_complexity)]
+    let (names, scheduled, retry, dead, processed, failed): (
+        Vec<String>,
+        u64,
+        u64,
+        u64,
+        Option<u64>,
+        Option<u64>,
+    ) = redis::pipe()
+        .cmd("SMEMBERS")
+        .arg("queues")
+        .cmd("ZCARD")
+        .arg("schedule")
+        .cmd("ZCARD")
+        .arg("retry")
+        .cmd("ZCARD")
+        .arg("dead")
+        .cmd("GET")
+        .arg("stat:processed")
+        .cmd("GET")
+        .arg("stat:failed")
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Reading Sidekiq data failed: {e}")))?;
+    if names.is_empty() && processed.is_none() && scheduled + retry + dead == 0 {
+        return Ok(None);
+    }
+    let totals = SidekiqTotals {
+        scheduled,
+        retry,
+        dead,
+        processed: processed.unwrap_or(0),
+        failed: failed.unwrap_or(0),
+    };
+    if names.is_empty() {
+        return Ok(Some((Vec::new(), totals)));
+    }
+
+    let mut pipe = redis::pipe();
+    for name in &names {
+        let key = format!("queue:{name}");
+        pipe.cmd("LLEN")
+            .arg(&key)
+            .cmd("LINDEX")
+            .arg(&key)
+            .arg(-1)
+            .cmd("SISMEMBER")
+            .arg("paused")
+            .arg(name);
+    }
+    let replies: Vec<(u64, Option<String>, bool)> = pipe
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Reading Sidekiq queues failed: {e}")))?;
+    let queues = names
+        .into_iter()
+        .zip(replies)
+        .map(|(name, (waiting, oldest, paused))| QueueStats {
+            framework: QueueFramework::Sidekiq,
+            name,
+            waiting,
+            active: None,
+            delayed: None,
+            failed: None,
+            completed: None,
+            oldest_job_age_ms: oldest
+                .and_then(|job| serde_json::from_str::<serde_json::Value>(&job).ok())
+                .and_then(|job| epoch_ms(job.get("enqueued_at")?))
+                .map(|at| (now_ms - at).max(0)),
+            paused,
+        })
+        .collect();
+    Ok(Some((queues, totals)))
+}
+
+/// Bull queues live under `<prefix>:<queue>:`: `wait`, `active` and
+/// `paused` lists of job IDs (pushed left, worked from the right),
+/// `delayed`, `prioritized`, `completed` and `failed` sorted sets, and one
+/// hash per job with its `timestamp` in milliseconds.
+async fn bull(pool: &Pool, prefix: &str, now_ms: i64) -> Result<Vec<QueueStats>, AppError> {
+    let mut conn = pool.get().await?;
+    let mut names = BTreeSet::new();
+    for marker in BULL_MARKERS {
+        let pattern = format!("{}:*:{marker}", glob::escape(prefix));
+        let mut cursor = 0;
+        loop {
+            let (next, keys) = scanner::scan_match(&mut conn, cursor, &pattern).await?;
+            names.extend(keys.iter().filter_map(|key| {
+                key.strip_prefix(prefix)?
+                    .strip_prefix(':')?
+                    .strip_suffix(marker)?
+                    .strip_suffix(':')
+                    .map(str::to_string)
+            }));
+            cursor = next;
+            if cursor == 0 {
+                break;
+            }
+        }
+    }
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut pipe = redis::pipe();
+    for name in &names {
+        let base = format!("{prefix}:{name}");
+        for list in ["wait", "paused", "active"] {
+            pipe.cmd("LLEN").arg(format!("{base}:{list}"));
+        }
+        for set in ["prioritized", "delayed", "failed", "completed"] {
+            pipe.cmd("ZCARD").arg(format!("{base}:{set}"));
+        }
+        pipe.cmd("HEXISTS")
+            .arg(format!("{base}:meta"))
+            .arg("paused")
+            .cmd("EXISTS")
+            .arg(format!("{base}:meta-paused"))
+            .cmd("LINDEX")
+            .arg(format!("{base}:wait"))
+            .arg(-1)
+            .cmd("LINDEX")
+            .arg(format!("{base}:paused"))
+            .arg(-1);
+    }
+    let replies: Vec<Value> = pipe
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Reading Bull queues failed: {e}")))?;
+
+    let mut stats = Vec::with_capacity(names.len());
+    let mut oldest_ids = Vec::new();
+    for (name, reply) in names.into_iter().zip(replies.chunks(BULL_REPLIES)) {
+        let (queue, oldest_id) = bull_stats_from_reply(name, reply)?;
+        oldest_ids.push(oldest_id.map(|id| format!("{prefix}:{}:{id}", queue.name)));
+        stats.push(queue);
+    }
+
+    let mut pipe = redis::pipe();
+    for job_key in oldest_ids.iter().flatten() {
+        pipe.cmd("HGET").arg(job_key).arg("timestamp");
+    }
+    if oldest_ids.iter().any(Option::is_some) {
+        let timestamps: Vec<Option<i64>> = pipe
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("Reading Bull jobs failed: {e}")))?;
+        let mut timestamps = timestamps.into_iter();
+        for (queue, job_key) in stats.iter_mut().zip(&oldest_ids) {
+            if job_key.is_some() {
+                queue.oldest_job_age_ms =
+                    timestamps.next().flatten().map(|at| (now_ms - at).max(0));
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Replies per queue in the Bull pipeline.
+const BULL_REPLIES: usize = 11;
+
+/// Parse one queue's replies; also returns the ID of its oldest waiting job.
+fn bull_stats_from_reply(
+    name: String,
+    reply: &[Value],
+) -> Result<(QueueStats, Option<String>), AppError> {
+    let [wait, paused_len, active, prioritized, delayed, failed, completed, meta_paused, legacy_paused, oldest_waiting, oldest_paused] =
+        reply
+    else {
+        return Err(AppError::Redis("Bad Bull reply: wrong length".into()));
+    };
+    let oldest_id = parse::<Option<String>>(oldest_waiting)?.or(parse(oldest_paused)?);
+    let stats = QueueStats {
+        framework: QueueFramework::Bull,
+        name,
+        waiting: parse::<u64>(wait)? + parse::<u64>(paused_len)? + parse::<u64>(prioritized)?,
+        active: Some(parse(active)?),
+        delayed: Some(parse(delayed)?),
+        failed: Some(parse(failed)?),
+        completed: Some(parse(completed)?),
+        oldest_job_age_ms: None,
+        paused: parse::<bool>(meta_paused)? || parse::<bool>(legacy_paused)?,
+    };
+    Ok((stats, oldest_id))
+}
+
+fn parse<T: redis::FromRedisValue>(value: &Value) -> Result<T, AppError> {
+    redis::from_redis_value(value).map_err(|e| AppError::Redis(format!("Bad Bull reply: {e}")))
+}
+
+/// A job timestamp in Unix milliseconds. Sidekiq wrote float seconds
+/// before 8.0 and integer milliseconds since.
+#[allow(clippy::cast_possible_truncation)]
+fn epoch_ms(value: &serde_json::Value) -> Option<i64> {
+    let n = value.as_f64()?;
+    Some(if n > 1e11 {
+        n as i64
+    } else {
+        (n * 1000.0) as i64
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_epoch_ms_accepts_seconds_and_millis() {
+        assert_eq!(epoch_ms(&json!(1_700_000_000.5)), Some(1_700_000_000_500));
+        assert_eq!(
+            epoch_ms(&json!(1_700_000_000_500_i64)),
+            Some(1_700_000_000_500)
+        );
+        assert_eq!(epoch_ms(&json!("soon")), None);
+    }
+
+    #[test]
+    fn test_bull_stats_from_reply() {
+        use redis::Value as V;
+        let reply = [
+            V::Int(3),
+            V::Int(0),
+            V::Int(1),
+            V::Int(0),
+            V::Int(2),
+            V::Int(5),
+            V::Int(10),
+            V::Int(1),
+            V::Int(0),
+            V::BulkString(b"42".to_vec()),
+            V::Nil,
+        ];
+        let (stats, oldest) = bull_stats_from_reply("mail".into(), &reply).unwrap();
+        assert_eq!(stats.waiting, 3);
+        assert_eq!(stats.active, Some(1));
+        assert_eq!(stats.delayed, Some(2));
+        assert_eq!(stats.failed, Some(5));
+        assert_eq!(stats.completed, Some(10));
+        assert!(stats.paused);
+        assert_eq!(oldest.as_deref(), Some("42"));
+
+        assert!(bull_stats_from_reply("mail".into(), &reply[..3]).is_err());
+    }
+}
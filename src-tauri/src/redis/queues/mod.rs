@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: MIT
+
+//! Job queue dashboards for Sidekiq and Bull/BullMQ: depths, oldest job
+//! age and failure counts read from their lists and sorted sets, and job
+//! moves that are safe while workers are running or the queue is paused.
+
+pub mod dashboard;
+pub mod model;
+pub mod ops;
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::AppError;
+
+/// Key prefix Bull and `BullMQ` use unless configured otherwise.
+pub const DEFAULT_BULL_PREFIX: &str = "bull";
+
+/// A job queue library whose layout the dashboard understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueueFramework {
+    Sidekiq,
+    Bull,
+}
+
+/// One queue's figures.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueStats {
+    pub framework: QueueFramework,
+    pub name: String,
+    /// Jobs waiting to be picked up (Bull: wait, paused and prioritized).
+    pub waiting: u64,
+    /// Bull only: jobs being processed.
+    pub active: Option<u64>,
+    /// Bull only: jobs waiting for their delay. Sidekiq's scheduled set is
+    /// shared by all queues; see [`SidekiqTotals`].
+    pub delayed: Option<u64>,
+    /// Bull only: failed jobs kept in the queue.
+    pub failed: Option<u64>,
+    /// Bull only: completed jobs kept in the queue.
+    pub completed: Option<u64>,
+    /// Time since the next job to run was enqueued.
+    pub oldest_job_age_ms: Option<i64>,
+    pub paused: bool,
+}
+
+/// Sidekiq's sets and counters shared by all its queues.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidekiqTotals {
+    pub scheduled: u64,
+    /// Failed jobs waiting to be retried.
+    pub retry: u64,
+    /// Jobs that ran out of retries.
+    pub dead: u64,
+    /// `stat:processed` and `stat:failed`, all-time counters.
+    pub processed: u64,
+    pub failed: u64,
+}
+
+/// The job queues found on a connection.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueDashboard {
+    /// Sorted by framework, then name.
+    pub queues: Vec<QueueStats>,
+    /// Present when Sidekiq data was found.
+    pub sidekiq: Option<SidekiqTotals>,
+    pub generated_at_ms: i64,
+}
+
+/// Where a Bull job can be moved back to waiting from. Active jobs are
+/// locked by a worker and can't be moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BullSource {
+    Failed,
+    Completed,
+    Delayed,
+}
+
+impl BullSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Failed => "failed",
+            Self::Completed => "completed",
+            Self::Delayed => "delayed",
+        }
+    }
+}
+
+fn default_bull_prefix() -> String {
+    DEFAULT_BULL_PREFIX.to_string()
+}
+
+/// A job to move.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "framework", rename_all = "camelCase")]
+pub enum JobMove {
+    /// Move a Sidekiq job from a queue list or the `schedule`, `retry` or
+    /// `dead` set to the back of `to_queue`, rewriting its `queue` field.
+    #[serde(rename_all = "camelCase")]
+    Sidekiq {
+        /// `queue:<name>`, `schedule`, `retry` or `dead`.
+        from: String,
+        jid: String,
+        to_queue: String,
+    },
+    /// Move a Bull job back to waiting (or to the paused list of a paused
+    /// queue), to run again.
+    #[serde(rename_all = "camelCase")]
+    Bull {
+        #[serde(default = "default_bull_prefix")]
+        prefix: String,
+        queue: String,
+        job_id: String,
+        from: BullSource,
+    },
+}
+
+/// Whether `name` can be used as a queue name inside job payloads and keys.
+pub fn valid_queue_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+}
+
+impl JobMove {
+    pub fn validate(&self) -> Result<(), AppError> {
+        match self {
+            Self::Sidekiq {
+                from,
+                jid,
+                to_queue,
+            } => {
+                let from_ok = matches!(from.as_str(), "schedule" | "retry" | "dead")
+                    || from.strip_prefix("queue:").is_some_and(valid_queue_name);
+                if !from_ok {
+                    return Err(AppError::InvalidInput(format!(
+                        "'{from}' is not a Sidekiq queue, schedule, retry or dead set"
+                    )));
+                }
+                if jid.is_empty() || jid.contains(['"', '\\']) {
+                    return Err(AppError::InvalidInput("Invalid job ID".into()));
+                }
+                if !valid_queue_name(to_queue) {
+                    return Err(AppError::InvalidInput(format!(
+                        "Invalid queue name '{to_queue}'"
+                    )));
+                }
+                Ok(())
+            }
+            Self::Bull {
+                prefix,
+                queue,
+                job_id,
+                ..
+            } => {
+                if prefix.is_empty() || queue.is_empty() || job_id.is_empty() {
+                    return Err(AppError::InvalidInput(
+                        "Prefix, queue and job ID are required".into(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_move_validation() {
+        let sidekiq = |from: &str, jid: &str, to: &str| JobMove::Sidekiq {
+            from: from.into(),
+            jid: jid.into(),
+            to_queue: to.into(),
+        };
+        assert!(sidekiq("retry", "abc", "default").validate().is_ok());
+        assert!(sidekiq("queue:low", "abc", "critical").validate().is_ok());
+        assert!(sidekiq("queues", "abc", "default").validate().is_err());
+        assert!(sidekiq("retry", "a\"b", "default").validate().is_err());
+        assert!(sidekiq("retry", "abc", "de fault").validate().is_err());
+    }
+
+    #[test]
+    fn test_job_move_deserializes_with_default_prefix() {
+        let json = r#"{"framework":"bull","queue":"mail","jobId":"7","from":"failed"}"#;
+        let JobMove::Bull { prefix, from, .. } = serde_json::from_str(json).unwrap() else {
+            panic!("expected a Bull move");
+        };
+        assert_eq!(prefix, "bull");
+        assert_eq!(from, BullSource::Failed);
+    }
+}
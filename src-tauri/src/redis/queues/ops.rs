@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+
+use super::model::{BullSource, JobMove};
+use crate::utils::errors::AppError;
+
+/// Entries read per page while looking for a Sidekiq job.
+const SEARCH_PAGE: isize = 1000;
+
+/// Moves a Sidekiq job payload only if it's still where it was found, so a
+/// job a worker picked up (or Sidekiq retried) in the meantime isn't
+/// duplicated.
+const SIDEKIQ_MOVE_SCRIPT: &str = r"
+local removed
+if ARGV[4] == 'list' then
+  removed = redis.call('LREM', KEYS[1], 1, ARGV[1])
+else
+  removed = redis.call('ZREM', KEYS[1], ARGV[1])
+end
+if removed == 0 then return 0 end
+redis.call('LPUSH', KEYS[2], ARGV[2])
+redis.call('SADD', KEYS[3], ARGV[3])
+return 1
+";
+
+/// Moves a Bull job ID from a finished or delayed set back to waiting. A
+/// paused queue gets it on its paused list, where workers won't take it
+/// until the queue is resumed; `BullMQ` workers blocked on the marker set
+/// are woken up.
+const BULL_MOVE_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[5]) == 0 then return 0 end
+if redis.call('ZREM', KEYS[1], ARGV[1]) == 0 then return 0 end
+redis.call('HDEL', KEYS[5], 'finishedOn', 'processedOn', 'failedReason', 'returnvalue')
+if ARGV[2] == 'delayed' then redis.call('HSET', KEYS[5], 'delay', 0) end
+if redis.call('HEXISTS', KEYS[4], 'paused') == 1 or redis.call('EXISTS', KEYS[7]) == 1 then
+  redis.call('LPUSH', KEYS[3], ARGV[1])
+else
+  redis.call('LPUSH', KEYS[2], ARGV[1])
+  if redis.call('EXISTS', KEYS[6]) == 1 then redis.call('ZADD', KEYS[6], 0, '0') end
+end
+return 1
+";
+
+/// Move a job; returns `false` if it wasn't found where `job_move` says.
+pub async fn move_job(pool: &Pool, job_move: &JobMove) -> Result<bool, AppError> {
+    match job_move {
+        JobMove::Sidekiq {
+            from,
+            jid,
+            to_queue,
+        } => move_sidekiq(pool, from, jid, to_queue).await,
+        JobMove::Bull {
+            prefix,
+            queue,
+            job_id,
+            from,
+        } => move_bull(pool, prefix, queue, job_id, *from).await,
+    }
+}
+
+async fn move_sidekiq(
+    pool: &Pool,
+    from: &str,
+    jid: &str,
+    to_queue: &str,
+) -> Result<bool, AppError> {
+    let mut conn = pool.get().await?;
+    let is_list = from.starts_with("queue:");
+    let Some(job) = find_sidekiq_job(&mut conn, from, is_list, jid).await? else {
+        return Ok(false);
+    };
+    let moved: u64 = redis::cmd("EVAL")
+        .arg(SIDEKIQ_MOVE_SCRIPT)
+        .arg(3)
+        .arg(from)
+        .arg(format!("queue:{to_queue}"))
+        .arg("queues")
+        .arg(&job)
+        .arg(retarget(&job, to_queue)?)
+        .arg(to_queue)
+        .arg(if is_list { "list" } else { "zset" })
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Moving job failed: {e}")))?;
+    Ok(moved > 0)
+}
+
+/// The payload of job `jid` in a Sidekiq queue list or sorted set.
+async fn find_sidekiq_job(
+    conn: &mut deadpool_redis::Connection,
+    key: &str,
+    is_list: bool,
+    jid: &str,
+) -> Result<Option<String>, AppError> {
+    let mut start = 0;
+    loop {
+        let command = if is_list { "LRANGE" } else { "ZRANGE" };
+        let page: Vec<String> = redis::cmd(command)
+            .arg(key)
+            .arg(start)
+            .arg(start + SEARCH_PAGE - 1)
+            .query_async(conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("{command} failed: {e}")))?;
+        if let Some(job) = page.iter().find(|job| job_id(job).as_deref() == Some(jid)) {
+            return Ok(Some(job.clone()));
+        }
+        if page.len() < SEARCH_PAGE.unsigned_abs() {
+            return Ok(None);
+        }
+        start += SEARCH_PAGE;
+    }
+}
+
+fn job_id(payload: &str) -> Option<String> {
+    let job: serde_json::Value = serde_json::from_str(payload).ok()?;
+    Some(job.get("jid")?.as_str()?.to_string())
+}
+
+/// `payload` with its `queue` field set to `queue`.
+fn retarget(payload: &str, queue: &str) -> Result<String, AppError> {
+    let mut job: serde_json::Value = serde_json::from_str(payload)
+        .map_err(|e| AppError::InvalidInput(format!("Job payload is not JSON: {e}")))?;
+    let fields = job
+        .as_object_mut()
+        .ok_or_else(|| AppError::InvalidInput("Job payload is not a JSON object".into()))?;
+    fields.insert("queue".into(), queue.into());
+    serde_json::to_string(&job).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+async fn move_bull(
+    pool: &Pool,
+    prefix: &str,
+    queue: &str,
+    job_id: &str,
+    from: BullSource,
+) -> Result<bool, AppError> {
+    let mut conn = pool.get().await?;
+    let base = format!("{prefix}:{queue}");
+    let moved: u64 = redis::cmd("EVAL")
+        .arg(BULL_MOVE_SCRIPT)
+        .arg(7)
+        .arg(format!("{base}:{}", from.as_str()))
+        .arg(format!("{base}:wait"))
+        .arg(format!("{base}:paused"))
+        .arg(format!("{base}:meta"))
+        .arg(format!("{base}:{job_id}"))
+        .arg(format!("{base}:marker"))
+        .arg(format!("{base}:meta-paused"))
+        .arg(job_id)
+        .arg(from.as_str())
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Moving job failed: {e}")))?;
+    Ok(moved > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retarget_rewrites_queue_only() {
+        let job = r#"{"class":"Mailer","args":[],"queue":"low","jid":"abc"}"#;
+        let moved: serde_json::Value =
+            serde_json::from_str(&retarget(job, "critical").unwrap()).unwrap();
+        assert_eq!(moved["queue"], "critical");
+        assert_eq!(moved["args"], serde_json::json!([]));
+        assert_eq!(job_id(job).as_deref(), Some("abc"));
+        assert!(retarget("[1]", "critical").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MIT
+
+//! Templates shipped with the app.
+
+use super::model::{AppTemplate, FieldPaths, RuleView, TemplateRule};
+
+/// IDs reserved for the built-in templates.
+pub const BUILTIN_IDS: &[&str] = &["sidekiq", "bull", "celery"];
+
+/// The built-in templates, in display order.
+pub fn templates() -> Vec<AppTemplate> {
+    vec![sidekiq(), bull(), celery()]
+}
+
+fn pointer(path: &str) -> String {
+    path.to_string()
+}
+
+fn rule(pattern: &str, view: RuleView, fields: &FieldPaths, expand: &[&str]) -> TemplateRule {
+    TemplateRule {
+        pattern: pattern.to_string(),
+        view,
+        fields: fields.clone(),
+        expand: expand.iter().map(|p| (*p).to_string()).collect(),
+    }
+}
+
+fn jobs(state: &str) -> RuleView {
+    RuleView::Jobs {
+        state: state.to_string(),
+    }
+}
+
+/// Sidekiq: JSON jobs in `queue:<name>` lists and in the `schedule`,
+/// `retry` and `dead` sorted sets (scored by when they run or died).
+fn sidekiq() -> AppTemplate {
+    let fields = FieldPaths {
+        id: Some(pointer("/jid")),
+        name: Some(pointer("/class")),
+        args: Some(pointer("/args")),
+        status: None,
+        created_at: Some(pointer("/created_at")),
+        error: Some(pointer("/error_message")),
+    };
+    AppTemplate {
+        id: "sidekiq".into(),
+        name: "Sidekiq".into(),
+        description: "Ruby background jobs: queues, scheduled, retry and dead sets".into(),
+        builtin: true,
+        rules: vec![
+            rule("queue:*", jobs("enqueued"), &fields, &[]),
+            rule("schedule", jobs("scheduled"), &fields, &[]),
+            rule("retry", jobs("retry"), &fields, &[]),
+            rule("dead", jobs("dead"), &fields, &[]),
+        ],
+    }
+}
+
+/// Bull / `BullMQ`: job IDs in per-state lists and sorted sets under
+/// `bull:<queue>:`, each job a hash at `bull:<queue>:<id>` whose `data`,
+/// `opts` and `returnvalue` fields hold JSON text.
+fn bull() -> AppTemplate {
+    let fields = FieldPaths {
+        id: None,
+        name: Some(pointer("/name")),
+        args: Some(pointer("/data")),
+        status: None,
+        created_at: Some(pointer("/timestamp")),
+        error: Some(pointer("/failedReason")),
+    };
+    let expand = ["/data", "/opts", "/returnvalue"];
+    let state_rule = |state: &str| {
+        rule(
+            &format!("bull:*:{state}"),
+            RuleView::JobIds {
+                state: state.to_string(),
+                job_key: "bull:{*}:{id}".into(),
+            },
+            &fields,
+            &expand,
+        )
+    };
+    AppTemplate {
+        id: "bull".into(),
+        name: "Bull / BullMQ".into(),
+        description: "Node.js job queues: waiting, active, delayed, completed and failed jobs"
+            .into(),
+        builtin: true,
+        rules: [
+            "wait",
+            "active",
+            "paused",
+            "prioritized",
+            "delayed",
+            "completed",
+            "failed",
+        ]
+        .into_iter()
+        .map(state_rule)
+        .collect(),
+    }
+}
+
+/// Celery with the Redis broker and result backend: kombu messages (base64
+/// JSON body) in a list per queue, `celery` by default, and task results
+/// in `celery-task-meta-<id>` strings.
+fn celery() -> AppTemplate {
+    AppTemplate {
+        id: "celery".into(),
+        name: "Celery".into(),
+        description: "Python task queue: pending messages and task results".into(),
+        builtin: true,
+        rules: vec![
+            rule(
+                "celery-task-meta-*",
+                RuleView::Record,
+                &FieldPaths {
+                    id: Some(pointer("/task_id")),
+                    name: Some(pointer("/name")),
+                    args: Some(pointer("/result")),
+                    status: Some(pointer("/status")),
+                    created_at: Some(pointer("/date_done")),
+                    error: Some(pointer("/traceback")),
+                },
+                &[],
+            ),
+            rule(
+                "celery",
+                jobs("pending"),
+                &FieldPaths {
+                    id: Some(pointer("/headers/id")),
+                    name: Some(pointer("/headers/task")),
+                    args: Some(pointer("/body")),
+                    status: None,
+                    created_at: Some(pointer("/headers/eta")),
+                    error: None,
+                },
+                &["/body"],
+            ),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_are_valid() {
+        let templates = templates();
+        let ids: Vec<&str> = templates.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, BUILTIN_IDS);
+        for template in &templates {
+            template.validate().unwrap();
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MIT
+
+//! Matching keys to template rules and decoding their payloads.
+
+use base64::Engine as _;
+use serde_json::Value;
+
+use super::model::{AppTemplate, DecodedJob, RuleView, TemplateRule};
+use crate::utils::glob;
+
+/// The first template and rule `key` matches, trying templates in order.
+pub fn find_rule<'a>(templates: &'a [AppTemplate], key: &str) -> Option<(&'a AppTemplate, usize)> {
+    templates.iter().find_map(|template| {
+        template
+            .rules
+            .iter()
+            .position(|rule| glob::matches(&rule.pattern, key))
+            .map(|index| (template, index))
+    })
+}
+
+/// The text the single `*` of `pattern` matches in `key`.
+fn capture<'k>(pattern: &str, key: &'k str) -> Option<&'k str> {
+    let (prefix, suffix) = pattern.split_once('*')?;
+    key.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+/// Name of the key holding job `id`, for a rule listing job IDs.
+pub fn job_key(rule: &TemplateRule, key: &str, id: &str) -> Option<String> {
+    let RuleView::JobIds { job_key, .. } = &rule.view else {
+        return None;
+    };
+    let mut name = job_key.replace("{id}", id);
+    if name.contains("{*}") {
+        name = name.replace("{*}", capture(&rule.pattern, key)?);
+    }
+    Some(name)
+}
+
+/// A stored value as JSON, or as a string when it isn't JSON.
+pub fn parse_payload(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+/// A hash as a JSON object of its (string) fields.
+pub fn hash_payload(fields: Vec<(String, String)>) -> Value {
+    Value::Object(
+        fields
+            .into_iter()
+            .map(|(field, value)| (field, Value::String(value)))
+            .collect(),
+    )
+}
+
+/// Replace the strings at `pointers` with the JSON they encode, either as
+/// JSON text or as base64-encoded JSON. Strings that are neither are left
+/// alone.
+pub fn expand(payload: &mut Value, pointers: &[String]) {
+    for pointer in pointers {
+        let Some(field) = payload.pointer_mut(pointer) else {
+            continue;
+        };
+        let Value::String(text) = field else {
+            continue;
+        };
+        let decoded = serde_json::from_str::<Value>(text).ok().or_else(|| {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(text.trim())
+                .ok()?;
+            serde_json::from_slice(&bytes).ok()
+        });
+        if let Some(decoded) = decoded.filter(|v| v.is_object() || v.is_array()) {
+            *field = decoded;
+        }
+    }
+}
+
+/// Pick a rule's fields out of a payload. `id` (from a job ID list) takes
+/// precedence over the rule's ID field.
+pub fn decode_job(
+    rule: &TemplateRule,
+    mut payload: Value,
+    id: Option<String>,
+    score: Option<f64>,
+) -> DecodedJob {
+    expand(&mut payload, &rule.expand);
+    let at = |path: &Option<String>| {
+        path.as_deref()
+            .and_then(|p| payload.pointer(p))
+            .filter(|v| !v.is_null())
+            .cloned()
+    };
+    let text = |path: &Option<String>| {
+        at(path).map(|v| match v {
+            Value::String(s) => s,
+            other => other.to_string(),
+        })
+    };
+    let fields = &rule.fields;
+    DecodedJob {
+        id: id.or_else(|| text(&fields.id)),
+        name: text(&fields.name),
+        status: rule
+            .view
+            .state()
+            .map(str::to_string)
+            .or_else(|| text(&fields.status)),
+        args: at(&fields.args),
+        created_at: at(&fields.created_at),
+        error: text(&fields.error),
+        score,
+        payload,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::redis::templates::builtin;
+
+    #[test]
+    fn test_find_rule_and_job_key() {
+        let templates = builtin::templates();
+        let (template, index) = find_rule(&templates, "bull:mail:failed").unwrap();
+        assert_eq!(template.id, "bull");
+        let rule = &template.rules[index];
+        assert_eq!(rule.view.state(), Some("failed"));
+        assert_eq!(
+            job_key(rule, "bull:mail:failed", "42").as_deref(),
+            Some("bull:mail:42")
+        );
+
+        let (template, _) = find_rule(&templates, "queue:default").unwrap();
+        assert_eq!(template.id, "sidekiq");
+        assert!(find_rule(&templates, "user:1").is_none());
+    }
+
+    #[test]
+    fn test_decode_sidekiq_job() {
+        let templates = builtin::templates();
+        let rule = &templates[0].rules[2];
+        let payload = parse_payload(
+            br#"{"class":"MailerJob","args":[1,"x"],"jid":"abc","created_at":1700000000.5,"error_message":"boom"}"#,
+        );
+        let job = decode_job(rule, payload, None, Some(1_700_000_100.0));
+        assert_eq!(job.id.as_deref(), Some("abc"));
+        assert_eq!(job.name.as_deref(), Some("MailerJob"));
+        assert_eq!(job.status.as_deref(), Some("retry"));
+        assert_eq!(job.args, Some(json!([1, "x"])));
+        assert_eq!(job.error.as_deref(), Some("boom"));
+        assert_eq!(job.score, Some(1_700_000_100.0));
+    }
+
+    #[test]
+    fn test_decode_celery_message_expands_base64_body() {
+        let templates = builtin::templates();
+        let rule = &templates[2].rules[1];
+        let body = base64::engine::general_purpose::STANDARD.encode(r"[[2, 3], {}, {}]");
+        let payload = json!({
+            "body": body,
+            "headers": {"id": "t-1", "task": "tasks.add", "eta": null},
+        });
+        let job = decode_job(rule, payload, None, None);
+        assert_eq!(job.id.as_deref(), Some("t-1"));
+        assert_eq!(job.name.as_deref(), Some("tasks.add"));
+        assert_eq!(job.args, Some(json!([[2, 3], {}, {}])));
+        assert_eq!(job.created_at, None);
+        assert_eq!(job.status.as_deref(), Some("pending"));
+    }
+
+    #[test]
+    fn test_decode_bull_job_hash() {
+        let templates = builtin::templates();
+        let rule = &templates[1].rules[0];
+        let payload = hash_payload(vec![
+            ("name".into(), "send".into()),
+            ("data".into(), r#"{"to":"a@b"}"#.into()),
+            ("timestamp".into(), "1700000000000".into()),
+        ]);
+        let job = decode_job(rule, payload, Some("7".into()), None);
+        assert_eq!(job.id.as_deref(), Some("7"));
+        assert_eq!(job.args, Some(json!({"to": "a@b"})));
+        assert_eq!(job.created_at, Some(json!("1700000000000")));
+        assert_eq!(job.error, None);
+    }
+
+    #[test]
+    fn test_expand_leaves_plain_strings() {
+        let mut payload = json!({"body": "hello", "n": "42"});
+        expand(
+            &mut payload,
+            &["/body".into(), "/n".into(), "/missing".into()],
+        );
+        assert_eq!(payload, json!({"body": "hello", "n": "42"}));
+    }
+}
@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: MIT
+
+//! Reading keys through a template.
+
+use std::collections::BTreeMap;
+
+use deadpool_redis::{Connection, Pool};
+use serde_json::Value;
+
+use super::decode;
+use super::model::{AppTemplate, RuleView, TemplateKey, TemplateRule, TemplateScan, TemplateView};
+use crate::redis::browser::scanner;
+use crate::utils::errors::AppError;
+
+/// Read `key` with rule `rule_index` of `template`: a record, or the jobs
+/// from `offset` on (at most `count`) of a queue key.
+pub async fn inspect_key(
+    pool: &Pool,
+    template: &AppTemplate,
+    rule_index: usize,
+    key: &str,
+    offset: u64,
+    count: u64,
+) -> Result<TemplateView, AppError> {
+    let rule = &template.rules[rule_index];
+    let mut conn = pool.get().await?;
+    let key_type: String = redis::cmd("TYPE")
+        .arg(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?;
+    let mut view = TemplateView {
+        template_id: template.id.clone(),
+        template_name: template.name.clone(),
+        key: key.to_string(),
+        key_type: key_type.clone(),
+        rule: rule_index,
+        state: rule.view.state().map(str::to_string),
+        total: 0,
+        jobs: Vec::new(),
+        missing: 0,
+    };
+    let unsupported = || {
+        AppError::InvalidInput(format!(
+            "'{key}' is a {key_type}, which the {} template doesn't read this way",
+            template.name
+        ))
+    };
+
+    if key_type == "none" {
+        return Err(AppError::NotFound(format!("Key '{key}' not found")));
+    }
+
+    if rule.view == RuleView::Record {
+        let payload = read_record(&mut conn, key, &key_type)
+            .await?
+            .ok_or_else(unsupported)?;
+        view.total = 1;
+        view.jobs
+            .push(decode::decode_job(rule, payload, None, None));
+        return Ok(view);
+    }
+
+    let start = i64::try_from(offset).unwrap_or(i64::MAX);
+    let stop = start.saturating_add(i64::try_from(count).unwrap_or(i64::MAX) - 1);
+    let (total, elements) = read_range(&mut conn, key, &key_type, start, stop)
+        .await?
+        .ok_or_else(unsupported)?;
+    view.total = total;
+
+    if elements.is_empty() {
+        return Ok(view);
+    }
+    if let RuleView::JobIds { .. } = rule.view {
+        let mut job_keys = Vec::with_capacity(elements.len());
+        for (id, score) in elements {
+            let id = String::from_utf8_lossy(&id).into_owned();
+            let job_key = decode::job_key(rule, key, &id).ok_or_else(unsupported)?;
+            job_keys.push((job_key, id, score));
+        }
+        read_jobs(&mut conn, rule, job_keys, &mut view).await?;
+    } else {
+        view.jobs = elements
+            .into_iter()
+            .map(|(item, score)| {
+                decode::decode_job(rule, decode::parse_payload(&item), None, score)
+            })
+            .collect();
+    }
+    Ok(view)
+}
+
+/// Read a record key as a payload: a string's value, or a hash's fields.
+/// `None` for any other key type.
+async fn read_record(
+    conn: &mut Connection,
+    key: &str,
+    key_type: &str,
+) -> Result<Option<Value>, AppError> {
+    match key_type {
+        "string" => {
+            let bytes: Vec<u8> = redis::cmd("GET")
+                .arg(key)
+                .query_async(conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("GET failed: {e}")))?;
+            Ok(Some(decode::parse_payload(&bytes)))
+        }
+        "hash" => {
+            let fields: Vec<(String, String)> = redis::cmd("HGETALL")
+                .arg(key)
+                .query_async(conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("HGETALL failed: {e}")))?;
+            Ok(Some(decode::hash_payload(fields)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Read elements `start..=stop` of a list or sorted set (with their scores)
+/// and the key's length. `None` for any other key type.
+async fn read_range(
+    conn: &mut Connection,
+    key: &str,
+    key_type: &str,
+    start: i64,
+    stop: i64,
+) -> Result<Option<(u64, Vec<(Vec<u8>, Option<f64>)>)>, AppError> {
+    match key_type {
+        "list" => {
+            let (total, items): (u64, Vec<Vec<u8>>) = redis::pipe()
+                .cmd("LLEN")
+                .arg(key)
+                .cmd("LRANGE")
+                .arg(key)
+                .arg(start)
+                .arg(stop)
+                .query_async(conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("LRANGE failed: {e}")))?;
+            Ok(Some((
+                total,
+                items.into_iter().map(|item| (item, None)).collect(),
+            )))
+        }
+        "zset" => {
+            let (total, items): (u64, Vec<(Vec<u8>, f64)>) = redis::pipe()
+                .cmd("ZCARD")
+                .arg(key)
+                .cmd("ZRANGE")
+                .arg(key)
+                .arg(start)
+                .arg(stop)
+                .arg("WITHSCORES")
+                .query_async(conn)
+                .await
+                .map_err(|e| AppError::Redis(format!("ZRANGE failed: {e}")))?;
+            Ok(Some((
+                total,
+                items
+                    .into_iter()
+                    .map(|(item, score)| (item, Some(score)))
+                    .collect(),
+            )))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Read the job hashes behind a queue's IDs into `view`, counting IDs whose
+/// hash is gone as missing.
+async fn read_jobs(
+    conn: &mut Connection,
+    rule: &TemplateRule,
+    job_keys: Vec<(String, String, Option<f64>)>,
+    view: &mut TemplateView,
+) -> Result<(), AppError> {
+    let mut pipe = redis::pipe();
+    for (job_key, _, _) in &job_keys {
+        pipe.cmd("HGETALL").arg(job_key);
+    }
+    let hashes: Vec<Vec<(String, String)>> = pipe
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("Reading jobs failed: {e}")))?;
+    for ((_, id, score), fields) in job_keys.into_iter().zip(hashes) {
+        if fields.is_empty() {
+            view.missing += 1;
+            continue;
+        }
+        view.jobs.push(decode::decode_job(
+            rule,
+            decode::hash_payload(fields),
+            Some(id),
+            score,
+        ));
+    }
+    Ok(())
+}
+
+/// Find up to `limit` keys matching the template's rules, with their
+/// queue lengths.
+pub async fn scan_template(
+    pool: &Pool,
+    template: &AppTemplate,
+    limit: usize,
+) -> Result<TemplateScan, AppError> {
+    let mut conn = pool.get().await?;
+    // A key matching several rules belongs to the first.
+    let mut found: BTreeMap<String, usize> = BTreeMap::new();
+    let mut truncated = false;
+    'rules: for (index, rule) in template.rules.iter().enumerate() {
+        let mut cursor = 0;
+        loop {
+            let (next, keys) = scanner::scan_match(&mut conn, cursor, &rule.pattern).await?;
+            for key in keys {
+                if found.contains_key(&key) {
+                    continue;
+                }
+                if found.len() == limit {
+                    truncated = true;
+                    break 'rules;
+                }
+                found.insert(key, index);
+            }
+            cursor = next;
+            if cursor == 0 {
+                break;
+            }
+        }
+    }
+    if found.is_empty() {
+        return Ok(TemplateScan {
+            keys: Vec::new(),
+            truncated,
+        });
+    }
+
+    let mut pipe = redis::pipe();
+    for key in found.keys() {
+        pipe.cmd("TYPE").arg(key);
+    }
+    let types: Vec<String> = pipe
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| AppError::Redis(format!("TYPE failed: {e}")))?;
+
+    let mut pipe = redis::pipe();
+    let mut queues = 0;
+    let mut keys = Vec::with_capacity(found.len());
+    for ((key, index), key_type) in found.into_iter().zip(types) {
+        let rule = &template.rules[index];
+        let length_cmd = match (key_type.as_str(), &rule.view) {
+            (_, RuleView::Record) => None,
+            ("list", _) => Some("LLEN"),
+            ("zset", _) => Some("ZCARD"),
+            _ => None,
+        };
+        if let Some(cmd) = length_cmd {
+            pipe.cmd(cmd).arg(&key);
+            queues += 1;
+        }
+        keys.push((
+            TemplateKey {
+                key,
+                key_type,
+                rule: index,
+                state: rule.view.state().map(str::to_string),
+                length: None,
+            },
+            length_cmd.is_some(),
+        ));
+    }
+    let lengths: Vec<u64> = if queues == 0 {
+        Vec::new()
+    } else {
+        pipe.query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Redis(format!("Reading queue lengths failed: {e}")))?
+    };
+    let mut lengths = lengths.into_iter();
+    Ok(TemplateScan {
+        keys: keys
+            .into_iter()
+            .map(|(mut key, has_length)| {
+                if has_length {
+                    key.length = lengths.next();
+                }
+                key
+            })
+            .collect(),
+        truncated,
+    })
+}
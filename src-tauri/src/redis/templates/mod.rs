@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+//! Application key templates: descriptions of how a framework (Sidekiq,
+//! Bull, Celery, ...) lays out its data in Redis, used to show its keys as
+//! jobs and records instead of raw values.
+
+pub mod builtin;
+pub mod decode;
+pub mod inspect;
+pub mod model;
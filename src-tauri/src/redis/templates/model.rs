@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::errors::AppError;
+
+/// Most rules one template may have.
+pub const MAX_RULES: usize = 32;
+/// Most jobs one inspection returns.
+pub const MAX_PAGE: u64 = 1_000;
+
+/// How a framework stores its data: which keys it uses and how to read them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTemplate {
+    /// Built-in templates have fixed IDs (`sidekiq`, `bull`, `celery`);
+    /// user templates get a UUID when first saved.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Set on built-in templates, which can't be changed or deleted.
+    #[serde(default)]
+    pub builtin: bool,
+    /// Checked in order; a key is read with the first rule it matches.
+    pub rules: Vec<TemplateRule>,
+}
+
+/// One kind of key of a template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateRule {
+    /// Glob the key names match, as in SCAN MATCH.
+    pub pattern: String,
+    pub view: RuleView,
+    /// Where the job fields sit in a decoded payload.
+    #[serde(default)]
+    pub fields: FieldPaths,
+    /// JSON pointers of string fields holding JSON text or base64-encoded
+    /// JSON, decoded in place (Celery's message body, Bull's job data).
+    #[serde(default)]
+    pub expand: Vec<String>,
+}
+
+/// How a rule's keys are read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RuleView {
+    /// A list or sorted set whose elements are the job payloads.
+    Jobs { state: String },
+    /// A list or sorted set of job IDs, each job stored in its own key.
+    /// `job_key` names it: `{id}` is the job ID and `{*}` the text the
+    /// pattern's single `*` matched.
+    #[serde(rename_all = "camelCase")]
+    JobIds { state: String, job_key: String },
+    /// A string or hash holding one record, such as a task result.
+    Record,
+}
+
+impl RuleView {
+    /// The job state every element of the key is in, for queue views.
+    pub fn state(&self) -> Option<&str> {
+        match self {
+            Self::Jobs { state } | Self::JobIds { state, .. } => Some(state),
+            Self::Record => None,
+        }
+    }
+}
+
+/// JSON pointers of the fields shown for each job or record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FieldPaths {
+    pub id: Option<String>,
+    /// Job class or task name.
+    pub name: Option<String>,
+    pub args: Option<String>,
+    /// Status of a record; queue views take it from the rule's state.
+    pub status: Option<String>,
+    pub created_at: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A job or record decoded with a template.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedJob {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub args: Option<Value>,
+    pub created_at: Option<Value>,
+    pub error: Option<String>,
+    /// Sorted set score: when a scheduled or retried job runs, or when it
+    /// finished, depending on the framework.
+    pub score: Option<f64>,
+    /// The whole decoded payload.
+    pub payload: Value,
+}
+
+/// A key read through a template rule.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateView {
+    pub template_id: String,
+    pub template_name: String,
+    pub key: String,
+    pub key_type: String,
+    /// Index of the rule in the template.
+    pub rule: usize,
+    pub state: Option<String>,
+    /// Elements in the key; 1 for records.
+    pub total: u64,
+    pub jobs: Vec<DecodedJob>,
+    /// Elements that couldn't be read: job keys gone since they were listed.
+    pub missing: u64,
+}
+
+/// A key found by scanning for a template's rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateKey {
+    pub key: String,
+    pub key_type: String,
+    pub rule: usize,
+    pub state: Option<String>,
+    /// Elements in a queue key; `None` for records.
+    pub length: Option<u64>,
+}
+
+/// Result of scanning for a template's keys.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateScan {
+    /// Sorted by key.
+    pub keys: Vec<TemplateKey>,
+    /// More keys matched than the scan's limit.
+    pub truncated: bool,
+}
+
+impl AppTemplate {
+    /// Reject templates that can't be applied.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput("Template name is required".into()));
+        }
+        if self.rules.is_empty() || self.rules.len() > MAX_RULES {
+            return Err(AppError::InvalidInput(format!(
+                "A template needs between 1 and {MAX_RULES} rules"
+            )));
+        }
+        for (i, rule) in self.rules.iter().enumerate() {
+            rule.validate()
+                .map_err(|e| AppError::InvalidInput(format!("Rule {}: {e}", i + 1)))?;
+        }
+        Ok(())
+    }
+}
+
+impl TemplateRule {
+    fn validate(&self) -> Result<(), String> {
+        if self.pattern.is_empty() {
+            return Err("pattern must not be empty".into());
+        }
+        let pointers = [
+            &self.fields.id,
+            &self.fields.name,
+            &self.fields.args,
+            &self.fields.status,
+            &self.fields.created_at,
+            &self.fields.error,
+        ];
+        let bad_pointer = pointers
+            .into_iter()
+            .flatten()
+            .chain(&self.expand)
+            .find(|p| !p.is_empty() && !p.starts_with('/'));
+        if let Some(pointer) = bad_pointer {
+            return Err(format!(
+                "'{pointer}' is not a JSON pointer (start it with /)"
+            ));
+        }
+        match &self.view {
+            RuleView::Jobs { state } | RuleView::JobIds { state, .. }
+                if state.trim().is_empty() =>
+            {
+                Err("queue views need a state".into())
+            }
+            RuleView::JobIds { job_key, .. } => {
+                if !job_key.contains("{id}") {
+                    return Err("job key must contain {id}".into());
+                }
+                if job_key.contains("{*}")
+                    && (self.pattern.matches('*').count() != 1
+                        || self.pattern.contains(['?', '[', '\\']))
+                {
+                    return Err(
+                        "{*} needs a pattern with exactly one * and no other wildcards".into(),
+                    );
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, view: RuleView) -> TemplateRule {
+        TemplateRule {
+            pattern: pattern.into(),
+            view,
+            fields: FieldPaths::default(),
+            expand: vec![],
+        }
+    }
+
+    #[test]
+    fn test_rule_validation() {
+        assert!(rule("q:*", RuleView::Record).validate().is_ok());
+        assert!(rule("", RuleView::Record).validate().is_err());
+        assert!(rule("q:*", RuleView::Jobs { state: " ".into() })
+            .validate()
+            .is_err());
+
+        let job_ids = |pattern: &str, job_key: &str| {
+            rule(
+                pattern,
+                RuleView::JobIds {
+                    state: "wait".into(),
+                    job_key: job_key.into(),
+                },
+            )
+        };
+        assert!(job_ids("bull:*:wait", "bull:{*}:{id}").validate().is_ok());
+        assert!(job_ids("bull:*:wait", "bull:{*}").validate().is_err());
+        assert!(job_ids("*:*:wait", "{*}:{id}").validate().is_err());
+
+        let mut bad = rule("q:*", RuleView::Record);
+        bad.fields.id = Some("jid".into());
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use tauri::ipc::Channel;
+
+use crate::utils::errors::AppError;
+
+/// Items per chunk when the caller does not choose.
+pub const DEFAULT_CHUNK_SIZE: usize = 500;
+/// Largest accepted chunk size.
+const MAX_CHUNK_SIZE: usize = 10_000;
+
+/// A message on a streamed response channel.
+///
+/// A stream is any number of `data` chunks followed by exactly one `done`
+/// or `failed` marker.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StreamChunk<T> {
+    Data { seq: u64, items: Vec<T> },
+    Done { total: u64 },
+    Failed { message: String },
+}
+
+/// Resolve a caller-supplied chunk size, falling back to the default.
+pub fn chunk_size(requested: Option<u32>) -> Result<usize, AppError> {
+    match requested {
+        None => Ok(DEFAULT_CHUNK_SIZE),
+        Some(0) => Err(AppError::InvalidInput(
+            "Chunk size must be at least 1".into(),
+        )),
+        Some(n) => Ok((n as usize).min(MAX_CHUNK_SIZE)),
+    }
+}
+
+/// Accumulates items and hands them out in fixed-size chunks.
+struct ChunkBuffer<T> {
+    items: Vec<T>,
+    size: usize,
+    seq: u64,
+    total: u64,
+}
+
+impl<T> ChunkBuffer<T> {
+    fn new(size: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(size),
+            size,
+            seq: 0,
+            total: 0,
+        }
+    }
+
+    /// Add an item; returns a full chunk once `size` items are buffered.
+    fn push(&mut self, item: T) -> Option<StreamChunk<T>> {
+        self.items.push(item);
+        self.total += 1;
+        (self.items.len() >= self.size).then(|| self.take())
+    }
+
+    /// Whatever is buffered, if anything.
+    fn drain(&mut self) -> Option<StreamChunk<T>> {
+        (!self.items.is_empty()).then(|| self.take())
+    }
+
+    fn take(&mut self) -> StreamChunk<T> {
+        let chunk = StreamChunk::Data {
+            seq: self.seq,
+            items: std::mem::replace(&mut self.items, Vec::with_capacity(self.size)),
+        };
+        self.seq += 1;
+        chunk
+    }
+}
+
+/// Sends a large result to the frontend as a sequence of chunks.
+///
+/// At most one chunk is held in memory, so the full result is never
+/// serialized as a single IPC payload.
+pub struct ChunkSender<T: Serialize + Clone> {
+    channel: Channel<StreamChunk<T>>,
+    buffer: ChunkBuffer<T>,
+}
+
+impl<T: Serialize + Clone> ChunkSender<T> {
+    /// Wrap a channel passed in by the frontend.
+    pub fn new(channel: Channel<StreamChunk<T>>, chunk_size: usize) -> Self {
+        Self {
+            channel,
+            buffer: ChunkBuffer::new(chunk_size),
+        }
+    }
+
+    /// Queue an item, sending a chunk when the buffer fills.
+    pub fn push(&mut self, item: T) -> Result<(), AppError> {
+        match self.buffer.push(item) {
+            Some(chunk) => self.send(chunk),
+            None => Ok(()),
+        }
+    }
+
+    /// Queue several items.
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>) -> Result<(), AppError> {
+        items.into_iter().try_for_each(|item| self.push(item))
+    }
+
+    /// Flush the remainder and send the completion marker (or the failure).
+    ///
+    /// Returns the number of items streamed, or the producer's error.
+    pub fn finish(mut self, outcome: Result<(), AppError>) -> Result<u64, AppError> {
+        match outcome {
+            Ok(()) => {
+                if let Some(chunk) = self.buffer.drain() {
+                    self.send(chunk)?;
+                }
+                let total = self.buffer.total;
+                self.send(StreamChunk::Done { total })?;
+                Ok(total)
+            }
+            Err(e) => {
+                // The caller gets the error either way; a closed channel adds nothing.
+                let _ = self.send(StreamChunk::Failed {
+                    message: e.to_string(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    fn send(&self, chunk: StreamChunk<T>) -> Result<(), AppError> {
+        self.channel
+            .send(chunk)
+            .map_err(|e| AppError::Internal(format!("Failed to send stream chunk: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(chunk: StreamChunk<u32>) -> (u64, Vec<u32>) {
+        match chunk {
+            StreamChunk::Data { seq, items } => (seq, items),
+            other => panic!("expected data chunk, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_chunk_buffer_splits_and_drains() {
+        let mut buffer = ChunkBuffer::new(2);
+        assert!(buffer.push(1).is_none());
+        assert_eq!(items(buffer.push(2).unwrap()), (0, vec![1, 2]));
+        assert!(buffer.push(3).is_none());
+        assert_eq!(items(buffer.drain().unwrap()), (1, vec![3]));
+        assert!(buffer.drain().is_none());
+        assert_eq!(buffer.total, 3);
+    }
+
+    #[test]
+    fn test_chunk_size() {
+        assert_eq!(chunk_size(None).unwrap(), DEFAULT_CHUNK_SIZE);
+        assert_eq!(chunk_size(Some(50)).unwrap(), 50);
+        assert_eq!(chunk_size(Some(1_000_000)).unwrap(), MAX_CHUNK_SIZE);
+        assert!(chunk_size(Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_stream_chunk_serialization() {
+        let json = serde_json::to_string(&StreamChunk::<u32>::Done { total: 7 }).unwrap();
+        assert_eq!(json, r#"{"type":"done","total":7}"#);
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: MIT
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::AppError;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Highest cost parameters accepted from sealed data, so a crafted file
+/// can't make opening it allocate gigabytes or spin for minutes.
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 16;
+const MAX_PARALLELISM: u32 = 16;
+
+/// Argon2id cost parameters, stored with the data so they can be raised later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// The OWASP-recommended Argon2id baseline (19 MiB, 2 passes).
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Data encrypted with a passphrase (AES-256-GCM, key from Argon2id).
+///
+/// Salt, nonce and ciphertext are base64.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealedData {
+    pub kdf: KdfParams,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// A key derived from a passphrase, reusable for many seal/open calls.
+///
+/// Deriving is deliberately slow, so long-lived holders (the profile store)
+/// keep one of these rather than the passphrase.
+#[derive(Clone)]
+pub struct SealingKey {
+    cipher: Aes256Gcm,
+    kdf: KdfParams,
+    salt: Vec<u8>,
+}
+
+impl SealingKey {
+    /// Derive a key with a fresh random salt.
+    pub fn generate(passphrase: &str, kdf: KdfParams) -> Result<Self, AppError> {
+        let mut salt = vec![0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(passphrase, kdf, salt)
+    }
+
+    /// Derive the key that sealed `sealed`, on the blocking thread pool.
+    ///
+    /// Cost parameters above the supported ceilings fail with `Decryption`
+    /// before anything is derived.
+    pub async fn for_sealed(passphrase: &str, sealed: &SealedData) -> Result<Self, AppError> {
+        let kdf = sealed.kdf;
+        if kdf.memory_kib > MAX_MEMORY_KIB
+            || kdf.iterations > MAX_ITERATIONS
+            || kdf.parallelism > MAX_PARALLELISM
+        {
+            return Err(AppError::Decryption(format!(
+                "Key derivation parameters exceed the supported limits \
+                 ({MAX_MEMORY_KIB} KiB, {MAX_ITERATIONS} passes, {MAX_PARALLELISM} lanes)"
+            )));
+        }
+        let salt = decode("salt", &sealed.salt)?;
+        let passphrase = passphrase.to_string();
+        tokio::task::spawn_blocking(move || Self::derive(&passphrase, kdf, salt))
+            .await
+            .map_err(|e| AppError::Internal(format!("Key derivation failed: {e}")))?
+    }
+
+    fn derive(passphrase: &str, kdf: KdfParams, salt: Vec<u8>) -> Result<Self, AppError> {
+        if passphrase.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Passphrase must not be empty".into(),
+            ));
+        }
+        let params = argon2::Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, None)
+            .map_err(|e| {
+                AppError::InvalidInput(format!("Invalid key derivation parameters: {e}"))
+            })?;
+        let mut key = [0u8; 32];
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| AppError::Internal(format!("Key derivation failed: {e}")))?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| AppError::Internal(format!("Invalid key: {e}")))?;
+        Ok(Self { cipher, kdf, salt })
+    }
+
+    /// Encrypt with a fresh nonce.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<SealedData, AppError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|e| AppError::Internal(format!("Encryption failed: {e}")))?;
+
+        let b64 = base64::engine::general_purpose::STANDARD;
+        Ok(SealedData {
+            kdf: self.kdf,
+            salt: b64.encode(&self.salt),
+            nonce: b64.encode(nonce),
+            ciphertext: b64.encode(ciphertext),
+        })
+    }
+
+    /// Decrypt data sealed with this key.
+    ///
+    /// A wrong key and tampered data are indistinguishable and both fail
+    /// with `Decryption`.
+    pub fn open(&self, sealed: &SealedData) -> Result<Vec<u8>, AppError> {
+        if sealed.kdf != self.kdf || decode("salt", &sealed.salt)? != self.salt {
+            return Err(AppError::Decryption(
+                "Data was sealed with a different key".into(),
+            ));
+        }
+        let nonce = decode("nonce", &sealed.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(AppError::Decryption("Invalid nonce length".into()));
+        }
+        let ciphertext = decode("ciphertext", &sealed.ciphertext)?;
+        self.cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| AppError::Decryption("Wrong passphrase or corrupted data".into()))
+    }
+}
+
+/// Encrypt `plaintext` with a key derived from `passphrase`.
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<SealedData, AppError> {
+    SealingKey::generate(passphrase, KdfParams::default())?.seal(plaintext)
+}
+
+/// Decrypt data sealed with [`seal`].
+pub async fn open(passphrase: &str, sealed: &SealedData) -> Result<Vec<u8>, AppError> {
+    SealingKey::for_sealed(passphrase, sealed)
+        .await?
+        .open(sealed)
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>, AppError> {
+    base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .map_err(|e| AppError::Decryption(format!("Invalid encrypted {field}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests don't pay for a real key derivation.
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[tokio::test]
+    async fn test_seal_open_roundtrip() {
+        let key = SealingKey::generate("hunter2", TEST_KDF).unwrap();
+        let sealed = key.seal(b"secret").unwrap();
+        assert_eq!(sealed.kdf, TEST_KDF);
+        assert_eq!(key.open(&sealed).unwrap(), b"secret");
+
+        let rederived = SealingKey::for_sealed("hunter2", &sealed).await.unwrap();
+        assert_eq!(rederived.open(&sealed).unwrap(), b"secret");
+    }
+
+    #[tokio::test]
+    async fn test_open_wrong_passphrase() {
+        let sealed = SealingKey::generate("hunter2", TEST_KDF)
+            .unwrap()
+            .seal(b"secret")
+            .unwrap();
+        let err = open("hunter3", &sealed).await.unwrap_err();
+        assert!(matches!(err, AppError::Decryption(_)));
+    }
+
+    #[test]
+    fn test_open_with_other_salt() {
+        let a = SealingKey::generate("same", TEST_KDF).unwrap();
+        let b = SealingKey::generate("same", TEST_KDF).unwrap();
+        let err = b.open(&a.seal(b"secret").unwrap()).unwrap_err();
+        assert!(matches!(err, AppError::Decryption(_)));
+    }
+
+    #[tokio::test]
+    async fn test_open_rejects_excessive_kdf_params() {
+        let mut sealed = SealingKey::generate("hunter2", TEST_KDF)
+            .unwrap()
+            .seal(b"secret")
+            .unwrap();
+        sealed.kdf.memory_kib = u32::MAX;
+        let err = open("hunter2", &sealed).await.unwrap_err();
+        assert!(matches!(err, AppError::Decryption(_)));
+
+        sealed.kdf = KdfParams {
+            iterations: 1_000_000,
+            ..TEST_KDF
+        };
+        let err = open("hunter2", &sealed).await.unwrap_err();
+        assert!(matches!(err, AppError::Decryption(_)));
+    }
+
+    #[test]
+    fn test_seal_rejects_empty_passphrase() {
+        assert!(seal("", b"secret").is_err());
+    }
+}
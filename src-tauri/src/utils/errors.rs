@@ -27,6 +27,18 @@ pub enum AppError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    /// The encrypted profile store must be unlocked first.
+    #[error("Locked: {0}")]
+    Locked(String),
+
+    /// Wrong passphrase, or encrypted data that was corrupted or tampered with.
+    #[error("Decryption failed: {0}")]
+    Decryption(String),
+
+    /// A compare-and-set write found the value changed since it was read.
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Timeout: {0}")]
     Timeout(String),
 
@@ -37,8 +49,12 @@ pub enum AppError {
 impl From<redis::RedisError> for AppError {
     fn from(err: redis::RedisError) -> Self {
         let msg = err.to_string();
-        if msg.contains("NOAUTH") || msg.contains("ERR AUTH") || msg.contains("WRONGPASS") {
+        if err.is_timeout() {
+            AppError::Timeout(format!("No reply from the server in time: {msg}"))
+        } else if msg.contains("NOAUTH") || msg.contains("ERR AUTH") || msg.contains("WRONGPASS") {
             AppError::Connection(format!("Authentication failed: {msg}"))
+        } else if err.code() == Some("NOPERM") {
+            AppError::PermissionDenied(format!("Rejected by the server's ACL rules: {msg}"))
         } else if msg.contains("Connection refused") {
             AppError::Connection(format!("Connection refused: {msg}"))
         } else {
@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::utils::errors::AppError;
+
+/// Length of a rate-limit window.
+const WINDOW: Duration = Duration::from_secs(1);
+/// Channels idle for this long are forgotten.
+const IDLE_EXPIRY: Duration = Duration::from_mins(1);
+
+/// How events over the limit are folded together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Coalesce {
+    /// Keep only the most recent payload (periodic state such as stats).
+    Latest,
+    /// Buffer payloads and deliver them together as an array on `<event>:batch`.
+    Batch,
+}
+
+/// Rate limit for one event name, applied per scope (connection, subscription).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottlePolicy {
+    pub max_per_sec: u32,
+    pub coalesce: Coalesce,
+    /// Payloads buffered per window in batch mode; the oldest are dropped beyond this.
+    #[serde(default = "default_max_batch")]
+    pub max_batch: usize,
+}
+
+fn default_max_batch() -> usize {
+    1000
+}
+
+/// Built-in limits for the high-frequency events.
+pub fn default_policies() -> BTreeMap<String, ThrottlePolicy> {
+    BTreeMap::from([(
+        "monitor:stats".to_string(),
+        ThrottlePolicy {
+            max_per_sec: 4,
+            coalesce: Coalesce::Latest,
+            max_batch: default_max_batch(),
+        },
+    )])
+}
+
+/// Reject policies that would block an event entirely.
+pub fn validate_policies(policies: &BTreeMap<String, ThrottlePolicy>) -> Result<(), AppError> {
+    for (event, policy) in policies {
+        if policy.max_per_sec == 0 || policy.max_batch == 0 {
+            return Err(AppError::InvalidInput(format!(
+                "Throttle limits for {event} must be at least 1"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Rate-limit state of one `(event, scope)` channel.
+struct ChannelState {
+    window_start: Instant,
+    sent: u32,
+    pending: Vec<serde_json::Value>,
+    dropped: u64,
+    flush_scheduled: bool,
+}
+
+impl ChannelState {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            sent: 0,
+            pending: Vec::new(),
+            dropped: 0,
+            flush_scheduled: false,
+        }
+    }
+
+    /// Whether an event may go out immediately, counting it if so.
+    ///
+    /// Held events must flush first so delivery order is preserved.
+    fn admit(&mut self, now: Instant, max_per_sec: u32) -> bool {
+        if now.duration_since(self.window_start) >= WINDOW {
+            self.window_start = now;
+            self.sent = 0;
+        }
+        if self.sent < max_per_sec && self.pending.is_empty() {
+            self.sent += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Hold a payload until the next window, coalescing per the policy.
+    fn hold(&mut self, payload: serde_json::Value, policy: &ThrottlePolicy) {
+        if policy.coalesce == Coalesce::Latest {
+            self.dropped += self.pending.len() as u64;
+            self.pending.clear();
+        }
+        self.pending.push(payload);
+        if self.pending.len() > policy.max_batch {
+            let excess = self.pending.len() - policy.max_batch;
+            self.pending.drain(..excess);
+            self.dropped += excess as u64;
+        }
+    }
+
+    /// Time left in the current window.
+    fn remaining(&self, now: Instant) -> Duration {
+        WINDOW.saturating_sub(now.duration_since(self.window_start))
+    }
+
+    /// Take the held payloads, counting the flush as one event of a new window.
+    fn take_pending(&mut self, now: Instant) -> Vec<serde_json::Value> {
+        self.window_start = now;
+        self.sent = 1;
+        self.flush_scheduled = false;
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Central rate limiter for events emitted to the frontend.
+///
+/// Events with a policy are limited per `(event, scope)`; anything over the
+/// limit is coalesced and flushed when the window reopens. Events without a
+/// policy pass straight through.
+#[derive(Clone)]
+pub struct EventThrottler {
+    policies: Arc<RwLock<BTreeMap<String, ThrottlePolicy>>>,
+    channels: Arc<Mutex<HashMap<(String, String), ChannelState>>>,
+}
+
+impl Default for EventThrottler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventThrottler {
+    /// Create a throttler with the built-in policies.
+    pub fn new() -> Self {
+        Self {
+            policies: Arc::new(RwLock::new(default_policies())),
+            channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Replace the per-event policies.
+    pub async fn configure(&self, policies: BTreeMap<String, ThrottlePolicy>) {
+        *self.policies.write().await = policies;
+    }
+
+    /// Emit `payload` as `event`, subject to the event's policy.
+    pub async fn emit<T: Serialize>(
+        &self,
+        app: &AppHandle,
+        event: &str,
+        scope: &str,
+        payload: &T,
+    ) -> Result<(), AppError> {
+        let Some(policy) = self.policies.read().await.get(event).copied() else {
+            return emit_now(app, event, payload);
+        };
+
+        let now = Instant::now();
+        let mut channels = self.channels.lock().await;
+        channels
+            .retain(|_, c| c.flush_scheduled || now.duration_since(c.window_start) < IDLE_EXPIRY);
+
+        let key = (event.to_string(), scope.to_string());
+        let channel = channels
+            .entry(key.clone())
+            .or_insert_with(|| ChannelState::new(now));
+        if channel.admit(now, policy.max_per_sec) {
+            drop(channels);
+            return emit_now(app, event, payload);
+        }
+
+        let value = serde_json::to_value(payload)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize {event}: {e}")))?;
+        channel.hold(value, &policy);
+        if !channel.flush_scheduled {
+            channel.flush_scheduled = true;
+            let delay = channel.remaining(now);
+            tokio::spawn(self.clone().flush_later(app.clone(), key, policy, delay));
+        }
+        Ok(())
+    }
+
+    /// Deliver a channel's held payloads once its window has passed.
+    async fn flush_later(
+        self,
+        app: AppHandle,
+        key: (String, String),
+        policy: ThrottlePolicy,
+        delay: Duration,
+    ) {
+        tokio::time::sleep(delay).await;
+
+        let (pending, dropped) = {
+            let mut channels = self.channels.lock().await;
+            let Some(channel) = channels.get_mut(&key) else {
+                return;
+            };
+            let dropped = std::mem::take(&mut channel.dropped);
+            (channel.take_pending(Instant::now()), dropped)
+        };
+
+        let (event, scope) = &key;
+        if dropped > 0 {
+            tracing::debug!(event = %event, scope = %scope, dropped = dropped, "Coalesced throttled events");
+        }
+        let result = match policy.coalesce {
+            Coalesce::Latest => match pending.last() {
+                Some(latest) => emit_now(&app, event, latest),
+                None => Ok(()),
+            },
+            Coalesce::Batch => emit_now(&app, &format!("{event}:batch"), &pending),
+        };
+        if let Err(e) = result {
+            tracing::warn!(event = %event, "Failed to flush throttled events: {e}");
+        }
+    }
+}
+
+fn emit_now<T: Serialize + ?Sized>(
+    app: &AppHandle,
+    event: &str,
+    payload: &T,
+) -> Result<(), AppError> {
+    app.emit(event, payload)
+        .map_err(|e| AppError::Internal(format!("Failed to emit {event}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_per_sec: u32, coalesce: Coalesce, max_batch: usize) -> ThrottlePolicy {
+        ThrottlePolicy {
+            max_per_sec,
+            coalesce,
+            max_batch,
+        }
+    }
+
+    #[test]
+    fn test_admit_within_window() {
+        let start = Instant::now();
+        let mut channel = ChannelState::new(start);
+        assert!(channel.admit(start, 2));
+        assert!(channel.admit(start, 2));
+        assert!(!channel.admit(start, 2));
+        assert!(channel.admit(start + WINDOW, 2));
+    }
+
+    #[test]
+    fn test_admit_waits_for_pending() {
+        let start = Instant::now();
+        let mut channel = ChannelState::new(start);
+        channel.hold(serde_json::json!(1), &policy(1, Coalesce::Batch, 10));
+        assert!(!channel.admit(start + WINDOW, 1));
+    }
+
+    #[test]
+    fn test_hold_latest_keeps_last() {
+        let mut channel = ChannelState::new(Instant::now());
+        let latest = policy(1, Coalesce::Latest, 10);
+        for i in 0..3 {
+            channel.hold(serde_json::json!(i), &latest);
+        }
+        assert_eq!(channel.pending, vec![serde_json::json!(2)]);
+        assert_eq!(channel.dropped, 2);
+    }
+
+    #[test]
+    fn test_hold_batch_caps_buffer() {
+        let mut channel = ChannelState::new(Instant::now());
+        let batch = policy(1, Coalesce::Batch, 2);
+        for i in 0..5 {
+            channel.hold(serde_json::json!(i), &batch);
+        }
+        assert_eq!(
+            channel.pending,
+            vec![serde_json::json!(3), serde_json::json!(4)]
+        );
+        assert_eq!(channel.dropped, 3);
+
+        let flushed = channel.take_pending(Instant::now());
+        assert_eq!(flushed.len(), 2);
+        assert!(channel.pending.is_empty());
+        assert_eq!(channel.sent, 1);
+    }
+
+    #[test]
+    fn test_validate_policies() {
+        assert!(validate_policies(&default_policies()).is_ok());
+        let mut policies = default_policies();
+        policies.insert("x".into(), policy(0, Coalesce::Latest, 1));
+        assert!(validate_policies(&policies).is_err());
+    }
+}
@@ -1,3 +1,9 @@
 // SPDX-License-Identifier: MIT
 
+pub mod chunked;
+pub mod crypto;
 pub mod errors;
+pub mod event_throttle;
+pub mod glob;
+pub mod progress;
+pub mod tasks;
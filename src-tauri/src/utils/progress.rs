@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// The one event every background operation reports progress on.
+pub const PROGRESS_EVENT: &str = "task:progress";
+
+/// Which stage a background operation is in.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgressPhase {
+    /// Walking the keyspace (SCAN, RANDOMKEY draws).
+    Scanning,
+    /// Reading or analyzing what was found.
+    Processing,
+    /// Writing to the server or to disk.
+    Writing,
+    /// The last event of a run that finished.
+    Done,
+    /// The last event of a run that stopped on an error (see `message`).
+    Failed,
+}
+
+/// Payload of `task:progress`, shared by scans, analyzers, exports and bulk
+/// operations so the frontend can render all background work the same way.
+///
+/// Operations that produce results (value search hits, benchmark figures,
+/// the replay report) send only the results on their own events; how far
+/// they are and how they ended is reported here.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressEvent {
+    /// The task, search, generation, replay or benchmark ID.
+    pub task_id: String,
+    /// What is running, e.g. `keyspaceSnapshot` or `generate`.
+    pub kind: String,
+    pub connection_id: Option<String>,
+    /// Units of work done so far (keys scanned, written, commands sent, ...).
+    pub processed: u64,
+    /// Total units, or an estimate of them, when known.
+    pub total: Option<u64>,
+    pub phase: ProgressPhase,
+    pub message: Option<String>,
+}
+
+impl ProgressEvent {
+    pub fn new(
+        task_id: &str,
+        kind: &str,
+        connection_id: Option<&str>,
+        phase: ProgressPhase,
+    ) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            kind: kind.to_string(),
+            connection_id: connection_id.map(str::to_string),
+            processed: 0,
+            total: None,
+            phase,
+            message: None,
+        }
+    }
+
+    /// Set how much is done out of `total`.
+    #[must_use]
+    pub fn processed(mut self, processed: u64, total: Option<u64>) -> Self {
+        self.processed = processed;
+        self.total = total;
+        self
+    }
+
+    #[must_use]
+    pub fn message(mut self, message: Option<String>) -> Self {
+        self.message = message;
+        self
+    }
+
+    /// The phase of a run's last event: `Failed` with the error as the
+    /// message, `Done` otherwise.
+    #[must_use]
+    pub fn finished(mut self, error: Option<String>) -> Self {
+        self.phase = if error.is_some() {
+            ProgressPhase::Failed
+        } else {
+            ProgressPhase::Done
+        };
+        self.message = error;
+        self
+    }
+
+    /// Send as `task:progress`.
+    pub fn emit(&self, app_handle: &AppHandle) {
+        let _ = app_handle.emit(PROGRESS_EVENT, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_event_serialization() {
+        let event = ProgressEvent::new("g1", "generate", Some("conn-1"), ProgressPhase::Writing)
+            .processed(250, Some(1_000));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["taskId"], "g1");
+        assert_eq!(json["connectionId"], "conn-1");
+        assert_eq!(json["processed"], 250);
+        assert_eq!(json["total"], 1_000);
+        assert_eq!(json["phase"], "writing");
+    }
+
+    #[test]
+    fn test_finished_phase() {
+        let done = ProgressEvent::new("t", "k", None, ProgressPhase::Scanning).finished(None);
+        assert_eq!(done.phase, ProgressPhase::Done);
+        assert!(done.message.is_none());
+
+        let failed = ProgressEvent::new("t", "k", None, ProgressPhase::Scanning)
+            .finished(Some("SCAN failed".into()));
+        assert_eq!(failed.phase, ProgressPhase::Failed);
+        assert_eq!(failed.message.as_deref(), Some("SCAN failed"));
+    }
+}
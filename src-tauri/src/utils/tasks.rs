@@ -0,0 +1,493 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+use tokio::task::{AbortHandle, JoinHandle};
+
+use crate::utils::errors::AppError;
+use crate::utils::progress::{ProgressEvent, ProgressPhase};
+
+/// Longest accepted task ID.
+const MAX_TASK_ID_CHARS: usize = 128;
+
+/// A running long-running command, as listed by `task_list`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    pub task_id: String,
+    /// What the task does, e.g. `keyspaceSnapshot`.
+    pub kind: String,
+    pub connection_id: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How a task ended.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskStatus {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Payload of `task:done`, emitted once per task however it ended.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDone {
+    pub task_id: String,
+    pub kind: String,
+    pub status: TaskStatus,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Emits `task:progress` for one task.
+#[derive(Clone)]
+pub struct TaskReporter {
+    app_handle: Option<AppHandle>,
+    task_id: String,
+    kind: String,
+    connection_id: Option<String>,
+}
+
+impl TaskReporter {
+    /// Report `processed` units of work (out of `total`, if known).
+    pub fn progress(
+        &self,
+        phase: ProgressPhase,
+        processed: u64,
+        total: Option<u64>,
+        message: Option<String>,
+    ) {
+        let Some(app_handle) = &self.app_handle else {
+            return;
+        };
+        ProgressEvent::new(
+            &self.task_id,
+            &self.kind,
+            self.connection_id.as_deref(),
+            phase,
+        )
+        .processed(processed, total)
+        .message(message)
+        .emit(app_handle);
+    }
+}
+
+struct RunningTask {
+    info: TaskInfo,
+    abort: AbortHandle,
+    /// Tells this run apart from a later one reusing its ID.
+    run: u64,
+}
+
+type Tasks = RwLock<HashMap<String, RunningTask>>;
+
+/// Long-running IPC commands that can be listed and cancelled by task ID.
+///
+/// The caller picks the ID and passes it with the command, so it can cancel
+/// while still awaiting the reply. A cancelled command fails; the task's
+/// `task:done` event says it was cancelled. Background jobs started with
+/// [`TaskRegistry::spawn`] return right away and are listed, cancelled and
+/// reported the same way.
+pub struct TaskRegistry {
+    tasks: Arc<Tasks>,
+    next_run: AtomicU64,
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self {
+            tasks: Arc::new(RwLock::new(HashMap::new())),
+            next_run: AtomicU64::new(0),
+        }
+    }
+
+    /// Run `work` as task `task_id`, emitting `task:done` when it ends.
+    ///
+    /// `work` gets a reporter for `task:progress` events. Without a task ID
+    /// the work simply runs, with no events and no way to cancel it. The
+    /// work's future is boxed here, so callers' futures stay small.
+    pub async fn run<T, F, Fut>(
+        &self,
+        app_handle: &AppHandle,
+        task_id: Option<String>,
+        kind: &str,
+        connection_id: Option<&str>,
+        work: F,
+    ) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(TaskReporter) -> Fut,
+        Fut: Future<Output = Result<T, AppError>> + Send + 'static,
+    {
+        let Some(task_id) = task_id else {
+            let reporter = TaskReporter {
+                app_handle: None,
+                task_id: String::new(),
+                kind: kind.to_string(),
+                connection_id: None,
+            };
+            return Box::pin(work(reporter)).await;
+        };
+
+        let reporter = reporter(app_handle, &task_id, kind, connection_id);
+        let started = Instant::now();
+        let (status, result) = self
+            .execute(
+                task_id.clone(),
+                kind,
+                connection_id,
+                Box::pin(work(reporter)),
+            )
+            .await;
+        emit_done(app_handle, task_id, kind, status, &result, started);
+        result
+    }
+
+    /// Start `work` as task `task_id` in the background, emitting
+    /// `task:done` when it ends. Returns once the task is registered; fails
+    /// without starting it if the ID is invalid or already running.
+    pub async fn spawn<F, Fut>(
+        &self,
+        app_handle: &AppHandle,
+        task_id: String,
+        kind: &str,
+        connection_id: Option<&str>,
+        work: F,
+    ) -> Result<(), AppError>
+    where
+        F: FnOnce(TaskReporter) -> Fut,
+        Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+    {
+        let reporter = reporter(app_handle, &task_id, kind, connection_id);
+        let started = Instant::now();
+        let app_handle = app_handle.clone();
+        let done_kind = kind.to_string();
+        self.start_detached(
+            task_id,
+            kind,
+            connection_id,
+            Box::pin(work(reporter)),
+            move |task_id, status, result| {
+                emit_done(&app_handle, task_id, &done_kind, status, &result, started);
+            },
+        )
+        .await
+    }
+
+    /// Register and spawn `work`, then hand how it ended to `on_done` from
+    /// a background task.
+    async fn start_detached<Fut>(
+        &self,
+        task_id: String,
+        kind: &str,
+        connection_id: Option<&str>,
+        work: Fut,
+        on_done: impl FnOnce(String, TaskStatus, Result<(), AppError>) + Send + 'static,
+    ) -> Result<(), AppError>
+    where
+        Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+    {
+        let (task, run) = self.start(&task_id, kind, connection_id, work).await?;
+        let tasks = Arc::clone(&self.tasks);
+        tokio::spawn(async move {
+            let (status, result) = finish(&tasks, &task_id, run, task).await;
+            on_done(task_id, status, result);
+        });
+        Ok(())
+    }
+
+    /// Spawn and register `work`, wait for it and say how it ended.
+    async fn execute<T, Fut>(
+        &self,
+        task_id: String,
+        kind: &str,
+        connection_id: Option<&str>,
+        work: Fut,
+    ) -> (TaskStatus, Result<T, AppError>)
+    where
+        T: Send + 'static,
+        Fut: Future<Output = Result<T, AppError>> + Send + 'static,
+    {
+        match self.start(&task_id, kind, connection_id, work).await {
+            Ok((task, run)) => finish(&self.tasks, &task_id, run, task).await,
+            Err(e) => (TaskStatus::Failed, Err(e)),
+        }
+    }
+
+    /// Register `work` as task `task_id` and spawn it. A duplicate ID is
+    /// refused before anything runs.
+    async fn start<T, Fut>(
+        &self,
+        task_id: &str,
+        kind: &str,
+        connection_id: Option<&str>,
+        work: Fut,
+    ) -> Result<(JoinHandle<Result<T, AppError>>, u64), AppError>
+    where
+        T: Send + 'static,
+        Fut: Future<Output = Result<T, AppError>> + Send + 'static,
+    {
+        validate_task_id(task_id)?;
+        // Spawn under the lock, so the task is registered before it can end
+        // or be cancelled.
+        let mut tasks = self.tasks.write().await;
+        if tasks.contains_key(task_id) {
+            return Err(AppError::InvalidInput(format!(
+                "Task {task_id} is already running"
+            )));
+        }
+        let run = self.next_run.fetch_add(1, Ordering::Relaxed);
+        let task = tokio::spawn(work);
+        let info = TaskInfo {
+            task_id: task_id.to_string(),
+            kind: kind.to_string(),
+            connection_id: connection_id.map(str::to_string),
+            started_at: chrono::Utc::now(),
+        };
+        tasks.insert(
+            task_id.to_string(),
+            RunningTask {
+                info,
+                abort: task.abort_handle(),
+                run,
+            },
+        );
+        Ok((task, run))
+    }
+
+    /// Abort a running task. Returns whether it was still running.
+    pub async fn cancel(&self, task_id: &str) -> bool {
+        match self.tasks.write().await.remove(task_id) {
+            Some(task) => {
+                task.abort.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Abort every running task (e.g., on app shutdown). Returns how many
+    /// were stopped.
+    pub async fn cancel_all(&self) -> usize {
+        let mut tasks = self.tasks.write().await;
+        let count = tasks.len();
+        for (_, task) in tasks.drain() {
+            task.abort.abort();
+        }
+        count
+    }
+
+    /// Running tasks, oldest first.
+    pub async fn list(&self) -> Vec<TaskInfo> {
+        let mut tasks: Vec<TaskInfo> = self
+            .tasks
+            .read()
+            .await
+            .values()
+            .map(|t| t.info.clone())
+            .collect();
+        tasks.sort_by_key(|t| t.started_at);
+        tasks
+    }
+}
+
+fn reporter(
+    app_handle: &AppHandle,
+    task_id: &str,
+    kind: &str,
+    connection_id: Option<&str>,
+) -> TaskReporter {
+    TaskReporter {
+        app_handle: Some(app_handle.clone()),
+        task_id: task_id.to_string(),
+        kind: kind.to_string(),
+        connection_id: connection_id.map(str::to_string),
+    }
+}
+
+/// Wait for a started task, deregister it and say how it ended. A task
+/// cancelled meanwhile is already deregistered, and its ID may have been
+/// reused by a newer run, which stays registered.
+async fn finish<T>(
+    tasks: &Tasks,
+    task_id: &str,
+    run: u64,
+    task: JoinHandle<Result<T, AppError>>,
+) -> (TaskStatus, Result<T, AppError>) {
+    let outcome = task.await;
+    {
+        let mut tasks = tasks.write().await;
+        if tasks.get(task_id).is_some_and(|t| t.run == run) {
+            tasks.remove(task_id);
+        }
+    }
+    match outcome {
+        Ok(Ok(value)) => (TaskStatus::Completed, Ok(value)),
+        Ok(Err(e)) => (TaskStatus::Failed, Err(e)),
+        Err(e) if e.is_cancelled() => (
+            TaskStatus::Cancelled,
+            Err(AppError::Internal("Task was cancelled".into())),
+        ),
+        Err(e) => (
+            TaskStatus::Failed,
+            Err(AppError::Internal(format!("Task failed: {e}"))),
+        ),
+    }
+}
+
+fn emit_done<T>(
+    app_handle: &AppHandle,
+    task_id: String,
+    kind: &str,
+    status: TaskStatus,
+    result: &Result<T, AppError>,
+    started: Instant,
+) {
+    let done = TaskDone {
+        task_id,
+        kind: kind.to_string(),
+        status,
+        error: result.as_ref().err().map(ToString::to_string),
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    };
+    if status != TaskStatus::Completed {
+        tracing::info!(task_id = %done.task_id, kind = %kind, ?status, "Task ended");
+    }
+    let _ = app_handle.emit("task:done", &done);
+}
+
+fn validate_task_id(task_id: &str) -> Result<(), AppError> {
+    if task_id.trim().is_empty() || task_id.chars().count() > MAX_TASK_ID_CHARS {
+        return Err(AppError::InvalidInput(format!(
+            "Task ID must be 1 to {MAX_TASK_ID_CHARS} characters"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancel_aborts_running_task() {
+        let tasks = Arc::new(TaskRegistry::new());
+        let runner = tasks.clone();
+        let running = tokio::spawn(async move {
+            runner
+                .execute("t1".into(), "backup", Some("conn-1"), async {
+                    tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                    Ok(())
+                })
+                .await
+        });
+
+        // Wait for the task to register.
+        while tasks.list().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let listed = tasks.list().await;
+        assert_eq!(listed[0].task_id, "t1");
+        assert_eq!(listed[0].connection_id.as_deref(), Some("conn-1"));
+
+        assert!(tasks.cancel("t1").await);
+        let (status, result) = running.await.unwrap();
+        assert_eq!(status, TaskStatus::Cancelled);
+        assert!(result.is_err());
+        assert!(!tasks.cancel("t1").await);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_id_is_refused_before_running() {
+        let tasks = Arc::new(TaskRegistry::new());
+        let runner = tasks.clone();
+        let first = tokio::spawn(async move {
+            runner
+                .execute("t4".into(), "k", None, async {
+                    tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                    Ok(())
+                })
+                .await
+        });
+        while tasks.list().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = ran.clone();
+        let (status, result) = tasks
+            .execute("t4".into(), "k", None, async move {
+                flag.store(true, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+        assert_eq!(status, TaskStatus::Failed);
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        assert!(!ran.load(Ordering::SeqCst));
+
+        // The first run is still registered and cancellable.
+        assert!(tasks.cancel("t4").await);
+        assert_eq!(first.await.unwrap().0, TaskStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_detached_task_deregisters_when_done() {
+        let tasks = TaskRegistry::new();
+        let noop = |_: String, _: TaskStatus, _: Result<(), AppError>| {};
+        tasks
+            .start_detached("s1".into(), "k", None, std::future::pending(), noop)
+            .await
+            .unwrap();
+        assert!(tasks
+            .start_detached("s1".into(), "k", None, async { Ok(()) }, noop)
+            .await
+            .is_err());
+        assert!(tasks.cancel("s1").await);
+        assert!(!tasks.cancel("s1").await);
+
+        let (sender, ended) = tokio::sync::oneshot::channel();
+        tasks
+            .start_detached("s2".into(), "k", None, async { Ok(()) }, |_, status, _| {
+                let _ = sender.send(status);
+            })
+            .await
+            .unwrap();
+        assert_eq!(ended.await.unwrap(), TaskStatus::Completed);
+        assert!(tasks.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_outcome() {
+        let tasks = TaskRegistry::new();
+        let (status, result) = tasks.execute("t2".into(), "k", None, async { Ok(7) }).await;
+        assert_eq!(status, TaskStatus::Completed);
+        assert_eq!(result.unwrap(), 7);
+
+        let (status, result): (_, Result<(), _>) = tasks
+            .execute("t3".into(), "k", None, async {
+                Err(AppError::NotFound("gone".into()))
+            })
+            .await;
+        assert_eq!(status, TaskStatus::Failed);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let (status, _) = tasks.execute(" ".into(), "k", None, async { Ok(()) }).await;
+        assert_eq!(status, TaskStatus::Failed);
+        assert!(tasks.list().await.is_empty());
+    }
+}
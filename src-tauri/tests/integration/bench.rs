@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::Instant;
+
+use redis_lens_lib::redis::browser::{keyspace, scanner};
+
+use crate::harness::{self, seed_strings};
+
+/// Key count for the throughput runs.
+const BENCH_KEYS: usize = 100_000;
+
+/// Full-keyspace SCAN plus metadata pipeline, and a keyspace snapshot.
+///
+/// Ignored by default; run with `--ignored --nocapture` to print timings.
+#[tokio::test]
+#[ignore = "benchmark"]
+async fn bench_scan_and_snapshot() {
+    let redis = harness::standalone().await;
+    for batch in 0..10 {
+        seed_strings(&redis.pool, &format!("bench{batch}"), BENCH_KEYS / 10).await;
+    }
+
+    let started = Instant::now();
+    let mut cursor = 0;
+    let mut seen = 0;
+    loop {
+        let page = scanner::scan_keys(&redis.pool, cursor, "*", 1000)
+            .await
+            .unwrap();
+        scanner::get_keys_info(&redis.pool, &page.keys, None, 4)
+            .await
+            .unwrap();
+        seen += page.keys.len();
+        if page.finished {
+            break;
+        }
+        cursor = page.cursor;
+    }
+    println!("scan + keys_info: {seen} keys in {:?}", started.elapsed());
+
+    let throttle = redis.manager.get_throttle(&redis.id).await.unwrap();
+    let connection_id = redis.id.to_string();
+    let started = Instant::now();
+    let (snapshot, keys) =
+        keyspace::capture_snapshot(&redis.pool, &throttle, &connection_id, ":", None)
+            .await
+            .unwrap();
+    println!(
+        "keyspace snapshot: {} keys in {:?}",
+        snapshot.total_keys,
+        started.elapsed()
+    );
+    assert_eq!(snapshot.total_keys as usize, BENCH_KEYS);
+    assert_eq!(keys.len(), BENCH_KEYS.min(keyspace::MAX_INDEXED_KEYS));
+}
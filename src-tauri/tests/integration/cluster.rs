@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT
+
+use redis_lens_lib::redis::browser::scanner;
+use redis_lens_lib::redis::cli::executor;
+use redis_lens_lib::redis::cli::model::CommandResult;
+
+use crate::harness::{self, exec};
+
+#[tokio::test]
+async fn test_cluster_node_is_browsable() {
+    let redis = harness::cluster().await;
+
+    let info: String = exec(&redis.pool, redis::cmd("CLUSTER").arg("INFO")).await;
+    assert!(info.contains("cluster_state:ok"));
+
+    // Only keys owned by this node's slots are visible through its pool.
+    let slot: u16 = exec(&redis.pool, redis::cmd("CLUSTER").arg("KEYSLOT").arg("{a}")).await;
+    let mine = exec::<Vec<Vec<redis::Value>>>(&redis.pool, redis::cmd("CLUSTER").arg("SLOTS"))
+        .await
+        .into_iter()
+        .any(|range| {
+            let bound = |i: usize| match range.get(i) {
+                Some(redis::Value::Int(n)) => *n,
+                _ => -1,
+            };
+            let port = match range.get(2) {
+                Some(redis::Value::Array(node)) => match node.get(1) {
+                    Some(redis::Value::Int(p)) => *p,
+                    _ => 0,
+                },
+                _ => 0,
+            };
+            port == 7000 && (bound(0)..=bound(1)).contains(&i64::from(slot))
+        });
+
+    if mine {
+        exec::<()>(&redis.pool, redis::cmd("SET").arg("{a}:1").arg("v")).await;
+        let page = scanner::scan_keys(&redis.pool, 0, "{a}:*", 1000)
+            .await
+            .unwrap();
+        assert!(page.keys.contains(&"{a}:1".to_string()));
+    } else {
+        let mut conn = redis.pool.get().await.unwrap();
+        let err = redis::cmd("SET")
+            .arg("{a}:1")
+            .arg("v")
+            .query_async::<()>(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("MOVED"));
+    }
+}
+
+#[tokio::test]
+async fn test_cli_annotates_and_follows_moved() {
+    let redis = harness::cluster().await;
+
+    // Find a key owned by another node; about two thirds of them are.
+    let mut moved = None;
+    for n in 0..100 {
+        let command = format!("SET redirect:{n} v");
+        let response = executor::execute(&redis.pool, &command, true, false, None, None)
+            .await
+            .unwrap();
+        if response.redirect.is_some() {
+            moved = Some((command, response));
+            break;
+        }
+    }
+    let (command, response) = moved.expect("a key on another node");
+    let redirect = response.redirect.unwrap();
+    assert_eq!(redirect.kind, "MOVED");
+    assert!(!redirect.followed);
+    assert!(matches!(response.result, CommandResult::Error(ref e) if e.contains("MOVED")));
+
+    let followed = executor::execute(&redis.pool, &command, true, false, None, Some(&redis.url))
+        .await
+        .unwrap();
+    let redirect = followed.redirect.unwrap();
+    assert!(redirect.followed);
+    assert!(matches!(followed.result, CommandResult::Ok(_)));
+}
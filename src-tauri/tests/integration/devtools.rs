@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MIT
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use redis_lens_lib::redis::devtools::model::{
+    BenchCommand, BenchSpec, BenchWeight, GeneratedType, GeneratorSpec, ReplaySpec, TtlSpec,
+};
+use redis_lens_lib::redis::devtools::tracking::TrackingSessions;
+use redis_lens_lib::redis::devtools::{benchmark, generator, replay};
+
+use crate::harness::{self, exec};
+
+#[tokio::test]
+async fn test_generate_writes_requested_keys() {
+    let redis = harness::standalone().await;
+    let spec = GeneratorSpec {
+        key_type: GeneratedType::Hash,
+        count: 2_500,
+        key_template: "gen:{n}".into(),
+        start: 0,
+        value_bytes: 16,
+        elements: 5,
+        score_min: 0.0,
+        score_max: 1.0,
+        ttl: Some(TtlSpec {
+            percent: 100,
+            min_secs: 600,
+            max_secs: 900,
+        }),
+        seed: Some(42),
+    };
+
+    let mut batches = 0;
+    let written = generator::generate(&redis.pool, &spec, |_| batches += 1)
+        .await
+        .unwrap();
+    assert_eq!(written, 2_500);
+    assert!(batches > 1);
+
+    let size: u64 = exec(&redis.pool, &mut redis::cmd("DBSIZE")).await;
+    assert_eq!(size, 2_500);
+    let fields: u64 = exec(&redis.pool, redis::cmd("HLEN").arg("gen:2499")).await;
+    assert_eq!(fields, 5);
+    let ttl: i64 = exec(&redis.pool, redis::cmd("TTL").arg("gen:0")).await;
+    assert!((1..=900).contains(&ttl));
+
+    // Regenerating replaces collections rather than growing them.
+    generator::generate(&redis.pool, &spec, |_| {})
+        .await
+        .unwrap();
+    let fields: u64 = exec(&redis.pool, redis::cmd("HLEN").arg("gen:0")).await;
+    assert_eq!(fields, 5);
+}
+
+#[tokio::test]
+async fn test_benchmark_reports_and_cleans_up() {
+    let redis = harness::standalone().await;
+    let spec = BenchSpec {
+        mix: vec![
+            BenchWeight {
+                command: BenchCommand::Set,
+                weight: 1,
+            },
+            BenchWeight {
+                command: BenchCommand::Get,
+                weight: 1,
+            },
+        ],
+        clients: 2,
+        pipeline: 4,
+        duration_secs: 2,
+        keyspace: 100,
+        value_bytes: 8,
+    };
+    let conns = benchmark::connect(&redis.url, spec.clients, None)
+        .await
+        .unwrap();
+
+    let mut ticks = 0;
+    let (report, error) = benchmark::run(
+        conns,
+        spec,
+        Arc::new(AtomicBool::new(false)),
+        ("bench-1".into(), redis.id.to_string()),
+        |_| ticks += 1,
+    )
+    .await;
+    assert_eq!(error, None);
+    assert!(report.total_ops > 0);
+    assert_eq!(report.errors, 0);
+    assert!(report.latency.p50_us <= report.latency.p99_us);
+    assert!(ticks >= 1);
+
+    let size: u64 = exec(&redis.pool, &mut redis::cmd("DBSIZE")).await;
+    assert_eq!(size, 0);
+}
+
+#[tokio::test]
+async fn test_tracking_counts_invalidations_per_key() {
+    let redis = harness::standalone().await;
+    let sessions = TrackingSessions::new();
+    sessions
+        .start(redis.id, &redis.url, vec!["track:".into()])
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        let _: () = exec(&redis.pool, redis::cmd("SET").arg("track:hot").arg("v")).await;
+    }
+    let _: () = exec(&redis.pool, redis::cmd("SET").arg("track:cold").arg("v")).await;
+    let _: () = exec(&redis.pool, redis::cmd("SET").arg("other").arg("v")).await;
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let stats = sessions.stop(&redis.id).await.unwrap();
+    assert_eq!(stats.total_invalidations, 4);
+    assert_eq!(stats.distinct_keys, 2);
+    assert_eq!(stats.top_keys[0].key, "track:hot");
+    assert_eq!(stats.top_keys[0].count, 3);
+    assert!(sessions.stats(&redis.id, None).await.is_none());
+}
+
+#[tokio::test]
+async fn test_replay_sends_checked_commands_and_collects_errors() {
+    let redis = harness::standalone().await;
+    let data = b"*3\r\n$3\r\nSET\r\n$8\r\nreplay:a\r\n$1\r\n1\r\n\
+*2\r\n$4\r\nINCR\r\n$8\r\nreplay:a\r\n\
+*1\r\n$8\r\nFLUSHALL\r\n\
+*3\r\n$5\r\nLPUSH\r\n$8\r\nreplay:a\r\n$1\r\nx\r\n";
+    let file = replay::parse(data, None).unwrap();
+    let catalog = redis.manager.get_command_catalog(&redis.id).await.unwrap();
+    let policy = redis.manager.get_command_policy(&redis.id).await.unwrap();
+    let (runnable, mut report) =
+        replay::check(&file.commands, false, &policy, &catalog, file.truncated);
+    assert_eq!(report.rejected, 1);
+
+    let spec = ReplaySpec {
+        path: "inline".into(),
+        format: None,
+        rate_per_sec: Some(1_000),
+        stop_on_error: false,
+    };
+    let mut conn = benchmark::connect(&redis.url, 1, None)
+        .await
+        .unwrap()
+        .remove(0);
+    replay::run(&mut conn, &runnable, &spec, &mut report, |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(report.sent, 3);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.errors.len(), 2);
+    assert!(report.errors[1].error.contains("WRONGTYPE"));
+    let value: i64 = exec(&redis.pool, redis::cmd("GET").arg("replay:a")).await;
+    assert_eq!(value, 2);
+}
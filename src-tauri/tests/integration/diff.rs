@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT
+
+use redis_lens_lib::redis::diff::model::DiffStatus;
+use redis_lens_lib::redis::diff::reader::{self, DiffTarget};
+
+use crate::harness::{self, exec};
+
+#[tokio::test]
+async fn test_diff_between_databases() {
+    let redis = harness::standalone().await;
+    let left = redis.pool.clone();
+    let right = redis
+        .manager
+        .get_pool_for_db(&redis.id, Some(1))
+        .await
+        .unwrap();
+
+    for (pool, plan, seats) in [(&left, "pro", "5"), (&right, "free", "5")] {
+        exec::<()>(
+            pool,
+            redis::cmd("HSET")
+                .arg("acct:1")
+                .arg("plan")
+                .arg(plan)
+                .arg("seats")
+                .arg(seats),
+        )
+        .await;
+        exec::<()>(pool, redis::cmd("SET").arg("acct:same").arg("v")).await;
+    }
+    exec::<()>(&left, redis::cmd("SET").arg("acct:gone").arg("v")).await;
+    exec::<()>(&right, redis::cmd("SADD").arg("acct:new").arg("a")).await;
+
+    let target = DiffTarget::Pattern {
+        pattern: "acct:*".into(),
+        max_keys: 100,
+    };
+    let report = reader::diff(&left, &right, &target, None).await.unwrap();
+    assert_eq!(report.keys_compared, 4);
+    assert_eq!(
+        (
+            report.equal,
+            report.different,
+            report.only_left,
+            report.only_right
+        ),
+        (1, 1, 1, 1)
+    );
+    let changed = &report.diffs[0];
+    assert_eq!(changed.key, "acct:1");
+    assert_eq!(changed.status, DiffStatus::Different);
+    assert_eq!(changed.changes.len(), 1);
+    assert_eq!(changed.changes[0].path.as_deref(), Some("plan"));
+
+    exec::<()>(&right, redis::cmd("EXPIRE").arg("acct:same").arg(600)).await;
+    let report = reader::diff(&left, &right, &DiffTarget::Key("acct:same".into()), None)
+        .await
+        .unwrap();
+    assert!(report.diffs[0].ttl.is_some());
+}
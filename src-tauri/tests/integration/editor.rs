@@ -0,0 +1,524 @@
+// SPDX-License-Identifier: MIT
+
+use redis_lens_lib::redis::connection::durability;
+use redis_lens_lib::redis::connection::model::WriteConcern;
+use redis_lens_lib::redis::editor::model::{
+    BinaryEncoding, ExpectedValue, HashField, ValueMatch, ValueSearchMode, ZRangeBy, ZRangeQuery,
+};
+use redis_lens_lib::redis::editor::undo::{self, UndoJournal};
+use redis_lens_lib::redis::editor::{
+    cas, hash_ops, key_watch, list_ops, search_ops, stream_ops, string_ops, string_stream, zset_ops,
+};
+use redis_lens_lib::utils::errors::AppError;
+
+use crate::harness::{self, exec};
+
+#[tokio::test]
+async fn test_string_roundtrip_and_binary_detection() {
+    let redis = harness::standalone().await;
+    string_ops::set_string_value(&redis.pool, "greeting", "hello", Some(60))
+        .await
+        .unwrap();
+    let value = string_ops::get_string_value(&redis.pool, "greeting")
+        .await
+        .unwrap();
+    assert_eq!(value.text.as_deref(), Some("hello"));
+    assert!(!value.is_binary);
+
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("SET").arg("blob").arg(vec![0u8, 159, 146, 150]),
+    )
+    .await;
+    let blob = string_ops::get_string_value(&redis.pool, "blob")
+        .await
+        .unwrap();
+    assert!(blob.is_binary);
+    assert!(blob.base64.is_some());
+}
+
+#[tokio::test]
+async fn test_string_counters_and_patches() {
+    let redis = harness::standalone().await;
+    assert_eq!(
+        string_ops::incr_string(&redis.pool, "n", 5).await.unwrap(),
+        5
+    );
+    assert_eq!(
+        string_ops::incr_string(&redis.pool, "n", -7).await.unwrap(),
+        -2
+    );
+    let f = string_ops::incr_string_float(&redis.pool, "n", 0.5)
+        .await
+        .unwrap();
+    assert!((f + 1.5).abs() < f64::EPSILON);
+
+    assert_eq!(
+        string_ops::append_string(&redis.pool, "s", "hello")
+            .await
+            .unwrap(),
+        5
+    );
+    assert_eq!(
+        string_ops::set_string_range(&redis.pool, "s", 1, b"EL")
+            .await
+            .unwrap(),
+        5
+    );
+    let value: String = exec(&redis.pool, redis::cmd("GET").arg("s")).await;
+    assert_eq!(value, "hELlo");
+}
+
+#[tokio::test]
+async fn test_binary_string_round_trip() {
+    let redis = harness::standalone().await;
+    let bytes = BinaryEncoding::Hex.decode("00 ff fe 80 00").unwrap();
+    string_ops::set_string_bytes(&redis.pool, "bin", &bytes, None)
+        .await
+        .unwrap();
+    string_ops::set_string_range(&redis.pool, "bin", 4, &[0xc3, 0x28])
+        .await
+        .unwrap();
+
+    let value = string_ops::get_string_value(&redis.pool, "bin")
+        .await
+        .unwrap();
+    assert!(value.is_binary);
+    assert_eq!(
+        BinaryEncoding::Base64
+            .decode(value.base64.as_deref().unwrap())
+            .unwrap(),
+        [0x00, 0xff, 0xfe, 0x80, 0xc3, 0x28]
+    );
+}
+
+#[tokio::test]
+async fn test_stream_string_in_chunks() {
+    let redis = harness::standalone().await;
+    let value: Vec<u8> = (0..=255u8).cycle().take(10_000).collect();
+    string_ops::set_string_bytes(&redis.pool, "big", &value, None)
+        .await
+        .unwrap();
+
+    let total = string_stream::string_length(&redis.pool, "big")
+        .await
+        .unwrap();
+    assert_eq!(total, 10_000);
+    let range = string_stream::byte_range(total, 100, Some(5000));
+    let mut chunks = Vec::new();
+    let read = string_stream::stream_string(&redis.pool, "big", range, 1024, |offset, bytes| {
+        chunks.push((offset, bytes));
+    })
+    .await
+    .unwrap();
+    assert_eq!(read, 5000);
+    assert_eq!(chunks.len(), 5);
+    assert_eq!(chunks[1].0, 1124);
+    let joined: Vec<u8> = chunks.into_iter().flat_map(|(_, bytes)| bytes).collect();
+    assert_eq!(joined, value[100..5100]);
+
+    assert!(string_stream::string_length(&redis.pool, "missing")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_key_fingerprints_track_changes() {
+    let redis = harness::standalone().await;
+    let keys = vec![
+        "fp:s".to_string(),
+        "fp:l".to_string(),
+        "fp:none".to_string(),
+    ];
+    exec::<()>(&redis.pool, redis::cmd("SET").arg("fp:s").arg("abc")).await;
+    exec::<()>(&redis.pool, redis::cmd("RPUSH").arg("fp:l").arg("a")).await;
+
+    let before = key_watch::fingerprint_keys(&redis.pool, &keys)
+        .await
+        .unwrap();
+    assert_eq!(before[0].as_ref().unwrap().key_type, "string");
+    assert_eq!(before[1].as_ref().unwrap().length, 1);
+    assert!(before[2].is_none());
+
+    // Same length, different content: caught by the string digest.
+    exec::<()>(&redis.pool, redis::cmd("SET").arg("fp:s").arg("xyz")).await;
+    exec::<()>(&redis.pool, redis::cmd("EXPIRE").arg("fp:l").arg(100)).await;
+    let after = key_watch::fingerprint_keys(&redis.pool, &keys)
+        .await
+        .unwrap();
+    assert!(before[0]
+        .as_ref()
+        .unwrap()
+        .differs(after[0].as_ref().unwrap()));
+    assert!(before[1]
+        .as_ref()
+        .unwrap()
+        .differs(after[1].as_ref().unwrap()));
+}
+
+#[tokio::test]
+async fn test_compare_and_set_writes() {
+    let redis = harness::standalone().await;
+    string_ops::set_string_value(&redis.pool, "cas", "v1", Some(60))
+        .await
+        .unwrap();
+
+    let stale = ExpectedValue::Value("v0".into());
+    let err = cas::set_string_value(&redis.pool, "cas", "v2", None, &stale)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Conflict(_)));
+
+    // SHA-1 of "v1".
+    let digest = ExpectedValue::Sha1("5a6df720540c20d95d530d3fd6885511223d5d20".into());
+    cas::set_string_value(&redis.pool, "cas", "v2", None, &digest)
+        .await
+        .unwrap();
+    assert!(
+        cas::set_string_value(&redis.pool, "cas", "v3", None, &digest)
+            .await
+            .is_err()
+    );
+    let ttl: i64 = exec(&redis.pool, redis::cmd("TTL").arg("cas")).await;
+    assert!(ttl > 0, "KEEPTTL preserves the expiry");
+
+    hash_ops::set_hash_field(&redis.pool, "cas:h", "f", "a")
+        .await
+        .unwrap();
+    cas::set_hash_field(
+        &redis.pool,
+        "cas:h",
+        "f",
+        "b",
+        &ExpectedValue::Value("a".into()),
+    )
+    .await
+    .unwrap();
+    assert!(cas::set_hash_field(
+        &redis.pool,
+        "cas:h",
+        "f",
+        "c",
+        &ExpectedValue::Value("a".into())
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_collection_ops() {
+    let redis = harness::standalone().await;
+
+    assert!(hash_ops::set_hash_field(&redis.pool, "h", "f1", "v1")
+        .await
+        .unwrap());
+    hash_ops::set_hash_field(&redis.pool, "h", "f2", "v2")
+        .await
+        .unwrap();
+    assert_eq!(
+        hash_ops::get_hash_all(&redis.pool, "h")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    let bulk = [
+        HashField {
+            field: "f2".into(),
+            value: "v2b".into(),
+        },
+        HashField {
+            field: "n".into(),
+            value: "10".into(),
+        },
+    ];
+    assert_eq!(
+        hash_ops::set_hash_fields(&redis.pool, "h", &bulk)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        hash_ops::incr_hash_field(&redis.pool, "h", "n", 5)
+            .await
+            .unwrap(),
+        15
+    );
+    let picked = hash_ops::get_hash_fields(&redis.pool, "h", &["f2".into(), "gone".into()])
+        .await
+        .unwrap();
+    assert_eq!(picked[0].value.as_deref(), Some("v2b"));
+    assert!(picked[1].value.is_none());
+    assert_eq!(
+        hash_ops::random_hash_fields(&redis.pool, "h", 10)
+            .await
+            .unwrap()
+            .len(),
+        3
+    );
+
+    list_ops::push_list_element(&redis.pool, "l", "b", true)
+        .await
+        .unwrap();
+    list_ops::push_list_element(&redis.pool, "l", "a", true)
+        .await
+        .unwrap();
+    let list = list_ops::get_list_range(&redis.pool, "l", 0, -1)
+        .await
+        .unwrap();
+    assert_eq!(list[0].value, "a");
+    assert_eq!(list[1].index, 1);
+
+    list_ops::insert_list_element(&redis.pool, "l", "b", "x", true)
+        .await
+        .unwrap();
+    list_ops::push_list_element(&redis.pool, "l", "x", false)
+        .await
+        .unwrap();
+    assert_eq!(
+        list_ops::list_positions(&redis.pool, "l", "x", None, 0, None)
+            .await
+            .unwrap(),
+        [1, 3]
+    );
+    assert!(
+        list_ops::insert_list_element(&redis.pool, "l", "nope", "y", false)
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        list_ops::delete_list_index(&redis.pool, "l", -1)
+            .await
+            .unwrap(),
+        "x"
+    );
+    let list = list_ops::get_list_range(&redis.pool, "l", 0, -1)
+        .await
+        .unwrap();
+    let values: Vec<_> = list.iter().map(|e| e.value.as_str()).collect();
+    assert_eq!(values, ["a", "x", "b"]);
+    assert!(list_ops::delete_list_index(&redis.pool, "l", 10)
+        .await
+        .is_err());
+
+    zset_ops::add_zset_member(&redis.pool, "z", "low", 1.0)
+        .await
+        .unwrap();
+    zset_ops::add_zset_member(&redis.pool, "z", "high", 9.0)
+        .await
+        .unwrap();
+    let range = zset_ops::get_zset_range(&redis.pool, "z", 0, -1)
+        .await
+        .unwrap();
+    assert_eq!(range[1].member, "high");
+    assert_eq!(zset_ops::zset_card(&redis.pool, "z").await.unwrap(), 2);
+
+    zset_ops::add_zset_member(&redis.pool, "z", "mid", 5.0)
+        .await
+        .unwrap();
+    let query = ZRangeQuery {
+        by: ZRangeBy::Score,
+        min: "(1".into(),
+        max: "+inf".into(),
+        rev: true,
+        offset: Some(0),
+        count: Some(1),
+    };
+    let top = zset_ops::get_zset_range_by(&redis.pool, "z", &query)
+        .await
+        .unwrap();
+    assert_eq!(top[0].member, "high");
+    assert_eq!(top.len(), 1);
+    let rank = zset_ops::get_zset_member_rank(&redis.pool, "z", "mid")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((rank.rank, rank.rev_rank, rank.score), (1, 1, 5.0));
+    assert!(zset_ops::get_zset_member_rank(&redis.pool, "z", "none")
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        zset_ops::zset_count(&redis.pool, "z", ZRangeBy::Score, "2", "10")
+            .await
+            .unwrap(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_stream_range() {
+    let redis = harness::standalone().await;
+    for n in 0..5 {
+        exec::<String>(
+            &redis.pool,
+            redis::cmd("XADD").arg("events").arg("*").arg("n").arg(n),
+        )
+        .await;
+    }
+    let page = stream_ops::get_stream_range(&redis.pool, "events", "-", "+", 3)
+        .await
+        .unwrap();
+    assert_eq!(page.total_length, 5);
+    assert_eq!(page.entries.len(), 3);
+    assert_eq!(
+        page.entries[0].fields,
+        vec![("n".to_string(), "0".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_undo_restores_prior_value() {
+    let redis = harness::standalone().await;
+    let journal = UndoJournal::new();
+    let conn_id = redis.id.to_string();
+
+    string_ops::set_string_value(&redis.pool, "k", "before", None)
+        .await
+        .unwrap();
+    let prior = undo::capture(&redis.pool, "k").await;
+    assert!(prior.is_some());
+    string_ops::set_string_value(&redis.pool, "k", "after", None)
+        .await
+        .unwrap();
+    journal.record(&conn_id, "k", "setStringValue", prior).await;
+
+    let prior = undo::capture(&redis.pool, "fresh").await;
+    assert!(prior.is_some());
+    hash_ops::set_hash_field(&redis.pool, "fresh", "f", "v")
+        .await
+        .unwrap();
+    journal
+        .record(&conn_id, "fresh", "setHashField", prior)
+        .await;
+
+    // Undo the newer change first: the key did not exist, so it is deleted.
+    assert_eq!(journal.last_key(&conn_id).await.as_deref(), Some("fresh"));
+    let entry = journal.pop_last_if(&conn_id, "fresh").await.unwrap();
+    undo::restore(&redis.pool, &entry).await.unwrap();
+    let exists: bool = exec(&redis.pool, redis::cmd("EXISTS").arg("fresh")).await;
+    assert!(!exists);
+
+    assert!(journal.pop_last_if(&conn_id, "fresh").await.is_none());
+    let entry = journal.pop_last_if(&conn_id, "k").await.unwrap();
+    undo::restore(&redis.pool, &entry).await.unwrap();
+    let value: String = exec(&redis.pool, redis::cmd("GET").arg("k")).await;
+    assert_eq!(value, "before");
+}
+
+#[tokio::test]
+async fn test_write_concern_pins_writes_and_waits() {
+    let redis = harness::standalone().await;
+    let concern = WriteConcern {
+        replicas: 1,
+        timeout_ms: 100,
+    };
+    redis
+        .manager
+        .set_write_concern(&redis.id, Some(concern))
+        .await
+        .unwrap();
+
+    let pool = redis.manager.get_write_pool(&redis.id).await.unwrap();
+    assert_eq!(pool.status().max_size, 1);
+    string_ops::set_string_value(&pool, "durable", "v", None)
+        .await
+        .unwrap();
+    // A standalone server has no replicas, so WAIT times out with none.
+    let acknowledged = durability::wait_for_replicas(&pool, &concern)
+        .await
+        .unwrap();
+    assert_eq!(acknowledged, 0);
+
+    redis
+        .manager
+        .set_write_concern(&redis.id, None)
+        .await
+        .unwrap();
+    let pool = redis.manager.get_write_pool(&redis.id).await.unwrap();
+    assert!(pool.status().max_size > 1);
+}
+
+#[tokio::test]
+async fn test_search_in_key_by_type() {
+    let redis = harness::standalone().await;
+    let mut list = redis::cmd("RPUSH");
+    list.arg("log");
+    for n in 0..2_500 {
+        list.arg(if n % 1_000 == 7 {
+            format!("error {n}")
+        } else {
+            format!("ok {n}")
+        });
+    }
+    exec::<()>(&redis.pool, &mut list).await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("ZADD")
+            .arg("board")
+            .arg(1)
+            .arg("ann")
+            .arg(2)
+            .arg("bob")
+            .arg(3)
+            .arg("anna"),
+    )
+    .await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("XADD")
+            .arg("events")
+            .arg("*")
+            .arg("level")
+            .arg("warn")
+            .arg("msg")
+            .arg("disk"),
+    )
+    .await;
+
+    let found =
+        search_ops::search_in_key(&redis.pool, "log", "error", ValueSearchMode::Substring, 100)
+            .await
+            .unwrap();
+    assert_eq!(found.scanned, 2_500);
+    let indexes: Vec<i64> = found
+        .matches
+        .iter()
+        .map(|m| match m {
+            ValueMatch::ListElement { index, .. } => *index,
+            other => panic!("unexpected {other:?}"),
+        })
+        .collect();
+    assert_eq!(indexes, [7, 1_007, 2_007]);
+
+    let exact = search_ops::search_in_key(&redis.pool, "log", "ok 3", ValueSearchMode::Exact, 100)
+        .await
+        .unwrap();
+    assert_eq!(
+        exact.matches,
+        [ValueMatch::ListElement {
+            index: 3,
+            value: "ok 3".into()
+        }]
+    );
+
+    let ranked = search_ops::search_in_key(&redis.pool, "board", "ann*", ValueSearchMode::Glob, 1)
+        .await
+        .unwrap();
+    assert_eq!(ranked.matches.len(), 1);
+    assert!(matches!(
+        ranked.matches[0],
+        ValueMatch::ZsetMember { rank: Some(_), .. }
+    ));
+
+    let stream = search_ops::search_in_key(
+        &redis.pool,
+        "events",
+        "disk",
+        ValueSearchMode::Substring,
+        10,
+    )
+    .await
+    .unwrap();
+    assert!(matches!(&stream.matches[0], ValueMatch::StreamEntry { field, .. } if field == "msg"));
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: MIT
+
+use deadpool_redis::Pool;
+use redis_lens_lib::redis::connection::manager::ConnectionManager;
+use redis_lens_lib::redis::connection::model::ConnectionProfile;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use uuid::Uuid;
+
+/// Redis image used for standalone servers.
+const REDIS_IMAGE: (&str, &str) = ("redis", "7.2-alpine");
+/// Single-container six-node cluster listening on 7000-7005.
+const CLUSTER_IMAGE: (&str, &str) = ("grokzen/redis-cluster", "7.0.10");
+const CLUSTER_PORTS: std::ops::RangeInclusive<u16> = 7000..=7005;
+
+/// A running Redis container connected through a [`ConnectionManager`].
+pub struct TestRedis {
+    pub manager: ConnectionManager,
+    pub id: Uuid,
+    pub pool: Pool,
+    pub url: String,
+    _container: ContainerAsync<GenericImage>,
+}
+
+/// Start a standalone Redis server and connect to it.
+pub async fn standalone() -> TestRedis {
+    let container = GenericImage::new(REDIS_IMAGE.0, REDIS_IMAGE.1)
+        .with_exposed_port(6379.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+        .start()
+        .await
+        .expect("start redis container");
+    let host = container.get_host().await.expect("container host");
+    let port = container
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("mapped port");
+
+    connect(container, host.to_string(), port).await
+}
+
+/// Start a six-node cluster and connect to its first node.
+///
+/// Ports are mapped one-to-one so the addresses nodes advertise in MOVED
+/// redirects are reachable from the host.
+pub async fn cluster() -> TestRedis {
+    let mut image = GenericImage::new(CLUSTER_IMAGE.0, CLUSTER_IMAGE.1)
+        .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+        .with_env_var("IP", "0.0.0.0");
+    for port in CLUSTER_PORTS {
+        image = image.with_mapped_port(port, port.tcp());
+    }
+    let container = image.start().await.expect("start cluster container");
+
+    connect(container, "127.0.0.1".into(), *CLUSTER_PORTS.start()).await
+}
+
+async fn connect(container: ContainerAsync<GenericImage>, host: String, port: u16) -> TestRedis {
+    let profile = ConnectionProfile::new_standalone("integration".into(), host.clone(), port);
+    let id = profile.id;
+    let manager = ConnectionManager::new();
+    manager.connect(profile).await.expect("connect");
+    let pool = manager.get_pool(&id).await.expect("pool");
+
+    TestRedis {
+        manager,
+        id,
+        pool,
+        url: format!("redis://{host}:{port}"),
+        _container: container,
+    }
+}
+
+/// Run a raw command on the pool.
+pub async fn exec<T: redis::FromRedisValue>(pool: &Pool, cmd: &mut redis::Cmd) -> T {
+    let mut conn = pool.get().await.expect("pooled connection");
+    cmd.query_async(&mut conn).await.expect("command")
+}
+
+/// Write `count` string keys named `{prefix}:{n}`.
+pub async fn seed_strings(pool: &Pool, prefix: &str, count: usize) {
+    let mut conn = pool.get().await.expect("pooled connection");
+    let mut pipe = redis::pipe();
+    for n in 0..count {
+        pipe.cmd("SET").arg(format!("{prefix}:{n}")).arg(n).ignore();
+    }
+    pipe.query_async::<()>(&mut conn).await.expect("seed keys");
+}
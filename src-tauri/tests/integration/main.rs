@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT
+
+//! End-to-end tests against real Redis servers started with testcontainers.
+//!
+//! Requires Docker. Run with `cargo test --features integration-tests`.
+
+mod bench;
+mod cluster;
+mod devtools;
+mod diff;
+mod editor;
+mod harness;
+mod monitor;
+mod pubsub;
+mod scanner;
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use redis_lens_lib::redis::monitor::model::{
+    AlertComparison, AlertMetric, AlertRule, ClientFilter, ClientKillFilter, MetricThresholds,
+};
+use redis_lens_lib::redis::monitor::{alerts, client_list, poller, slow_log};
+
+use crate::harness::{self, exec, seed_strings};
+
+#[tokio::test]
+async fn test_poll_iteration_builds_snapshot() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "m", 10).await;
+    let _: Option<String> = exec(&redis.pool, redis::cmd("GET").arg("m:1")).await;
+    let _: Option<String> = exec(&redis.pool, redis::cmd("GET").arg("missing")).await;
+
+    let snapshot = poller::fetch_info_all(&redis.pool, &MetricThresholds::default())
+        .await
+        .unwrap();
+
+    assert!(snapshot.info.server.redis_version.starts_with('7'));
+    assert_eq!(snapshot.info.keyspace[0].keys, 10);
+    assert!((snapshot.derived.hit_rate_percent - 50.0).abs() < f64::EPSILON);
+
+    let rules = vec![AlertRule {
+        id: "clients".into(),
+        name: "Any client".into(),
+        metric: AlertMetric::ConnectedClients,
+        comparison: AlertComparison::Above,
+        threshold: 0.0,
+        cooldown_secs: 300,
+    }];
+    assert_eq!(alerts::evaluate(&rules, &snapshot).len(), 1);
+}
+
+#[tokio::test]
+async fn test_slow_log_and_clients() {
+    let redis = harness::standalone().await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("CONFIG")
+            .arg("SET")
+            .arg("slowlog-log-slower-than")
+            .arg(0),
+    )
+    .await;
+    seed_strings(&redis.pool, "s", 3).await;
+
+    let entries = slow_log::get_slow_log(&redis.pool, 10).await.unwrap();
+    assert!(!entries.is_empty());
+
+    let clients = client_list::get_client_list(&redis.pool).await.unwrap();
+    assert!(!clients.is_empty());
+}
+
+#[tokio::test]
+async fn test_slow_log_len_reset_and_groups() {
+    let redis = harness::standalone().await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("CONFIG")
+            .arg("SET")
+            .arg("slowlog-log-slower-than")
+            .arg(0),
+    )
+    .await;
+    slow_log::reset_slow_log(&redis.pool).await.unwrap();
+    let _: Option<String> = exec(&redis.pool, redis::cmd("GET").arg("user:1")).await;
+    let _: Option<String> = exec(&redis.pool, redis::cmd("GET").arg("user:2")).await;
+
+    assert!(slow_log::get_slow_log_len(&redis.pool).await.unwrap() >= 2);
+    let entries = slow_log::get_slow_log(&redis.pool, 128).await.unwrap();
+    let catalog = redis.manager.get_command_catalog(&redis.id).await.unwrap();
+    let groups = slow_log::group_entries(&entries, &catalog);
+    let get = groups.iter().find(|g| g.shape == "GET user:*").unwrap();
+    assert_eq!(get.count, 2);
+}
+
+#[tokio::test]
+async fn test_client_page_and_kill_by_addr() {
+    let redis = harness::standalone().await;
+    let client = redis::Client::open(redis.url.as_str()).unwrap();
+    let mut victim = client.get_multiplexed_async_connection().await.unwrap();
+    let _: () = redis::cmd("CLIENT")
+        .arg("SETNAME")
+        .arg("victim")
+        .query_async(&mut victim)
+        .await
+        .unwrap();
+
+    let filter = ClientFilter {
+        name: Some("vict*".into()),
+        ..ClientFilter::default()
+    };
+    let page = client_list::get_client_page(&redis.pool, &filter, 0, Some(10))
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.clients[0].name, "victim");
+
+    let kill = ClientKillFilter {
+        addr: Some(page.clients[0].addr.clone()),
+        ..ClientKillFilter::default()
+    };
+    assert_eq!(
+        client_list::kill_clients(&redis.pool, &kill).await.unwrap(),
+        1
+    );
+    assert!(
+        client_list::kill_clients(&redis.pool, &ClientKillFilter::default())
+            .await
+            .is_err()
+    );
+}
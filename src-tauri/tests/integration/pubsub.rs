@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use redis_lens_lib::redis::pubsub::discovery;
+
+use crate::harness::{self, exec};
+
+#[tokio::test]
+async fn test_discovery_and_delivery() {
+    let redis = harness::standalone().await;
+
+    // A dedicated subscriber connection, as the subscriber manager uses.
+    let client = redis::Client::open(redis.url.as_str()).unwrap();
+    let mut pubsub = client.get_async_pubsub().await.unwrap();
+    pubsub.subscribe("orders").await.unwrap();
+    pubsub.subscribe("audit").await.unwrap();
+
+    let mut channels = discovery::get_active_channels(&redis.pool, None)
+        .await
+        .unwrap();
+    channels.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(channels.len(), 2);
+    assert_eq!(channels[1].name, "orders");
+    assert_eq!(channels[1].subscribers, 1);
+
+    let filtered = discovery::get_active_channels(&redis.pool, Some("ord*"))
+        .await
+        .unwrap();
+    assert_eq!(filtered.len(), 1);
+
+    let receivers: u64 = exec(&redis.pool, redis::cmd("PUBLISH").arg("orders").arg("o-1")).await;
+    assert_eq!(receivers, 1);
+
+    let mut stream = pubsub.on_message();
+    let msg = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("message within timeout")
+        .expect("stream open");
+    assert_eq!(msg.get_channel_name(), "orders");
+    assert_eq!(msg.get_payload::<String>().unwrap(), "o-1");
+}
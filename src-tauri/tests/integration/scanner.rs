@@ -0,0 +1,356 @@
+// SPDX-License-Identifier: MIT
+
+use redis_lens_lib::redis::browser::introspect;
+use redis_lens_lib::redis::browser::key_index::KeyIndex;
+use redis_lens_lib::redis::browser::model::{RedisKeyType, Ttl, ValueSearchSpec};
+use redis_lens_lib::redis::browser::rename::{self, KeyRewrite};
+use redis_lens_lib::redis::browser::value_search::{self, ValueSearch};
+use redis_lens_lib::redis::browser::{sampling, scanner, schema};
+use redis_lens_lib::redis::connection::databases;
+
+use crate::harness::{self, exec, seed_strings};
+
+#[tokio::test]
+async fn test_scan_visits_every_matching_key() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "user", 250).await;
+    seed_strings(&redis.pool, "session", 40).await;
+
+    let mut found = Vec::new();
+    let mut cursor = 0;
+    loop {
+        let page = scanner::scan_keys(&redis.pool, cursor, "user:*", 100)
+            .await
+            .unwrap();
+        assert_eq!(page.total_estimate, 290);
+        found.extend(page.keys);
+        if page.finished {
+            break;
+        }
+        cursor = page.cursor;
+    }
+    found.sort();
+    found.dedup();
+    assert_eq!(found.len(), 250);
+}
+
+#[tokio::test]
+async fn test_keys_info_reports_type_and_ttl() {
+    let redis = harness::standalone().await;
+    exec::<()>(&redis.pool, redis::cmd("SET").arg("plain").arg("v")).await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("SET")
+            .arg("expiring")
+            .arg("v")
+            .arg("EX")
+            .arg(100),
+    )
+    .await;
+    exec::<()>(&redis.pool, redis::cmd("RPUSH").arg("queue").arg("a")).await;
+
+    let keys = vec![
+        "plain".into(),
+        "expiring".into(),
+        "queue".into(),
+        "gone".into(),
+    ];
+    let info = scanner::get_keys_info(&redis.pool, &keys, None, 1)
+        .await
+        .unwrap();
+
+    assert!(matches!(info[0].ttl, Ttl::Persistent));
+    assert!(matches!(info[1].ttl, Ttl::Seconds { value } if value > 0 && value <= 100));
+    assert!(matches!(info[2].key_type, RedisKeyType::List));
+    assert!(matches!(info[3].ttl, Ttl::Missing));
+    assert!(info.iter().all(|i| i.size_bytes.is_none()));
+
+    let sized = scanner::get_keys_info(&redis.pool, &keys, Some(5), 1)
+        .await
+        .unwrap();
+    assert!(sized[0].size_bytes.is_some_and(|b| b > 0));
+    assert!(sized[3].size_bytes.is_none());
+}
+
+#[tokio::test]
+async fn test_keys_info_splits_large_batches_in_order() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "big", 1200).await;
+
+    let keys: Vec<String> = (0..1200).map(|i| format!("big:{i}")).collect();
+    let info = scanner::get_keys_info(&redis.pool, &keys, None, 4)
+        .await
+        .unwrap();
+    assert_eq!(info.len(), keys.len());
+    assert!(info.iter().zip(&keys).all(|(i, k)| &i.key == k));
+    assert!(info
+        .iter()
+        .all(|i| matches!(i.key_type, RedisKeyType::String)));
+}
+
+#[tokio::test]
+async fn test_pattern_operations() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "tmp", 30).await;
+    seed_strings(&redis.pool, "keep", 5).await;
+
+    let (count, sample) = scanner::count_matching(&redis.pool, "tmp:*", 3)
+        .await
+        .unwrap();
+    assert_eq!(count, 30);
+    assert_eq!(sample.len(), 3);
+    assert_eq!(
+        scanner::set_ttl_by_pattern(&redis.pool, "tmp:*", Some(60))
+            .await
+            .unwrap(),
+        30
+    );
+    assert_eq!(
+        scanner::delete_by_pattern(&redis.pool, "tmp:*")
+            .await
+            .unwrap(),
+        30
+    );
+
+    let remaining: u64 = exec(&redis.pool, &mut redis::cmd("DBSIZE")).await;
+    assert_eq!(remaining, 5);
+}
+
+#[tokio::test]
+async fn test_rename_by_pattern_reports_collisions() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "cache:v1", 10).await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("SET").arg("cache:v2:3").arg("taken"),
+    )
+    .await;
+
+    let rewrite = KeyRewrite::parse("cache:v1:*", "cache:v2:*").unwrap();
+    let recorder = KeyIndex::new().recorder(redis.id, None);
+    let preview = rename::rename_by_pattern(&redis.pool, &rewrite, true, &recorder, |_| {})
+        .await
+        .unwrap();
+    assert_eq!((preview.renamed, preview.collisions), (9, 1));
+    let untouched: bool = exec(&redis.pool, redis::cmd("EXISTS").arg("cache:v1:0")).await;
+    assert!(untouched);
+
+    let mut batches = 0;
+    let report = rename::rename_by_pattern(&redis.pool, &rewrite, false, &recorder, |_| {
+        batches += 1;
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        (report.matched, report.renamed, report.collisions),
+        (10, 9, 1)
+    );
+    assert!(batches >= 1);
+    let kept: String = exec(&redis.pool, redis::cmd("GET").arg("cache:v2:3")).await;
+    assert_eq!(kept, "taken");
+    let leftover: bool = exec(&redis.pool, redis::cmd("EXISTS").arg("cache:v1:3")).await;
+    assert!(leftover);
+}
+
+#[tokio::test]
+async fn test_rename_refuses_existing_target() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "k", 2).await;
+
+    assert!(scanner::rename_key(&redis.pool, "k:0", "k:1")
+        .await
+        .is_err());
+    scanner::rename_key(&redis.pool, "k:0", "k:new")
+        .await
+        .unwrap();
+    let exists: bool = exec(&redis.pool, redis::cmd("EXISTS").arg("k:new")).await;
+    assert!(exists);
+}
+
+#[tokio::test]
+async fn test_database_override_pool() {
+    let redis = harness::standalone().await;
+    let db3 = redis
+        .manager
+        .get_pool_for_db(&redis.id, Some(3))
+        .await
+        .unwrap();
+    seed_strings(&db3, "other", 4).await;
+
+    let own: u64 = exec(&redis.pool, &mut redis::cmd("DBSIZE")).await;
+    let other: u64 = exec(&db3, &mut redis::cmd("DBSIZE")).await;
+    assert_eq!((own, other), (0, 4));
+
+    let dbs = databases::list_databases(&redis.pool, 0).await.unwrap();
+    assert_eq!(dbs.len(), 16);
+    assert!(dbs[0].active);
+    assert_eq!(dbs[3].keys, 4);
+}
+
+#[tokio::test]
+async fn test_flush_db_leaves_other_databases() {
+    let redis = harness::standalone().await;
+    let db5 = redis
+        .manager
+        .get_pool_for_db(&redis.id, Some(5))
+        .await
+        .unwrap();
+    seed_strings(&redis.pool, "own", 3).await;
+    seed_strings(&db5, "other", 2).await;
+
+    assert_eq!(databases::db_size(&db5).await.unwrap(), 2);
+    assert_eq!(databases::total_keys(&redis.pool).await.unwrap(), 5);
+    databases::flush_db(&db5, true).await.unwrap();
+    assert_eq!(databases::db_size(&db5).await.unwrap(), 0);
+    assert_eq!(databases::db_size(&redis.pool).await.unwrap(), 3);
+
+    databases::flush_all(&redis.pool, false).await.unwrap();
+    assert_eq!(databases::total_keys(&redis.pool).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_introspect_key_degrades_per_subcommand() {
+    let redis = harness::standalone().await;
+    let _: () = exec(
+        &redis.pool,
+        redis::cmd("RPUSH").arg("queue").arg("a").arg("b"),
+    )
+    .await;
+
+    let info = introspect::introspect_key(&redis.pool, "queue", true)
+        .await
+        .unwrap();
+    assert_eq!(info.encoding.as_deref(), Some("listpack"));
+    assert_eq!(info.refcount, Some(1));
+    // The default LRU policy answers IDLETIME and refuses FREQ.
+    assert!(info.idle_secs.is_some());
+    assert!(info.freq.is_none());
+    assert!(info
+        .unavailable
+        .iter()
+        .any(|r| r.starts_with("OBJECT FREQ")));
+    // DEBUG is disabled by default since Redis 7.
+    assert!(
+        info.serialized_length.is_some()
+            || info
+                .unavailable
+                .iter()
+                .any(|r| r.starts_with("DEBUG OBJECT"))
+    );
+
+    assert!(introspect::introspect_key(&redis.pool, "missing", true)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_randomkey_sampling_estimates_namespace_shares() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "user", 300).await;
+    seed_strings(&redis.pool, "session", 100).await;
+
+    let sample = sampling::sample_namespaces(&redis.pool, ":", 2_000)
+        .await
+        .unwrap();
+    assert_eq!(sample.total_keys, 400);
+    assert_eq!(sample.samples, 2_000);
+    let user = &sample.namespaces[0];
+    assert_eq!(user.prefix, "user");
+    assert!((user.proportion - 0.75).abs() < 0.1);
+    assert!(user.low < user.proportion && user.proportion < user.high);
+    assert_eq!(
+        sample.namespaces.iter().map(|n| n.hits).sum::<u64>(),
+        sample.samples
+    );
+}
+
+#[tokio::test]
+async fn test_value_search_finds_matches_across_types() {
+    let redis = harness::standalone().await;
+    seed_strings(&redis.pool, "noise", 50).await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("SET")
+            .arg("vs:str")
+            .arg("order ref-1234 shipped"),
+    )
+    .await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("HSET").arg("vs:hash").arg("ref").arg("ref-5678"),
+    )
+    .await;
+    exec::<()>(
+        &redis.pool,
+        redis::cmd("RPUSH")
+            .arg("vs:list")
+            .arg("a")
+            .arg("ref-9")
+            .arg("b"),
+    )
+    .await;
+
+    let search = ValueSearch::new(ValueSearchSpec {
+        query: r"ref-\d+".into(),
+        regex: true,
+        pattern: Some("vs:*".into()),
+        types: Vec::new(),
+        max_value_bytes: None,
+        max_elements: None,
+        max_matches: None,
+    })
+    .unwrap();
+    let mut hits = Vec::new();
+    let throttle = redis.manager.get_throttle(&redis.id).await.unwrap();
+    let tally = value_search::search(&redis.pool, &throttle, &search, |_, batch| {
+        hits.extend(batch);
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(tally.inspected_keys, 3);
+    assert!(!tally.truncated);
+    hits.sort_by(|a, b| a.key.cmp(&b.key));
+    let found: Vec<_> = hits
+        .iter()
+        .map(|h| (h.key.as_str(), h.location.as_deref()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("vs:hash", Some("ref")),
+            ("vs:list", Some("1")),
+            ("vs:str", None)
+        ]
+    );
+    assert_eq!(hits[2].snippet, "order ref-1234 shipped");
+}
+
+#[tokio::test]
+async fn test_schema_inference_over_hashes() {
+    let redis = harness::standalone().await;
+    for i in 0..20 {
+        let mut cmd = redis::cmd("HSET");
+        cmd.arg(format!("user:{i}"))
+            .arg("name")
+            .arg(format!("user {i}"))
+            .arg("age")
+            .arg(20 + i);
+        if i % 4 == 0 {
+            cmd.arg("email").arg(format!("u{i}@example.com"));
+        }
+        exec::<()>(&redis.pool, &mut cmd).await;
+    }
+    exec::<()>(&redis.pool, redis::cmd("SET").arg("user:count").arg(20)).await;
+
+    let report = schema::infer_schema(&redis.pool, "user:*", 200)
+        .await
+        .unwrap();
+    assert_eq!(report.hash_keys, 20);
+    assert_eq!(report.skipped_keys, 1);
+    assert!(!report.truncated);
+    let names: Vec<&str> = report.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["age", "name", "email"]);
+    assert_eq!(report.fields[0].types[0].value_type, "integer");
+    assert!((report.fields[2].fill_rate - 0.25).abs() < f64::EPSILON);
+}
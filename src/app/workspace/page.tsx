@@ -258,15 +258,7 @@ function WorkspaceContent() {
             <MetricCard
               label="Hit Rate"
               value={latestDerived ? `${latestDerived.hitRatePercent.toFixed(1)}%` : '-'}
-              status={
-                latestDerived
-                  ? latestDerived.hitRatePercent > 90
-                    ? 'good'
-                    : latestDerived.hitRatePercent > 50
-                      ? 'warning'
-                      : 'critical'
-                  : undefined
-              }
+              status={latestDerived?.hitRateHealth}
             />
             <MetricCard
               label="Uptime"
@@ -64,9 +64,9 @@ export function CommandInput({ connectionId }: CommandInputProps) {
   );
 
   const handleExecute = useCallback(
-    async (command: string, force = false): Promise<ExecuteResponse | null> => {
+    async (command: string, confirmationToken?: string): Promise<ExecuteResponse | null> => {
       if (!command.trim()) return null;
-      const result = await execute(connectionId, command.trim(), force);
+      const result = await execute(connectionId, command.trim(), confirmationToken);
       setInput('');
       setHistoryIndex(-1);
       clearSuggestions();
@@ -74,6 +74,69 @@ function ResultValue({ result, indent = 0 }: { result: CommandResult; indent?: n
           )}
         </div>
       );
+    case 'set':
+      return (
+        <div style={{ paddingLeft: padding }}>
+          {result.data.length === 0 ? (
+            <span className="italic text-muted-foreground">(empty set)</span>
+          ) : (
+            result.data.map((item, idx) => (
+              <div key={idx} className="flex gap-1">
+                <span className="shrink-0 text-muted-foreground">{idx + 1}~</span>
+                <ResultValue result={item} indent={0} />
+              </div>
+            ))
+          )}
+        </div>
+      );
+    case 'map':
+      return (
+        <div style={{ paddingLeft: padding }}>
+          {result.data.length === 0 ? (
+            <span className="italic text-muted-foreground">(empty hash)</span>
+          ) : (
+            result.data.map(([field, value], idx) => (
+              <div key={idx} className="flex gap-1">
+                <span className="shrink-0 text-muted-foreground">{idx + 1}#</span>
+                <ResultValue result={field} indent={0} />
+                <span className="shrink-0 text-muted-foreground">=&gt;</span>
+                <ResultValue result={value} indent={0} />
+              </div>
+            ))
+          )}
+        </div>
+      );
+    case 'double':
+      return (
+        <span style={{ paddingLeft: padding }} className="text-cyan-500">
+          (double) {result.data}
+        </span>
+      );
+    case 'boolean':
+      return (
+        <span style={{ paddingLeft: padding }} className="text-cyan-500">
+          ({result.data ? 'true' : 'false'})
+        </span>
+      );
+    case 'bigNumber':
+      return (
+        <span style={{ paddingLeft: padding }} className="text-cyan-500">
+          (big number) {result.data}
+        </span>
+      );
+    case 'verbatim':
+      return (
+        <span style={{ paddingLeft: padding }} className="whitespace-pre-wrap text-green-500">
+          {result.data.text}
+        </span>
+      );
+    case 'push':
+      return (
+        <div style={{ paddingLeft: padding }}>
+          <span className="text-muted-foreground">(push) {result.data.kind}</span>
+          <ResultValue result={{ type: 'array', data: result.data.data }} indent={1} />
+        </div>
+      );
     default:
       return null;
   }
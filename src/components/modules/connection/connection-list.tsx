@@ -3,9 +3,11 @@
 
 import { useEffect } from 'react';
 import { useRouter } from 'next/navigation';
+import { listen } from '@tauri-apps/api/event';
 import { Plus, Database } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { useConnectionStore } from '@/lib/stores/connection-store';
+import type { ConnectionStateEvent } from '@/lib/api/types';
 import { ConnectionCard } from './connection-card';
 
 export function ConnectionList() {
@@ -19,6 +21,7 @@ export function ConnectionList() {
     disconnect,
     removeProfile,
     setActiveConnection,
+    updateState,
   } = useConnectionStore();
 
   useEffect(() => {
@@ -27,6 +30,16 @@ export function ConnectionList() {
     }
   }, [loaded, loadProfiles]);
 
+  // Health-check failures and automatic reconnects from the backend supervisor
+  useEffect(() => {
+    const unlisten = listen<ConnectionStateEvent>('connection:state-changed', (event) => {
+      updateState(event.payload.connectionId, event.payload.state);
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [updateState]);
+
   const handleConnect = async (id: string) => {
     try {
       await connect(id);
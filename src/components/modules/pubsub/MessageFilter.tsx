@@ -19,6 +19,7 @@ export function MessageFilter({
   const channelFilter = usePubSubStore((s) => s.channelFilter);
   const payloadFilter = usePubSubStore((s) => s.payloadFilter);
   const isPaused = usePubSubStore((s) => s.isPaused);
+  const droppedCount = usePubSubStore((s) => s.droppedCount);
   const setChannelFilter = usePubSubStore((s) => s.setChannelFilter);
   const setPayloadFilter = usePubSubStore((s) => s.setPayloadFilter);
   const togglePause = usePubSubStore((s) => s.togglePause);
@@ -73,6 +74,9 @@ export function MessageFilter({
         {isPaused && (
           <span className="ml-2 font-medium text-yellow-600 dark:text-yellow-400">Paused</span>
         )}
+        {droppedCount > 0 && (
+          <span className="ml-2 text-yellow-600 dark:text-yellow-400">{droppedCount} dropped</span>
+        )}
       </div>
     </div>
   );
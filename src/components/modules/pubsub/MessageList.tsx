@@ -73,7 +73,13 @@ export function MessageList({ messages }: MessageListProps) {
                 {msg.pattern}
               </Badge>
             )}
-            <span className="min-w-0 break-all font-mono text-xs">{msg.payload}</span>
+            {msg.isBinary ? (
+              <span className="min-w-0 break-all font-mono text-xs text-muted-foreground">
+                [binary, {msg.sizeBytes} bytes] {msg.payloadBase64}
+              </span>
+            ) : (
+              <span className="min-w-0 break-all font-mono text-xs">{msg.payload}</span>
+            )}
           </div>
         ))}
       </div>
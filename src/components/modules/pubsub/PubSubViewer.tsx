@@ -50,7 +50,7 @@ export function PubSubViewer({ connectionId }: PubSubViewerProps) {
 
     if (payloadFilter) {
       const lower = payloadFilter.toLowerCase();
-      filtered = filtered.filter((m) => m.payload.toLowerCase().includes(lower));
+      filtered = filtered.filter((m) => m.payload?.toLowerCase().includes(lower));
     }
 
     return filtered;
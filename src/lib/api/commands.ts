@@ -1,37 +1,136 @@
 // SPDX-License-Identifier: MIT
 
-import { invoke } from '@tauri-apps/api/core';
+import { Channel, invoke } from '@tauri-apps/api/core';
 import {
   AppError,
+  type AlertRecord,
+  type PinnedInfo,
+  type InfoDiff,
+  type AppSettings,
+  type ProfileWorkspace,
+  type Workspace,
+  type BackupRecord,
+  type BackupVerifyReport,
+  type BinaryEncoding,
+  type BenchSpec,
+  type DiagnosticsReport,
+  type DiffOptions,
+  type CommandResult,
+  type LatencyStats,
+  type TrackingStats,
+  type LockScan,
+  type WriteConcern,
+  type KeyIntrospection,
+  type KeySearchResult,
+  type KeyspaceSample,
+  type TenantReport,
+  type LintReport,
+  type NamingRules,
+  type SchemaReport,
+  type PermissionCheck,
+  type ReplayReport,
+  type ReplaySpec,
+  type ValueSearchMode,
+  type ValueSearchSpec,
+  type GeneratorSpec,
+  type DiffReport,
+  type ConfigSnapshot,
+  type ConfigSource,
+  type ConfigDiffReport,
+  type BitfieldOp,
+  type BitfieldOverflow,
   type BitmapInfo,
+  type CollectionItem,
+  type Confirmable,
+  type ConfigDrift,
+  type ConnectionGroup,
   type ConnectionProfile,
   type ConnectionState,
+  type DatabaseSummary,
+  type ExpectedValue,
+  type ExpireCondition,
+  type Expiry,
+  type FullValue,
   type GeoMember,
+  type GeoSearchQuery,
+  type GeoSearchResult,
   type HashField,
+  type HashFieldValue,
   type HashScanResult,
   type HealthResponse,
   type HllInfo,
+  type InfoSection,
+  type JsonPatch,
   type JsonValue,
+  type KeyFilter,
+  type KeyFilterResult,
+  type KeyFingerprint,
+  type ExpiryWatch,
   type KeyInfo,
+  type KeyListPage,
+  type KeyQueueDepth,
+  type KeyListQuery,
   type KeyNode,
+  type KeyspaceComparison,
+  type KeyspaceSnapshot,
+  type NamespaceStats,
   type ListElement,
+  type ProfileImportResult,
+  type ProfileStoreStatus,
+  type RenameReport,
+  type ScanPreset,
+  type TaskInfo,
   type ScanResult,
+  type ServerCapabilities,
   type ServerInfoSummary,
   type SetScanResult,
+  type StreamChunk,
   type StreamInfo,
   type StreamRangeResult,
+  type StreamTrimStrategy,
+  type StringStreamInfo,
   type StringValue,
   type TtlInfo,
+  type UndoEntry,
+  type ValueExportFormat,
+  type ValueExportReport,
+  type ZRangeBy,
+  type ZRangeQuery,
+  type ZSetBulkAddReport,
   type ZSetMember,
+  type ZSetRank,
   type ZSetScanResult,
   type StatsSnapshot,
+  type MonitorSubscription,
+  type MetricPoint,
+  type MetricsExportFormat,
+  type SlowLogDigest,
   type SlowLogEntry,
-  type MonitorClientInfo,
+  type ClientFilter,
+  type ClientKillFilter,
+  type ClientPage,
   type MemoryStats,
+  type UpgradeReport,
+  type PersistenceStatus,
   type ExecuteResponse,
+  type BroadcastResult,
   type CommandSuggestion,
+  type CompletionResult,
   type CliHistoryEntry,
+  type StreamOptions,
+  type Snippet,
+  type AppTemplate,
+  type TemplateScan,
+  type QueueDashboard,
+  type JobMove,
+  type TemplateView,
+  type ScheduledJob,
+  type JobRun,
   type ChannelInfo,
+  type DeliveryOptions,
+  type RecordingInfo,
+  type AuditEntry,
+  type AuditQuery,
 } from './types';
 
 /**
@@ -57,6 +156,17 @@ export async function healthCheck(): Promise<HealthResponse> {
   return tauriInvoke<HealthResponse>('health_check');
 }
 
+/**
+ * Run the troubleshooting checks; with `probe`, also open a TCP connection
+ * to every saved profile (default timeout 2s each).
+ */
+export async function healthDiagnostics(
+  probe?: boolean,
+  timeoutMs?: number,
+): Promise<DiagnosticsReport> {
+  return tauriInvoke<DiagnosticsReport>('health_diagnostics', { probe, timeoutMs });
+}
+
 // ─── Connection Management ─────────────────────────────────────
 
 /** Test a Redis connection without persisting it. */
@@ -79,6 +189,93 @@ export async function connectionList(): Promise<ConnectionProfile[]> {
   return tauriInvoke<ConnectionProfile[]>('connection_list');
 }
 
+/** Whether the saved profiles are encrypted and, if so, locked. */
+export async function connectionStoreStatus(): Promise<ProfileStoreStatus> {
+  return tauriInvoke<ProfileStoreStatus>('connection_store_status');
+}
+
+/** Encrypt the saved profiles with a master passphrase; stays unlocked this session. */
+export async function connectionStoreEnableEncryption(passphrase: string): Promise<void> {
+  return tauriInvoke<void>('connection_store_enable_encryption', { passphrase });
+}
+
+/** Unlock the encrypted profiles (throws a `Decryption` error on a wrong passphrase). */
+export async function connectionStoreUnlock(passphrase: string): Promise<void> {
+  return tauriInvoke<void>('connection_store_unlock', { passphrase });
+}
+
+/** Lock the encrypted profiles. */
+export async function connectionStoreLock(): Promise<void> {
+  return tauriInvoke<void>('connection_store_lock');
+}
+
+/** Decrypt the saved profiles back to plaintext. */
+export async function connectionStoreDisableEncryption(passphrase: string): Promise<void> {
+  return tauriInvoke<void>('connection_store_disable_encryption', { passphrase });
+}
+
+/** Move profiles into a group (or the top level), in the given order. */
+export async function connectionReorder(groupId: string | null, ids: string[]): Promise<void> {
+  return tauriInvoke<void>('connection_reorder', { groupId, ids });
+}
+
+/**
+ * Save a scan preset on a profile, replacing the one with the same ID
+ * (pass a nil UUID to add a new one).
+ */
+export async function connectionSaveScanPreset(
+  id: string,
+  preset: ScanPreset,
+  makeDefault?: boolean,
+): Promise<ConnectionProfile> {
+  return tauriInvoke<ConnectionProfile>('connection_save_scan_preset', {
+    id,
+    preset,
+    makeDefault,
+  });
+}
+
+/** Delete a scan preset from a profile. */
+export async function connectionDeleteScanPreset(
+  id: string,
+  presetId: string,
+): Promise<ConnectionProfile> {
+  return tauriInvoke<ConnectionProfile>('connection_delete_scan_preset', { id, presetId });
+}
+
+/** Choose the scan preset a profile opens with, or `null` for a `*` scan. */
+export async function connectionSetDefaultScanPreset(
+  id: string,
+  presetId: string | null,
+): Promise<ConnectionProfile> {
+  return tauriInvoke<ConnectionProfile>('connection_set_default_scan_preset', { id, presetId });
+}
+
+/** List connection groups in display order. */
+export async function connectionGroupList(): Promise<ConnectionGroup[]> {
+  return tauriInvoke<ConnectionGroup[]>('connection_group_list');
+}
+
+/** Create a connection group. */
+export async function connectionGroupCreate(name: string): Promise<ConnectionGroup> {
+  return tauriInvoke<ConnectionGroup>('connection_group_create', { name });
+}
+
+/** Rename a connection group. */
+export async function connectionGroupRename(id: string, name: string): Promise<ConnectionGroup> {
+  return tauriInvoke<ConnectionGroup>('connection_group_rename', { id, name });
+}
+
+/** Delete a connection group; its profiles move to the top level. */
+export async function connectionGroupDelete(id: string): Promise<void> {
+  return tauriInvoke<void>('connection_group_delete', { id });
+}
+
+/** Set the display order of connection groups. */
+export async function connectionGroupReorder(ids: string[]): Promise<void> {
+  return tauriInvoke<void>('connection_group_reorder', { ids });
+}
+
 /** Delete a connection profile by ID. */
 export async function connectionDelete(id: string): Promise<void> {
   return tauriInvoke<void>('connection_delete', { id });
@@ -99,6 +296,146 @@ export async function connectionState(id: string): Promise<ConnectionState> {
   return tauriInvoke<ConnectionState>('connection_state', { id });
 }
 
+/** PING a connection in the background (emits `connection:quality` on changes). */
+export async function connectionStartLatencyProbe(id: string, intervalMs?: number): Promise<void> {
+  return tauriInvoke<void>('connection_start_latency_probe', { id, intervalMs: intervalMs ?? null });
+}
+
+/** Stop the latency probe. Returns whether one was running. */
+export async function connectionStopLatencyProbe(id: string): Promise<boolean> {
+  return tauriInvoke<boolean>('connection_stop_latency_probe', { id });
+}
+
+/** Rolling latency figures, or null if no probe is running. */
+export async function connectionLatencyStats(id: string): Promise<LatencyStats | null> {
+  return tauriInvoke<LatencyStats | null>('connection_latency_stats', { id });
+}
+
+/** Open a RESP3 channel whose server pushes arrive as `connection:push` events. */
+export async function connectionOpenPushChannel(id: string): Promise<void> {
+  return tauriInvoke<void>('connection_open_push_channel', { id });
+}
+
+/** Run a command (e.g. `CLIENT TRACKING ON`, `SUBSCRIBE ch`) on the push channel. */
+export async function connectionPushExecute(id: string, command: string): Promise<CommandResult> {
+  return tauriInvoke<CommandResult>('connection_push_execute', { id, command });
+}
+
+/** Close the push channel. Returns whether one was open. */
+export async function connectionClosePushChannel(id: string): Promise<boolean> {
+  return tauriInvoke<boolean>('connection_close_push_channel', { id });
+}
+
+/** Make editor and bulk writes wait for replicas (WAIT); null stops waiting. */
+export async function connectionSetWriteConcern(
+  id: string,
+  concern: WriteConcern | null,
+): Promise<void> {
+  return tauriInvoke<void>('connection_set_write_concern', { id, concern });
+}
+
+/** The write concern set on a connection, or null. */
+export async function connectionWriteConcern(id: string): Promise<WriteConcern | null> {
+  return tauriInvoke<WriteConcern | null>('connection_write_concern', { id });
+}
+
+/** Get the capabilities (modules, version-gated features) of a connected server. */
+export async function connectionCapabilities(id: string): Promise<ServerCapabilities> {
+  return tauriInvoke<ServerCapabilities>('connection_capabilities', { id });
+}
+
+/** Get deviations from the profile's baseline found when connecting. */
+export async function connectionConfigDrift(id: string): Promise<ConfigDrift[]> {
+  return tauriInvoke<ConfigDrift[]>('connection_config_drift', { id });
+}
+
+/** List every logical database with its key count, marking the profile's own. */
+export async function connectionListDatabases(id: string): Promise<DatabaseSummary[]> {
+  return tauriInvoke<DatabaseSummary[]>('connection_list_databases', { id });
+}
+
+/**
+ * Delete every key in a database (the profile's own when `db` is omitted).
+ * Confirmation-gated: the pending confirmation reports the current DBSIZE.
+ */
+export async function connectionFlushDb(
+  id: string,
+  db?: number,
+  asyncFlush?: boolean,
+  confirmationToken?: string,
+): Promise<Confirmable<number>> {
+  return tauriInvoke<Confirmable<number>>('connection_flush_db', {
+    id,
+    db: db ?? null,
+    asyncFlush: asyncFlush ?? null,
+    confirmationToken: confirmationToken ?? null,
+  });
+}
+
+/** Delete every key in every database. Confirmation-gated. */
+export async function connectionFlushAll(
+  id: string,
+  asyncFlush?: boolean,
+  confirmationToken?: string,
+): Promise<Confirmable<number>> {
+  return tauriInvoke<Confirmable<number>>('connection_flush_all', {
+    id,
+    asyncFlush: asyncFlush ?? null,
+    confirmationToken: confirmationToken ?? null,
+  });
+}
+
+/**
+ * Export profiles (all, or just `ids`) as a JSON document.
+ * Secrets are encrypted with `passphrase` if given, otherwise omitted.
+ */
+export async function connectionExport(ids?: string[], passphrase?: string): Promise<string> {
+  return tauriInvoke<string>('connection_export', {
+    ids: ids ?? null,
+    passphrase: passphrase ?? null,
+  });
+}
+
+/**
+ * Import profiles from a RedisLens export, a RedisInsight export or a list of
+ * Redis URIs. Invalid profiles and duplicates (same host/port/db and SSH
+ * host) are skipped.
+ */
+export async function connectionImport(
+  content: string,
+  passphrase?: string,
+): Promise<ProfileImportResult> {
+  return tauriInvoke<ProfileImportResult>('connection_import', {
+    content,
+    passphrase: passphrase ?? null,
+  });
+}
+
+// ─── Backup ───────────────────────────────────────────────────
+
+/** Take a backup of a connected server now; with `taskId`, `taskCancel` can abort it. */
+export async function backupRunNow(connectionId: string, taskId?: string): Promise<BackupRecord> {
+  return tauriInvoke<BackupRecord>('backup_run_now', { connectionId, taskId: taskId ?? null });
+}
+
+/** List backup archives for a profile, newest first. */
+export async function backupList(connectionId: string): Promise<BackupRecord[]> {
+  return tauriInvoke<BackupRecord[]>('backup_list', { connectionId });
+}
+
+/** Verify a backup's checksums and dry-run RESTORE it against a target server. */
+export async function backupVerify(
+  connectionId: string,
+  backupId: string,
+  targetConnectionId: string,
+): Promise<BackupVerifyReport> {
+  return tauriInvoke<BackupVerifyReport>('backup_verify', {
+    connectionId,
+    backupId,
+    targetConnectionId,
+  });
+}
+
 // ─── Browser ──────────────────────────────────────────────────
 
 /** Scan keys matching a pattern. Call repeatedly until `finished` is true. */
@@ -107,12 +444,33 @@ export async function browserScanKeys(
   cursor: number,
   pattern: string,
   count: number,
+  db?: number,
 ): Promise<ScanResult> {
   return tauriInvoke<ScanResult>('browser_scan_keys', {
     connectionId,
     cursor,
     pattern,
     count,
+    db: db ?? null,
+  });
+}
+
+/** Scan every key matching a pattern, delivered in chunks. Resolves with the key count. */
+export async function browserScanStream(
+  connectionId: string,
+  pattern: string,
+  onChunk: (chunk: StreamChunk<string>) => void,
+  chunkSize?: number,
+  db?: number,
+): Promise<number> {
+  const channel = new Channel<StreamChunk<string>>();
+  channel.onmessage = onChunk;
+  return tauriInvoke<number>('browser_scan_stream', {
+    connectionId,
+    pattern,
+    chunkSize: chunkSize ?? null,
+    db: db ?? null,
+    onChunk: channel,
   });
 }
 
@@ -131,208 +489,485 @@ export async function browserGetChildren(
   return tauriInvoke<KeyNode[]>('browser_get_children', { keys, prefix, delimiter, depth });
 }
 
-/** Get metadata (type + TTL) for a batch of keys. */
-export async function browserGetKeysInfo(connectionId: string, keys: string[]): Promise<KeyInfo[]> {
-  return tauriInvoke<KeyInfo[]>('browser_get_keys_info', { connectionId, keys });
+/**
+ * Get metadata (type + TTL) for a batch of keys. With `withMemory`, `sizeBytes` is filled
+ * from MEMORY USAGE, sampling `memorySamples` elements of aggregate types (default 5, 0 = all).
+ * Large batches run as `concurrency` parallel pipelines (default 4); keys from a failed
+ * pipeline are left out.
+ */
+export async function browserGetKeysInfo(
+  connectionId: string,
+  keys: string[],
+  db?: number,
+  withMemory?: boolean,
+  memorySamples?: number,
+  concurrency?: number,
+): Promise<KeyInfo[]> {
+  return tauriInvoke<KeyInfo[]>('browser_get_keys_info', {
+    connectionId,
+    keys,
+    db: db ?? null,
+    withMemory: withMemory ?? null,
+    memorySamples: memorySamples ?? null,
+    concurrency: concurrency ?? null,
+  });
 }
 
-/** Get detailed info for a single key. */
-export async function browserGetKeyInfo(connectionId: string, key: string): Promise<KeyInfo> {
-  return tauriInvoke<KeyInfo>('browser_get_key_info', { connectionId, key });
+/**
+ * List known keys as a flat table page, filtered and sorted in the backend from the
+ * key index filled by earlier scans.
+ */
+export async function browserListKeys(
+  connectionId: string,
+  query: KeyListQuery,
+  db?: number,
+): Promise<KeyListPage> {
+  return tauriInvoke<KeyListPage>('browser_list_keys', {
+    connectionId,
+    db: db ?? null,
+    query,
+  });
 }
 
-/** Delete one or more keys using UNLINK. Returns count of deleted keys. */
-export async function browserDeleteKeys(connectionId: string, keys: string[]): Promise<number> {
-  return tauriInvoke<number>('browser_delete_keys', { connectionId, keys });
+/**
+ * Filter known keys by glob, regex, exclusion globs and type. Works on the key index
+ * filled by earlier scans; returns at most `limit` keys (default 1000).
+ */
+export async function browserFilterKeys(
+  connectionId: string,
+  filter: KeyFilter,
+  limit?: number,
+  db?: number,
+): Promise<KeyFilterResult> {
+  return tauriInvoke<KeyFilterResult>('browser_filter_keys', {
+    connectionId,
+    db: db ?? null,
+    filter,
+    limit: limit ?? null,
+  });
 }
 
-/** Rename a key. Fails if the new name already exists. */
-export async function browserRenameKey(
+/**
+ * Summarize the keys under a tree folder (count, type and TTL mix, memory), sampling up
+ * to `sampleSize` keys (default 500). An empty `prefix` covers every known key.
+ */
+export async function browserNamespaceStats(
   connectionId: string,
-  oldName: string,
-  newName: string,
-): Promise<void> {
-  return tauriInvoke<void>('browser_rename_key', { connectionId, oldName, newName });
+  prefix: string,
+  delimiter: string,
+  db?: number,
+  sampleSize?: number,
+  taskId?: string,
+): Promise<NamespaceStats> {
+  return tauriInvoke<NamespaceStats>('browser_namespace_stats', {
+    connectionId,
+    prefix,
+    delimiter,
+    db: db ?? null,
+    sampleSize: sampleSize ?? null,
+    taskId: taskId ?? null,
+  });
 }
 
-// ─── Editor — String ────────────────────────────────────────────
-
-/** Get a string value (auto-detects binary content). */
-export async function editorGetStringValue(
+/** Get detailed info for a single key. */
+export async function browserGetKeyInfo(
   connectionId: string,
   key: string,
-): Promise<StringValue> {
-  return tauriInvoke<StringValue>('editor_get_string_value', { connectionId, key });
+  db?: number,
+): Promise<KeyInfo> {
+  return tauriInvoke<KeyInfo>('browser_get_key_info', { connectionId, key, db: db ?? null });
 }
 
-/** Set a string value, optionally with a TTL in seconds. */
-export async function editorSetStringValue(
+/** OBJECT ENCODING/REFCOUNT/IDLETIME/FREQ and DEBUG OBJECT details of a key. */
+export async function browserIntrospectKey(
   connectionId: string,
   key: string,
-  value: string,
-  ttl?: number,
-): Promise<void> {
-  return tauriInvoke<void>('editor_set_string_value', { connectionId, key, value, ttl });
+  db?: number,
+): Promise<KeyIntrospection> {
+  return tauriInvoke<KeyIntrospection>('browser_introspect_key', { connectionId, key, db: db ?? null });
 }
 
-/** Get a substring of a string value (for large strings). */
-export async function editorGetStringRange(
+/** Delete one or more keys using UNLINK. Returns count of deleted keys. */
+export async function browserDeleteKeys(
   connectionId: string,
-  key: string,
-  start: number,
-  end: number,
-): Promise<string> {
-  return tauriInvoke<string>('editor_get_string_range', { connectionId, key, start, end });
+  keys: string[],
+  db?: number,
+): Promise<number> {
+  return tauriInvoke<number>('browser_delete_keys', { connectionId, keys, db: db ?? null });
 }
 
-// ─── Editor — Hash ──────────────────────────────────────────────
-
-/** Get all fields of a hash (suitable for small hashes). */
-export async function editorGetHashAll(connectionId: string, key: string): Promise<HashField[]> {
-  return tauriInvoke<HashField[]>('editor_get_hash_all', { connectionId, key });
+/** Rename a key. Fails if the new name already exists. */
+export async function browserRenameKey(
+  connectionId: string,
+  oldName: string,
+  newName: string,
+  db?: number,
+): Promise<void> {
+  return tauriInvoke<void>('browser_rename_key', {
+    connectionId,
+    oldName,
+    newName,
+    db: db ?? null,
+  });
 }
 
-/** Paginate hash fields with HSCAN. */
-export async function editorScanHashFields(
+/**
+ * Rename every key matching `pattern` (one `*`) to `replacement` (one `*`), e.g.
+ * `cache:v1:*` → `cache:v2:*`. Existing keys are never overwritten; collisions are
+ * reported. With `dryRun`, nothing is renamed. With `taskId`, progress arrives as
+ * `task:progress` events and `taskCancel` can stop the rename.
+ */
+export async function browserRenameByPattern(
   connectionId: string,
-  key: string,
-  cursor: number,
   pattern: string,
-  count: number,
-): Promise<HashScanResult> {
-  return tauriInvoke<HashScanResult>('editor_scan_hash_fields', {
+  replacement: string,
+  dryRun: boolean,
+  db?: number,
+  taskId?: string,
+): Promise<RenameReport> {
+  return tauriInvoke<RenameReport>('browser_rename_by_pattern', {
     connectionId,
-    key,
-    cursor,
     pattern,
-    count,
+    replacement,
+    dryRun,
+    db: db ?? null,
+    taskId: taskId ?? null,
   });
 }
 
-/** Set a single hash field. Returns true if the field was created (not updated). */
-export async function editorSetHashField(
+/** Delete all keys matching a pattern. Without a token, returns a pending confirmation. */
+export async function browserDeleteByPattern(
   connectionId: string,
-  key: string,
-  field: string,
-  value: string,
-): Promise<boolean> {
-  return tauriInvoke<boolean>('editor_set_hash_field', { connectionId, key, field, value });
+  pattern: string,
+  confirmationToken?: string,
+): Promise<Confirmable<number>> {
+  return tauriInvoke<Confirmable<number>>('browser_delete_by_pattern', {
+    connectionId,
+    pattern,
+    confirmationToken: confirmationToken ?? null,
+  });
 }
 
-/** Delete one or more hash fields. Returns count of deleted fields. */
-export async function editorDeleteHashFields(
+/** Set (or remove, with null) the TTL of all keys matching a pattern. Confirmation-gated. */
+export async function browserSetTtlByPattern(
   connectionId: string,
-  key: string,
-  fields: string[],
-): Promise<number> {
-  return tauriInvoke<number>('editor_delete_hash_fields', { connectionId, key, fields });
+  pattern: string,
+  ttlSeconds: number | null,
+  confirmationToken?: string,
+): Promise<Confirmable<number>> {
+  return tauriInvoke<Confirmable<number>>('browser_set_ttl_by_pattern', {
+    connectionId,
+    pattern,
+    ttlSeconds,
+    confirmationToken: confirmationToken ?? null,
+  });
 }
 
-// ─── Editor — List ──────────────────────────────────────────────
-
-/** Get a range of list elements. */
-export async function editorGetListRange(
+/**
+ * Scan the whole keyspace now and persist a per-prefix summary. With
+ * `taskId`, keys scanned so far arrive as `task:progress` events and
+ * `taskCancel` can abort the scan.
+ */
+export async function browserCaptureKeyspaceSnapshot(
   connectionId: string,
-  key: string,
-  start: number,
-  stop: number,
-): Promise<ListElement[]> {
-  return tauriInvoke<ListElement[]>('editor_get_list_range', { connectionId, key, start, stop });
+  delimiter: string,
+  name?: string,
+  taskId?: string,
+): Promise<KeyspaceSnapshot> {
+  return tauriInvoke<KeyspaceSnapshot>('browser_capture_keyspace_snapshot', {
+    connectionId,
+    delimiter,
+    name: name ?? null,
+    taskId: taskId ?? null,
+  });
 }
 
-/** Push an element to the head or tail of a list. Returns new list length. */
-export async function editorPushListElement(
+/** Estimate keys per prefix from RANDOMKEY draws, without a full SCAN. */
+export async function browserSampleKeyspace(
   connectionId: string,
-  key: string,
-  value: string,
-  head: boolean,
-): Promise<number> {
-  return tauriInvoke<number>('editor_push_list_element', { connectionId, key, value, head });
+  delimiter: string,
+  samples?: number,
+  db?: number,
+  taskId?: string,
+): Promise<KeyspaceSample> {
+  return tauriInvoke<KeyspaceSample>('browser_sample_keyspace', {
+    connectionId,
+    delimiter,
+    samples: samples ?? null,
+    db: db ?? null,
+    taskId: taskId ?? null,
+  });
 }
 
-/** Set a list element at a specific index. */
-export async function editorSetListElement(
+/** Rank tenants of a namespace template like `tenant:{id}:*` by estimated memory. */
+export async function browserTenantReport(
   connectionId: string,
-  key: string,
-  index: number,
-  value: string,
-): Promise<void> {
-  return tauriInvoke<void>('editor_set_list_element', { connectionId, key, index, value });
+  template: string,
+  samplesPerTenant?: number,
+  limit?: number,
+  db?: number,
+  taskId?: string,
+): Promise<TenantReport> {
+  return tauriInvoke<TenantReport>('browser_tenant_report', {
+    connectionId,
+    template,
+    samplesPerTenant: samplesPerTenant ?? null,
+    limit: limit ?? null,
+    db: db ?? null,
+    taskId: taskId ?? null,
+  });
 }
 
-/** Remove list elements by value. Returns count of removed elements. */
-export async function editorRemoveListElement(
+/** Check keys against naming rules (default: the `keyNamingRules` setting). */
+export async function browserLintKeys(
   connectionId: string,
-  key: string,
-  count: number,
-  value: string,
-): Promise<number> {
-  return tauriInvoke<number>('editor_remove_list_element', { connectionId, key, count, value });
-}
-
-// ─── Editor — Set ───────────────────────────────────────────────
-
-/** Get all members of a set (for small sets). */
-export async function editorGetSetMembers(connectionId: string, key: string): Promise<string[]> {
-  return tauriInvoke<string[]>('editor_get_set_members', { connectionId, key });
+  rules?: NamingRules,
+  pattern?: string,
+  db?: number,
+  taskId?: string,
+): Promise<LintReport> {
+  return tauriInvoke<LintReport>('browser_lint_keys', {
+    connectionId,
+    rules: rules ?? null,
+    pattern: pattern ?? null,
+    db: db ?? null,
+    taskId: taskId ?? null,
+  });
 }
 
-/** Scan set members using SSCAN (for large sets). */
-export async function editorScanSetMembers(
+/** Infer the field schema of the hashes and JSON documents matching a pattern. */
+export async function browserInferSchema(
   connectionId: string,
-  key: string,
-  cursor: number,
   pattern: string,
-  count: number,
-): Promise<SetScanResult> {
-  return tauriInvoke<SetScanResult>('editor_scan_set_members', {
+  samples?: number,
+  db?: number,
+  taskId?: string,
+): Promise<SchemaReport> {
+  return tauriInvoke<SchemaReport>('browser_infer_schema', {
     connectionId,
-    key,
-    cursor,
     pattern,
-    count,
+    samples: samples ?? null,
+    db: db ?? null,
+    taskId: taskId ?? null,
   });
 }
 
-/** Add one or more members to a set. Returns count of newly added members. */
-export async function editorAddSetMembers(
+/**
+ * Search values across the keyspace in the background. Returns the search
+ * ID; hits arrive as `browser:value-search-hits` events and progress as
+ * `task:progress`.
+ */
+export async function browserSearchValues(
+  connectionId: string,
+  spec: ValueSearchSpec,
+  db?: number,
+): Promise<string> {
+  return tauriInvoke<string>('browser_search_values', {
+    connectionId,
+    spec,
+    db: db ?? null,
+  });
+}
+
+/** Stop a running value search. Resolves to whether it was still running. */
+export async function browserCancelValueSearch(searchId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('browser_cancel_value_search', { searchId });
+}
+
+/** Capture keyspace snapshots periodically (emits `browser:keyspace-snapshot`). */
+export async function browserStartKeyspaceSnapshots(
+  connectionId: string,
+  delimiter: string,
+  intervalSecs: number,
+): Promise<void> {
+  return tauriInvoke<void>('browser_start_keyspace_snapshots', {
+    connectionId,
+    delimiter,
+    intervalSecs,
+  });
+}
+
+/** Stop periodic keyspace snapshots. */
+export async function browserStopKeyspaceSnapshots(connectionId: string): Promise<void> {
+  return tauriInvoke<void>('browser_stop_keyspace_snapshots', { connectionId });
+}
+
+/** List persisted keyspace snapshots for a connection, oldest first. */
+export async function browserListKeyspaceSnapshots(
+  connectionId: string,
+): Promise<KeyspaceSnapshot[]> {
+  return tauriInvoke<KeyspaceSnapshot[]>('browser_list_keyspace_snapshots', { connectionId });
+}
+
+/** Compare two keyspace snapshots by namespace. */
+export async function browserCompareKeyspaceSnapshots(
+  beforeId: string,
+  afterId: string,
+): Promise<KeyspaceComparison> {
+  return tauriInvoke<KeyspaceComparison>('browser_compare_keyspace_snapshots', {
+    beforeId,
+    afterId,
+  });
+}
+
+/** Delete a persisted keyspace snapshot. */
+export async function browserDeleteKeyspaceSnapshot(id: string): Promise<void> {
+  return tauriInvoke<void>('browser_delete_keyspace_snapshot', { id });
+}
+
+/**
+ * Watch keyspace notifications and receive `browser:invalidate` hints for changed prefixes,
+ * at most once per `debounceMs` (default 2000). With `enableNotifications` the server's
+ * `notify-keyspace-events` is switched on if needed. Returns the effective flags, or null
+ * if the server doesn't allow reading them.
+ */
+export async function browserWatchKeyspace(
+  connectionId: string,
+  delimiter: string,
+  options?: { db?: number; depth?: number; debounceMs?: number; enableNotifications?: boolean },
+): Promise<string | null> {
+  return tauriInvoke<string | null>('browser_watch_keyspace', {
+    connectionId,
+    delimiter,
+    db: options?.db ?? null,
+    depth: options?.depth ?? null,
+    debounceMs: options?.debounceMs ?? null,
+    enableNotifications: options?.enableNotifications ?? null,
+  });
+}
+
+/** Stop watching keyspace notifications for a connection. */
+export async function browserUnwatchKeyspace(connectionId: string): Promise<void> {
+  return tauriInvoke<void>('browser_unwatch_keyspace', { connectionId });
+}
+
+// ─── Editor — String ────────────────────────────────────────────
+
+/** Get a string value (auto-detects binary content). */
+export async function editorGetStringValue(
   connectionId: string,
   key: string,
-  members: string[],
+): Promise<StringValue> {
+  return tauriInvoke<StringValue>('editor_get_string_value', { connectionId, key });
+}
+
+/**
+ * Set a string value, optionally with a TTL in seconds. With `expected`, fails with a
+ * `Conflict` error if the value changed since it was read.
+ */
+export async function editorSetStringValue(
+  connectionId: string,
+  key: string,
+  value: string,
+  ttl?: number,
+  expected?: ExpectedValue,
+): Promise<void> {
+  return tauriInvoke<void>('editor_set_string_value', {
+    connectionId,
+    key,
+    value,
+    ttl,
+    expected: expected ?? null,
+  });
+}
+
+/** Get a substring of a string value (for large strings). */
+export async function editorGetStringRange(
+  connectionId: string,
+  key: string,
+  start: number,
+  end: number,
+): Promise<string> {
+  return tauriInvoke<string>('editor_get_string_range', { connectionId, key, start, end });
+}
+
+/** Add `delta` to an integer string (negative to decrement). Returns the new value. */
+export async function editorIncrString(
+  connectionId: string,
+  key: string,
+  delta: number,
 ): Promise<number> {
-  return tauriInvoke<number>('editor_add_set_members', { connectionId, key, members });
+  return tauriInvoke<number>('editor_incr_string', { connectionId, key, delta });
 }
 
-/** Remove one or more members from a set. Returns count of removed members. */
-export async function editorRemoveSetMembers(
+/** Add `delta` to a floating-point string (INCRBYFLOAT). Returns the new value. */
+export async function editorIncrStringFloat(
   connectionId: string,
   key: string,
-  members: string[],
+  delta: number,
 ): Promise<number> {
-  return tauriInvoke<number>('editor_remove_set_members', { connectionId, key, members });
+  return tauriInvoke<number>('editor_incr_string_float', { connectionId, key, delta });
 }
 
-// ─── Editor — Sorted Set ────────────────────────────────────────
+/** Append to a string value. Returns the new length. */
+export async function editorAppendString(
+  connectionId: string,
+  key: string,
+  value: string,
+): Promise<number> {
+  return tauriInvoke<number>('editor_append_string', { connectionId, key, value });
+}
 
-/** Get sorted set members by rank range, with scores. */
-export async function editorGetZsetRange(
+/** Overwrite part of a string at `offset` (SETRANGE). Returns the new length. */
+export async function editorSetStringRange(
   connectionId: string,
   key: string,
-  start: number,
-  stop: number,
-): Promise<ZSetMember[]> {
-  return tauriInvoke<ZSetMember[]>('editor_get_zset_range', { connectionId, key, start, stop });
+  offset: number,
+  value: string,
+): Promise<number> {
+  return tauriInvoke<number>('editor_set_string_range', { connectionId, key, offset, value });
 }
 
-/** Scan sorted set members with ZSCAN. */
-export async function editorScanZsetMembers(
+/** Write raw bytes, given as base64 or hex, to a string. */
+export async function editorSetStringBinary(
+  connectionId: string,
+  key: string,
+  payload: string,
+  encoding: BinaryEncoding,
+  ttl?: number,
+): Promise<void> {
+  return tauriInvoke<void>('editor_set_string_binary', {
+    connectionId,
+    key,
+    payload,
+    encoding,
+    ttl: ttl ?? null,
+  });
+}
+
+/** Overwrite part of a string at `offset` with raw bytes. Returns the new length. */
+export async function editorSetStringRangeBinary(
+  connectionId: string,
+  key: string,
+  offset: number,
+  payload: string,
+  encoding: BinaryEncoding,
+): Promise<number> {
+  return tauriInvoke<number>('editor_set_string_range_binary', {
+    connectionId,
+    key,
+    offset,
+    payload,
+    encoding,
+  });
+}
+
+// ─── Editor — Hash ──────────────────────────────────────────────
+
+/** Get all fields of a hash (suitable for small hashes). */
+export async function editorGetHashAll(connectionId: string, key: string): Promise<HashField[]> {
+  return tauriInvoke<HashField[]>('editor_get_hash_all', { connectionId, key });
+}
+
+/** Paginate hash fields with HSCAN. */
+export async function editorScanHashFields(
   connectionId: string,
   key: string,
   cursor: number,
   pattern: string,
   count: number,
-): Promise<ZSetScanResult> {
-  return tauriInvoke<ZSetScanResult>('editor_scan_zset_members', {
+): Promise<HashScanResult> {
+  return tauriInvoke<HashScanResult>('editor_scan_hash_fields', {
     connectionId,
     key,
     cursor,
@@ -341,319 +976,1533 @@ export async function editorScanZsetMembers(
   });
 }
 
-/** Add or update a sorted set member. Returns count of new members added. */
-export async function editorAddZsetMember(
+/**
+ * Set a single hash field. Returns true if the field was created (not updated). With
+ * `expected`, fails with a `Conflict` error if the field changed since it was read.
+ */
+export async function editorSetHashField(
   connectionId: string,
   key: string,
-  member: string,
-  score: number,
+  field: string,
+  value: string,
+  expected?: ExpectedValue,
+): Promise<boolean> {
+  return tauriInvoke<boolean>('editor_set_hash_field', {
+    connectionId,
+    key,
+    field,
+    value,
+    expected: expected ?? null,
+  });
+}
+
+/** Delete one or more hash fields. Returns count of deleted fields. */
+export async function editorDeleteHashFields(
+  connectionId: string,
+  key: string,
+  fields: string[],
 ): Promise<number> {
-  return tauriInvoke<number>('editor_add_zset_member', { connectionId, key, member, score });
+  return tauriInvoke<number>('editor_delete_hash_fields', { connectionId, key, fields });
 }
 
-/** Remove members from a sorted set. Returns count of removed. */
-export async function editorRemoveZsetMembers(
+/** Set many hash fields in one call. Returns the number of fields created. */
+export async function editorSetHashFields(
   connectionId: string,
   key: string,
-  members: string[],
+  fields: HashField[],
 ): Promise<number> {
-  return tauriInvoke<number>('editor_remove_zset_members', { connectionId, key, members });
+  return tauriInvoke<number>('editor_set_hash_fields', { connectionId, key, fields });
 }
 
-/** Increment a member's score. Returns the new score. */
-export async function editorIncrZsetScore(
+/** Get the values of selected hash fields (HMGET); missing fields have a null value. */
+export async function editorGetHashFields(
   connectionId: string,
   key: string,
-  member: string,
+  fields: string[],
+): Promise<HashFieldValue[]> {
+  return tauriInvoke<HashFieldValue[]>('editor_get_hash_fields', { connectionId, key, fields });
+}
+
+/** Increment an integer hash field (HINCRBY). Returns the new value. */
+export async function editorIncrHashField(
+  connectionId: string,
+  key: string,
+  field: string,
   delta: number,
 ): Promise<number> {
-  return tauriInvoke<number>('editor_incr_zset_score', { connectionId, key, member, delta });
+  return tauriInvoke<number>('editor_incr_hash_field', { connectionId, key, field, delta });
 }
 
-/** Get sorted set cardinality. */
-export async function editorZsetCard(connectionId: string, key: string): Promise<number> {
-  return tauriInvoke<number>('editor_zset_card', { connectionId, key });
+/** Increment a floating-point hash field (HINCRBYFLOAT). Returns the new value. */
+export async function editorIncrHashFieldFloat(
+  connectionId: string,
+  key: string,
+  field: string,
+  delta: number,
+): Promise<number> {
+  return tauriInvoke<number>('editor_incr_hash_field_float', { connectionId, key, field, delta });
 }
 
-// ─── Editor — Stream ────────────────────────────────────────────
-
-/** Get a range of stream entries (oldest first). */
-export async function editorGetStreamRange(
+/** Sample up to `count` random fields with values (HRANDFIELD) to preview huge hashes. */
+export async function editorSampleHashFields(
   connectionId: string,
   key: string,
-  start: string,
-  end: string,
   count: number,
-): Promise<StreamRangeResult> {
-  return tauriInvoke<StreamRangeResult>('editor_get_stream_range', {
+): Promise<HashField[]> {
+  return tauriInvoke<HashField[]>('editor_sample_hash_fields', { connectionId, key, count });
+}
+
+// ─── Editor — List ──────────────────────────────────────────────
+
+/** Get a range of list elements. */
+export async function editorGetListRange(
+  connectionId: string,
+  key: string,
+  start: number,
+  stop: number,
+): Promise<ListElement[]> {
+  return tauriInvoke<ListElement[]>('editor_get_list_range', { connectionId, key, start, stop });
+}
+
+/** Push an element to the head or tail of a list. Returns new list length. */
+export async function editorPushListElement(
+  connectionId: string,
+  key: string,
+  value: string,
+  head: boolean,
+): Promise<number> {
+  return tauriInvoke<number>('editor_push_list_element', { connectionId, key, value, head });
+}
+
+/**
+ * Set a list element at a specific index. With `expected`, fails with a `Conflict` error
+ * if the element changed since it was read.
+ */
+export async function editorSetListElement(
+  connectionId: string,
+  key: string,
+  index: number,
+  value: string,
+  expected?: ExpectedValue,
+): Promise<void> {
+  return tauriInvoke<void>('editor_set_list_element', {
     connectionId,
     key,
-    start,
-    end,
-    count,
+    index,
+    value,
+    expected: expected ?? null,
   });
 }
 
-/** Get stream entries in reverse (newest first). */
-export async function editorGetStreamRangeRev(
+/** Remove list elements by value. Returns count of removed elements. */
+export async function editorRemoveListElement(
   connectionId: string,
   key: string,
-  end: string,
-  start: string,
   count: number,
-): Promise<StreamRangeResult> {
-  return tauriInvoke<StreamRangeResult>('editor_get_stream_range_rev', {
+  value: string,
+): Promise<number> {
+  return tauriInvoke<number>('editor_remove_list_element', { connectionId, key, count, value });
+}
+
+/** Insert an element before or after the first occurrence of `pivot`. Returns the new length. */
+export async function editorInsertListElement(
+  connectionId: string,
+  key: string,
+  pivot: string,
+  value: string,
+  before: boolean,
+): Promise<number> {
+  return tauriInvoke<number>('editor_insert_list_element', {
     connectionId,
     key,
-    end,
-    start,
-    count,
+    pivot,
+    value,
+    before,
+  });
+}
+
+/** Delete the element at `index` (negative counts from the tail) and return its value. */
+export async function editorDeleteListIndex(
+  connectionId: string,
+  key: string,
+  index: number,
+): Promise<string> {
+  return tauriInvoke<string>('editor_delete_list_index', { connectionId, key, index });
+}
+
+/**
+ * Move the element at `from` so it ends up at index `to` (negative counts from the
+ * tail) in one atomic step, for drag-to-reorder. Returns the moved value. With
+ * `expected`, fails with `Conflict` if the element at `from` changed.
+ */
+export async function editorMoveListElement(
+  connectionId: string,
+  key: string,
+  from: number,
+  to: number,
+  expected?: ExpectedValue,
+): Promise<string> {
+  return tauriInvoke<string>('editor_move_list_element', {
+    connectionId,
+    key,
+    from,
+    to,
+    expected: expected ?? null,
+  });
+}
+
+/** Indexes of elements equal to `element` (LPOS). `count` defaults to 1; 0 returns all. */
+export async function editorListPositions(
+  connectionId: string,
+  key: string,
+  element: string,
+  rank?: number,
+  count?: number,
+  maxLen?: number,
+): Promise<number[]> {
+  return tauriInvoke<number[]>('editor_list_positions', {
+    connectionId,
+    key,
+    element,
+    rank: rank ?? null,
+    count: count ?? null,
+    maxLen: maxLen ?? null,
   });
 }
 
-/** Add a stream entry. Returns the entry ID. */
-export async function editorAddStreamEntry(
-  connectionId: string,
-  key: string,
-  id: string,
-  fields: [string, string][],
-): Promise<string> {
-  return tauriInvoke<string>('editor_add_stream_entry', { connectionId, key, id, fields });
+// ─── Editor — Set ───────────────────────────────────────────────
+
+/** Get all members of a set (for small sets). */
+export async function editorGetSetMembers(connectionId: string, key: string): Promise<string[]> {
+  return tauriInvoke<string[]>('editor_get_set_members', { connectionId, key });
+}
+
+/** Scan set members using SSCAN (for large sets). */
+export async function editorScanSetMembers(
+  connectionId: string,
+  key: string,
+  cursor: number,
+  pattern: string,
+  count: number,
+): Promise<SetScanResult> {
+  return tauriInvoke<SetScanResult>('editor_scan_set_members', {
+    connectionId,
+    key,
+    cursor,
+    pattern,
+    count,
+  });
+}
+
+/** Add one or more members to a set. Returns count of newly added members. */
+export async function editorAddSetMembers(
+  connectionId: string,
+  key: string,
+  members: string[],
+): Promise<number> {
+  return tauriInvoke<number>('editor_add_set_members', { connectionId, key, members });
+}
+
+/** Remove one or more members from a set. Returns count of removed members. */
+export async function editorRemoveSetMembers(
+  connectionId: string,
+  key: string,
+  members: string[],
+): Promise<number> {
+  return tauriInvoke<number>('editor_remove_set_members', { connectionId, key, members });
+}
+
+// ─── Editor — Sorted Set ────────────────────────────────────────
+
+/** Get sorted set members by rank range, with scores. */
+export async function editorGetZsetRange(
+  connectionId: string,
+  key: string,
+  start: number,
+  stop: number,
+): Promise<ZSetMember[]> {
+  return tauriInvoke<ZSetMember[]>('editor_get_zset_range', { connectionId, key, start, stop });
+}
+
+/** Get sorted set members by score or lex range, optionally reversed and paged. */
+export async function editorGetZsetRangeBy(
+  connectionId: string,
+  key: string,
+  query: ZRangeQuery,
+): Promise<ZSetMember[]> {
+  return tauriInvoke<ZSetMember[]>('editor_get_zset_range_by', { connectionId, key, query });
+}
+
+/** Rank (from both ends) and score of a member, or null if it isn't in the set. */
+export async function editorGetZsetMemberRank(
+  connectionId: string,
+  key: string,
+  member: string,
+): Promise<ZSetRank | null> {
+  return tauriInvoke<ZSetRank | null>('editor_get_zset_member_rank', {
+    connectionId,
+    key,
+    member,
+  });
+}
+
+/** Count members within a score (ZCOUNT) or lex (ZLEXCOUNT) range. */
+export async function editorZsetCount(
+  connectionId: string,
+  key: string,
+  by: ZRangeBy,
+  min: string,
+  max: string,
+): Promise<number> {
+  return tauriInvoke<number>('editor_zset_count', { connectionId, key, by, min, max });
+}
+
+/** Scan sorted set members with ZSCAN. */
+export async function editorScanZsetMembers(
+  connectionId: string,
+  key: string,
+  cursor: number,
+  pattern: string,
+  count: number,
+): Promise<ZSetScanResult> {
+  return tauriInvoke<ZSetScanResult>('editor_scan_zset_members', {
+    connectionId,
+    key,
+    cursor,
+    pattern,
+    count,
+  });
+}
+
+/** Add or update a sorted set member. Returns count of new members added. */
+export async function editorAddZsetMember(
+  connectionId: string,
+  key: string,
+  member: string,
+  score: number,
+): Promise<number> {
+  return tauriInvoke<number>('editor_add_zset_member', { connectionId, key, member, score });
+}
+
+/** Remove members from a sorted set. Returns count of removed. */
+export async function editorRemoveZsetMembers(
+  connectionId: string,
+  key: string,
+  members: string[],
+): Promise<number> {
+  return tauriInvoke<number>('editor_remove_zset_members', { connectionId, key, members });
+}
+
+/**
+ * Add or update many sorted set members, from `members` or from pasted
+ * `score member` lines in `text` (pass exactly one).
+ */
+export async function editorAddZsetMembersBulk(
+  connectionId: string,
+  key: string,
+  members?: ZSetMember[],
+  text?: string,
+): Promise<ZSetBulkAddReport> {
+  return tauriInvoke<ZSetBulkAddReport>('editor_add_zset_members_bulk', {
+    connectionId,
+    key,
+    members: members ?? null,
+    text: text ?? null,
+  });
+}
+
+/** Increment a member's score. Returns the new score. */
+export async function editorIncrZsetScore(
+  connectionId: string,
+  key: string,
+  member: string,
+  delta: number,
+): Promise<number> {
+  return tauriInvoke<number>('editor_incr_zset_score', { connectionId, key, member, delta });
+}
+
+/** Get sorted set cardinality. */
+export async function editorZsetCard(connectionId: string, key: string): Promise<number> {
+  return tauriInvoke<number>('editor_zset_card', { connectionId, key });
+}
+
+// ─── Editor — Stream ────────────────────────────────────────────
+
+/** Get a range of stream entries (oldest first). */
+export async function editorGetStreamRange(
+  connectionId: string,
+  key: string,
+  start: string,
+  end: string,
+  count: number,
+): Promise<StreamRangeResult> {
+  return tauriInvoke<StreamRangeResult>('editor_get_stream_range', {
+    connectionId,
+    key,
+    start,
+    end,
+    count,
+  });
+}
+
+/** Get stream entries in reverse (newest first). */
+export async function editorGetStreamRangeRev(
+  connectionId: string,
+  key: string,
+  end: string,
+  start: string,
+  count: number,
+): Promise<StreamRangeResult> {
+  return tauriInvoke<StreamRangeResult>('editor_get_stream_range_rev', {
+    connectionId,
+    key,
+    end,
+    start,
+    count,
+  });
+}
+
+/** Add a stream entry. Returns the entry ID. */
+export async function editorAddStreamEntry(
+  connectionId: string,
+  key: string,
+  id: string,
+  fields: [string, string][],
+): Promise<string> {
+  return tauriInvoke<string>('editor_add_stream_entry', { connectionId, key, id, fields });
+}
+
+/** Delete stream entries by ID. */
+export async function editorDeleteStreamEntries(
+  connectionId: string,
+  key: string,
+  ids: string[],
+): Promise<number> {
+  return tauriInvoke<number>('editor_delete_stream_entries', { connectionId, key, ids });
+}
+
+/** Trim a stream by MAXLEN or MINID. Without a token, returns a pending confirmation. */
+export async function editorTrimStream(
+  connectionId: string,
+  key: string,
+  strategy: StreamTrimStrategy,
+  threshold: string,
+  approximate: boolean,
+  confirmationToken?: string,
+): Promise<Confirmable<number>> {
+  return tauriInvoke<Confirmable<number>>('editor_trim_stream', {
+    connectionId,
+    key,
+    strategy,
+    threshold,
+    approximate,
+    confirmationToken: confirmationToken ?? null,
+  });
+}
+
+/** Get stream info including consumer groups. */
+export async function editorGetStreamInfo(connectionId: string, key: string): Promise<StreamInfo> {
+  return tauriInvoke<StreamInfo>('editor_get_stream_info', { connectionId, key });
+}
+
+// ─── Editor — Search ────────────────────────────────────────────
+
+/** Search inside one key's value; matches carry their index, rank or stream ID. */
+export async function editorSearchInKey(
+  connectionId: string,
+  key: string,
+  query: string,
+  mode?: ValueSearchMode,
+  limit?: number,
+): Promise<KeySearchResult> {
+  return tauriInvoke<KeySearchResult>('editor_search_in_key', {
+    connectionId,
+    key,
+    query,
+    mode: mode ?? null,
+    limit: limit ?? null,
+  });
+}
+
+// ─── Editor — JSON ──────────────────────────────────────────────
+
+/** Get a JSON value (tries RedisJSON module first). */
+export async function editorGetJsonValue(
+  connectionId: string,
+  key: string,
+  path: string,
+): Promise<JsonValue> {
+  return tauriInvoke<JsonValue>('editor_get_json_value', { connectionId, key, path });
+}
+
+/** Set a JSON value. */
+export async function editorSetJsonValue(
+  connectionId: string,
+  key: string,
+  path: string,
+  value: string,
+  useModule: boolean,
+): Promise<void> {
+  return tauriInvoke<void>('editor_set_json_value', {
+    connectionId,
+    key,
+    path,
+    value,
+    useModule,
+  });
+}
+
+/** Diff the stored RedisJSON document against an edited version, without writing. */
+export async function editorDiffJsonValue(
+  connectionId: string,
+  key: string,
+  value: string,
+): Promise<JsonPatch> {
+  return tauriInvoke<JsonPatch>('editor_diff_json_value', { connectionId, key, value });
+}
+
+/**
+ * Save an edited RedisJSON document as minimal path operations. With
+ * `base`, rejects with a conflict if the stored document changed meanwhile.
+ */
+export async function editorPatchJsonValue(
+  connectionId: string,
+  key: string,
+  value: string,
+  base?: string,
+): Promise<JsonPatch> {
+  return tauriInvoke<JsonPatch>('editor_patch_json_value', {
+    connectionId,
+    key,
+    value,
+    base: base ?? null,
+  });
+}
+
+// ─── Editor — HyperLogLog ──────────────────────────────────────
+
+/** Get HyperLogLog info. */
+export async function editorGetHllInfo(connectionId: string, key: string): Promise<HllInfo> {
+  return tauriInvoke<HllInfo>('editor_get_hll_info', { connectionId, key });
+}
+
+/** Add elements to a HyperLogLog. Returns true if cardinality changed. */
+export async function editorAddHllElements(
+  connectionId: string,
+  key: string,
+  elements: string[],
+): Promise<boolean> {
+  return tauriInvoke<boolean>('editor_add_hll_elements', { connectionId, key, elements });
+}
+
+// ─── Editor — Bitmap ────────────────────────────────────────────
+
+/** Get bitmap info and a range of bits. */
+export async function editorGetBitmapInfo(
+  connectionId: string,
+  key: string,
+  byteOffset: number,
+  byteCount: number,
+): Promise<BitmapInfo> {
+  return tauriInvoke<BitmapInfo>('editor_get_bitmap_info', {
+    connectionId,
+    key,
+    byteOffset,
+    byteCount,
+  });
+}
+
+/** Set a single bit. Returns the old bit value. */
+export async function editorSetBitmapBit(
+  connectionId: string,
+  key: string,
+  offset: number,
+  value: number,
+): Promise<number> {
+  return tauriInvoke<number>('editor_set_bitmap_bit', { connectionId, key, offset, value });
+}
+
+/** Run BITFIELD GET/SET/INCRBY operations; one result per op (null on FAIL overflow). */
+export async function editorBitfieldOps(
+  connectionId: string,
+  key: string,
+  ops: BitfieldOp[],
+  overflow?: BitfieldOverflow,
+): Promise<(number | null)[]> {
+  return tauriInvoke<(number | null)[]>('editor_bitfield_ops', {
+    connectionId,
+    key,
+    ops,
+    overflow: overflow ?? null,
+  });
+}
+
+// ─── Editor — Geospatial ───────────────────────────────────────
+
+/** Get all geospatial members with coordinates. */
+export async function editorGetGeoMembers(connectionId: string, key: string): Promise<GeoMember[]> {
+  return tauriInvoke<GeoMember[]>('editor_get_geo_members', { connectionId, key });
+}
+
+/** Add a geospatial member. */
+export async function editorAddGeoMember(
+  connectionId: string,
+  key: string,
+  longitude: number,
+  latitude: number,
+  member: string,
+): Promise<number> {
+  return tauriInvoke<number>('editor_add_geo_member', {
+    connectionId,
+    key,
+    longitude,
+    latitude,
+    member,
+  });
+}
+
+/** Get distance between two members. */
+export async function editorGeoDistance(
+  connectionId: string,
+  key: string,
+  member1: string,
+  member2: string,
+  unit: string,
+): Promise<number | null> {
+  return tauriInvoke<number | null>('editor_geo_distance', {
+    connectionId,
+    key,
+    member1,
+    member2,
+    unit,
+  });
+}
+
+/** Remove geospatial members. */
+export async function editorRemoveGeoMembers(
+  connectionId: string,
+  key: string,
+  members: string[],
+): Promise<number> {
+  return tauriInvoke<number>('editor_remove_geo_members', { connectionId, key, members });
+}
+
+/** Search members by radius or box around a member or a point (GEOSEARCH). */
+export async function editorGeoSearch(
+  connectionId: string,
+  key: string,
+  query: GeoSearchQuery,
+): Promise<GeoSearchResult> {
+  return tauriInvoke<GeoSearchResult>('editor_geo_search', { connectionId, key, query });
+}
+
+// ─── Editor — TTL ───────────────────────────────────────────────
+
+/** Get TTL information for a key. */
+export async function editorGetTtl(connectionId: string, key: string): Promise<TtlInfo> {
+  return tauriInvoke<TtlInfo>('editor_get_ttl', { connectionId, key });
+}
+
+/** Set TTL on a key (in seconds). Returns true if the timeout was set. */
+export async function editorSetTtl(
+  connectionId: string,
+  key: string,
+  seconds: number,
+): Promise<boolean> {
+  return tauriInvoke<boolean>('editor_set_ttl', { connectionId, key, seconds });
+}
+
+/**
+ * Set a key's expiry relative to now or at a timestamp, optionally under a
+ * Redis 7 NX/XX/GT/LT condition. Returns false if the condition kept the old expiry.
+ */
+export async function editorSetExpiry(
+  connectionId: string,
+  key: string,
+  expiry: Expiry,
+  condition?: ExpireCondition,
+): Promise<boolean> {
+  return tauriInvoke<boolean>('editor_set_expiry', {
+    connectionId,
+    key,
+    expiry,
+    condition: condition ?? null,
+  });
+}
+
+/** Remove TTL from a key (make it persistent). Returns true if TTL was removed. */
+export async function editorPersistKey(connectionId: string, key: string): Promise<boolean> {
+  return tauriInvoke<boolean>('editor_persist_key', { connectionId, key });
+}
+
+// ─── Editor — Streamed Reads ────────────────────────────────────
+
+/** Read every element of a collection key in chunks. Resolves with the element count. */
+export async function editorStreamValue(
+  connectionId: string,
+  key: string,
+  onChunk: (chunk: StreamChunk<CollectionItem>) => void,
+  chunkSize?: number,
+): Promise<number> {
+  const channel = new Channel<StreamChunk<CollectionItem>>();
+  channel.onmessage = onChunk;
+  return tauriInvoke<number>('editor_stream_value', {
+    connectionId,
+    key,
+    chunkSize: chunkSize ?? null,
+    onChunk: channel,
+  });
+}
+
+/**
+ * Read a whole collection value in one reply (e.g. for "copy value"), up to
+ * `maxItems` elements and `maxBytes` of element text; check `truncated`.
+ */
+export async function editorReadFullValue(
+  connectionId: string,
+  key: string,
+  maxItems?: number,
+  maxBytes?: number,
+): Promise<FullValue> {
+  return tauriInvoke<FullValue>('editor_read_full_value', {
+    connectionId,
+    key,
+    maxItems: maxItems ?? null,
+    maxBytes: maxBytes ?? null,
+  });
+}
+
+/**
+ * Export a list, hash, set or sorted set value to a CSV or JSON file. With a
+ * `taskId` the export reports `task:progress` and can be cancelled.
+ */
+export async function editorExportKeyValue(
+  connectionId: string,
+  key: string,
+  path: string,
+  format: ValueExportFormat,
+  taskId?: string,
+): Promise<ValueExportReport> {
+  return tauriInvoke<ValueExportReport>('editor_export_key_value', {
+    connectionId,
+    key,
+    path,
+    format,
+    taskId: taskId ?? null,
+  });
+}
+
+/**
+ * Stream a (possibly huge) string value in GETRANGE chunks. Chunks arrive as
+ * `editor:string-chunk` events; resolves immediately with the stream ID and size.
+ */
+export async function editorStreamString(
+  connectionId: string,
+  key: string,
+  offset?: number,
+  length?: number,
+  chunkBytes?: number,
+): Promise<StringStreamInfo> {
+  return tauriInvoke<StringStreamInfo>('editor_stream_string', {
+    connectionId,
+    key,
+    offset: offset ?? null,
+    length: length ?? null,
+    chunkBytes: chunkBytes ?? null,
+  });
+}
+
+/** Stop a string stream. Returns whether it was still running. */
+export async function editorCancelStringStream(streamId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('editor_cancel_string_stream', { streamId });
+}
+
+// ─── Editor — Key Watch ─────────────────────────────────────────
+
+/**
+ * Watch a key open in an editor; changes by other clients arrive as `editor:key-changed`
+ * events. Resolves with the key's current fingerprint, or null if it doesn't exist.
+ */
+export async function editorWatchKey(
+  connectionId: string,
+  key: string,
+  intervalMs?: number,
+  notifications?: boolean,
+): Promise<KeyFingerprint | null> {
+  return tauriInvoke<KeyFingerprint | null>('editor_watch_key', {
+    connectionId,
+    key,
+    intervalMs: intervalMs ?? null,
+    notifications: notifications ?? null,
+  });
+}
+
+/** Stop watching a key. */
+export async function editorUnwatchKey(connectionId: string, key: string): Promise<void> {
+  return tauriInvoke<void>('editor_unwatch_key', { connectionId, key });
+}
+
+/** Keys currently watched on a connection. */
+export async function editorWatchedKeys(connectionId: string): Promise<string[]> {
+  return tauriInvoke<string[]>('editor_watched_keys', { connectionId });
+}
+
+/**
+ * Add a key to the expiry watch list (or update its entry). `key:expiring` fires when its
+ * TTL drops below `thresholdSecs` and when it expires; `notify` also shows an OS notification.
+ */
+export async function editorWatchExpiry(
+  connectionId: string,
+  key: string,
+  thresholdSecs: number,
+  notify?: boolean,
+): Promise<ExpiryWatch> {
+  return tauriInvoke<ExpiryWatch>('editor_watch_expiry', {
+    connectionId,
+    key,
+    thresholdSecs,
+    notify: notify ?? null,
+  });
+}
+
+/** Remove a key from the expiry watch list. */
+export async function editorUnwatchExpiry(connectionId: string, key: string): Promise<void> {
+  return tauriInvoke<void>('editor_unwatch_expiry', { connectionId, key });
+}
+
+/** The connection's expiry watch list with each key's last-read TTL. */
+export async function editorExpiryWatches(connectionId: string): Promise<ExpiryWatch[]> {
+  return tauriInvoke<ExpiryWatch[]>('editor_expiry_watches', { connectionId });
+}
+
+// ─── Editor — Undo ──────────────────────────────────────────────
+
+/** Revert the most recent editor change. Returns the reverted entry, or null if none. */
+export async function editorUndoLast(connectionId: string): Promise<UndoEntry | null> {
+  return tauriInvoke<UndoEntry | null>('editor_undo_last', { connectionId });
+}
+
+/** List journaled editor changes, newest first. */
+export async function editorUndoHistory(connectionId: string): Promise<UndoEntry[]> {
+  return tauriInvoke<UndoEntry[]>('editor_undo_history', { connectionId });
+}
+
+/** Keys with editor writes running or queued, deepest queue first. */
+export async function editorWriteQueueDepths(connectionId: string): Promise<KeyQueueDepth[]> {
+  return tauriInvoke<KeyQueueDepth[]>('editor_write_queue_depths', { connectionId });
+}
+
+// ─── Key Diff ─────────────────────────────────────────────────
+
+/** Compare a key or pattern between two connections or databases. */
+export async function diffKeys(
+  leftConnectionId: string,
+  rightConnectionId: string,
+  options: DiffOptions,
+): Promise<DiffReport> {
+  return tauriInvoke<DiffReport>('diff_keys', { leftConnectionId, rightConnectionId, options });
+}
+
+/** Capture a connection's `CONFIG GET *` as a snapshot to save as a baseline. */
+export async function diffCaptureConfig(connectionId: string): Promise<ConfigSnapshot> {
+  return tauriInvoke<ConfigSnapshot>('diff_capture_config', { connectionId });
+}
+
+/** Compare the config of two connections, or of a connection and a saved snapshot. */
+export async function diffConfig(
+  left: ConfigSource,
+  right: ConfigSource,
+): Promise<ConfigDiffReport> {
+  return tauriInvoke<ConfigDiffReport>('diff_config', { left, right });
+}
+
+// ─── Dev Tools ────────────────────────────────────────────────
+
+/** Write synthetic keys in the background (emits `task:progress`). Returns the generation ID. */
+export async function devtoolsGenerate(
+  connectionId: string,
+  spec: GeneratorSpec,
+  db?: number,
+): Promise<string> {
+  return tauriInvoke<string>('devtools_generate', { connectionId, spec, db: db ?? null });
+}
+
+/** Stop a running generation. Returns whether it was still running. */
+export async function devtoolsCancelGenerate(generationId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('devtools_cancel_generate', { generationId });
+}
+
+/** Run a benchmark in the background (emits `devtools:bench-figures`). Returns the benchmark ID. */
+export async function devtoolsBenchmark(
+  connectionId: string,
+  spec: BenchSpec,
+  db?: number,
+): Promise<string> {
+  return tauriInvoke<string>('devtools_benchmark', { connectionId, spec, db: db ?? null });
+}
+
+/** Stop a benchmark early; it still reports and cleans up. */
+export async function devtoolsStopBenchmark(benchmarkId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('devtools_stop_benchmark', { benchmarkId });
+}
+
+/** Parse a command file and report what a replay would refuse, without sending anything. */
+export async function devtoolsValidateReplay(
+  connectionId: string,
+  spec: ReplaySpec,
+): Promise<ReplayReport> {
+  return tauriInvoke<ReplayReport>('devtools_validate_replay', { connectionId, spec });
+}
+
+/** Replay a command file in the background (emits `devtools:replay-report`). Returns the replay ID. */
+export async function devtoolsReplay(
+  connectionId: string,
+  spec: ReplaySpec,
+  db?: number,
+): Promise<string> {
+  return tauriInvoke<string>('devtools_replay', { connectionId, spec, db: db ?? null });
+}
+
+/** Stop a running replay; commands already sent stay applied. */
+export async function devtoolsCancelReplay(replayId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('devtools_cancel_replay', { replayId });
+}
+
+/** Start counting CLIENT TRACKING invalidations for `prefixes` (all keys when empty). */
+export async function devtoolsStartTracking(
+  connectionId: string,
+  prefixes?: string[],
+): Promise<void> {
+  return tauriInvoke<void>('devtools_start_tracking', {
+    connectionId,
+    prefixes: prefixes ?? null,
+  });
+}
+
+/** Invalidation figures of the running session, or null if none is running. */
+export async function devtoolsTrackingStats(
+  connectionId: string,
+  limit?: number,
+): Promise<TrackingStats | null> {
+  return tauriInvoke<TrackingStats | null>('devtools_tracking_stats', {
+    connectionId,
+    limit: limit ?? null,
+  });
+}
+
+/** End the tracking session and return its final figures. */
+export async function devtoolsStopTracking(connectionId: string): Promise<TrackingStats | null> {
+  return tauriInvoke<TrackingStats | null>('devtools_stop_tracking', { connectionId });
+}
+
+/**
+ * Find lock keys matching `patterns` (default: the `lockPatterns` setting) with their owner,
+ * TTL and idle time. `leaseMs`, the TTL locks are taken with, adds each lock's age.
+ */
+export async function devtoolsScanLocks(
+  connectionId: string,
+  patterns?: string[],
+  leaseMs?: number,
+  limit?: number,
+): Promise<LockScan> {
+  return tauriInvoke<LockScan>('devtools_scan_locks', {
+    connectionId,
+    patterns: patterns ?? null,
+    leaseMs: leaseMs ?? null,
+    limit: limit ?? null,
+  });
+}
+
+/**
+ * Force-release a lock (DEL). With `owner`, only while that token still holds it.
+ * Call without a token first to get the confirmation, then again with its token.
+ */
+export async function devtoolsReleaseLock(
+  connectionId: string,
+  key: string,
+  owner?: string,
+  confirmationToken?: string,
+): Promise<Confirmable<boolean>> {
+  return tauriInvoke<Confirmable<boolean>>('devtools_release_lock', {
+    connectionId,
+    key,
+    owner: owner ?? null,
+    confirmationToken: confirmationToken ?? null,
+  });
+}
+
+// ─── Monitor ──────────────────────────────────────────────────
+
+/** Fetch a one-shot server info snapshot. */
+export async function monitorServerInfo(connectionId: string): Promise<StatsSnapshot> {
+  return tauriInvoke<StatsSnapshot>('monitor_server_info', { connectionId });
+}
+
+/** Start background polling (emits monitor:stats events). */
+export async function monitorStartPolling(
+  connectionId: string,
+  intervalMs: number = 2000,
+  sections?: InfoSection[],
+): Promise<void> {
+  return tauriInvoke<void>('monitor_start_polling', {
+    connectionId,
+    intervalMs,
+    sections: sections ?? null,
+  });
+}
+
+/** Stop background polling for the default view; other subscribers keep polling. */
+export async function monitorStopPolling(connectionId: string): Promise<void> {
+  return tauriInvoke<void>('monitor_stop_polling', { connectionId });
+}
+
+/**
+ * Subscribe a view to `monitor:stats`. Views of one connection share a single
+ * poller running at the fastest requested interval and fetching every INFO
+ * section any of them asked for (default: server, clients, memory,
+ * persistence, stats, replication).
+ */
+export async function monitorSubscribe(
+  connectionId: string,
+  subscriberId: string,
+  intervalMs: number = 2000,
+  sections?: InfoSection[],
+): Promise<MonitorSubscription> {
+  return tauriInvoke<MonitorSubscription>('monitor_subscribe', {
+    connectionId,
+    subscriberId,
+    intervalMs,
+    sections: sections ?? null,
+  });
+}
+
+/** Remove a view's subscription. Resolves with null once nobody polls the connection. */
+export async function monitorUnsubscribe(
+  connectionId: string,
+  subscriberId: string,
+): Promise<MonitorSubscription | null> {
+  return tauriInvoke<MonitorSubscription | null>('monitor_unsubscribe', {
+    connectionId,
+    subscriberId,
+  });
+}
+
+/** Fetch slow log entries (on demand). */
+export async function monitorSlowLog(
+  connectionId: string,
+  count: number = 50,
+): Promise<SlowLogEntry[]> {
+  return tauriInvoke<SlowLogEntry[]>('monitor_slow_log', { connectionId, count });
+}
+
+/** Number of entries in the slow log. */
+export async function monitorSlowLogLen(connectionId: string): Promise<number> {
+  return tauriInvoke<number>('monitor_slow_log_len', { connectionId });
+}
+
+/** Empty the slow log (SLOWLOG RESET). */
+export async function monitorSlowLogReset(connectionId: string): Promise<void> {
+  return tauriInvoke<void>('monitor_slow_log_reset', { connectionId });
+}
+
+/**
+ * Slow log grouped by command shape. With `sinceLast`, only entries logged
+ * since the previous call for this connection are grouped.
+ */
+export async function monitorSlowLogGroups(
+  connectionId: string,
+  count: number = 128,
+  sinceLast?: boolean,
+): Promise<SlowLogDigest> {
+  return tauriInvoke<SlowLogDigest>('monitor_slow_log_groups', {
+    connectionId,
+    count,
+    sinceLast: sinceLast ?? null,
+  });
+}
+
+/** Fetch a page of the connected client list (on demand), optionally filtered. */
+export async function monitorClientList(
+  connectionId: string,
+  filter?: ClientFilter,
+  offset?: number,
+  limit?: number,
+): Promise<ClientPage> {
+  return tauriInvoke<ClientPage>('monitor_client_list', {
+    connectionId,
+    filter: filter ?? null,
+    offset: offset ?? null,
+    limit: limit ?? null,
+  });
+}
+
+/** Kill a connected client by ID. */
+export async function monitorKillClient(connectionId: string, clientId: number): Promise<void> {
+  return tauriInvoke<void>('monitor_kill_client', { connectionId, clientId });
+}
+
+/** Kill every client matching the filter; returns the number killed. */
+export async function monitorKillClients(
+  connectionId: string,
+  filter: ClientKillFilter,
+): Promise<number> {
+  return tauriInvoke<number>('monitor_kill_clients', { connectionId, filter });
+}
+
+/** Fetch MEMORY STATS + MEMORY DOCTOR. */
+export async function monitorMemoryStats(connectionId: string): Promise<MemoryStats> {
+  return tauriInvoke<MemoryStats>('monitor_memory_stats', { connectionId });
+}
+
+/** Build a pre-upgrade checklist (defaults to the latest known release). */
+export async function monitorUpgradeAdvisor(
+  connectionId: string,
+  targetVersion?: string,
+): Promise<UpgradeReport> {
+  return tauriInvoke<UpgradeReport>('monitor_upgrade_advisor', {
+    connectionId,
+    targetVersion: targetVersion ?? null,
+  });
+}
+
+/** INFO persistence plus LASTSAVE. */
+export async function monitorPersistenceStatus(connectionId: string): Promise<PersistenceStatus> {
+  return tauriInvoke<PersistenceStatus>('monitor_persistence_status', { connectionId });
+}
+
+/** Start an RDB snapshot (BGSAVE [SCHEDULE]). Confirmation-gated. */
+export async function monitorBgsave(
+  connectionId: string,
+  schedule?: boolean,
+  confirmationToken?: string,
+): Promise<Confirmable<string>> {
+  return tauriInvoke<Confirmable<string>>('monitor_bgsave', {
+    connectionId,
+    schedule: schedule ?? null,
+    confirmationToken: confirmationToken ?? null,
+  });
+}
+
+/** Start an AOF rewrite (BGREWRITEAOF). Confirmation-gated. */
+export async function monitorBgrewriteaof(
+  connectionId: string,
+  confirmationToken?: string,
+): Promise<Confirmable<string>> {
+  return tauriInvoke<Confirmable<string>>('monitor_bgrewriteaof', {
+    connectionId,
+    confirmationToken: confirmationToken ?? null,
+  });
+}
+
+/**
+ * Tail the redis-server log over the profile's SSH connection (`logPath`, or the one in
+ * its SSH config). Lines arrive as `server:log` events.
+ */
+export async function monitorStartLogTail(
+  connectionId: string,
+  logPath?: string,
+  backlog?: number,
+): Promise<void> {
+  return tauriInvoke<void>('monitor_start_log_tail', {
+    connectionId,
+    logPath: logPath ?? null,
+    backlog: backlog ?? null,
+  });
+}
+
+/** Stop tailing the server log. Returns whether a tail was running. */
+export async function monitorStopLogTail(connectionId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('monitor_stop_log_tail', { connectionId });
+}
+
+/** List fired alerts for a connection, newest first, with their captured bundles. */
+export async function monitorAlertsHistory(
+  connectionId: string,
+  limit?: number,
+): Promise<AlertRecord[]> {
+  return tauriInvoke<AlertRecord[]>('monitor_alerts_history', {
+    connectionId,
+    limit: limit ?? null,
+  });
+}
+
+/** Metrics recorded by this session's pollers for a connection, oldest first. */
+export async function monitorMetricsHistory(connectionId: string): Promise<MetricPoint[]> {
+  return tauriInvoke<MetricPoint[]>('monitor_metrics_history', { connectionId });
+}
+
+/** Export a connection's metrics history as CSV or JSON text. */
+export async function monitorExportMetrics(
+  connectionId: string,
+  format: MetricsExportFormat,
+): Promise<string> {
+  return tauriInvoke<string>('monitor_export_metrics', { connectionId, format });
+}
+
+/** Pin the server's INFO ALL under a label, to diff against later. */
+export async function monitorPinSnapshot(connectionId: string, label: string): Promise<PinnedInfo> {
+  return tauriInvoke<PinnedInfo>('monitor_pin_snapshot', { connectionId, label });
+}
+
+export async function monitorPinnedSnapshots(connectionId: string): Promise<PinnedInfo[]> {
+  return tauriInvoke<PinnedInfo[]>('monitor_pinned_snapshots', { connectionId });
+}
+
+export async function monitorDeleteSnapshot(snapshotId: string): Promise<void> {
+  return tauriInvoke<void>('monitor_delete_snapshot', { snapshotId });
+}
+
+/** Diff a pinned snapshot against another one, or against INFO now when `afterId` is omitted. */
+export async function monitorDiffSnapshots(
+  connectionId: string,
+  beforeId: string,
+  afterId?: string,
+): Promise<InfoDiff> {
+  return tauriInvoke<InfoDiff>('monitor_diff_snapshots', {
+    connectionId,
+    beforeId,
+    afterId: afterId ?? null,
+  });
+}
+
+// ─── Settings ─────────────────────────────────────────────
+
+/** Load application settings. */
+export async function settingsGet(): Promise<AppSettings> {
+  return tauriInvoke<AppSettings>('settings_get');
+}
+
+/** Replace application settings. */
+export async function settingsUpdate(settings: AppSettings): Promise<void> {
+  return tauriInvoke<void>('settings_update', { settings });
 }
 
-/** Delete stream entries by ID. */
-export async function editorDeleteStreamEntries(
-  connectionId: string,
-  key: string,
-  ids: string[],
-): Promise<number> {
-  return tauriInvoke<number>('editor_delete_stream_entries', { connectionId, key, ids });
+// ─── Workspace ────────────────────────────────────────────
+
+/** Load the saved workspace (tabs, scan patterns, view preferences). */
+export async function workspaceGet(): Promise<Workspace> {
+  return tauriInvoke<Workspace>('workspace_get');
 }
 
-/** Get stream info including consumer groups. */
-export async function editorGetStreamInfo(connectionId: string, key: string): Promise<StreamInfo> {
-  return tauriInvoke<StreamInfo>('editor_get_stream_info', { connectionId, key });
+/** Save one profile's workspace; returns it as stored (deduplicated, capped). */
+export async function workspaceSaveProfile(
+  profileId: string,
+  state: ProfileWorkspace,
+): Promise<ProfileWorkspace> {
+  return tauriInvoke<ProfileWorkspace>('workspace_save_profile', { profileId, state });
 }
 
-// ─── Editor — JSON ──────────────────────────────────────────────
+/** Remember the selected connection, or clear it with null. */
+export async function workspaceSetLastConnection(connectionId: string | null): Promise<void> {
+  return tauriInvoke<void>('workspace_set_last_connection', { connectionId });
+}
 
-/** Get a JSON value (tries RedisJSON module first). */
-export async function editorGetJsonValue(
-  connectionId: string,
-  key: string,
-  path: string,
-): Promise<JsonValue> {
-  return tauriInvoke<JsonValue>('editor_get_json_value', { connectionId, key, path });
+// ─── Confirmation ─────────────────────────────────────────
+
+/** Discard a pending confirmation token. */
+export async function confirmCancel(token: string): Promise<boolean> {
+  return tauriInvoke<boolean>('confirm_cancel', { token });
 }
 
-/** Set a JSON value. */
-export async function editorSetJsonValue(
+// ─── CLI ────────────────────────────────────────────────────
+
+/** Execute a Redis command string. Dangerous commands need the token from a prior pending response. */
+export async function cliExecute(
   connectionId: string,
-  key: string,
-  path: string,
-  value: string,
-  useModule: boolean,
-): Promise<void> {
-  return tauriInvoke<void>('editor_set_json_value', {
+  command: string,
+  confirmationToken?: string,
+  db?: number,
+  raw?: boolean,
+  stream?: StreamOptions,
+  preflight?: boolean,
+  followRedirects?: boolean,
+): Promise<ExecuteResponse> {
+  return tauriInvoke<ExecuteResponse>('cli_execute', {
     connectionId,
-    key,
-    path,
-    value,
-    useModule,
+    command,
+    confirmationToken: confirmationToken ?? null,
+    db: db ?? null,
+    raw: raw ?? null,
+    stream: stream ?? null,
+    preflight: preflight ?? null,
+    followRedirects: followRedirects ?? null,
   });
 }
 
-// ─── Editor — HyperLogLog ──────────────────────────────────────
+/** Ask the server (ACL DRYRUN, Redis 7) whether the connection's user may run a command. */
+export async function cliCheckPermission(
+  connectionId: string,
+  command: string,
+): Promise<PermissionCheck> {
+  return tauriInvoke<PermissionCheck>('cli_check_permission', { connectionId, command });
+}
 
-/** Get HyperLogLog info. */
-export async function editorGetHllInfo(connectionId: string, key: string): Promise<HllInfo> {
-  return tauriInvoke<HllInfo>('editor_get_hll_info', { connectionId, key });
+/**
+ * Run one command on several connections concurrently. Read commands only
+ * unless `allowWrites`; dangerous and blocking commands are refused.
+ */
+export async function cliExecuteBroadcast(
+  connectionIds: string[],
+  command: string,
+  allowWrites?: boolean,
+  db?: number,
+): Promise<BroadcastResult[]> {
+  return tauriInvoke<BroadcastResult[]>('cli_execute_broadcast', {
+    connectionIds,
+    command,
+    allowWrites: allowWrites ?? null,
+    db: db ?? null,
+  });
 }
 
-/** Add elements to a HyperLogLog. Returns true if cardinality changed. */
-export async function editorAddHllElements(
-  connectionId: string,
-  key: string,
-  elements: string[],
-): Promise<boolean> {
-  return tauriInvoke<boolean>('editor_add_hll_elements', { connectionId, key, elements });
+/** Abort a command started with stream options. Returns whether it was still running. */
+export async function cliCancel(requestId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('cli_cancel', { requestId });
 }
 
-// ─── Editor — Bitmap ────────────────────────────────────────────
+/**
+ * Get command suggestions matching a prefix (for autocomplete). With a connection,
+ * commands the server documents (e.g. module commands) are included.
+ */
+export async function cliGetCommandSuggestions(
+  prefix: string,
+  connectionId?: string,
+): Promise<CommandSuggestion[]> {
+  return tauriInvoke<CommandSuggestion[]>('cli_get_command_suggestions', {
+    prefix,
+    connectionId: connectionId ?? null,
+  });
+}
 
-/** Get bitmap info and a range of bits. */
-export async function editorGetBitmapInfo(
+/** Syntax and summary for the command being typed, or null if unknown. */
+export async function cliGetCommandHelp(
   connectionId: string,
-  key: string,
-  byteOffset: number,
-  byteCount: number,
-): Promise<BitmapInfo> {
-  return tauriInvoke<BitmapInfo>('editor_get_bitmap_info', {
+  command: string,
+): Promise<CommandSuggestion | null> {
+  return tauriInvoke<CommandSuggestion | null>('cli_get_command_help', { connectionId, command });
+}
+
+/**
+ * Completions for the last token of `line`: fuzzy-matched commands and subcommands,
+ * or keys already seen by the browser when the token is a key argument.
+ */
+export async function cliComplete(
+  connectionId: string,
+  line: string,
+  db?: number,
+  limit?: number,
+): Promise<CompletionResult> {
+  return tauriInvoke<CompletionResult>('cli_complete', {
     connectionId,
-    key,
-    byteOffset,
-    byteCount,
+    line,
+    db: db ?? null,
+    limit: limit ?? null,
   });
 }
 
-/** Set a single bit. Returns the old bit value. */
-export async function editorSetBitmapBit(
+/** Get command history for a connection. */
+export async function cliGetCommandHistory(
   connectionId: string,
-  key: string,
-  offset: number,
-  value: number,
-): Promise<number> {
-  return tauriInvoke<number>('editor_set_bitmap_bit', { connectionId, key, offset, value });
+  limit: number = 100,
+): Promise<CliHistoryEntry[]> {
+  return tauriInvoke<CliHistoryEntry[]>('cli_get_command_history', { connectionId, limit });
 }
 
-// ─── Editor — Geospatial ───────────────────────────────────────
+/** Search a connection's history across sessions, newest first. All terms must match. */
+export async function cliSearchHistory(
+  connectionId: string,
+  query: string,
+  limit: number = 100,
+): Promise<CliHistoryEntry[]> {
+  return tauriInvoke<CliHistoryEntry[]>('cli_search_history', { connectionId, query, limit });
+}
 
-/** Get all geospatial members with coordinates. */
-export async function editorGetGeoMembers(connectionId: string, key: string): Promise<GeoMember[]> {
-  return tauriInvoke<GeoMember[]>('editor_get_geo_members', { connectionId, key });
+/** Get the pinned commands for a connection. */
+export async function cliGetPinnedCommands(connectionId: string): Promise<string[]> {
+  return tauriInvoke<string[]>('cli_get_pinned_commands', { connectionId });
 }
 
-/** Add a geospatial member. */
-export async function editorAddGeoMember(
+/** Pin or unpin a command. Returns the updated pinned list. */
+export async function cliSetCommandPinned(
   connectionId: string,
-  key: string,
-  longitude: number,
-  latitude: number,
-  member: string,
-): Promise<number> {
-  return tauriInvoke<number>('editor_add_geo_member', {
-    connectionId,
-    key,
-    longitude,
-    latitude,
-    member,
-  });
+  command: string,
+  pinned: boolean,
+): Promise<string[]> {
+  return tauriInvoke<string[]>('cli_set_command_pinned', { connectionId, command, pinned });
 }
 
-/** Get distance between two members. */
-export async function editorGeoDistance(
-  connectionId: string,
-  key: string,
-  member1: string,
-  member2: string,
-  unit: string,
-): Promise<number | null> {
-  return tauriInvoke<number | null>('editor_geo_distance', {
-    connectionId,
-    key,
-    member1,
-    member2,
-    unit,
+// ─── Snippets ───────────────────────────────────────────────
+
+/** List snippets usable on a connection (global plus its own), optionally filtered. */
+export async function snippetList(connectionId?: string, filter?: string): Promise<Snippet[]> {
+  return tauriInvoke<Snippet[]>('snippet_list', {
+    connectionId: connectionId ?? null,
+    filter: filter ?? null,
   });
 }
 
-/** Remove geospatial members. */
-export async function editorRemoveGeoMembers(
-  connectionId: string,
-  key: string,
-  members: string[],
-): Promise<number> {
-  return tauriInvoke<number>('editor_remove_geo_members', { connectionId, key, members });
+/** Create or update a snippet. */
+export async function snippetSave(snippet: Snippet): Promise<Snippet> {
+  return tauriInvoke<Snippet>('snippet_save', { snippet });
 }
 
-// ─── Editor — TTL ───────────────────────────────────────────────
+/** Delete a snippet. */
+export async function snippetDelete(id: string): Promise<void> {
+  return tauriInvoke<void>('snippet_delete', { id });
+}
 
-/** Get TTL information for a key. */
-export async function editorGetTtl(connectionId: string, key: string): Promise<TtlInfo> {
-  return tauriInvoke<TtlInfo>('editor_get_ttl', { connectionId, key });
+/** Parameter names a snippet expects, in order of first appearance. */
+export async function snippetGetParameters(id: string): Promise<string[]> {
+  return tauriInvoke<string[]>('snippet_get_parameters', { id });
 }
 
-/** Set TTL on a key (in seconds). Returns true if the timeout was set. */
-export async function editorSetTtl(
+/**
+ * Fill in a snippet's parameters and run it like a CLI command, including
+ * the dangerous-command confirmation flow.
+ */
+export async function snippetExecute(
+  id: string,
   connectionId: string,
-  key: string,
-  seconds: number,
-): Promise<boolean> {
-  return tauriInvoke<boolean>('editor_set_ttl', { connectionId, key, seconds });
+  args: Record<string, string>,
+  confirmationToken?: string,
+  db?: number,
+): Promise<ExecuteResponse> {
+  return tauriInvoke<ExecuteResponse>('snippet_execute', {
+    id,
+    connectionId,
+    args,
+    confirmationToken: confirmationToken ?? null,
+    db: db ?? null,
+  });
 }
 
-/** Remove TTL from a key (make it persistent). Returns true if TTL was removed. */
-export async function editorPersistKey(connectionId: string, key: string): Promise<boolean> {
-  return tauriInvoke<boolean>('editor_persist_key', { connectionId, key });
+// ─── Key Templates ──────────────────────────────────────────
+
+/** List key templates: the user's own, then the built-in ones. */
+export async function templateList(): Promise<AppTemplate[]> {
+  return tauriInvoke<AppTemplate[]>('template_list');
 }
 
-// ─── Monitor ──────────────────────────────────────────────────
+/** Create or update a user template (an empty `id` creates one). */
+export async function templateSave(template: AppTemplate): Promise<AppTemplate> {
+  return tauriInvoke<AppTemplate>('template_save', { template });
+}
 
-/** Fetch a one-shot server info snapshot. */
-export async function monitorServerInfo(connectionId: string): Promise<StatsSnapshot> {
-  return tauriInvoke<StatsSnapshot>('monitor_server_info', { connectionId });
+/** Delete a user template. */
+export async function templateDelete(id: string): Promise<void> {
+  return tauriInvoke<void>('template_delete', { id });
 }
 
-/** Start background polling (emits monitor:stats events). */
-export async function monitorStartPolling(
+/**
+ * Read a key through the template that describes it (`templateId`, or the first matching one):
+ * a decoded record, or a page of decoded jobs for queue keys.
+ */
+export async function templateInspectKey(
   connectionId: string,
-  intervalMs: number = 2000,
-): Promise<void> {
-  return tauriInvoke<void>('monitor_start_polling', { connectionId, intervalMs });
+  key: string,
+  templateId?: string,
+  offset?: number,
+  count?: number,
+): Promise<TemplateView> {
+  return tauriInvoke<TemplateView>('template_inspect_key', {
+    connectionId,
+    key,
+    templateId: templateId ?? null,
+    offset: offset ?? null,
+    count: count ?? null,
+  });
 }
 
-/** Stop background polling. */
-export async function monitorStopPolling(connectionId: string): Promise<void> {
-  return tauriInvoke<void>('monitor_stop_polling', { connectionId });
+/** Find a template's keys, e.g. every queue with its state and length. */
+export async function templateScan(
+  connectionId: string,
+  templateId: string,
+  limit?: number,
+): Promise<TemplateScan> {
+  return tauriInvoke<TemplateScan>('template_scan', {
+    connectionId,
+    templateId,
+    limit: limit ?? null,
+  });
 }
 
-/** Fetch slow log entries (on demand). */
-export async function monitorSlowLog(
+// ─── Job Queues ─────────────────────────────────────────────
+
+export async function queueDashboard(
   connectionId: string,
-  count: number = 50,
-): Promise<SlowLogEntry[]> {
-  return tauriInvoke<SlowLogEntry[]>('monitor_slow_log', { connectionId, count });
+  bullPrefix?: string,
+): Promise<QueueDashboard> {
+  return tauriInvoke<QueueDashboard>('queue_dashboard', {
+    connectionId,
+    bullPrefix: bullPrefix ?? null,
+  });
 }
 
-/** Fetch the connected client list (on demand). */
-export async function monitorClientList(connectionId: string): Promise<MonitorClientInfo[]> {
-  return tauriInvoke<MonitorClientInfo[]>('monitor_client_list', { connectionId });
+export async function queueMoveJob(connectionId: string, jobMove: JobMove): Promise<boolean> {
+  return tauriInvoke<boolean>('queue_move_job', { connectionId, jobMove });
 }
 
-/** Kill a connected client by ID. */
-export async function monitorKillClient(connectionId: string, clientId: number): Promise<void> {
-  return tauriInvoke<void>('monitor_kill_client', { connectionId, clientId });
+// ─── Scheduled Jobs ─────────────────────────────────────────
+
+/** List scheduled jobs, optionally only those of one connection. */
+export async function jobList(connectionId?: string): Promise<ScheduledJob[]> {
+  return tauriInvoke<ScheduledJob[]>('job_list', { connectionId: connectionId ?? null });
 }
 
-/** Fetch MEMORY STATS + MEMORY DOCTOR. */
-export async function monitorMemoryStats(connectionId: string): Promise<MemoryStats> {
-  return tauriInvoke<MemoryStats>('monitor_memory_stats', { connectionId });
+/** Create or update a job and (re)schedule it. */
+export async function jobSave(job: ScheduledJob): Promise<ScheduledJob> {
+  return tauriInvoke<ScheduledJob>('job_save', { job });
 }
 
-// ─── CLI ────────────────────────────────────────────────────
+/** Delete a job and its run history. */
+export async function jobDelete(id: string): Promise<void> {
+  return tauriInvoke<void>('job_delete', { id });
+}
 
-/** Execute a Redis command string. Set force=true to bypass dangerous command warnings. */
-export async function cliExecute(
-  connectionId: string,
-  command: string,
-  force: boolean = false,
-): Promise<ExecuteResponse> {
-  return tauriInvoke<ExecuteResponse>('cli_execute', { connectionId, command, force });
+/** Enable or disable a job. */
+export async function jobSetEnabled(id: string, enabled: boolean): Promise<ScheduledJob> {
+  return tauriInvoke<ScheduledJob>('job_set_enabled', { id, enabled });
 }
 
-/** Get command suggestions matching a prefix (for autocomplete). */
-export async function cliGetCommandSuggestions(prefix: string): Promise<CommandSuggestion[]> {
-  return tauriInvoke<CommandSuggestion[]>('cli_get_command_suggestions', { prefix });
+/** Run a job now, outside its schedule. */
+export async function jobRunNow(id: string): Promise<JobRun> {
+  return tauriInvoke<JobRun>('job_run_now', { id });
 }
 
-/** Get command history for a connection. */
-export async function cliGetCommandHistory(
-  connectionId: string,
-  limit: number = 100,
-): Promise<CliHistoryEntry[]> {
-  return tauriInvoke<CliHistoryEntry[]>('cli_get_command_history', { connectionId, limit });
+/** A job's run history, newest first. */
+export async function jobRuns(id: string, limit?: number): Promise<JobRun[]> {
+  return tauriInvoke<JobRun[]>('job_runs', { id, limit: limit ?? null });
 }
 
 // ─── Pub/Sub ────────────────────────────────────────────────
 
-/** Subscribe to one or more literal channels. Returns a subscription ID. */
-export async function pubsubSubscribe(connectionId: string, channels: string[]): Promise<string> {
-  return tauriInvoke<string>('pubsub_subscribe', { connectionId, channels });
+/**
+ * Subscribe to one or more literal channels. Returns a subscription ID.
+ * Messages arrive batched on `pubsub:messages`.
+ */
+export async function pubsubSubscribe(
+  connectionId: string,
+  channels: string[],
+  options?: DeliveryOptions,
+): Promise<string> {
+  return tauriInvoke<string>('pubsub_subscribe', {
+    connectionId,
+    channels,
+    options: options ?? null,
+  });
 }
 
 /** Subscribe to one or more channel patterns. Returns a subscription ID. */
-export async function pubsubPsubscribe(connectionId: string, patterns: string[]): Promise<string> {
-  return tauriInvoke<string>('pubsub_psubscribe', { connectionId, patterns });
+export async function pubsubPsubscribe(
+  connectionId: string,
+  patterns: string[],
+  options?: DeliveryOptions,
+): Promise<string> {
+  return tauriInvoke<string>('pubsub_psubscribe', {
+    connectionId,
+    patterns,
+    options: options ?? null,
+  });
 }
 
 /** Unsubscribe from a subscription by ID. */
@@ -661,6 +2510,16 @@ export async function pubsubUnsubscribe(subscriptionId: string): Promise<void> {
   return tauriInvoke<void>('pubsub_unsubscribe', { subscriptionId });
 }
 
+/** Stop delivering a subscription's messages; the backend buffers them until resumed. */
+export async function pubsubPause(subscriptionId: string): Promise<void> {
+  return tauriInvoke<void>('pubsub_pause', { subscriptionId });
+}
+
+/** Resume delivering a paused subscription's messages. */
+export async function pubsubResume(subscriptionId: string): Promise<void> {
+  return tauriInvoke<void>('pubsub_resume', { subscriptionId });
+}
+
 /** Publish a message to a channel. Returns the number of subscribers that received it. */
 export async function pubsubPublish(
   connectionId: string,
@@ -670,6 +2529,15 @@ export async function pubsubPublish(
   return tauriInvoke<number>('pubsub_publish', { connectionId, channel, message });
 }
 
+/** Publish a binary message given as base64. Returns the number of receivers. */
+export async function pubsubPublishBinary(
+  connectionId: string,
+  channel: string,
+  payloadBase64: string,
+): Promise<number> {
+  return tauriInvoke<number>('pubsub_publish_binary', { connectionId, channel, payloadBase64 });
+}
+
 /** Get active channels with subscriber counts. */
 export async function pubsubGetActiveChannels(
   connectionId: string,
@@ -677,3 +2545,59 @@ export async function pubsubGetActiveChannels(
 ): Promise<ChannelInfo[]> {
   return tauriInvoke<ChannelInfo[]>('pubsub_get_active_channels', { connectionId, pattern });
 }
+
+/** Start recording a subscription's messages to a file. */
+export async function pubsubStartRecording(subscriptionId: string): Promise<RecordingInfo> {
+  return tauriInvoke<RecordingInfo>('pubsub_start_recording', { subscriptionId });
+}
+
+/** Stop recording a subscription. Returns the final summary. */
+export async function pubsubStopRecording(subscriptionId: string): Promise<RecordingInfo> {
+  return tauriInvoke<RecordingInfo>('pubsub_stop_recording', { subscriptionId });
+}
+
+/**
+ * Republish a recording. `speed` divides the recorded spacing (default 1 = original timing).
+ * Returns a replay ID; the outcome arrives on `pubsub:replay-finished`.
+ */
+export async function pubsubReplayRecording(
+  connectionId: string,
+  recordingId: string,
+  speed?: number,
+): Promise<string> {
+  return tauriInvoke<string>('pubsub_replay_recording', {
+    connectionId,
+    recordingId,
+    speed: speed ?? null,
+  });
+}
+
+/** Stop a running replay. Returns false if it had already finished. */
+export async function pubsubStopReplay(replayId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('pubsub_stop_replay', { replayId });
+}
+
+// ─── Audit ──────────────────────────────────────────────────
+
+/** Query a connection's audit log, newest first. */
+export async function auditQuery(
+  connectionId: string,
+  query?: AuditQuery,
+): Promise<AuditEntry[]> {
+  return tauriInvoke<AuditEntry[]>('audit_query', { connectionId, query: query ?? null });
+}
+
+// ─── Tasks ─────────────────────────────────────────────────────
+
+/**
+ * Abort a long-running command started with a `taskId`. Returns whether it
+ * was still running; its `task:done` event reports it as cancelled.
+ */
+export async function taskCancel(taskId: string): Promise<boolean> {
+  return tauriInvoke<boolean>('task_cancel', { taskId });
+}
+
+/** List running long-running commands, oldest first. */
+export async function taskList(): Promise<TaskInfo[]> {
+  return tauriInvoke<TaskInfo[]>('task_list');
+}
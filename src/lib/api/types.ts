@@ -32,6 +32,21 @@ export class AppError extends Error {
   get isTimeout(): boolean {
     return this.kind === 'Timeout';
   }
+
+  /** The encrypted profile store must be unlocked first. */
+  get isLocked(): boolean {
+    return this.kind === 'Locked';
+  }
+
+  /** Wrong passphrase, or corrupted encrypted data. */
+  get isDecryptionError(): boolean {
+    return this.kind === 'Decryption';
+  }
+
+  /** A compare-and-set write found the value changed since it was read. */
+  get isConflict(): boolean {
+    return this.kind === 'Conflict';
+  }
 }
 
 /** Health check response from the Rust backend. */
@@ -40,6 +55,37 @@ export interface HealthResponse {
   version: string;
 }
 
+/** Outcome of one diagnostic check, from best to worst. */
+export type CheckStatus = 'ok' | 'skipped' | 'warning' | 'error';
+
+/** One app-level check on the troubleshooting screen. */
+export interface DiagnosticCheck {
+  /** `dataDir`, `profileStore` or `secretStorage`. */
+  name: string;
+  status: CheckStatus;
+  detail: string;
+}
+
+/** Reachability of one saved profile. */
+export interface ProfileProbe {
+  id: string;
+  name: string;
+  /** `host:port` that was probed: the SSH host for tunneled profiles. */
+  target: string;
+  status: CheckStatus;
+  latencyMs?: number | null;
+  detail?: string | null;
+}
+
+/** Structured result of `health_diagnostics`. */
+export interface DiagnosticsReport {
+  version: string;
+  /** Worst status across the checks; unreachable profiles count as warnings. */
+  status: CheckStatus;
+  checks: DiagnosticCheck[];
+  profiles: ProfileProbe[];
+}
+
 // ─── Connection Types ──────────────────────────────────────────
 
 export type ConnectionType = 'standalone' | 'cluster' | 'sentinel';
@@ -76,6 +122,8 @@ export interface SshConfig {
   username: string;
   auth: SshAuth;
   localPort?: number;
+  /** redis-server log file on the SSH host, for log tailing. */
+  logPath?: string;
 }
 
 export interface PoolConfig {
@@ -86,12 +134,36 @@ export interface PoolConfig {
   connectionTimeoutSecs: number;
 }
 
+/** Per-operation timeouts; a read or write limit of 0 means no limit. */
 export interface TimeoutConfig {
   connectSecs: number;
   readSecs: number;
   writeSecs: number;
 }
 
+/** Per-connection throttle and circuit breaker; a limit of 0 means no limit. */
+export interface ThrottleConfig {
+  /** Commands per second across the connection's pools. */
+  maxCommandsPerSec: number;
+  /** Connections open at once across the connection's pools (concurrent commands and pipelines). */
+  maxConcurrent: number;
+  /** Health-check round trip above which background polling and scans pause. */
+  breakerLatencyMs: number;
+}
+
+/** How the app's own connections present themselves to the server. */
+export interface ClientOptions {
+  /** CLIENT SETNAME `redislens-<version>` on every pooled connection. */
+  setName: boolean;
+  /** CLIENT NO-TOUCH (Redis 7.2+): browsing leaves LRU/LFU stats alone. */
+  noTouch: boolean;
+  /** CLIENT NO-EVICT (Redis 7.0+): exempt from client eviction. */
+  noEvict: boolean;
+}
+
+/** RESP version negotiated with HELLO; `resp3` needs Redis 6+. */
+export type RespProtocol = 'resp2' | 'resp3';
+
 /** Full connection profile persisted to disk. */
 export interface ConnectionProfile {
   id: string;
@@ -107,11 +179,53 @@ export interface ConnectionProfile {
   ssh?: SshConfig;
   pool: PoolConfig;
   timeout: TimeoutConfig;
+  /** Defaults to no limits. */
+  throttle?: ThrottleConfig;
+  /** CLIENT settings applied to every pooled connection. */
+  client?: ClientOptions;
+  /** Defaults to `resp2`. */
+  protocol?: RespProtocol;
   readonly: boolean;
+  baseline?: ConfigBaseline;
+  /** Overrides of the built-in dangerous command table; absent when none. */
+  commandPolicy?: CommandPolicy;
+  backup?: BackupSchedule;
+  metricThresholds?: MetricThresholds;
+  alertRules?: AlertRule[];
+  /** Folder the profile is listed under; absent for the top level. */
+  groupId?: string;
+  /** Position within its group (ascending). */
+  sortOrder?: number;
+  tags?: string[];
+  /** Saved key browser filters for this connection. */
+  scanPresets?: ScanPreset[];
+  /** Preset the key browser opens with; a `*` scan when absent. */
+  defaultScanPreset?: string;
   createdAt: string;
   updatedAt: string;
 }
 
+/** A saved key browser filter. */
+export interface ScanPreset {
+  id: string;
+  name: string;
+  /** SCAN MATCH pattern, e.g. `orders:*`. */
+  pattern: string;
+  /** SCAN COUNT hint. */
+  count: number;
+  /** Separator the key tree splits names on. */
+  delimiter: string;
+  /** Only keys of this type; every type when absent. */
+  keyType?: RedisKeyType;
+}
+
+/** A folder in the connection list. */
+export interface ConnectionGroup {
+  id: string;
+  name: string;
+  sortOrder: number;
+}
+
 /** Summary of Redis server info returned after a successful connection. */
 export interface ServerInfoSummary {
   redisVersion: string;
@@ -130,6 +244,183 @@ export type ConnectionState =
   | { status: 'connected'; serverInfo: ServerInfoSummary }
   | { status: 'error'; message: string; retryCount: number };
 
+/** A loaded server module as reported by MODULE LIST. */
+/** Per-profile backup cadence and retention. */
+export interface BackupSchedule {
+  enabled: boolean;
+  intervalMinutes: number;
+  keepLast: number;
+  pattern?: string;
+}
+
+/** A backup archive on disk. */
+export interface BackupRecord {
+  id: string;
+  connectionId: string;
+  createdAt: string;
+  sizeBytes: number;
+}
+
+/** A key that failed backup verification. */
+export interface BackupKeyIssue {
+  key: string;
+  reason: string;
+}
+
+/** Result of verifying a backup archive against a target server. */
+export interface BackupVerifyReport {
+  backupId: string;
+  sourceVersion: string;
+  targetVersion: string;
+  totalKeys: number;
+  verifiedKeys: number;
+  uncheckedKeys: number;
+  checksumFailures: BackupKeyIssue[];
+  restoreFailures: BackupKeyIssue[];
+  versionWarning: string | null;
+}
+
+/** Payload of the `backup:failed` event. */
+export interface BackupFailure {
+  connectionId: string;
+  error: string;
+  failedAt: string;
+}
+
+export interface ConfigBaseline {
+  minVersion?: string;
+  maxVersion?: string;
+  maxmemoryPolicy?: string;
+  appendonly?: boolean;
+}
+
+/**
+ * Entries are a command, optionally with a subcommand (`KEYS`, `CONFIG SET`).
+ * Block wins over allow, allow over confirm.
+ */
+export interface CommandPolicy {
+  /** Always refused. */
+  block: string[];
+  /** Need confirmation even if not dangerous by default. */
+  confirm: string[];
+  /** Run without confirmation even if dangerous by default. */
+  allow: string[];
+}
+
+export interface ConfigDrift {
+  parameter: string;
+  expected: string;
+  actual: string;
+}
+
+export interface ConfigDriftEvent {
+  connectionId: string;
+  drift: ConfigDrift[];
+}
+
+/** Payload of the `connection:state-changed` event. */
+export interface ConnectionStateEvent {
+  connectionId: string;
+  state: ConnectionState;
+}
+
+/** Badge-level connection quality derived from the latency probe. */
+export type ConnectionQuality = 'unknown' | 'good' | 'fair' | 'poor' | 'down';
+
+/** Rolling PING round-trip figures over the last 60 probes. */
+export interface LatencyStats {
+  connectionId: string;
+  quality: ConnectionQuality;
+  samples: number;
+  /** 0–100. */
+  lossPercent: number;
+  lastUs?: number;
+  minUs?: number;
+  avgUs?: number;
+  p99Us?: number;
+}
+
+/** Replicas editor and bulk writes wait for (WAIT) before being reported. */
+export interface WriteConcern {
+  replicas: number;
+  /** 1–60000; WAIT never blocks forever. */
+  timeoutMs: number;
+}
+
+/** Payload of the `connection:write-ack` event. */
+export interface WriteAck {
+  connectionId: string;
+  /** Operation name as recorded in the audit log. */
+  operation: string;
+  key?: string;
+  requested: number;
+  acknowledged: number;
+  elapsedMs: number;
+  /** Set when WAIT itself failed. */
+  error?: string;
+}
+
+/** Payload of the `connection:quality` event. */
+export interface QualityEvent {
+  connectionId: string;
+  quality: ConnectionQuality;
+  stats: LatencyStats;
+}
+
+/** Payload of the `connection:breaker` event, sent when a circuit breaker opens or closes. */
+export interface BreakerEvent {
+  connectionId: string;
+  open: boolean;
+  /** The health-check round trip that changed the state. */
+  latencyMs: number;
+  thresholdMs: number;
+}
+
+export interface ModuleInfo {
+  name: string;
+  version: number;
+}
+
+/** Server capabilities probed at connect time. */
+export interface ServerCapabilities {
+  redisVersion: string;
+  commandCount: number;
+  modules: ModuleInfo[];
+  json: boolean;
+  search: boolean;
+  timeSeries: boolean;
+  acl: boolean;
+  functions: boolean;
+}
+
+/** Keyspace summary for one logical database. */
+export interface DatabaseSummary {
+  index: number;
+  keys: number;
+  expires: number;
+  avgTtl: number;
+  active: boolean;
+}
+
+/** Encryption-at-rest state of the saved profiles. */
+export interface ProfileStoreStatus {
+  encrypted: boolean;
+  locked: boolean;
+}
+
+/** A profile import entry that was not saved. */
+export interface SkippedProfile {
+  name: string;
+  reason: string;
+}
+
+/** Outcome of importing connection profiles. */
+export interface ProfileImportResult {
+  imported: ConnectionProfile[];
+  skipped: SkippedProfile[];
+  warnings: string[];
+}
+
 // ─── Browser Types ───────────────────────────────────────────
 
 /** Redis key type classification. */
@@ -143,11 +434,128 @@ export interface KeyInfo {
   key: string;
   keyType: RedisKeyType;
   ttl: Ttl;
+  /** MEMORY USAGE in bytes, when requested. */
   sizeBytes?: number;
   encoding?: string;
   length?: number;
 }
 
+/** Low-level storage details of a key, from OBJECT and DEBUG OBJECT. */
+export interface KeyIntrospection {
+  key: string;
+  encoding?: string;
+  refcount?: number;
+  /** Seconds since last access (LRU maxmemory policies). */
+  idleSecs?: number;
+  /** Access frequency counter (LFU maxmemory policies). */
+  freq?: number;
+  /** From DEBUG OBJECT, which many servers disable. */
+  serializedLength?: number;
+  /** `SUBCOMMAND: reason` per figure that could not be read. */
+  unavailable: string[];
+}
+
+/** Column a flat key listing is sorted by. */
+export type KeySort = 'name' | 'type' | 'ttl' | 'size';
+
+/** One page request for the flat key listing. */
+export interface KeyListQuery {
+  /** Case-insensitive substring keys must contain. */
+  filter?: string;
+  sort?: KeySort;
+  descending?: boolean;
+  offset?: number;
+  /** Page size, 1-1000 (default 100). */
+  limit?: number;
+  /** Fetch MEMORY USAGE for the page so size sorting has data. */
+  withMemory?: boolean;
+}
+
+/** One page of the flat key listing, built from the cached key index. */
+export interface KeyListPage {
+  items: KeyInfo[];
+  /** Keys known for this connection and database. */
+  totalKeys: number;
+  /** Keys matching the filter. */
+  matched: number;
+  offset: number;
+  /** Matching keys without cached metadata for the sort column, listed last. */
+  unsorted: number;
+}
+
+/** Criteria for filtering the key index; every set criterion must hold. */
+export interface KeyFilter {
+  /** Glob keys must match, as in SCAN MATCH. */
+  pattern?: string;
+  /** Regular expression keys must match (`(?i)` for case-insensitive). */
+  regex?: string;
+  /** Globs whose matches are left out, e.g. `cache:*`. */
+  exclude?: string[];
+  /** Key types to keep; empty keeps every type. */
+  types?: RedisKeyType[];
+}
+
+/** Result of filtering the key index. */
+export interface KeyFilterResult {
+  /** Matching keys in name order, up to the requested limit. */
+  keys: string[];
+  matched: number;
+  /** Keys in the index that were checked. */
+  scanned: number;
+  /** Keys whose type was loaded from the server for a type filter. */
+  typesLoaded: number;
+}
+
+/** Number of sampled keys of one type in a namespace. */
+export interface TypeCount {
+  keyType: RedisKeyType;
+  count: number;
+}
+
+/** Sampled keys per TTL band in a namespace. */
+export interface TtlDistribution {
+  persistent: number;
+  underMinute: number;
+  underHour: number;
+  underDay: number;
+  overDay: number;
+}
+
+/** Summary of the keys under one tree folder. */
+export interface NamespaceStats {
+  prefix: string;
+  /** Known keys under the prefix in the key index. */
+  keyCount: number;
+  /** Keys whose metadata was fetched; the distributions cover only these. */
+  sampled: number;
+  /** Most common type first. */
+  types: TypeCount[];
+  ttl: TtlDistribution;
+  sampledMemoryBytes: number;
+  /** Sampled memory scaled up to `keyCount`. */
+  estimatedMemoryBytes: number;
+}
+
+/** One key's old and new name. */
+export interface RenamePair {
+  from: string;
+  to: string;
+}
+
+/** Outcome of a rename by pattern. */
+export interface RenameReport {
+  dryRun: boolean;
+  matched: number;
+  /** Keys renamed (or, in a dry run, that would be). */
+  renamed: number;
+  /** Keys left alone because the new name already exists. */
+  collisions: number;
+  /** Keys that disappeared between SCAN and rename. */
+  missing: number;
+  sample: RenamePair[];
+  collisionSample: RenamePair[];
+}
+
 /** Result of a single SCAN iteration. */
 export interface ScanResult {
   cursor: number;
@@ -177,6 +585,246 @@ export interface FlatTreeNode {
   indent: number;
 }
 
+/** Key count and memory for one namespace prefix. */
+export interface NamespaceSummary {
+  prefix: string;
+  keyCount: number;
+  memoryBytes: number;
+}
+
+/** A persisted per-prefix summary of the keyspace at a point in time. */
+export interface KeyspaceSnapshot {
+  id: string;
+  connectionId: string;
+  name?: string;
+  capturedAt: string;
+  delimiter: string;
+  totalKeys: number;
+  totalMemoryBytes: number;
+  namespaces: NamespaceSummary[];
+  /** Keys in the snapshot's key index; 0 if it has none. */
+  indexedKeys: number;
+  /** Set when the index stops at this key (name order). */
+  indexCutoff?: string;
+}
+
+/** Estimated share of the keyspace under one prefix, from random samples. */
+export interface NamespaceEstimate {
+  prefix: string;
+  hits: number;
+  /** Estimated fraction of all keys, 0 to 1. */
+  proportion: number;
+  /** Confidence interval bounds around `proportion`. */
+  low: number;
+  high: number;
+  estimatedKeys: number;
+}
+
+/** Namespace distribution estimated from RANDOMKEY samples. */
+export interface KeyspaceSample {
+  delimiter: string;
+  /** DBSIZE when sampling started. */
+  totalKeys: number;
+  samples: number;
+  /** Confidence level of the intervals, e.g. 0.95. */
+  confidence: number;
+  /** Most sampled first. */
+  namespaces: NamespaceEstimate[];
+  elapsedMs: number;
+}
+
+export interface TenantUsage {
+  /** The part of the key that filled `{id}` in the template. */
+  tenantId: string;
+  keyCount: number;
+  /** Keys whose MEMORY USAGE was read. */
+  sampled: number;
+  sampledMemoryBytes: number;
+  estimatedMemoryBytes: number;
+  /** Fraction of all tenants' estimated memory, 0 to 1. */
+  memoryShare: number;
+}
+
+/** Key count and memory per tenant of a template such as `tenant:{id}:*`. */
+export interface TenantReport {
+  template: string;
+  matchedKeys: number;
+  tenantCount: number;
+  /** Sum over all tenants, including those past the limit. */
+  estimatedMemoryBytes: number;
+  /** Largest estimated memory first. */
+  tenants: TenantUsage[];
+  truncated: boolean;
+  elapsedMs: number;
+}
+
+export type CasePolicy = 'any' | 'lower' | 'upper';
+
+/** Key naming conventions checked by `browser_lint_keys`. */
+export interface NamingRules {
+  delimiter: string;
+  /** Delimiters that mean a key doesn't use `delimiter` consistently. */
+  foreignDelimiters: string[];
+  /** Most segments a key may have; unlimited when null. */
+  maxDepth: number | null;
+  /** Characters keys must not contain; control characters are always reported. */
+  forbiddenChars: string;
+  /** Case of every segment but the last, which usually holds an ID. */
+  case: CasePolicy;
+  /** Keys matching these globs must have a TTL. */
+  ttlRequired: string[];
+}
+
+export type LintRule = 'mixedDelimiter' | 'maxDepth' | 'forbiddenChar' | 'case' | 'missingTtl';
+
+export interface LintViolation {
+  key: string;
+  detail: string;
+}
+
+export interface RuleViolations {
+  rule: LintRule;
+  count: number;
+  /** The first violations found, at most 100. */
+  examples: LintViolation[];
+}
+
+export interface LintReport {
+  scanned: number;
+  /** Keys breaking at least one rule. */
+  violatingKeys: number;
+  /** Most violated first. */
+  rules: RuleViolations[];
+  elapsedMs: number;
+}
+
+export interface FieldTypeCount {
+  /** e.g. `integer`, `float`, `boolean`, `json`, `string`, `empty`, `null`, `array`. */
+  valueType: string;
+  count: number;
+}
+
+export interface FieldSchema {
+  /** Hash field, or dotted path of a JSON document member. */
+  name: string;
+  present: number;
+  /** 0 to 1. */
+  fillRate: number;
+  /** Most common type first. */
+  types: FieldTypeCount[];
+  examples: string[];
+}
+
+/** Field schema inferred from the hashes and JSON documents under a pattern. */
+export interface SchemaReport {
+  pattern: string;
+  scannedKeys: number;
+  hashKeys: number;
+  jsonKeys: number;
+  skippedKeys: number;
+  /** Most often present first. */
+  fields: FieldSchema[];
+  truncated: boolean;
+}
+
+/** What `browserSearchValues` looks for, and how much of each key it reads. */
+export interface ValueSearchSpec {
+  query: string;
+  /** Treat `query` as a regular expression. */
+  regex?: boolean;
+  /** Key glob, as in SCAN MATCH; every key when unset. */
+  pattern?: string | null;
+  /** Empty inspects every supported type. */
+  types?: RedisKeyType[];
+  /** Default 1 MiB. */
+  maxValueBytes?: number | null;
+  /** Default 1000. */
+  maxElements?: number | null;
+  /** Default 1000. */
+  maxMatches?: number | null;
+}
+
+export interface ValueSearchHit {
+  key: string;
+  keyType: RedisKeyType;
+  /** Hash field, member, list index or `id field`; null for strings. */
+  location: string | null;
+  snippet: string;
+}
+
+/**
+ * Payload of `browser:value-search-hits`, for SCAN batches with hits.
+ * Progress and the end of the search arrive as `task:progress`.
+ */
+export interface ValueSearchHits {
+  searchId: string;
+  connectionId: string;
+  /** Hits of this batch only. */
+  hits: ValueSearchHit[];
+  totalHits: number;
+  /** The match limit was reached; no more hits follow. */
+  truncated: boolean;
+}
+
+/** A key whose memory grew between two snapshots. */
+export interface KeyGrowth {
+  key: string;
+  memoryBefore: number;
+  memoryAfter: number;
+}
+
+/** Change in a single namespace between two snapshots. */
+export interface NamespaceDelta {
+  prefix: string;
+  keysBefore: number;
+  keysAfter: number;
+  keyDelta: number;
+  memoryBefore: number;
+  memoryAfter: number;
+  memoryDelta: number;
+  addedKeys: number;
+  removedKeys: number;
+  grownKeys: number;
+  addedSample: string[];
+  removedSample: string[];
+  /** Largest growth first. */
+  grownSample: KeyGrowth[];
+}
+
+/** Result of comparing two keyspace snapshots. */
+export interface KeyspaceComparison {
+  before: string;
+  after: string;
+  keyDelta: number;
+  memoryDelta: number;
+  namespaces: NamespaceDelta[];
+  /** Both snapshots have key indexes, so per-key changes are filled in. */
+  keyLevel: boolean;
+  /** Per-key changes only cover keys up to this one. */
+  indexCutoff?: string;
+}
+
+/** Changes under one namespace prefix within an invalidation window. */
+export interface PrefixChange {
+  /** Empty for root-level keys. */
+  prefix: string;
+  events: number;
+  /** Distinct keyspace event names (`set`, `del`, `expired`, ...). */
+  operations: string[];
+}
+
+/** Payload of `browser:invalidate`: which parts of the key tree changed. */
+export interface InvalidationHint {
+  connectionId: string;
+  db: number;
+  windowMs: number;
+  totalEvents: number;
+  /** Too many prefixes changed to list; rescan everything. */
+  fullRefresh: boolean;
+  /** Busiest first. */
+  prefixes: PrefixChange[];
+}
+
 // ─── Editor Types ───────────────────────────────────────────
 
 /** String value returned by the editor (may be text or base64-encoded binary). */
@@ -187,12 +835,24 @@ export interface StringValue {
   isBinary: boolean;
 }
 
+/** The value an editor last read, for compare-and-set writes: exact, or its hex SHA-1. */
+export type ExpectedValue = { value: string } | { sha1: string };
+
+/** Text encoding of a binary payload written to a string. */
+export type BinaryEncoding = 'base64' | 'hex';
+
 /** A single field-value pair in a Redis hash. */
 export interface HashField {
   field: string;
   value: string;
 }
 
+/** A requested hash field and its value, null if the field doesn't exist. */
+export interface HashFieldValue {
+  field: string;
+  value: string | null;
+}
+
 /** Paginated result from HSCAN. */
 export interface HashScanResult {
   cursor: number;
@@ -216,10 +876,146 @@ export interface SetScanResult {
 /** TTL metadata for a key. */
 export interface TtlInfo {
   seconds: number;
+  /** Remaining time in milliseconds; negative like `seconds`. */
+  millis: number;
+  /** ISO timestamp of when the key expires, if it has a TTL. */
+  expiresAt: string | null;
   isPersistent: boolean;
   isMissing: boolean;
 }
 
+/** When a key should expire: relative to now, or at a Unix timestamp. */
+export type Expiry =
+  | { type: 'seconds'; seconds: number }
+  | { type: 'millis'; millis: number }
+  | { type: 'atSeconds'; unixSeconds: number }
+  | { type: 'atMillis'; unixMillis: number };
+
+/** Redis 7 condition on setting an expiry. */
+export type ExpireCondition = 'nx' | 'xx' | 'gt' | 'lt';
+
+/** A journaled editor change that can be reverted with `editorUndoLast`. */
+export interface UndoEntry {
+  id: string;
+  key: string;
+  operation: string;
+  recordedAt: string;
+  /** False if the key did not exist before; undo deletes it. */
+  existed: boolean;
+}
+
+/** A key with editor writes running or queued behind each other. */
+export interface KeyQueueDepth {
+  key: string;
+  /** The running write plus the ones queued behind it. */
+  depth: number;
+}
+
+/** A message on a streamed response channel; `done` or `failed` ends the stream. */
+export type StreamChunk<T> =
+  | { type: 'data'; seq: number; items: T[] }
+  | { type: 'done'; total: number }
+  | { type: 'failed'; message: string };
+
+/** One element of a collection value read with `editorStreamValue`. */
+export type CollectionItem =
+  | { type: 'listElement'; index: number; value: string }
+  | { type: 'hashField'; field: string; value: string }
+  | { type: 'setMember'; member: string }
+  | { type: 'zsetMember'; member: string; score: number }
+  | { type: 'streamEntry'; id: string; fields: [string, string][] };
+
+/** A collection value read whole by `editorReadFullValue`. */
+export interface FullValue {
+  keyType: string;
+  items: CollectionItem[];
+  /** Elements in the value. */
+  total: number;
+  /** Text size of the returned elements. */
+  bytes: number;
+  /** A limit stopped the read before the last element. */
+  truncated: boolean;
+}
+
+/** File format of `editorExportKeyValue`. */
+export type ValueExportFormat = 'csv' | 'json';
+
+/** Outcome of `editorExportKeyValue`. */
+export interface ValueExportReport {
+  path: string;
+  format: ValueExportFormat;
+  keyType: string;
+  /** Elements written; SCAN may repeat some if the value changed meanwhile. */
+  rows: number;
+  bytes: number;
+}
+
+/** Returned by `editorStreamString` before any chunk arrives. */
+export interface StringStreamInfo {
+  streamId: string;
+  totalBytes: number;
+  /** First and one-past-last byte that will be streamed. */
+  start: number;
+  end: number;
+}
+
+/** What the key watch compares between polls. */
+export interface KeyFingerprint {
+  keyType: string;
+  /** STRLEN, LLEN, HLEN, SCARD, ZCARD or XLEN, depending on the type. */
+  length: number;
+  /** Unix time in milliseconds the key expires at; null if persistent. */
+  expiresAtMs: number | null;
+}
+
+/** Payload of `editor:key-changed`: a watched key changed on the server. */
+export interface KeyChanged {
+  connectionId: string;
+  key: string;
+  previous: KeyFingerprint | null;
+  /** Null once the key was deleted or expired. */
+  current: KeyFingerprint | null;
+  source: 'poll' | 'notification';
+  /** Keyspace event name (`set`, `hset`, `expired`, ...) for notifications. */
+  event: string | null;
+}
+
+/** Where a watched key stands relative to its expiry threshold. */
+export type ExpiryPhase = 'clear' | 'expiring' | 'gone';
+
+/** A key on the expiry watch list. */
+export interface ExpiryWatch {
+  key: string;
+  thresholdSecs: number;
+  /** Also show an OS notification, not just the `key:expiring` event. */
+  notify: boolean;
+  /** PTTL at the last check; null if persistent or gone. */
+  ttlMs: number | null;
+  phase: ExpiryPhase;
+}
+
+/** Payload of `key:expiring`: a watched key's TTL fell below its threshold, or it expired. */
+export interface KeyExpiring {
+  connectionId: string;
+  key: string;
+  /** Remaining TTL; null once the key is gone. */
+  ttlMs: number | null;
+  thresholdSecs: number;
+  expired: boolean;
+}
+
+/** Payload of `editor:string-chunk`; the last chunk of a stream has `done` set. */
+export interface StringChunk {
+  streamId: string;
+  offset: number;
+  /** Raw bytes, base64-encoded. */
+  data: string;
+  totalBytes: number;
+  done: boolean;
+  /** Set on the final chunk if reading failed part way. */
+  error?: string;
+}
+
 // ─── Sorted Set Types ─────────────────────────────────────────
 
 /** A single member-score pair in a sorted set. */
@@ -235,6 +1031,44 @@ export interface ZSetScanResult {
   finished: boolean;
 }
 
+/** How a sorted set range interprets its bounds. */
+export type ZRangeBy = 'score' | 'lex';
+
+/**
+ * A ZRANGE BYSCORE/BYLEX query. Score bounds: `1.5`, `(1.5` (exclusive), `-inf`, `+inf`.
+ * Lex bounds: `[a` (inclusive), `(a`, `-`, `+`.
+ */
+export interface ZRangeQuery {
+  by: ZRangeBy;
+  /** Lower bound, also when `rev` is set. */
+  min: string;
+  max: string;
+  /** Highest first. */
+  rev?: boolean;
+  offset?: number;
+  count?: number;
+}
+
+/** Position and score of one sorted set member. */
+export interface ZSetRank {
+  member: string;
+  score: number;
+  /** 0-based rank, lowest score first. */
+  rank: number;
+  /** 0-based rank, highest score first. */
+  revRank: number;
+}
+
+/** Outcome of `editor_add_zset_members_bulk`. */
+export interface ZSetBulkAddReport {
+  /** Pairs sent, including repeated members. */
+  total: number;
+  /** Members that were not in the set before. */
+  added: number;
+  /** Existing members whose score changed. */
+  updated: number;
+}
+
 // ─── Stream Types ─────────────────────────────────────────────
 
 /** A single stream entry (ID + field-value pairs). */
@@ -265,6 +1099,31 @@ export interface StreamInfo {
   groups: ConsumerGroupInfo[];
 }
 
+/** XTRIM strategy. */
+export type StreamTrimStrategy = 'maxLen' | 'minId';
+
+// ─── In-Key Search Types ──────────────────────────────────────
+
+/** `substring` (default), `glob` as in SCAN MATCH, or `exact`. */
+export type ValueSearchMode = 'substring' | 'glob' | 'exact';
+
+/** One element found inside a key, with where it sits. */
+export type ValueMatch =
+  | { kind: 'hashField'; field: string; value: string }
+  | { kind: 'setMember'; member: string }
+  | { kind: 'zsetMember'; member: string; score: number; rank?: number }
+  | { kind: 'listElement'; index: number; value: string }
+  | { kind: 'streamEntry'; id: string; field: string; value: string };
+
+export interface KeySearchResult {
+  keyType: string;
+  matches: ValueMatch[];
+  /** List elements or stream entries compared; 0 for SCAN-matched types. */
+  scanned: number;
+  /** Stopped at the match limit or scan budget before covering the value. */
+  truncated: boolean;
+}
+
 // ─── JSON Type ────────────────────────────────────────────────
 
 /** JSON value from RedisJSON module or string fallback. */
@@ -273,6 +1132,24 @@ export interface JsonValue {
   isModule: boolean;
 }
 
+export interface JsonPatchOp {
+  op: 'set' | 'del';
+  /** JSONPath of the changed node, e.g. `$.items[2].name`. */
+  path: string;
+  /** New value for `set`; null for `del`. */
+  value: unknown;
+}
+
+/** Structural diff between a stored JSON document and an edited version. */
+export interface JsonPatch {
+  ops: JsonPatchOp[];
+  patchBytes: number;
+  /** What a full rewrite would send. */
+  documentBytes: number;
+  /** The ops were replaced by one `set` of the root, as they sent no less. */
+  fullRewrite: boolean;
+}
+
 // ─── HyperLogLog Type ────────────────────────────────────────
 
 /** HyperLogLog information. */
@@ -290,8 +1167,21 @@ export interface BitmapInfo {
   byteLength: number;
   bits: number[];
   offset: number;
+  /** Set bits within the returned window. */
+  rangeBitCount: number;
+  /** Absolute offset of the first set bit within the window. */
+  firstSetBit: number | null;
 }
 
+/** A single BITFIELD sub-operation; offset is `N` or `#N`. */
+export type BitfieldOp =
+  | { op: 'get'; encoding: string; offset: string }
+  | { op: 'set'; encoding: string; offset: string; value: number }
+  | { op: 'incrBy'; encoding: string; offset: string; increment: number };
+
+/** BITFIELD OVERFLOW behaviour for SET and INCRBY. */
+export type BitfieldOverflow = 'wrap' | 'sat' | 'fail';
+
 // ─── Geospatial Types ────────────────────────────────────────
 
 /** A single geospatial member with coordinates. */
@@ -301,6 +1191,118 @@ export interface GeoMember {
   latitude: number;
 }
 
+/** Center of a GEOSEARCH query. */
+export type GeoOrigin =
+  | { type: 'member'; member: string }
+  | { type: 'lonLat'; longitude: number; latitude: number };
+
+/** Area of a GEOSEARCH query, in the query's unit. */
+export type GeoShape =
+  | { type: 'radius'; radius: number }
+  | { type: 'box'; width: number; height: number };
+
+/** Parameters for a GEOSEARCH query. */
+export interface GeoSearchQuery {
+  origin: GeoOrigin;
+  shape: GeoShape;
+  unit: 'm' | 'km' | 'mi' | 'ft';
+  withCoord?: boolean;
+  withDist?: boolean;
+  ascending?: boolean;
+  count?: number;
+}
+
+/** A single GEOSEARCH match; distance/coords only when requested. */
+export interface GeoSearchMember {
+  member: string;
+  distance: number | null;
+  longitude: number | null;
+  latitude: number | null;
+}
+
+/** Result of a GEOSEARCH query. */
+export interface GeoSearchResult {
+  members: GeoSearchMember[];
+  unit: string;
+}
+
+// ─── Audit Types ────────────────────────────────────────────
+
+export type AuditSource =
+  | 'editor'
+  | 'browser'
+  | 'cli'
+  | 'server'
+  | 'pubSub'
+  | 'scheduler'
+  | 'devTools'
+  | 'queues';
+
+export interface AuditEntry {
+  timestamp: string;
+  connectionId: string;
+  source: AuditSource;
+  operation: string;
+  key?: string;
+  args?: string[];
+}
+
+export interface AuditQuery {
+  since?: string;
+  until?: string;
+  source?: AuditSource;
+  /** Case-insensitive operation name. */
+  operation?: string;
+  /** Substring of the key or of any argument. */
+  key?: string;
+  /** Defaults to 200. */
+  limit?: number;
+}
+
+// ─── Task Types ─────────────────────────────────────────────
+
+/** A running long-running command, as listed by `task_list`. */
+export interface TaskInfo {
+  taskId: string;
+  /** What the task does, e.g. `keyspaceSnapshot`. */
+  kind: string;
+  connectionId: string | null;
+  startedAt: string;
+}
+
+/** Which stage a background operation is in. */
+export type ProgressPhase = 'scanning' | 'processing' | 'writing' | 'done' | 'failed';
+
+/**
+ * Payload of `task:progress`, shared by scans, analyzers, exports and bulk
+ * operations (tasks, value searches, generation, replay, benchmarks).
+ */
+export interface ProgressEvent {
+  /** The task, search, generation, replay or benchmark ID. */
+  taskId: string;
+  kind: string;
+  connectionId: string | null;
+  /** Units of work done so far (keys scanned, written, commands sent, ...). */
+  processed: number;
+  /** Total units, or an estimate of them, when known. */
+  total: number | null;
+  phase: ProgressPhase;
+  /** On a `failed` event, the error. */
+  message: string | null;
+}
+
+/** How a task ended. */
+export type TaskStatus = 'completed' | 'failed' | 'cancelled';
+
+/** Payload of `task:done`, emitted once per task however it ended. */
+export interface TaskDone {
+  taskId: string;
+  kind: string;
+  status: TaskStatus;
+  error: string | null;
+  durationMs: number;
+}
+
 // ─── Default Factories ─────────────────────────────────────────
 
 // ─── Monitor Types ───────────────────────────────────────────
@@ -311,10 +1313,29 @@ export interface ServerInfo {
   memory: MemorySection;
   stats: StatsSection;
   replication: ReplicationSection;
+  persistence: PersistenceSection;
   keyspace: DatabaseInfo[];
   raw: Record<string, string>;
+  /** Sections fetched; null for full INFO. Fields of other sections hold defaults. */
+  sections?: InfoSection[] | null;
 }
 
+/** A section of INFO output the monitor can poll. */
+export type InfoSection =
+  | 'server'
+  | 'clients'
+  | 'memory'
+  | 'persistence'
+  | 'stats'
+  | 'replication'
+  | 'cpu'
+  | 'modules'
+  | 'errorstats'
+  | 'cluster'
+  | 'keyspace'
+  | 'commandstats'
+  | 'latencystats';
+
 export interface ServerSection {
   redisVersion: string;
   redisMode: string;
@@ -346,6 +1367,8 @@ export interface StatsSection {
   keyspaceMisses: number;
   expiredKeys: number;
   evictedKeys: number;
+  totalNetInputBytes: number;
+  totalNetOutputBytes: number;
 }
 
 export interface ReplicationSection {
@@ -354,6 +1377,34 @@ export interface ReplicationSection {
   masterReplOffset: number | null;
 }
 
+/** INFO persistence: RDB/AOF state and loading progress. */
+export interface PersistenceSection {
+  loading: boolean;
+  /** Set only while a dataset is loading. */
+  loadingLoadedPerc: number | null;
+  loadingEtaSeconds: number | null;
+  rdbChangesSinceLastSave: number;
+  rdbBgsaveInProgress: boolean;
+  rdbLastSaveTime: number;
+  rdbLastBgsaveStatus: string;
+  /** Duration of the last BGSAVE in seconds; -1 if none ran. */
+  rdbLastBgsaveTimeSec: number;
+  aofEnabled: boolean;
+  aofRewriteInProgress: boolean;
+  aofRewriteScheduled: boolean;
+  aofLastBgrewriteStatus: string;
+  aofLastWriteStatus: string;
+}
+
+/** Persistence health for the dashboard. */
+export interface PersistenceStatus {
+  persistence: PersistenceSection;
+  /** LASTSAVE: Unix time of the last successful save. */
+  lastSave: number;
+  /** No failed save, rewrite or AOF write. */
+  healthy: boolean;
+}
+
 export interface DatabaseInfo {
   index: number;
   keys: number;
@@ -365,12 +1416,129 @@ export interface DerivedMetrics {
   hitRatePercent: number;
   memoryUsagePercent: number | null;
   fragmentationHealth: 'good' | 'warning' | 'critical';
+  hitRateHealth: 'good' | 'warning' | 'critical';
+  /** Thresholds the health fields were computed with. */
+  thresholds: MetricThresholds;
+  /** Counter rates since the previous poll; null on the first poll and after a reset. */
+  rates?: IntervalRates | null;
+}
+
+/** Per-second rates of INFO counters between two consecutive polls. */
+export interface IntervalRates {
+  intervalMs: number;
+  commandsPerSec: number;
+  expiredKeysPerSec: number;
+  evictedKeysPerSec: number;
+  netInputBytesPerSec: number;
+  netOutputBytesPerSec: number;
+  /** Change in total keys, when both polls fetched the keyspace section. */
+  keysDelta: number | null;
+}
+
+/** The dashboard metrics of one polled snapshot, as kept in the metrics history. */
+export interface MetricPoint {
+  timestampMs: number;
+  usedMemory: number;
+  memoryUsagePercent: number | null;
+  memFragmentationRatio: number;
+  connectedClients: number;
+  blockedClients: number;
+  opsPerSec: number;
+  hitRatePercent: number;
+  /** From the snapshot's interval rates, when it had them. */
+  commandsPerSec: number | null;
+  expiredKeysPerSec: number | null;
+  evictedKeysPerSec: number | null;
+  netInputBytesPerSec: number | null;
+  netOutputBytesPerSec: number | null;
+  /** Keys over all databases, when the keyspace section was polled. */
+  totalKeys: number | null;
+}
+
+/** File format of a metrics history export. */
+export type MetricsExportFormat = 'csv' | 'json';
+
+/** Cutoffs for derived metric health (app-wide, overridable per profile). */
+export interface MetricThresholds {
+  fragmentationWarning: number;
+  fragmentationCritical: number;
+  hitRateWarningPercent: number;
+  hitRateCriticalPercent: number;
+}
+
+/** How throttled events over the limit are folded together. */
+export type Coalesce = 'latest' | 'batch';
+
+/** Per-event rate limit; batched events arrive as arrays on `<event>:batch`. */
+export interface ThrottlePolicy {
+  maxPerSec: number;
+  coalesce: Coalesce;
+  maxBatch: number;
+}
+
+/** Application-wide settings. */
+export interface AppSettings {
+  metricThresholds: MetricThresholds;
+  eventThrottle: Record<string, ThrottlePolicy>;
+  /** Run concurrent editor writes to the same key one at a time (FIFO). */
+  serializeKeyWrites: boolean;
+  /** Serve current monitor metrics for Prometheus at http://127.0.0.1:<port>/metrics; off when null. */
+  prometheusPort: number | null;
+  /** Key patterns the lock inspector scans by default. */
+  lockPatterns: string[];
+  /** Naming conventions the key linter checks by default. */
+  keyNamingRules: NamingRules;
+}
+
+/** What was open when the app last ran. */
+export interface Workspace {
+  lastConnectionId: string | null;
+  /** Keyed by profile ID. */
+  profiles: Record<string, ProfileWorkspace>;
+}
+
+/** Per-profile session state. */
+export interface ProfileWorkspace {
+  openTabs: WorkspaceTab[];
+  /** Index into `openTabs`. */
+  activeTab: number | null;
+  /** Recent browser scan patterns, newest first. */
+  scanPatterns: string[];
+  treeDelimiter: string | null;
+  editor: EditorViewPrefs;
+}
+
+/** A key open in an editor tab. */
+export interface WorkspaceTab {
+  key: string;
+  /** Database the key was opened from; omitted for the profile's own. */
+  db?: number;
+}
+
+/** How the value editor displays values. */
+export interface EditorViewPrefs {
+  /** e.g. `json`, `raw` or `hex`. */
+  valueFormat: string | null;
+  wordWrap: boolean | null;
+  pageSize: number | null;
 }
 
 export interface StatsSnapshot {
   timestampMs: number;
   info: ServerInfo;
   derived: DerivedMetrics;
+  /** Interval until the next polled snapshot, after load back-off; null on one-shot snapshots. */
+  pollIntervalMs?: number | null;
+}
+
+/** A connection's shared monitor poller, as seen by its subscribers. */
+export interface MonitorSubscription {
+  connectionId: string;
+  subscribers: number;
+  /** The fastest interval any subscriber requested. */
+  intervalMs: number;
+  /** Every INFO section any subscriber asked for. */
+  sections: InfoSection[];
 }
 
 export interface SlowLogEntry {
@@ -382,15 +1550,97 @@ export interface SlowLogEntry {
   clientName: string;
 }
 
+/** Slow log entries sharing a command shape, e.g. `HGETALL user:*`. */
+export interface SlowLogGroup {
+  shape: string;
+  command: string;
+  /** First key with ID-like segments replaced by `*`; null for keyless commands. */
+  keyPattern: string | null;
+  count: number;
+  totalUs: number;
+  avgUs: number;
+  maxUs: number;
+  /** Unix time of the most recent entry. */
+  lastSeen: number;
+  /** The slowest entry's full command line. */
+  slowest: string;
+}
+
+/** Grouped slow log, worst offenders first. */
+export interface SlowLogDigest {
+  groups: SlowLogGroup[];
+  /** Entries that went into the groups. */
+  entries: number;
+  /** SLOWLOG LEN at the time of the fetch. */
+  length: number;
+}
+
 export interface MonitorClientInfo {
   id: number;
   addr: string;
+  /** Local (server-side) address the client connected to. */
+  laddr: string;
+  fd: number;
   age: number;
   idle: number;
   flags: string;
   db: number;
   cmd: string;
   name: string;
+  user: string;
+  /** Protocol version (2 or 3). */
+  resp: number;
+  libName: string;
+  libVer: string;
+  sub: number;
+  psub: number;
+  ssub: number;
+  /** Commands queued in MULTI; -1 outside a transaction. */
+  multi: number;
+  qbuf: number;
+  qbufFree: number;
+  argvMem: number;
+  obl: number;
+  oll: number;
+  omem: number;
+  totMem: number;
+  events: string;
+  redir: number;
+}
+
+/** CLIENT LIST / CLIENT KILL client type. */
+export type ClientType = 'normal' | 'master' | 'replica' | 'pubsub';
+
+/** Client list criteria; all set criteria must match. */
+export interface ClientFilter {
+  /** Substring of the client's address. */
+  addr?: string;
+  /** Glob pattern for the client name. */
+  name?: string;
+  /** Only clients idle for more than this many seconds. */
+  minIdle?: number;
+  /** Flag letters that must all be set. */
+  flags?: string;
+  clientType?: ClientType;
+}
+
+/** One page of a filtered client list, ordered by client ID. */
+export interface ClientPage {
+  clients: MonitorClientInfo[];
+  /** Clients matching the filter, across all pages. */
+  total: number;
+  offset: number;
+}
+
+/** CLIENT KILL criteria; at least one besides `skipMe` is required. */
+export interface ClientKillFilter {
+  id?: number;
+  addr?: string;
+  laddr?: string;
+  clientType?: ClientType;
+  user?: string;
+  /** Spare the app's own connection (default true). */
+  skipMe?: boolean;
 }
 
 export interface MemoryStats {
@@ -398,20 +1648,233 @@ export interface MemoryStats {
   doctorAdvice: string;
 }
 
+export type AdvisorySeverity = 'info' | 'warning' | 'critical';
+
+/** A single item on the pre-upgrade checklist. */
+export interface UpgradeAdvisory {
+  id: string;
+  severity: AdvisorySeverity;
+  title: string;
+  detail: string;
+  sinceVersion: string | null;
+}
+
+/** Payload of `server:log`: log lines read since the previous event. */
+export interface ServerLogBatch {
+  connectionId: string;
+  lines: string[];
+  /** Lines came from `tail`'s stderr (missing file, permissions). */
+  stderr: boolean;
+  /** Set on the last event, once the tail ended. */
+  ended: boolean;
+}
+
+/** Pre-upgrade checklist for a server. */
+export interface UpgradeReport {
+  currentVersion: string;
+  targetVersion: string;
+  advisories: UpgradeAdvisory[];
+}
+
+export type AlertMetric =
+  | 'usedMemoryBytes'
+  | 'memoryUsagePercent'
+  | 'hitRatePercent'
+  | 'fragmentationRatio'
+  | 'connectedClients'
+  | 'blockedClients'
+  | 'opsPerSec';
+
+/** Threshold rule evaluated against every polled snapshot. */
+export interface AlertRule {
+  id: string;
+  name: string;
+  metric: AlertMetric;
+  comparison: 'above' | 'below';
+  threshold: number;
+  cooldownSecs: number;
+}
+
+export interface CommandStat {
+  command: string;
+  calls: number;
+  usec: number;
+  usecPerCall: number;
+}
+
+export interface ClientSummary {
+  total: number;
+  blocked: number;
+  longIdle: number;
+  /** `[host, count]` pairs, largest first. */
+  topHosts: [string, number][];
+}
+
+/** Server state captured when an alert fired. */
+export interface AlertBundle {
+  snapshot: StatsSnapshot;
+  slowLog: SlowLogEntry[];
+  clients: ClientSummary;
+  topCommands: CommandStat[];
+}
+
+/** A fired alert with its correlated bundle (also the `monitor:alert` payload). */
+export interface AlertRecord {
+  id: string;
+  connectionId: string;
+  rule: AlertRule;
+  value: number;
+  firedAt: string;
+  bundle: AlertBundle;
+}
+
+/** INFO ALL pinned at a moment worth comparing against, e.g. "before deploy". */
+export interface PinnedInfo {
+  id: string;
+  connectionId: string;
+  label: string;
+  capturedAt: string;
+  /** Fields by section; `k=v,...` values are split into `db0.keys`, `cmdstat_get.calls`, ... */
+  sections: Record<string, Record<string, string>>;
+}
+
+export interface InfoFieldDiff {
+  section: string;
+  field: string;
+  before: string | null;
+  after: string | null;
+  /** `after - before` when both are numbers. */
+  delta: number | null;
+  /** Null when `before` is 0 or not a number. */
+  percentChange: number | null;
+}
+
+export interface InfoDiff {
+  beforeLabel: string;
+  beforeAt: string;
+  afterLabel: string;
+  afterAt: string;
+  unchanged: number;
+  changes: InfoFieldDiff[];
+}
+
 // ─── CLI Types ──────────────────────────────────────────────
 
+/** A reply. The variants after `nil` only come from RESP3 connections. */
 export type CommandResult =
   | { type: 'ok'; data: string }
   | { type: 'integer'; data: number }
   | { type: 'bulkString'; data: string }
   | { type: 'array'; data: CommandResult[] }
   | { type: 'error'; data: string }
-  | { type: 'nil' };
+  | { type: 'nil' }
+  | { type: 'map'; data: [CommandResult, CommandResult][] }
+  | { type: 'set'; data: CommandResult[] }
+  /** Text, so `inf`, `-inf` and `nan` survive. */
+  | { type: 'double'; data: string }
+  | { type: 'boolean'; data: boolean }
+  | { type: 'bigNumber'; data: string }
+  | { type: 'verbatim'; data: { format: string; text: string } }
+  | { type: 'push'; data: { kind: string; data: CommandResult[] } };
+
+/** Payload of the `connection:push` event. */
+export interface PushEvent {
+  connectionId: string;
+  /** `invalidate`, `message`, `subscribe`, ..., or `disconnection`. */
+  kind: string;
+  data: CommandResult[];
+}
+
+/** Outcome of an ACL DRYRUN pre-flight. */
+export interface PermissionCheck {
+  user?: string;
+  /** Unset when the server couldn't check (before Redis 7, or no ACL access). */
+  allowed?: boolean;
+  reason?: string;
+}
 
 export interface ExecuteResponse {
   result: CommandResult;
   durationMs: number;
   command: string;
+  /** Present when a dangerous command was held back pending confirmation. */
+  confirmation?: PendingConfirmation;
+  /** Present when the raw RESP view was requested. */
+  raw?: RespView;
+  /** Present when a large reply was sent as `cli:result-chunk` events; `result` is then empty. */
+  streamed?: StreamedReply;
+  /** Present when a cluster node answered with MOVED or ASK. */
+  redirect?: Redirect;
+}
+
+/** A cluster redirect met by a CLI command. */
+export interface Redirect {
+  kind: 'MOVED' | 'ASK';
+  slot: number;
+  /** `host:port` of the node the slot lives on. */
+  node: string;
+  /** Whether the command was re-sent there; the reply then came from `node`. */
+  followed: boolean;
+}
+
+/** One connection's outcome of `cliExecuteBroadcast`. */
+export interface BroadcastResult {
+  connectionId: string;
+  /** Profile name; empty when the connection is not open. */
+  connectionName: string;
+  /** The reply, unless the command could not run on this connection. */
+  result: CommandResult | null;
+  error: string | null;
+  durationMs: number;
+}
+
+/** Makes `cliExecute` cancellable and streams array replies longer than `chunkSize`. */
+export interface StreamOptions {
+  requestId: string;
+  chunkSize?: number;
+}
+
+export interface StreamedReply {
+  total: number;
+  chunks: number;
+}
+
+/** Payload of the `cli:result-chunk` event. */
+export interface ResultChunk {
+  requestId: string;
+  seq: number;
+  /** Index of the first item in the full reply. */
+  offset: number;
+  total: number;
+  items: CommandResult[];
+}
+
+/** A reply with every RESP2/RESP3 type kept distinct. */
+export type RespNode =
+  | { type: 'simpleString'; value: string }
+  | { type: 'error'; message: string }
+  | { type: 'integer'; value: number }
+  | { type: 'bulkString'; value: string; binary: boolean }
+  | { type: 'null' }
+  | { type: 'array'; items: RespNode[] }
+  | { type: 'map'; entries: RespEntry[] }
+  | { type: 'set'; items: RespNode[] }
+  | { type: 'double'; value: number }
+  | { type: 'boolean'; value: boolean }
+  | { type: 'verbatimString'; format: string; text: string }
+  | { type: 'bigNumber'; value: string }
+  | { type: 'push'; kind: string; items: RespNode[] }
+  | { type: 'attribute'; attributes: RespEntry[]; data: RespNode };
+
+export interface RespEntry {
+  key: RespNode;
+  value: RespNode;
+}
+
+export interface RespView {
+  /** RESP wire form, re-encoded from the parsed reply; non-printable bytes as `\xNN`. */
+  wire: string;
+  tree: RespNode;
 }
 
 export interface CommandSuggestion {
@@ -421,6 +1884,21 @@ export interface CommandSuggestion {
   group: string;
 }
 
+export type CompletionKind = 'command' | 'subcommand' | 'key';
+
+export interface Completion {
+  /** Replacement text for the token, quoted if needed. */
+  value: string;
+  kind: CompletionKind;
+  detail?: string;
+}
+
+export interface CompletionResult {
+  /** Character offset where the completed token starts; replace from here to the end. */
+  replaceFrom: number;
+  items: Completion[];
+}
+
 export interface CliHistoryEntry {
   command: string;
   timestampMs: number;
@@ -428,21 +1906,582 @@ export interface CliHistoryEntry {
   durationMs: number;
 }
 
+/** A saved command template; `{name}` marks a parameter, `{{`/`}}` are literal braces. */
+export interface Snippet {
+  id: string;
+  name: string;
+  template: string;
+  description?: string;
+  /** Connection the snippet belongs to; omitted for global snippets. */
+  connectionId?: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+// ─── Key Template Types ─────────────────────────────────────
+
+/** How a template rule reads its keys. */
+export type RuleView =
+  /** A list or sorted set whose elements are the job payloads. */
+  | { kind: 'jobs'; state: string }
+  /** A list or sorted set of job IDs; `jobKey` names each job's hash (`{id}`, `{*}`). */
+  | { kind: 'jobIds'; state: string; jobKey: string }
+  /** A string or hash holding one record. */
+  | { kind: 'record' };
+
+/** JSON pointers of the fields shown for each job or record. */
+export interface FieldPaths {
+  id?: string | null;
+  name?: string | null;
+  args?: string | null;
+  status?: string | null;
+  createdAt?: string | null;
+  error?: string | null;
+}
+
+/** One kind of key of a template. */
+export interface TemplateRule {
+  /** Glob the key names match. */
+  pattern: string;
+  view: RuleView;
+  fields?: FieldPaths;
+  /** Pointers of string fields holding JSON text or base64 JSON, decoded in place. */
+  expand?: string[];
+}
+
+/** How a framework (Sidekiq, Bull, Celery, ...) stores its data in Redis. */
+export interface AppTemplate {
+  /** Fixed for built-ins; empty for a new user template. */
+  id: string;
+  name: string;
+  description: string;
+  builtin: boolean;
+  /** Checked in order; a key is read with the first rule it matches. */
+  rules: TemplateRule[];
+}
+
+/** A job or record decoded with a template. */
+export interface DecodedJob {
+  id: string | null;
+  name: string | null;
+  status: string | null;
+  args: unknown;
+  createdAt: unknown;
+  error: string | null;
+  /** Sorted set score (run-at or finished-at time, depending on the framework). */
+  score: number | null;
+  payload: unknown;
+}
+
+/** A key read through a template rule. */
+export interface TemplateView {
+  templateId: string;
+  templateName: string;
+  key: string;
+  keyType: string;
+  rule: number;
+  state: string | null;
+  /** Elements in the key; 1 for records. */
+  total: number;
+  jobs: DecodedJob[];
+  /** Job keys gone since they were listed. */
+  missing: number;
+}
+
+/** A key found by scanning for a template's rules. */
+export interface TemplateKey {
+  key: string;
+  keyType: string;
+  rule: number;
+  state: string | null;
+  /** Elements in a queue key; null for records. */
+  length: number | null;
+}
+
+/** Result of scanning for a template's keys. */
+export interface TemplateScan {
+  keys: TemplateKey[];
+  truncated: boolean;
+}
+
+// ─── Job Queue Types ────────────────────────────────────────
+
+export type QueueFramework = 'sidekiq' | 'bull';
+
+export interface QueueStats {
+  framework: QueueFramework;
+  name: string;
+  /** Jobs waiting to be picked up (Bull: wait, paused and prioritized). */
+  waiting: number;
+  /** Bull only. */
+  active: number | null;
+  /** Bull only; Sidekiq's scheduled set is in `SidekiqTotals`. */
+  delayed: number | null;
+  /** Bull only. */
+  failed: number | null;
+  /** Bull only. */
+  completed: number | null;
+  oldestJobAgeMs: number | null;
+  paused: boolean;
+}
+
+export interface SidekiqTotals {
+  scheduled: number;
+  retry: number;
+  dead: number;
+  processed: number;
+  failed: number;
+}
+
+export interface QueueDashboard {
+  queues: QueueStats[];
+  sidekiq: SidekiqTotals | null;
+  generatedAtMs: number;
+}
+
+export type BullSource = 'failed' | 'completed' | 'delayed';
+
+export type JobMove =
+  | {
+      framework: 'sidekiq';
+      /** `queue:<name>`, `schedule`, `retry` or `dead`. */
+      from: string;
+      jid: string;
+      toQueue: string;
+    }
+  | {
+      framework: 'bull';
+      prefix?: string;
+      queue: string;
+      jobId: string;
+      from: BullSource;
+    };
+
+// ─── Scheduled Job Types ────────────────────────────────────
+
+/** What a scheduled job does on each run. */
+export type JobAction =
+  | { type: 'keyspaceSnapshot'; delimiter: string }
+  | { type: 'infoSnapshot' }
+  | { type: 'prunePattern'; pattern: string };
+
+/** A recurring job registered against a connection. */
+export interface ScheduledJob {
+  id: string;
+  connectionId: string;
+  name: string;
+  action: JobAction;
+  /** 1 minute to 4 weeks. */
+  intervalMinutes: number;
+  enabled: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** `skipped` means the connection was not open when the job came due. */
+export type JobRunStatus = 'succeeded' | 'failed' | 'skipped';
+
+/** What a successful run produced. */
+export type JobOutput =
+  | {
+      type: 'keyspaceSnapshot';
+      snapshotId: string;
+      totalKeys: number;
+      totalMemoryBytes: number;
+    }
+  | {
+      type: 'infoSnapshot';
+      usedMemory: number;
+      connectedClients: number;
+      opsPerSec: number;
+      hitRatePercent: number;
+      totalKeys: number;
+    }
+  | { type: 'prunePattern'; deleted: number };
+
+/** One execution of a job; also the `job:run` event payload. */
+export interface JobRun {
+  id: string;
+  jobId: string;
+  connectionId: string;
+  startedAt: string;
+  finishedAt: string;
+  status: JobRunStatus;
+  output?: JobOutput;
+  error?: string;
+}
+
+// ─── Key Diff Types ─────────────────────────────────────────
+
+/** How a key compares between the left and right connection. */
+export type DiffStatus = 'equal' | 'different' | 'onlyLeft' | 'onlyRight' | 'typeMismatch' | 'skipped';
+
+/** Kind of a value change, read from left to right. */
+export type ChangeKind = 'added' | 'removed' | 'changed';
+
+/** One differing field, member, list index or stream entry. */
+export interface ValueChange {
+  kind: ChangeKind;
+  /** Absent for a string or JSON document as a whole. */
+  path?: string;
+  left?: string;
+  right?: string;
+}
+
+/** Remaining TTLs in milliseconds; absent means no expiry. */
+export interface TtlDiff {
+  leftMs?: number;
+  rightMs?: number;
+}
+
+export interface KeyDiff {
+  key: string;
+  status: DiffStatus;
+  leftType?: string;
+  rightType?: string;
+  ttl?: TtlDiff;
+  changes: ValueChange[];
+  truncated: boolean;
+}
+
+/** Compare either one `key` or every key matching `pattern`. */
+export interface DiffOptions {
+  key?: string;
+  pattern?: string;
+  leftDb?: number;
+  rightDb?: number;
+  ttlToleranceMs?: number;
+  maxKeys?: number;
+}
+
+export interface DiffReport {
+  leftConnectionId: string;
+  rightConnectionId: string;
+  keysCompared: number;
+  equal: number;
+  different: number;
+  onlyLeft: number;
+  onlyRight: number;
+  typeMismatch: number;
+  skipped: number;
+  /** Keys that are not equal, in key order. */
+  diffs: KeyDiff[];
+  truncated: boolean;
+}
+
+/** A server's `CONFIG GET *` with secrets redacted; saved as JSON it serves as a baseline. */
+export interface ConfigSnapshot {
+  connectionId: string | null;
+  redisVersion: string | null;
+  /** `master` or `slave`. */
+  role: string | null;
+  capturedAt: string;
+  parameters: Record<string, string>;
+}
+
+export type ConfigSource =
+  | { type: 'connection'; connectionId: string }
+  | { type: 'snapshot'; snapshot: ConfigSnapshot };
+
+/** `info`: expected to differ per instance; `danger`: changes eviction or durability. */
+export type DriftSeverity = 'info' | 'notice' | 'danger';
+
+export interface ConfigParamDiff {
+  parameter: string;
+  /** Null when that side doesn't know the parameter. */
+  left: string | null;
+  right: string | null;
+  severity: DriftSeverity;
+}
+
+export interface ConfigDiffReport {
+  left: ConfigSnapshot;
+  right: ConfigSnapshot;
+  equal: number;
+  /** Most severe first, then by name. */
+  differences: ConfigParamDiff[];
+  dangerous: number;
+}
+
+// ─── Dev Tools Types ────────────────────────────────────────
+
+export type GeneratedType = 'string' | 'hash' | 'list' | 'set' | 'zset' | 'stream';
+
+/** Expiry given to a share of the generated keys. */
+export interface TtlSpec {
+  /** 0–100. */
+  percent: number;
+  minSecs: number;
+  maxSecs: number;
+}
+
+/** What `devtools_generate` writes. */
+export interface GeneratorSpec {
+  keyType: GeneratedType;
+  count: number;
+  /** Placeholders: `{n}`, `{rand}`, `{uuid}`. */
+  keyTemplate: string;
+  start?: number;
+  valueBytes: number;
+  elements?: number;
+  scoreMin?: number;
+  scoreMax?: number;
+  ttl?: TtlSpec;
+  /** Same seed, same data. */
+  seed?: number;
+}
+
+export type BenchCommand =
+  | 'ping'
+  | 'get'
+  | 'set'
+  | 'incr'
+  | 'lpush'
+  | 'lpop'
+  | 'sadd'
+  | 'hset'
+  | 'zadd';
+
+/** One command of a benchmark workload and its share of it. */
+export interface BenchWeight {
+  command: BenchCommand;
+  weight: number;
+}
+
+/** Workload for `devtools_benchmark`. */
+export interface BenchSpec {
+  mix: BenchWeight[];
+  clients?: number;
+  /** Commands per round trip. */
+  pipeline?: number;
+  durationSecs?: number;
+  /** Distinct keys per data type. */
+  keyspace?: number;
+  valueBytes?: number;
+}
+
+/** Round-trip latency percentiles in microseconds. */
+export interface LatencySummary {
+  minUs: number;
+  meanUs: number;
+  p50Us: number;
+  p90Us: number;
+  p99Us: number;
+  p999Us: number;
+  maxUs: number;
+}
+
+/**
+ * Payload of `devtools:bench-figures`, about once a second and once more
+ * with the final figures; the run's end arrives as `task:progress`.
+ */
+export interface BenchFigures {
+  benchmarkId: string;
+  connectionId: string;
+  elapsedMs: number;
+  totalOps: number;
+  errors: number;
+  opsPerSec: number;
+  latency: LatencySummary;
+}
+
+/** `aof`: RESP-encoded commands; `lines`: one command per line, or MONITOR output. */
+export type ReplayFormat = 'aof' | 'lines';
+
+export interface ReplaySpec {
+  /** The command file to read. */
+  path: string;
+  /** Detected from the content when unset. */
+  format?: ReplayFormat;
+  /** Commands per second; as fast as the server answers when unset. */
+  ratePerSec?: number;
+  /** Stop at the first command the server rejects. */
+  stopOnError?: boolean;
+}
+
+/** A command that was not replayed, or that the server rejected. */
+export interface ReplayError {
+  /** Position of the command in the file, from 0. */
+  index: number;
+  /** Line the command starts on, from 1. */
+  line: number;
+  command: string;
+  error: string;
+}
+
+export interface ReplayReport {
+  total: number;
+  /** Refused before sending: dangerous, blocking, unknown, wrong arity, or writes on a read-only connection. */
+  rejected: number;
+  sent: number;
+  /** Sent commands the server answered with an error. */
+  failed: number;
+  /** The file ended inside a command, which was dropped. */
+  truncated: boolean;
+  errors: ReplayError[];
+}
+
+/** Payload of `devtools:replay-report`, once a replay ends (also when stopped early). */
+export interface ReplayFinished {
+  replayId: string;
+  connectionId: string;
+  report: ReplayReport;
+}
+
+/** How often one key was invalidated while tracking. */
+export interface KeyInvalidations {
+  key: string;
+  count: number;
+  /** Milliseconds since tracking started. */
+  lastAtMs: number;
+}
+
+/** Figures of a client-side caching (CLIENT TRACKING) session. */
+export interface TrackingStats {
+  connectionId: string;
+  /** Key prefixes tracked; empty means every key. */
+  prefixes: string[];
+  elapsedMs: number;
+  /** False once the tracking connection dropped. */
+  active: boolean;
+  totalInvalidations: number;
+  distinctKeys: number;
+  /** Invalidations beyond the per-session key limit, not counted per key. */
+  untrackedInvalidations: number;
+  /** FLUSHDB/FLUSHALL invalidations. */
+  flushes: number;
+  invalidationsPerSec: number;
+  /** Over the last 10 seconds. */
+  recentInvalidationsPerSec: number;
+  /** Most-invalidated keys, busiest first. */
+  topKeys: KeyInvalidations[];
+}
+
+/** A lock key found by the lock inspector. */
+export interface LockInfo {
+  key: string;
+  keyType: string;
+  /** A string lock's value (first 256 bytes), or a hash lock's single field. */
+  owner: string | null;
+  /** Re-entrant hold count of a hash lock. */
+  holds: number | null;
+  /** Remaining TTL; null if the lock never expires. */
+  ttlMs: number | null;
+  /** Seconds since last written or read (OBJECT IDLETIME); null under LFU eviction. */
+  idleSecs: number | null;
+  /** Lease minus remaining TTL, when the lease was given. */
+  ageMs: number | null;
+}
+
+/** Result of a lock scan. */
+export interface LockScan {
+  /** Soonest to expire first; locks without a TTL last. */
+  locks: LockInfo[];
+  /** More keys matched than the scan's limit. */
+  truncated: boolean;
+}
+
+// ─── Confirmation Types ─────────────────────────────────────
+
+/** A destructive operation that requires a confirmation token. */
+export type DestructiveOp =
+  | { type: 'deleteByPattern'; pattern: string }
+  | { type: 'bulkTtl'; pattern: string; ttlSeconds: number | null }
+  | {
+      type: 'streamTrim';
+      key: string;
+      strategy: StreamTrimStrategy;
+      threshold: string;
+      approximate: boolean;
+    }
+  | { type: 'cliCommand'; command: string }
+  | { type: 'flushDb'; db: number; asyncFlush: boolean }
+  | { type: 'flushAll'; asyncFlush: boolean }
+  | { type: 'backgroundSave'; schedule: boolean }
+  | { type: 'rewriteAof' }
+  | { type: 'releaseLock'; key: string; owner: string | null };
+
+/** Estimated effect of a destructive operation. */
+export interface ImpactEstimate {
+  affectedKeys: number | null;
+  affectedEntries: number | null;
+  sampleKeys: string[];
+  exact: boolean;
+  summary: string;
+}
+
+/** A destructive operation awaiting re-submission with its token. */
+export interface PendingConfirmation {
+  token: string;
+  connectionId: string;
+  operation: DestructiveOp;
+  impact: ImpactEstimate;
+  expiresAt: string;
+}
+
+/** Result of a confirmation-gated command. */
+export type Confirmable<T> =
+  | { status: 'pending'; confirmation: PendingConfirmation }
+  | { status: 'done'; result: T };
+
 // ─── Pub/Sub Types ──────────────────────────────────────────
 
 export interface PubSubMessage {
   subscriptionId: string;
   channel: string;
   pattern: string | null;
-  payload: string;
+  /** Text payload; null when the payload is binary. */
+  payload: string | null;
+  /** Base64-encoded payload; set only when binary. */
+  payloadBase64: string | null;
+  sizeBytes: number;
+  isBinary: boolean;
   timestampMs: number;
 }
 
+/** How a subscription's messages are paced; omitted fields use the defaults. */
+export interface DeliveryOptions {
+  /** Batch interval (default 100 ms, 10–10000). */
+  flushIntervalMs?: number;
+  /** Messages delivered per second at most (default 1000); the rest stay buffered. */
+  maxPerSec?: number;
+  /** Messages held while paused or over the rate (default 10000); the oldest are dropped. */
+  maxBuffered?: number;
+}
+
+/** Payload of the `pubsub:messages` event. */
+export interface PubSubBatch {
+  subscriptionId: string;
+  messages: PubSubMessage[];
+  /** Dropped since the previous batch. */
+  dropped: number;
+  totalDropped: number;
+  /** Still buffered after this batch. */
+  buffered: number;
+}
+
 export interface ChannelInfo {
   name: string;
   subscribers: number;
 }
 
+export interface RecordingInfo {
+  id: string;
+  subscriptionId: string;
+  startedAtMs: number;
+  messages: number;
+  bytes: number;
+  /** Part files written; a new part starts every 16 MiB. */
+  parts: number;
+}
+
+/** Payload of the `pubsub:replay-finished` event. */
+export interface ReplayFinished {
+  replayId: string;
+  published: number;
+  error: string | null;
+}
+
 // ─── Default Factories ─────────────────────────────────────────
 
 export function createDefaultProfile(
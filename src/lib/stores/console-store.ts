@@ -20,7 +20,7 @@ interface ConsoleActions {
   execute: (
     connectionId: string,
     command: string,
-    force?: boolean,
+    confirmationToken?: string,
   ) => Promise<ExecuteResponse | null>;
   loadSuggestions: (prefix: string) => Promise<void>;
   clearSuggestions: () => void;
@@ -40,10 +40,10 @@ export const useConsoleStore = create<ConsoleStore>()(
       isExecuting: false,
       error: null,
 
-      execute: async (connectionId, command, force = false) => {
+      execute: async (connectionId, command, confirmationToken) => {
         set({ isExecuting: true, error: null });
         try {
-          const response = await api.cliExecute(connectionId, command, force);
+          const response = await api.cliExecute(connectionId, command, confirmationToken);
           set((state) => {
             const history = [...(state.histories[connectionId] ?? []), response];
             // Trim to max history
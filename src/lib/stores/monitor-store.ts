@@ -138,7 +138,7 @@ export const useMonitorStore = create<MonitorStore>((set, get) => ({
   fetchClientList: async (connectionId) => {
     set({ loadingClientList: true });
     try {
-      const clients = await api.monitorClientList(connectionId);
+      const { clients } = await api.monitorClientList(connectionId);
       set({ clientList: clients, loadingClientList: false });
     } catch (e) {
       set({ error: String(e), loadingClientList: false });
@@ -149,7 +149,7 @@ export const useMonitorStore = create<MonitorStore>((set, get) => ({
     try {
       await api.monitorKillClient(connectionId, clientId);
       // Refresh client list after kill
-      const clients = await api.monitorClientList(connectionId);
+      const { clients } = await api.monitorClientList(connectionId);
       set({ clientList: clients });
     } catch (e) {
       set({ error: String(e) });
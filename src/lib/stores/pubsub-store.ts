@@ -3,7 +3,7 @@
 import { create } from 'zustand';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import * as api from '@/lib/api/commands';
-import type { PubSubMessage, ChannelInfo } from '@/lib/api/types';
+import type { PubSubMessage, PubSubBatch, ChannelInfo } from '@/lib/api/types';
 
 interface Subscription {
   id: string;
@@ -17,6 +17,8 @@ interface PubSubState {
   messages: PubSubMessage[];
   activeChannels: ChannelInfo[];
   isPaused: boolean;
+  /** Messages the backend dropped because a subscription's buffer overflowed. */
+  droppedCount: number;
   maxMessages: number;
   channelFilter: string;
   payloadFilter: string;
@@ -32,7 +34,7 @@ interface PubSubActions {
   unsubscribeAll: () => Promise<void>;
   publish: (connectionId: string, channel: string, message: string) => Promise<number | null>;
   loadActiveChannels: (connectionId: string, pattern?: string) => Promise<void>;
-  togglePause: () => void;
+  togglePause: () => Promise<void>;
   clearMessages: () => void;
   setChannelFilter: (filter: string) => void;
   setPayloadFilter: (filter: string) => void;
@@ -49,6 +51,7 @@ export const usePubSubStore = create<PubSubStore>()((set, get) => ({
   messages: [],
   activeChannels: [],
   isPaused: false,
+  droppedCount: 0,
   maxMessages: MAX_MESSAGES,
   channelFilter: '',
   payloadFilter: '',
@@ -133,9 +136,20 @@ export const usePubSubStore = create<PubSubStore>()((set, get) => ({
     }
   },
 
-  togglePause: () => set((state) => ({ isPaused: !state.isPaused })),
+  togglePause: async () => {
+    // The backend buffers a paused subscription's messages until it resumes
+    const { isPaused, subscriptions } = get();
+    for (const sub of subscriptions) {
+      try {
+        await (isPaused ? api.pubsubResume(sub.id) : api.pubsubPause(sub.id));
+      } catch {
+        // Already unsubscribed
+      }
+    }
+    set({ isPaused: !isPaused });
+  },
 
-  clearMessages: () => set({ messages: [] }),
+  clearMessages: () => set({ messages: [], droppedCount: 0 }),
 
   setChannelFilter: (filter) => set({ channelFilter: filter }),
   setPayloadFilter: (filter) => set({ payloadFilter: filter }),
@@ -144,18 +158,21 @@ export const usePubSubStore = create<PubSubStore>()((set, get) => ({
     // Stop existing listener if any
     get().stopListening();
 
-    const fn_ = await listen<PubSubMessage>('pubsub:message', (event) => {
+    const append = (incoming: PubSubMessage[], dropped: number) => {
       const state = get();
-      if (state.isPaused) return;
-
-      const msgs = [...state.messages, event.payload];
+      const msgs = [...state.messages, ...incoming];
       // Trim ring buffer
       const trimmed =
         msgs.length > state.maxMessages ? msgs.slice(msgs.length - state.maxMessages) : msgs;
-      set({ messages: trimmed });
+      set({ messages: trimmed, droppedCount: state.droppedCount + dropped });
+    };
+
+    // The backend buffers each subscription and delivers batches at a bounded rate
+    const unlisten = await listen<PubSubBatch>('pubsub:messages', (event) => {
+      append(event.payload.messages, event.payload.dropped);
     });
 
-    set({ unlisten: fn_ });
+    set({ unlisten });
   },
 
   stopListening: () => {